@@ -12,9 +12,9 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{
     parse::Parse, spanned::Spanned, BinOp, Error, Expr, ExprBinary, ExprCall, ExprGroup, ExprLit,
-    ExprParen, ExprPath, ExprUnary, Lit, Token, UnOp,
+    ExprParen, ExprPath, ExprUnary, Lit, LitInt, Token, UnOp,
 };
-use util::{check_arg_num, str_to_bigfloat_expr};
+use util::{check_arg_num, str_to_bigfloat_const_expr, str_to_bigfloat_expr};
 
 // Size of exponent in bits.
 const EXPONENT_BIT_SIZE: usize = core::mem::size_of::<Exponent>() * 8;
@@ -38,6 +38,24 @@ impl Parse for MacroInput {
     }
 }
 
+struct ConstInput {
+    neg: bool,
+    lit: Lit,
+    p: LitInt,
+}
+
+impl Parse for ConstInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let neg = input.parse::<Option<Token![-]>>()?.is_some();
+        let lit = input.parse()?;
+        input.parse::<Token![,]>()?;
+
+        let p = input.parse()?;
+
+        Ok(ConstInput { neg, lit, p })
+    }
+}
+
 // Algorithm of error computation.
 #[derive(Copy, Clone, Debug, PartialEq)]
 enum ErrAlgo {
@@ -441,10 +459,12 @@ pub fn expr(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         let mut ctx = &mut (#ctx);
         let p: usize = ctx.precision();
         let rm = ctx.rounding_mode();
+        let (emin, emax) = (ctx.emin(), ctx.emax());
         let cc = ctx.consts();
 
         let mut p_inc = astro_float::WORD_BIT_SIZE;
-        let mut p_rnd = p + p_inc;
+        // the working precision used for rounding is a multiple of the word size
+        let mut p_rnd = p.div_ceil(astro_float::WORD_BIT_SIZE) * astro_float::WORD_BIT_SIZE + p_inc;
         let mut errs: [usize; #err_sz] = [#(#err, )*];
 
         fn compute_added_err_near_one(arg: &astro_float::BigFloat, p: usize) -> usize {
@@ -474,23 +494,94 @@ pub fn expr(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             return 0;
         }
 
-        loop {
+        let ret = loop {
             let p_wrk = p_rnd.saturating_add(errs.iter().sum());
 
             let mut ret: astro_float::BigFloat = (#expr).into();
 
-            if ret.inexact() {
-                if ret.try_set_precision(p, rm, p_rnd) {
-                    break ret;
+            match ret.exponent() {
+                Some(e) if e < emin && !ret.is_zero() => {
+                    // The result is rounded as a subnormal number of the exponent range of the context:
+                    // rounding of c + ret to p bits, where c = 2^(emin - 1) has the sign of ret,
+                    // gives c plus the multiple of 2^(emin - p) nearest to ret.
+                    let mut c = astro_float::BigFloat::from_word(1, p);
+                    c.set_exponent(emin);
+
+                    if ret.is_negative() {
+                        c = c.neg();
+                    }
+
+                    if (e as isize) < emin as isize - p as isize {
+                        // ret is less than half of 2^(emin - p), and all such numbers are rounded in the same way.
+                        ret = c.clone();
+                        ret.set_exponent((emin as isize - p as isize - 1) as astro_float::Exponent);
+                        ret.set_inexact(true);
+                    }
+
+                    let mut w = ret.add(&c, p_wrk.saturating_add(p), astro_float::RoundingMode::None);
+
+                    if !w.inexact() {
+                        if w.set_precision(p, rm).is_err() {
+                            break astro_float::NAN;
+                        }
+
+                        break w.sub(&c, p, astro_float::RoundingMode::None);
+                    } else if w.try_set_precision(p, rm, p_rnd) {
+                        break w.sub(&c, p, astro_float::RoundingMode::None);
+                    }
+                }
+                _ => {
+                    if ret.inexact() {
+                        if ret.try_set_precision(p, rm, p_rnd) {
+                            break ret;
+                        }
+                    } else {
+                        break ret;
+                    }
                 }
-            } else {
-                break ret;
             }
 
             p_rnd = p_rnd.saturating_add(p_inc);
             p_inc = (((p_rnd / 5).saturating_add(astro_float::WORD_BIT_SIZE - 1)) / astro_float::WORD_BIT_SIZE) * astro_float::WORD_BIT_SIZE;
+        };
+
+        match ret.exponent() {
+            Some(e) if e > emax => ret.fit_exponent_range(emin, emax, rm),
+            _ => ret,
         }
     });
 
     ret.into()
 }
+
+// Docs for the macro are in the astro-float crate.
+
+///
+#[proc_macro]
+pub fn bigfloat(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ConstInput { neg, lit, p } = syn::parse_macro_input!(input as ConstInput);
+
+    let sign = if neg { "-" } else { "" };
+
+    let ret = p
+        .base10_parse::<usize>()
+        .and_then(|p| {
+            let span = lit.span();
+            match &lit {
+                Lit::Str(v) => str_to_bigfloat_const_expr(&(sign.to_owned() + &v.value()), p, span),
+                Lit::Int(v) => {
+                    str_to_bigfloat_const_expr(&(sign.to_owned() + v.base10_digits()), p, span)
+                }
+                Lit::Float(v) => {
+                    str_to_bigfloat_const_expr(&(sign.to_owned() + v.base10_digits()), p, span)
+                }
+                _ => Err(Error::new(
+                    span,
+                    "unexpected literal. Only string, integer, or floating point literals are supported.",
+                )),
+            }
+        })
+        .unwrap_or_else(|e| e.to_compile_error());
+
+    ret.into()
+}
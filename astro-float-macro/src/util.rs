@@ -1,6 +1,8 @@
 //! Utility functions.
 
 use astro_float_num::BigFloat;
+use astro_float_num::Radix;
+use astro_float_num::RoundingMode;
 use core::str::FromStr;
 use proc_macro2::Span;
 use proc_macro2::TokenStream;
@@ -29,6 +31,36 @@ pub fn str_to_bigfloat_expr(s: &str, span: Span) -> Result<TokenStream, Error> {
     Ok(q)
 }
 
+pub fn str_to_bigfloat_const_expr(s: &str, p: usize, span: Span) -> Result<TokenStream, Error> {
+    let f = BigFloat::parse(s, Radix::Dec, p, RoundingMode::ToEven);
+
+    if f.is_inf() {
+        return Err(Error::new(span, format!("value {} is out of range", s)));
+    }
+
+    let (m, _, sign, e, inexact) = f
+        .as_raw_parts()
+        .ok_or_else(|| Error::new(span, format!("failed to parse BigFloat from {}", s)))?;
+
+    let stoken = if sign.is_positive() {
+        quote!(astro_float::Sign::Pos)
+    } else {
+        quote!(astro_float::Sign::Neg)
+    };
+
+    let q = if inexact {
+        quote!({
+            let mut f = astro_float::BigFloat::from_words(&[#(#m),*], #stoken, #e);
+            f.set_inexact(true);
+            f
+        })
+    } else {
+        quote!(astro_float::BigFloat::from_words(&[#(#m),*], #stoken, #e))
+    };
+
+    Ok(q)
+}
+
 pub fn check_arg_num(narg: usize, expr: &ExprCall) -> Result<(), Error> {
     if expr.args.len() != narg {
         return Err(Error::new(
@@ -38,3 +70,29 @@ pub fn check_arg_num(narg: usize, expr: &ExprCall) -> Result<(), Error> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_str_to_bigfloat_const_expr() {
+        let span = Span::call_site();
+
+        let q = str_to_bigfloat_const_expr("1.5", 64, span)
+            .unwrap()
+            .to_string();
+        assert!(q.starts_with("astro_float :: BigFloat :: from_words (& [13835058055282163712u64] , astro_float :: Sign :: Pos , 1i32)"));
+
+        let q = str_to_bigfloat_const_expr("-0.1", 128, span)
+            .unwrap()
+            .to_string();
+        assert!(q.contains("from_words"));
+        assert!(q.contains("Sign :: Neg"));
+        assert!(q.contains("set_inexact (true)"));
+
+        assert!(str_to_bigfloat_const_expr("abc", 64, span).is_err());
+        assert!(str_to_bigfloat_const_expr("1e+999999999999", 64, span).is_err());
+    }
+}
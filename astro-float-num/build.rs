@@ -0,0 +1,28 @@
+//! Selects the size of a word.
+//!
+//! The `word32` cfg is set when the mantissa uses 32-bit words:
+//! on x86 by default, and on any target with the `word32` feature.
+//! The `word64` feature forces 64-bit words on x86.
+//! If both features are enabled, e.g. by different dependents, `word32` takes precedence.
+
+use std::env;
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(word32)");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let word32 = env::var_os("CARGO_FEATURE_WORD32").is_some();
+    let word64 = env::var_os("CARGO_FEATURE_WORD64").is_some();
+
+    if word32 && word64 {
+        println!(
+            "cargo:warning=Both word32 and word64 features are enabled, 32-bit words are used."
+        );
+    }
+
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+
+    if word32 || (target_arch == "x86" && !word64) {
+        println!("cargo:rustc-cfg=word32");
+    }
+}
@@ -0,0 +1,457 @@
+//! Conversion between BigFloat and the IEEE 754 decimal interchange formats decimal64 and decimal128
+//! in the binary integer decimal (BID) encoding.
+
+use crate::BigFloat;
+use crate::BigUInt;
+use crate::Error;
+use crate::Radix;
+use crate::RoundingMode;
+use crate::Sign;
+use core::cmp::Ordering;
+
+// Parameters of a decimal interchange format.
+struct Format {
+    // storage width in bits
+    k: u32,
+    // precision in decimal digits
+    p: u32,
+    // maximum exponent
+    emax: i64,
+    // width of the exponent field in bits
+    ebits: u32,
+}
+
+const DECIMAL64: Format = Format {
+    k: 64,
+    p: 16,
+    emax: 384,
+    ebits: 10,
+};
+
+const DECIMAL128: Format = Format {
+    k: 128,
+    p: 34,
+    emax: 6144,
+    ebits: 14,
+};
+
+impl Format {
+    fn bias(&self) -> i64 {
+        self.emax + self.p as i64 - 2
+    }
+
+    // minimum exponent of the coefficient
+    fn qmin(&self) -> i64 {
+        -self.bias()
+    }
+
+    // maximum exponent of the coefficient
+    fn qmax(&self) -> i64 {
+        self.emax - self.p as i64 + 1
+    }
+
+    // width of the coefficient field in bits
+    fn cbits(&self) -> u32 {
+        self.k - 1 - self.ebits
+    }
+
+    fn max_coeff(&self) -> u128 {
+        10u128.pow(self.p) - 1
+    }
+
+    // Encodes the coefficient `c` and the exponent `q` of a finite number.
+    fn pack(&self, c: u128, q: i64) -> u128 {
+        let b = (q + self.bias()) as u128;
+        let cbits = self.cbits();
+
+        if c >> cbits == 0 {
+            (b << cbits) | c
+        } else {
+            (0b11 << (self.k - 3)) | (b << (cbits - 2)) | (c & ((1 << (cbits - 2)) - 1))
+        }
+    }
+
+    fn encode(&self, x: &BigFloat, rm: RoundingMode) -> u128 {
+        let nan = 0b11111 << (self.k - 6);
+        let inf = 0b11110 << (self.k - 6);
+
+        if x.is_nan() {
+            return nan;
+        }
+
+        let s = if x.is_negative() { 1 << (self.k - 1) } else { 0 };
+
+        if x.is_inf() {
+            return s | inf;
+        }
+
+        if x.is_zero() {
+            return s | self.pack(0, 0);
+        }
+
+        match self.round(x, rm) {
+            Ok(Some((c, q))) => s | self.pack(c, q),
+            Ok(None) => {
+                let sign = if s == 0 { Sign::Pos } else { Sign::Neg };
+
+                if BigFloat::overflow_towards_zero(sign, rm) {
+                    s | self.pack(self.max_coeff(), self.qmax())
+                } else {
+                    s | inf
+                }
+            }
+            Err(_) => nan,
+        }
+    }
+
+    // Returns the coefficient and the exponent of nonzero finite `x` rounded using the rounding mode `rm`,
+    // or None if the result overflows.
+    fn round(&self, x: &BigFloat, rm: RoundingMode) -> Result<Option<(u128, i64)>, Error> {
+        let is_negative = x.is_negative();
+
+        // 10^(e-1) <= |x| < 10^e
+        let (_, _, e) = x.convert_to_radix_digits(Radix::Dec, Some(1), RoundingMode::ToZero)?;
+        let e = e as i64;
+
+        if e - 1 > self.emax {
+            return Ok(None);
+        }
+
+        if e < self.qmin() {
+            // |x| is less than half of the smallest subnormal number
+            let c = round_up(rm, is_negative, Ordering::Less, false, false) as u128;
+            return Ok(Some((c, self.qmin())));
+        }
+
+        let mut q = (e - self.p as i64).max(self.qmin());
+
+        // |x| = m * 2^ex, and the coefficient is |x| / 10^q rounded to an integer
+        let (_, m, ex) = x.to_int_ratio()?;
+
+        let mut num = BigUInt::from_words(&m)?.shl(ex.max(0) as usize)?;
+        let mut den = BigUInt::from_word(1)?.shl((-ex).max(0) as usize)?;
+
+        if q < 0 {
+            num = num.mul(&pow10((-q) as usize)?)?;
+        } else {
+            den = den.mul(&pow10(q as usize)?)?;
+        }
+
+        let (c, r) = num.div_rem(&den)?;
+
+        let is_odd = c.as_words().first().is_some_and(|d| d & 1 == 1);
+        let half = r.shl(1)?.cmp(&den);
+
+        let mut c = c.to_u128().ok_or(Error::InvalidArgument)?;
+
+        if r.is_zero() {
+            // exact results use the largest possible exponent
+            while c % 10 == 0 && q < self.qmax() {
+                c /= 10;
+                q += 1;
+            }
+
+            return Ok(Some((c, q)));
+        }
+
+        if round_up(rm, is_negative, half, r.is_zero(), is_odd) {
+            c += 1;
+        }
+
+        if c > self.max_coeff() {
+            c /= 10;
+            q += 1;
+        }
+
+        if q > self.qmax() {
+            return Ok(None);
+        }
+
+        Ok(Some((c, q)))
+    }
+
+    fn decode(&self, v: u128, p: usize, rm: RoundingMode) -> BigFloat {
+        let is_negative = (v >> (self.k - 1)) & 1 == 1;
+
+        match (v >> (self.k - 6)) & 0b11111 {
+            0b11111 => return BigFloat::nan(None),
+            0b11110 => return if is_negative { crate::INF_NEG } else { crate::INF_POS },
+            _ => {}
+        }
+
+        let cbits = self.cbits();
+        let emask = (1 << self.ebits) - 1;
+
+        let (b, c) = if (v >> (self.k - 3)) & 0b11 == 0b11 {
+            (
+                (v >> (cbits - 2)) & emask,
+                (0b100 << (cbits - 2)) | (v & ((1 << (cbits - 2)) - 1)),
+            )
+        } else {
+            ((v >> cbits) & emask, v & ((1 << cbits) - 1))
+        };
+
+        // non-canonical coefficients are interpreted as zero
+        let c = if c > self.max_coeff() { 0 } else { c };
+        let q = b as i64 - self.bias();
+
+        let mut x = BigFloat::from_u128(c, 128);
+
+        if is_negative {
+            x = x.neg();
+        }
+
+        if c == 0 {
+            if let Err(err) = x.set_precision(p, rm) {
+                return BigFloat::nan(Some(err));
+            }
+            return x;
+        }
+
+        let t = match pow10(q.unsigned_abs() as usize) {
+            Ok(t) => BigFloat::from_biguint(&t, t.bit_len().max(1), RoundingMode::None),
+            Err(err) => return BigFloat::nan(Some(err)),
+        };
+
+        if q < 0 {
+            x.div(&t, p, rm)
+        } else {
+            x.mul(&t, p, rm)
+        }
+    }
+}
+
+// Returns true if the magnitude of the truncated value is incremented when it is rounded using the rounding mode `rm`.
+// `half` is the result of comparison of the discarded part to one half of the unit,
+// `is_zero` is true if the discarded part is zero, and `is_odd` is true if the truncated value is odd.
+fn round_up(
+    rm: RoundingMode,
+    is_negative: bool,
+    half: Ordering,
+    is_zero: bool,
+    is_odd: bool,
+) -> bool {
+    match rm {
+        RoundingMode::ToEven => half == Ordering::Greater || (half == Ordering::Equal && is_odd),
+        RoundingMode::ToOdd => half == Ordering::Greater || (half == Ordering::Equal && !is_odd),
+        RoundingMode::ToNearestAway => half != Ordering::Less,
+        RoundingMode::FromZero => !is_zero,
+        RoundingMode::Up => !is_zero && !is_negative,
+        RoundingMode::Down => !is_zero && is_negative,
+        RoundingMode::ToZero | RoundingMode::None => false,
+    }
+}
+
+// Returns 10^n.
+fn pow10(mut n: usize) -> Result<BigUInt, Error> {
+    let mut ret = BigUInt::from_word(1)?;
+    let mut b = BigUInt::from_word(10)?;
+
+    while n > 0 {
+        if n & 1 == 1 {
+            ret = ret.mul(&b)?;
+        }
+
+        n >>= 1;
+
+        if n > 0 {
+            b = b.mul(&b)?;
+        }
+    }
+
+    Ok(ret)
+}
+
+impl BigFloat {
+    /// Converts the number to the IEEE 754 decimal64 format in the binary integer decimal (BID) encoding,
+    /// rounding it to 16 decimal digits using the rounding mode `rm`.
+    /// Exactly representable numbers are encoded with the largest possible exponent.
+    /// Rounding mode None is treated as ToZero.
+    /// Numbers which exceed the range of decimal64 become infinite,
+    /// or the largest finite decimal64 value if rounding is performed towards zero.
+    /// Small numbers become subnormal or zero.
+    /// NaN is converted to the quiet NaN.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let n = BigFloat::from_word(1, 64);
+    ///
+    /// assert_eq!(n.to_bid64(RoundingMode::ToEven), 0x31C0_0000_0000_0001);
+    /// assert_eq!(BigFloat::from_bid64(0x31C0_0000_0000_0001, 64, RoundingMode::ToEven), n);
+    /// ```
+    pub fn to_bid64(&self, rm: RoundingMode) -> u64 {
+        DECIMAL64.encode(self, rm) as u64
+    }
+
+    /// Converts the number to the IEEE 754 decimal128 format in the binary integer decimal (BID) encoding,
+    /// rounding it to 34 decimal digits using the rounding mode `rm`.
+    /// Exactly representable numbers are encoded with the largest possible exponent.
+    /// Rounding mode None is treated as ToZero.
+    /// Numbers which exceed the range of decimal128 become infinite,
+    /// or the largest finite decimal128 value if rounding is performed towards zero.
+    /// Small numbers become subnormal or zero.
+    /// NaN is converted to the quiet NaN.
+    pub fn to_bid128(&self, rm: RoundingMode) -> u128 {
+        DECIMAL128.encode(self, rm)
+    }
+
+    /// Constructs a number with precision `p` from the IEEE 754 decimal64 value `v` in the binary integer decimal (BID) encoding.
+    /// The result is rounded using the rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// Non-canonical encodings of finite numbers are interpreted as zero, and both quiet and signaling NaN are converted to NaN.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn from_bid64(v: u64, p: usize, rm: RoundingMode) -> Self {
+        DECIMAL64.decode(v as u128, p, rm)
+    }
+
+    /// Constructs a number with precision `p` from the IEEE 754 decimal128 value `v` in the binary integer decimal (BID) encoding.
+    /// The result is rounded using the rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// Non-canonical encodings of finite numbers are interpreted as zero, and both quiet and signaling NaN are converted to NaN.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn from_bid128(v: u128, p: usize, rm: RoundingMode) -> Self {
+        DECIMAL128.decode(v, p, rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::INF_NEG;
+    use crate::INF_POS;
+    use crate::NAN;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    #[test]
+    fn test_bid() {
+        let rm = RoundingMode::ToEven;
+        let p = 256;
+
+        let parse = |s: &str| BigFloat::parse(s, Radix::Dec, p, rm);
+
+        // known encodings
+        for (s, v) in [
+            ("1", 0x31C0_0000_0000_0001),
+            ("-1", 0xB1C0_0000_0000_0001),
+            ("0", 0x31C0_0000_0000_0000),
+            ("1e-398", 0x0000_0000_0000_0001),
+            ("9.999999999999999e384", 0x77FB_86F2_6FC0_FFFF),
+            ("1.234567890123456e0", 0x2FE4_62D5_3C8A_BAC0),
+            ("1.5", 0x31A0_0000_0000_000F),
+        ] {
+            let x = parse(s);
+            assert_eq!(x.to_bid64(rm), v, "{}", s);
+            assert_eq!(BigFloat::from_bid64(v, p, rm), x, "{}", s);
+        }
+
+        for (s, v) in [
+            ("1", 0x3040_0000_0000_0000_0000_0000_0000_0001),
+            ("-1", 0xB040_0000_0000_0000_0000_0000_0000_0001),
+            ("1e-6176", 1),
+            (
+                "9.999999999999999999999999999999999e6144",
+                0x5FFF_ED09_BEAD_87C0_378D_8E63_FFFF_FFFF,
+            ),
+        ] {
+            let x = parse(s);
+            assert_eq!(x.to_bid128(rm), v, "{}", s);
+            assert_eq!(BigFloat::from_bid128(v, p, rm), x, "{}", s);
+        }
+
+        // the sign of zero
+        assert_eq!(BigFloat::new(64).neg().to_bid64(rm), 0xB1C0_0000_0000_0000);
+        assert!(BigFloat::from_bid64(0xB1C0_0000_0000_0000, p, rm).is_negative());
+
+        // special values
+        assert_eq!(NAN.to_bid64(rm), 0x7C00_0000_0000_0000);
+        assert_eq!(INF_POS.to_bid64(rm), 0x7800_0000_0000_0000);
+        assert_eq!(INF_NEG.to_bid64(rm), 0xF800_0000_0000_0000);
+        assert_eq!(INF_NEG.to_bid128(rm), 0xF800 << 112);
+        assert!(BigFloat::from_bid64(0x7C00_0000_0000_0000, p, rm).is_nan());
+        assert!(BigFloat::from_bid64(0x7E00_0000_0000_0000, p, rm).is_nan());
+        assert!(BigFloat::from_bid64(0xF800_0000_0000_0000, p, rm).is_inf_neg());
+        assert!(BigFloat::from_bid128(0x7800 << 112, p, rm).is_inf_pos());
+
+        // non-canonical coefficient
+        assert!(BigFloat::from_bid64(0x6FFF_FFFF_FFFF_FFFF, p, rm).is_zero());
+
+        // rounding
+        let third = BigFloat::from_word(1, p).div(&BigFloat::from_word(3, p), p, rm);
+        assert_eq!(
+            BigFloat::from_bid64(third.to_bid64(rm), p, rm),
+            parse("0.3333333333333333")
+        );
+        assert_eq!(
+            BigFloat::from_bid64(third.to_bid64(RoundingMode::Up), p, rm),
+            parse("0.3333333333333334")
+        );
+        assert_eq!(
+            BigFloat::from_bid64(third.neg().to_bid64(RoundingMode::Down), p, rm),
+            parse("-0.3333333333333334")
+        );
+        assert_eq!(
+            BigFloat::from_bid128(third.to_bid128(rm), p, rm),
+            parse("0.3333333333333333333333333333333333")
+        );
+
+        // ties
+        let x = parse("12345678901234565");
+        assert_eq!(x.to_bid64(rm), parse("1234567890123456e1").to_bid64(rm));
+        assert_eq!(
+            x.to_bid64(RoundingMode::ToNearestAway),
+            parse("1234567890123457e1").to_bid64(rm)
+        );
+        assert_eq!(
+            x.to_bid64(RoundingMode::ToOdd),
+            parse("1234567890123457e1").to_bid64(rm)
+        );
+
+        // carry to the next power of 10
+        let x = parse("99999999999999999");
+        assert_eq!(BigFloat::from_bid64(x.to_bid64(rm), p, rm), parse("1e17"));
+
+        // overflow and underflow
+        let x = parse("1e385");
+        assert_eq!(x.to_bid64(rm), 0x7800_0000_0000_0000);
+        assert_eq!(x.to_bid64(RoundingMode::ToZero), 0x77FB_86F2_6FC0_FFFF);
+        assert_eq!(x.neg().to_bid64(RoundingMode::Up), 0xF7FB_86F2_6FC0_FFFF);
+        assert_eq!(
+            parse("9.9999999999999999e384").to_bid64(rm),
+            0x7800_0000_0000_0000
+        );
+        assert_eq!(parse("1e-400").to_bid64(rm), 0x0000_0000_0000_0000);
+        assert_eq!(
+            parse("1e-400").to_bid64(RoundingMode::Up),
+            0x0000_0000_0000_0001
+        );
+        assert_eq!(parse("7e-399").to_bid64(rm), 0x0000_0000_0000_0001);
+        assert_eq!(
+            BigFloat::from_bid64(parse("1.26e-397").to_bid64(rm), p, rm),
+            parse("1.3e-397")
+        );
+
+        // binary values are rounded as their exact decimal expansions
+        for f in [0.1, -2.5e-300, 1.0 / 3.0, 6.02214076e23, f64::MAX, 5e-324] {
+            let x = BigFloat::from_f64(f, 64);
+            let expected = parse(&format!("{:.15e}", f));
+            assert_eq!(
+                BigFloat::from_bid64(x.to_bid64(rm), p, rm),
+                expected,
+                "{}",
+                f
+            );
+        }
+
+        // round trip
+        for v in [0x2FF4_62D5_3C8A_BAC0, 0x8000_0000_0000_0007, 0x5FE0_0000_0000_0001] {
+            let x = BigFloat::from_bid64(v, p, rm);
+            assert_eq!(BigFloat::from_bid64(x.to_bid64(rm), p, rm), x);
+        }
+    }
+}
@@ -0,0 +1,713 @@
+//! Block floating point vector.
+
+use crate::common::util::{
+    add_slices, cmp_slices, leading_zeros, round_p, round_slice, shift_slice_left_copy,
+    shift_slice_right, sub_slices,
+};
+use crate::defs::{
+    Error, Exponent, RoundingMode, Sign, Word, EXPONENT_MAX, EXPONENT_MIN, WORD_BIT_SIZE,
+};
+use crate::ext::BigFloat;
+use crate::mantissa::Mantissa;
+use crate::num::BigFloatNumber;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A vector of numbers which share one exponent and one precision.
+///
+/// Each element is stored as a sign and a `p`-bit integer mantissa `m`, and has the value `±m * 2^(e - p)`,
+/// where the exponent `e` is common for all elements. The exponent is chosen so that the largest element
+/// is normalized, i.e. the most significant bit of its mantissa is set. Elements which are `2^k` times smaller
+/// than the largest one keep about `p - k` significant bits.
+///
+/// The mantissas are stored contiguously without per-element exponents and allocations, and bulk operations
+/// work directly on them. Each operation computes the exact result of every element and rounds all of them
+/// once to the precision of the result using the common exponent.
+///
+/// ## Examples
+///
+/// ```
+/// use astro_float_num::{BigFloat, BlockFloatVec, RoundingMode};
+///
+/// let rm = RoundingMode::ToEven;
+///
+/// let xs = [1.5, -0.25, 3.0].map(|v| BigFloat::from_f64(v, 64));
+/// let ys = [2.0, 4.0, 0.5].map(|v| BigFloat::from_f64(v, 64));
+///
+/// let a = BlockFloatVec::from_slice(&xs, 64, rm).unwrap();
+/// let b = BlockFloatVec::from_slice(&ys, 64, rm).unwrap();
+///
+/// // the largest element 3 is in [2, 4)
+/// assert_eq!(a.exponent(), 2);
+///
+/// let c = a.mul(&b, rm).unwrap();
+/// assert_eq!(c.get(1), Some(BigFloat::from_f64(-1.0, 64)));
+///
+/// let d = a.dot(&b, 64, rm).unwrap();
+/// assert_eq!(d, BigFloat::from_f64(3.5, 64));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlockFloatVec {
+    m: Vec<Word>,
+    s: Vec<Sign>,
+    w: usize,
+    e: Exponent,
+    inexact: bool,
+}
+
+impl BlockFloatVec {
+    /// Returns a vector of `n` zeroes with precision `p`. Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the precision is zero or too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn new(n: usize, p: usize) -> Result<Self, Error> {
+        let p = Self::p_assertion(p)?;
+
+        Self::zeros(n, p / WORD_BIT_SIZE)
+    }
+
+    /// Constructs a vector with precision `p` from the numbers `xs`.
+    /// The numbers are rounded to the common exponent using the rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the precision is zero or too large, or any of `xs` is NaN.
+    ///  - ExponentOverflow: any of `xs` is Inf, or rounding causes exponent overflow.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn from_slice(xs: &[BigFloat], p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let p = Self::p_assertion(p)?;
+
+        let mut ratios = Vec::new();
+        ratios.try_reserve_exact(xs.len())?;
+
+        for x in xs {
+            ratios.push(x.to_int_ratio()?);
+        }
+
+        let mut parts = Vec::new();
+        parts.try_reserve_exact(xs.len())?;
+        parts.extend(ratios.iter().map(|(s, m, k)| (*s, m.as_slice(), *k)));
+
+        Self::from_parts(&parts, p, rm, false)
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.s.len()
+    }
+
+    /// Returns true if the vector has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.s.is_empty()
+    }
+
+    /// Returns the precision of the elements.
+    #[inline]
+    pub fn precision(&self) -> usize {
+        self.w * WORD_BIT_SIZE
+    }
+
+    /// Returns the common exponent: all elements are smaller than `2^e` by absolute value.
+    /// The exponent of a vector of zeroes is 0.
+    #[inline]
+    pub fn exponent(&self) -> Exponent {
+        self.e
+    }
+
+    /// Returns true if any element of the vector has been rounded.
+    #[inline]
+    pub fn inexact(&self) -> bool {
+        self.inexact
+    }
+
+    /// Returns the element at index `i` as a number with the precision of the vector,
+    /// or None if `i` is out of bounds.
+    pub fn get(&self, i: usize) -> Option<BigFloat> {
+        if i >= self.len() {
+            return None;
+        }
+
+        let p = self.precision();
+        let mut ret = BigFloat::from_int_ratio(self.s[i], self.mantissa(i), self.unit());
+        ret.set_precision(p, RoundingMode::None).ok()?;
+
+        Some(ret)
+    }
+
+    /// Returns all elements of the vector.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn to_vec(&self) -> Result<Vec<BigFloat>, Error> {
+        let mut ret = Vec::new();
+        ret.try_reserve_exact(self.len())?;
+
+        for i in 0..self.len() {
+            ret.push(self.get(i).ok_or(Error::InvalidArgument)?);
+        }
+
+        Ok(ret)
+    }
+
+    /// Returns the element-wise sum of `self` and `d2`.
+    /// The precision of the result is the larger of the precisions of `self` and `d2`.
+    /// The result is rounded using the rounding mode `rm`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the vectors have different lengths.
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn add(&self, d2: &Self, rm: RoundingMode) -> Result<Self, Error> {
+        self.add_sub(d2, false, rm)
+    }
+
+    /// Returns the element-wise difference of `self` and `d2`.
+    /// The precision of the result is the larger of the precisions of `self` and `d2`.
+    /// The result is rounded using the rounding mode `rm`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the vectors have different lengths.
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn sub(&self, d2: &Self, rm: RoundingMode) -> Result<Self, Error> {
+        self.add_sub(d2, true, rm)
+    }
+
+    /// Returns the element-wise product of `self` and `d2`.
+    /// The precision of the result is the larger of the precisions of `self` and `d2`.
+    /// The result is rounded using the rounding mode `rm`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the vectors have different lengths.
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn mul(&self, d2: &Self, rm: RoundingMode) -> Result<Self, Error> {
+        if self.len() != d2.len() {
+            return Err(Error::InvalidArgument);
+        }
+
+        let wa = self.w + d2.w;
+        let mut buf = Self::zero_words(self.len() * wa)?;
+
+        for (i, a) in buf.chunks_mut(wa).enumerate() {
+            Mantissa::mul_unbalanced(self.mantissa(i), d2.mantissa(i), a)?;
+        }
+
+        let l = self.unit() + d2.unit();
+
+        let mut parts = Vec::new();
+        parts.try_reserve_exact(self.len())?;
+        parts.extend(
+            buf.chunks(wa)
+                .zip(self.s.iter().zip(d2.s.iter()))
+                .map(|(a, (&s1, &s2))| (if s1 == s2 { Sign::Pos } else { Sign::Neg }, a, l)),
+        );
+
+        Self::from_parts(
+            &parts,
+            self.precision().max(d2.precision()),
+            rm,
+            self.inexact || d2.inexact,
+        )
+    }
+
+    /// Returns the dot product of `self` and `d2` with precision `p`.
+    /// The sum of products is computed exactly and rounded once using the rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the vectors have different lengths, or the precision is zero or too large.
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn dot(&self, d2: &Self, p: usize, rm: RoundingMode) -> Result<BigFloat, Error> {
+        if self.len() != d2.len() {
+            return Err(Error::InvalidArgument);
+        }
+
+        let p = Self::p_assertion(p)?;
+
+        // the sum of n products needs log2(n) bits more than a product
+        let wp = self.w + d2.w;
+        let wa = wp + (usize::BITS - self.len().leading_zeros()) as usize / WORD_BIT_SIZE + 1;
+
+        let mut prod = Self::zero_words(wp)?;
+        let mut pos = Self::zero_words(wa)?;
+        let mut neg = Self::zero_words(wa)?;
+
+        for i in 0..self.len() {
+            Mantissa::mul_unbalanced(self.mantissa(i), d2.mantissa(i), &mut prod)?;
+
+            let acc = if self.s[i] == d2.s[i] { &mut pos } else { &mut neg };
+            if add_slices(acc, &prod) {
+                for v in acc[wp..].iter_mut() {
+                    *v = v.wrapping_add(1);
+                    if *v != 0 {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let s = if cmp_slices(&pos, &neg) >= 0 {
+            sub_slices(&mut pos, &neg);
+            Sign::Pos
+        } else {
+            sub_slices(&mut neg, &pos);
+            pos = neg;
+            Sign::Neg
+        };
+
+        let mut ret = BigFloatNumber::from_int_ratio(s, &pos, self.unit() + d2.unit())?;
+        ret.set_precision(p, rm)?;
+
+        if self.inexact || d2.inexact {
+            ret.set_inexact(true);
+        }
+
+        Ok(ret.into())
+    }
+
+    // Element-wise addition if `neg2` is false, or subtraction otherwise.
+    fn add_sub(&self, d2: &Self, neg2: bool, rm: RoundingMode) -> Result<Self, Error> {
+        if self.len() != d2.len() {
+            return Err(Error::InvalidArgument);
+        }
+
+        let p = self.precision().max(d2.precision());
+        let inexact = self.inexact || d2.inexact;
+
+        let s2: Vec<Sign> = if neg2 {
+            let mut s2 = Vec::new();
+            s2.try_reserve_exact(d2.len())?;
+            s2.extend(d2.s.iter().map(|s| s.invert()));
+            s2
+        } else {
+            Vec::new()
+        };
+        let s2 = if neg2 { &s2 } else { &d2.s };
+
+        // a vector of zeroes does not constrain the exponent
+        let (hi, shi, lo, slo) = if self.is_zero() {
+            (d2, s2, self, &self.s)
+        } else if d2.is_zero() || self.e >= d2.e {
+            (self, &self.s, d2, s2)
+        } else {
+            (d2, s2, self, &self.s)
+        };
+
+        let (l, jam) = if lo.is_zero() {
+            (hi.unit(), false)
+        } else if (hi.e as isize - lo.e as isize) <= (p + 2 * WORD_BIT_SIZE) as isize {
+            (hi.unit().min(lo.unit()), false)
+        } else {
+            // The elements of `lo` are smaller than 2^(-3) units of the least significant bit of the result and of `hi`,
+            // and only their signs and whether they are zero affect rounding.
+            (hi.unit().min(hi.e as isize - p as isize) - 3, true)
+        };
+
+        let wa = (hi.e as isize - l + 1) as usize / WORD_BIT_SIZE + 1;
+        let mut buf = Self::zero_words(self.len() * wa)?;
+        let mut b = Self::zero_words(wa)?;
+        let mut signs = Vec::new();
+        signs.try_reserve_exact(self.len())?;
+
+        for (i, a) in buf.chunks_mut(wa).enumerate() {
+            shift_slice_left_copy(hi.mantissa(i), a, (hi.unit() - l) as usize);
+
+            if jam {
+                b.fill(0);
+                b[0] = !lo.mantissa(i).iter().all(|&v| v == 0) as Word;
+            } else {
+                shift_slice_left_copy(lo.mantissa(i), &mut b, (lo.unit() - l) as usize);
+            }
+
+            let (s1, s2) = (shi[i], slo[i]);
+
+            let s = if s1 == s2 {
+                add_slices(a, &b);
+                s1
+            } else if cmp_slices(a, &b) >= 0 {
+                sub_slices(a, &b);
+                s1
+            } else {
+                sub_slices(&mut b, a);
+                a.copy_from_slice(&b);
+                s2
+            };
+
+            signs.push(s);
+        }
+
+        let mut parts = Vec::new();
+        parts.try_reserve_exact(self.len())?;
+        parts.extend(buf.chunks(wa).zip(signs.iter()).map(|(a, &s)| (s, a, l)));
+
+        Self::from_parts(&parts, p, rm, inexact)
+    }
+
+    // Builds a vector with precision `p` from the exact values `s * m * 2^k` given in `parts`
+    // rounding them to the common exponent using `rm`.
+    fn from_parts(
+        parts: &[(Sign, &[Word], isize)],
+        p: usize,
+        rm: RoundingMode,
+        inexact: bool,
+    ) -> Result<Self, Error> {
+        let mut ret = Self::zeros(parts.len(), p / WORD_BIT_SIZE)?;
+        ret.inexact = inexact;
+
+        // exponent of the largest value
+        let mut top = None;
+        for &(s, m, k) in parts {
+            let bl = m.len() * WORD_BIT_SIZE - leading_zeros(m);
+            if bl > 0 {
+                let e = k + bl as isize;
+                if top.is_none_or(|(t, _)| e > t) {
+                    top = Some((e, s));
+                }
+            }
+        }
+
+        let (mut e, s_top) = match top {
+            Some(t) => t,
+            None => return Ok(ret),
+        };
+
+        // values below the exponent range lose their least significant bits
+        e = e.max(EXPONENT_MIN as isize);
+
+        let mut buf = Vec::new();
+
+        loop {
+            if e > EXPONENT_MAX as isize {
+                return Err(Error::ExponentOverflow(s_top));
+            }
+
+            let unit = e - p as isize;
+            let mut fits = true;
+            let mut inexact = false;
+
+            for (i, &(s, m, k)) in parts.iter().enumerate() {
+                let dst = &mut ret.m[i * ret.w..(i + 1) * ret.w];
+
+                match Self::round_shifted(m, unit - k, s == Sign::Pos, rm, dst, &mut buf)? {
+                    Some(ix) => inexact |= ix,
+                    None => {
+                        fits = false;
+                        break;
+                    }
+                }
+
+                ret.s[i] = if dst.iter().all(|&v| v == 0) { Sign::Pos } else { s };
+            }
+
+            if fits {
+                ret.e = e as Exponent;
+                ret.inexact |= inexact;
+                return Ok(ret);
+            }
+
+            // the largest value has been rounded upwards to the next power of 2
+            e += 1;
+        }
+    }
+
+    // Writes `m * 2^(-shift)` rounded to an integer using `rm` to `dst`.
+    // Returns None if the result does not fit in `dst`, otherwise returns true if the result is inexact.
+    fn round_shifted(
+        m: &[Word],
+        shift: isize,
+        is_positive: bool,
+        rm: RoundingMode,
+        dst: &mut [Word],
+        buf: &mut Vec<Word>,
+    ) -> Result<Option<bool>, Error> {
+        let bl = m.len() * WORD_BIT_SIZE - leading_zeros(m);
+
+        if bl == 0 {
+            dst.fill(0);
+            return Ok(Some(false));
+        }
+
+        if shift <= 0 {
+            let sh = shift.unsigned_abs();
+
+            if bl + sh > dst.len() * WORD_BIT_SIZE {
+                return Ok(None);
+            }
+
+            let ml = bl.div_ceil(WORD_BIT_SIZE);
+            shift_slice_left_copy(&m[..ml], dst, sh);
+
+            Ok(Some(false))
+        } else {
+            // an extra word keeps the carry, and shifting by more than the length of `m` gives the same rounding
+            let sh = (shift as usize).min(m.len() * WORD_BIT_SIZE + 1);
+
+            buf.clear();
+            buf.try_reserve(m.len() + 1)?;
+            buf.extend_from_slice(m);
+            buf.push(0);
+
+            let (_, inexact) = round_slice(
+                buf,
+                (m.len() + 1) * WORD_BIT_SIZE - sh,
+                rm,
+                is_positive,
+                false,
+            );
+
+            shift_slice_right(buf, sh);
+
+            if buf.len() > dst.len() && buf[dst.len()..].iter().any(|&v| v != 0) {
+                return Ok(None);
+            }
+
+            let l = buf.len().min(dst.len());
+            dst[..l].copy_from_slice(&buf[..l]);
+            dst[l..].fill(0);
+
+            Ok(Some(inexact))
+        }
+    }
+
+    // Checks precision `p` and rounds it upwards to a multiple of `PRECISION_GRANULARITY`.
+    fn p_assertion(p: usize) -> Result<usize, Error> {
+        let p = round_p(p);
+
+        if p == 0 || p >= (isize::MAX / 2 + EXPONENT_MIN as isize) as usize {
+            Err(Error::InvalidArgument)
+        } else {
+            Ok(p)
+        }
+    }
+
+    // Returns `n` zeroes, each having `w` words.
+    fn zeros(n: usize, w: usize) -> Result<Self, Error> {
+        let mut s = Vec::new();
+        s.try_reserve_exact(n)?;
+        s.resize(n, Sign::Pos);
+
+        Ok(BlockFloatVec {
+            m: Self::zero_words(n.checked_mul(w).ok_or(Error::MemoryAllocation)?)?,
+            s,
+            w,
+            e: 0,
+            inexact: false,
+        })
+    }
+
+    // Returns `n` zero words.
+    fn zero_words(n: usize) -> Result<Vec<Word>, Error> {
+        let mut m = Vec::new();
+        m.try_reserve_exact(n)?;
+        m.resize(n, 0);
+        Ok(m)
+    }
+
+    // Returns the mantissa of the element at index `i`.
+    #[inline]
+    fn mantissa(&self, i: usize) -> &[Word] {
+        &self.m[i * self.w..(i + 1) * self.w]
+    }
+
+    // Returns the exponent of the least significant bit of the mantissas.
+    #[inline]
+    fn unit(&self) -> isize {
+        self.e as isize - self.precision() as isize
+    }
+
+    // Returns true if all elements are zero.
+    fn is_zero(&self) -> bool {
+        self.m.iter().all(|&v| v == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const P_EXACT: usize = 16384;
+
+    fn random_vec(n: usize, p: usize, exp_from: Exponent, exp_to: Exponent) -> Vec<BigFloat> {
+        (0..n)
+            .map(|_| {
+                if rand::random::<u8>() % 8 == 0 {
+                    BigFloat::new(p)
+                } else {
+                    let mut x = BigFloat::random_normal(p, exp_from, exp_to);
+                    if rand::random::<bool>() {
+                        x.inv_sign();
+                    }
+                    x
+                }
+            })
+            .collect()
+    }
+
+    // Checks that elements of `b` are the values `x` rounded to the common exponent of `b` with `rm`.
+    fn check_rounded(b: &BlockFloatVec, x: &[BigFloat], rm: RoundingMode) {
+        assert_eq!(b.len(), x.len());
+
+        let u = b.e as isize - b.precision() as isize;
+        let ulp = BigFloat::from_int_ratio(Sign::Pos, &[1], u);
+        let half = BigFloat::from_int_ratio(Sign::Pos, &[1], u - 1);
+        let top = BigFloat::from_int_ratio(Sign::Pos, &[1], b.e as isize);
+        let msb = BigFloat::from_int_ratio(Sign::Pos, &[1], b.e as isize - 1);
+
+        let mut normalized = x.iter().all(|v| v.is_zero());
+        let mut inexact = false;
+
+        for (i, v) in x.iter().enumerate() {
+            let r = b.get(i).unwrap();
+            if !r.is_zero() {
+                assert_eq!(r.precision(), Some(b.precision()));
+            }
+            assert!(r.abs_cmp(&top).unwrap() < 0);
+
+            let d = r.sub(v, P_EXACT, RoundingMode::None);
+            inexact |= !d.is_zero();
+
+            match rm {
+                RoundingMode::ToEven => assert!(d.abs_cmp(&half).unwrap() <= 0),
+                RoundingMode::Down => {
+                    assert!(d.is_zero() || d.is_negative());
+                    assert!(d.abs_cmp(&ulp).unwrap() < 0);
+                }
+                RoundingMode::Up => {
+                    assert!(d.is_zero() || d.is_positive());
+                    assert!(d.abs_cmp(&ulp).unwrap() < 0);
+                }
+                _ => unreachable!(),
+            }
+
+            if r.abs_cmp(&msb).unwrap() >= 0 {
+                normalized = true;
+            }
+        }
+
+        assert!(normalized || b.e == EXPONENT_MIN);
+        if inexact {
+            assert!(b.inexact());
+        }
+    }
+
+    #[test]
+    fn test_block_float_vec() {
+        let rms = [RoundingMode::ToEven, RoundingMode::Down, RoundingMode::Up];
+
+        for _ in 0..200 {
+            let n = rand::random::<usize>() % 20;
+            let p1 = (rand::random::<usize>() % 4 + 1) * WORD_BIT_SIZE;
+            let p2 = (rand::random::<usize>() % 4 + 1) * WORD_BIT_SIZE;
+            let p = p1.max(p2);
+            let rm = rms[rand::random::<usize>() % rms.len()];
+
+            let x = random_vec(n, p1 * 2, -100, 100);
+            let y = random_vec(n, p2 * 2, -100, 100);
+
+            let a = BlockFloatVec::from_slice(&x, p1, rm).unwrap();
+            let b = BlockFloatVec::from_slice(&y, p2, rm).unwrap();
+            check_rounded(&a, &x, rm);
+            check_rounded(&b, &y, rm);
+
+            let x = a.to_vec().unwrap();
+            let y = b.to_vec().unwrap();
+
+            let sum: Vec<BigFloat> = x
+                .iter()
+                .zip(y.iter())
+                .map(|(u, v)| u.add(v, P_EXACT, RoundingMode::None))
+                .collect();
+            check_rounded(&a.add(&b, rm).unwrap(), &sum, rm);
+
+            let diff: Vec<BigFloat> = x
+                .iter()
+                .zip(y.iter())
+                .map(|(u, v)| u.sub(v, P_EXACT, RoundingMode::None))
+                .collect();
+            check_rounded(&a.sub(&b, rm).unwrap(), &diff, rm);
+
+            let prod: Vec<BigFloat> = x
+                .iter()
+                .zip(y.iter())
+                .map(|(u, v)| u.mul(v, P_EXACT, RoundingMode::None))
+                .collect();
+            check_rounded(&a.mul(&b, rm).unwrap(), &prod, rm);
+
+            let mut dot = BigFloat::new(P_EXACT);
+            for v in prod.iter() {
+                dot = dot.add(v, P_EXACT, RoundingMode::None);
+            }
+            let mut expected = dot.clone();
+            expected.set_precision(round_p(p), rm).unwrap();
+            let d = a.dot(&b, p, rm).unwrap();
+            assert!(d.is_zero() && expected.is_zero() || d == expected);
+        }
+
+        // large difference in exponents
+        let rm = RoundingMode::ToEven;
+        let x = [BigFloat::from_f64(1.0e100, 64), BigFloat::new(64), BigFloat::from_f64(-3.0, 64)];
+        let y = [
+            BigFloat::from_f64(1.0e-100, 64),
+            BigFloat::from_f64(-1.0e-100, 64),
+            BigFloat::new(64),
+        ];
+        let a = BlockFloatVec::from_slice(&x, 64, rm).unwrap();
+        let b = BlockFloatVec::from_slice(&y, 64, rm).unwrap();
+
+        for rm in rms {
+            let x = a.to_vec().unwrap();
+            let y = b.to_vec().unwrap();
+            let sum: Vec<BigFloat> = x
+                .iter()
+                .zip(y.iter())
+                .map(|(u, v)| u.add(v, P_EXACT, RoundingMode::None))
+                .collect();
+            check_rounded(&a.add(&b, rm).unwrap(), &sum, rm);
+            check_rounded(&b.add(&a, rm).unwrap(), &sum, rm);
+        }
+
+        let c = a.add(&b, RoundingMode::Down).unwrap();
+        assert!(c.get(1).unwrap().is_negative());
+        assert!(!c.get(1).unwrap().is_zero());
+
+        // errors
+        assert_eq!(
+            BlockFloatVec::new(3, 0).unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            a.add(&BlockFloatVec::new(2, 64).unwrap(), rm).unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            BlockFloatVec::from_slice(&[crate::NAN], 64, rm).unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert!(BlockFloatVec::from_slice(&[crate::INF_POS], 64, rm).is_err());
+
+        // zero vectors
+        let z = BlockFloatVec::new(3, 128).unwrap();
+        assert_eq!(z.exponent(), 0);
+        assert!(!z.inexact());
+        assert_eq!(
+            z.add(&a, rm).unwrap().to_vec().unwrap(),
+            a.to_vec().unwrap()
+        );
+        assert!(z.dot(&a, 64, rm).unwrap().is_zero());
+        assert!(BlockFloatVec::new(0, 64).unwrap().is_empty());
+    }
+}
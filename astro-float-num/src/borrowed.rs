@@ -0,0 +1,279 @@
+//! Floating point number borrowing its mantissa from an externally owned buffer.
+
+use crate::common::util::leading_zeros;
+use crate::defs::{
+    Error, Exponent, Sign, SignedWord, Word, EXPONENT_MAX, EXPONENT_MIN, WORD_BIT_SIZE,
+};
+
+#[cfg(feature = "alloc")]
+use crate::ext::BigFloat;
+
+/// A read-only view of a finite floating point number whose mantissa is stored in a borrowed slice of words.
+///
+/// `BigFloatRef` wraps memory owned by someone else, e.g. a memory-mapped file or a buffer shared with
+/// another library, without copying it. The view supports inspection and comparison.
+/// Arithmetic operations require an owned mantissa, and are performed after converting the view
+/// to `BigFloat` with `to_big_float`, which copies the words.
+///
+/// ## Examples
+///
+/// ```
+/// use astro_float_num::{BigFloat, BigFloatRef, Sign, Word};
+///
+/// let words: [Word; 2] = [0, 1 << (Word::BITS - 1)];
+///
+/// // 0.1 * 2^1 in binary
+/// let x = BigFloatRef::from_borrowed_words(&words, Word::BITS as usize * 2, Sign::Pos, 1, false).unwrap();
+///
+/// assert_eq!(x.cmp(&BigFloat::from_word(1, 128).as_borrowed().unwrap()), 0);
+/// assert_eq!(x.to_big_float(), BigFloat::from_word(1, 128));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BigFloatRef<'a> {
+    m: &'a [Word],
+    n: usize,
+    s: Sign,
+    e: Exponent,
+    inexact: bool,
+}
+
+impl<'a> BigFloatRef<'a> {
+    /// Constructs a view of a number from the raw parts without copying the mantissa `m`.
+    /// The parts are the same as in `BigFloat::from_raw_parts`:
+    ///
+    ///  - `m` is the mantissa, the most significant word comes last.
+    ///  - `n` is the number of significant bits in mantissa.
+    ///  - `s` is the sign.
+    ///  - `e` is the exponent.
+    ///  - `inexact` specify whether number is inexact.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `n` is not equal to the number of significant bits in `m`;
+    ///    `n` is smaller than the number of bits in `m`, but `e` is not the minimum possible exponent;
+    ///    the size of `m` is too large (larger than isize::MAX / 2 + EXPONENT_MIN);
+    ///    `e` is less than EXPONENT_MIN or greater than EXPONENT_MAX.
+    pub fn from_borrowed_words(
+        m: &'a [Word],
+        n: usize,
+        s: Sign,
+        mut e: Exponent,
+        inexact: bool,
+    ) -> Result<Self, Error> {
+        let p = m.len() * WORD_BIT_SIZE;
+
+        if p >= (isize::MAX / 2 + EXPONENT_MIN as isize) as usize
+            || !(EXPONENT_MIN..=EXPONENT_MAX).contains(&e)
+            || n > p
+        {
+            return Err(Error::InvalidArgument);
+        }
+
+        if p - leading_zeros(m) != n {
+            return Err(Error::InvalidArgument);
+        }
+
+        if n > 0 && n < p && e > EXPONENT_MIN {
+            return Err(Error::InvalidArgument);
+        }
+
+        if n == 0 {
+            e = 0;
+        }
+
+        Ok(BigFloatRef {
+            m,
+            n,
+            s,
+            e,
+            inexact,
+        })
+    }
+
+    /// Returns the raw parts of the number: the mantissa, the number of significant bits in the mantissa,
+    /// the sign, the exponent, and the inexact flag.
+    /// The mantissa is the slice the view was constructed from.
+    pub fn as_raw_parts(&self) -> (&'a [Word], usize, Sign, Exponent, bool) {
+        (self.m, self.n, self.s, self.e, self.inexact)
+    }
+
+    /// Returns the words of the mantissa, the most significant word comes last.
+    pub fn mantissa(&self) -> &'a [Word] {
+        self.m
+    }
+
+    /// Returns the number of bits in the mantissa.
+    pub fn mantissa_max_bit_len(&self) -> usize {
+        self.m.len() * WORD_BIT_SIZE
+    }
+
+    /// Returns the number of significant bits used in the mantissa.
+    pub fn precision(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the sign of the number.
+    pub fn sign(&self) -> Sign {
+        self.s
+    }
+
+    /// Returns the exponent of the number.
+    pub fn exponent(&self) -> Exponent {
+        self.e
+    }
+
+    /// Returns true if the number is inexact.
+    pub fn inexact(&self) -> bool {
+        self.inexact
+    }
+
+    /// Returns true if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Compares `self` to `d2`.
+    /// Returns positive if `self` is greater than `d2`, negative if `self` is smaller than `d2`, 0 otherwise.
+    pub fn cmp(&self, d2: &Self) -> SignedWord {
+        if self.is_zero() && d2.is_zero() {
+            return 0;
+        }
+
+        if self.s != d2.s {
+            return self.s as SignedWord;
+        }
+
+        self.abs_cmp(d2) * self.s as SignedWord
+    }
+
+    /// Compares the absolute value of `self` to the absolute value of `d2`.
+    /// Returns positive if `|self|` is greater than `|d2|`, negative if `|self|` is smaller than `|d2|`, 0 otherwise.
+    pub fn abs_cmp(&self, d2: &Self) -> SignedWord {
+        if self.is_zero() {
+            return if d2.is_zero() { 0 } else { -1 };
+        } else if d2.is_zero() {
+            return 1;
+        }
+
+        if self.e != d2.e {
+            return if self.e > d2.e { 1 } else { -1 };
+        }
+
+        // mantissas of different length are aligned at the most significant word;
+        // subnormal numbers have the same exponent and can be compared word by word as well
+        let mut i1 = self.m.iter().rev();
+        let mut i2 = d2.m.iter().rev();
+
+        loop {
+            match (i1.next(), i2.next()) {
+                (None, None) => return 0,
+                (a, b) => {
+                    let a = a.copied().unwrap_or(0);
+                    let b = b.copied().unwrap_or(0);
+
+                    if a != b {
+                        return if a > b { 1 } else { -1 };
+                    }
+                }
+            }
+        }
+    }
+
+    /// Converts the view to `BigFloat` copying the mantissa.
+    #[cfg(feature = "alloc")]
+    pub fn to_big_float(&self) -> BigFloat {
+        BigFloat::from_raw_parts(self.m, self.n, self.s, self.e, self.inexact)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&BigFloatRef<'_>> for BigFloat {
+    fn from(x: &BigFloatRef<'_>) -> Self {
+        x.to_big_float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_borrowed() {
+        let hi: Word = 1 << (WORD_BIT_SIZE - 1);
+        let one_words = [0, hi];
+        let three_words = [0, hi | (hi >> 1)];
+        let short_words = [hi];
+        let p = 2 * WORD_BIT_SIZE;
+
+        let one = BigFloatRef::from_borrowed_words(&one_words, p, Sign::Pos, 1, false).unwrap();
+        let three = BigFloatRef::from_borrowed_words(&three_words, p, Sign::Pos, 2, true).unwrap();
+
+        // the view does not copy the words
+        assert!(core::ptr::eq(one.mantissa(), &one_words[..]));
+        assert_eq!(one.as_raw_parts(), (&one_words[..], p, Sign::Pos, 1, false));
+        assert_eq!(three.precision(), p);
+        assert_eq!(three.mantissa_max_bit_len(), p);
+        assert!(three.inexact());
+
+        // comparison
+        assert_eq!(one.cmp(&three), -1);
+        assert_eq!(three.cmp(&one), 1);
+        assert_eq!(one.cmp(&one), 0);
+
+        let neg_three =
+            BigFloatRef::from_borrowed_words(&three_words, p, Sign::Neg, 2, false).unwrap();
+        assert_eq!(neg_three.cmp(&one), -1);
+        assert_eq!(neg_three.abs_cmp(&three), 0);
+
+        // different lengths
+        let one_short =
+            BigFloatRef::from_borrowed_words(&short_words, WORD_BIT_SIZE, Sign::Pos, 1, false)
+                .unwrap();
+        assert_eq!(one_short.cmp(&one), 0);
+        assert_eq!(one_short.cmp(&three), -1);
+
+        // zero and subnormal numbers
+        let zero_words = [0, 0];
+        let zero = BigFloatRef::from_borrowed_words(&zero_words, 0, Sign::Neg, 5, false).unwrap();
+        assert!(zero.is_zero());
+        assert_eq!(zero.exponent(), 0);
+        let sub =
+            BigFloatRef::from_borrowed_words(&[1, 0], 1, Sign::Pos, EXPONENT_MIN, false).unwrap();
+        assert_eq!(sub.cmp(&zero), 1);
+        let min_normal = BigFloatRef::from_borrowed_words(
+            &short_words,
+            WORD_BIT_SIZE,
+            Sign::Pos,
+            EXPONENT_MIN,
+            false,
+        )
+        .unwrap();
+        assert_eq!(sub.cmp(&min_normal), -1);
+
+        // invalid parts
+        assert!(matches!(
+            BigFloatRef::from_borrowed_words(&[1, 0], 1, Sign::Pos, 0, false),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            BigFloatRef::from_borrowed_words(&one_words, p - 1, Sign::Pos, 0, false),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            BigFloatRef::from_borrowed_words(&one_words, p + 1, Sign::Pos, 0, false),
+            Err(Error::InvalidArgument)
+        ));
+
+        // conversion
+        #[cfg(feature = "alloc")]
+        {
+            let x = BigFloat::from(&three);
+            assert_eq!(x, BigFloat::from_word(3, p));
+            assert!(x.inexact());
+            assert_eq!(x.as_borrowed().unwrap().cmp(&three), 0);
+            assert!(crate::NAN.as_borrowed().is_none());
+            assert!(crate::INF_POS.as_borrowed().is_none());
+        }
+    }
+}
@@ -33,6 +33,18 @@ impl WordBuf {
         Ok(WordBuf { inner })
     }
 
+    /// Constructs the buffer from the vector of words without copying.
+    #[inline]
+    pub fn from_vec(inner: Vec<Word>) -> Self {
+        WordBuf { inner }
+    }
+
+    /// Returns the vector of words of the buffer without copying.
+    #[inline]
+    pub fn into_vec(self) -> Vec<Word> {
+        self.inner
+    }
+
     #[inline]
     pub fn fill(&mut self, d: Word) {
         self.inner.fill(d);
@@ -43,6 +55,18 @@ impl WordBuf {
         self.inner.len()
     }
 
+    /// Returns the number of words the buffer can hold without reallocation.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Releases the memory which is not used by the buffer.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit();
+    }
+
     /// Decrease length of the buffer to l bits. Data is shifted.
     pub fn trunc_to(&mut self, l: usize) {
         let n = (l + WORD_BIT_SIZE - 1) / WORD_BIT_SIZE;
@@ -1,6 +1,10 @@
 //! Mathematical constants.
 
+#[cfg(feature = "alloc")]
 pub mod buf;
+#[cfg(feature = "alloc")]
 pub mod consts;
+#[cfg(feature = "alloc")]
 pub mod int;
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
 pub mod util;
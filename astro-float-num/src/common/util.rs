@@ -1,7 +1,9 @@
 //! Auxiliary functions.
 
 use crate::{
-    defs::{Word, WORD_BIT_SIZE, WORD_MAX, WORD_SIGNIFICANT_BIT},
+    defs::{
+        SignedWord, Word, PRECISION_GRANULARITY, WORD_BIT_SIZE, WORD_MAX, WORD_SIGNIFICANT_BIT,
+    },
     RoundingMode,
 };
 
@@ -117,19 +119,22 @@ pub fn calc_sqrt_cost(p: usize, cost_mul: usize, cost_add: usize) -> usize {
 
 #[inline(always)]
 pub fn add_carry(a: Word, b: Word, c: Word, r: &mut Word) -> Word {
-    #[cfg(target_arch = "x86_64")]
+    #[cfg(all(target_arch = "x86_64", not(word32)))]
     {
         // platform-specific operation
         unsafe { core::arch::x86_64::_addcarry_u64(c as u8, a, b, r) as Word }
     }
 
-    #[cfg(target_arch = "x86")]
+    #[cfg(all(target_arch = "x86", word32))]
     {
         // platform-specific operation
         unsafe { core::arch::x86::_addcarry_u32(c as u8, a, b, r) as Word }
     }
 
-    #[cfg(not(any(target_arch = "x86_64", target_arch = "x86")))]
+    #[cfg(not(any(
+        all(target_arch = "x86_64", not(word32)),
+        all(target_arch = "x86", word32)
+    )))]
     {
         use crate::defs::DoubleWord;
         use crate::defs::WORD_BASE;
@@ -148,19 +153,22 @@ pub fn add_carry(a: Word, b: Word, c: Word, r: &mut Word) -> Word {
 
 #[inline(always)]
 pub fn sub_borrow(a: Word, b: Word, c: Word, r: &mut Word) -> Word {
-    #[cfg(target_arch = "x86_64")]
+    #[cfg(all(target_arch = "x86_64", not(word32)))]
     {
         // platform-specific operation
         unsafe { core::arch::x86_64::_subborrow_u64(c as u8, a, b, r) as Word }
     }
 
-    #[cfg(target_arch = "x86")]
+    #[cfg(all(target_arch = "x86", word32))]
     {
         // platform-specific operation
         unsafe { core::arch::x86::_subborrow_u32(c as u8, a, b, r) as Word }
     }
 
-    #[cfg(not(any(target_arch = "x86_64", target_arch = "x86")))]
+    #[cfg(not(any(
+        all(target_arch = "x86_64", not(word32)),
+        all(target_arch = "x86", word32)
+    )))]
     {
         use crate::defs::DoubleWord;
         use crate::defs::WORD_BASE;
@@ -326,9 +334,151 @@ pub fn count_leading_ones(m: &[Word]) -> usize {
     ret
 }
 
-/// Round precision to word bounday.
+/// Round precision to word bounday, or to 64 bits with the `portable` feature.
 pub fn round_p(p: usize) -> usize {
-    ((p.saturating_add(WORD_BIT_SIZE - 1)) / WORD_BIT_SIZE) * WORD_BIT_SIZE
+    ((p.saturating_add(PRECISION_GRANULARITY - 1)) / PRECISION_GRANULARITY) * PRECISION_GRANULARITY
+}
+
+/// Returns the integer value of the mantissa `m` with exponent `e`, given the number has no fractional part.
+/// Returns None if the value does not fit in u128.
+pub fn int_to_u128(m: &[Word], e: isize) -> Option<u128> {
+    if e <= 0 {
+        return Some(0);
+    }
+
+    if e > 128 {
+        return None;
+    }
+
+    let mut acc = 0u128;
+    let mut bits = 0;
+
+    for &w in m.iter().rev() {
+        if bits >= 128 {
+            break;
+        }
+
+        acc = (acc << WORD_BIT_SIZE) | w as u128;
+        bits += WORD_BIT_SIZE;
+    }
+
+    let e = e as usize;
+
+    Some(if e <= bits { acc >> (bits - e) } else { acc << (e - bits) })
+}
+
+// Rounds `m` to `p` bits according to `rm` zeroing the discarded bits.
+// `sticky` is true if any nonzero bits were discarded beyond the least significant bit of `m`.
+// Returns true if rounding resulted in carry out of the most significant bit,
+// and true if the result is inexact.
+pub fn round_slice(
+    m: &mut [Word],
+    p: usize,
+    rm: RoundingMode,
+    is_positive: bool,
+    sticky: bool,
+) -> (bool, bool) {
+    let n = m.len() * WORD_BIT_SIZE - p;
+
+    debug_assert!(n > 0 && p > 0);
+
+    let rpos = n - 1;
+    let rbit = (m[rpos / WORD_BIT_SIZE] >> (rpos % WORD_BIT_SIZE)) & 1 != 0;
+    let rest = sticky
+        || m[..rpos / WORD_BIT_SIZE].iter().any(|&v| v != 0)
+        || m[rpos / WORD_BIT_SIZE] & ((1 << (rpos % WORD_BIT_SIZE)) - 1) != 0;
+    let lsb = (m[n / WORD_BIT_SIZE] >> (n % WORD_BIT_SIZE)) & 1 != 0;
+
+    let inexact = rbit || rest;
+
+    let inc = match rm {
+        RoundingMode::None | RoundingMode::ToZero => false,
+        RoundingMode::Up => inexact && is_positive,
+        RoundingMode::Down => inexact && !is_positive,
+        RoundingMode::FromZero => inexact,
+        RoundingMode::ToEven => rbit && (rest || lsb),
+        RoundingMode::ToOdd => rbit && (rest || !lsb),
+        RoundingMode::ToNearestAway => rbit,
+    };
+
+    // discarded bits become 0
+    m[..n / WORD_BIT_SIZE].fill(0);
+    m[n / WORD_BIT_SIZE] &= !((1 << (n % WORD_BIT_SIZE)) - 1);
+
+    let mut carry = false;
+
+    if inc {
+        let mut c = 1 << (n % WORD_BIT_SIZE);
+        for v in m[n / WORD_BIT_SIZE..].iter_mut() {
+            c = add_carry(*v, c, 0, v);
+            if c == 0 {
+                break;
+            }
+        }
+        carry = c != 0;
+    }
+
+    (carry, inexact)
+}
+
+// Shifts `m` right by `n` bits, returns true if any of the discarded bits is nonzero.
+pub fn shift_right_sticky(m: &mut [Word], n: usize) -> bool {
+    let idx = n / WORD_BIT_SIZE;
+
+    if idx >= m.len() {
+        let sticky = m.iter().any(|&v| v != 0);
+        m.fill(0);
+        return sticky;
+    }
+
+    let shift = n % WORD_BIT_SIZE;
+    let sticky =
+        m[..idx].iter().any(|&v| v != 0) || (shift > 0 && m[idx] & ((1 << shift) - 1) != 0);
+
+    shift_slice_right(m, n);
+
+    sticky
+}
+
+// Adds `m2` to `m` in place, returns carry.
+pub fn add_slices(m: &mut [Word], m2: &[Word]) -> bool {
+    let mut c = 0;
+    for (a, &b) in m.iter_mut().zip(m2.iter()) {
+        c = add_carry(*a, b, c, a);
+    }
+    c != 0
+}
+
+// Subtracts `m2` from `m` in place ignoring the final borrow.
+pub fn sub_slices(m: &mut [Word], m2: &[Word]) {
+    let mut c = 0;
+    for (a, &b) in m.iter_mut().zip(m2.iter()) {
+        c = sub_borrow(*a, b, c, a);
+    }
+}
+
+// Compares slices of equal length as integers.
+pub fn cmp_slices(m: &[Word], m2: &[Word]) -> SignedWord {
+    for (&a, &b) in m.iter().rev().zip(m2.iter().rev()) {
+        if a > b {
+            return 1;
+        } else if a < b {
+            return -1;
+        }
+    }
+    0
+}
+
+// Returns the number of leading zero bits of `m`.
+pub fn leading_zeros(m: &[Word]) -> usize {
+    let mut ret = 0;
+    for &v in m.iter().rev() {
+        ret += v.leading_zeros() as usize;
+        if v != 0 {
+            break;
+        }
+    }
+    ret
 }
 
 // Convert rounding mode for an opposite sign.
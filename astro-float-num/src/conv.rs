@@ -1,10 +1,7 @@
 //! Conversion utilities.
 
-use crate::common::consts::EIGHT;
-use crate::common::consts::SIXTEEN;
 use crate::common::consts::TEN;
 use crate::common::consts::TEN_POW_9;
-use crate::common::consts::TWO;
 use crate::common::util::round_p;
 use crate::defs::DoubleWord;
 use crate::defs::Error;
@@ -17,6 +14,8 @@ use crate::defs::WORD_BIT_SIZE;
 use crate::defs::WORD_MAX;
 use crate::mantissa::Mantissa;
 use crate::num::BigFloatNumber;
+use crate::uint::BigUInt;
+use crate::BigFloat;
 use crate::EXPONENT_MAX;
 use crate::EXPONENT_MIN;
 
@@ -28,7 +27,7 @@ impl BigFloatNumber {
     /// `digits` represents mantissa and is interpreted as a number smaller than 1 and greater or equal to 1/`rdx`.
     /// The first element in `digits` is the most significant digit.
     /// `e` is the exponent part of the number, such that the number can be represented as `digits` * `rdx` ^ `e`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -149,7 +148,7 @@ impl BigFloatNumber {
 
         if zeroes == digits.len() {
             // mantissa is zero
-            let m = Mantissa::new(p)?;
+            let m = Mantissa::new(round_p(p))?;
 
             Ok(BigFloatNumber::from_raw_unchecked(m, sign, 0, false))
         } else {
@@ -206,6 +205,10 @@ impl BigFloatNumber {
 
                 num.subnormalize(e, RoundingMode::None);
 
+                if num.is_zero() {
+                    return Self::underflow(sign, p, rm);
+                }
+
                 if num.inexact() {
                     num.mantissa_mut().digits_mut()[0] |= 1; // sticky for correct rounding when calling set_precision()
                 }
@@ -274,10 +277,14 @@ impl BigFloatNumber {
             f = f.add(&d2, pf, RoundingMode::None)?;
         }
 
+        if f.is_zero() {
+            return Self::new2(p, sign, false);
+        }
+
         // exponent part
         let n = e as isize - digits.len() as isize;
 
-        let nmax = (EXPONENT_MAX as u64 * 301029995 / 1000000000) as usize;
+        let nmax = (EXPONENT_MAX as u128 * 301029995 / 1000000000) as usize;
 
         let ten = Self::from_word(10, 4)?;
 
@@ -305,82 +312,291 @@ impl BigFloatNumber {
             }?;
         }
 
+        if f.is_zero() {
+            return Self::underflow(sign, p, rm);
+        }
+
         f.set_sign(sign);
         f.set_precision(p, rm)?;
 
         Ok(f)
     }
 
+    /// Returns the result of rounding a nonzero number with sign `s` which is smaller in magnitude than
+    /// the minimum positive subnormal number: either zero, or the minimum subnormal number when
+    /// rounding `rm` is away from zero. The result is inexact.
+    pub(crate) fn underflow(s: Sign, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let mut ret = if rm == RoundingMode::FromZero
+            || (s.is_positive() && rm == RoundingMode::Up)
+            || (s.is_negative() && rm == RoundingMode::Down)
+        {
+            Self::min_positive(p)?
+        } else {
+            Self::new(p)?
+        };
+
+        ret.set_sign(s);
+        ret.set_inexact(true);
+
+        Ok(ret)
+    }
+
+    // Returns the largest finite number with sign `s` and precision `p` marked as inexact,
+    // if the number which is too large to be represented is rounded towards zero using rounding mode `rm`.
+    pub(crate) fn overflow(s: Sign, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        if !BigFloat::overflow_towards_zero(s, rm) {
+            return Err(Error::ExponentOverflow(s));
+        }
+
+        let mut ret = Self::max_value(p)?;
+        ret.set_sign(s);
+        ret.set_inexact(true);
+
+        Ok(ret)
+    }
+
     /// Converts `self` to radix `rdx` using rounding mode `rm`.
     /// The function returns sign, mantissa digits in radix `rdx`, and exponent such that the converted number
     /// can be represented as `mantissa digits` * `rdx` ^ `exponent`.
     /// The first element in the mantissa is the most significant digit.
+    /// Conversion to radixes 2, 8, and 16 is exact. Conversion to radix 10 produces enough digits for the number
+    /// to be restored from them, and the last digit is correctly rounded according to `rm`.
+    /// If `max_digits` is not None, the mantissa is correctly rounded to at most `max_digits` digits according to `rm`.
+    /// In radix 10 only the digits which are returned are computed, which makes the conversion of a
+    /// number with large precision to a few digits fast.
+    /// Rounding mode None is treated as ToZero.
     ///
     /// ## Errors
     ///
     ///  - MemoryAllocation: failed to allocate memory for mantissa.
     ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - InvalidArgument: `max_digits` is zero.
     pub fn convert_to_radix(
         &self,
         rdx: Radix,
+        max_digits: Option<usize>,
         rm: RoundingMode,
     ) -> Result<(Sign, Vec<u8>, Exponent), Error> {
-        match rdx {
-            Radix::Bin => self.conv_to_binary(),
-            Radix::Oct => self.conv_to_commensurable(3),
-            Radix::Dec => self.conv_to_dec(rm),
-            Radix::Hex => self.conv_to_commensurable(4),
-        }
+        let mut digits = Vec::new();
+        let (s, _, e) = self.convert_to_radix_into(rdx, max_digits, rm, &mut digits)?;
+        Ok((s, digits, e))
     }
 
-    fn conv_to_dec(&self, rm: RoundingMode) -> Result<(Sign, Vec<u8>, Exponent), Error> {
-        // input: rdx = 10, self = m*2^e, 0.5 <= m < 1,
-        // let self = m*2^e * rdx^n / rdx^n, where n = floor(e * log_rdx(2))
-        // let f = m / rdx^n,
-        // then resulting number is F = f * rdx^n
+    /// Converts `self` to radix `rdx` using rounding mode `rm` like `convert_to_radix`,
+    /// but appends the mantissa digits to `buf` instead of returning a new vector.
+    /// The function returns sign, the number of digits appended to `buf`, and exponent.
+    /// Conversion to radixes 2, 8, and 16 does not allocate memory if `buf` has enough capacity.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - InvalidArgument: `max_digits` is zero.
+    pub fn convert_to_radix_into(
+        &self,
+        rdx: Radix,
+        max_digits: Option<usize>,
+        rm: RoundingMode,
+        buf: &mut Vec<u8>,
+    ) -> Result<(Sign, usize, Exponent), Error> {
+        if max_digits == Some(0) {
+            return Err(Error::InvalidArgument);
+        }
+
+        let start = buf.len();
 
-        let n = (self.exponent().unsigned_abs() as u64 * 301029996 / 1000000000) as usize;
-        let l = (self.mantissa_max_bit_len() as u64 * 301029996 / 1000000000 + 1) as usize;
+        let e = match rdx {
+            Radix::Bin => self.conv_to_binary(buf),
+            Radix::Oct => self.conv_to_commensurable(3, buf),
+            Radix::Dec => self.conv_to_dec(max_digits, rm, buf),
+            Radix::Hex => self.conv_to_commensurable(4, buf),
+        }
+        .and_then(|e| match max_digits {
+            Some(n) if rdx != Radix::Dec => self.round_digits(e, buf, start, n, rdx as u8, rm),
+            _ => Ok(e),
+        })
+        .inspect_err(|_| {
+            buf.truncate(start);
+        })?;
 
-        let (digits, e_shift) = if n == 0 {
-            self.conv_mantissa(l, Radix::Dec, rm)
-        } else {
-            let p_w = self.mantissa_max_bit_len() + WORD_BIT_SIZE;
+        Ok((self.sign(), buf.len() - start, e))
+    }
 
-            let rdx = Self::number_for_radix(Radix::Dec)?;
+    // Rounds the digits in radix `rdx` starting at `start` in `buf` to `n` digits using rounding mode `rm`,
+    // and returns the exponent `e` adjusted for the carry.
+    fn round_digits(
+        &self,
+        e: Exponent,
+        buf: &mut Vec<u8>,
+        start: usize,
+        n: usize,
+        rdx: u8,
+        rm: RoundingMode,
+    ) -> Result<Exponent, Error> {
+        if buf.len() - start <= n {
+            return Ok(e);
+        }
 
-            let f = if n >= 646456993 {
-                // avoid powi overflow
+        let last = start + n;
+        let half = rdx / 2;
+        let first = buf[last];
+        let rest_nonzero = buf[last + 1..].iter().any(|&d| d != 0);
+        let tail_nonzero = first != 0 || rest_nonzero;
+        let odd = buf[last - 1] & 1 == 1;
+
+        let round_up = match rm {
+            RoundingMode::None | RoundingMode::ToZero => false,
+            RoundingMode::FromZero => tail_nonzero,
+            RoundingMode::Up => tail_nonzero && self.is_positive(),
+            RoundingMode::Down => tail_nonzero && self.is_negative(),
+            RoundingMode::ToEven => first > half || (first == half && (rest_nonzero || odd)),
+            RoundingMode::ToNearestAway => first >= half,
+            RoundingMode::ToOdd => first > half || (first == half && (rest_nonzero || !odd)),
+        };
 
-                let d = rdx.powi(n - 1, p_w, RoundingMode::None)?;
+        buf.truncate(last);
 
-                if self.exponent() < 0 {
-                    self.mul(&d, self.mantissa_max_bit_len(), RoundingMode::None)?
-                        .mul(rdx, self.mantissa_max_bit_len(), RoundingMode::None)
+        if round_up {
+            for d in buf[start..].iter_mut().rev() {
+                if *d == rdx - 1 {
+                    *d = 0;
                 } else {
-                    self.div(&d, self.mantissa_max_bit_len(), RoundingMode::None)?
-                        .div(rdx, self.mantissa_max_bit_len(), RoundingMode::None)
+                    *d += 1;
+                    return Ok(e);
                 }
+            }
+
+            // all digits were rdx - 1
+            if e == EXPONENT_MAX {
+                return Err(Error::ExponentOverflow(self.sign()));
+            }
+
+            buf[start] = 1;
+
+            return Ok(e + 1);
+        }
+
+        Ok(e)
+    }
+
+    fn conv_to_dec(
+        &self,
+        max_digits: Option<usize>,
+        rm: RoundingMode,
+        buf: &mut Vec<u8>,
+    ) -> Result<Exponent, Error> {
+        // input: self = m*2^e, 0.5 <= m < 1.
+        // The result is D*10^(n-l), where D is the integer with l digits obtained by rounding self*10^(l-n),
+        // and n = floor(log10(self)) + 1.
+
+        if self.is_zero() {
+            return Ok(0);
+        }
+
+        let mut l = (self.mantissa_max_bit_len() as u64 * 301029996 / 1000000000 + 2) as usize;
+
+        if let Some(max_digits) = max_digits {
+            l = l.min(max_digits);
+        }
+
+        // the number of bits of the integer with l digits
+        let p = (l as u64 * 3321928095 / 1000000000 + 1) as usize;
+
+        // 2^(e-1) <= self < 2^e, where e is the exponent of the normalized value of a subnormal number,
+        // and log_10(2) is approximated accurately enough for a 64-bit exponent,
+        // so the estimate can differ from n by at most one.
+        let e = self.exponent() as i128
+            - (self.mantissa_max_bit_len() - self.mantissa().bit_len()) as i128;
+        let mut n = ((e - 1) * 3010299956639811953).div_euclid(10000000000000000000) as isize + 1;
+
+        let start = buf.len();
+
+        // n is adjusted until the integer has exactly l digits. The estimate of n differs from n by at most one,
+        // and rounding of self*10^(l-n) can carry into an additional digit only once, because the carry
+        // gives 10^l, and self*10^(l-n-1) is then rounded to 10^(l-1) in the same direction.
+        // So n is adjusted at most twice.
+        loop {
+            let d = self.round_scaled_dec(l as isize - n, p, rm)?;
+            BigUInt::from_number(&d)?.dec_digits(buf)?;
+
+            let len = buf.len() - start;
+
+            if len == l {
+                // strip zeroes
+                let nzeroes = buf.iter().rev().take_while(|x| **x == 0).count();
+                buf.truncate(buf.len() - nzeroes);
+
+                return Exponent::try_from(n).map_err(|_| Error::ExponentOverflow(self.sign()));
+            }
+
+            buf.truncate(start);
+
+            if len > l {
+                n += 1;
             } else {
-                let d = rdx.powi(n, p_w, RoundingMode::None)?;
+                n -= 1;
+            }
+        }
+    }
 
-                if self.exponent() < 0 {
-                    self.mul(&d, self.mantissa_max_bit_len(), RoundingMode::None)
-                } else {
-                    self.div(&d, self.mantissa_max_bit_len(), RoundingMode::None)
+    // Returns self*10^k rounded to an integer using rounding mode `rm`, where the integer has about `p` bits.
+    // Rounding mode None is treated as ToZero.
+    fn round_scaled_dec(&self, k: isize, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let rm = if rm == RoundingMode::None { RoundingMode::ToZero } else { rm };
+
+        let mut x = self.clone()?;
+        x.set_inexact(false);
+
+        let kabs = k.unsigned_abs();
+
+        // 10^k is split in two parts when it would overflow.
+        let kmax = (EXPONENT_MAX as u128 * 3010299956639811953 / 10000000000000000000) as usize;
+        let (k1, k2) = if kabs > kmax { (kabs / 2, kabs - kabs / 2) } else { (kabs, 0) };
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = round_p(self.mantissa_max_bit_len().min(p)) + 2 * WORD_BIT_SIZE;
+
+        loop {
+            let mut y = x.clone()?;
+
+            if y.mantissa_max_bit_len() > p_wrk {
+                y.set_precision(p_wrk, RoundingMode::ToEven)?;
+            }
+
+            for kp in [k1, k2] {
+                if kp > 0 {
+                    let t = TEN.powi(kp, p_wrk, RoundingMode::ToEven)?;
+
+                    y = if k < 0 {
+                        y.div(&t, p_wrk, RoundingMode::ToEven)
+                    } else {
+                        y.mul(&t, p_wrk, RoundingMode::ToEven)
+                    }?;
                 }
-            }?;
+            }
 
-            f.conv_mantissa(l, Radix::Dec, rm)
-        }?;
+            if !y.inexact() {
+                return y.round(0, rm);
+            }
 
-        let e = (n as Exponent) * self.exponent().signum() + e_shift;
+            // at most 5 roundings with relative error of 2^(-p_wrk) each
+            let mut err = y.abs()?;
+            err.set_exponent(y.exponent() - p_wrk as Exponent + 4);
 
-        Ok((self.sign(), digits, e))
+            let lo = y.sub_full_prec(&err)?.round(0, rm)?;
+            let hi = y.add_full_prec(&err)?.round(0, rm)?;
+
+            if lo.cmp(&hi) == 0 {
+                return Ok(lo);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
     }
 
     /// Conversion for radixes of power of 2.
-    fn conv_to_commensurable(&self, shift: usize) -> Result<(Sign, Vec<u8>, Exponent), Error> {
+    fn conv_to_commensurable(&self, shift: usize, buf: &mut Vec<u8>) -> Result<Exponent, Error> {
         let mut e = self.exponent();
         let mut e_shift = e.unsigned_abs() as usize % shift;
         e /= shift as Exponent;
@@ -389,7 +605,7 @@ impl BigFloatNumber {
             e += 1;
         }
 
-        let mut ret = Vec::new();
+        buf.try_reserve(self.mantissa_max_bit_len().div_ceil(shift) + 1)?;
 
         let mask = (WORD_MAX >> (WORD_BIT_SIZE - shift)) as DoubleWord;
         let mut iter = self.mantissa().digits().iter().rev();
@@ -400,7 +616,7 @@ impl BigFloatNumber {
         loop {
             let digit = ((d >> done) & mask) as u8;
 
-            ret.push(digit);
+            buf.push(digit);
 
             if done < shift {
                 d <<= WORD_BIT_SIZE;
@@ -423,118 +639,22 @@ impl BigFloatNumber {
             }
 
             let digit = ((d >> done) & mask) as u8;
-            ret.push(digit);
+            buf.push(digit);
         }
 
-        Ok((self.sign(), ret, e))
+        Ok(e)
     }
 
-    fn conv_to_binary(&self) -> Result<(Sign, Vec<u8>, Exponent), Error> {
-        let mut ret = Vec::new();
+    fn conv_to_binary(&self, buf: &mut Vec<u8>) -> Result<Exponent, Error> {
+        buf.try_reserve(self.mantissa_max_bit_len())?;
 
         for v in self.mantissa().digits().iter().rev() {
             for i in (0..WORD_BIT_SIZE).rev() {
-                ret.push(((v >> i) & 1) as u8);
+                buf.push(((v >> i) & 1) as u8);
             }
         }
 
-        Ok((self.sign(), ret, self.exponent()))
-    }
-
-    fn conv_mantissa(
-        &self,
-        l: usize,
-        rdx: Radix,
-        rm: RoundingMode,
-    ) -> Result<(Vec<u8>, Exponent), Error> {
-        let mut ret = Vec::new();
-        let mut e_shift = 0;
-
-        if self.is_zero() {
-            ret.try_reserve_exact(1)?;
-            ret.push(0);
-        } else {
-            ret.try_reserve_exact(3 + l)?;
-
-            let mut r = self.clone()?;
-            r.set_sign(Sign::Pos);
-            r.set_precision(r.mantissa_max_bit_len() + 4, RoundingMode::None)?;
-
-            let rdx_num = Self::number_for_radix(rdx)?;
-            let rdx_word = Self::word_for_radix(rdx);
-
-            let mut word;
-
-            let d = r.mul(rdx_num, r.mantissa_max_bit_len(), RoundingMode::None)?;
-            r = d.fract()?;
-            word = d.int_as_word();
-            if word == 0 {
-                e_shift = -1;
-                let d = r.mul(rdx_num, r.mantissa_max_bit_len(), RoundingMode::None)?;
-                r = d.fract()?;
-                word = d.int_as_word();
-            } else if word >= rdx_word {
-                e_shift = 1;
-
-                ret.push((word / rdx_word) as u8);
-                ret.push((word % rdx_word) as u8);
-
-                let d = r.mul(rdx_num, r.mantissa_max_bit_len(), RoundingMode::None)?;
-                r = d.fract()?;
-                word = d.int_as_word();
-            }
-
-            for _ in 0..l {
-                ret.push(word as u8);
-
-                let d = r.mul(rdx_num, r.mantissa_max_bit_len(), RoundingMode::None)?;
-                r = d.fract()?;
-                word = d.int_as_word();
-            }
-
-            if !r.round(0, rm)?.is_zero() {
-                word += 1;
-
-                if word == rdx_word {
-                    ret.push(0);
-
-                    let mut i = ret.len() - 2;
-                    while i > 0 && ret[i] + 1 == rdx_word as u8 {
-                        ret[i] = 0;
-                        i -= 1;
-                    }
-                    ret[i] += 1;
-                } else {
-                    ret.push(word as u8);
-                }
-            } else {
-                ret.push(word as u8);
-            }
-        }
-
-        // strip zeroes
-        let nzeroes = ret.iter().rev().take_while(|x| **x == 0).count();
-        ret.truncate(ret.len() - nzeroes);
-
-        Ok((ret, e_shift))
-    }
-
-    fn word_for_radix(rdx: Radix) -> Word {
-        match rdx {
-            Radix::Bin => 2,
-            Radix::Oct => 8,
-            Radix::Dec => 10,
-            Radix::Hex => 16,
-        }
-    }
-
-    fn number_for_radix(rdx: Radix) -> Result<&'static Self, Error> {
-        Ok(match rdx {
-            Radix::Bin => &TWO,
-            Radix::Oct => &EIGHT,
-            Radix::Dec => &TEN,
-            Radix::Hex => &SIXTEEN,
-        })
+        Ok(self.exponent())
     }
 }
 
@@ -543,16 +663,21 @@ mod tests {
 
     use super::*;
     use crate::common::consts::ONE;
-    use crate::common::util::random_subnormal;
+    use crate::common::util::{random_subnormal, round_p};
     use crate::defs::{Sign, EXPONENT_MAX, EXPONENT_MIN};
     use rand::random;
 
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
     #[test]
     fn test_conv() {
         // basic tests
         let n = BigFloatNumber::from_f64(64, 0.031256789f64).unwrap();
 
-        let (s, m, e) = n.convert_to_radix(Radix::Bin, RoundingMode::None).unwrap();
+        let (s, m, e) = n
+            .convert_to_radix(Radix::Bin, None, RoundingMode::None)
+            .unwrap();
 
         assert_eq!(
             m,
@@ -573,7 +698,9 @@ mod tests {
 
         let n = BigFloatNumber::from_f64(64, 0.00012345678f64).unwrap();
 
-        let (s, m, e) = n.convert_to_radix(Radix::Dec, RoundingMode::None).unwrap();
+        let (s, m, e) = n
+            .convert_to_radix(Radix::Dec, None, RoundingMode::None)
+            .unwrap();
 
         assert_eq!(s, Sign::Pos);
         assert_eq!(
@@ -594,7 +721,7 @@ mod tests {
         let n = BigFloatNumber::from_f64(64, -83.591552734375).unwrap();
         assert_eq!(n.cmp(&g), 0);
 
-        #[cfg(target_arch = "x86")]
+        #[cfg(word32)]
         {
             let n = BigFloatNumber::from_raw_parts(
                 &[2576980377, 2576980377, 2576980377],
@@ -604,7 +731,9 @@ mod tests {
                 false,
             )
             .unwrap();
-            let (s, m, e) = n.convert_to_radix(Radix::Oct, RoundingMode::None).unwrap();
+            let (s, m, e) = n
+                .convert_to_radix(Radix::Oct, None, RoundingMode::None)
+                .unwrap();
             let g =
                 BigFloatNumber::convert_from_radix(s, &m, e, Radix::Oct, 160, RoundingMode::ToEven)
                     .unwrap();
@@ -619,7 +748,9 @@ mod tests {
                 false,
             )
             .unwrap();
-            let (s, m, e) = n.convert_to_radix(Radix::Dec, RoundingMode::None).unwrap();
+            let (s, m, e) = n
+                .convert_to_radix(Radix::Dec, None, RoundingMode::None)
+                .unwrap();
 
             assert_eq!(
                 m,
@@ -631,13 +762,14 @@ mod tests {
             assert!(s == Sign::Pos);
             assert!(e == 0);
 
-            let g =
+            let mut g =
                 BigFloatNumber::convert_from_radix(s, &m, e, Radix::Dec, 96, RoundingMode::ToEven)
                     .unwrap();
+            g.set_precision(96, RoundingMode::ToEven).unwrap();
             assert!(g.cmp(&n) == 0);
         }
 
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(word32))]
         {
             let n = BigFloatNumber::from_raw_parts(
                 &[0x9999999999999999, 0x9999999999999999, 0x9999999999999999],
@@ -647,7 +779,9 @@ mod tests {
                 false,
             )
             .unwrap();
-            let (s, m, e) = n.convert_to_radix(Radix::Oct, RoundingMode::None).unwrap();
+            let (s, m, e) = n
+                .convert_to_radix(Radix::Oct, None, RoundingMode::None)
+                .unwrap();
             let g =
                 BigFloatNumber::convert_from_radix(s, &m, e, Radix::Oct, 192, RoundingMode::ToEven)
                     .unwrap();
@@ -663,7 +797,7 @@ mod tests {
             )
             .unwrap();
             let (s, m, e) = n
-                .convert_to_radix(Radix::Dec, RoundingMode::ToEven)
+                .convert_to_radix(Radix::Dec, None, RoundingMode::ToEven)
                 .unwrap();
 
             assert_eq!(
@@ -697,8 +831,8 @@ mod tests {
         let p_rng = 32;
 
         for _ in 0..1000 {
-            let p1 = (random::<usize>() % p_rng + 1) * WORD_BIT_SIZE;
-            let p2 = (random::<usize>() % p_rng + 1) * WORD_BIT_SIZE;
+            let p1 = round_p((random::<usize>() % p_rng + 1) * WORD_BIT_SIZE);
+            let p2 = round_p((random::<usize>() % p_rng + 1) * WORD_BIT_SIZE);
             let p = p1.min(p2);
 
             let mut n =
@@ -706,7 +840,7 @@ mod tests {
                     .unwrap();
             let rdx = random_radix();
 
-            let (s1, m1, e1) = n.convert_to_radix(rdx, RoundingMode::ToEven).unwrap();
+            let (s1, m1, e1) = n.convert_to_radix(rdx, None, RoundingMode::ToEven).unwrap();
             let mut g =
                 BigFloatNumber::convert_from_radix(s1, &m1, e1, rdx, p2, RoundingMode::ToEven)
                     .unwrap();
@@ -738,14 +872,14 @@ mod tests {
 
         // subnormal values
         for _ in 0..1000 {
-            let p1 = (random::<usize>() % p_rng + 3) * WORD_BIT_SIZE;
-            let p2 = (random::<usize>() % p_rng + 3) * WORD_BIT_SIZE;
+            let p1 = round_p((random::<usize>() % p_rng + 3) * WORD_BIT_SIZE);
+            let p2 = round_p((random::<usize>() % p_rng + 3) * WORD_BIT_SIZE);
             let p = p1.min(p2);
 
             let mut n = random_subnormal(p1);
             let rdx = random_radix();
 
-            let (s1, m1, e1) = n.convert_to_radix(rdx, RoundingMode::ToEven).unwrap();
+            let (s1, m1, e1) = n.convert_to_radix(rdx, None, RoundingMode::ToEven).unwrap();
 
             //println!("\n{:?}", rdx);
             //println!("{:?} {:?} {}", s1, m1, e1);
@@ -785,8 +919,8 @@ mod tests {
         }
 
         // MIN, MAX, min_subnormal
-        let p1 = (random::<usize>() % p_rng + 1) * WORD_BIT_SIZE;
-        let p2 = (random::<usize>() % p_rng + 1) * WORD_BIT_SIZE;
+        let p1 = round_p((random::<usize>() % p_rng + 1) * WORD_BIT_SIZE);
+        let p2 = round_p((random::<usize>() % p_rng + 1) * WORD_BIT_SIZE);
         let p = p1.min(p2);
 
         for rdx in [Radix::Bin, Radix::Oct, Radix::Dec, Radix::Hex] {
@@ -799,7 +933,7 @@ mod tests {
                 //println!("\n{:?} {} {}", rdx, p1, p2);
                 //println!("{:?}", n);
 
-                let (s1, m1, e1) = n.convert_to_radix(rdx, rm).unwrap();
+                let (s1, m1, e1) = n.convert_to_radix(rdx, None, rm).unwrap();
 
                 //println!("{:?} {:?} {}", s1, m1, e1);
 
@@ -826,7 +960,7 @@ mod tests {
             let mut n = BigFloatNumber::min_positive(p1).unwrap();
             //println!("\n{:?} {} {}", rdx, p1, p2);
             //println!("{:?}", n);
-            let (s1, m1, e1) = n.convert_to_radix(rdx, rm).unwrap();
+            let (s1, m1, e1) = n.convert_to_radix(rdx, None, rm).unwrap();
 
             //println!("{:?} {:?} {}", s1, m1, e1);
 
@@ -905,6 +1039,263 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_conv_dec_rounding() {
+        let digits = |d: &str| d.bytes().map(|b| b - b'0').collect::<Vec<u8>>();
+
+        // 0.5 + 2^-22 = 0.5000002384185791015625 is halfway between two 21-digit decimals
+        let mut n = BigFloatNumber::from_f64(64, 0.5 + 2f64.powi(-22)).unwrap();
+        let lower = digits("500000238418579101562");
+        let upper = digits("500000238418579101563");
+
+        for (rm, pos, neg) in [
+            (RoundingMode::ToEven, &lower, &lower),
+            (RoundingMode::ToOdd, &upper, &upper),
+            (RoundingMode::ToNearestAway, &upper, &upper),
+            (RoundingMode::Up, &upper, &lower),
+            (RoundingMode::Down, &lower, &upper),
+            (RoundingMode::FromZero, &upper, &upper),
+            (RoundingMode::ToZero, &lower, &lower),
+            (RoundingMode::None, &lower, &lower),
+        ] {
+            n.set_sign(Sign::Pos);
+            assert_eq!(
+                n.convert_to_radix(Radix::Dec, None, rm).unwrap(),
+                (Sign::Pos, pos.clone(), 0)
+            );
+            n.set_sign(Sign::Neg);
+            assert_eq!(
+                n.convert_to_radix(Radix::Dec, None, rm).unwrap(),
+                (Sign::Neg, neg.clone(), 0)
+            );
+        }
+
+        // values close to a power of 10
+        let n = BigFloatNumber::from_f64(64, 1e23).unwrap();
+        let (_, m, e) = n
+            .convert_to_radix(Radix::Dec, None, RoundingMode::Up)
+            .unwrap();
+        assert_eq!((m, e), (digits("999999999999999916114"), 23));
+        let n = BigFloatNumber::from_raw_parts(
+            &[WORD_MAX; 64 / WORD_BIT_SIZE],
+            64,
+            Sign::Pos,
+            0,
+            false,
+        )
+        .unwrap();
+        let (_, m, e) = n
+            .convert_to_radix(Radix::Dec, None, RoundingMode::ToEven)
+            .unwrap();
+        assert_eq!((m, e), (digits("999999999999999999946"), 0));
+
+        // exact values
+        let n = BigFloatNumber::from_word(1000, 64).unwrap();
+        let (_, m, e) = n
+            .convert_to_radix(Radix::Dec, None, RoundingMode::Up)
+            .unwrap();
+        assert_eq!((m, e), (digits("1"), 4));
+
+        // conversion with enough digits round-trips
+        for _ in 0..1000 {
+            let p = (random::<usize>() % 8 + 1) * WORD_BIT_SIZE;
+            let n = BigFloatNumber::random_normal(p, EXPONENT_MIN + p as Exponent, EXPONENT_MAX)
+                .unwrap();
+
+            let (s, m, e) = n
+                .convert_to_radix(Radix::Dec, None, RoundingMode::ToEven)
+                .unwrap();
+            let g =
+                BigFloatNumber::convert_from_radix(s, &m, e, Radix::Dec, p, RoundingMode::ToEven)
+                    .unwrap();
+
+            assert!(n.cmp(&g) == 0);
+        }
+    }
+
+    #[test]
+    fn test_conv_max_digits() {
+        let digits = |d: &str| d.bytes().map(|b| b - b'0').collect::<Vec<u8>>();
+        let mut cc = crate::Consts::new().unwrap();
+
+        // pi = 3.14159265358979323846264338327|950288...
+        let pi = cc.pi_num(1024, RoundingMode::ToEven).unwrap();
+        for (rm, d) in [
+            (RoundingMode::ToEven, "314159265358979323846264338328"),
+            (RoundingMode::ToZero, "314159265358979323846264338327"),
+            (RoundingMode::Down, "314159265358979323846264338327"),
+            (RoundingMode::Up, "314159265358979323846264338328"),
+        ] {
+            let (s, m, e) = pi.convert_to_radix(Radix::Dec, Some(30), rm).unwrap();
+            assert_eq!((s, m, e), (Sign::Pos, digits(d), 1));
+        }
+
+        // pi = 0x3.243f6a8885a308d3...
+        let (_, m, e) = pi
+            .convert_to_radix(Radix::Hex, Some(6), RoundingMode::ToEven)
+            .unwrap();
+        assert_eq!((m, e), (vec![3, 2, 4, 3, 15, 7], 1));
+        let (_, m, _) = pi
+            .convert_to_radix(Radix::Hex, Some(5), RoundingMode::ToEven)
+            .unwrap();
+        assert_eq!(m, [3, 2, 4, 3, 15]);
+        let (_, m, _) = pi
+            .convert_to_radix(Radix::Hex, Some(4), RoundingMode::ToEven)
+            .unwrap();
+        assert_eq!(m, [3, 2, 4, 4]);
+        let (_, m, _) = pi
+            .convert_to_radix(Radix::Hex, Some(4), RoundingMode::ToZero)
+            .unwrap();
+        assert_eq!(m, [3, 2, 4, 3]);
+
+        // carry out of the most significant digit
+        let n = BigFloatNumber::from_f64(64, -0.99996).unwrap();
+        let (s, m, e) = n
+            .convert_to_radix(Radix::Dec, Some(4), RoundingMode::ToEven)
+            .unwrap();
+        assert_eq!((s, m, e), (Sign::Neg, digits("1"), 1));
+        let (_, m, e) = n
+            .convert_to_radix(Radix::Dec, Some(4), RoundingMode::Up)
+            .unwrap();
+        assert_eq!((m, e), (digits("9999"), 0));
+        let n = BigFloatNumber::from_f64(64, 0.9375).unwrap();
+        let (_, m, e) = n
+            .convert_to_radix(Radix::Bin, Some(3), RoundingMode::ToEven)
+            .unwrap();
+        assert_eq!((m, e), (vec![1, 0, 0], 1));
+        let (_, m, e) = n
+            .convert_to_radix(Radix::Bin, Some(3), RoundingMode::ToZero)
+            .unwrap();
+        assert_eq!((m, e), (vec![1, 1, 1], 0));
+        let (_, m, e) = n
+            .convert_to_radix(Radix::Oct, Some(1), RoundingMode::ToOdd)
+            .unwrap();
+        assert_eq!((m, e), (vec![7], 0));
+        let (_, m, e) = n
+            .convert_to_radix(Radix::Oct, Some(1), RoundingMode::FromZero)
+            .unwrap();
+        assert_eq!((m, e), (vec![1], 1));
+
+        // ties
+        let n = BigFloatNumber::from_f64(64, 0.625).unwrap();
+        for (rm, d) in [
+            (RoundingMode::ToEven, "62"),
+            (RoundingMode::ToNearestAway, "63"),
+            (RoundingMode::ToOdd, "63"),
+        ] {
+            let (_, m, _) = n.convert_to_radix(Radix::Dec, Some(2), rm).unwrap();
+            assert_eq!(m, digits(d));
+        }
+
+        // the full conversion is not affected
+        for rdx in [Radix::Bin, Radix::Oct, Radix::Dec, Radix::Hex] {
+            let full = pi
+                .convert_to_radix(rdx, None, RoundingMode::ToEven)
+                .unwrap();
+            assert_eq!(
+                pi.convert_to_radix(rdx, Some(usize::MAX), RoundingMode::ToEven)
+                    .unwrap(),
+                full
+            );
+        }
+
+        assert_eq!(
+            pi.convert_to_radix(Radix::Dec, Some(0), RoundingMode::ToEven),
+            Err(Error::InvalidArgument)
+        );
+
+        // rounding to a number of bits is the same as set_precision
+        for _ in 0..100 {
+            let p = (random::<usize>() % 8 + 2) * WORD_BIT_SIZE;
+            let n = BigFloatNumber::random_normal(p, -100, 100).unwrap();
+            let k = (random::<usize>() % (p / WORD_BIT_SIZE - 1) + 1) * WORD_BIT_SIZE;
+
+            for rm in [RoundingMode::ToEven, RoundingMode::Up, RoundingMode::ToOdd] {
+                let mut n1 = n.clone().unwrap();
+                n1.set_precision(k, rm).unwrap();
+                let (s1, mut m1, e1) = n1.convert_to_radix(Radix::Bin, None, rm).unwrap();
+                m1.resize(k, 0);
+
+                assert_eq!(
+                    n.convert_to_radix(Radix::Bin, Some(k), rm).unwrap(),
+                    (s1, m1, e1)
+                );
+            }
+        }
+
+        // decimal digits bracket the number
+        let ulp = |k: usize| {
+            let mut u = vec![0; k];
+            u[k - 1] = 1;
+            u
+        };
+        for _ in 0..100 {
+            let p = (random::<usize>() % 8 + 1) * WORD_BIT_SIZE;
+            let n = BigFloatNumber::random_normal(p, -100, 100)
+                .unwrap()
+                .abs()
+                .unwrap();
+            // not more than the number of decimal digits which a p-bit mantissa always has
+            let k = random::<usize>() % (p * 3 / 10) + 1;
+            let p_ref = 4096;
+
+            let (_, m, e) = n
+                .convert_to_radix(Radix::Dec, Some(k), RoundingMode::ToZero)
+                .unwrap();
+            assert!(m.len() <= k);
+            let lo = BigFloatNumber::convert_from_radix(
+                Sign::Pos,
+                &m,
+                e,
+                Radix::Dec,
+                p_ref,
+                RoundingMode::None,
+            )
+            .unwrap();
+            let d = BigFloatNumber::convert_from_radix(
+                Sign::Pos,
+                &ulp(k),
+                e,
+                Radix::Dec,
+                p_ref,
+                RoundingMode::None,
+            )
+            .unwrap();
+            let hi = lo.add(&d, p_ref, RoundingMode::None).unwrap();
+            assert!(lo.cmp(&n) <= 0 && n.cmp(&hi) < 0);
+
+            let (_, m, e) = n
+                .convert_to_radix(Radix::Dec, Some(k), RoundingMode::ToEven)
+                .unwrap();
+            let v = BigFloatNumber::convert_from_radix(
+                Sign::Pos,
+                &m,
+                e,
+                Radix::Dec,
+                p_ref,
+                RoundingMode::None,
+            )
+            .unwrap();
+            let mut half = d.clone().unwrap();
+            half.set_exponent(half.exponent() - 1);
+            let err = v.sub(&n, p_ref, RoundingMode::None).unwrap();
+            assert!(err.abs_cmp(&half) <= 0);
+        }
+
+        // only the leading digits of a number with large precision are computed
+        let n = ONE
+            .div(
+                &BigFloatNumber::from_word(3, 64).unwrap(),
+                200_000,
+                RoundingMode::ToEven,
+            )
+            .unwrap();
+        let (_, m, e) = n
+            .convert_to_radix(Radix::Dec, Some(30), RoundingMode::ToEven)
+            .unwrap();
+        assert_eq!((m, e), (vec![3; 30], 0));
+    }
+
     fn random_radix() -> Radix {
         match random::<usize>() % 4 {
             0 => Radix::Bin,
@@ -1,9 +1,22 @@
 //! Context is used in expressions returning `BigFloat`.
 
+use crate::common::util::round_p;
 use crate::BigFloat;
+use crate::BigFloatPool;
 use crate::Consts;
 use crate::Error;
+use crate::ErrorPolicy;
+use crate::Exponent;
+use crate::MulThresholds;
+use crate::OpError;
 use crate::RoundingMode;
+use crate::EXPONENT_MAX;
+use crate::EXPONENT_MIN;
+use crate::INF_NEG;
+use crate::INF_POS;
+use crate::WORD_BIT_SIZE;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::Ordering;
 
 /// Context contains parameters, like rounding mode and precision, as well as constant values, and is used with `expr!` macro.
 #[derive(Debug)]
@@ -11,17 +24,72 @@ pub struct Context {
     cc: Consts,
     p: usize,
     rm: RoundingMode,
+    emin: Exponent,
+    emax: Exponent,
+    ep: ErrorPolicy,
+    strict: bool,
+    inexact: AtomicBool,
+    pool: BigFloatPool,
+    binary64: bool,
+    mul_thresholds: MulThresholds,
 }
 
 impl Context {
     /// Create a new context.
+    /// The exponent range of the context is set to the range from EXPONENT_MIN to EXPONENT_MAX,
+    /// the error handling policy is set to `ErrorPolicy::NaN`, and the strict mode is disabled.
     pub fn new(p: usize, rm: RoundingMode, cc: Consts) -> Self {
-        Context { cc, p, rm }
+        Context {
+            cc,
+            p,
+            rm,
+            emin: EXPONENT_MIN,
+            emax: EXPONENT_MAX,
+            ep: ErrorPolicy::NaN,
+            strict: false,
+            inexact: AtomicBool::new(false),
+            pool: BigFloatPool::new(),
+            binary64: false,
+            mul_thresholds: MulThresholds::default(),
+        }
+    }
+
+    /// Create a new context which emulates the IEEE 754 binary64 format, i.e. `f64`.
+    /// The precision of the context is set to 53 bits, and the rounding mode is set to `RoundingMode::ToEven`.
+    /// Only the operations [Context::add], [Context::sub], [Context::mul], [Context::div], [Context::sqrt], [Context::mul_add],
+    /// and [Context::round], and their checked variants, emulate `f64`: they return the same values as the
+    /// corresponding operations on `f64`, including subnormal results, overflow to infinity, and the sign of zero,
+    /// provided that the arguments are representable as `f64`.
+    /// The exponent range of `f64` is built into these operations, and the exponent range of the context is not changed.
+    /// Other uses of the context, like the `expr!` macro, compute results with the precision of 53 bits, but do not emulate `f64`.
+    /// The rounding mode of the context can be changed to emulate other IEEE 754 rounding-direction attributes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::ctx::Context;
+    /// let cc = Consts::new().expect("Constants cache allocated");
+    /// let ctx = Context::binary64(cc);
+    ///
+    /// let a = BigFloat::from_f64(0.1, 64);
+    /// let b = BigFloat::from_f64(0.2, 64);
+    ///
+    /// assert_eq!(ctx.add(&a, &b).to_f64(), 0.1 + 0.2);
+    ///
+    /// let tiny = BigFloat::from_f64(f64::MIN_POSITIVE, 64);
+    /// assert_eq!(ctx.div(&tiny, &BigFloat::from_f64(3.0, 64)).to_f64(), f64::MIN_POSITIVE / 3.0);
+    /// ```
+    pub fn binary64(cc: Consts) -> Self {
+        let mut ctx = Context::new(53, RoundingMode::ToEven, cc);
+        ctx.binary64 = true;
+        ctx
     }
 
     /// Destructures the context and returns its parts.
     pub fn to_raw_parts(self) -> (usize, RoundingMode, Consts) {
-        let Context { p, rm, cc } = self;
+        let Context { p, rm, cc, .. } = self;
         (p, rm, cc)
     }
 
@@ -40,6 +108,48 @@ impl Context {
         self.cc = cc;
     }
 
+    /// Sets the minimum exponent of the context.
+    /// Results of the arithmetic operations of the context, like [Context::add], and results of the `expr!` macro
+    /// with the exponent smaller than `emin` are rounded as subnormal numbers of the exponent range of the context.
+    /// See [Context::fit_exponent_range].
+    pub fn set_emin(&mut self, emin: Exponent) {
+        self.emin = emin;
+    }
+
+    /// Sets the maximum exponent of the context.
+    /// Results of the arithmetic operations of the context, and results of the `expr!` macro
+    /// with the exponent greater than `emax` become infinite.
+    pub fn set_emax(&mut self, emax: Exponent) {
+        self.emax = emax;
+    }
+
+    /// Sets the error handling policy of the context.
+    /// The policy is applied to the results of the arithmetic operations of the context, like [Context::add]:
+    /// with `ErrorPolicy::Panic` the operations panic on error, and with `ErrorPolicy::Result`
+    /// the checked variants of the operations, like [Context::try_add], return the error as `Err`. See [Context::check].
+    pub fn set_error_policy(&mut self, ep: ErrorPolicy) {
+        self.ep = ep;
+    }
+
+    /// Enables or disables the strict mode of the context.
+    /// In the strict mode, the arithmetic operations of the context, like [Context::add], and [Context::check]
+    /// treat inexact results as if they hold `Error::Inexact`: the operations return NaN with `Error::Inexact`,
+    /// or panic if the error handling policy is `ErrorPolicy::Panic`.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Sets the thresholds for switching between multiplication algorithms used by [Context::mul].
+    /// Values of `toom2`, `toom3`, and `fft` smaller than `MulThresholds::MIN` are replaced with `MulThresholds::MIN`.
+    pub fn set_mul_thresholds(&mut self, t: MulThresholds) {
+        self.mul_thresholds = t.clamped();
+    }
+
+    /// Clears the sticky inexact flag of the context.
+    pub fn clear_inexact(&self) {
+        self.inexact.store(false, Ordering::Relaxed);
+    }
+
     /// Returns the precision of the context.
     pub fn precision(&self) -> usize {
         self.p
@@ -55,6 +165,213 @@ impl Context {
         &mut self.cc
     }
 
+    /// Returns a mutable reference to the pool of numbers of the context.
+    /// The pool of a new or cloned context is empty.
+    pub fn pool(&mut self) -> &mut BigFloatPool {
+        &mut self.pool
+    }
+
+    /// Returns the minimum exponent of the context.
+    pub fn emin(&self) -> Exponent {
+        self.emin
+    }
+
+    /// Returns the maximum exponent of the context.
+    pub fn emax(&self) -> Exponent {
+        self.emax
+    }
+
+    /// Returns the error handling policy of the context.
+    pub fn error_policy(&self) -> ErrorPolicy {
+        self.ep
+    }
+
+    /// Returns true if the strict mode of the context is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns the thresholds for switching between multiplication algorithms used by [Context::mul].
+    pub fn mul_thresholds(&self) -> MulThresholds {
+        self.mul_thresholds
+    }
+
+    /// Returns true if the context emulates the IEEE 754 binary64 format. See [Context::binary64].
+    pub fn is_binary64(&self) -> bool {
+        self.binary64
+    }
+
+    /// Returns the sticky inexact flag of the context.
+    /// The flag is set when an arithmetic operation of the context, like [Context::add], gives an inexact result,
+    /// or when [Context::check] is given an inexact number, and stays set until [Context::clear_inexact] is called.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// # use astro_float_num::ctx::Context;
+    /// let cc = Consts::new().expect("Constants cache allocated");
+    /// let ctx = Context::new(128, RoundingMode::ToEven, cc);
+    ///
+    /// let one = BigFloat::from_word(1, 128);
+    /// let three = BigFloat::from_word(3, 128);
+    ///
+    /// ctx.add(&one, &three);
+    /// assert!(!ctx.inexact());
+    ///
+    /// ctx.div(&one, &three);
+    /// ctx.add(&one, &three);
+    /// assert!(ctx.inexact());
+    ///
+    /// ctx.clear_inexact();
+    /// ctx.check(one.div(&three, 128, RoundingMode::ToEven)).unwrap();
+    /// assert!(ctx.inexact());
+    /// ```
+    pub fn inexact(&self) -> bool {
+        self.inexact.load(Ordering::Relaxed)
+    }
+
+    /// Checks `x` for an error according to the error handling policy of the context.
+    /// With `ErrorPolicy::NaN`, `x` is returned as is.
+    /// With `ErrorPolicy::Result`, the error is returned if `x` is NaN which holds an error.
+    /// With `ErrorPolicy::Panic`, the function panics if `x` is NaN which holds an error.
+    /// If `x` is inexact, the sticky inexact flag of the context is set, and in the strict mode `x` is treated as an error `Error::Inexact`:
+    /// NaN is returned with `ErrorPolicy::NaN`, the error is returned with `ErrorPolicy::Result`, and the function panics with `ErrorPolicy::Panic`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory during computation of `x`.
+    ///  - InvalidArgument: invalid argument was used in computation of `x`.
+    ///  - Inexact: `x` is inexact, and the strict mode is enabled.
+    ///
+    /// ## Panics
+    ///
+    /// The function panics if the error handling policy of the context is `ErrorPolicy::Panic`, and `x` holds an error.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::Error;
+    /// # use astro_float_num::ErrorPolicy;
+    /// # use astro_float_num::RoundingMode;
+    /// # use astro_float_num::ctx::Context;
+    /// let cc = Consts::new().expect("Constants cache allocated");
+    /// let mut ctx = Context::new(128, RoundingMode::ToEven, cc);
+    ///
+    /// // invalid precision
+    /// let n = BigFloat::from_word(1, usize::MAX);
+    ///
+    /// assert!(ctx.check(n.clone()).expect("NaN is returned").is_nan());
+    ///
+    /// ctx.set_error_policy(ErrorPolicy::Result);
+    ///
+    /// assert_eq!(ctx.check(n).unwrap_err(), Error::InvalidArgument);
+    ///
+    /// // inexact result in the strict mode
+    /// ctx.set_strict(true);
+    /// let n = BigFloat::from_word(1, 128).div(&BigFloat::from_word(3, 128), 128, RoundingMode::ToEven);
+    ///
+    /// assert_eq!(ctx.check(n).unwrap_err(), Error::Inexact);
+    /// ```
+    pub fn check(&self, x: BigFloat) -> Result<BigFloat, Error> {
+        let err = if let Some(err) = x.err() {
+            Some(err)
+        } else if x.inexact() {
+            self.inexact.store(true, Ordering::Relaxed);
+
+            if self.strict {
+                Some(Error::Inexact)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        match (self.ep, err) {
+            (ErrorPolicy::Result, Some(err)) => Err(err),
+            (ErrorPolicy::Panic, Some(err)) => panic!("{}", err),
+            (ErrorPolicy::NaN, Some(Error::Inexact)) => Ok(BigFloat::nan(Some(Error::Inexact))),
+            _ => Ok(x),
+        }
+    }
+
+    /// Same as [Context::check], but the error is returned together with the name of the operation `op`
+    /// which computed `x`, and the precision of the context.
+    /// If the result has overflowed, the error also includes the maximum exponent of the context.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory during computation of `x`.
+    ///  - InvalidArgument: invalid argument was used in computation of `x`.
+    ///  - ExponentOverflow: the result of computation of `x` has overflowed.
+    ///
+    /// ## Panics
+    ///
+    /// The function panics if the error handling policy of the context is `ErrorPolicy::Panic`, and `x` holds an error.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::Error;
+    /// # use astro_float_num::ErrorPolicy;
+    /// # use astro_float_num::RoundingMode;
+    /// # use astro_float_num::ctx::Context;
+    /// let cc = Consts::new().expect("Constants cache allocated");
+    /// let mut ctx = Context::new(128, RoundingMode::ToEven, cc);
+    /// ctx.set_error_policy(ErrorPolicy::Result);
+    ///
+    /// let n = BigFloat::from_i8(-1, 128);
+    ///
+    /// let err = ctx.check_op("sqrt", n.sqrt(128, RoundingMode::ToEven)).unwrap_err();
+    ///
+    /// assert_eq!(err.kind(), Error::InvalidArgument);
+    /// assert_eq!(err.to_string(), "sqrt: invalid argument, precision 128");
+    /// ```
+    pub fn check_op(&self, op: &'static str, x: BigFloat) -> Result<BigFloat, OpError> {
+        self.check(x).map_err(|err| {
+            let err = err.with_op(op).with_precision(self.p);
+
+            if let Error::ExponentOverflow(_) = err.kind() {
+                err.with_exponent(self.emax)
+            } else {
+                err
+            }
+        })
+    }
+
+    /// Rounds `x` using the rounding mode of the context so that it fits the exponent range of the context.
+    /// Numbers with the exponent smaller than the minimum exponent of the context become subnormal or zero,
+    /// and numbers with the exponent greater than the maximum exponent of the context become infinite.
+    /// See [BigFloat::fit_exponent_range] for details.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// # use astro_float_num::ctx::Context;
+    /// let cc = Consts::new().expect("Constants cache allocated");
+    /// let mut ctx = Context::new(64, RoundingMode::ToEven, cc);
+    /// ctx.set_emin(-1021);
+    /// ctx.set_emax(1024);
+    ///
+    /// let mut n = BigFloat::from_word(1, 64);
+    /// n.set_exponent(1025);
+    ///
+    /// assert!(ctx.fit_exponent_range(&n).is_inf_pos());
+    /// ```
+    pub fn fit_exponent_range(&self, x: &BigFloat) -> BigFloat {
+        x.fit_exponent_range(self.emin, self.emax, self.rm)
+    }
+
     /// Returns the value of the pi number.
     pub fn const_pi(&mut self) -> BigFloat {
         self.cc.pi(self.p, self.rm)
@@ -85,9 +402,324 @@ impl Context {
         Ok(Context {
             p: self.p,
             rm: self.rm,
+            emin: self.emin,
+            emax: self.emax,
+            ep: self.ep,
+            strict: self.strict,
+            inexact: AtomicBool::new(self.inexact()),
+            pool: BigFloatPool::new(),
+            binary64: self.binary64,
+            mul_thresholds: self.mul_thresholds,
             cc,
         })
     }
+
+    /// Returns the sum of `a` and `b` computed with the precision and the rounding mode of the context.
+    pub fn add(&self, a: &BigFloat, b: &BigFloat) -> BigFloat {
+        self.op([a, b], |[a, b], p, rm| {
+            self.sum_zero_sign(a.add(b, p, rm), a.is_negative(), b.is_negative())
+        })
+    }
+
+    /// Returns the difference of `a` and `b` computed with the precision and the rounding mode of the context.
+    pub fn sub(&self, a: &BigFloat, b: &BigFloat) -> BigFloat {
+        self.op([a, b], |[a, b], p, rm| {
+            self.sum_zero_sign(a.sub(b, p, rm), a.is_negative(), b.is_positive())
+        })
+    }
+
+    /// Returns the product of `a` and `b` computed with the precision and the rounding mode of the context.
+    /// The mantissas are multiplied using the multiplication thresholds of the context.
+    pub fn mul(&self, a: &BigFloat, b: &BigFloat) -> BigFloat {
+        self.op([a, b], |[a, b], p, rm| {
+            self.zero_sign(
+                a.mul_with_thresholds(b, p, rm, &self.mul_thresholds),
+                a.is_negative() != b.is_negative(),
+            )
+        })
+    }
+
+    /// Returns the quotient of `a` and `b` computed with the precision and the rounding mode of the context.
+    pub fn div(&self, a: &BigFloat, b: &BigFloat) -> BigFloat {
+        self.op([a, b], |[a, b], p, rm| {
+            self.zero_sign(a.div(b, p, rm), a.is_negative() != b.is_negative())
+        })
+    }
+
+    /// Returns the square root of `a` computed with the precision and the rounding mode of the context.
+    pub fn sqrt(&self, a: &BigFloat) -> BigFloat {
+        self.op([a], |[a], p, rm| {
+            self.zero_sign(a.sqrt(p, rm), a.is_negative())
+        })
+    }
+
+    /// Returns `a*b + c` computed with the precision and the rounding mode of the context with a single rounding.
+    pub fn mul_add(&self, a: &BigFloat, b: &BigFloat, c: &BigFloat) -> BigFloat {
+        self.op([a, b, c], |[a, b, c], p, rm| {
+            let p_ab = match (a.mantissa_max_bit_len(), b.mantissa_max_bit_len()) {
+                (Some(pa), Some(pb)) => pa + pb,
+                _ => WORD_BIT_SIZE,
+            };
+
+            // the product is exact
+            let ab = a.mul(b, p_ab, RoundingMode::None);
+            let ab_neg = a.is_negative() != b.is_negative();
+
+            self.sum_zero_sign(ab.add(c, p, rm), ab_neg, c.is_negative())
+        })
+    }
+
+    /// Rounds `a` to the precision of the context using the rounding mode of the context.
+    /// If the context emulates the binary64 format, the result is the value of `a` converted to `f64`.
+    pub fn round(&self, a: &BigFloat) -> BigFloat {
+        self.op([a], |[a], p, rm| Self::round_to_precision(a.clone(), p, rm))
+    }
+
+    /// Same as [Context::add], but the error is returned as described in [Context::check_op].
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision of the context is incorrect.
+    ///  - Inexact: the result is inexact, and the strict mode is enabled.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::Error;
+    /// # use astro_float_num::ErrorPolicy;
+    /// # use astro_float_num::RoundingMode;
+    /// # use astro_float_num::ctx::Context;
+    /// let cc = Consts::new().expect("Constants cache allocated");
+    /// let mut ctx = Context::new(usize::MAX, RoundingMode::ToEven, cc);
+    /// ctx.set_error_policy(ErrorPolicy::Result);
+    ///
+    /// let one = BigFloat::from_word(1, 64);
+    /// let err = ctx.try_add(&one, &one).unwrap_err();
+    ///
+    /// assert_eq!(err.kind(), Error::InvalidArgument);
+    /// assert_eq!(err.op(), "add");
+    /// ```
+    pub fn try_add(&self, a: &BigFloat, b: &BigFloat) -> Result<BigFloat, OpError> {
+        self.check_op("add", self.add(a, b))
+    }
+
+    /// Same as [Context::sub], but the error is returned as described in [Context::check_op].
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision of the context is incorrect.
+    ///  - Inexact: the result is inexact, and the strict mode is enabled.
+    pub fn try_sub(&self, a: &BigFloat, b: &BigFloat) -> Result<BigFloat, OpError> {
+        self.check_op("sub", self.sub(a, b))
+    }
+
+    /// Same as [Context::mul], but the error is returned as described in [Context::check_op].
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision of the context is incorrect.
+    ///  - Inexact: the result is inexact, and the strict mode is enabled.
+    pub fn try_mul(&self, a: &BigFloat, b: &BigFloat) -> Result<BigFloat, OpError> {
+        self.check_op("mul", self.mul(a, b))
+    }
+
+    /// Same as [Context::div], but the error is returned as described in [Context::check_op].
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision of the context is incorrect.
+    ///  - Inexact: the result is inexact, and the strict mode is enabled.
+    pub fn try_div(&self, a: &BigFloat, b: &BigFloat) -> Result<BigFloat, OpError> {
+        self.check_op("div", self.div(a, b))
+    }
+
+    /// Same as [Context::sqrt], but the error is returned as described in [Context::check_op].
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision of the context is incorrect, or `a` is negative.
+    ///  - Inexact: the result is inexact, and the strict mode is enabled.
+    pub fn try_sqrt(&self, a: &BigFloat) -> Result<BigFloat, OpError> {
+        self.check_op("sqrt", self.sqrt(a))
+    }
+
+    /// Same as [Context::mul_add], but the error is returned as described in [Context::check_op].
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision of the context is incorrect.
+    ///  - Inexact: the result is inexact, and the strict mode is enabled.
+    pub fn try_mul_add(
+        &self,
+        a: &BigFloat,
+        b: &BigFloat,
+        c: &BigFloat,
+    ) -> Result<BigFloat, OpError> {
+        self.check_op("mul_add", self.mul_add(a, b, c))
+    }
+
+    /// Same as [Context::round], but the error is returned as described in [Context::check_op].
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision of the context is incorrect.
+    ///  - Inexact: the result is inexact, and the strict mode is enabled.
+    pub fn try_round(&self, a: &BigFloat) -> Result<BigFloat, OpError> {
+        self.check_op("round", self.round(a))
+    }
+
+    // Computes the result of an operation using `compute`, and checks it according to the error handling policy of the context.
+    // With `ErrorPolicy::Result`, the error is returned as NaN, and it is returned as `Err` by the checked variant of the operation.
+    fn op<const N: usize>(
+        &self,
+        args: [&BigFloat; N],
+        f: impl FnOnce([&BigFloat; N], usize, RoundingMode) -> BigFloat,
+    ) -> BigFloat {
+        let x = self.compute(args, f);
+        self.check(x).unwrap_or_else(|err| BigFloat::nan(Some(err)))
+    }
+
+    // Computes the result of an operation on the arguments `args` using `f`,
+    // which is called with the arguments, a precision, and a rounding mode.
+    fn compute<const N: usize>(
+        &self,
+        args: [&BigFloat; N],
+        f: impl FnOnce([&BigFloat; N], usize, RoundingMode) -> BigFloat,
+    ) -> BigFloat {
+        if !self.binary64 && self.emin == EXPONENT_MIN && self.emax == EXPONENT_MAX {
+            return f(args, self.p, self.rm);
+        }
+
+        // The result is truncated to the working precision, and if it is inexact, a sticky bit is added below the last bit.
+        // Rounding of such number to the precision of the context gives the same result as rounding of the exact result.
+        // The arguments are marked as exact, so that the inexact flag of the result tells if the truncation was exact.
+        let p_wrk = round_p(self.p) + WORD_BIT_SIZE;
+        let inexact = args.iter().any(|x| x.inexact());
+        let exact = args.map(|x| {
+            let mut x = x.clone();
+            x.set_inexact(false);
+            x
+        });
+
+        let x = f(
+            core::array::from_fn(|i| &exact[i]),
+            p_wrk,
+            RoundingMode::ToZero,
+        );
+
+        if x.is_nan() {
+            return x;
+        }
+
+        let inexact = inexact || x.inexact();
+        let x = Self::add_sticky(x);
+
+        let mut ret = if self.binary64 {
+            let f = x.to_f64_round(self.rm);
+            let mut ret = BigFloat::from_f64(f, 64);
+
+            // from_f64 does not keep the sign of zero
+            if f == 0.0 && f.is_sign_negative() {
+                ret = ret.neg();
+            }
+
+            ret
+        } else {
+            self.round_to_range(&x, p_wrk)
+        };
+
+        ret.set_inexact(inexact || ret != x);
+        ret
+    }
+
+    // Rounds `x` with a sticky bit to the precision and the exponent range of the context.
+    fn round_to_range(&self, x: &BigFloat, p_wrk: usize) -> BigFloat {
+        let p = round_p(self.p);
+
+        match x.exponent() {
+            Some(e) if e < self.emin && !x.is_zero() => {
+                // Rounding of c + x to p bits, where c = 2^(emin - 1) has the sign of x,
+                // gives c plus the multiple of 2^(emin - p) nearest to x.
+                let mut c = BigFloat::from_word(1, p);
+                c.set_exponent(self.emin);
+
+                if x.is_negative() {
+                    c = c.neg();
+                }
+
+                let mut x = x.clone();
+                x.set_inexact(false);
+
+                let w = Self::add_sticky(x.add(&c, p_wrk, RoundingMode::ToZero));
+                let w = Self::round_to_precision(w, p, self.rm);
+
+                w.sub(&c, p, RoundingMode::None)
+            }
+            _ => Self::round_to_precision(x.clone(), p, self.rm)
+                .fit_exponent_range(self.emin, self.emax, self.rm),
+        }
+    }
+
+    // Rounds `x` to precision `p` using rounding mode `rm`.
+    // Overflow results in infinity like in the arithmetic operations.
+    fn round_to_precision(mut x: BigFloat, p: usize, rm: RoundingMode) -> BigFloat {
+        match x.set_precision(p, rm) {
+            Ok(()) => x,
+            Err(Error::ExponentOverflow(s)) => {
+                if s.is_positive() {
+                    INF_POS
+                } else {
+                    INF_NEG
+                }
+            }
+            Err(err) => BigFloat::nan(Some(err)),
+        }
+    }
+
+    // Adds a sticky bit below the last bit of the mantissa of truncated `x` if `x` is inexact.
+    fn add_sticky(x: BigFloat) -> BigFloat {
+        if x.inexact() && !x.is_zero() && !x.is_inf() {
+            let p = x.mantissa_max_bit_len().unwrap_or(0);
+            let e = x.exponent().map(|e| e as isize - p as isize - 1);
+
+            if let Some(e) = e.filter(|&e| e >= EXPONENT_MIN as isize) {
+                let mut sticky = BigFloat::from_word(1, WORD_BIT_SIZE);
+                sticky.set_exponent(e as Exponent);
+
+                if x.is_negative() {
+                    sticky = sticky.neg();
+                }
+
+                return x.add(&sticky, p + WORD_BIT_SIZE, RoundingMode::None);
+            }
+        }
+
+        x
+    }
+
+    // Sets the sign of an exact zero sum of numbers with signs `a_neg` and `b_neg` as required by IEEE 754 in the binary64 mode.
+    fn sum_zero_sign(&self, x: BigFloat, a_neg: bool, b_neg: bool) -> BigFloat {
+        let neg = if a_neg == b_neg { a_neg } else { self.rm == RoundingMode::Down };
+        self.zero_sign(x, neg)
+    }
+
+    // Makes zero `x` negative if `neg` is true, or positive otherwise, in the binary64 mode.
+    fn zero_sign(&self, mut x: BigFloat, neg: bool) -> BigFloat {
+        if self.binary64 && x.is_zero() && neg != x.is_negative() {
+            x = x.neg();
+        }
+
+        x
+    }
 }
 
 /// Represents a type that can be used as context in `expr!` macro.
@@ -120,6 +752,21 @@ pub trait Contextable {
     /// Returns a mutable reference to the constant cache of the context.
     fn consts(&mut self) -> &mut Consts;
 
+    /// Returns the minimum exponent of the context.
+    fn emin(&self) -> Exponent {
+        EXPONENT_MIN
+    }
+
+    /// Returns the maximum exponent of the context.
+    fn emax(&self) -> Exponent {
+        EXPONENT_MAX
+    }
+
+    /// Returns the error handling policy of the context.
+    fn error_policy(&self) -> ErrorPolicy {
+        ErrorPolicy::NaN
+    }
+
     /// Returns the value of the pi number.
     fn const_pi(&mut self) -> BigFloat;
 
@@ -180,6 +827,18 @@ impl Contextable for Context {
         Context::consts(self)
     }
 
+    fn emin(&self) -> Exponent {
+        Context::emin(self)
+    }
+
+    fn emax(&self) -> Exponent {
+        Context::emax(self)
+    }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        Context::error_policy(self)
+    }
+
     fn const_pi(&mut self) -> BigFloat {
         Context::const_pi(self)
     }
@@ -196,3 +855,467 @@ impl Contextable for Context {
         Context::const_ln10(self)
     }
 }
+
+/// Required accuracy of the result of `compute_with_accuracy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Accuracy {
+    /// The absolute error of the result does not exceed `2^e`.
+    Abs(isize),
+
+    /// The relative error of the result does not exceed `2^(-n)`.
+    Rel(usize),
+}
+
+/// Computes a value using the function `f` with the accuracy `acc`, and returns the result rounded using the rounding mode `rm`.
+///
+/// `f` is called with the working precision, rounding mode `RoundingMode::None`, and the constants cache `cc`.
+/// The working precision is increased on each call, and the result is returned when two successive results differ
+/// by no more than half of the required error, or when `f` returns an exact result, or Inf, or NaN.
+/// The precision of the result is chosen so that rounding adds no more than half of the required error.
+/// Similarly to the `expr!` macro, the error of the result is estimated from the results of `f`,
+/// and thus `f` is expected to return more accurate results with larger working precision.
+///
+/// Inexact zero results of `f` are never considered accurate, because they give no information about the magnitude of the value.
+/// The function returns NaN with `Error::Inexact` if the working precision exceeds `p_max`
+/// before the required accuracy is reached.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::{BigFloat, Consts, RoundingMode};
+/// # use astro_float_num::ctx::{compute_with_accuracy, Accuracy};
+/// let mut cc = Consts::new().expect("Constants cache initialized.");
+///
+/// // sin(pi/6) = 0.5 with the absolute error not exceeding 2^(-200)
+/// let ret = compute_with_accuracy(
+///     |p, rm, cc| {
+///         let pi = cc.pi(p, rm);
+///         let x = pi.div(&BigFloat::from_word(6, p), p, rm);
+///         x.sin(p, rm, cc)
+///     },
+///     Accuracy::Abs(-200),
+///     RoundingMode::ToEven,
+///     &mut cc,
+///     10000,
+/// );
+///
+/// let err = ret.sub(&BigFloat::from_f64(0.5, 64), 1024, RoundingMode::None);
+/// assert!(err.is_zero() || err.exponent().unwrap() <= -200);
+/// ```
+pub fn compute_with_accuracy<F>(
+    mut f: F,
+    acc: Accuracy,
+    rm: RoundingMode,
+    cc: &mut Consts,
+    p_max: usize,
+) -> BigFloat
+where
+    F: FnMut(usize, RoundingMode, &mut Consts) -> BigFloat,
+{
+    let mut p_inc = WORD_BIT_SIZE;
+    let mut p_wrk = match acc {
+        Accuracy::Abs(_) => 2 * WORD_BIT_SIZE,
+        Accuracy::Rel(n) => round_p(n.saturating_add(2)).saturating_add(p_inc),
+    };
+
+    let mut prev: Option<BigFloat> = None;
+
+    while p_wrk <= p_max {
+        let ret = f(p_wrk, RoundingMode::None, cc);
+
+        if ret.is_nan() || ret.is_inf() {
+            return ret;
+        }
+
+        if !ret.inexact() {
+            return round_to_accuracy(ret, acc, rm);
+        }
+
+        if let Some(prev) = prev {
+            let d = ret.sub(&prev, p_wrk, RoundingMode::FromZero);
+
+            // inexact zero gives no information about the magnitude of the result
+            let stable = if ret.is_zero() {
+                false
+            } else if d.is_zero() {
+                true
+            } else {
+                // the results differ by less than 2^(d.exponent()), and ret >= 2^(ret.exponent() - 1)
+                match (d.exponent(), ret.exponent(), acc) {
+                    (Some(de), _, Accuracy::Abs(e)) => (de as isize) < e,
+                    (Some(de), Some(re), Accuracy::Rel(n)) => {
+                        (de as isize) < (re as isize).saturating_sub(n as isize).saturating_sub(1)
+                    }
+                    _ => false,
+                }
+            };
+
+            if stable {
+                return round_to_accuracy(ret, acc, rm);
+            }
+        }
+
+        prev = Some(ret);
+
+        p_wrk = p_wrk.saturating_add(p_inc);
+        p_inc = round_p(p_wrk / 5);
+    }
+
+    BigFloat::nan(Some(Error::Inexact))
+}
+
+// Rounds `x` to the precision for which rounding error does not exceed half of the error required by `acc`.
+fn round_to_accuracy(mut x: BigFloat, acc: Accuracy, rm: RoundingMode) -> BigFloat {
+    let p = match (acc, x.exponent()) {
+        (Accuracy::Rel(n), _) => n.saturating_add(2),
+        (Accuracy::Abs(e), Some(xe)) if !x.is_zero() => {
+            (xe as isize).saturating_sub(e).saturating_add(1).max(1) as usize
+        }
+        _ => 1,
+    };
+
+    if let Err(e) = x.set_precision(p, rm) {
+        return BigFloat::nan(Some(e));
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Sign;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_compute_with_accuracy() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+
+        // (1 + y) - 1 with y = 2^(-300) / 3 is zero for small working precision
+        let f = |p, rm, _: &mut Consts| {
+            let mut y = BigFloat::from_word(1, p).div(&BigFloat::from_word(3, p), p, rm);
+            y.set_exponent(y.exponent().unwrap() - 300);
+            BigFloat::from_word(1, p)
+                .add(&y, p, rm)
+                .sub(&BigFloat::from_word(1, p), p, rm)
+        };
+
+        let mut y = BigFloat::from_word(1, 1024).div(&BigFloat::from_word(3, 1024), 1024, rm);
+        y.set_exponent(y.exponent().unwrap() - 300);
+
+        let ret = compute_with_accuracy(f, Accuracy::Rel(100), rm, &mut cc, 10000);
+        let d = ret.sub(&y, 1024, RoundingMode::None);
+        assert!(d.exponent().unwrap() as isize <= y.exponent().unwrap() as isize - 101);
+        assert!(ret.precision().unwrap() <= 192);
+
+        let ret = compute_with_accuracy(f, Accuracy::Abs(-500), rm, &mut cc, 10000);
+        let d = ret.sub(&y, 1024, RoundingMode::None);
+        assert!(d.is_zero() || d.exponent().unwrap() <= -500);
+
+        // precision limit
+        let ret = compute_with_accuracy(f, Accuracy::Rel(100), rm, &mut cc, 256);
+        assert_eq!(ret.err(), Some(Error::Inexact));
+
+        // exact result
+        let ret = compute_with_accuracy(
+            |p, rm, _| BigFloat::from_word(3, p).mul(&BigFloat::from_word(5, p), p, rm),
+            Accuracy::Rel(2),
+            rm,
+            &mut cc,
+            256,
+        );
+        assert_eq!(ret, BigFloat::from_word(15, 64));
+        assert!(!ret.inexact());
+    }
+
+    #[test]
+    fn test_exponent_range() {
+        let mut ctx = Context::new(64, RoundingMode::ToEven, Consts::new().unwrap());
+        ctx.set_emin(-100);
+        ctx.set_emax(100);
+
+        let pow2 = |e| {
+            let mut x = BigFloat::from_word(1, 128);
+            x.set_exponent(e);
+            x
+        };
+
+        // 2^(-165) is half of the smallest positive subnormal number 2^(-164)
+        let half = pow2(-164);
+        let one = BigFloat::from_word(1, 64);
+        assert!(ctx.mul(&half, &one).is_zero());
+        assert!(ctx.mul(&half, &one).inexact());
+
+        ctx.set_rounding_mode(RoundingMode::Up);
+        assert_eq!(ctx.mul(&half, &one), pow2(-163));
+        ctx.set_rounding_mode(RoundingMode::ToEven);
+
+        // slightly above half is rounded once, and not to the tie
+        let a = BigFloat::from_word(1, 128).add(&pow2(-68), 128, RoundingMode::None);
+        assert_eq!(ctx.mul(&a, &half), pow2(-163));
+
+        // subnormal result
+        let x = ctx.div(&pow2(-110), &BigFloat::from_word(3, 64));
+        let y =
+            BigFloat::from_word(1, 256).div(&BigFloat::from_word(3, 256), 256, RoundingMode::None);
+        let mut y = y.mul(&pow2(-110), 256, RoundingMode::None);
+        y.set_precision(64 - 12, RoundingMode::ToEven).unwrap();
+        assert_eq!(x, y);
+        assert!(x.inexact());
+
+        // overflow
+        assert!(ctx
+            .mul(&pow2(100), &BigFloat::from_word(2, 64))
+            .is_inf_pos());
+        assert!(ctx.add(&pow2(100), &pow2(100)).is_inf_pos());
+
+        // numbers in range
+        let b = BigFloat::from_word(3, 64);
+        assert_eq!(ctx.div(&one, &b), one.div(&b, 64, RoundingMode::ToEven));
+        assert!(!ctx.add(&one, &b).inexact());
+    }
+
+    #[test]
+    fn test_error_policy() {
+        let mut ctx = Context::new(128, RoundingMode::ToEven, Consts::new().unwrap());
+        let one = BigFloat::from_word(1, 64);
+        let three = BigFloat::from_word(3, 64);
+
+        assert!(ctx.try_sqrt(&one.neg()).unwrap().is_nan());
+
+        ctx.set_error_policy(ErrorPolicy::Result);
+        assert!(ctx.sqrt(&one.neg()).is_nan());
+
+        let err = ctx.try_sqrt(&one.neg()).unwrap_err();
+        assert_eq!(err.kind(), Error::InvalidArgument);
+        assert_eq!(err.op(), "sqrt");
+        assert_eq!(err.precision(), Some(128));
+        assert_eq!(err.to_string(), "sqrt: invalid argument, precision 128");
+
+        #[cfg(feature = "std")]
+        assert!(std::error::Error::source(&err).is_none());
+
+        assert_eq!(
+            ctx.try_div(&one, &three).unwrap(),
+            one.div(&three, 128, RoundingMode::ToEven)
+        );
+
+        // rounding of the maximum value upwards overflows to infinity
+        let mut ctx_up = Context::new(64, RoundingMode::Up, Consts::new().unwrap());
+        ctx_up.set_error_policy(ErrorPolicy::Result);
+
+        assert!(ctx_up
+            .try_round(&BigFloat::max_value(128))
+            .unwrap()
+            .is_inf_pos());
+
+        let overflow = BigFloat::nan(Some(Error::ExponentOverflow(Sign::Pos)));
+        let err = ctx_up.check_op("round", overflow).unwrap_err();
+        assert_eq!(err.kind(), Error::ExponentOverflow(Sign::Pos));
+        assert_eq!(err.exponent(), Some(EXPONENT_MAX));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "round: positive overflow, precision 64, exponent {}",
+                EXPONENT_MAX
+            )
+        );
+        assert_eq!(ctx.try_sqrt(&one.neg()).unwrap_err().exponent(), None);
+
+        ctx.set_error_policy(ErrorPolicy::Panic);
+        assert!(ctx.add(&one, &three).is_positive());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_error_policy_panic() {
+        let mut ctx = Context::new(128, RoundingMode::ToEven, Consts::new().unwrap());
+        ctx.set_error_policy(ErrorPolicy::Panic);
+        ctx.sqrt(&BigFloat::from_word(1, 64).neg());
+    }
+
+    #[test]
+    fn test_strict() {
+        let mut ctx = Context::new(128, RoundingMode::ToEven, Consts::new().unwrap());
+        let one = BigFloat::from_word(1, 64);
+        let three = BigFloat::from_word(3, 64);
+
+        assert!(!ctx.div(&one, &three).is_nan());
+        assert!(ctx.inexact());
+
+        ctx.clear_inexact();
+        ctx.set_strict(true);
+
+        assert_eq!(ctx.add(&one, &three), BigFloat::from_word(4, 64));
+        assert!(!ctx.inexact());
+
+        assert_eq!(ctx.div(&one, &three).err(), Some(Error::Inexact));
+        assert!(ctx.inexact());
+
+        ctx.set_error_policy(ErrorPolicy::Result);
+        assert_eq!(ctx.try_sqrt(&three).unwrap_err().kind(), Error::Inexact);
+        assert!(ctx.try_mul(&one, &three).is_ok());
+
+        // the exponent range of the context
+        ctx.set_emin(-100);
+        let mut tiny = BigFloat::from_word(3, 64);
+        tiny.set_exponent(-226);
+        assert!(ctx.try_mul(&tiny, &one).is_ok());
+        assert_eq!(
+            ctx.try_div(&tiny, &BigFloat::from_word(4, 64))
+                .unwrap_err()
+                .kind(),
+            Error::Inexact
+        );
+
+        // errors take precedence over the inexact flag
+        ctx.clear_inexact();
+        let nan = BigFloat::nan(Some(Error::DivisionByZero));
+        assert_eq!(ctx.check(nan).unwrap_err(), Error::DivisionByZero);
+        assert!(!ctx.inexact());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_context_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Context>();
+
+        // the sticky inexact flag is shared by the threads using the context
+        let ctx = Context::new(128, RoundingMode::ToEven, Consts::new().unwrap());
+        let one = BigFloat::from_word(1, 64);
+        std::thread::scope(|s| {
+            s.spawn(|| ctx.div(&one, &BigFloat::from_word(3, 64)));
+        });
+        assert!(ctx.inexact());
+    }
+
+    #[test]
+    fn test_binary64() {
+        let cc = Consts::new().unwrap();
+        let mut ctx = Context::binary64(cc);
+        assert_eq!((ctx.emin(), ctx.emax()), (EXPONENT_MIN, EXPONENT_MAX));
+
+        let same = |x: BigFloat, f: f64, op: &str| {
+            if f.is_nan() {
+                assert!(x.is_nan());
+            } else {
+                assert_eq!(
+                    x.to_f64_round(RoundingMode::None).to_bits(),
+                    f.to_bits(),
+                    "{} {:?}",
+                    op,
+                    x
+                );
+                assert_eq!(x.is_zero(), f == 0.0);
+                assert_eq!(x.is_negative(), f.is_sign_negative());
+            }
+        };
+
+        // from_f64 does not keep the sign of zero
+        let from_f64 = |f: f64| {
+            let x = BigFloat::from_f64(f, 64);
+            if f.is_sign_negative() && f == 0.0 {
+                x.neg()
+            } else {
+                x
+            }
+        };
+
+        let rand_f64 = || {
+            let f = f64::from_bits(rand::random::<u64>());
+            match rand::random::<u8>() % 4 {
+                0 => f64::from_bits(rand::random::<u64>() & 0x801F_FFFF_FFFF_FFFF),
+                1 => f64::from_bits(rand::random::<u64>() >> 12 | 0x3FF0_0000_0000_0000),
+                _ if f.is_finite() => f,
+                _ => 1.5,
+            }
+        };
+
+        for _ in 0..10000 {
+            let (a, b, c) = (rand_f64(), rand_f64(), rand_f64());
+            let (x, y, z) = (from_f64(a), from_f64(b), from_f64(c));
+
+            same(ctx.add(&x, &y), a + b, "add");
+            same(ctx.sub(&x, &y), a - b, "sub");
+            same(ctx.mul(&x, &y), a * b, "mul");
+            same(ctx.div(&x, &y), a / b, "div");
+            same(ctx.sqrt(&x), a.sqrt(), "sqrt");
+            same(ctx.mul_add(&x, &y, &z), a.mul_add(b, c), "mul_add");
+        }
+
+        // special values
+        let vals = [0.0, -0.0, 1.0, -1.0, f64::MAX, f64::MIN_POSITIVE, 5e-324, -5e-324];
+        for a in vals {
+            for b in vals {
+                let (x, y) = (from_f64(a), from_f64(b));
+                same(ctx.add(&x, &y), a + b, "add");
+                same(ctx.sub(&x, &y), a - b, "sub");
+                same(ctx.mul(&x, &y), a * b, "mul");
+                same(ctx.div(&x, &y), a / b, "div");
+                same(ctx.sqrt(&x), a.sqrt(), "sqrt");
+            }
+        }
+
+        // inexact flag and rounding
+        let third = ctx.div(&from_f64(1.0), &from_f64(3.0));
+        assert!(third.inexact());
+        same(ctx.add(&third, &third), 1.0 / 3.0 + 1.0 / 3.0, "add");
+        assert!(!ctx.add(&from_f64(1.0), &from_f64(0.5)).inexact());
+        assert_eq!(
+            ctx.round(&BigFloat::from_word(1, 128).div(
+                &BigFloat::from_word(3, 128),
+                128,
+                RoundingMode::ToEven
+            )),
+            third
+        );
+
+        // the sign of exact zero rounded down
+        ctx.set_rounding_mode(RoundingMode::Down);
+        let one = BigFloat::from_f64(1.0, 64);
+        assert!(ctx.sub(&one, &one).is_negative());
+        assert!(ctx.round(&third).cmp(&third).unwrap() == 0);
+
+        // other contexts are not affected
+        let ctx = Context::new(128, RoundingMode::ToEven, Consts::new().unwrap());
+        assert!(!ctx.is_binary64());
+        assert_eq!(
+            ctx.div(&one, &BigFloat::from_word(3, 64)),
+            one.div(&BigFloat::from_word(3, 64), 128, RoundingMode::ToEven)
+        );
+    }
+
+    #[test]
+    fn test_mul_thresholds() {
+        let p = 320 * WORD_BIT_SIZE;
+        let mut ctx = Context::new(p, RoundingMode::ToEven, Consts::new().unwrap());
+        assert_eq!(ctx.mul_thresholds(), MulThresholds::default());
+
+        let a = BigFloat::random_normal(p, -100, 100);
+        let b = BigFloat::random_normal(p / 2, -100, 100);
+        let expected = a.mul(&b, p, RoundingMode::ToEven);
+
+        // the thresholds force FFT multiplication
+        let min = MulThresholds::MIN;
+        ctx.set_mul_thresholds(MulThresholds {
+            toom2: 0,
+            toom3: min,
+            fft: min,
+            balancing: usize::MAX,
+        });
+        assert_eq!(ctx.mul_thresholds().toom2, min);
+        assert_eq!(ctx.mul(&a, &b), expected);
+        assert_eq!(ctx.clone().unwrap().mul_thresholds(), ctx.mul_thresholds());
+
+        // other contexts are not affected
+        let ctx2 = Context::new(p, RoundingMode::ToEven, Consts::new().unwrap());
+        assert_eq!(ctx2.mul_thresholds(), MulThresholds::default());
+    }
+}
@@ -5,50 +5,83 @@ use core::fmt::Display;
 #[cfg(feature = "std")]
 use std::collections::TryReserveError;
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::collections::TryReserveError;
 
 /// A word.
-#[cfg(not(target_arch = "x86"))]
+/// It is 64 bits wide, unless the target is x86 or the `word32` feature is enabled.
+#[cfg(not(word32))]
 pub type Word = u64;
 
 /// Doubled word.
-#[cfg(not(target_arch = "x86"))]
+#[cfg(not(word32))]
 pub type DoubleWord = u128;
 
 /// Word with sign.
-#[cfg(not(target_arch = "x86"))]
+#[cfg(not(word32))]
 pub type SignedWord = i128;
 
 /// A word.
-#[cfg(target_arch = "x86")]
+/// It is 32 bits wide on x86, or with the `word32` feature enabled.
+#[cfg(word32)]
 pub type Word = u32;
 
 /// Doubled word.
-#[cfg(target_arch = "x86")]
+#[cfg(word32)]
 pub type DoubleWord = u64;
 
 /// Word with sign.
-#[cfg(target_arch = "x86")]
+#[cfg(word32)]
 pub type SignedWord = i64;
 
 /// An exponent.
+#[cfg(not(feature = "exp64"))]
 pub type Exponent = i32;
 
+/// An exponent.
+#[cfg(feature = "exp64")]
+pub type Exponent = i64;
+
+#[cfg(all(feature = "exp64", not(target_pointer_width = "64")))]
+compile_error!("The exp64 feature requires a 64-bit target.");
+
+#[cfg(all(feature = "exp64", feature = "portable"))]
+compile_error!("The exp64 and portable features are mutually exclusive.");
+
 /// Maximum exponent value.
-#[cfg(not(any(target_arch = "x86", target_arch = "wasm32")))]
+#[cfg(not(any(
+    target_arch = "x86",
+    target_arch = "wasm32",
+    feature = "portable",
+    feature = "exp64"
+)))]
 pub const EXPONENT_MAX: Exponent = Exponent::MAX;
 
 /// Maximum exponent value.
-#[cfg(any(target_arch = "x86", target_arch = "wasm32"))]
+#[cfg(any(
+    target_arch = "x86",
+    target_arch = "wasm32",
+    feature = "portable",
+    feature = "exp64"
+))]
 pub const EXPONENT_MAX: Exponent = Exponent::MAX / 4;
 
 /// Minimum exponent value.
-#[cfg(not(any(target_arch = "x86", target_arch = "wasm32")))]
+#[cfg(not(any(
+    target_arch = "x86",
+    target_arch = "wasm32",
+    feature = "portable",
+    feature = "exp64"
+)))]
 pub const EXPONENT_MIN: Exponent = Exponent::MIN;
 
 /// Minimum exponent value.
-#[cfg(any(target_arch = "x86", target_arch = "wasm32"))]
+#[cfg(any(
+    target_arch = "x86",
+    target_arch = "wasm32",
+    feature = "portable",
+    feature = "exp64"
+))]
 pub const EXPONENT_MIN: Exponent = Exponent::MIN / 4;
 
 /// Maximum value of a word.
@@ -60,13 +93,27 @@ pub const WORD_BASE: DoubleWord = WORD_MAX as DoubleWord + 1;
 /// Size of a word in bits.
 pub const WORD_BIT_SIZE: usize = core::mem::size_of::<Word>() * 8;
 
+/// Precision of numbers is rounded upwards to a multiple of this value in bits.
+/// It is equal to the word size, unless the `portable` feature is enabled,
+/// in which case it is 64 bits on all targets.
+#[cfg(not(feature = "portable"))]
+pub const PRECISION_GRANULARITY: usize = WORD_BIT_SIZE;
+
+/// Precision of numbers is rounded upwards to a multiple of this value in bits.
+/// It is equal to the word size, unless the `portable` feature is enabled,
+/// in which case it is 64 bits on all targets.
+#[cfg(feature = "portable")]
+pub const PRECISION_GRANULARITY: usize = 64;
+
 /// Word with the most significant bit set.
 pub const WORD_SIGNIFICANT_BIT: Word = WORD_MAX << (WORD_BIT_SIZE - 1);
 
 /// Default rounding mode.
+#[cfg(feature = "alloc")]
 pub const DEFAULT_RM: RoundingMode = RoundingMode::ToEven;
 
 /// Default precision.
+#[cfg(feature = "alloc")]
 pub const DEFAULT_P: usize = 128;
 
 /// Sign.
@@ -118,6 +165,9 @@ pub enum Error {
 
     /// Memory allocation error.
     MemoryAllocation,
+
+    /// The value cannot be represented exactly.
+    Inexact,
 }
 
 #[cfg(feature = "std")]
@@ -140,6 +190,7 @@ impl Display for Error {
             Error::DivisionByZero => "division by zero",
             Error::InvalidArgument => "invalid argument",
             Error::MemoryAllocation => "memory allocation failure",
+            Error::Inexact => "inexact result",
         };
         f.write_str(repr)
     }
@@ -154,6 +205,93 @@ impl PartialEq for Error {
     }
 }
 
+impl Error {
+    /// Attaches the name of the operation `op` which caused the error.
+    pub fn with_op(self, op: &'static str) -> OpError {
+        OpError::new(self, op)
+    }
+}
+
+/// Error with information about the operation which caused it.
+/// The text of the error includes the kind of the error, and thus the kind is not returned as the source of the error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpError {
+    kind: Error,
+    op: &'static str,
+    p: Option<usize>,
+    e: Option<Exponent>,
+}
+
+impl OpError {
+    /// Returns a new error of kind `kind` caused by the operation `op`.
+    pub fn new(kind: Error, op: &'static str) -> Self {
+        OpError {
+            kind,
+            op,
+            p: None,
+            e: None,
+        }
+    }
+
+    /// Sets the precision requested for the operation.
+    pub fn with_precision(mut self, p: usize) -> Self {
+        self.p = Some(p);
+        self
+    }
+
+    /// Sets the maximum exponent which was exceeded by the result of the operation.
+    pub fn with_exponent(mut self, e: Exponent) -> Self {
+        self.e = Some(e);
+        self
+    }
+
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> Error {
+        self.kind
+    }
+
+    /// Returns the name of the operation which caused the error.
+    pub fn op(&self) -> &'static str {
+        self.op
+    }
+
+    /// Returns the precision requested for the operation, if known.
+    pub fn precision(&self) -> Option<usize> {
+        self.p
+    }
+
+    /// Returns the maximum exponent which was exceeded by the result of the operation, if known.
+    pub fn exponent(&self) -> Option<Exponent> {
+        self.e
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OpError {}
+
+impl Display for OpError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: {}", self.op, self.kind)?;
+
+        if let Some(p) = self.p {
+            write!(f, ", precision {}", p)?;
+        }
+
+        if let Some(e) = self.e {
+            write!(f, ", exponent {}", e)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<OpError> for Error {
+    fn from(e: OpError) -> Self {
+        e.kind
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl From<TryReserveError> for Error {
     fn from(_: TryReserveError) -> Self {
         Error::MemoryAllocation
@@ -176,10 +314,40 @@ pub enum Radix {
     Hex = 16,
 }
 
+impl Radix {
+    /// Returns the number of bits required to represent `n` digits in radix `self`.
+    /// For the decimal radix the result is rounded upwards.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::Radix;
+    /// assert_eq!(Radix::Dec.digits_to_bits(30), 100);
+    /// assert_eq!(Radix::Hex.digits_to_bits(30), 120);
+    /// ```
+    pub fn digits_to_bits(&self, n: usize) -> usize {
+        match self {
+            Radix::Bin => n,
+            Radix::Oct => n.saturating_mul(3),
+            Radix::Dec => {
+                // log_2(10) rounded up
+                let b = (n as u128 * 3321928094887362348).div_ceil(1000000000000000000);
+                b.min(usize::MAX as u128) as usize
+            }
+            Radix::Hex => n.saturating_mul(4),
+        }
+    }
+}
+
 /// Rounding modes.
 #[derive(Eq, PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
 pub enum RoundingMode {
-    /// Skip rounding operation.
+    /// Faithful rounding: the result is either of the two representable numbers closest to the exact result
+    /// from below and from above, i.e. the error of the result is less than 1 ulp.
+    /// The result is not correctly rounded, but it is computed faster, because no check is needed
+    /// to decide in which direction the result must be rounded. The guarantee holds for all operations,
+    /// including the transcendental ones. The inexact flag is set as for any other rounding mode.
     None = 1,
 
     /// Round half toward positive infinity.
@@ -199,4 +367,21 @@ pub enum RoundingMode {
 
     /// Round half to odd.
     ToOdd = 64,
+
+    /// Round to nearest, ties away from zero.
+    ToNearestAway = 128,
+}
+
+/// Error handling policies.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub enum ErrorPolicy {
+    /// Errors are propagated as NaN values, and can be retrieved using `BigFloat::err()`.
+    #[default]
+    NaN,
+
+    /// Errors are returned as `Err` by the checked functions.
+    Result,
+
+    /// Errors cause panic.
+    Panic,
 }
@@ -0,0 +1,520 @@
+//! Dual numbers for automatic differentiation.
+
+use crate::BigFloat;
+use crate::Consts;
+use crate::RoundingMode;
+use crate::WORD_BIT_SIZE;
+use core::ops::Neg;
+
+// Rounding mode of intermediate results of derivative computation.
+const RM_WRK: RoundingMode = RoundingMode::ToEven;
+
+/// Dual number `val + der*ε`, where `ε^2 = 0`.
+///
+/// Evaluating a function on a dual number with `der` equal to 1 gives the value of the function in `val`
+/// together with the value of its derivative, because the derivative is propagated through every operation using the chain rule.
+///
+/// The value of an operation is computed exactly as the corresponding operation on BigFloat computes it.
+/// The derivative is computed with `WORD_BIT_SIZE` additional bits of precision and then rounded to precision `p` using the rounding mode `rm`,
+/// so it is usually accurate within a few ulp, unless the derivative itself is ill-conditioned.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::{BigFloat, BigFloatDual, Consts, RoundingMode};
+/// let mut cc = Consts::new().expect("Constants cache initialized");
+/// let p = 256;
+/// let rm = RoundingMode::ToEven;
+///
+/// // solve x * exp(x) = 3 using Newton's method
+/// let three = BigFloatDual::constant(BigFloat::from_word(3, p));
+/// let mut x = BigFloat::from_word(1, p);
+///
+/// for _ in 0..12 {
+///     let d = BigFloatDual::variable(x.clone());
+///     let f = d.mul(&d.exp(p, rm, &mut cc), p, rm).sub(&three, p, rm);
+///     x = x.sub(&f.val().div(f.der(), p, rm), p, rm);
+/// }
+///
+/// let check = x.mul(&x.exp(p, rm, &mut cc), p, rm);
+/// assert!(check.sub(&BigFloat::from_word(3, p), p, rm).abs() < BigFloat::from_f64(1e-70, p));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BigFloatDual {
+    val: BigFloat,
+    der: BigFloat,
+}
+
+impl BigFloatDual {
+    /// Constructs a dual number from the value `val` and the derivative `der`.
+    pub fn new(val: BigFloat, der: BigFloat) -> Self {
+        BigFloatDual { val, der }
+    }
+
+    /// Constructs an independent variable with value `val` and derivative 1.
+    pub fn variable(val: BigFloat) -> Self {
+        let der = BigFloat::from_word(1, val.precision().unwrap_or(WORD_BIT_SIZE));
+        BigFloatDual { val, der }
+    }
+
+    /// Constructs a constant with value `val` and derivative 0.
+    pub fn constant(val: BigFloat) -> Self {
+        let der = BigFloat::new(val.precision().unwrap_or(WORD_BIT_SIZE));
+        BigFloatDual { val, der }
+    }
+
+    /// Returns the value.
+    pub fn val(&self) -> &BigFloat {
+        &self.val
+    }
+
+    /// Returns the derivative.
+    pub fn der(&self) -> &BigFloat {
+        &self.der
+    }
+
+    /// Decomposes the dual number into the value and the derivative.
+    pub fn into_parts(self) -> (BigFloat, BigFloat) {
+        (self.val, self.der)
+    }
+
+    // Returns the dual number with value `val` and derivative `df * self.der`,
+    // where `df` is the derivative of the applied function at `self.val`.
+    fn chain(&self, val: BigFloat, df: &BigFloat, p: usize, rm: RoundingMode) -> Self {
+        let der = df.mul(&self.der, p, rm);
+        BigFloatDual { val, der }
+    }
+
+    /// Adds `d2` to `self` and returns the result with precision `p` rounded using the rounding mode `rm`.
+    pub fn add(&self, d2: &Self, p: usize, rm: RoundingMode) -> Self {
+        BigFloatDual {
+            val: self.val.add(&d2.val, p, rm),
+            der: self.der.add(&d2.der, p, rm),
+        }
+    }
+
+    /// Subtracts `d2` from `self` and returns the result with precision `p` rounded using the rounding mode `rm`.
+    pub fn sub(&self, d2: &Self, p: usize, rm: RoundingMode) -> Self {
+        BigFloatDual {
+            val: self.val.sub(&d2.val, p, rm),
+            der: self.der.sub(&d2.der, p, rm),
+        }
+    }
+
+    /// Multiplies `self` by `d2` and returns the result with precision `p` rounded using the rounding mode `rm`.
+    pub fn mul(&self, d2: &Self, p: usize, rm: RoundingMode) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (uv)' = u'v + uv'
+        let t1 = self.der.mul(&d2.val, pw, RM_WRK);
+        let t2 = self.val.mul(&d2.der, pw, RM_WRK);
+
+        BigFloatDual {
+            val: self.val.mul(&d2.val, p, rm),
+            der: t1.add(&t2, p, rm),
+        }
+    }
+
+    /// Divides `self` by `d2` and returns the result with precision `p` rounded using the rounding mode `rm`.
+    pub fn div(&self, d2: &Self, p: usize, rm: RoundingMode) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (u/v)' = (u' - (u/v)v') / v
+        let q = self.val.div(&d2.val, pw, RM_WRK);
+        let t = self.der.sub(&q.mul(&d2.der, pw, RM_WRK), pw, RM_WRK);
+
+        BigFloatDual {
+            val: self.val.div(&d2.val, p, rm),
+            der: t.div(&d2.val, p, rm),
+        }
+    }
+
+    /// Computes the reciprocal of `self` with precision `p` rounded using the rounding mode `rm`.
+    pub fn reciprocal(&self, p: usize, rm: RoundingMode) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (1/u)' = -u'/u^2
+        let df = self
+            .val
+            .mul(&self.val, pw, RM_WRK)
+            .reciprocal(pw, RM_WRK)
+            .neg();
+
+        self.chain(self.val.reciprocal(p, rm), &df, p, rm)
+    }
+
+    /// Returns the absolute value of `self`.
+    pub fn abs(&self) -> Self {
+        if self.val.is_negative() {
+            self.clone().neg()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Computes the square root of `self` with precision `p` rounded using the rounding mode `rm`.
+    pub fn sqrt(&self, p: usize, rm: RoundingMode) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (sqrt(u))' = u' / (2 sqrt(u))
+        let mut df = self.val.sqrt(pw, RM_WRK).reciprocal(pw, RM_WRK);
+        df.set_exponent(df.exponent().unwrap_or(0) - 1);
+
+        self.chain(self.val.sqrt(p, rm), &df, p, rm)
+    }
+
+    /// Computes the cube root of `self` with precision `p` rounded using the rounding mode `rm`.
+    pub fn cbrt(&self, p: usize, rm: RoundingMode) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (cbrt(u))' = u' / (3 cbrt(u)^2)
+        let c = self.val.cbrt(pw, RM_WRK);
+        let df = c
+            .mul(&c, pw, RM_WRK)
+            .mul(&BigFloat::from_word(3, pw), pw, RM_WRK)
+            .reciprocal(pw, RM_WRK);
+
+        self.chain(self.val.cbrt(p, rm), &df, p, rm)
+    }
+
+    /// Computes `self` to the power of integer `n` with precision `p` rounded using the rounding mode `rm`.
+    pub fn powi(&self, n: usize, p: usize, rm: RoundingMode) -> Self {
+        if n == 0 {
+            return BigFloatDual::constant(BigFloat::from_word(1, p));
+        }
+
+        let pw = p + WORD_BIT_SIZE;
+
+        // (u^n)' = n u^(n-1) u'
+        let df =
+            self.val
+                .powi(n - 1, pw, RM_WRK)
+                .mul(&BigFloat::from_u64(n as u64, pw), pw, RM_WRK);
+
+        self.chain(self.val.powi(n, p, rm), &df, p, rm)
+    }
+
+    /// Computes `self` to the power of `d2` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn pow(&self, d2: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        let val = self.val.pow(&d2.val, p, rm, cc);
+        let v = self.val.pow(&d2.val, pw, RM_WRK, cc);
+
+        // (u^v)' = u^v (v' ln(u) + v u' / u), the first term is omitted if v is constant,
+        // which keeps the derivative defined for negative u.
+        let mut t = d2.val.mul(&self.der, pw, RM_WRK).div(&self.val, pw, RM_WRK);
+
+        if !d2.der.is_zero() {
+            let l = self.val.ln(pw, RM_WRK, cc).mul(&d2.der, pw, RM_WRK);
+            t = t.add(&l, pw, RM_WRK);
+        }
+
+        BigFloatDual {
+            val,
+            der: v.mul(&t, p, rm),
+        }
+    }
+
+    /// Computes `e` to the power of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn exp(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (e^u)' = e^u u'
+        let df = self.val.exp(pw, RM_WRK, cc);
+
+        self.chain(self.val.exp(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the natural logarithm of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn ln(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (ln(u))' = u' / u
+        let df = self.val.reciprocal(pw, RM_WRK);
+
+        self.chain(self.val.ln(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the logarithm base 2 of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn log2(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (log2(u))' = u' / (u ln(2))
+        let df = self
+            .val
+            .mul(&cc.ln_2(pw, RM_WRK), pw, RM_WRK)
+            .reciprocal(pw, RM_WRK);
+
+        self.chain(self.val.log2(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the logarithm base 10 of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn log10(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (log10(u))' = u' / (u ln(10))
+        let df = self
+            .val
+            .mul(&cc.ln_10(pw, RM_WRK), pw, RM_WRK)
+            .reciprocal(pw, RM_WRK);
+
+        self.chain(self.val.log10(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the sine of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn sin(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (sin(u))' = cos(u) u'
+        let df = self.val.cos(pw, RM_WRK, cc);
+
+        self.chain(self.val.sin(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the cosine of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn cos(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (cos(u))' = -sin(u) u'
+        let df = self.val.sin(pw, RM_WRK, cc).neg();
+
+        self.chain(self.val.cos(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the tangent of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn tan(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (tan(u))' = u' / cos(u)^2
+        let c = self.val.cos(pw, RM_WRK, cc);
+        let df = c.mul(&c, pw, RM_WRK).reciprocal(pw, RM_WRK);
+
+        self.chain(self.val.tan(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the arcsine of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn asin(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        // (asin(u))' = u' / sqrt(1 - u^2)
+        let df = Self::one_minus_sq(&self.val, p).sqrt(p + WORD_BIT_SIZE, RM_WRK);
+        let df = df.reciprocal(p + WORD_BIT_SIZE, RM_WRK);
+
+        self.chain(self.val.asin(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the arccosine of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn acos(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        // (acos(u))' = -u' / sqrt(1 - u^2)
+        let df = Self::one_minus_sq(&self.val, p).sqrt(p + WORD_BIT_SIZE, RM_WRK);
+        let df = df.reciprocal(p + WORD_BIT_SIZE, RM_WRK).neg();
+
+        self.chain(self.val.acos(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the arctangent of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn atan(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        // (atan(u))' = u' / (1 + u^2)
+        let df = Self::one_plus_sq(&self.val, p).reciprocal(p + WORD_BIT_SIZE, RM_WRK);
+
+        self.chain(self.val.atan(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the hyperbolic sine of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn sinh(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (sinh(u))' = cosh(u) u'
+        let df = self.val.cosh(pw, RM_WRK, cc);
+
+        self.chain(self.val.sinh(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the hyperbolic cosine of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn cosh(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (cosh(u))' = sinh(u) u'
+        let df = self.val.sinh(pw, RM_WRK, cc);
+
+        self.chain(self.val.cosh(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the hyperbolic tangent of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn tanh(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (tanh(u))' = u' / cosh(u)^2
+        let c = self.val.cosh(pw, RM_WRK, cc);
+        let df = c.mul(&c, pw, RM_WRK).reciprocal(pw, RM_WRK);
+
+        self.chain(self.val.tanh(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the hyperbolic arcsine of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn asinh(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (asinh(u))' = u' / sqrt(u^2 + 1)
+        let df = Self::one_plus_sq(&self.val, p)
+            .sqrt(pw, RM_WRK)
+            .reciprocal(pw, RM_WRK);
+
+        self.chain(self.val.asinh(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the hyperbolic arccosine of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn acosh(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let pw = p + WORD_BIT_SIZE;
+
+        // (acosh(u))' = u' / sqrt(u^2 - 1)
+        let df = Self::one_minus_sq(&self.val, p)
+            .neg()
+            .sqrt(pw, RM_WRK)
+            .reciprocal(pw, RM_WRK);
+
+        self.chain(self.val.acosh(p, rm, cc), &df, p, rm)
+    }
+
+    /// Computes the hyperbolic arctangent of `self` with precision `p` rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    pub fn atanh(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        // (atanh(u))' = u' / (1 - u^2)
+        let df = Self::one_minus_sq(&self.val, p).reciprocal(p + WORD_BIT_SIZE, RM_WRK);
+
+        self.chain(self.val.atanh(p, rm, cc), &df, p, rm)
+    }
+
+    // Computes 1 - x^2 as (1 - x)(1 + x) to avoid cancellation near |x| = 1.
+    fn one_minus_sq(x: &BigFloat, p: usize) -> BigFloat {
+        let pw = p + WORD_BIT_SIZE;
+        let one = BigFloat::from_word(1, pw);
+
+        one.sub(x, pw, RM_WRK)
+            .mul(&one.add(x, pw, RM_WRK), pw, RM_WRK)
+    }
+
+    // Computes 1 + x^2.
+    fn one_plus_sq(x: &BigFloat, p: usize) -> BigFloat {
+        let pw = p + WORD_BIT_SIZE;
+
+        x.mul(x, pw, RM_WRK)
+            .add(&BigFloat::from_word(1, pw), pw, RM_WRK)
+    }
+}
+
+impl Neg for BigFloatDual {
+    type Output = BigFloatDual;
+
+    fn neg(self) -> Self::Output {
+        BigFloatDual {
+            val: self.val.neg(),
+            der: self.der.neg(),
+        }
+    }
+}
+
+impl From<BigFloat> for BigFloatDual {
+    fn from(val: BigFloat) -> Self {
+        BigFloatDual::constant(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_dual() {
+        let p = 320;
+        let rm = RoundingMode::ToEven;
+        let mut cc = Consts::new().unwrap();
+
+        let x0 = BigFloat::parse("0.375", crate::Radix::Dec, p, rm);
+        let x = BigFloatDual::variable(x0.clone());
+        let h = BigFloat::from_f64(1e-40, p);
+
+        // compares the derivative with the central difference quotient computed with a step of 1e-40 at doubled precision
+        let mut check = |f: &dyn Fn(&BigFloatDual, usize, &mut Consts) -> BigFloatDual| {
+            let d = f(&x, p, &mut cc);
+
+            let pd = 640;
+            let xp = BigFloatDual::constant(x0.add(&h, pd, rm));
+            let xm = BigFloatDual::constant(x0.sub(&h, pd, rm));
+            let fp = f(&xp, pd, &mut cc).val().clone();
+            let fm = f(&xm, pd, &mut cc).val().clone();
+
+            let q = fp.sub(&fm, pd, rm).div(&h, pd, rm);
+            let mut q = q.div(&BigFloat::from_word(2, pd), pd, rm);
+            q.set_precision(p, rm).unwrap();
+
+            let err = d.der().sub(&q, p, rm).abs();
+            assert!(err < BigFloat::from_f64(1e-70, p), "{} {}", d.der(), q);
+        };
+
+        let c = BigFloatDual::constant(BigFloat::from_f64(1.25, p));
+
+        check(&|x, p, _| x.mul(x, p, rm).add(&c, p, rm));
+        check(&|x, p, _| c.div(x, p, rm).sub(x, p, rm));
+        check(&|x, p, _| x.reciprocal(p, rm).abs());
+        check(&|x, p, _| x.sqrt(p, rm).mul(&x.cbrt(p, rm), p, rm));
+        check(&|x, p, _| x.powi(5, p, rm));
+        check(&|x, p, cc| x.pow(&c, p, rm, cc));
+        check(&|x, p, cc| c.pow(x, p, rm, cc));
+        check(&|x, p, cc| x.pow(x, p, rm, cc));
+        check(&|x, p, cc| x.exp(p, rm, cc).ln(p, rm, cc).log2(p, rm, cc));
+        check(&|x, p, cc| x.log10(p, rm, cc));
+        check(&|x, p, cc| x.sin(p, rm, cc).mul(&x.cos(p, rm, cc), p, rm));
+        check(&|x, p, cc| x.tan(p, rm, cc));
+        check(&|x, p, cc| x.asin(p, rm, cc).add(&x.acos(p, rm, cc), p, rm));
+        check(&|x, p, cc| x.asin(p, rm, cc).mul(&x.atan(p, rm, cc), p, rm));
+        check(&|x, p, cc| x.sinh(p, rm, cc).div(&x.cosh(p, rm, cc), p, rm));
+        check(&|x, p, cc| x.tanh(p, rm, cc).mul(&x.asinh(p, rm, cc), p, rm));
+        check(&|x, p, cc| x.atanh(p, rm, cc));
+        check(&|x, p, cc| x.add(&c, p, rm).acosh(p, rm, cc));
+        check(&|x, p, _| x.clone().neg().powi(3, p, rm));
+
+        // exact derivatives
+        let two = BigFloatDual::variable(BigFloat::from_word(2, p));
+        let d = two.powi(3, p, rm);
+        assert_eq!(d.val(), &BigFloat::from_word(8, p));
+        assert_eq!(d.der(), &BigFloat::from_word(12, p));
+
+        let d = two.clone().neg().mul(&two, p, rm);
+        assert_eq!(d.der(), &BigFloat::from_i8(-4, p));
+        assert_eq!(two.powi(0, p, rm).der(), &BigFloat::new(p));
+
+        // constant power of a negative base
+        let neg = BigFloatDual::variable(BigFloat::from_i8(-2, p));
+        let d = neg.pow(
+            &BigFloatDual::from(BigFloat::from_word(3, p)),
+            p,
+            rm,
+            &mut cc,
+        );
+        assert_eq!(d.val(), &BigFloat::from_i8(-8, p));
+        assert_eq!(d.der(), &BigFloat::from_word(12, p));
+
+        let (v, d) = BigFloatDual::new(BigFloat::from_word(4, p), BigFloat::from_word(3, p))
+            .sqrt(p, rm)
+            .into_parts();
+        assert_eq!(v, BigFloat::from_word(2, p));
+        assert_eq!(d, BigFloat::from_f64(0.75, p));
+    }
+}
@@ -1,5 +1,6 @@
 //! BigFloat including finite numbers, NaN, and `Inf`.
 
+use crate::borrowed::BigFloatRef;
 use crate::defs::SignedWord;
 use crate::defs::DEFAULT_P;
 use crate::defs::DEFAULT_RM;
@@ -7,10 +8,12 @@ use crate::num::BigFloatNumber;
 use crate::Consts;
 use crate::Error;
 use crate::Exponent;
+use crate::MulThresholds;
 use crate::Radix;
 use crate::RoundingMode;
 use crate::Sign;
 use crate::Word;
+use crate::WORD_BIT_SIZE;
 use core::fmt::Write;
 use core::num::FpCategory;
 use lazy_static::lazy_static;
@@ -18,6 +21,14 @@ use lazy_static::lazy_static;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
+// Enters the tracing span of the operation `name` computed with precision `p` until the end of the enclosing block.
+macro_rules! trace_op {
+    ($name:expr, $p:expr) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("op", name = $name, p = $p).entered();
+    };
+}
+
 /// Not a number.
 pub const NAN: BigFloat = BigFloat {
     inner: Flavor::NaN(None),
@@ -35,20 +46,33 @@ pub const INF_NEG: BigFloat = BigFloat {
 
 lazy_static! {
 
-    /// 1
+    /// 1 with precision of 128 bits.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use astro_float_num::{BigFloat, RoundingMode, ONE, ONE_HALF, TWO};
+    ///
+    /// let x = ONE.add(&ONE_HALF, 64, RoundingMode::None);
+    /// assert_eq!(x.mul(&TWO, 64, RoundingMode::None), BigFloat::from_word(3, 64));
+    /// ```
     pub static ref ONE: BigFloat = BigFloat { inner: Flavor::Value(BigFloatNumber::from_word(1, DEFAULT_P).expect("Constant ONE initialized")) };
 
-    /// 2
+    /// 2 with precision of 128 bits.
     pub static ref TWO: BigFloat = BigFloat { inner: Flavor::Value(BigFloatNumber::from_word(2, DEFAULT_P).expect("Constant TWO initialized")) };
+
+    /// 10 with precision of 128 bits.
+    pub static ref TEN: BigFloat = BigFloat { inner: Flavor::Value(BigFloatNumber::from_word(10, DEFAULT_P).expect("Constant TEN initialized")) };
+
+    /// 0.5 with precision of 128 bits.
+    pub static ref ONE_HALF: BigFloat = BigFloat { inner: Flavor::Value(BigFloatNumber::from_f64(DEFAULT_P, 0.5).expect("Constant ONE_HALF initialized")) };
 }
 
 /// A floating point number of arbitrary precision.
-#[derive(Debug)]
 pub struct BigFloat {
     inner: Flavor,
 }
 
-#[derive(Debug)]
 enum Flavor {
     Value(BigFloatNumber),
     NaN(Option<Error>),
@@ -56,19 +80,62 @@ enum Flavor {
 }
 
 impl BigFloat {
-    /// Returns a new number with value of 0 and precision of `p` bits. Precision is rounded upwards to the word size.
+    /// Returns a new number with value of 0 and precision of `p` bits. Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn new(p: usize) -> Self {
         Self::result_to_ext(BigFloatNumber::new(p), false, true)
     }
 
+    /// Returns a new number with value of 0 and precision sufficient to hold `n` digits in radix `rdx`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision is incorrect.
+    pub fn new_digits(n: usize, rdx: Radix) -> Self {
+        Self::new(rdx.digits_to_bits(n))
+    }
+
     /// Constructs a number with precision `p` from f64 value.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn from_f64(f: f64, p: usize) -> Self {
         Self::result_to_ext(BigFloatNumber::from_f64(p, f), false, true)
     }
 
+    /// Constructs a number from f64 value choosing the smallest precision which holds `f` exactly:
+    /// 53 significant bits, or fewer if `f` is a dyadic number with a short mantissa,
+    /// rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if `f` is NaN, and Inf if `f` is infinite.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// let n = BigFloat::from_f64_minimal(0.1);
+    ///
+    /// assert_eq!(n.to_f64(), 0.1);
+    /// assert!(n.is_f64_exact());
+    /// ```
+    pub fn from_f64_minimal(f: f64) -> Self {
+        Self::result_to_ext(BigFloatNumber::from_f64_minimal(f), false, true)
+    }
+
+    /// Constructs a number from f32 value choosing the smallest precision which holds `f` exactly:
+    /// 24 significant bits, or fewer if `f` is a dyadic number with a short mantissa,
+    /// rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if `f` is NaN, and Inf if `f` is infinite.
+    pub fn from_f32_minimal(f: f32) -> Self {
+        Self::from_f64_minimal(f as f64)
+    }
+
+    /// Returns true if the value of `self` is exactly representable as f64, i.e. `to_f64` returns the stored value
+    /// without rounding. Inf is considered representable, NaN is not.
+    pub fn is_f64_exact(&self) -> bool {
+        match &self.inner {
+            Flavor::Value(v) => matches!(v.to_f64_rm(RoundingMode::ToZero), Ok((_, false))),
+            Flavor::NaN(_) => false,
+            Flavor::Inf(_) => true,
+        }
+    }
+
     /// Converts to f64
     pub fn to_f64(&self) -> f64 {
         match &self.inner {
@@ -78,6 +145,218 @@ impl BigFloat {
         }
     }
 
+    /// Converts the number to f64 value rounding it using rounding mode `rm`.
+    /// Rounding mode None is treated as ToZero.
+    /// Numbers which exceed the range of f64 become infinite,
+    /// or the largest finite f64 value if rounding is performed towards zero.
+    /// NaN is converted to f64 NaN.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let n = BigFloat::from_word(1, 128).div(&BigFloat::from_word(3, 128), 128, RoundingMode::None);
+    ///
+    /// assert_eq!(n.to_f64_round(RoundingMode::ToEven), 1.0 / 3.0);
+    /// assert!(n.to_f64_round(RoundingMode::Up) > 1.0 / 3.0);
+    /// ```
+    pub fn to_f64_round(&self, rm: RoundingMode) -> f64 {
+        match &self.inner {
+            Flavor::Value(v) => match v.to_f64_rm(rm) {
+                Ok((f, _)) => f,
+                Err(Error::ExponentOverflow(s)) => {
+                    if Self::overflow_towards_zero(s, rm) {
+                        if s.is_positive() {
+                            f64::MAX
+                        } else {
+                            f64::MIN
+                        }
+                    } else if s.is_positive() {
+                        f64::INFINITY
+                    } else {
+                        f64::NEG_INFINITY
+                    }
+                }
+                Err(_) => f64::NAN,
+            },
+            Flavor::NaN(_) => f64::NAN,
+            Flavor::Inf(s) => {
+                if s.is_positive() {
+                    f64::INFINITY
+                } else {
+                    f64::NEG_INFINITY
+                }
+            }
+        }
+    }
+
+    /// Converts the number to f32 value rounding it using rounding mode `rm`.
+    /// Rounding mode None is treated as ToZero.
+    /// Numbers which exceed the range of f32 become infinite,
+    /// or the largest finite f32 value if rounding is performed towards zero.
+    /// NaN is converted to f32 NaN.
+    pub fn to_f32_round(&self, rm: RoundingMode) -> f32 {
+        match &self.inner {
+            Flavor::Value(v) => match v.to_f32_rm(rm) {
+                Ok((f, _)) => f,
+                Err(Error::ExponentOverflow(s)) => {
+                    if Self::overflow_towards_zero(s, rm) {
+                        if s.is_positive() {
+                            f32::MAX
+                        } else {
+                            f32::MIN
+                        }
+                    } else if s.is_positive() {
+                        f32::INFINITY
+                    } else {
+                        f32::NEG_INFINITY
+                    }
+                }
+                Err(_) => f32::NAN,
+            },
+            Flavor::NaN(_) => f32::NAN,
+            Flavor::Inf(s) => {
+                if s.is_positive() {
+                    f32::INFINITY
+                } else {
+                    f32::NEG_INFINITY
+                }
+            }
+        }
+    }
+
+    /// Rounds the number to an integer using rounding mode `rm` and converts it to i64 value.
+    /// Rounding mode None is treated as ToZero.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, Error, RoundingMode, Sign};
+    /// let n = BigFloat::from_f64(-2.5, 64);
+    ///
+    /// assert_eq!(n.to_i64(RoundingMode::ToZero), Ok(-2));
+    /// assert_eq!(n.to_i64(RoundingMode::Down), Ok(-3));
+    /// assert_eq!(n.to_i64(RoundingMode::ToEven), Ok(-2));
+    /// assert_eq!(n.to_i64(RoundingMode::ToNearestAway), Ok(-3));
+    ///
+    /// let n = BigFloat::from_f64(1.0e19, 64);
+    /// assert_eq!(n.to_i64(RoundingMode::ToEven), Err(Error::ExponentOverflow(Sign::Pos)));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `self` is NaN.
+    ///  - ExponentOverflow: `self` is Inf, or the rounded value exceeds the range of i64.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn to_i64(&self, rm: RoundingMode) -> Result<i64, Error> {
+        match &self.inner {
+            Flavor::Value(v) => v.to_i64(rm),
+            Flavor::NaN(_) => Err(Error::InvalidArgument),
+            Flavor::Inf(s) => Err(Error::ExponentOverflow(*s)),
+        }
+    }
+
+    /// Rounds the number to an integer using rounding mode `rm` and converts it to u64 value.
+    /// Rounding mode None is treated as ToZero.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `self` is NaN, or the rounded value is negative.
+    ///  - ExponentOverflow: `self` is Inf, or the rounded value exceeds the range of u64.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn to_u64(&self, rm: RoundingMode) -> Result<u64, Error> {
+        match &self.inner {
+            Flavor::Value(v) => v.to_u64(rm),
+            Flavor::NaN(_) => Err(Error::InvalidArgument),
+            Flavor::Inf(s) => Err(Error::ExponentOverflow(*s)),
+        }
+    }
+
+    /// Rounds the number to an integer using rounding mode `rm` and converts it to i128 value.
+    /// Rounding mode None is treated as ToZero.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `self` is NaN.
+    ///  - ExponentOverflow: `self` is Inf, or the rounded value exceeds the range of i128.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn to_i128(&self, rm: RoundingMode) -> Result<i128, Error> {
+        match &self.inner {
+            Flavor::Value(v) => v.to_i128(rm),
+            Flavor::NaN(_) => Err(Error::InvalidArgument),
+            Flavor::Inf(s) => Err(Error::ExponentOverflow(*s)),
+        }
+    }
+
+    /// Rounds the number to an integer using rounding mode `rm` and converts it to u128 value.
+    /// Rounding mode None is treated as ToZero.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `self` is NaN, or the rounded value is negative.
+    ///  - ExponentOverflow: `self` is Inf, or the rounded value exceeds the range of u128.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn to_u128(&self, rm: RoundingMode) -> Result<u128, Error> {
+        match &self.inner {
+            Flavor::Value(v) => v.to_u128(rm),
+            Flavor::NaN(_) => Err(Error::InvalidArgument),
+            Flavor::Inf(s) => Err(Error::ExponentOverflow(*s)),
+        }
+    }
+
+    // Returns true if the overflowing value with sign `s` is rounded to the largest finite value using rounding mode `rm`.
+    pub(crate) fn overflow_towards_zero(s: Sign, rm: RoundingMode) -> bool {
+        match rm {
+            RoundingMode::ToZero | RoundingMode::None => true,
+            RoundingMode::Up => s.is_negative(),
+            RoundingMode::Down => s.is_positive(),
+            _ => false,
+        }
+    }
+
+    /// Constructs a number with precision `p` from f128 value.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// If `p` is smaller than 113, the value is rounded to the nearest even.
+    /// The function returns NaN if the precision `p` is incorrect.
+    #[cfg(feature = "f128")]
+    pub fn from_f128(f: f128, p: usize) -> Self {
+        Self::result_to_ext(BigFloatNumber::from_f128(p, f), false, true)
+    }
+
+    /// Converts the number to f128 value rounding it using rounding mode `rm`.
+    /// Rounding mode None is treated as ToZero.
+    /// Numbers which exceed the range of f128 become infinite,
+    /// or the largest finite f128 value if rounding is performed towards zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let n = BigFloat::from_word(1, 256).div(&BigFloat::from_word(3, 256), 256, RoundingMode::None);
+    ///
+    /// let f = n.to_f128(RoundingMode::ToEven);
+    ///
+    /// assert_eq!(f, 1.0f128 / 3.0f128);
+    /// assert_eq!(BigFloat::from_f128(f, 128).to_f128(RoundingMode::ToEven), f);
+    /// ```
+    #[cfg(feature = "f128")]
+    pub fn to_f128(&self, rm: RoundingMode) -> f128 {
+        match &self.inner {
+            Flavor::Value(v) => v.to_f128(rm).unwrap_or(f128::NAN),
+            Flavor::NaN(_) => f128::NAN,
+            Flavor::Inf(s) => {
+                if s.is_positive() {
+                    f128::INFINITY
+                } else {
+                    f128::NEG_INFINITY
+                }
+            }
+        }
+    }
+
     pub(crate) fn nan(err: Option<Error>) -> Self {
         BigFloat {
             inner: Flavor::NaN(err),
@@ -85,10 +364,10 @@ impl BigFloat {
     }
 
     /// Constructs a number with precision `p` from f32 value.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn from_f32(f: f32, p: usize) -> Self {
-        Self::result_to_ext(BigFloatNumber::from_f64(p, f as f64), false, true)
+        Self::result_to_ext(BigFloatNumber::from_f32(p, f), false, true)
     }
 
     /// Returns true if `self` is positive infinity.
@@ -120,6 +399,34 @@ impl BigFloat {
         }
     }
 
+    /// Returns true if `self` is inexact, i.e. rounding took place while computing `self`,
+    /// or while computing any of the values `self` was derived from.
+    /// The flag is sticky: all arithmetic and transcendental operations propagate it from their arguments to the result.
+    /// The function returns false if `self` is Inf or NaN.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let rm = RoundingMode::ToEven;
+    /// let one = BigFloat::from_word(1, 128);
+    /// let three = BigFloat::from_word(3, 128);
+    ///
+    /// assert!(!one.add(&three, 128, rm).is_inexact());
+    ///
+    /// let third = one.div(&three, 128, rm);
+    /// assert!(third.is_inexact());
+    ///
+    /// // the flag survives subsequent exact operations
+    /// let zero = third.sub(&third, 128, rm);
+    /// assert!(zero.is_inexact());
+    /// assert!(zero.mul(&three, 128, rm).is_inexact());
+    /// ```
+    pub fn is_inexact(&self) -> bool {
+        self.inexact()
+    }
+
     /// Returns the associated with NaN error, if any.
     pub fn err(&self) -> Option<Error> {
         match &self.inner {
@@ -129,9 +436,11 @@ impl BigFloat {
     }
 
     /// Adds `d2` to `self` and returns the result of the operation with precision `p` rounded according to `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn add(&self, d2: &Self, p: usize, rm: RoundingMode) -> Self {
+        trace_op!("add", p);
+
         self.add_op(d2, p, rm, false)
     }
 
@@ -175,9 +484,11 @@ impl BigFloat {
     }
 
     /// Subtracts `d2` from `self` and returns the result of the operation with precision `p` rounded according to `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn sub(&self, d2: &Self, p: usize, rm: RoundingMode) -> Self {
+        trace_op!("sub", p);
+
         self.sub_op(d2, p, rm, false)
     }
 
@@ -225,25 +536,83 @@ impl BigFloat {
     }
 
     /// Multiplies `d2` by `self` and returns the result of the operation with precision `p` rounded according to `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn mul(&self, d2: &Self, p: usize, rm: RoundingMode) -> Self {
-        self.mul_op(d2, p, rm, false)
+        trace_op!("mul", p);
+
+        self.mul_op(d2, p, rm, false, &MulThresholds::default())
+    }
+
+    // Same as `mul`, but the mantissas are multiplied using thresholds `t`.
+    pub(crate) fn mul_with_thresholds(
+        &self,
+        d2: &Self,
+        p: usize,
+        rm: RoundingMode,
+        t: &MulThresholds,
+    ) -> Self {
+        trace_op!("mul", p);
+
+        self.mul_op(d2, p, rm, false, t)
     }
 
     /// Multiplies `d2` by `self` and returns the result of the operation.
     /// The resulting precision is equal to the full precision of the result.
     /// This operation can be used to emulate integer multiplication.
     pub fn mul_full_prec(&self, d2: &Self) -> Self {
-        self.mul_op(d2, 0, RoundingMode::None, true)
+        self.mul_op(d2, 0, RoundingMode::None, true, &MulThresholds::default())
+    }
+
+    /// Computes `self`·`b` + `c`·`d` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// The products are computed with full precision, so the result is rounded only once.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use astro_float_num::{BigFloat, RoundingMode};
+    ///
+    /// let one = BigFloat::from_word(1, 64);
+    /// let a = one.add(&BigFloat::from_f64(2.0f64.powi(-40), 64), 64, RoundingMode::None);
+    /// let c = one.add(&BigFloat::from_f64(2.0f64.powi(-39), 64), 64, RoundingMode::None);
+    ///
+    /// // a·a - c·1 = 2^-80, while the product a·a rounded to 64 bits is equal to c.
+    /// let r = a.mul_add_mul(&a, &c, &one.neg(), 64, RoundingMode::ToEven);
+    /// assert_eq!(r, BigFloat::from_f64(2.0f64.powi(-80), 64));
+    ///
+    /// let ab = a.mul(&a, 64, RoundingMode::ToEven);
+    /// assert!(ab.sub(&c, 64, RoundingMode::ToEven).is_zero());
+    /// ```
+    pub fn mul_add_mul(&self, b: &Self, c: &Self, d: &Self, p: usize, rm: RoundingMode) -> Self {
+        trace_op!("mul_add_mul", p);
+
+        match (&self.inner, &b.inner, &c.inner, &d.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2), Flavor::Value(v3), Flavor::Value(v4)) => {
+                Self::result_to_ext(v1.mul_add_mul(v2, v3, v4, p, rm), false, true)
+            }
+            _ => self.mul_full_prec(b).add(&c.mul_full_prec(d), p, rm),
+        }
     }
 
-    fn mul_op(&self, d2: &Self, p: usize, rm: RoundingMode, full_prec: bool) -> Self {
+    fn mul_op(
+        &self,
+        d2: &Self,
+        p: usize,
+        rm: RoundingMode,
+        full_prec: bool,
+        t: &MulThresholds,
+    ) -> Self {
         match &self.inner {
             Flavor::Value(v1) => {
                 match &d2.inner {
                     Flavor::Value(v2) => Self::result_to_ext(
-                        if full_prec { v1.mul_full_prec(v2) } else { v1.mul(v2, p, rm) },
+                        if full_prec {
+                            v1.mul_full_prec(v2)
+                        } else {
+                            v1.mul_with_thresholds(v2, p, rm, t)
+                        },
                         v1.is_zero(),
                         v1.sign() == v2.sign(),
                     ),
@@ -288,9 +657,11 @@ impl BigFloat {
     }
 
     /// Divides `self` by `d2` and returns the result of the operation with precision `p` rounded according to `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn div(&self, d2: &Self, p: usize, rm: RoundingMode) -> Self {
+        trace_op!("div", p);
+
         match &self.inner {
             Flavor::Value(v1) => match &d2.inner {
                 Flavor::Value(v2) => {
@@ -314,77 +685,681 @@ impl BigFloat {
         }
     }
 
-    /// Returns the remainder of division of `|self|` by `|d2|`. The sign of the result is set to the sign of `self`.
-    pub fn rem(&self, d2: &Self) -> Self {
+    /// Adds the word `d` to `self` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn add_word(&self, d: Word, p: usize, rm: RoundingMode) -> Self {
         match &self.inner {
-            Flavor::Value(v1) => match &d2.inner {
-                Flavor::Value(v2) => {
-                    Self::result_to_ext(v1.rem(v2), v1.is_zero(), v1.sign() == v2.sign())
-                }
-                Flavor::Inf(_) => self.clone(),
-                Flavor::NaN(err) => Self::nan(*err),
+            Flavor::Value(v) => Self::result_to_ext(v.add_word(d, p, rm), false, true),
+            Flavor::Inf(s) => BigFloat {
+                inner: Flavor::Inf(*s),
             },
-            Flavor::Inf(_) => NAN,
             Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Compares `self` to `d2`.
-    /// Returns positive if `self` > `d2`, negative if `self` < `d2`, zero if `self` == `d2`, None if `self` or `d2` is NaN.
-    pub fn cmp(&self, d2: &BigFloat) -> Option<SignedWord> {
+    /// Multiplies `self` by the word `d` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// This function is faster than `mul` for scaling by small integers.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let x = BigFloat::from_f64(1.25, 128);
+    ///
+    /// assert_eq!(x.mul_word(3, 128, RoundingMode::ToEven), BigFloat::from_f64(3.75, 128));
+    /// assert_eq!(x.div_word(5, 128, RoundingMode::ToEven), BigFloat::from_f64(0.25, 128));
+    /// assert_eq!(x.add_word(2, 128, RoundingMode::ToEven), BigFloat::from_f64(3.25, 128));
+    /// ```
+    pub fn mul_word(&self, d: Word, p: usize, rm: RoundingMode) -> Self {
         match &self.inner {
-            Flavor::Value(v1) => match &d2.inner {
-                Flavor::Value(v2) => Some(v1.cmp(v2)),
-                Flavor::Inf(s2) => {
-                    if *s2 == Sign::Pos {
-                        Some(-1)
-                    } else {
-                        Some(1)
+            Flavor::Value(v) => Self::result_to_ext(v.mul_word(d, p, rm), v.is_zero(), true),
+            Flavor::Inf(s) => {
+                if d == 0 {
+                    NAN
+                } else {
+                    BigFloat {
+                        inner: Flavor::Inf(*s),
                     }
                 }
-                Flavor::NaN(_) => None,
-            },
-            Flavor::Inf(s1) => match &d2.inner {
-                Flavor::Value(_) => Some(*s1 as SignedWord),
-                Flavor::Inf(s2) => Some(*s1 as SignedWord - *s2 as SignedWord),
-                Flavor::NaN(_) => None,
-            },
-            Flavor::NaN(_) => None,
+            }
+            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Compares the absolute value of `self` to the absolute value of `d2`.
-    /// Returns positive if `|self|` is greater than `|d2|`, negative if `|self|` is smaller than `|d2|`, 0 if `|self|` equals to `|d2|`, None if `self` or `d2` is NaN.
-    pub fn abs_cmp(&self, d2: &Self) -> Option<SignedWord> {
+    /// Divides `self` by the word `d` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// This function is faster than `div` for division by small integers.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn div_word(&self, d: Word, p: usize, rm: RoundingMode) -> Self {
         match &self.inner {
-            Flavor::Value(v1) => match &d2.inner {
-                Flavor::Value(v2) => Some(v1.cmp(v2)),
-                Flavor::Inf(_) => Some(-1),
-                Flavor::NaN(_) => None,
-            },
-            Flavor::Inf(_) => match &d2.inner {
-                Flavor::Value(_) => Some(1),
-                Flavor::Inf(_) => Some(0),
-                Flavor::NaN(_) => None,
+            Flavor::Value(v) => {
+                Self::result_to_ext(v.div_word(d, p, rm), v.is_zero(), v.is_positive())
+            }
+            Flavor::Inf(s) => BigFloat {
+                inner: Flavor::Inf(*s),
             },
-            Flavor::NaN(_) => None,
+            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Reverses the sign of `self`.
-    pub fn inv_sign(&mut self) {
-        match &mut self.inner {
-            Flavor::Value(v1) => v1.inv_sign(),
-            Flavor::Inf(s) => self.inner = Flavor::Inf(s.invert()),
-            Flavor::NaN(_) => {}
+    /// Divides `self` by `d2` and returns the result of the operation with precision `p` rounded according to `rm`,
+    /// and a bool value which is true if the quotient of `self` and `d2` is representable exactly with precision `p`.
+    /// The returned bool value does not depend on whether `self` or `d2` are inexact, and it is false if the result is NaN or Inf.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let three = BigFloat::from_word(3, 128);
+    ///
+    /// let (q, exact) = BigFloat::from_word(6, 128).div_exact(&three, 128, RoundingMode::ToEven);
+    /// assert_eq!(q, BigFloat::from_word(2, 128));
+    /// assert!(exact);
+    ///
+    /// let (_, exact) = BigFloat::from_word(1, 128).div_exact(&three, 128, RoundingMode::ToEven);
+    /// assert!(!exact);
+    /// ```
+    pub fn div_exact(&self, d2: &Self, p: usize, rm: RoundingMode) -> (Self, bool) {
+        match (&self.inner, &d2.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2)) => match v1.div_exact(v2, p, rm) {
+                Ok((v, exact)) => (v.into(), exact),
+                Err(e) => (
+                    Self::result_to_ext(Err(e), v1.is_zero(), v1.sign() == v2.sign()),
+                    false,
+                ),
+            },
+            (Flavor::Value(_), Flavor::Inf(_)) => (self.div(d2, p, rm), true),
+            _ => (self.div(d2, p, rm), false),
         }
     }
 
-    /// Compute the power of `self` to the `n` with precision `p`. The result is rounded using the rounding mode `rm`.
-    /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// Computes the square root of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Along with the result the function returns a bool value which is true if the square root of `self`
+    /// is representable exactly with precision `p`.
+    /// The returned bool value does not depend on whether `self` is inexact, and it is false if the result is NaN or Inf.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
-    pub fn pow(&self, n: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let (r, exact) = BigFloat::from_word(9, 128).sqrt_exact(128, RoundingMode::ToEven);
+    /// assert_eq!(r, BigFloat::from_word(3, 128));
+    /// assert!(exact);
+    ///
+    /// let (_, exact) = BigFloat::from_word(2, 128).sqrt_exact(128, RoundingMode::ToEven);
+    /// assert!(!exact);
+    /// ```
+    pub fn sqrt_exact(&self, p: usize, rm: RoundingMode) -> (Self, bool) {
+        match &self.inner {
+            Flavor::Value(v) => match v.sqrt_exact(p, rm) {
+                Ok((v, exact)) => (v.into(), exact),
+                Err(e) => (Self::result_to_ext(Err(e), v.is_zero(), true), false),
+            },
+            _ => (self.sqrt(p, rm), false),
+        }
+    }
+
+    /// Computes the square root of `self`^2 + `d2`^2 + `d3`^2 with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is rounded only once, and intermediate results do not overflow or underflow.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let d = BigFloat::from_word(2, 128).hypot3(&BigFloat::from_word(3, 128), &BigFloat::from_word(6, 128), 128, RoundingMode::ToEven);
+    /// assert_eq!(d, BigFloat::from_word(7, 128));
+    /// ```
+    pub fn hypot3(&self, d2: &Self, d3: &Self, p: usize, rm: RoundingMode) -> Self {
+        trace_op!("hypot3", p);
+
+        Self::norm_op([self, d2, d3].into_iter(), p, rm)
+    }
+
+    /// Computes the Euclidean norm of the vector `xs`, i.e. the square root of the sum of squares of its elements,
+    /// with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is rounded only once, and intermediate results do not overflow or underflow.
+    /// If any of the elements is Inf, the result is positive Inf, otherwise if any of the elements is NaN, the result is NaN.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn norm(xs: &[BigFloat], p: usize, rm: RoundingMode) -> Self {
+        trace_op!("norm", p);
+
+        Self::norm_op(xs.iter(), p, rm)
+    }
+
+    fn norm_op<'a>(
+        xs: impl ExactSizeIterator<Item = &'a BigFloat> + Clone,
+        p: usize,
+        rm: RoundingMode,
+    ) -> Self {
+        if xs.clone().any(|x| x.is_inf()) {
+            return INF_POS;
+        }
+
+        let mut v = Vec::new();
+        if v.try_reserve_exact(xs.len()).is_err() {
+            return Self::nan(Some(Error::MemoryAllocation));
+        }
+
+        for x in xs {
+            match &x.inner {
+                Flavor::Value(x) => v.push(x),
+                Flavor::NaN(err) => return Self::nan(*err),
+                Flavor::Inf(_) => unreachable!(),
+            }
+        }
+
+        Self::result_to_ext(BigFloatNumber::norm(&v, p, rm), false, true)
+    }
+
+    /// Computes the logarithm base 2 of 1 + `self` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is accurate even if `self` is close to zero.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, Consts, RoundingMode};
+    /// let mut cc = Consts::new().unwrap();
+    /// let x = BigFloat::from_word(7, 128);
+    ///
+    /// assert_eq!(x.log2p1(128, RoundingMode::ToEven, &mut cc), BigFloat::from_word(3, 128));
+    /// assert_eq!(x.log2p1(128, RoundingMode::ToEven, &mut cc).exp2m1(128, RoundingMode::ToEven, &mut cc), x);
+    /// ```
+    pub fn log2p1(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("log2p1", p);
+
+        self.logp1_op(p, rm, cc, BigFloatNumber::log2p1)
+    }
+
+    /// Computes the logarithm base 10 of 1 + `self` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is accurate even if `self` is close to zero.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.
+    pub fn log10p1(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("log10p1", p);
+
+        self.logp1_op(p, rm, cc, BigFloatNumber::log10p1)
+    }
+
+    fn logp1_op(
+        &self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+        f: fn(&BigFloatNumber, usize, RoundingMode, &mut Consts) -> Result<BigFloatNumber, Error>,
+    ) -> Self {
+        match &self.inner {
+            Flavor::Value(v) => {
+                if v.is_negative() && v.abs_cmp(&crate::common::consts::ONE) == 0 {
+                    return INF_NEG;
+                }
+                Self::result_to_ext(f(v, p, rm, cc), v.is_zero(), true)
+            }
+            Flavor::Inf(s) => {
+                if s.is_positive() {
+                    INF_POS
+                } else {
+                    NAN
+                }
+            }
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Computes the root of order `n` of `self` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// If `neg_odd` is true and `n` is odd, the root of a negative number is the negative real root,
+    /// otherwise the root of a negative number is NaN.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect, or if `n` is zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, Consts, RoundingMode};
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    /// let x = BigFloat::from_i32(-243, 128);
+    ///
+    /// assert_eq!(x.rootn(5, true, 128, rm, &mut cc), BigFloat::from_i8(-3, 128));
+    /// assert!(x.rootn(5, false, 128, rm, &mut cc).is_nan());
+    /// ```
+    pub fn rootn(
+        &self,
+        n: usize,
+        neg_odd: bool,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Self {
+        trace_op!("rootn", p);
+
+        match &self.inner {
+            Flavor::Value(v) => Self::result_to_ext(v.rootn(n, neg_odd, p, rm, cc), false, true),
+            Flavor::Inf(s) => {
+                if n == 0 {
+                    NAN
+                } else if s.is_positive() {
+                    INF_POS
+                } else if neg_odd && n & 1 == 1 {
+                    INF_NEG
+                } else {
+                    NAN
+                }
+            }
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Computes (1 + `self`)^`n` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is accurate even if `self` is close to zero and `n` is large,
+    /// because 1 + `self` is never rounded before it is raised to the power.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect,
+    /// or if `self` is less than -1.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, Consts, RoundingMode};
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// // 1 + 2^-70 is not representable with precision 64.
+    /// let x = BigFloat::from_word(2, 64).pow(&BigFloat::from_i8(-70, 64), 64, rm, &mut cc);
+    /// let n = BigFloat::from_word(2, 64).pow(&BigFloat::from_i8(70, 64), 64, rm, &mut cc);
+    ///
+    /// let naive = BigFloat::from_word(1, 64).add(&x, 64, rm).pow(&n, 64, rm, &mut cc);
+    /// assert_eq!(naive, BigFloat::from_word(1, 64));
+    ///
+    /// // compound returns the value close to e.
+    /// assert_eq!(x.compound(&n, 64, rm, &mut cc), cc.e(64, rm));
+    /// ```
+    pub fn compound(&self, n: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("compound", p);
+
+        match (&self.inner, &n.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2)) => {
+                Self::result_to_ext(v1.compound(v2, p, rm, cc), false, true)
+            }
+            (Flavor::NaN(err), _) | (_, Flavor::NaN(err)) => Self::nan(*err),
+            _ => Self::from_u8(1, p).add_full_prec(self).pow(n, p, rm, cc),
+        }
+    }
+
+    /// Computes (`self` / `d2`)^`n` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is accurate even if the ratio is close to 1 and `n` is large,
+    /// because the ratio is never rounded before it is raised to the power.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect,
+    /// or if the ratio is negative and `n` is not an integer number.
+    pub fn pow_ratio(
+        &self,
+        d2: &Self,
+        n: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Self {
+        trace_op!("pow_ratio", p);
+
+        match (&self.inner, &d2.inner, &n.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2), Flavor::Value(v3)) if !v2.is_zero() => {
+                Self::result_to_ext(v1.pow_ratio(v2, v3, p, rm, cc), false, true)
+            }
+            _ => self.div(d2, p, rm).pow(n, p, rm, cc),
+        }
+    }
+
+    /// Rounds `self` to a multiple of `rdx`^`e` using rounding mode `rm`, so the least significant retained digit
+    /// of the number in radix `rdx` is at the position `e`. The result is then rounded to precision `p` using the rounding mode `rm`.
+    /// Along with the result the function returns a bool value which is true if `self` is a multiple of `rdx`^`e`,
+    /// i.e. no non-zero digits were discarded. The bool value is false if the result is NaN or Inf.
+    /// If `rdx` is `Radix::Dec` and `e` is negative, the result is the quantized decimal value rounded to precision `p`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Radix;
+    /// # use astro_float_num::RoundingMode;
+    /// let rm = RoundingMode::ToEven;
+    /// let price = BigFloat::parse("12.3449", Radix::Dec, 128, rm);
+    ///
+    /// // round to cents
+    /// let (cents, exact) = price.quantize(-2, Radix::Dec, 128, rm);
+    /// assert_eq!(cents, BigFloat::parse("12.34", Radix::Dec, 128, rm));
+    /// assert!(!exact);
+    ///
+    /// let (_, exact) = cents.quantize(-2, Radix::Dec, 128, rm);
+    /// assert!(!exact); // 12.34 is not representable in binary exactly
+    ///
+    /// let (_, exact) = BigFloat::from_f64(12.25, 128).quantize(-2, Radix::Dec, 128, rm);
+    /// assert!(exact);
+    /// ```
+    pub fn quantize(&self, e: Exponent, rdx: Radix, p: usize, rm: RoundingMode) -> (Self, bool) {
+        match &self.inner {
+            Flavor::Value(v) => match v.quantize(e, rdx, p, rm) {
+                Ok((v, exact)) => (v.into(), exact),
+                Err(e) => (Self::result_to_ext(Err(e), v.is_zero(), true), false),
+            },
+            _ => (self.clone(), false),
+        }
+    }
+
+    /// Returns the remainder of division of `|self|` by `|d2|`. The sign of the result is set to the sign of `self`.
+    /// This is the truncated modulo, which is consistent with the `%` operator for primitive types:
+    /// `self = q * d2 + r`, where `q` is the quotient of division of `self` by `d2` rounded towards zero.
+    /// The result is exact, and its precision is the smallest precision which can hold the value.
+    /// The operator `%` for BigFloat computes the same value.
+    /// The function returns NaN if `d2` is zero, if `self` is Inf, or if any of the arguments is NaN.
+    /// If `d2` is Inf and `self` is a finite number, the result is `self`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// let x = BigFloat::from_f64(-7.5, 64);
+    /// let y = BigFloat::from_word(2, 64);
+    ///
+    /// assert_eq!(x.rem(&y), BigFloat::from_f64(-1.5, 64));
+    /// assert_eq!(&x % &y, BigFloat::from_f64(-1.5, 64));
+    /// assert!(x.rem(&BigFloat::new(64)).is_nan());
+    /// ```
+    pub fn rem(&self, d2: &Self) -> Self {
+        match &self.inner {
+            Flavor::Value(v1) => match &d2.inner {
+                Flavor::Value(v2) => {
+                    Self::result_to_ext(v1.rem(v2), v1.is_zero(), v1.sign() == v2.sign())
+                }
+                Flavor::Inf(_) => self.clone(),
+                Flavor::NaN(err) => Self::nan(*err),
+            },
+            Flavor::Inf(_) => NAN,
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Returns the Euclidean remainder of division of `self` by `d2`, which is the non-negative number `r` such that
+    /// `self = q * d2 + r` for an integer `q`, and `r < |d2|`, like `rem_euclid` for primitive types.
+    /// The result is exact, and a negative zero remainder keeps its sign.
+    /// The function returns NaN if `d2` is zero, if `self` is Inf, or if any of the arguments is NaN.
+    /// If `d2` is Inf, the result is `self` for non-negative `self`, and positive Inf for negative `self`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// let x = BigFloat::from_f64(-7.5, 64);
+    ///
+    /// assert_eq!(x.rem_euclid(&BigFloat::from_word(2, 64)), BigFloat::from_f64(0.5, 64));
+    /// assert_eq!(x.rem_euclid(&BigFloat::from_i8(-2, 64)), BigFloat::from_f64(0.5, 64));
+    /// ```
+    pub fn rem_euclid(&self, d2: &Self) -> Self {
+        match &self.inner {
+            Flavor::Value(v1) => match &d2.inner {
+                Flavor::Value(v2) => Self::result_to_ext(v1.rem_euclid(v2), v1.is_zero(), true),
+                Flavor::Inf(_) => {
+                    if v1.is_negative() && !v1.is_zero() {
+                        INF_POS
+                    } else {
+                        self.clone()
+                    }
+                }
+                Flavor::NaN(err) => Self::nan(*err),
+            },
+            Flavor::Inf(_) => NAN,
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Returns the integer quotient `q` of division of `self` by `d2` rounded towards zero, and the remainder `r`.
+    /// Both values are exact and satisfy `self = q * d2 + r`, where `|r| < |d2|`, and the sign of `r` is the sign of `self`.
+    /// The values are computed with a single integer division of the mantissas.
+    /// The precision of the quotient and of the remainder is the smallest precision which can hold the value.
+    /// Note that the quotient of numbers with distant exponents can take a large amount of memory.
+    /// If `d2` is zero, the quotient is Inf, or NaN if `self` is also zero, and the remainder is NaN.
+    /// The function returns NaN for both values if `self` is Inf, or if any of the arguments is NaN.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// // 7.5 = 2 * 3 + 1.5
+    /// let (q, r) = BigFloat::from_f64(7.5, 64).div_rem(&BigFloat::from_word(3, 64));
+    ///
+    /// assert_eq!(q, BigFloat::from_word(2, 64));
+    /// assert_eq!(r, BigFloat::from_f64(1.5, 64));
+    ///
+    /// // the quotient is exact
+    /// let (q, r) = BigFloat::from_f64(1.0e30, 64).div_rem(&BigFloat::from_f64(0.125, 64));
+    ///
+    /// assert_eq!(q, BigFloat::from_f64(8.0e30, 64));
+    /// assert!(r.is_zero());
+    /// ```
+    pub fn div_rem(&self, d2: &Self) -> (Self, Self) {
+        match (&self.inner, &d2.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2)) => match v1.div_rem(v2) {
+                Ok((q, r)) => (q.into(), r.into()),
+                Err(e) => {
+                    let r = match e {
+                        Error::MemoryAllocation | Error::InvalidArgument => Self::nan(Some(e)),
+                        _ => NAN,
+                    };
+
+                    (
+                        Self::result_to_ext(Err(e), v1.is_zero(), v1.sign() == v2.sign()),
+                        r,
+                    )
+                }
+            },
+            (Flavor::Value(v1), Flavor::Inf(s)) => {
+                let mut q = Self::new(WORD_BIT_SIZE);
+                q.set_sign(if v1.sign() == *s { Sign::Pos } else { Sign::Neg });
+                (q, self.clone())
+            }
+            (Flavor::NaN(err), _) | (_, Flavor::NaN(err)) => (Self::nan(*err), Self::nan(*err)),
+            (Flavor::Inf(_), _) => (NAN, NAN),
+        }
+    }
+
+    /// Reduces an angle in radians modulo 2*pi and returns the remainder `r` with precision `p` rounded using the rounding mode `rm`,
+    /// and the integer quotient `q`, such that `self` = 2*pi*q + r.
+    /// If `centered` is true, the remainder is in the interval (-pi, pi], otherwise it is in the interval [0, 2*pi).
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN for both values if `self` is NaN or Inf, or if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut cc = Consts::new().expect("Constants cache allocated");
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// let pi = cc.pi(256, rm);
+    /// let x = pi.mul(&BigFloat::from_word(7, 64), 256, rm);
+    ///
+    /// let (r, q) = x.rem_2pi_quo(true, 128, rm, &mut cc);
+    ///
+    /// assert_eq!(q, BigFloat::from_word(3, 64));
+    /// assert_eq!(r, cc.pi(128, rm));
+    /// ```
+    pub fn rem_2pi_quo(
+        &self,
+        centered: bool,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> (Self, Self) {
+        match &self.inner {
+            Flavor::Value(v) => match v.rem_2pi_quo(centered, p, rm, cc) {
+                Ok((r, q)) => (r.into(), q.into()),
+                Err(e) => {
+                    let err = Self::result_to_ext(Err(e), false, true);
+                    (err.clone(), err)
+                }
+            },
+            Flavor::Inf(_) => (NAN, NAN),
+            Flavor::NaN(err) => (Self::nan(*err), Self::nan(*err)),
+        }
+    }
+
+    /// Reduces an angle in radians modulo pi/2 and returns the remainder `r` with precision `p` rounded using the rounding mode `rm`,
+    /// and the quadrant `k`, such that `self` = pi/2*(4*n + k) + r for an integer n, and `r` is in the interval (-pi/4, pi/4].
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN and quadrant 0 if `self` is NaN or Inf, or if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut cc = Consts::new().expect("Constants cache allocated");
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// let x = BigFloat::from_word(3, 64);
+    /// let (r, k) = x.rem_pi2(128, rm, &mut cc);
+    ///
+    /// // 3 = pi - (pi - 3)
+    /// assert_eq!(k, 2);
+    /// assert_eq!(r, x.sub(&cc.pi(256, rm), 128, rm));
+    /// ```
+    pub fn rem_pi2(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> (Self, usize) {
+        self.quadrant_op(p, rm, cc, BigFloatNumber::rem_pi2)
+    }
+
+    /// Computes the sine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Along with the result the function returns the quadrant of the argument as described in [BigFloat::rem_pi2],
+    /// which allows related functions to reuse the argument reduction.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN and quadrant 0 if `self` is NaN or Inf, or if the precision `p` is incorrect.
+    pub fn sin_quadrant(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> (Self, usize) {
+        self.quadrant_op(p, rm, cc, BigFloatNumber::sin_quadrant)
+    }
+
+    /// Computes the cosine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Along with the result the function returns the quadrant of the argument as described in [BigFloat::rem_pi2].
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN and quadrant 0 if `self` is NaN or Inf, or if the precision `p` is incorrect.
+    pub fn cos_quadrant(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> (Self, usize) {
+        self.quadrant_op(p, rm, cc, BigFloatNumber::cos_quadrant)
+    }
+
+    /// Computes the tangent of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Along with the result the function returns the quadrant of the argument as described in [BigFloat::rem_pi2].
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN and quadrant 0 if `self` is NaN or Inf, or if the precision `p` is incorrect.
+    pub fn tan_quadrant(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> (Self, usize) {
+        self.quadrant_op(p, rm, cc, BigFloatNumber::tan_quadrant)
+    }
+
+    fn quadrant_op<F>(&self, p: usize, rm: RoundingMode, cc: &mut Consts, f: F) -> (Self, usize)
+    where
+        F: FnOnce(
+            &BigFloatNumber,
+            usize,
+            RoundingMode,
+            &mut Consts,
+        ) -> Result<(BigFloatNumber, usize), Error>,
+    {
+        match &self.inner {
+            Flavor::Value(v) => match f(v, p, rm, cc) {
+                Ok((r, k)) => (r.into(), k),
+                Err(e) => (Self::result_to_ext(Err(e), false, true), 0),
+            },
+            Flavor::Inf(_) => (NAN, 0),
+            Flavor::NaN(err) => (Self::nan(*err), 0),
+        }
+    }
+
+    /// Compares `self` to `d2`.
+    /// Returns positive if `self` > `d2`, negative if `self` < `d2`, zero if `self` == `d2`, None if `self` or `d2` is NaN.
+    pub fn cmp(&self, d2: &BigFloat) -> Option<SignedWord> {
+        match &self.inner {
+            Flavor::Value(v1) => match &d2.inner {
+                Flavor::Value(v2) => Some(v1.cmp(v2)),
+                Flavor::Inf(s2) => {
+                    if *s2 == Sign::Pos {
+                        Some(-1)
+                    } else {
+                        Some(1)
+                    }
+                }
+                Flavor::NaN(_) => None,
+            },
+            Flavor::Inf(s1) => match &d2.inner {
+                Flavor::Value(_) => Some(*s1 as SignedWord),
+                Flavor::Inf(s2) => Some(*s1 as SignedWord - *s2 as SignedWord),
+                Flavor::NaN(_) => None,
+            },
+            Flavor::NaN(_) => None,
+        }
+    }
+
+    /// Compares the absolute value of `self` to the absolute value of `d2`.
+    /// Returns positive if `|self|` is greater than `|d2|`, negative if `|self|` is smaller than `|d2|`, 0 if `|self|` equals to `|d2|`, None if `self` or `d2` is NaN.
+    pub fn abs_cmp(&self, d2: &Self) -> Option<SignedWord> {
+        match &self.inner {
+            Flavor::Value(v1) => match &d2.inner {
+                Flavor::Value(v2) => Some(v1.abs_cmp(v2)),
+                Flavor::Inf(_) => Some(-1),
+                Flavor::NaN(_) => None,
+            },
+            Flavor::Inf(_) => match &d2.inner {
+                Flavor::Value(_) => Some(1),
+                Flavor::Inf(_) => Some(0),
+                Flavor::NaN(_) => None,
+            },
+            Flavor::NaN(_) => None,
+        }
+    }
+
+    /// Compares the absolute value of `self` to the absolute value of `d2`.
+    /// Returns the ordering of `|self|` relative to `|d2|`, or None if `self` or `d2` is NaN.
+    pub fn cmp_abs(&self, d2: &Self) -> Option<Ordering> {
+        self.abs_cmp(d2).map(|c| c.cmp(&0))
+    }
+
+    /// Reverses the sign of `self`.
+    pub fn inv_sign(&mut self) {
+        match &mut self.inner {
+            Flavor::Value(v1) => v1.inv_sign(),
+            Flavor::Inf(s) => self.inner = Flavor::Inf(s.invert()),
+            Flavor::NaN(_) => {}
+        }
+    }
+
+    /// Compute the power of `self` to the `n` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn pow(&self, n: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("pow", p);
+
         match &self.inner {
             Flavor::Value(v1) => {
                 match &n.inner {
@@ -406,330 +1381,1251 @@ impl BigFloat {
                             Self::from_u8(1, p)
                         }
                     }
-                    Flavor::NaN(err) => Self::nan(*err),
+                    Flavor::NaN(err) => Self::nan(*err),
+                }
+            }
+            Flavor::Inf(s1) => {
+                match &n.inner {
+                    Flavor::Value(v2) => {
+                        // inf ^ v2
+                        if v2.is_zero() {
+                            Self::from_u8(1, p)
+                        } else if v2.is_positive() {
+                            if s1.is_negative() && v2.is_odd_int() {
+                                // v2 is odd and has no fractional part.
+                                INF_NEG
+                            } else {
+                                INF_POS
+                            }
+                        } else {
+                            Self::new(p)
+                        }
+                    }
+                    Flavor::Inf(s2) => {
+                        // inf^inf
+                        if s2.is_positive() {
+                            INF_POS
+                        } else {
+                            Self::new(p)
+                        }
+                    }
+                    Flavor::NaN(err) => Self::nan(*err),
+                }
+            }
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Prepares `self` as the base for computing many powers with precision `p`.
+    /// The returned `PowCache` keeps the logarithm of the base between calls,
+    /// which makes raising the same base to different exponents considerably faster than calling `pow` repeatedly.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut cc = Consts::new().expect("Constants cache initialized");
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// let base = BigFloat::from_f64(1.5, 128);
+    /// let mut pb = base.prepare_pow(128);
+    ///
+    /// for i in 1..10 {
+    ///     let n = BigFloat::from_f64(0.7 * i as f64, 64);
+    ///     assert_eq!(pb.pow(&n, rm, &mut cc), base.pow(&n, 128, rm, &mut cc));
+    /// }
+    /// ```
+    pub fn prepare_pow(&self, p: usize) -> PowCache {
+        PowCache {
+            base: self.clone(),
+            p,
+            ln: None,
+        }
+    }
+
+    /// Compute the power of `self` to the integer `n` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn powi(&self, n: usize, p: usize, rm: RoundingMode) -> Self {
+        trace_op!("powi", p);
+
+        match &self.inner {
+            Flavor::Value(v1) => Self::result_to_ext(v1.powi(n, p, rm), false, true),
+            Flavor::Inf(s1) => {
+                // inf ^ v2
+                if n == 0 {
+                    Self::from_u8(1, p)
+                } else if s1.is_negative() && (n & 1 == 1) {
+                    INF_NEG
+                } else {
+                    INF_POS
+                }
+            }
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Computes the logarithm base `n` of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn log(&self, n: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("log", p);
+
+        match &self.inner {
+            Flavor::Value(v1) => {
+                match &n.inner {
+                    Flavor::Value(v2) => {
+                        if v2.is_zero() {
+                            return INF_NEG;
+                        }
+                        Self::result_to_ext(v1.log(v2, p, rm, cc), false, true)
+                    }
+                    Flavor::Inf(s2) => {
+                        // v1.log(inf)
+                        if s2.is_positive() {
+                            Self::new(p)
+                        } else {
+                            NAN
+                        }
+                    }
+                    Flavor::NaN(err) => Self::nan(*err),
+                }
+            }
+            Flavor::Inf(s1) => {
+                if *s1 == Sign::Neg {
+                    // -inf.log(any)
+                    NAN
+                } else {
+                    match &n.inner {
+                        Flavor::Value(v2) => {
+                            // +inf.log(v2)
+                            if v2.exponent() <= 0 {
+                                INF_NEG
+                            } else {
+                                INF_POS
+                            }
+                        }
+                        Flavor::Inf(_) => NAN, // +inf.log(inf)
+                        Flavor::NaN(err) => Self::nan(*err),
+                    }
+                }
+            }
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Returns true if `self` is positive.
+    /// The function returns false if `self` is NaN.
+    pub fn is_positive(&self) -> bool {
+        match &self.inner {
+            Flavor::Value(v) => v.is_positive(),
+            Flavor::Inf(s) => *s == Sign::Pos,
+            Flavor::NaN(_) => false,
+        }
+    }
+
+    /// Returns true if `self` is negative.
+    /// The function returns false if `self` is NaN.
+    pub fn is_negative(&self) -> bool {
+        match &self.inner {
+            Flavor::Value(v) => v.is_negative(),
+            Flavor::Inf(s) => *s == Sign::Neg,
+            Flavor::NaN(_) => false,
+        }
+    }
+
+    /// Returns true if `self` is subnormal. A number is subnormal if the most significant bit of the mantissa is not equal to 1.
+    pub fn is_subnormal(&self) -> bool {
+        if let Flavor::Value(v) = &self.inner {
+            return v.is_subnormal();
+        }
+        false
+    }
+
+    /// Returns true if `self` is zero.
+    pub fn is_zero(&self) -> bool {
+        match &self.inner {
+            Flavor::Value(v) => v.is_zero(),
+            Flavor::Inf(_) => false,
+            Flavor::NaN(_) => false,
+        }
+    }
+
+    /// Restricts the value of `self` to an interval determined by the values of `min` and `max`.
+    /// The function returns `max` if `self` is greater than `max`, `min` if `self` is less than `min`, and `self` otherwise.
+    /// If either argument is NaN or `min` is greater than `max`, the function returns NaN.
+    pub fn clamp(&self, min: &Self, max: &Self) -> Self {
+        if self.is_nan() || min.is_nan() || max.is_nan() || max.cmp(min).unwrap() < 0 {
+            // call to unwrap() is unreacheable
+            NAN
+        } else if self.cmp(min).unwrap() < 0 {
+            // call to unwrap() is unreacheable
+            let mut ret = min.clone();
+            ret.set_inexact(ret.inexact() || self.inexact());
+            ret
+        } else if self.cmp(max).unwrap() > 0 {
+            // call to unwrap() is unreacheable
+            let mut ret = max.clone();
+            ret.set_inexact(ret.inexact() || self.inexact());
+            ret
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns the value of `d1` if `d1` is greater than `self`, or the value of `self` otherwise.
+    /// If either argument is NaN, the function returns NaN.
+    pub fn max(&self, d1: &Self) -> Self {
+        if self.is_nan() || d1.is_nan() {
+            NAN
+        } else if self.cmp(d1).unwrap() < 0 {
+            // call to unwrap() is unreacheable
+            d1.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns value of `d1` if `d1` is less than `self`, or the value of `self` otherwise.
+    /// If either argument is NaN, the function returns NaN.
+    pub fn min(&self, d1: &Self) -> Self {
+        if self.is_nan() || d1.is_nan() {
+            NAN
+        } else if self.cmp(d1).unwrap() > 0 {
+            // call to unwrap() is unreacheable
+            d1.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns the value of `d1` if `d1` is greater than `self`, or the value of `self` otherwise.
+    /// Unlike [BigFloat::max], if one of the arguments is NaN, the other argument is returned, like `fmax` of the C standard library.
+    /// If both arguments are NaN, the function returns NaN.
+    pub fn fmax(&self, d1: &Self) -> Self {
+        if self.is_nan() {
+            d1.clone()
+        } else if d1.is_nan() {
+            self.clone()
+        } else {
+            self.max(d1)
+        }
+    }
+
+    /// Returns the value of `d1` if `d1` is less than `self`, or the value of `self` otherwise.
+    /// Unlike [BigFloat::min], if one of the arguments is NaN, the other argument is returned, like `fmin` of the C standard library.
+    /// If both arguments are NaN, the function returns NaN.
+    pub fn fmin(&self, d1: &Self) -> Self {
+        if self.is_nan() {
+            d1.clone()
+        } else if d1.is_nan() {
+            self.clone()
+        } else {
+            self.min(d1)
+        }
+    }
+
+    /// Returns the greater of `self` and `d1` as specified by the `maximum` operation of IEEE 754-2019.
+    /// Unlike [BigFloat::max], positive zero is considered greater than negative zero.
+    /// If either argument is NaN, the function returns NaN.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::NAN;
+    /// let z = BigFloat::from_word(0, 64);
+    ///
+    /// assert!(z.neg().maximum(&z).is_positive());
+    /// assert!(z.maximum(&NAN).is_nan());
+    /// ```
+    pub fn maximum(&self, d1: &Self) -> Self {
+        if self.is_nan() {
+            self.clone()
+        } else if d1.is_nan() || self.total_cmp_zero(d1) < 0 {
+            d1.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns the lesser of `self` and `d1` as specified by the `minimum` operation of IEEE 754-2019.
+    /// Unlike [BigFloat::min], negative zero is considered less than positive zero.
+    /// If either argument is NaN, the function returns NaN.
+    pub fn minimum(&self, d1: &Self) -> Self {
+        if self.is_nan() {
+            self.clone()
+        } else if d1.is_nan() || self.total_cmp_zero(d1) > 0 {
+            d1.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns the greater of `self` and `d1` as specified by the `maximumNumber` operation of IEEE 754-2019.
+    /// Positive zero is considered greater than negative zero.
+    /// If one of the arguments is NaN, the other argument is returned.
+    /// If both arguments are NaN, the function returns NaN.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::NAN;
+    /// let d1 = BigFloat::from_word(2, 64);
+    ///
+    /// assert_eq!(d1.maximum_number(&NAN), d1);
+    /// assert_eq!(NAN.maximum_number(&d1), d1);
+    /// ```
+    pub fn maximum_number(&self, d1: &Self) -> Self {
+        if self.is_nan() {
+            d1.clone()
+        } else if d1.is_nan() {
+            self.clone()
+        } else {
+            self.maximum(d1)
+        }
+    }
+
+    /// Returns the lesser of `self` and `d1` as specified by the `minimumNumber` operation of IEEE 754-2019.
+    /// Negative zero is considered less than positive zero.
+    /// If one of the arguments is NaN, the other argument is returned.
+    /// If both arguments are NaN, the function returns NaN.
+    pub fn minimum_number(&self, d1: &Self) -> Self {
+        if self.is_nan() {
+            d1.clone()
+        } else if d1.is_nan() {
+            self.clone()
+        } else {
+            self.minimum(d1)
+        }
+    }
+
+    // Compares `self` to `d1` which are not NaN, negative zero is less than positive zero.
+    fn total_cmp_zero(&self, d1: &Self) -> SignedWord {
+        // call to unwrap() is unreacheable
+        match self.cmp(d1).unwrap() {
+            0 if self.is_zero() => {
+                d1.is_negative() as SignedWord - self.is_negative() as SignedWord
+            }
+            c => c,
+        }
+    }
+
+    /// Returns the positive difference of `self` and `d1`, i.e. `self - d1` if `self` is greater than `d1`, or positive zero otherwise,
+    /// like `fdim` of the C standard library.
+    /// The result has precision `p` and is rounded using rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if either argument is NaN, or if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let rm = RoundingMode::ToEven;
+    /// let d1 = BigFloat::from_word(5, 64);
+    /// let d2 = BigFloat::from_word(3, 64);
+    ///
+    /// assert_eq!(d1.positive_difference(&d2, 64, rm), BigFloat::from_word(2, 64));
+    /// assert!(d2.positive_difference(&d1, 64, rm).is_zero());
+    /// ```
+    pub fn positive_difference(&self, d1: &Self, p: usize, rm: RoundingMode) -> Self {
+        match self.cmp(d1) {
+            None => NAN,
+            Some(c) if c > 0 => self.sub(d1, p, rm),
+            _ => Self::from_word(0, p),
+        }
+    }
+
+    /// Returns the value of `d1` if the absolute value of `d1` is greater than the absolute value of `self`,
+    /// or the value of `self` otherwise.
+    /// If either argument is NaN, the function returns NaN.
+    pub fn max_abs(&self, d1: &Self) -> Self {
+        match self.abs_cmp(d1) {
+            None => NAN,
+            Some(c) if c < 0 => d1.clone(),
+            _ => self.clone(),
+        }
+    }
+
+    /// Returns the value of `d1` if the absolute value of `d1` is less than the absolute value of `self`,
+    /// or the value of `self` otherwise.
+    /// If either argument is NaN, the function returns NaN.
+    pub fn min_abs(&self, d1: &Self) -> Self {
+        match self.abs_cmp(d1) {
+            None => NAN,
+            Some(c) if c > 0 => d1.clone(),
+            _ => self.clone(),
+        }
+    }
+
+    /// Returns a BigFloat with the value -1 if `self` is negative, 1 if `self` is positive, zero otherwise.
+    /// The function returns NaN If `self` is NaN.
+    pub fn signum(&self) -> Self {
+        if self.is_nan() {
+            NAN
+        } else {
+            let mut ret = Self::from_u8(1, DEFAULT_P);
+            if self.is_negative() {
+                ret.inv_sign();
+            }
+            ret.set_inexact(self.inexact());
+            ret
+        }
+    }
+
+    /// Parses a number from the string `s` with precision sufficient to hold `n` digits in radix `rdx`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// See [`parse`](Self::parse) for the format of `s`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Radix;
+    /// # use astro_float_num::RoundingMode;
+    /// let n = BigFloat::parse_digits("0.1234567890123456789012345", Radix::Dec, 25, RoundingMode::ToEven);
+    ///
+    /// assert_eq!(n, BigFloat::parse("0.1234567890123456789012345", Radix::Dec, 84, RoundingMode::ToEven));
+    /// ```
+    pub fn parse_digits(s: &str, rdx: Radix, n: usize, rm: RoundingMode) -> Self {
+        Self::parse(s, rdx, rdx.digits_to_bits(n), rm)
+    }
+
+    /// Parses a number from the string `s`.
+    /// The function expects `s` to be a number in scientific format in base 10, or +-Inf, or NaN.
+    /// If the exponent of the number is too large, the result is Inf with the sign of the number,
+    /// or the largest finite number with the sign of the number marked as inexact when `rm` rounds towards zero.
+    /// If the number is too small to be represented, the result is zero, or the minimum positive subnormal
+    /// number with the sign of the number when `rm` rounds away from zero, and the result is marked as inexact.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Radix;
+    /// # use astro_float_num::RoundingMode;
+    /// let n = BigFloat::parse("0.0", Radix::Bin, 64, RoundingMode::ToEven);
+    /// assert!(n.is_zero());
+    ///
+    /// let n = BigFloat::parse("1.124e-24", Radix::Dec, 128, RoundingMode::ToEven);
+    /// assert!(n.sub(&BigFloat::from_f64(1.124e-24, 128), 128, RoundingMode::ToEven).exponent() <= Some(-52 - 24));
+    ///
+    /// let n = BigFloat::parse("-Inf", Radix::Hex, 1, RoundingMode::None);
+    /// assert!(n.is_inf_neg());
+    ///
+    /// let n = BigFloat::parse("NaN", Radix::Oct, 2, RoundingMode::None);
+    /// assert!(n.is_nan());
+    ///
+    /// let n = BigFloat::parse("-1e99999999999", Radix::Dec, 64, RoundingMode::ToEven);
+    /// assert!(n.is_inf_neg());
+    ///
+    /// let n = BigFloat::parse("-1e99999999999", Radix::Dec, 64, RoundingMode::Up);
+    /// assert!(n == BigFloat::max_value(64).neg() && n.inexact());
+    ///
+    /// let n = BigFloat::parse("1e-99999999999", Radix::Dec, 64, RoundingMode::ToEven);
+    /// assert!(n.is_zero() && n.is_inexact());
+    ///
+    /// let n = BigFloat::parse("1e-99999999999", Radix::Dec, 64, RoundingMode::Up);
+    /// assert!(n.is_subnormal() && n.is_inexact());
+    /// ```
+    pub fn parse(s: &str, rdx: Radix, p: usize, rm: RoundingMode) -> Self {
+        match crate::parser::parse(s, rdx) {
+            Ok(ps) => {
+                if ps.is_inf() && !ps.is_exp_overflow() {
+                    if ps.sign() == Sign::Pos {
+                        INF_POS
+                    } else {
+                        INF_NEG
+                    }
+                } else if ps.is_nan() {
+                    NAN
+                } else {
+                    Self::result_to_ext(
+                        BigFloatNumber::from_parser_state(&ps, rdx, p, rm),
+                        false,
+                        true,
+                    )
                 }
             }
-            Flavor::Inf(s1) => {
-                match &n.inner {
-                    Flavor::Value(v2) => {
-                        // inf ^ v2
-                        if v2.is_zero() {
-                            Self::from_u8(1, p)
-                        } else if v2.is_positive() {
-                            if s1.is_negative() && v2.is_odd_int() {
-                                // v2 is odd and has no fractional part.
-                                INF_NEG
-                            } else {
-                                INF_POS
-                            }
+            Err(e) => Self::nan(Some(e)),
+        }
+    }
+
+    pub(crate) fn write_str<T: Write>(
+        &self,
+        w: &mut T,
+        rdx: Radix,
+        rm: RoundingMode,
+    ) -> Result<(), core::fmt::Error> {
+        match &self.inner {
+            Flavor::Value(v) => match v.format(rdx, rm) {
+                Ok(s) => w.write_str(&s),
+                Err(e) => match e {
+                    Error::ExponentOverflow(s) => {
+                        if s.is_positive() {
+                            w.write_str("Inf")
                         } else {
-                            Self::new(p)
+                            w.write_str("-Inf")
                         }
                     }
-                    Flavor::Inf(s2) => {
-                        // inf^inf
-                        if s2.is_positive() {
-                            INF_POS
-                        } else {
-                            Self::new(p)
-                        }
+                    _ => w.write_str("Err"),
+                },
+            },
+            Flavor::Inf(sign) => {
+                let s = if sign.is_negative() { "-Inf" } else { "Inf" };
+                w.write_str(s)
+            }
+            crate::ext::Flavor::NaN(_) => w.write_str("NaN"),
+        }
+    }
+
+    // Formats the number using radix `rdx` honoring the width, fill, alignment, sign, and precision options of `f`.
+    // The precision is the number of mantissa digits after the point.
+    fn fmt_rdx(&self, f: &mut Formatter<'_>, rdx: Radix) -> Result<(), core::fmt::Error> {
+        let mut buf = Vec::new();
+
+        let s = match &self.inner {
+            Flavor::Value(v) => {
+                let ret = match f.precision() {
+                    Some(prec) => v.format_prec_into(rdx, DEFAULT_RM, prec, &mut buf),
+                    None => v.format_into(rdx, DEFAULT_RM, &mut buf),
+                };
+
+                match ret {
+                    Ok(_) => v.sign(),
+                    Err(Error::ExponentOverflow(s)) => {
+                        buf.clear();
+                        buf.extend_from_slice(b"Inf");
+                        s
                     }
-                    Flavor::NaN(err) => Self::nan(*err),
+                    Err(_) => return Self::pad_unsigned(f, "Err"),
                 }
             }
-            Flavor::NaN(err) => Self::nan(*err),
-        }
+            Flavor::Inf(s) => {
+                buf.extend_from_slice(b"Inf");
+                *s
+            }
+            Flavor::NaN(_) => return Self::pad_unsigned(f, "NaN"),
+        };
+
+        let digits = core::str::from_utf8(&buf).map_err(|_| core::fmt::Error)?;
+
+        f.pad_integral(s.is_positive(), "", digits.trim_start_matches('-'))
     }
 
-    /// Compute the power of `self` to the integer `n` with precision `p`. The result is rounded using the rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
-    /// The function returns NaN if the precision `p` is incorrect.
-    pub fn powi(&self, n: usize, p: usize, rm: RoundingMode) -> Self {
+    // Writes `s` padded according to the width, fill, and alignment options of `f`.
+    fn pad_unsigned(f: &mut Formatter<'_>, s: &str) -> Result<(), core::fmt::Error> {
+        let pad = f.width().unwrap_or(0).saturating_sub(s.chars().count());
+
+        let (pre, post) = match f.align() {
+            Some(Alignment::Left) => (0, pad),
+            Some(Alignment::Center) => (pad / 2, pad - pad / 2),
+            _ => (pad, 0),
+        };
+
+        let fill = f.fill();
+
+        (0..pre).try_for_each(|_| f.write_char(fill))?;
+        f.write_str(s)?;
+        (0..post).try_for_each(|_| f.write_char(fill))
+    }
+
+    /// Returns a random normalized (not subnormal) BigFloat number with exponent in the range
+    /// from `exp_from` to `exp_to` inclusive. The sign can be positive and negative. Zero is excluded.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// Function does not follow any specific distribution law.
+    /// The intended use of this function is for testing.
+    /// The function returns NaN if the precision `p` is incorrect or when `exp_from` is less than EXPONENT_MIN or `exp_to` is greater than EXPONENT_MAX.
+    #[cfg(feature = "random")]
+    pub fn random_normal(p: usize, exp_from: Exponent, exp_to: Exponent) -> Self {
+        Self::result_to_ext(
+            BigFloatNumber::random_normal(p, exp_from, exp_to),
+            false,
+            true,
+        )
+    }
+
+    /// Returns category of `self`.
+    pub fn classify(&self) -> FpCategory {
         match &self.inner {
-            Flavor::Value(v1) => Self::result_to_ext(v1.powi(n, p, rm), false, true),
-            Flavor::Inf(s1) => {
-                // inf ^ v2
-                if n == 0 {
-                    Self::from_u8(1, p)
-                } else if s1.is_negative() && (n & 1 == 1) {
-                    INF_NEG
+            Flavor::Value(v) => {
+                if v.is_subnormal() {
+                    FpCategory::Subnormal
+                } else if v.is_zero() {
+                    FpCategory::Zero
                 } else {
-                    INF_POS
+                    FpCategory::Normal
                 }
             }
+            Flavor::Inf(_) => FpCategory::Infinite,
+            Flavor::NaN(_) => FpCategory::Nan,
+        }
+    }
+
+    /// Computes the arctangent of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn atan(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("atan", p);
+
+        match &self.inner {
+            Flavor::Value(v) => Self::result_to_ext(v.atan(p, rm, cc), v.is_zero(), true),
+            Flavor::Inf(s) => Self::result_to_ext(Self::half_pi(*s, p, rm, cc), false, true),
             Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Computes the logarithm base `n` of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Computes the arctangent of a number divided by pi with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is in half-revolutions, in the interval [-1/2, 1/2].
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
-    pub fn log(&self, n: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+    pub fn atanpi(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("atanpi", p);
+
         match &self.inner {
-            Flavor::Value(v1) => {
-                match &n.inner {
-                    Flavor::Value(v2) => {
-                        if v2.is_zero() {
-                            return INF_NEG;
-                        }
-                        Self::result_to_ext(v1.log(v2, p, rm, cc), false, true)
-                    }
-                    Flavor::Inf(s2) => {
-                        // v1.log(inf)
-                        if s2.is_positive() {
-                            Self::new(p)
-                        } else {
-                            NAN
-                        }
-                    }
-                    Flavor::NaN(err) => Self::nan(*err),
-                }
+            Flavor::Value(v) => Self::result_to_ext(v.atanpi(p, rm, cc), v.is_zero(), true),
+            Flavor::Inf(s) => Self::from_i8(s.to_int(), p).div(&TWO, p, rm),
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Computes the angle between the positive x axis and the point (`d2`, `self`) divided by pi with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// The result is in half-revolutions, in the interval [-1, 1], and its sign is the sign of `self`, including the sign of zero.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, Consts, RoundingMode};
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    /// let one = BigFloat::from_word(1, 64);
+    ///
+    /// // the point (-1, -1) is at -3/4 of a half-revolution
+    /// let a = one.neg().atan2pi(&one.neg(), 64, rm, &mut cc);
+    /// assert_eq!(a, BigFloat::from_f64(-0.75, 64));
+    /// ```
+    pub fn atan2pi(&self, d2: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("atan2pi", p);
+
+        match (&self.inner, &d2.inner) {
+            (Flavor::Value(y), Flavor::Value(x)) => {
+                Self::result_to_ext(y.atan2pi(x, p, rm, cc), false, true)
             }
-            Flavor::Inf(s1) => {
-                if *s1 == Sign::Neg {
-                    // -inf.log(any)
-                    NAN
-                } else {
-                    match &n.inner {
-                        Flavor::Value(v2) => {
-                            // +inf.log(v2)
-                            if v2.exponent() <= 0 {
-                                INF_NEG
-                            } else {
-                                INF_POS
-                            }
-                        }
-                        Flavor::Inf(_) => NAN, // +inf.log(inf)
-                        Flavor::NaN(err) => Self::nan(*err),
+            (Flavor::NaN(err), _) | (_, Flavor::NaN(err)) => Self::nan(*err),
+            (Flavor::Inf(sy), Flavor::Inf(sx)) => {
+                let a = if sx.is_positive() { 0.25 } else { 0.75 };
+                Self::from_f64(a * sy.to_int() as f64, p)
+            }
+            (Flavor::Inf(sy), Flavor::Value(_)) => Self::from_f64(0.5 * sy.to_int() as f64, p),
+            (Flavor::Value(y), Flavor::Inf(sx)) => {
+                if sx.is_positive() {
+                    let mut ret = Self::new(p);
+                    if y.is_negative() {
+                        ret = ret.neg();
                     }
+                    ret
+                } else {
+                    Self::from_i8(y.sign().to_int(), p)
                 }
             }
-            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Returns true if `self` is positive.
-    /// The function returns false if `self` is NaN.
-    pub fn is_positive(&self) -> bool {
+    /// Computes the hyperbolic tangent of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn tanh(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("tanh", p);
+
         match &self.inner {
-            Flavor::Value(v) => v.is_positive(),
-            Flavor::Inf(s) => *s == Sign::Pos,
-            Flavor::NaN(_) => false,
+            Flavor::Value(v) => Self::result_to_ext(v.tanh(p, rm, cc), v.is_zero(), true),
+            Flavor::Inf(s) => Self::from_i8(s.to_int(), p),
+            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Returns true if `self` is negative.
-    /// The function returns false if `self` is NaN.
-    pub fn is_negative(&self) -> bool {
+    /// Computes the logistic function 1 / (1 + e^(-x)) of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn sigmoid(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("sigmoid", p);
+
         match &self.inner {
-            Flavor::Value(v) => v.is_negative(),
-            Flavor::Inf(s) => *s == Sign::Neg,
-            Flavor::NaN(_) => false,
+            Flavor::Value(v) => Self::result_to_ext(v.sigmoid(p, rm, cc), false, true),
+            Flavor::Inf(s) => {
+                if s.is_positive() {
+                    Self::from_word(1, p)
+                } else {
+                    Self::new(p)
+                }
+            }
+            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Returns true if `self` is subnormal. A number is subnormal if the most significant bit of the mantissa is not equal to 1.
-    pub fn is_subnormal(&self) -> bool {
-        if let Flavor::Value(v) = &self.inner {
-            return v.is_subnormal();
+    /// Computes the softplus function ln(1 + e^x) of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn softplus(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("softplus", p);
+
+        match &self.inner {
+            Flavor::Value(v) => Self::result_to_ext(v.softplus(p, rm, cc), false, true),
+            Flavor::Inf(s) => {
+                if s.is_positive() {
+                    INF_POS
+                } else {
+                    Self::new(p)
+                }
+            }
+            Flavor::NaN(err) => Self::nan(*err),
         }
-        false
     }
 
-    /// Returns true if `self` is zero.
-    pub fn is_zero(&self) -> bool {
-        match &self.inner {
-            Flavor::Value(v) => v.is_zero(),
-            Flavor::Inf(_) => false,
-            Flavor::NaN(_) => false,
+    /// Computes ln(e^x1 + e^x2 + ... + e^xn) for the numbers `xs` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The maximum of the numbers is subtracted before exponentiation, so the function does not overflow for large arguments.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if `xs` is empty, if any of the numbers is NaN, or if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    /// let x = BigFloat::from_f64(1e10, 64);
+    ///
+    /// let s = BigFloat::log_sum_exp(&[x.clone(), x.clone()], 64, rm, &mut cc);
+    ///
+    /// assert_eq!(s, x.add(&cc.ln_2(128, rm), 64, rm));
+    /// ```
+    pub fn log_sum_exp(xs: &[BigFloat], p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        Self::log_sum_exp_op(xs.iter(), p, rm, cc)
+    }
+
+    /// Computes ln(e^`self` + e^`d2`) with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The larger of the numbers is subtracted before exponentiation, so the function does not overflow for large arguments.
+    /// Negative Inf represents the logarithm of zero, and the result is the other number rounded to precision `p`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if any of the numbers is NaN, or if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// // ln(1/4 + 1/4) = ln(1/2)
+    /// let x = BigFloat::from_word(4, 128).ln(128, rm, &mut cc).neg();
+    /// let s = x.log_add_exp(&x, 128, rm, &mut cc);
+    ///
+    /// assert_eq!(s, BigFloat::from_word(2, 128).ln(128, rm, &mut cc).neg());
+    /// ```
+    pub fn log_add_exp(&self, d2: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("log_add_exp", p);
+
+        match (&self.inner, &d2.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2)) => {
+                Self::result_to_ext(v1.log_add_exp(v2, p, rm, cc), false, true)
+            }
+            _ => Self::log_sum_exp_op([self, d2].into_iter(), p, rm, cc),
         }
     }
 
-    /// Restricts the value of `self` to an interval determined by the values of `min` and `max`.
-    /// The function returns `max` if `self` is greater than `max`, `min` if `self` is less than `min`, and `self` otherwise.
-    /// If either argument is NaN or `min` is greater than `max`, the function returns NaN.
-    pub fn clamp(&self, min: &Self, max: &Self) -> Self {
-        if self.is_nan() || min.is_nan() || max.is_nan() || max.cmp(min).unwrap() < 0 {
-            // call to unwrap() is unreacheable
-            NAN
-        } else if self.cmp(min).unwrap() < 0 {
-            // call to unwrap() is unreacheable
-            min.clone()
-        } else if self.cmp(max).unwrap() > 0 {
-            // call to unwrap() is unreacheable
-            max.clone()
+    fn log_sum_exp_op<'a>(
+        xs: impl ExactSizeIterator<Item = &'a BigFloat>,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Self {
+        if xs.len() == 0 {
+            return Self::nan(Some(Error::InvalidArgument));
+        }
+
+        let mut v = Vec::new();
+        if let Err(err) = v.try_reserve_exact(xs.len()) {
+            return Self::nan(Some(err.into()));
+        }
+
+        let mut inf_pos = false;
+        for x in xs {
+            match &x.inner {
+                Flavor::Value(n) => v.push(n),
+                Flavor::Inf(s) => inf_pos |= s.is_positive(),
+                Flavor::NaN(err) => return Self::nan(*err),
+            }
+        }
+
+        if inf_pos {
+            INF_POS
+        } else if v.is_empty() {
+            INF_NEG
         } else {
-            self.clone()
+            Self::result_to_ext(BigFloatNumber::log_sum_exp(&v, p, rm, cc), false, true)
         }
     }
 
-    /// Returns the value of `d1` if `d1` is greater than `self`, or the value of `self` otherwise.
-    /// If either argument is NaN, the function returns NaN.
-    pub fn max(&self, d1: &Self) -> Self {
-        if self.is_nan() || d1.is_nan() {
-            NAN
-        } else if self.cmp(d1).unwrap() < 0 {
-            // call to unwrap() is unreacheable
-            d1.clone()
-        } else {
-            self.clone()
+    /// Computes the cumulative distribution function of the standard normal distribution (1 + erf(x / sqrt(2))) / 2
+    /// of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn norm_cdf(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("norm_cdf", p);
+
+        match &self.inner {
+            Flavor::Value(v) => Self::result_to_ext(v.norm_cdf(p, rm, cc), false, true),
+            Flavor::Inf(s) => {
+                if s.is_positive() {
+                    Self::from_word(1, p)
+                } else {
+                    Self::new(p)
+                }
+            }
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Computes the inverse of the cumulative distribution function of the standard normal distribution (probit)
+    /// of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns -Inf for 0, +Inf for 1, and NaN if the argument is outside of the interval [0, 1],
+    /// or the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// // one-sided tail probability of 1e-100
+    /// let q = BigFloat::from_f64(1e-100, 1024);
+    /// let x = q.norm_cdf_inv(256, rm, &mut cc);
+    ///
+    /// let q1 = x.norm_cdf(256, rm, &mut cc);
+    /// assert!(q1.sub(&q, 256, rm).abs() < BigFloat::from_f64(1e-170, 64));
+    /// ```
+    pub fn norm_cdf_inv(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("norm_cdf_inv", p);
+
+        match &self.inner {
+            Flavor::Value(v) => {
+                if v.is_zero() {
+                    INF_NEG
+                } else if v.cmp(&crate::common::consts::ONE) == 0 {
+                    INF_POS
+                } else {
+                    Self::result_to_ext(v.norm_cdf_inv(p, rm, cc), false, true)
+                }
+            }
+            Flavor::Inf(_) => Self::nan(Some(Error::InvalidArgument)),
+            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Returns value of `d1` if `d1` is less than `self`, or the value of `self` otherwise.
-    /// If either argument is NaN, the function returns NaN.
-    pub fn min(&self, d1: &Self) -> Self {
-        if self.is_nan() || d1.is_nan() {
-            NAN
-        } else if self.cmp(d1).unwrap() > 0 {
-            // call to unwrap() is unreacheable
-            d1.clone()
-        } else {
-            self.clone()
+    /// Computes the error function of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn erf(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("erf", p);
+
+        match &self.inner {
+            Flavor::Value(v) => Self::result_to_ext(v.erf(p, rm, cc), false, true),
+            Flavor::Inf(s) => Self::from_i8(s.to_int(), p),
+            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Returns a BigFloat with the value -1 if `self` is negative, 1 if `self` is positive, zero otherwise.
-    /// The function returns NaN If `self` is NaN.
-    pub fn signum(&self) -> Self {
-        if self.is_nan() {
-            NAN
-        } else if self.is_negative() {
-            let mut ret = Self::from_u8(1, DEFAULT_P);
-            ret.inv_sign();
-            ret
-        } else {
-            Self::from_u8(1, DEFAULT_P)
+    /// Computes the complementary error function 1 - erf(x) of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Large positive arguments do not lose relative precision, the result becomes zero only when it underflows the exponent range.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// // erfc(100) = 6.4059614249217320390...e-4346
+    /// let x = BigFloat::from_word(100, 128);
+    /// let c = x.erfc(128, rm, &mut cc);
+    ///
+    /// let r = BigFloat::parse("6.4059614249217320390e-4346", astro_float_num::Radix::Dec, 128, rm);
+    /// assert!(c.sub(&r, 128, rm).div(&r, 128, rm).abs() < BigFloat::from_f64(1e-19, 64));
+    ///
+    /// // 1 - erf(100) is indistinguishable from zero
+    /// assert!(BigFloat::from_word(1, 128).sub(&x.erf(128, rm, &mut cc), 128, rm).is_zero());
+    /// ```
+    pub fn erfc(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("erfc", p);
+
+        match &self.inner {
+            Flavor::Value(v) => Self::result_to_ext(v.erfc(p, rm, cc), false, true),
+            Flavor::Inf(s) => {
+                if s.is_positive() {
+                    Self::new(p)
+                } else {
+                    Self::from_word(2, p)
+                }
+            }
+            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Parses a number from the string `s`.
-    /// The function expects `s` to be a number in scientific format in base 10, or +-Inf, or NaN.
+    /// Computes the gamma function of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns +Inf for +0 and +Inf, -Inf for -0, and NaN for negative integers and -Inf,
+    /// or if the precision `p` is incorrect.
     ///
     /// ## Examples
     ///
     /// ```
     /// # use astro_float_num::BigFloat;
-    /// # use astro_float_num::Radix;
+    /// # use astro_float_num::Consts;
     /// # use astro_float_num::RoundingMode;
-    /// let n = BigFloat::parse("0.0", Radix::Bin, 64, RoundingMode::ToEven);
-    /// assert!(n.is_zero());
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
     ///
-    /// let n = BigFloat::parse("1.124e-24", Radix::Dec, 128, RoundingMode::ToEven);
-    /// assert!(n.sub(&BigFloat::from_f64(1.124e-24, 128), 128, RoundingMode::ToEven).exponent() <= Some(-52 - 24));
+    /// // gamma(1/2) = sqrt(pi)
+    /// let x = BigFloat::from_f64(0.5, 256);
+    /// assert_eq!(x.gamma(256, rm, &mut cc), cc.pi(256, rm).sqrt(256, rm));
     ///
-    /// let n = BigFloat::parse("-Inf", Radix::Hex, 1, RoundingMode::None);
-    /// assert!(n.is_inf_neg());
+    /// // gamma(n) = (n - 1)!
+    /// assert_eq!(BigFloat::from_word(6, 64).gamma(64, rm, &mut cc), BigFloat::from_word(120, 64));
     ///
-    /// let n = BigFloat::parse("NaN", Radix::Oct, 2, RoundingMode::None);
-    /// assert!(n.is_nan());
+    /// assert!(BigFloat::from_word(0, 64).gamma(64, rm, &mut cc).is_inf_pos());
+    /// assert!(BigFloat::from_i8(-2, 64).gamma(64, rm, &mut cc).is_nan());
     /// ```
-    pub fn parse(s: &str, rdx: Radix, p: usize, rm: RoundingMode) -> Self {
-        match crate::parser::parse(s, rdx) {
-            Ok(ps) => {
-                if ps.is_inf() {
-                    if ps.sign() == Sign::Pos {
-                        INF_POS
-                    } else {
-                        INF_NEG
-                    }
-                } else if ps.is_nan() {
-                    NAN
+    pub fn gamma(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("gamma", p);
+
+        match &self.inner {
+            Flavor::Value(v) => Self::result_to_ext(v.gamma(p, rm, cc), false, true),
+            Flavor::Inf(s) => {
+                if s.is_positive() {
+                    INF_POS
                 } else {
-                    let (m, s, e) = ps.raw_parts();
-                    Self::result_to_ext(
-                        BigFloatNumber::convert_from_radix(s, m, e, rdx, p, rm),
-                        false,
-                        true,
-                    )
+                    Self::nan(Some(Error::InvalidArgument))
                 }
             }
-            Err(e) => Self::nan(Some(e)),
+            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    pub(crate) fn write_str<T: Write>(
-        &self,
-        w: &mut T,
-        rdx: Radix,
-        rm: RoundingMode,
-    ) -> Result<(), core::fmt::Error> {
+    /// Computes the Bessel function of the first kind J_n(x) of order `n` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// // J_0(1) = 0.76519768655796655144971752610266322...
+    /// let j = BigFloat::from_word(1, 128).bessel_j(0, 128, rm, &mut cc);
+    ///
+    /// let r = BigFloat::parse("0.76519768655796655144971752610266322", astro_float_num::Radix::Dec, 128, rm);
+    /// assert!(j.sub(&r, 128, rm).abs() < BigFloat::from_f64(1e-35, 64));
+    /// ```
+    pub fn bessel_j(&self, n: usize, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("bessel_j", p);
+
         match &self.inner {
-            Flavor::Value(v) => match v.format(rdx, rm) {
-                Ok(s) => w.write_str(&s),
-                Err(e) => match e {
-                    Error::ExponentOverflow(s) => {
-                        if s.is_positive() {
-                            w.write_str("Inf")
-                        } else {
-                            w.write_str("-Inf")
-                        }
-                    }
-                    _ => w.write_str("Err"),
-                },
-            },
-            Flavor::Inf(sign) => {
-                let s = if sign.is_negative() { "-Inf" } else { "Inf" };
-                w.write_str(s)
+            Flavor::Value(v) => Self::result_to_ext(v.bessel_j(n, p, rm, cc), false, true),
+            Flavor::Inf(_) => Self::new(p),
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Computes the Bessel function of the second kind Y_n(x) of order `n` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns -Inf for zero, and NaN for negative arguments, or if the precision `p` is incorrect.
+    pub fn bessel_y(&self, n: usize, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("bessel_y", p);
+
+        match &self.inner {
+            Flavor::Value(v) => Self::result_to_ext(v.bessel_y(n, p, rm, cc), false, true),
+            Flavor::Inf(s) => {
+                if s.is_positive() {
+                    Self::new(p)
+                } else {
+                    Self::nan(Some(Error::InvalidArgument))
+                }
             }
-            crate::ext::Flavor::NaN(_) => w.write_str("NaN"),
+            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Returns a random normalized (not subnormal) BigFloat number with exponent in the range
-    /// from `exp_from` to `exp_to` inclusive. The sign can be positive and negative. Zero is excluded.
-    /// Precision is rounded upwards to the word size.
-    /// Function does not follow any specific distribution law.
-    /// The intended use of this function is for testing.
-    /// The function returns NaN if the precision `p` is incorrect or when `exp_from` is less than EXPONENT_MIN or `exp_to` is greater than EXPONENT_MAX.
-    #[cfg(feature = "random")]
-    pub fn random_normal(p: usize, exp_from: Exponent, exp_to: Exponent) -> Self {
+    /// Computes the harmonic number H_n = 1 + 1/2 + ... + 1/n with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// // H_n - ln(n) approaches the Euler-Mascheroni constant
+    /// let n = 1_000_000_000_000usize;
+    /// let h = BigFloat::harmonic(n, 128, rm, &mut cc);
+    /// let g = h.sub(&BigFloat::from_u64(n as u64, 64).ln(128, rm, &mut cc), 128, rm);
+    ///
+    /// let gamma = BigFloat::parse("0.5772156649015", astro_float_num::Radix::Dec, 64, rm);
+    /// assert!(g.sub(&gamma, 64, rm).abs() < BigFloat::from_f64(1e-12, 64));
+    /// ```
+    pub fn harmonic(n: usize, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        Self::result_to_ext(BigFloatNumber::harmonic(n, p, rm, cc), false, true)
+    }
+
+    /// Computes the generalized harmonic number H(n, s) = 1 + 1/2^s + ... + 1/n^s with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn generalized_harmonic(
+        n: usize,
+        s: usize,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Self {
         Self::result_to_ext(
-            BigFloatNumber::random_normal(p, exp_from, exp_to),
+            BigFloatNumber::generalized_harmonic(n, s, p, rm, cc),
             false,
             true,
         )
     }
 
-    /// Returns category of `self`.
-    pub fn classify(&self) -> FpCategory {
+    /// Computes the Legendre polynomial P_n(x) of degree `n` at `self` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// The polynomial is evaluated with the three-term recurrence (k+1)P_(k+1)(x) = (2k+1)xP_k(x) - kP_(k-1)(x),
+    /// and the working precision is increased until the result can be rounded correctly.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// // P_3(x) = (5x^3 - 3x) / 2
+    /// let x = BigFloat::from_f64(0.5, 64);
+    /// assert_eq!(x.legendre(3, 64, rm), BigFloat::from_f64(-0.4375, 64));
+    /// ```
+    pub fn legendre(&self, n: usize, p: usize, rm: RoundingMode) -> Self {
+        trace_op!("legendre", p);
+
         match &self.inner {
-            Flavor::Value(v) => {
-                if v.is_subnormal() {
-                    FpCategory::Subnormal
-                } else if v.is_zero() {
-                    FpCategory::Zero
+            Flavor::Value(v) => Self::result_to_ext(v.legendre(n, p, rm), false, true),
+            _ => self.assoc_legendre(n, 0, p, rm),
+        }
+    }
+
+    /// Computes the associated Legendre function P_n^m(x) of degree `n` and order `m` at `self` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// The function includes the Condon-Shortley phase (-1)^m, i.e. P_n^m(x) = (-1)^m (1-x^2)^(m/2) d^m/dx^m P_n(x).
+    /// The result is zero if `m` is larger than `n`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect, or if `m` is not zero and `self` is outside of the interval [-1, 1].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// // P_2^2(x) = 3(1 - x^2)
+    /// let x = BigFloat::from_f64(0.5, 64);
+    /// assert_eq!(x.assoc_legendre(2, 2, 64, rm), BigFloat::from_f64(2.25, 64));
+    ///
+    /// assert!(BigFloat::from_word(2, 64).assoc_legendre(2, 1, 64, rm).is_nan());
+    /// ```
+    pub fn assoc_legendre(&self, n: usize, m: usize, p: usize, rm: RoundingMode) -> Self {
+        trace_op!("assoc_legendre", p);
+
+        match &self.inner {
+            Flavor::Value(v) => Self::result_to_ext(v.assoc_legendre(n, m, p, rm), false, true),
+            Flavor::Inf(s) => {
+                if m > n {
+                    Self::new(p)
+                } else if m > 0 {
+                    Self::nan(Some(Error::InvalidArgument))
+                } else if n == 0 {
+                    Self::from_u8(1, p)
+                } else if s.is_negative() && (n & 1 == 1) {
+                    INF_NEG
                 } else {
-                    FpCategory::Normal
+                    INF_POS
                 }
             }
-            Flavor::Inf(_) => FpCategory::Infinite,
-            Flavor::NaN(_) => FpCategory::Nan,
+            Flavor::NaN(err) => Self::nan(*err),
         }
     }
 
-    /// Computes the arctangent of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Computes the Jacobi elliptic function sn(u | m) of the argument `self` and the parameter `m` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
-    /// The function returns NaN if the precision `p` is incorrect.
-    pub fn atan(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
-        match &self.inner {
-            Flavor::Value(v) => Self::result_to_ext(v.atan(p, rm, cc), v.is_zero(), true),
-            Flavor::Inf(s) => Self::result_to_ext(Self::half_pi(*s, p, rm, cc), false, true),
-            Flavor::NaN(err) => Self::nan(*err),
-        }
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect, or if `m` is outside of the interval [0, 1].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, Consts, RoundingMode};
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    /// let u = BigFloat::from_f64(0.75, 128);
+    ///
+    /// // sn(u | 0) = sin(u), sn(u | 1) = tanh(u)
+    /// assert_eq!(u.jacobi_sn(&BigFloat::new(64), 128, rm, &mut cc), u.sin(128, rm, &mut cc));
+    /// assert_eq!(u.jacobi_sn(&BigFloat::from_word(1, 64), 128, rm, &mut cc), u.tanh(128, rm, &mut cc));
+    /// ```
+    pub fn jacobi_sn(&self, m: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("jacobi_sn", p);
+
+        self.jacobi_op(
+            m,
+            |u, m| u.jacobi_sn(m, p, rm, cc),
+            |s| Self::from_i8(s.to_int(), p),
+        )
     }
 
-    /// Computes the hyperbolic tangent of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Computes the Jacobi elliptic function cn(u | m) of the argument `self` and the parameter `m` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
-    /// The function returns NaN if the precision `p` is incorrect.
-    pub fn tanh(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
-        match &self.inner {
-            Flavor::Value(v) => Self::result_to_ext(v.tanh(p, rm, cc), v.is_zero(), true),
-            Flavor::Inf(s) => Self::from_i8(s.to_int(), p),
-            Flavor::NaN(err) => Self::nan(*err),
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect, or if `m` is outside of the interval [0, 1].
+    pub fn jacobi_cn(&self, m: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("jacobi_cn", p);
+
+        self.jacobi_op(m, |u, m| u.jacobi_cn(m, p, rm, cc), |_| Self::new(p))
+    }
+
+    /// Computes the Jacobi elliptic function dn(u | m) of the argument `self` and the parameter `m` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect, or if `m` is outside of the interval [0, 1].
+    pub fn jacobi_dn(&self, m: &Self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        trace_op!("jacobi_dn", p);
+
+        self.jacobi_op(m, |u, m| u.jacobi_dn(m, p, rm, cc), |_| Self::new(p))
+    }
+
+    /// Computes the Jacobi elliptic functions sn(u | m), cn(u | m), and dn(u | m) of the argument `self` and the parameter `m`
+    /// with precision `p`. Each of the results is rounded using the rounding mode `rm`.
+    /// The functions share the arithmetic-geometric mean sequence, so computing them together is faster than computing them one by one.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN for all values if the precision `p` is incorrect, or if `m` is outside of the interval [0, 1].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, Consts, RoundingMode};
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    /// let u = BigFloat::from_f64(0.75, 128);
+    /// let m = BigFloat::from_f64(0.5, 64);
+    ///
+    /// let (sn, cn, dn) = u.jacobi_elliptic(&m, 128, rm, &mut cc);
+    ///
+    /// assert_eq!(sn, u.jacobi_sn(&m, 128, rm, &mut cc));
+    /// assert_eq!(cn, u.jacobi_cn(&m, 128, rm, &mut cc));
+    /// assert_eq!(dn, u.jacobi_dn(&m, 128, rm, &mut cc));
+    /// ```
+    pub fn jacobi_elliptic(
+        &self,
+        m: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> (Self, Self, Self) {
+        trace_op!("jacobi_elliptic", p);
+
+        match (&self.inner, &m.inner) {
+            (Flavor::Value(u), Flavor::Value(mv)) => match u.jacobi_elliptic(mv, p, rm, cc) {
+                Ok((sn, cn, dn)) => (sn.into(), cn.into(), dn.into()),
+                Err(e) => {
+                    let err = Self::result_to_ext(Err(e), false, true);
+                    (err.clone(), err.clone(), err)
+                }
+            },
+            (Flavor::Inf(s), Flavor::Value(mv)) if mv.cmp(&crate::common::consts::ONE) == 0 => {
+                (Self::from_i8(s.to_int(), p), Self::new(p), Self::new(p))
+            }
+            (Flavor::NaN(err), _) | (_, Flavor::NaN(err)) => {
+                (Self::nan(*err), Self::nan(*err), Self::nan(*err))
+            }
+            _ => {
+                let err = Self::nan(Some(Error::InvalidArgument));
+                (err.clone(), err.clone(), err)
+            }
+        }
+    }
+
+    // Computes a Jacobi elliptic function using `f`.
+    // If `self` is infinite and `m` is 1, the result is the limit returned by `inf` for the sign of `self`.
+    fn jacobi_op(
+        &self,
+        m: &Self,
+        f: impl FnOnce(&BigFloatNumber, &BigFloatNumber) -> Result<BigFloatNumber, Error>,
+        inf: impl FnOnce(Sign) -> Self,
+    ) -> Self {
+        match (&self.inner, &m.inner) {
+            (Flavor::Value(u), Flavor::Value(mv)) => Self::result_to_ext(f(u, mv), false, true),
+            (Flavor::Inf(s), Flavor::Value(mv)) if mv.cmp(&crate::common::consts::ONE) == 0 => {
+                inf(*s)
+            }
+            (Flavor::NaN(err), _) | (_, Flavor::NaN(err)) => Self::nan(*err),
+            _ => Self::nan(Some(Error::InvalidArgument)),
         }
     }
 
@@ -747,7 +2643,7 @@ impl BigFloat {
         Ok(half_pi)
     }
 
-    fn result_to_ext(
+    pub(crate) fn result_to_ext(
         res: Result<BigFloatNumber, Error>,
         is_dividend_zero: bool,
         is_same_sign: bool,
@@ -772,6 +2668,7 @@ impl BigFloat {
                 }
                 Error::MemoryAllocation => Self::nan(Some(Error::MemoryAllocation)),
                 Error::InvalidArgument => Self::nan(Some(Error::InvalidArgument)),
+                Error::Inexact => Self::nan(Some(Error::Inexact)),
             },
             Ok(v) => BigFloat {
                 inner: Flavor::Value(v),
@@ -787,9 +2684,111 @@ impl BigFloat {
         }
     }
 
+    /// Returns the binary exponent of `self` as an integer, i.e. floor(log2(|`self`|)),
+    /// or None if `self` is zero, Inf, or NaN.
+    /// Unlike `exponent`, the value is not limited by the range of the exponent for subnormal numbers,
+    /// and it corresponds to the mantissa normalized to the range [1, 2).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// assert_eq!(BigFloat::from_word(8, 128).ilogb(), Some(3));
+    /// assert_eq!(BigFloat::from_f64(0.7, 128).ilogb(), Some(-1));
+    /// assert_eq!(BigFloat::from_word(0, 128).ilogb(), None);
+    /// ```
+    pub fn ilogb(&self) -> Option<isize> {
+        match &self.inner {
+            Flavor::Value(v) if !v.is_zero() => Some(v.normalized_exponent() - 1),
+            _ => None,
+        }
+    }
+
+    /// Returns the binary exponent of `self` as a number, i.e. floor(log2(|`self`|)).
+    /// The function returns negative Inf if `self` is zero, positive Inf if `self` is Inf, and NaN if `self` is NaN.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// assert_eq!(BigFloat::from_f64(-0.1, 128).logb(), BigFloat::from_i8(-4, 64));
+    /// assert!(BigFloat::from_word(0, 128).logb().is_inf_neg());
+    /// ```
+    pub fn logb(&self) -> Self {
+        match &self.inner {
+            Flavor::Value(v) => {
+                if v.is_zero() {
+                    INF_NEG
+                } else {
+                    Self::from_i64((v.normalized_exponent() - 1) as i64, 64)
+                }
+            }
+            Flavor::Inf(_) => INF_POS,
+            Flavor::NaN(err) => Self::nan(*err),
+        }
+    }
+
+    /// Returns the smallest and the largest exponent which the result of multiplication of `self` by `d2`
+    /// can have after rounding to any precision, without computing the product.
+    /// The exponents are not limited by the range of the exponent, so they can be used to detect overflow and underflow in advance.
+    /// The function returns None if any of the arguments is zero, Inf, or NaN.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// let d1 = BigFloat::from_word(3, 128);
+    /// let d2 = BigFloat::from_word(5, 128);
+    ///
+    /// let (e_min, e_max) = d1.mul_exponent_estimate(&d2).unwrap();
+    /// let e = d1.mul_full_prec(&d2).exponent().unwrap() as isize;
+    /// assert!(e_min <= e && e <= e_max);
+    /// ```
+    pub fn mul_exponent_estimate(&self, d2: &Self) -> Option<(isize, isize)> {
+        match (&self.inner, &d2.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2)) if !v1.is_zero() && !v2.is_zero() => {
+                // 2^(e1+e2-2) <= d1*d2 < 2^(e1+e2), and the product can be rounded up to 2^(e1+e2)
+                let e = v1.normalized_exponent() + v2.normalized_exponent();
+                Some((e - 1, e + 1))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the smallest and the largest exponent which the result of division of `self` by `d2`
+    /// can have after rounding to any precision, without computing the quotient.
+    /// The exponents are not limited by the range of the exponent, so they can be used to detect overflow and underflow in advance.
+    /// The function returns None if any of the arguments is zero, Inf, or NaN.
+    pub fn div_exponent_estimate(&self, d2: &Self) -> Option<(isize, isize)> {
+        match (&self.inner, &d2.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2)) if !v1.is_zero() && !v2.is_zero() => {
+                // 2^(e1-e2-1) < d1/d2 < 2^(e1-e2+1), and the quotient can be rounded up to 2^(e1-e2+1)
+                let e = v1.normalized_exponent() - v2.normalized_exponent();
+                Some((e, e + 2))
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the number of significant bits used in the mantissa, or None if `self` is Inf or NaN.
     /// Normal numbers use all bits of the mantissa.
-    /// Subnormal numbers use fewer bits than the mantissa can hold.
+    /// Subnormal numbers use fewer bits than the mantissa can hold, and zero uses no bits.
+    /// The length of the mantissa regardless of whether the number is normal or subnormal is returned by `mantissa_max_bit_len`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// let n = BigFloat::from_word(3, 128);
+    /// assert_eq!(n.precision(), Some(128));
+    ///
+    /// let n = BigFloat::min_positive(128);
+    /// assert_eq!(n.precision(), Some(1));
+    /// assert_eq!(n.mantissa_max_bit_len(), Some(128));
+    ///
+    /// assert_eq!(BigFloat::from_word(0, 128).precision(), Some(0));
+    /// assert_eq!(BigFloat::from_f64(f64::NAN, 128).precision(), None);
+    /// ```
     pub fn precision(&self) -> Option<usize> {
         match &self.inner {
             Flavor::Value(v) => Some(v.precision()),
@@ -797,15 +2796,103 @@ impl BigFloat {
         }
     }
 
+    /// Returns the number of bytes allocated on the heap for the mantissa of the number, or 0 if `self` is Inf or NaN.
+    /// The value does not include the size of `BigFloat` itself, and it can be larger than the size
+    /// of the mantissa, e.g. after the precision was reduced with `set_precision`.
+    pub fn allocated_bytes(&self) -> usize {
+        match &self.inner {
+            Flavor::Value(v) => v.allocated_bytes(),
+            _ => 0,
+        }
+    }
+
+    /// Releases the memory which is allocated for the mantissa, but is not used by it.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut x = BigFloat::from_word(3, 1024);
+    ///
+    /// x.set_precision(64, RoundingMode::ToEven).unwrap();
+    /// assert_eq!(x.allocated_bytes(), 1024 / 8);
+    ///
+    /// x.shrink_to_fit();
+    /// assert_eq!(x.allocated_bytes(), 64 / 8);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        if let Flavor::Value(v) = &mut self.inner {
+            v.shrink_to_fit();
+        }
+    }
+
+    // Sets `self` to zero with precision `p` reusing the memory allocated for the mantissa.
+    // `self` is set to NaN if the precision is incorrect.
+    pub(crate) fn reset(&mut self, p: usize) {
+        let ret = match &mut self.inner {
+            Flavor::Value(v) => v.reset(p),
+            _ => {
+                *self = Self::new(p);
+                return;
+            }
+        };
+
+        if let Err(e) = ret {
+            self.inner = Flavor::NaN(Some(e));
+        }
+    }
+
+    // Sets `self` to the value of `d` reusing the memory allocated for the mantissa.
+    pub(crate) fn assign(&mut self, d: &Self) {
+        let ret = match (&mut self.inner, &d.inner) {
+            (Flavor::Value(v), Flavor::Value(dv)) => v.assign(dv),
+            _ => {
+                *self = d.clone();
+                return;
+            }
+        };
+
+        if let Err(e) = ret {
+            self.inner = Flavor::NaN(Some(e));
+        }
+    }
+
+    /// Reduces the precision of `self` to the minimum number of words which represents the value of `self` exactly,
+    /// i.e. removes the least significant words of the mantissa containing zeroes, and releases the unused memory.
+    /// The value of `self` does not change. The precision of zero is reduced to the word size.
+    /// The function has no effect if `self` is Inf or NaN.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// # use astro_float_num::WORD_BIT_SIZE;
+    /// let mut x = BigFloat::from_word(12345, 1024);
+    /// let x0 = x.clone();
+    ///
+    /// x.compact();
+    ///
+    /// assert_eq!(x.mantissa_max_bit_len(), Some(WORD_BIT_SIZE));
+    /// assert_eq!(x.allocated_bytes(), WORD_BIT_SIZE / 8);
+    /// assert_eq!(x, x0);
+    /// ```
+    pub fn compact(&mut self) {
+        if let Flavor::Value(v) = &mut self.inner {
+            v.compact();
+        }
+    }
+
     /// Returns the maximum value for the specified precision `p`: all bits of the mantissa are set to 1,
     /// the exponent has the maximum possible value, and the sign is positive.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn max_value(p: usize) -> Self {
         Self::result_to_ext(BigFloatNumber::max_value(p), false, true)
     }
 
-    /// Returns the minimum value for the specified precision `p`: all bits of the mantissa are set to 1, the exponent has the maximum possible value, and the sign is negative. Precision is rounded upwards to the word size.
+    /// Returns the minimum value for the specified precision `p`: all bits of the mantissa are set to 1, the exponent has the maximum possible value, and the sign is negative. Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn min_value(p: usize) -> Self {
         Self::result_to_ext(BigFloatNumber::min_value(p), false, true)
@@ -814,7 +2901,7 @@ impl BigFloat {
     /// Returns the minimum positive subnormal value for the specified precision `p`:
     /// only the least significant bit of the mantissa is set to 1, the exponent has
     /// the minimum possible value, and the sign is positive.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn min_positive(p: usize) -> Self {
         Self::result_to_ext(BigFloatNumber::min_positive(p), false, true)
@@ -823,13 +2910,13 @@ impl BigFloat {
     /// Returns the minimum positive normal value for the specified precision `p`:
     /// only the most significant bit of the mantissa is set to 1, the exponent has
     /// the minimum possible value, and the sign is positive.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn min_positive_normal(p: usize) -> Self {
         Self::result_to_ext(BigFloatNumber::min_positive_normal(p), false, true)
     }
 
-    /// Returns a new number with value `d` and the precision `p`. Precision is rounded upwards to the word size.
+    /// Returns a new number with value `d` and the precision `p`. Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn from_word(d: Word, p: usize) -> Self {
         Self::result_to_ext(BigFloatNumber::from_word(d, p), false, true)
@@ -842,6 +2929,32 @@ impl BigFloat {
         ret
     }
 
+    /// Returns true if `self` and `d2` have the same representation: the same sign, exponent, precision,
+    /// and mantissa words. Unlike `==`, numbers with equal values but different precision are not identical,
+    /// and NaN is identical to NaN regardless of the error it carries. Infinities are identical if they have the same sign.
+    /// The inexact flag is not compared.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// let a = BigFloat::from_word(3, 64);
+    /// let b = BigFloat::from_word(3, 128);
+    ///
+    /// assert!(a == b);
+    /// assert!(!a.identical(&b));
+    /// assert!(a.identical(&a.clone()));
+    /// assert!(BigFloat::from_f64(f64::NAN, 64).identical(&astro_float_num::NAN));
+    /// ```
+    pub fn identical(&self, d2: &Self) -> bool {
+        match (&self.inner, &d2.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2)) => v1.identical(v2),
+            (Flavor::NaN(_), Flavor::NaN(_)) => true,
+            (Flavor::Inf(s1), Flavor::Inf(s2)) => s1 == s2,
+            _ => false,
+        }
+    }
+
     /// Decomposes `self` into raw parts.
     /// The function returns a reference to a slice of words representing mantissa,
     /// numbers of significant bits in the mantissa, sign, exponent,
@@ -854,6 +2967,15 @@ impl BigFloat {
         }
     }
 
+    /// Returns a view of `self` which borrows the mantissa without copying it.
+    /// The function returns None if `self` is Inf or NaN.
+    pub fn as_borrowed(&self) -> Option<BigFloatRef<'_>> {
+        let (m, n, s, e, inexact) = self.as_raw_parts()?;
+
+        // the parts of a valid number are always accepted
+        BigFloatRef::from_borrowed_words(m, n, s, e, inexact).ok()
+    }
+
     /// Constructs a number from the raw parts:
     ///
     ///  - `m` is the mantisaa.
@@ -877,6 +2999,46 @@ impl BigFloat {
         )
     }
 
+    /// Constructs a number from the raw parts taking ownership of the mantissa `m` without copying it.
+    /// The parts are the same as in `from_raw_parts`, and the function returns NaN in the same situations.
+    /// Together with `into_raw_parts` it allows moving the memory of the mantissa between `BigFloat`
+    /// and the code which manages memory on its own.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, Sign, Word, WORD_SIGNIFICANT_BIT};
+    /// let words: Vec<Word> = vec![0, WORD_SIGNIFICANT_BIT];
+    /// let ptr = words.as_ptr();
+    ///
+    /// // 0.5
+    /// let x = BigFloat::from_raw_vec(words, Word::BITS as usize * 2, Sign::Pos, 0, false);
+    /// assert_eq!(x, BigFloat::from_f64(0.5, 64));
+    ///
+    /// // the mantissa has not been copied
+    /// let (m, n, s, e, inexact) = x.into_raw_parts().unwrap();
+    /// assert_eq!(m.as_ptr(), ptr);
+    /// assert_eq!((n, s, e, inexact), (Word::BITS as usize * 2, Sign::Pos, 0, false));
+    /// ```
+    pub fn from_raw_vec(m: Vec<Word>, n: usize, s: Sign, e: Exponent, inexact: bool) -> Self {
+        Self::result_to_ext(
+            BigFloatNumber::from_raw_vec(m, n, s, e, inexact),
+            false,
+            true,
+        )
+    }
+
+    /// Decomposes `self` into raw parts taking the mantissa without copying it.
+    /// The parts are the same as returned by `as_raw_parts`, except the mantissa is returned as a vector of words.
+    /// The function returns None if `self` is Inf or NaN.
+    pub fn into_raw_parts(self) -> Option<(Vec<Word>, usize, Sign, Exponent, bool)> {
+        if let Flavor::Value(v) = self.inner {
+            Some(v.into_raw_parts())
+        } else {
+            None
+        }
+    }
+
     /// Constructs a number from the slice of words:
     ///
     ///  - `m` is the mantissa.
@@ -888,6 +3050,97 @@ impl BigFloat {
         Self::result_to_ext(BigFloatNumber::from_words(m, s, e), false, true)
     }
 
+    /// Decomposes `self` into an exact integer ratio `s * m * 2^e`.
+    /// The function returns the sign `s`, the odd integer `m` as a vector of words with the least significant word first,
+    /// and the binary exponent `e`. Zero is returned as an empty `m` and `e` equal to 0.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, Sign};
+    /// let n = BigFloat::from_f64(-0.375, 64);
+    ///
+    /// assert_eq!(n.to_int_ratio().unwrap(), (Sign::Neg, vec![3], -3));
+    /// assert_eq!(BigFloat::from_int_ratio(Sign::Neg, &[3], -3), n);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `self` is NaN.
+    ///  - ExponentOverflow: `self` is Inf.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn to_int_ratio(&self) -> Result<(Sign, Vec<Word>, isize), Error> {
+        match &self.inner {
+            Flavor::Value(v) => v.to_int_ratio(),
+            Flavor::NaN(_) => Err(Error::InvalidArgument),
+            Flavor::Inf(s) => Err(Error::ExponentOverflow(*s)),
+        }
+    }
+
+    /// Returns the best rational approximation `num / den` of `self` with `1 <= den <= max_den`.
+    /// The approximation is found using the continued fraction expansion of `self`.
+    /// The numerator is returned as an integer number, and the denominator as an unsigned integer.
+    /// If two approximations are equally close, the one with the smaller denominator is returned.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, Consts, RoundingMode};
+    /// let mut cc = Consts::new().expect("Constants cache initialized.");
+    /// let pi = cc.pi(128, RoundingMode::ToEven);
+    ///
+    /// let (num, den) = pi.best_rational(1000).unwrap();
+    ///
+    /// assert_eq!(num, BigFloat::from_word(355, 64));
+    /// assert_eq!(den, 113);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `self` is NaN, or `max_den` is zero.
+    ///  - ExponentOverflow: `self` is Inf.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn best_rational(&self, max_den: u64) -> Result<(Self, u64), Error> {
+        match &self.inner {
+            Flavor::Value(v) => v.best_rational(max_den).map(|(n, d)| (n.into(), d)),
+            Flavor::NaN(_) => Err(Error::InvalidArgument),
+            Flavor::Inf(s) => Err(Error::ExponentOverflow(*s)),
+        }
+    }
+
+    /// Constructs a number from an integer ratio `s * m * 2^e` without rounding.
+    /// `m` is an integer given as a slice of words with the least significant word first.
+    /// The precision of the result is the number of significant bits of `m` rounded upwards to a multiple of `PRECISION_GRANULARITY`,
+    /// or larger if the result is subnormal.
+    /// The function returns Inf if the value is too large, and NaN if the value requires an incorrect precision.
+    pub fn from_int_ratio(s: Sign, m: &[Word], e: isize) -> Self {
+        Self::result_to_ext(BigFloatNumber::from_int_ratio(s, m, e), false, true)
+    }
+
+    /// Computes `s * num / den` with precision `p` rounded according to `rm`.
+    /// `num` and `den` are integers given as slices of words with the least significant word first.
+    /// Unlike converting both integers with precision `p` and dividing, the quotient is rounded only once,
+    /// so the result is correctly rounded.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns Inf if `den` is zero and `num` is not zero, and NaN if both are zero or the precision is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::{BigFloat, RoundingMode, Sign};
+    /// let n = BigFloat::from_ratio(Sign::Pos, &[2], &[3], 64, RoundingMode::ToEven);
+    ///
+    /// assert_eq!(n.to_f64_round(RoundingMode::ToEven), 2.0 / 3.0);
+    /// ```
+    pub fn from_ratio(s: Sign, num: &[Word], den: &[Word], p: usize, rm: RoundingMode) -> Self {
+        let is_num_zero = num.iter().all(|w| *w == 0);
+        Self::result_to_ext(
+            BigFloatNumber::from_ratio(s, num, den, p, rm),
+            is_num_zero,
+            s.is_positive(),
+        )
+    }
+
     /// Returns the sign of `self`, or None if `self` is NaN.
     pub fn sign(&self) -> Option<Sign> {
         match &self.inner {
@@ -927,6 +3180,44 @@ impl BigFloat {
         }
     }
 
+    /// Returns a copy of `self` rounded using the rounding mode `rm` so that it fits the exponent range from `emin` to `emax`.
+    /// The function can be used to emulate floating point formats with the exponent range narrower than
+    /// the range from EXPONENT_MIN to EXPONENT_MAX.
+    /// If the exponent of `self` is smaller than `emin`, `self` is rounded as a subnormal number of that range,
+    /// i.e. the result is a multiple of 2^(`emin` - `p`), where `p` is the mantissa length of `self` in bits.
+    /// If the exponent of `self` is greater than `emax`, the function returns Inf with the sign of `self`.
+    /// The function returns NaN if `emin` is greater than `emax`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let emin = -100;
+    /// let emax = 100;
+    ///
+    /// // 2^-165 is half of the smallest positive subnormal number 2^(-100 - 64).
+    /// let n = BigFloat::from_word(1, 64);
+    /// let mut tiny = n.clone();
+    /// tiny.set_exponent(-164);
+    ///
+    /// assert!(tiny.fit_exponent_range(emin, emax, RoundingMode::ToEven).is_zero());
+    /// assert!(!tiny.fit_exponent_range(emin, emax, RoundingMode::Up).is_zero());
+    ///
+    /// let mut huge = n;
+    /// huge.set_exponent(101);
+    ///
+    /// assert!(huge.fit_exponent_range(emin, emax, RoundingMode::ToEven).is_inf_pos());
+    /// ```
+    pub fn fit_exponent_range(&self, emin: Exponent, emax: Exponent, rm: RoundingMode) -> Self {
+        match &self.inner {
+            Flavor::Value(v) => {
+                Self::result_to_ext(v.fit_exponent_range(emin, emax, rm), false, true)
+            }
+            _ => self.clone(),
+        }
+    }
+
     /// Returns the maximum mantissa length of `self` in bits regardless of whether `self` is normal or subnormal.
     pub fn mantissa_max_bit_len(&self) -> Option<usize> {
         if let Flavor::Value(v) = &self.inner {
@@ -941,21 +3232,128 @@ impl BigFloat {
     ///
     /// ## Errors
     ///
-    ///  - MemoryAllocation: failed to allocate memory for mantissa.
-    ///  - InvalidArgument: the precision is incorrect.
-    pub fn set_precision(&mut self, p: usize, rm: RoundingMode) -> Result<(), Error> {
-        if let Flavor::Value(v) = &mut self.inner {
-            v.set_precision(p, rm)
-        } else {
-            Ok(())
-        }
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn set_precision(&mut self, p: usize, rm: RoundingMode) -> Result<(), Error> {
+        if let Flavor::Value(v) = &mut self.inner {
+            v.set_precision(p, rm)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Extends the mantissa of `self` with zero bits to precision `p`. The value of `self` does not change, and no rounding is performed.
+    /// Unlike `set_precision`, the function never reduces the precision: if `p` is not greater than the length of the mantissa of `self`,
+    /// or `self` is Inf or NaN, the function has no effect.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut n = BigFloat::from_word(1, 64).div(&BigFloat::from_word(3, 64), 64, RoundingMode::ToEven);
+    /// let n0 = n.clone();
+    ///
+    /// n.grow_precision(256).expect("Precision grown");
+    ///
+    /// assert_eq!(n.mantissa_max_bit_len(), Some(256));
+    /// assert_eq!(n, n0);
+    ///
+    /// // the precision is not reduced
+    /// n.grow_precision(128).expect("Precision unchanged");
+    ///
+    /// assert_eq!(n.mantissa_max_bit_len(), Some(256));
+    /// ```
+    pub fn grow_precision(&mut self, p: usize) -> Result<(), Error> {
+        if let Flavor::Value(v) = &mut self.inner {
+            v.grow_precision(p)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets the precision of `self` to `p` using stochastic rounding.
+    /// If the new precision is smaller than the existing one, the number is rounded away from zero with probability
+    /// equal to the discarded part of the mantissa divided by the unit in the last place, and towards zero otherwise.
+    /// Random numbers are taken from `rng`.
+    /// Stochastic rounding of an arithmetic operation can be done by computing the result with
+    /// rounding mode `RoundingMode::None` and then setting the precision using this function.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// # use rand::SeedableRng;
+    /// let mut rng = rand::rngs::StdRng::seed_from_u64(123);
+    ///
+    /// let mut n = BigFloat::from_word(1, 64).div(&BigFloat::from_word(3, 64), 256, RoundingMode::None);
+    /// n.set_precision_stochastic(64, &mut rng).unwrap();
+    ///
+    /// let up = BigFloat::from_word(1, 64).div(&BigFloat::from_word(3, 64), 64, RoundingMode::Up);
+    /// let down = BigFloat::from_word(1, 64).div(&BigFloat::from_word(3, 64), 64, RoundingMode::Down);
+    ///
+    /// assert!(n == up || n == down);
+    /// ```
+    #[cfg(feature = "random")]
+    pub fn set_precision_stochastic<R: rand::Rng + ?Sized>(
+        &mut self,
+        p: usize,
+        rng: &mut R,
+    ) -> Result<(), Error> {
+        if let Flavor::Value(v) = &mut self.inner {
+            v.set_precision_stochastic(p, rng)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets the precision of `self` to the number of bits sufficient to hold `n` digits in radix `rdx`.
+    /// If the new precision is smaller than the existing one, the number is rounded using specified rounding mode `rm`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Radix;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut n = BigFloat::from_word(1, 64);
+    /// n.set_precision_digits(50, Radix::Dec, RoundingMode::ToEven).unwrap();
+    ///
+    /// assert!(n.precision() >= Some(167));
+    /// ```
+    pub fn set_precision_digits(
+        &mut self,
+        n: usize,
+        rdx: Radix,
+        rm: RoundingMode,
+    ) -> Result<(), Error> {
+        self.set_precision(rdx.digits_to_bits(n), rm)
     }
 
     /// Computes the reciprocal of a number with precision `p`.
     /// The result is rounded using the rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// The function returns NaN if the precision `p` is incorrect.
     pub fn reciprocal(&self, p: usize, rm: RoundingMode) -> Self {
+        trace_op!("reciprocal", p);
+
         match &self.inner {
             Flavor::Value(v) => Self::result_to_ext(v.reciprocal(p, rm), false, v.is_positive()),
             Flavor::Inf(s) => {
@@ -967,6 +3365,39 @@ impl BigFloat {
         }
     }
 
+    /// Computes the reciprocal of a number with precision `p` by refining `prev`, a previously computed approximation
+    /// of the reciprocal of `self`, with Newton's iteration.
+    /// A single iteration is needed if `prev` has slightly more than half of `p` correct bits,
+    /// so algorithms that progressively increase the precision can reuse the earlier result.
+    /// If `prev` is not close enough to the reciprocal, the result is computed with division.
+    /// The result is rounded using the rounding mode `rm`, and is the same as the result of `reciprocal`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let rm = RoundingMode::ToEven;
+    /// let n = BigFloat::from_word(3, 1024);
+    ///
+    /// let r1 = n.reciprocal(576, rm);
+    /// let r2 = n.reciprocal_refine(&r1, 1024, rm);
+    ///
+    /// assert_eq!(r2, n.reciprocal(1024, rm));
+    /// ```
+    pub fn reciprocal_refine(&self, prev: &Self, p: usize, rm: RoundingMode) -> Self {
+        trace_op!("reciprocal_refine", p);
+
+        match (&self.inner, &prev.inner) {
+            (Flavor::Value(v), Flavor::Value(pv)) => {
+                Self::result_to_ext(v.reciprocal_refine(pv, p, rm), false, v.is_positive())
+            }
+            _ => self.reciprocal(p, rm),
+        }
+    }
+
     /// Sets the sign of `self`.
     pub fn set_sign(&mut self, s: Sign) {
         match &mut self.inner {
@@ -989,7 +3420,7 @@ impl BigFloat {
     /// `digits` represents mantissa and is interpreted as a number smaller than 1 and greater or equal to 1/`rdx`.
     /// The first element in `digits` is the most significant digit.
     /// `e` is the exponent part of the number, such that the number can be represented as `digits` * `rdx` ^ `e`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Examples
     ///
@@ -1061,13 +3492,188 @@ impl BigFloat {
         rdx: Radix,
         rm: RoundingMode,
     ) -> Result<(Sign, Vec<u8>, Exponent), Error> {
+        self.convert_to_radix_digits(rdx, None, rm)
+    }
+
+    /// Converts `self` to radix `rdx` using rounding mode `rm` like `convert_to_radix`.
+    /// If `max_digits` is not None, the mantissa is correctly rounded to at most `max_digits` digits,
+    /// and in radix 10 only these digits are computed.
+    ///
+    /// ## Examples
+    ///
+    /// ``` rust
+    /// # use astro_float_num::{BigFloat, Sign, RoundingMode, Radix};
+    /// let n = BigFloat::from_f64(0.00012345678f64, 64);
+    ///
+    /// let (s, m, e) = n.convert_to_radix_digits(Radix::Dec, None, RoundingMode::None).expect("Conversion failed");
+    ///
+    /// assert_eq!(s, Sign::Pos);
+    /// assert_eq!(m, [1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5, 5, 4, 2]);
+    /// assert_eq!(e, -3);
+    ///
+    /// let (s, m, e) = n.convert_to_radix_digits(Radix::Dec, Some(4), RoundingMode::ToEven).expect("Conversion failed");
+    ///
+    /// assert_eq!(m, [1, 2, 3, 5]);
+    /// assert_eq!(e, -3);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - InvalidArgument: `self` is Inf or NaN, or `max_digits` is zero.
+    pub fn convert_to_radix_digits(
+        &self,
+        rdx: Radix,
+        max_digits: Option<usize>,
+        rm: RoundingMode,
+    ) -> Result<(Sign, Vec<u8>, Exponent), Error> {
+        match &self.inner {
+            Flavor::Value(v) => v.convert_to_radix(rdx, max_digits, rm),
+            Flavor::NaN(_) => Err(Error::InvalidArgument),
+            Flavor::Inf(_) => Err(Error::InvalidArgument),
+        }
+    }
+
+    /// Converts `self` to radix `rdx` using rounding mode `rm` like `convert_to_radix_digits`,
+    /// but appends the mantissa digits to `buf` instead of returning a new vector.
+    /// The function returns sign, the number of digits appended to `buf`, and exponent.
+    /// Conversion to radixes 2, 8, and 16 does not allocate memory if `buf` has enough capacity,
+    /// so the same buffer can be reused across calls.
+    ///
+    /// ## Examples
+    ///
+    /// ``` rust
+    /// # use astro_float_num::{BigFloat, Sign, RoundingMode, Radix};
+    /// let mut buf = Vec::with_capacity(256);
+    ///
+    /// for f in [0.5, -3.0] {
+    ///     buf.clear();
+    ///     let n = BigFloat::from_f64(f, 64);
+    ///     let (s, len, e) = n.convert_to_radix_into(Radix::Hex, None, RoundingMode::None, &mut buf).unwrap();
+    ///     assert_eq!(len, buf.len());
+    ///     assert_eq!(buf[0], if f > 0.0 { 8 } else { 3 });
+    /// }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - InvalidArgument: `self` is Inf or NaN, or `max_digits` is zero.
+    pub fn convert_to_radix_into(
+        &self,
+        rdx: Radix,
+        max_digits: Option<usize>,
+        rm: RoundingMode,
+        buf: &mut Vec<u8>,
+    ) -> Result<(Sign, usize, Exponent), Error> {
         match &self.inner {
-            Flavor::Value(v) => v.convert_to_radix(rdx, rm),
+            Flavor::Value(v) => v.convert_to_radix_into(rdx, max_digits, rm, buf),
             Flavor::NaN(_) => Err(Error::InvalidArgument),
             Flavor::Inf(_) => Err(Error::InvalidArgument),
         }
     }
 
+    /// Formats `self` using radix `rdx` and rounding mode `rm`, and appends the ASCII characters of the result to `buf`.
+    /// The format is the same as the one used by `Display` and `format!` for the given radix.
+    /// Inf and NaN are written as `Inf`, `-Inf`, and `NaN`.
+    /// The function returns the number of bytes appended to `buf`.
+    /// Formatting in radixes 2, 8, and 16 does not allocate memory if `buf` has enough capacity.
+    ///
+    /// ## Examples
+    ///
+    /// ``` rust
+    /// # use astro_float_num::{BigFloat, RoundingMode, Radix};
+    /// let mut buf = Vec::new();
+    ///
+    /// let len = BigFloat::from_f64(-0.375, 64).format_into(Radix::Dec, RoundingMode::ToEven, &mut buf).unwrap();
+    ///
+    /// assert_eq!(len, 8);
+    /// assert_eq!(buf, b"-3.75e-1");
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    pub fn format_into(
+        &self,
+        rdx: Radix,
+        rm: RoundingMode,
+        buf: &mut Vec<u8>,
+    ) -> Result<usize, Error> {
+        let s: &[u8] = match &self.inner {
+            Flavor::Value(v) => return v.format_into(rdx, rm, buf),
+            Flavor::Inf(sign) => {
+                if sign.is_negative() {
+                    b"-Inf"
+                } else {
+                    b"Inf"
+                }
+            }
+            Flavor::NaN(_) => b"NaN",
+        };
+
+        buf.try_reserve(s.len())?;
+        buf.extend_from_slice(s);
+
+        Ok(s.len())
+    }
+
+    /// Formats `self` like `format_into`, but if the mantissa has more than `2*k` digits, only the first and the last `k` digits are written,
+    /// separated by an ellipsis, and the total number of digits is appended in parentheses.
+    /// The function returns the number of bytes appended to `buf`.
+    ///
+    /// ## Examples
+    ///
+    /// ``` rust
+    /// # use astro_float_num::{BigFloat, RoundingMode, Radix};
+    /// let mut buf = Vec::new();
+    ///
+    /// let x = BigFloat::from_word(1, 256).div(&BigFloat::from_word(3, 64), 256, RoundingMode::ToEven);
+    /// x.format_abbrev_into(Radix::Hex, RoundingMode::ToEven, 4, &mut buf).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(buf).unwrap(), "5.555…5558_e-1 (65 digits)");
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - InvalidArgument: `k` is zero.
+    pub fn format_abbrev_into(
+        &self,
+        rdx: Radix,
+        rm: RoundingMode,
+        k: usize,
+        buf: &mut Vec<u8>,
+    ) -> Result<usize, Error> {
+        match &self.inner {
+            Flavor::Value(v) => v.format_abbrev_into(rdx, rm, k, buf),
+            _ if k == 0 => Err(Error::InvalidArgument),
+            _ => self.format_into(rdx, rm, buf),
+        }
+    }
+
+    /// Returns a wrapper of `self` which implements `Display` and `Debug` printing only the first and the last `k` digits
+    /// of the mantissa and the total number of digits, as described in `format_abbrev_into`.
+    /// It is useful for logging numbers with very large precision.
+    /// If `k` is zero, all digits are printed.
+    ///
+    /// ## Examples
+    ///
+    /// ``` rust
+    /// # use astro_float_num::{BigFloat, Consts, RoundingMode};
+    /// let mut cc = Consts::new().expect("Constants cache initialized");
+    /// let pi = cc.pi(4000, RoundingMode::ToEven);
+    ///
+    /// assert_eq!(format!("{}", pi.abbrev(6)), "3.14159…356372e+0 (1215 digits)");
+    /// ```
+    pub fn abbrev(&self, k: usize) -> Abbreviated<'_> {
+        Abbreviated { x: self, k }
+    }
+
     /// Returns true if `self` is inexact. The function returns false if `self` is Inf or NaN.
     pub fn inexact(&self) -> bool {
         if let Flavor::Value(v) = &self.inner {
@@ -1153,6 +3759,8 @@ macro_rules! gen_wrapper_arg_rm_cc {
     ($comment:literal, $fname:ident, $ret:ty, $pos_inf:block, $neg_inf:block, $($arg:ident, $arg_type:ty),*) => {
         #[doc=$comment]
         pub fn $fname(&self$(,$arg: $arg_type)*, rm: RoundingMode, cc: &mut Consts) -> $ret {
+            trace_op!(stringify!($fname)$(, $arg)*);
+
             match &self.inner {
                 Flavor::Value(v) => {
                     Self::result_to_ext(v.$fname($($arg,)* rm, cc), v.is_zero(), true)
@@ -1168,6 +3776,8 @@ macro_rules! gen_wrapper_log {
     ($comment:literal, $fname:ident, $ret:ty, $pos_inf:block, $neg_inf:block, $($arg:ident, $arg_type:ty),*) => {
         #[doc=$comment]
         pub fn $fname(&self$(,$arg: $arg_type)*, rm: RoundingMode, cc: &mut Consts) -> $ret {
+            trace_op!(stringify!($fname)$(, $arg)*);
+
             match &self.inner {
                 Flavor::Value(v) => {
                     if v.is_zero() {
@@ -1224,7 +3834,7 @@ impl BigFloat {
     );
     gen_wrapper_arg_rm!(
         "Computes the square root of a number with precision `p`. The result is rounded using the rounding mode `rm`.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         sqrt,
         Self,
         { INF_POS },
@@ -1234,7 +3844,7 @@ impl BigFloat {
     );
     gen_wrapper_arg_rm!(
         "Computes the cube root of a number with precision `p`. The result is rounded using the rounding mode `rm`.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         cbrt,
         Self,
         { INF_POS },
@@ -1245,7 +3855,7 @@ impl BigFloat {
     gen_wrapper_log!(
         "Computes the natural logarithm of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         ln,
         Self,
         { INF_POS },
@@ -1256,7 +3866,7 @@ impl BigFloat {
     gen_wrapper_log!(
         "Computes the logarithm base 2 of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         log2,
         Self,
         { INF_POS },
@@ -1267,7 +3877,7 @@ impl BigFloat {
     gen_wrapper_log!(
         "Computes the logarithm base 10 of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         log10,
         Self,
         { INF_POS },
@@ -1278,7 +3888,7 @@ impl BigFloat {
     gen_wrapper_arg_rm_cc!(
         "Computes `e` to the power of `self` with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         exp,
         Self,
         { INF_POS },
@@ -1286,11 +3896,35 @@ impl BigFloat {
         p,
         usize
     );
+    gen_wrapper_arg_rm_cc!(
+        "Computes 2 to the power of `self` minus 1 with precision `p`. The result is rounded using the rounding mode `rm`.
+        The result is accurate even if `self` is close to zero.
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        exp2m1,
+        Self,
+        { INF_POS },
+        { Self::from_i8(-1, DEFAULT_P) },
+        p,
+        usize
+    );
+    gen_wrapper_arg_rm_cc!(
+        "Computes 10 to the power of `self` minus 1 with precision `p`. The result is rounded using the rounding mode `rm`.
+        The result is accurate even if `self` is close to zero.
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        exp10m1,
+        Self,
+        { INF_POS },
+        { Self::from_i8(-1, DEFAULT_P) },
+        p,
+        usize
+    );
 
     gen_wrapper_arg_rm_cc!(
         "Computes the sine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         sin,
         Self,
         { NAN },
@@ -1301,7 +3935,7 @@ impl BigFloat {
     gen_wrapper_arg_rm_cc!(
         "Computes the cosine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         cos,
         Self,
         { NAN },
@@ -1312,7 +3946,7 @@ impl BigFloat {
     gen_wrapper_arg_rm_cc!(
         "Computes the tangent of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         tan,
         Self,
         { NAN },
@@ -1323,7 +3957,7 @@ impl BigFloat {
     gen_wrapper_arg_rm_cc!(
         "Computes the arcsine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.", 
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.", 
         asin,
         Self,
         {NAN},
@@ -1334,7 +3968,7 @@ impl BigFloat {
     gen_wrapper_arg_rm_cc!(
         "Computes the arccosine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         acos,
         Self,
         { NAN },
@@ -1342,11 +3976,118 @@ impl BigFloat {
         p,
         usize
     );
+    gen_wrapper_arg_rm_cc!(
+        "Computes the arcsine of a number divided by pi with precision `p`. The result is rounded using the rounding mode `rm`.
+        The result is in half-revolutions, in the interval [-1/2, 1/2].
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        asinpi,
+        Self,
+        { NAN },
+        { NAN },
+        p,
+        usize
+    );
+    gen_wrapper_arg_rm_cc!(
+        "Computes the arccosine of a number divided by pi with precision `p`. The result is rounded using the rounding mode `rm`.
+        The result is in half-revolutions, in the interval [0, 1].
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        acospi,
+        Self,
+        { NAN },
+        { NAN },
+        p,
+        usize
+    );
+
+    gen_wrapper_arg_rm_cc!(
+        "Converts an angle in degrees to radians with precision `p`. The result is rounded using the rounding mode `rm`.
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        to_radians,
+        Self,
+        { INF_POS },
+        { INF_NEG },
+        p,
+        usize
+    );
+    gen_wrapper_arg_rm_cc!(
+        "Converts an angle in radians to degrees with precision `p`. The result is rounded using the rounding mode `rm`.
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        to_degrees,
+        Self,
+        { INF_POS },
+        { INF_NEG },
+        p,
+        usize
+    );
+    gen_wrapper_arg_rm_cc!(
+        "Computes the sine of an angle in degrees with precision `p`. The result is rounded using the rounding mode `rm`.
+        The argument is reduced modulo 360 exactly, so the result is accurate for large angles, and exact for angles like 30 or 90.
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        sin_deg,
+        Self,
+        { NAN },
+        { NAN },
+        p,
+        usize
+    );
+    gen_wrapper_arg_rm_cc!(
+        "Computes the cosine of an angle in degrees with precision `p`. The result is rounded using the rounding mode `rm`.
+        The argument is reduced modulo 360 exactly, so the result is accurate for large angles, and exact for angles like 60 or 90.
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        cos_deg,
+        Self,
+        { NAN },
+        { NAN },
+        p,
+        usize
+    );
+    gen_wrapper_arg_rm_cc!(
+        "Computes the tangent of an angle in degrees with precision `p`. The result is rounded using the rounding mode `rm`.
+        The argument is reduced modulo 360 exactly, so the result is accurate for large angles, and exact for angles like 45.
+        The tangent of 90 + 360 * k is positive infinity, and the tangent of 270 + 360 * k is negative infinity.
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        tan_deg,
+        Self,
+        { NAN },
+        { NAN },
+        p,
+        usize
+    );
+
+    gen_wrapper_arg_rm_cc!(
+        "Reduces an angle in radians to the interval [0, 2*pi) with precision `p`. The result is rounded using the rounding mode `rm`.
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        rem_2pi,
+        Self,
+        { NAN },
+        { NAN },
+        p,
+        usize
+    );
+    gen_wrapper_arg_rm_cc!(
+        "Reduces an angle in radians to the interval (-pi, pi] with precision `p`. The result is rounded using the rounding mode `rm`.
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        rem_2pi_centered,
+        Self,
+        { NAN },
+        { NAN },
+        p,
+        usize
+    );
 
     gen_wrapper_arg_rm_cc!(
         "Computes the hyperbolic sine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache cc for computing the result. 
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         sinh,
         Self,
         { INF_POS },
@@ -1357,7 +4098,7 @@ impl BigFloat {
     gen_wrapper_arg_rm_cc!(
         "Computes the hyperbolic cosine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache cc for computing the result. 
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         cosh,
         Self,
         { INF_POS },
@@ -1368,7 +4109,7 @@ impl BigFloat {
     gen_wrapper_arg_rm_cc!(
         "Computes the hyperbolic arcsine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         asinh,
         Self,
         { INF_POS },
@@ -1379,7 +4120,7 @@ impl BigFloat {
     gen_wrapper_arg_rm_cc!(
         "Computes the hyperbolic arccosine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
         This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
         acosh,
         Self,
         { BigFloat::new(1) },
@@ -1387,24 +4128,243 @@ impl BigFloat {
         p,
         usize
     );
-    gen_wrapper_arg_rm_cc!(
-        "Computes the hyperbolic arctangent of a number with precision `p`. The result is rounded using the rounding mode `rm`.
-        This function requires constants cache `cc` for computing the result.
-        Precision is rounded upwards to the word size. The function returns NaN if the precision `p` is incorrect.",
-        atanh,
-        Self,
-        { BigFloat::new(1) },
-        { BigFloat::new(1) },
-        p,
-        usize
+    gen_wrapper_arg_rm_cc!(
+        "Computes the hyperbolic arctangent of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+        This function requires constants cache `cc` for computing the result.
+        Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`. The function returns NaN if the precision `p` is incorrect.",
+        atanh,
+        Self,
+        { BigFloat::new(1) },
+        { BigFloat::new(1) },
+        p,
+        usize
+    );
+}
+
+macro_rules! gen_try_op {
+    // binary operation with precision and rounding mode
+    ($comment:literal, $fname:ident, $op:ident) => {
+        #[doc=$comment]
+        ///
+        /// ## Errors
+        ///
+        ///  - ExponentOverflow: the result is too large or too small number.
+        ///  - DivisionByZero: the divisor is zero.
+        ///  - MemoryAllocation: failed to allocate memory.
+        ///  - InvalidArgument: the precision is incorrect, or the result is NaN.
+        pub fn $fname(&self, d2: &Self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+            match (&self.inner, &d2.inner) {
+                (Flavor::Value(v1), Flavor::Value(v2)) => v1.$op(v2, p, rm).map(Self::from),
+                _ => self.$op(d2, p, rm).nan_to_err(),
+            }
+        }
+    };
+}
+
+macro_rules! gen_try_unary_op {
+    // unary operation with precision and rounding mode
+    ($comment:literal, $fname:ident, $op:ident) => {
+        #[doc=$comment]
+        ///
+        /// ## Errors
+        ///
+        ///  - ExponentOverflow: the result is too large or too small number.
+        ///  - MemoryAllocation: failed to allocate memory.
+        ///  - InvalidArgument: the precision is incorrect, or the argument is outside of the domain of the function.
+        pub fn $fname(&self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+            match &self.inner {
+                Flavor::Value(v) => v.$op(p, rm).map(Self::from),
+                _ => self.$op(p, rm).nan_to_err(),
+            }
+        }
+    };
+}
+
+macro_rules! gen_try_unary_op_cc {
+    // unary operation with precision, rounding mode, and constants cache
+    ($comment:literal, $fname:ident, $op:ident) => {
+        #[doc=$comment]
+        ///
+        /// ## Errors
+        ///
+        ///  - ExponentOverflow: the result is too large or too small number.
+        ///  - MemoryAllocation: failed to allocate memory.
+        ///  - InvalidArgument: the precision is incorrect, or the argument is outside of the domain of the function.
+        pub fn $fname(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+            match &self.inner {
+                Flavor::Value(v) => v.$op(p, rm, cc).map(Self::from),
+                _ => self.$op(p, rm, cc).nan_to_err(),
+            }
+        }
+    };
+}
+
+/// Checked operations which return an error instead of NaN or infinity when the operation fails.
+/// If any of the arguments is infinite, the result of the corresponding operation is returned, or an error if the result is NaN.
+/// If any of the arguments is NaN, the error it holds is returned, or InvalidArgument.
+impl BigFloat {
+    gen_try_op!(
+        "Adds `d2` to `self` and returns the result of the operation with precision `p` rounded according to `rm`.",
+        try_add,
+        add
+    );
+    gen_try_op!(
+        "Subtracts `d2` from `self` and returns the result of the operation with precision `p` rounded according to `rm`.",
+        try_sub,
+        sub
+    );
+    gen_try_op!(
+        "Multiplies `d2` by `self` and returns the result of the operation with precision `p` rounded according to `rm`.",
+        try_mul,
+        mul
+    );
+    gen_try_op!(
+        "Divides `self` by `d2` and returns the result of the operation with precision `p` rounded according to `rm`.",
+        try_div,
+        div
+    );
+    gen_try_unary_op!(
+        "Computes the reciprocal of `self` with precision `p` rounded according to `rm`.",
+        try_reciprocal,
+        reciprocal
+    );
+    gen_try_unary_op!(
+        "Computes the square root of `self` with precision `p` rounded according to `rm`.",
+        try_sqrt,
+        sqrt
+    );
+    gen_try_unary_op!(
+        "Computes the cube root of `self` with precision `p` rounded according to `rm`.",
+        try_cbrt,
+        cbrt
+    );
+    gen_try_unary_op_cc!(
+        "Computes the natural logarithm of `self` with precision `p` rounded according to `rm`.",
+        try_ln,
+        ln
+    );
+    gen_try_unary_op_cc!(
+        "Computes `e` to the power of `self` with precision `p` rounded according to `rm`.",
+        try_exp,
+        exp
     );
+
+    /// Returns the remainder of division of `|self|` by `|d2|`. The sign of the result is set to the sign of `self`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: `d2` is zero, or the result is NaN.
+    pub fn try_rem(&self, d2: &Self) -> Result<Self, Error> {
+        match (&self.inner, &d2.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2)) => v1.rem(v2).map(Self::from),
+            _ => self.rem(d2).nan_to_err(),
+        }
+    }
+
+    /// Computes the power of `self` to the `n` with precision `p` rounded according to `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large or too small number.
+    ///  - DivisionByZero: `self` is zero, and `n` is negative.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect, or `self` is negative and `n` is not an integer.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::Error;
+    /// # use astro_float_num::RoundingMode;
+    /// # fn main() -> Result<(), Error> {
+    /// let mut cc = Consts::new()?;
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// let two = BigFloat::from_word(2, 64);
+    /// let x = two.try_pow(&BigFloat::from_f64(0.5, 64), 128, rm, &mut cc)?;
+    ///
+    /// assert_eq!(x, two.try_sqrt(128, rm)?);
+    ///
+    /// let m = BigFloat::max_value(128);
+    /// assert_eq!(m.try_mul(&m, 128, rm), Err(Error::ExponentOverflow(astro_float_num::Sign::Pos)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_pow(
+        &self,
+        n: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        match (&self.inner, &n.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2)) => v1.pow(v2, p, rm, cc).map(Self::from),
+            _ => self.pow(n, p, rm, cc).nan_to_err(),
+        }
+    }
+
+    // Converts NaN to the error it holds, or to InvalidArgument.
+    fn nan_to_err(self) -> Result<Self, Error> {
+        match self.inner {
+            Flavor::NaN(err) => Err(err.unwrap_or(Error::InvalidArgument)),
+            _ => Ok(self),
+        }
+    }
+
+    // Returns the finite value of `self`, or the error for Inf and NaN.
+    pub(crate) fn try_into_number(self) -> Result<BigFloatNumber, Error> {
+        match self.inner {
+            Flavor::Value(v) => Ok(v),
+            Flavor::Inf(s) => Err(Error::ExponentOverflow(s)),
+            Flavor::NaN(err) => Err(err.unwrap_or(Error::InvalidArgument)),
+        }
+    }
+}
+
+/// A base prepared for raising to many exponents with the fixed precision.
+/// An instance is created using `BigFloat::prepare_pow`.
+#[derive(Debug)]
+pub struct PowCache {
+    base: BigFloat,
+    p: usize,
+    ln: Option<BigFloatNumber>,
+}
+
+impl PowCache {
+    /// Returns the base.
+    pub fn base(&self) -> &BigFloat {
+        &self.base
+    }
+
+    /// Returns the precision of the results.
+    pub fn precision(&self) -> usize {
+        self.p
+    }
+
+    /// Compute the power of the base to the `n`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// The result is the same as the result of `BigFloat::pow` with the precision of the cache.
+    /// The function returns NaN if the precision of the cache is incorrect.
+    pub fn pow(&mut self, n: &BigFloat, rm: RoundingMode, cc: &mut Consts) -> BigFloat {
+        match (&self.base.inner, &n.inner) {
+            (Flavor::Value(v1), Flavor::Value(v2)) => BigFloat::result_to_ext(
+                v1.pow_with_ln(v2, self.p, rm, cc, &mut self.ln),
+                v1.is_zero(),
+                v1.sign() == v2.sign(),
+            ),
+            _ => self.base.pow(n, self.p, rm, cc),
+        }
+    }
 }
 
 macro_rules! impl_int_conv {
     ($s:ty, $from_s:ident) => {
         impl BigFloat {
             /// Constructs BigFloat with precision `p` from an integer value `i`.
-            /// Precision is rounded upwards to the word size.
+            /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
             /// The function returns NaN if the precision `p` is incorrect.
             pub fn $from_s(i: $s, p: usize) -> Self {
                 Self::result_to_ext(BigFloatNumber::$from_s(i, p), false, true)
@@ -1441,9 +4401,69 @@ impl From<BigFloatNumber> for BigFloat {
     }
 }
 
+macro_rules! impl_try_from_float {
+    ($t:ty, $conv:ident) => {
+        /// Conversion which succeeds only if the value is represented exactly.
+        /// Infinity and NaN are converted to infinity and NaN of the target type.
+        ///
+        /// ## Errors
+        ///
+        ///  - ExponentOverflow: the number exceeds the range of the target type.
+        ///  - Inexact: the number can't be represented exactly in the target type.
+        ///  - MemoryAllocation: failed to allocate memory.
+        impl TryFrom<&BigFloat> for $t {
+            type Error = Error;
+
+            fn try_from(x: &BigFloat) -> Result<Self, Self::Error> {
+                match &x.inner {
+                    Flavor::Value(v) => match v.$conv(RoundingMode::ToZero)? {
+                        (f, false) => Ok(f),
+                        (_, true) => Err(Error::Inexact),
+                    },
+                    Flavor::NaN(_) => Ok(<$t>::NAN),
+                    Flavor::Inf(Sign::Pos) => Ok(<$t>::INFINITY),
+                    Flavor::Inf(Sign::Neg) => Ok(<$t>::NEG_INFINITY),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from_float!(f64, to_f64_rm);
+impl_try_from_float!(f32, to_f32_rm);
+
+macro_rules! impl_try_from_int {
+    ($t:ty, $conv:ident) => {
+        /// Conversion which succeeds only if the value is an integer within the range of the target type.
+        ///
+        /// ## Errors
+        ///
+        ///  - InvalidArgument: the number is NaN, or it is negative and the target type is unsigned.
+        ///  - ExponentOverflow: the number is infinite, or exceeds the range of the target type.
+        ///  - Inexact: the number is not an integer.
+        impl TryFrom<&BigFloat> for $t {
+            type Error = Error;
+
+            fn try_from(x: &BigFloat) -> Result<Self, Self::Error> {
+                match &x.inner {
+                    Flavor::Value(v) => v.$conv(),
+                    Flavor::NaN(_) => Err(Error::InvalidArgument),
+                    Flavor::Inf(s) => Err(Error::ExponentOverflow(*s)),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from_int!(i64, to_i64_exact);
+impl_try_from_int!(u64, to_u64_exact);
+
+use core::fmt::Alignment;
 use core::fmt::Binary;
+use core::fmt::Debug;
 use core::fmt::Octal;
 use core::fmt::UpperHex;
+use core::ops::{Add, Div, Mul, Rem, Sub};
 use core::{
     cmp::Eq, cmp::Ordering, cmp::PartialEq, cmp::PartialOrd, fmt::Display, fmt::Formatter,
     ops::Neg, str::FromStr,
@@ -1466,6 +4486,22 @@ impl Neg for &BigFloat {
     }
 }
 
+/// Truncated modulo, see [BigFloat::rem].
+impl Rem for BigFloat {
+    type Output = BigFloat;
+    fn rem(self, d2: Self) -> Self::Output {
+        BigFloat::rem(&self, &d2)
+    }
+}
+
+/// Truncated modulo, see [BigFloat::rem].
+impl Rem<&BigFloat> for &BigFloat {
+    type Output = BigFloat;
+    fn rem(self, d2: &BigFloat) -> Self::Output {
+        BigFloat::rem(self, d2)
+    }
+}
+
 //
 // ordering traits
 //
@@ -1522,6 +4558,107 @@ impl<'a> PartialOrd<&'a BigFloat> for BigFloat {
     }
 }
 
+macro_rules! impl_cmp_prim {
+    ($t:ty, $u:ty, $from:ident, $p:expr) => {
+        /// Comparison which converts the primitive value to BigFloat exactly.
+        impl PartialEq<$t> for BigFloat {
+            fn eq(&self, other: &$t) -> bool {
+                let other = BigFloat::$from(*other as $u, $p);
+                matches!(BigFloat::cmp(self, &other), Some(0))
+            }
+        }
+
+        impl PartialEq<BigFloat> for $t {
+            fn eq(&self, other: &BigFloat) -> bool {
+                other == self
+            }
+        }
+
+        /// Comparison which converts the primitive value to BigFloat exactly.
+        impl PartialOrd<$t> for BigFloat {
+            fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                let other = BigFloat::$from(*other as $u, $p);
+                BigFloat::cmp(self, &other).map(|v| v.cmp(&0))
+            }
+        }
+
+        impl PartialOrd<BigFloat> for $t {
+            fn partial_cmp(&self, other: &BigFloat) -> Option<Ordering> {
+                other.partial_cmp(self).map(|o| o.reverse())
+            }
+        }
+    };
+}
+
+impl_cmp_prim!(i8, i64, from_i64, 64);
+impl_cmp_prim!(i16, i64, from_i64, 64);
+impl_cmp_prim!(i32, i64, from_i64, 64);
+impl_cmp_prim!(i64, i64, from_i64, 64);
+impl_cmp_prim!(i128, i128, from_i128, 128);
+impl_cmp_prim!(isize, i64, from_i64, 64);
+impl_cmp_prim!(u8, u64, from_u64, 64);
+impl_cmp_prim!(u16, u64, from_u64, 64);
+impl_cmp_prim!(u32, u64, from_u64, 64);
+impl_cmp_prim!(u64, u64, from_u64, 64);
+impl_cmp_prim!(u128, u128, from_u128, 128);
+impl_cmp_prim!(usize, u64, from_u64, 64);
+impl_cmp_prim!(f32, f64, from_f64, 64);
+impl_cmp_prim!(f64, f64, from_f64, 64);
+
+//
+// arithmetic with primitive types
+//
+
+macro_rules! impl_arith_prim_op {
+    ($t:ty, $u:ty, $from:ident, $p:expr, $trait:ident, $fn:ident) => {
+        /// The primitive value is converted to BigFloat exactly.
+        /// The result has the precision of the BigFloat operand and is rounded using `DEFAULT_RM`.
+        impl $trait<$t> for &BigFloat {
+            type Output = BigFloat;
+            fn $fn(self, d2: $t) -> BigFloat {
+                let p = self.mantissa_max_bit_len().unwrap_or(DEFAULT_P);
+                let d2 = BigFloat::$from(d2 as $u, $p);
+                BigFloat::$fn(self, &d2, p, DEFAULT_RM)
+            }
+        }
+
+        /// The primitive value is converted to BigFloat exactly.
+        /// The result has the precision of the BigFloat operand and is rounded using `DEFAULT_RM`.
+        impl $trait<&BigFloat> for $t {
+            type Output = BigFloat;
+            fn $fn(self, d2: &BigFloat) -> BigFloat {
+                let p = d2.mantissa_max_bit_len().unwrap_or(DEFAULT_P);
+                let d1 = BigFloat::$from(self as $u, $p);
+                BigFloat::$fn(&d1, d2, p, DEFAULT_RM)
+            }
+        }
+    };
+}
+
+macro_rules! impl_arith_prim {
+    ($t:ty, $u:ty, $from:ident, $p:expr) => {
+        impl_arith_prim_op!($t, $u, $from, $p, Add, add);
+        impl_arith_prim_op!($t, $u, $from, $p, Sub, sub);
+        impl_arith_prim_op!($t, $u, $from, $p, Mul, mul);
+        impl_arith_prim_op!($t, $u, $from, $p, Div, div);
+    };
+}
+
+impl_arith_prim!(i8, i64, from_i64, 64);
+impl_arith_prim!(i16, i64, from_i64, 64);
+impl_arith_prim!(i32, i64, from_i64, 64);
+impl_arith_prim!(i64, i64, from_i64, 64);
+impl_arith_prim!(i128, i128, from_i128, 128);
+impl_arith_prim!(isize, i64, from_i64, 64);
+impl_arith_prim!(u8, u64, from_u64, 64);
+impl_arith_prim!(u16, u64, from_u64, 64);
+impl_arith_prim!(u32, u64, from_u64, 64);
+impl_arith_prim!(u64, u64, from_u64, 64);
+impl_arith_prim!(u128, u128, from_u128, 128);
+impl_arith_prim!(usize, u64, from_u64, 64);
+impl_arith_prim!(f32, f64, from_f64, 64);
+impl_arith_prim!(f64, f64, from_f64, 64);
+
 impl Default for BigFloat {
     fn default() -> BigFloat {
         BigFloat::new(DEFAULT_P)
@@ -1549,6 +4686,8 @@ macro_rules! impl_from {
 
 impl_from!(f32, from_f32);
 impl_from!(f64, from_f64);
+#[cfg(feature = "f128")]
+impl_from!(f128, from_f128);
 impl_from!(i8, from_i8);
 impl_from!(i16, from_i16);
 impl_from!(i32, from_i32);
@@ -1564,7 +4703,7 @@ macro_rules! impl_format_rdx {
     ($trait:ty, $rdx:path) => {
         impl $trait for BigFloat {
             fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
-                self.write_str(f, $rdx, DEFAULT_RM)
+                self.fmt_rdx(f, $rdx)
             }
         }
     };
@@ -1575,6 +4714,51 @@ impl_format_rdx!(Octal, Radix::Oct);
 impl_format_rdx!(Display, Radix::Dec);
 impl_format_rdx!(UpperHex, Radix::Hex);
 
+impl Debug for BigFloat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match &self.inner {
+            Flavor::Value(v) => v.fmt_debug("BigFloat", f),
+            Flavor::NaN(err) => f.debug_tuple("NaN").field(err).finish(),
+            Flavor::Inf(s) => f.debug_tuple("Inf").field(s).finish(),
+        }
+    }
+}
+
+/// Wrapper of a BigFloat which prints only the first and the last digits of the mantissa.
+/// It is returned by [BigFloat::abbrev].
+#[derive(Clone, Copy)]
+pub struct Abbreviated<'a> {
+    x: &'a BigFloat,
+    k: usize,
+}
+
+impl Display for Abbreviated<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        if self.k == 0 {
+            return self.x.write_str(f, Radix::Dec, DEFAULT_RM);
+        }
+
+        let mut buf = Vec::new();
+
+        match self
+            .x
+            .format_abbrev_into(Radix::Dec, DEFAULT_RM, self.k, &mut buf)
+        {
+            Ok(_) => f.write_str(core::str::from_utf8(&buf).map_err(|_| core::fmt::Error)?),
+            Err(Error::ExponentOverflow(s)) => {
+                f.write_str(if s.is_positive() { "Inf" } else { "-Inf" })
+            }
+            Err(_) => f.write_str("Err"),
+        }
+    }
+}
+
+impl Debug for Abbreviated<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Display::fmt(self, f)
+    }
+}
+
 /// A trait for conversion with additional arguments.
 pub trait FromExt<T> {
     /// Converts `v` to BigFloat with precision `p` using rounding mode `rm`.
@@ -1605,20 +4789,24 @@ impl FromExt<&str> for BigFloat {
 mod tests {
 
     use crate::common::util::rand_p;
+    use crate::common::util::round_p;
     use crate::defs::DEFAULT_P;
     use crate::ext::ONE;
     use crate::ext::TWO;
     use crate::BigFloat;
     use crate::Consts;
     use crate::Error;
+    use crate::Exponent;
     use crate::Radix;
     use crate::Sign;
     use crate::Word;
+    use crate::EXPONENT_MIN;
     use crate::INF_NEG;
     use crate::INF_POS;
     use crate::NAN;
     use crate::{defs::RoundingMode, WORD_BIT_SIZE};
 
+    use core::cmp::Ordering;
     use core::num::FpCategory;
     #[cfg(feature = "std")]
     use std::str::FromStr;
@@ -1629,6 +4817,12 @@ mod tests {
     #[cfg(not(feature = "std"))]
     use alloc::format;
 
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
     #[test]
     fn test_ext() {
         let rm = RoundingMode::ToOdd;
@@ -1694,6 +4888,24 @@ mod tests {
         assert!(INF_NEG.as_raw_parts().is_none());
         assert!(NAN.as_raw_parts().is_none());
 
+        let (m, n, s, e, inexact) = TWO.clone().into_raw_parts().unwrap();
+        assert!(BigFloat::from_raw_vec(m, n, s, e, inexact).identical(&TWO));
+        assert!(INF_POS.into_raw_parts().is_none());
+        assert!(NAN.into_raw_parts().is_none());
+        assert!(BigFloat::from_raw_vec(vec![1, 0], 1, Sign::Pos, 0, false).is_nan());
+        assert!(
+            BigFloat::from_raw_vec(vec![0, 1], WORD_BIT_SIZE + 1, Sign::Pos, 0, false).is_nan()
+        );
+        assert!(!BigFloat::from_raw_vec(
+            vec![0, 1],
+            WORD_BIT_SIZE + 1,
+            Sign::Pos,
+            EXPONENT_MIN,
+            false
+        )
+        .is_nan());
+        assert!(BigFloat::from_raw_vec(vec![0, 1], 1, Sign::Pos, EXPONENT_MIN, false).is_nan());
+
         assert!(ONE.add(&ONE, rand_p(), rm).cmp(&TWO) == Some(0));
         assert!(ONE.add(&INF_POS, rand_p(), rm).is_inf_pos());
         assert!(INF_POS.add(&ONE, rand_p(), rm).is_inf_pos());
@@ -1870,6 +5082,72 @@ mod tests {
         assert!(NAN.abs_cmp(&INF_NEG).is_none());
         assert!(NAN.abs_cmp(&NAN).is_none());
 
+        let d1 = BigFloat::from_i8(-3, DEFAULT_P);
+        let d2 = BigFloat::from_i8(2, DEFAULT_P);
+        assert!(d1.abs_cmp(&d2).unwrap() > 0);
+        assert!(d2.abs_cmp(&d1).unwrap() < 0);
+        assert!(d1.abs_cmp(&d1.neg()).unwrap() == 0);
+        assert_eq!(d1.cmp_abs(&d2), Some(Ordering::Greater));
+        assert_eq!(d2.cmp_abs(&d1), Some(Ordering::Less));
+        assert_eq!(d2.cmp_abs(&d2.neg()), Some(Ordering::Equal));
+        assert_eq!(INF_NEG.cmp_abs(&d1), Some(Ordering::Greater));
+        assert_eq!(d1.cmp_abs(&NAN), None);
+
+        assert_eq!(d1.max_abs(&d2), d1);
+        assert_eq!(d2.max_abs(&d1), d1);
+        assert_eq!(d1.min_abs(&d2), d2);
+        assert_eq!(d2.min_abs(&d1), d2);
+        assert!(d2.max_abs(&INF_NEG).is_inf_neg());
+        assert_eq!(d2.min_abs(&INF_NEG), d2);
+        assert!(d2.max_abs(&NAN).is_nan());
+        assert!(NAN.min_abs(&d2).is_nan());
+
+        assert_eq!(d1.fmax(&d2), d2);
+        assert_eq!(d1.fmin(&d2), d1);
+        assert_eq!(NAN.fmax(&d1), d1);
+        assert_eq!(d1.fmax(&NAN), d1);
+        assert_eq!(NAN.fmin(&d2), d2);
+        assert_eq!(d2.fmin(&NAN), d2);
+        assert!(NAN.fmax(&NAN).is_nan());
+        assert!(NAN.fmin(&NAN).is_nan());
+
+        let zp = BigFloat::from_word(0, DEFAULT_P);
+        let zn = zp.neg();
+        assert_eq!(d1.maximum(&d2), d2);
+        assert_eq!(d1.minimum(&d2), d1);
+        assert!(zn.maximum(&zp).is_positive());
+        assert!(zp.maximum(&zn).is_positive());
+        assert!(zn.minimum(&zp).is_negative());
+        assert!(zp.minimum(&zn).is_negative());
+        assert!(d1.maximum(&INF_POS).is_inf_pos());
+        assert!(d1.minimum(&INF_NEG).is_inf_neg());
+        assert!(NAN.maximum(&d1).is_nan());
+        assert!(d1.minimum(&NAN).is_nan());
+        assert_eq!(d1.maximum_number(&d2), d2);
+        assert_eq!(d1.minimum_number(&d2), d1);
+        assert!(zn.maximum_number(&zp).is_positive());
+        assert!(zp.minimum_number(&zn).is_negative());
+        assert_eq!(NAN.maximum_number(&d1), d1);
+        assert_eq!(d2.minimum_number(&NAN), d2);
+        assert!(NAN.maximum_number(&NAN).is_nan());
+        assert!(NAN.minimum_number(&NAN).is_nan());
+
+        let rm = RoundingMode::ToEven;
+        assert_eq!(
+            d2.positive_difference(&d1, DEFAULT_P, rm),
+            BigFloat::from_i8(5, DEFAULT_P)
+        );
+        let z = d1.positive_difference(&d2, DEFAULT_P, rm);
+        assert!(z.is_zero() && z.is_positive());
+        assert!(d2.positive_difference(&d2, DEFAULT_P, rm).is_zero());
+        assert!(INF_POS.positive_difference(&d1, DEFAULT_P, rm).is_inf_pos());
+        assert!(d1.positive_difference(&INF_POS, DEFAULT_P, rm).is_zero());
+        assert!(INF_POS
+            .positive_difference(&INF_POS, DEFAULT_P, rm)
+            .is_zero());
+        assert!(d1.positive_difference(&NAN, DEFAULT_P, rm).is_nan());
+        assert!(NAN.positive_difference(&d1, DEFAULT_P, rm).is_nan());
+
         assert!(ONE.is_positive());
         assert!(!ONE.is_negative());
 
@@ -1923,6 +5201,25 @@ mod tests {
         assert!(INF_POS.pow(&INF_NEG, rand_p(), rm, &mut cc).is_zero());
         assert!(INF_NEG.pow(&INF_NEG, rand_p(), rm, &mut cc).is_zero());
 
+        assert!(INF_POS.to_radians(rand_p(), rm, &mut cc).is_inf_pos());
+        assert!(INF_NEG.to_degrees(rand_p(), rm, &mut cc).is_inf_neg());
+        assert!(INF_POS.sin_deg(rand_p(), rm, &mut cc).is_nan());
+        assert!(NAN.cos_deg(rand_p(), rm, &mut cc).is_nan());
+        let d90 = BigFloat::from_word(90, DEFAULT_P);
+        assert!(d90.tan_deg(rand_p(), rm, &mut cc).is_inf_pos());
+        assert!(d90.neg().tan_deg(rand_p(), rm, &mut cc).is_inf_neg());
+        assert!(d90.sin_deg(rand_p(), rm, &mut cc).cmp(&ONE) == Some(0));
+        assert!(d90.cos_deg(rand_p(), rm, &mut cc).is_zero());
+        assert!(INF_POS.rem_2pi(rand_p(), rm, &mut cc).is_nan());
+        assert!(NAN.rem_2pi_centered(rand_p(), rm, &mut cc).is_nan());
+        let (r, q) = INF_NEG.rem_2pi_quo(false, rand_p(), rm, &mut cc);
+        assert!(r.is_nan() && q.is_nan());
+        let (r, k) = INF_POS.rem_pi2(rand_p(), rm, &mut cc);
+        assert!(r.is_nan() && k == 0);
+        assert!(NAN.sin_quadrant(rand_p(), rm, &mut cc).0.is_nan());
+        assert!(INF_NEG.cos_quadrant(rand_p(), rm, &mut cc).0.is_nan());
+        assert!(INF_POS.tan_quadrant(rand_p(), rm, &mut cc).0.is_nan());
+
         let half = ONE.div(&TWO, rand_p(), rm);
         assert!(TWO.log(&TWO, rand_p(), rm, &mut cc).cmp(&ONE) == Some(0));
         assert!(TWO.log(&INF_POS, rand_p(), rm, &mut cc).is_zero());
@@ -2016,6 +5313,100 @@ mod tests {
         assert!(INF_NEG.cbrt(rand_p(), rm).is_inf_neg());
         assert!(INF_POS.cbrt(rand_p(), rm).is_inf_pos());
         assert!(NAN.cbrt(rand_p(), rm).is_nan());
+        assert!(INF_NEG.hypot3(&ONE, &NAN, rand_p(), rm).is_inf_pos());
+        assert!(ONE.hypot3(&NAN, &ONE, rand_p(), rm).is_nan());
+        assert!(INF_POS.exp2m1(rand_p(), rm, &mut cc).is_inf_pos());
+        assert_eq!(INF_NEG.exp10m1(rand_p(), rm, &mut cc), ONE.neg());
+        assert!(NAN.exp2m1(rand_p(), rm, &mut cc).is_nan());
+        assert!(INF_POS.log2p1(rand_p(), rm, &mut cc).is_inf_pos());
+        assert!(INF_NEG.log10p1(rand_p(), rm, &mut cc).is_nan());
+        assert!(ONE.neg().log2p1(rand_p(), rm, &mut cc).is_inf_neg());
+        assert!(TWO.neg().log10p1(rand_p(), rm, &mut cc).is_nan());
+        assert!(INF_POS.asinpi(rand_p(), rm, &mut cc).is_nan());
+        assert!(INF_NEG.acospi(rand_p(), rm, &mut cc).is_nan());
+        assert_eq!(
+            INF_NEG.atanpi(128, rm, &mut cc),
+            BigFloat::from_f64(-0.5, 128)
+        );
+        assert!(NAN.atanpi(rand_p(), rm, &mut cc).is_nan());
+        assert_eq!(
+            INF_POS.atan2pi(&INF_NEG, 128, rm, &mut cc),
+            BigFloat::from_f64(0.75, 128)
+        );
+        assert_eq!(
+            INF_NEG.atan2pi(&ONE, 128, rm, &mut cc),
+            BigFloat::from_f64(-0.5, 128)
+        );
+        assert_eq!(
+            ONE.neg().atan2pi(&INF_NEG, 128, rm, &mut cc),
+            BigFloat::from_f64(-1.0, 128)
+        );
+        assert!(ONE.neg().atan2pi(&INF_POS, 128, rm, &mut cc).is_zero());
+        assert!(ONE.neg().atan2pi(&INF_POS, 128, rm, &mut cc).is_negative());
+        assert!(ONE.atan2pi(&NAN, rand_p(), rm, &mut cc).is_nan());
+        assert!(INF_POS.rootn(4, false, rand_p(), rm, &mut cc).is_inf_pos());
+        assert!(INF_NEG.rootn(5, true, rand_p(), rm, &mut cc).is_inf_neg());
+        assert!(INF_NEG.rootn(5, false, rand_p(), rm, &mut cc).is_nan());
+        assert!(INF_NEG.rootn(4, true, rand_p(), rm, &mut cc).is_nan());
+        assert!(INF_POS.rootn(0, true, rand_p(), rm, &mut cc).is_nan());
+        assert!(TWO.rootn(0, true, rand_p(), rm, &mut cc).is_nan());
+        assert!(NAN.rootn(3, true, rand_p(), rm, &mut cc).is_nan());
+        assert!(INF_POS.compound(&TWO, rand_p(), rm, &mut cc).is_inf_pos());
+        assert!(INF_POS
+            .compound(&TWO.neg(), rand_p(), rm, &mut cc)
+            .is_zero());
+        assert!(ONE.compound(&INF_POS, rand_p(), rm, &mut cc).is_inf_pos());
+        assert!(ONE
+            .neg()
+            .compound(&TWO.neg(), rand_p(), rm, &mut cc)
+            .is_inf_pos());
+        assert!(TWO.neg().compound(&TWO, rand_p(), rm, &mut cc).is_nan());
+        assert!(NAN.compound(&TWO, rand_p(), rm, &mut cc).is_nan());
+        assert!(ONE
+            .pow_ratio(&BigFloat::new(128), &TWO, rand_p(), rm, &mut cc)
+            .is_inf_pos());
+        assert!(ONE
+            .pow_ratio(&INF_NEG, &TWO, rand_p(), rm, &mut cc)
+            .is_zero());
+        assert!(ONE
+            .neg()
+            .pow_ratio(&TWO, &BigFloat::from_f64(0.5, 64), rand_p(), rm, &mut cc)
+            .is_nan());
+        assert_eq!(
+            TWO.neg().pow_ratio(&ONE, &TWO, 128, rm, &mut cc),
+            BigFloat::from_word(4, 128)
+        );
+        assert!(BigFloat::norm(&[ONE.clone(), INF_NEG], rand_p(), rm).is_inf_pos());
+        assert!(BigFloat::norm(&[], rand_p(), rm).is_zero());
+        assert!(BigFloat::max_value(128)
+            .hypot3(&ONE, &BigFloat::max_value(128), rand_p(), rm)
+            .is_inf_pos());
+        assert_eq!(INF_NEG.jacobi_sn(&ONE, 128, rm, &mut cc), ONE.neg());
+        assert!(INF_POS.jacobi_cn(&ONE, rand_p(), rm, &mut cc).is_zero());
+        assert!(INF_POS
+            .jacobi_dn(&BigFloat::from_f64(0.5, 64), rand_p(), rm, &mut cc)
+            .is_nan());
+        assert!(ONE.jacobi_sn(&TWO, rand_p(), rm, &mut cc).is_nan());
+        assert!(ONE.jacobi_sn(&NAN, rand_p(), rm, &mut cc).is_nan());
+        let (sn, cn, dn) = INF_POS.jacobi_elliptic(&ONE, 128, rm, &mut cc);
+        assert!(sn == *ONE && cn.is_zero() && dn.is_zero());
+        let (sn, cn, dn) = ONE.jacobi_elliptic(&TWO, rand_p(), rm, &mut cc);
+        assert!(sn.is_nan() && cn.is_nan() && dn.is_nan());
+        assert!(INF_NEG.legendre(3, rand_p(), rm).is_inf_neg());
+        assert!(INF_NEG.legendre(4, rand_p(), rm).is_inf_pos());
+        assert!(INF_POS.assoc_legendre(4, 1, rand_p(), rm).is_nan());
+        assert!(INF_POS.assoc_legendre(4, 5, rand_p(), rm).is_zero());
+        assert_eq!(INF_POS.legendre(0, 128, rm), *ONE);
+        assert!(NAN.legendre(3, rand_p(), rm).is_nan());
+        assert_eq!(INF_NEG.log_add_exp(&TWO, 128, rm, &mut cc), *TWO);
+        assert!(INF_NEG
+            .log_add_exp(&INF_NEG, rand_p(), rm, &mut cc)
+            .is_inf_neg());
+        assert!(ONE
+            .log_add_exp(&INF_POS, rand_p(), rm, &mut cc)
+            .is_inf_pos());
+        assert!(NAN.log_add_exp(&ONE, rand_p(), rm, &mut cc).is_nan());
+        assert!(BigFloat::log_sum_exp(&[], rand_p(), rm, &mut cc).is_nan());
 
         for op in [BigFloat::ln, BigFloat::log2, BigFloat::log10] {
             assert!(op(&INF_NEG, rand_p(), rm, &mut cc).is_nan());
@@ -2091,11 +5482,11 @@ mod tests {
         let d1 = ONE.clone();
         assert!(d1.exponent() == Some(1));
         let words: &[Word] = {
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(word32))]
             {
                 &[0, 0x8000000000000000]
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(word32)]
             {
                 &[0, 0, 0, 0x80000000]
             }
@@ -2213,13 +5604,298 @@ mod tests {
         assert!(n1.is_nan());
         assert!(n1.err() == Some(Error::InvalidArgument));
 
-        assert!(n1.convert_to_radix(Radix::Dec, RoundingMode::None) == Err(Error::InvalidArgument));
         assert!(
-            INF_POS.convert_to_radix(Radix::Dec, RoundingMode::None) == Err(Error::InvalidArgument)
+            n1.convert_to_radix_digits(Radix::Dec, None, RoundingMode::None)
+                == Err(Error::InvalidArgument)
+        );
+        assert!(
+            INF_POS.convert_to_radix_digits(Radix::Dec, None, RoundingMode::None)
+                == Err(Error::InvalidArgument)
         );
         assert!(
-            INF_NEG.convert_to_radix(Radix::Dec, RoundingMode::None) == Err(Error::InvalidArgument)
+            INF_NEG.convert_to_radix_digits(Radix::Dec, None, RoundingMode::None)
+                == Err(Error::InvalidArgument)
+        );
+
+        // all digits are converted without `max_digits`
+        let d = ONE.convert_to_radix(Radix::Dec, RoundingMode::None);
+        assert_eq!(
+            d,
+            ONE.convert_to_radix_digits(Radix::Dec, None, RoundingMode::None)
+        );
+
+        // exact conversion to primitives
+        let third = ONE.div(&BigFloat::from_word(3, 64), 128, RoundingMode::None);
+        let two_pow = |e: Exponent| {
+            let mut x = BigFloat::from_word(1, 64);
+            x.set_exponent(e + 1);
+            x
+        };
+
+        for f in [
+            1.5,
+            f64::MAX,
+            f64::MIN,
+            f64::MIN_POSITIVE,
+            f64::MIN_POSITIVE / 1024.0,
+            -5e-324,
+            123456789.0625,
+        ] {
+            let x = BigFloat::from_f64(f, 128);
+            assert_eq!(f64::try_from(&x).unwrap().to_bits(), f.to_bits());
+            assert_eq!(x.to_f64_round(RoundingMode::Up).to_bits(), f.to_bits());
+        }
+        assert_eq!(f32::try_from(&BigFloat::from_f64(0.375, 64)), Ok(0.375f32));
+        assert_eq!(
+            f32::try_from(&BigFloat::from_f32(f32::MIN_POSITIVE / 8.0, 64)),
+            Ok(f32::MIN_POSITIVE / 8.0)
+        );
+        assert_eq!(f64::try_from(&third), Err(Error::Inexact));
+        assert_eq!(
+            f32::try_from(&BigFloat::from_f64(0.1, 64)),
+            Err(Error::Inexact)
+        );
+        assert_eq!(
+            f32::try_from(&BigFloat::from_f64(1e39, 64)),
+            Err(Error::ExponentOverflow(Sign::Pos))
+        );
+        assert_eq!(
+            f64::try_from(&two_pow(1024).neg()),
+            Err(Error::ExponentOverflow(Sign::Neg))
+        );
+        assert_eq!(f64::try_from(&two_pow(-1075)), Err(Error::Inexact));
+        assert!(f64::try_from(&NAN).unwrap().is_nan());
+        assert_eq!(f64::try_from(&INF_NEG), Ok(f64::NEG_INFINITY));
+        assert_eq!(f32::try_from(&INF_POS), Ok(f32::INFINITY));
+
+        // rounding conversion to primitives
+        assert_eq!(third.to_f64_round(RoundingMode::ToEven), 1.0 / 3.0);
+        assert_eq!(third.to_f32_round(RoundingMode::ToEven), 1.0f32 / 3.0);
+        let up = third.to_f64_round(RoundingMode::Up);
+        let down = third.to_f64_round(RoundingMode::Down);
+        assert_eq!(up.to_bits(), down.to_bits() + 1);
+        assert_eq!(third.neg().to_f64_round(RoundingMode::Up), -down);
+        assert_eq!(third.to_f64_round(RoundingMode::None), down);
+
+        assert_eq!(
+            two_pow(1024).to_f64_round(RoundingMode::ToEven),
+            f64::INFINITY
+        );
+        assert_eq!(two_pow(1024).to_f64_round(RoundingMode::ToZero), f64::MAX);
+        assert_eq!(two_pow(1024).neg().to_f64_round(RoundingMode::Up), f64::MIN);
+        assert_eq!(two_pow(128).to_f32_round(RoundingMode::Down), f32::MAX);
+        assert_eq!(two_pow(128).to_f32_round(RoundingMode::Up), f32::INFINITY);
+
+        assert_eq!(two_pow(-1075).to_f64_round(RoundingMode::ToEven), 0.0);
+        assert_eq!(two_pow(-1075).to_f64_round(RoundingMode::ToOdd), 5e-324);
+        assert_eq!(
+            two_pow(-1075).to_f64_round(RoundingMode::ToNearestAway),
+            5e-324
+        );
+
+        // ties
+        let tie = ONE.add(&two_pow(-53), 128, RoundingMode::None);
+        assert_eq!(tie.to_f64_round(RoundingMode::ToEven), 1.0);
+        assert_eq!(
+            tie.to_f64_round(RoundingMode::ToNearestAway),
+            1.0 + f64::EPSILON
+        );
+        assert_eq!(
+            tie.neg().to_f64_round(RoundingMode::ToNearestAway),
+            -1.0 - f64::EPSILON
+        );
+        assert_eq!(two_pow(-1080).to_f64_round(RoundingMode::Up), 5e-324);
+        assert_eq!(
+            two_pow(-1080).neg().to_f64_round(RoundingMode::Down),
+            -5e-324
+        );
+        assert_eq!(
+            two_pow(-1080)
+                .neg()
+                .to_f64_round(RoundingMode::Up)
+                .to_bits(),
+            (-0.0f64).to_bits()
+        );
+        let x = two_pow(-1075).add(&two_pow(-1200), 256, RoundingMode::None);
+        assert_eq!(x.to_f64_round(RoundingMode::ToEven), 5e-324);
+        let x = two_pow(-1022).sub(&two_pow(-1200), 256, RoundingMode::None);
+        assert_eq!(x.to_f64_round(RoundingMode::ToEven), f64::MIN_POSITIVE);
+        assert_eq!(
+            x.to_f64_round(RoundingMode::Down),
+            f64::MIN_POSITIVE - 5e-324
+        );
+        assert!(NAN.to_f64_round(RoundingMode::ToEven).is_nan());
+        assert_eq!(INF_POS.to_f32_round(RoundingMode::ToZero), f32::INFINITY);
+
+        for _ in 0..1000 {
+            let f = f64::from_bits(rand::random::<u64>());
+            if f.is_finite() {
+                let x = BigFloat::from_f64(f, 64);
+                assert_eq!(f64::try_from(&x).unwrap().to_bits(), f.to_bits());
+                assert_eq!(x.to_f32_round(RoundingMode::ToEven), f as f32);
+            }
+        }
+
+        // exact conversion to integers
+        assert_eq!(
+            i64::try_from(&BigFloat::from_i64(i64::MIN, 64)),
+            Ok(i64::MIN)
+        );
+        assert_eq!(
+            i64::try_from(&BigFloat::from_i64(i64::MAX, 64)),
+            Ok(i64::MAX)
+        );
+        assert_eq!(i64::try_from(&BigFloat::from_i64(-12345, 64)), Ok(-12345));
+        assert_eq!(
+            u64::try_from(&BigFloat::from_u64(u64::MAX, 64)),
+            Ok(u64::MAX)
+        );
+        assert_eq!(u64::try_from(&BigFloat::new(64)), Ok(0));
+        assert_eq!(
+            i64::try_from(&two_pow(63)),
+            Err(Error::ExponentOverflow(Sign::Pos))
+        );
+        assert_eq!(
+            i64::try_from(&two_pow(63).neg().sub(&ONE, 128, rm)),
+            Err(Error::ExponentOverflow(Sign::Neg))
+        );
+        assert_eq!(
+            u64::try_from(&two_pow(64)),
+            Err(Error::ExponentOverflow(Sign::Pos))
+        );
+        assert_eq!(u64::try_from(&ONE.neg()), Err(Error::InvalidArgument));
+        assert_eq!(
+            i64::try_from(&BigFloat::from_f64(1.5, 64)),
+            Err(Error::Inexact)
+        );
+        assert_eq!(u64::try_from(&third), Err(Error::Inexact));
+        assert_eq!(u64::try_from(&NAN), Err(Error::InvalidArgument));
+        assert_eq!(
+            i64::try_from(&INF_POS),
+            Err(Error::ExponentOverflow(Sign::Pos))
+        );
+
+        // checked operations
+        let mut cc = Consts::new().unwrap();
+        let p = 128;
+        let two = BigFloat::from_word(2, 64);
+        let max = BigFloat::max_value(p);
+        let zero = BigFloat::new(p);
+
+        assert_eq!(two.try_add(&ONE, p, rm), Ok(BigFloat::from_word(3, p)));
+        assert_eq!(two.try_sub(&ONE, p, rm), Ok(ONE.clone()));
+        assert_eq!(two.try_mul(&two, p, rm), Ok(BigFloat::from_word(4, p)));
+        assert_eq!(ONE.try_div(&two, p, rm), Ok(BigFloat::from_f64(0.5, p)));
+        assert_eq!(BigFloat::from_word(7, p).try_rem(&two), Ok(ONE.clone()));
+        assert_eq!(BigFloat::from_word(4, p).try_sqrt(p, rm), Ok(two.clone()));
+        assert_eq!(BigFloat::from_word(8, p).try_cbrt(p, rm), Ok(two.clone()));
+        assert_eq!(two.try_reciprocal(p, rm), Ok(BigFloat::from_f64(0.5, p)));
+        assert_eq!(ONE.try_ln(p, rm, &mut cc), Ok(zero.clone()));
+        assert_eq!(zero.try_exp(p, rm, &mut cc), Ok(ONE.clone()));
+        assert_eq!(
+            two.try_pow(&two, p, rm, &mut cc),
+            Ok(BigFloat::from_word(4, p))
+        );
+
+        assert_eq!(
+            max.try_add(&max, p, rm),
+            Err(Error::ExponentOverflow(Sign::Pos))
+        );
+        assert_eq!(
+            max.neg().try_mul(&max, p, rm),
+            Err(Error::ExponentOverflow(Sign::Neg))
+        );
+        assert_eq!(ONE.try_div(&zero, p, rm), Err(Error::DivisionByZero));
+        assert_eq!(ONE.try_rem(&zero), Err(Error::InvalidArgument));
+        assert_eq!(zero.try_reciprocal(p, rm), Err(Error::DivisionByZero));
+        assert_eq!(ONE.neg().try_sqrt(p, rm), Err(Error::InvalidArgument));
+        assert_eq!(
+            two.neg().try_ln(p, rm, &mut cc),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            two.neg()
+                .try_pow(&BigFloat::from_f64(0.5, p), p, rm, &mut cc),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            ONE.try_add(&ONE, usize::MAX, rm),
+            Err(Error::InvalidArgument)
         );
+
+        // infinite and NaN arguments
+        assert_eq!(INF_POS.try_add(&ONE, p, rm), Ok(INF_POS));
+        assert_eq!(ONE.try_div(&INF_NEG, p, rm).map(|v| v.is_zero()), Ok(true));
+        assert_eq!(INF_POS.try_exp(p, rm, &mut cc), Ok(INF_POS));
+        assert_eq!(
+            INF_POS.try_sub(&INF_POS, p, rm),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(INF_POS.try_mul(&zero, p, rm), Err(Error::InvalidArgument));
+        assert_eq!(NAN.try_add(&ONE, p, rm), Err(Error::InvalidArgument));
+        assert_eq!(
+            ONE.try_mul(&BigFloat::nan(Some(Error::MemoryAllocation)), p, rm),
+            Err(Error::MemoryAllocation)
+        );
+        assert_eq!(NAN.try_sqrt(p, rm), Err(Error::InvalidArgument));
+
+        // powers of the same base
+        for base in [
+            BigFloat::from_f64(1.5, p),
+            BigFloat::from_f64(0.001, p),
+            BigFloat::from_i8(-3, p),
+            zero.clone(),
+            ONE.clone(),
+            INF_POS,
+            NAN,
+        ] {
+            let mut pb = base.prepare_pow(p);
+            assert_eq!(pb.precision(), p);
+            for n in [
+                BigFloat::from_f64(0.3, p),
+                BigFloat::from_f64(7.5, p),
+                BigFloat::from_f64(-12.25, p),
+                BigFloat::from_i32(100000, p),
+                zero.clone(),
+                INF_NEG,
+            ] {
+                for rm in [RoundingMode::ToEven, RoundingMode::Up, RoundingMode::Down] {
+                    let r1 = pb.pow(&n, rm, &mut cc);
+                    let r2 = base.pow(&n, p, rm, &mut cc);
+                    assert!(r1 == r2 || (r1.is_nan() && r2.is_nan()));
+                }
+            }
+        }
+
+        // the cached logarithm is refined when higher precision is needed
+        let base = BigFloat::from_word(3, p);
+        let mut pb = base.prepare_pow(p * 4);
+        let n = BigFloat::from_f64(0.5, p);
+        assert_eq!(pb.pow(&n, rm, &mut cc), base.sqrt(p * 4, rm));
+
+        // precision in digits
+        assert_eq!(Radix::Bin.digits_to_bits(13), 13);
+        assert_eq!(Radix::Oct.digits_to_bits(13), 39);
+        assert_eq!(Radix::Hex.digits_to_bits(13), 52);
+        assert_eq!(Radix::Dec.digits_to_bits(0), 0);
+        assert_eq!(Radix::Dec.digits_to_bits(1), 4);
+        assert_eq!(Radix::Dec.digits_to_bits(16), 54);
+        assert_eq!(Radix::Dec.digits_to_bits(1000), 3322);
+        assert_eq!(Radix::Hex.digits_to_bits(usize::MAX), usize::MAX);
+
+        let n = BigFloat::new_digits(40, Radix::Dec);
+        assert!(n.is_zero());
+        assert_eq!(n.mantissa_max_bit_len(), Some(round_p(133)));
+        assert!(BigFloat::new_digits(usize::MAX, Radix::Dec).is_nan());
+
+        let mut n = BigFloat::parse_digits("1.5", Radix::Dec, 20, rm);
+        assert_eq!(n.precision(), Some(round_p(67)));
+        n.set_precision_digits(100, Radix::Dec, rm).unwrap();
+        assert_eq!(n.precision(), Some(round_p(333)));
+        n.set_precision_digits(3, Radix::Hex, RoundingMode::Down)
+            .unwrap();
+        assert_eq!(n, BigFloat::from_f64(1.5, 64));
+        assert!(n.set_precision_digits(usize::MAX, Radix::Dec, rm).is_err());
     }
 
     #[test]
@@ -2235,7 +5911,7 @@ mod tests {
         );
 
         let d1str = format!("{}", d1);
-        assert_eq!(&d1str, "1.234567890123456789012345678901234567888e-2");
+        assert_eq!(&d1str, "1.234567890123456789012345678901234567889e-2");
         assert!(BigFloat::from_str(&d1str).unwrap() == d1);
 
         let d1 = BigFloat::parse(
@@ -2245,7 +5921,7 @@ mod tests {
             RoundingMode::None,
         );
         let d1str = format!("{}", d1);
-        assert_eq!(&d1str, "-1.23456789012345678901234567890123456788917e+2");
+        assert_eq!(&d1str, "-1.23456789012345678901234567890123456789e+2");
         assert_eq!(BigFloat::from_str(&d1str).unwrap(), d1);
 
         let d1str = format!("{}", INF_POS);
@@ -2257,6 +5933,36 @@ mod tests {
         let d1str = format!("{}", NAN);
         assert_eq!(d1str, "NaN");
 
+        // abbreviated
+        let d1str = format!("{}", d1.abbrev(5));
+        assert_eq!(&d1str, "-1.2345…56789e+2 (39 digits)");
+        assert_eq!(format!("{:?}", d1.abbrev(5)), d1str);
+        assert_eq!(format!("{}", d1.abbrev(20)), format!("{}", d1));
+        assert_eq!(format!("{}", d1.abbrev(0)), format!("{}", d1));
+        assert_eq!(format!("{}", INF_NEG.abbrev(5)), "-Inf");
+        assert_eq!(format!("{}", NAN.abbrev(5)), "NaN");
+        assert_eq!(
+            NAN.format_abbrev_into(Radix::Dec, RoundingMode::None, 0, &mut Vec::new()),
+            Err(Error::InvalidArgument)
+        );
+
+        // format spec
+        assert_eq!(format!("{:.5}", d1), "-1.23457e+2");
+        assert_eq!(format!("{:>14.3}", d1), "     -1.235e+2");
+        assert_eq!(format!("{:*<14.3}", d1), "-1.235e+2*****");
+        assert_eq!(format!("{:^13.0}", d1), "    -1e+2    ");
+        assert_eq!(format!("{:+.3}", d1.neg()), "+1.235e+2");
+        assert_eq!(format!("{:012.3}", d1), "-0001.235e+2");
+        assert_eq!(format!("{:.4}", BigFloat::from_word(2, 64)), "2.0000e+0");
+        assert_eq!(format!("{:.2}", BigFloat::new(64)), "0.00");
+        assert_eq!(format!("{:.0}", BigFloat::new(64)), "0");
+        assert_eq!(format!("{:+6}", INF_POS), "  +Inf");
+        assert_eq!(format!("{:<6}", INF_NEG), "-Inf  ");
+        assert_eq!(format!("{:+>6.1}", NAN), "+++NaN");
+        assert_eq!(format!("{:.3X}", BigFloat::from_word(255, 64)), "F.F00_e+1");
+        assert_eq!(format!("{:.3b}", BigFloat::from_word(5, 64)), "1.010e+10");
+        assert_eq!(format!("{}", d1), format!("{:1}", d1));
+
         assert!(BigFloat::from_str("abc").is_ok());
         assert!(BigFloat::from_str("abc").unwrap().is_nan());
 
@@ -2364,6 +6070,299 @@ mod tests {
         assert!(INF_NEG == INF_NEG);
         assert!(INF_POS == INF_POS);
     }
+
+    #[test]
+    fn test_ilogb() {
+        assert_eq!(ONE.ilogb(), Some(0));
+        assert_eq!(ONE.neg().ilogb(), Some(0));
+        assert_eq!(BigFloat::from_f64(0.5, 64).ilogb(), Some(-1));
+        assert_eq!(BigFloat::from_f64(-1000.0, 64).ilogb(), Some(9));
+        assert_eq!(INF_POS.ilogb(), None);
+        assert_eq!(NAN.ilogb(), None);
+
+        let min = BigFloat::min_positive(128);
+        assert_eq!(min.ilogb(), Some(EXPONENT_MIN as isize - 128));
+        assert_eq!(
+            min.logb(),
+            BigFloat::from_i64(EXPONENT_MIN as i64 - 128, 64)
+        );
+        assert!(INF_NEG.logb().is_inf_pos());
+        assert!(NAN.logb().is_nan());
+        assert!(BigFloat::new(128).neg().logb().is_inf_neg());
+
+        for _ in 0..1000 {
+            let p = rand_p();
+            let n = |w: Word, e: Exponent| {
+                let mut x = BigFloat::from_word(w | 1, p);
+                x.set_exponent(e);
+                x
+            };
+            let d1 = n(rand::random(), rand::random::<i8>() as Exponent);
+            let d2 = n(rand::random(), rand::random::<i8>() as Exponent);
+            let test_rm = [RoundingMode::Up, RoundingMode::Down, RoundingMode::ToEven];
+            let p2 = WORD_BIT_SIZE * (rand::random::<usize>() % 3 + 1);
+
+            let (e_min, e_max) = d1.mul_exponent_estimate(&d2).unwrap();
+            for rm in test_rm {
+                let e = d1.mul(&d2, p2, rm).exponent().unwrap() as isize;
+                assert!(e_min <= e && e <= e_max);
+            }
+
+            let (e_min, e_max) = d1.div_exponent_estimate(&d2).unwrap();
+            for rm in test_rm {
+                let e = d1.div(&d2, p2, rm).exponent().unwrap() as isize;
+                assert!(e_min <= e && e <= e_max);
+            }
+        }
+
+        let d = ONE.sub(
+            &BigFloat::from_f64(2.0f64.powi(-100), 64),
+            128,
+            RoundingMode::None,
+        );
+        assert_eq!(
+            d.mul(&d, 64, RoundingMode::Up).exponent().unwrap() as isize,
+            d.mul_exponent_estimate(&d).unwrap().1
+        );
+        assert_eq!(d.mul_exponent_estimate(&BigFloat::new(64)), None);
+        assert_eq!(d.div_exponent_estimate(&INF_POS), None);
+        assert_eq!(NAN.div_exponent_estimate(&d), None);
+    }
+
+    #[test]
+    fn test_mul_add_mul() {
+        let rm = RoundingMode::ToEven;
+
+        for _ in 0..100 {
+            let p = rand_p();
+            let v: Vec<BigFloat> = (0..4)
+                .map(|_| {
+                    let n = BigFloat::from_i64(rand::random::<i64>(), p);
+                    let d = BigFloat::from_word(rand::random::<Word>() | 1, p);
+                    n.div(&d, p, rm)
+                })
+                .collect();
+
+            // reference with enough precision to represent the sum exactly
+            let ab = v[0].mul_full_prec(&v[1]);
+            let cd = v[2].mul_full_prec(&v[3]);
+            let r0 = ab.add(&cd, 4 * p + 128, RoundingMode::None);
+            let r0 = r0.add(&BigFloat::new(p), p, rm);
+
+            let r = v[0].mul_add_mul(&v[1], &v[2], &v[3], p, rm);
+            assert_eq!(r, r0);
+        }
+
+        // cancellation
+        let p = 128;
+        let x = BigFloat::from_word(3, p).div(&BigFloat::from_word(7, p), p, rm);
+        let y = x.add(&BigFloat::from_f64(2.0f64.powi(-120), p), p, rm);
+        let r = x.mul_add_mul(&y, &y.neg(), &x, p, rm);
+        assert!(r.is_zero());
+
+        let r = x.mul_add_mul(&x, &y.neg(), &y, p, rm);
+        let r0 = x.add_full_prec(&y).mul(&x.sub_full_prec(&y), p, rm);
+        assert_eq!(r, r0);
+        assert!(r.is_negative());
+
+        // special values
+        let one = BigFloat::from_word(1, p);
+        assert!(INF_POS.mul_add_mul(&one, &one, &one, p, rm).is_inf_pos());
+        assert!(one.mul_add_mul(&one, &INF_NEG, &one, p, rm).is_inf_neg());
+        assert!(INF_POS.mul_add_mul(&one, &INF_NEG, &one, p, rm).is_nan());
+        assert!(INF_POS
+            .mul_add_mul(&BigFloat::new(p), &one, &one, p, rm)
+            .is_nan());
+        assert!(NAN.mul_add_mul(&one, &one, &one, p, rm).is_nan());
+        assert!(one.mul_add_mul(&one, &one, &one, usize::MAX, rm).is_nan());
+    }
+
+    #[test]
+    fn test_inexact_propagation() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+        let p = 128;
+
+        let third = ONE.div(&BigFloat::from_word(3, p), p, rm);
+        assert!(third.is_inexact());
+        assert!(!ONE.is_inexact());
+        assert!(!NAN.is_inexact());
+
+        // inexact zero
+        let z = third.sub(&third, p, rm);
+        assert!(z.is_zero() && z.is_inexact());
+
+        assert!(z.add(&TWO, p, rm).is_inexact());
+        assert!(TWO.sub(&z, p, rm).is_inexact());
+        assert!(TWO.add_full_prec(&z).is_inexact());
+        assert!(z.mul(&TWO, p, rm).is_inexact());
+        assert!(TWO.mul(&z, p, rm).is_inexact());
+        assert!(z.div(&TWO, p, rm).is_inexact());
+        assert!(z.add_word(3, p, rm).is_inexact());
+        assert!(z.mul_word(3, p, rm).is_inexact());
+        assert!(z.div_word(3, p, rm).is_inexact());
+        assert!(z.sqrt(p, rm).is_inexact());
+        assert!(z.cbrt(p, rm).is_inexact());
+        assert!(TWO.rem(&third).is_inexact());
+
+        // rounding to an integer
+        let x = BigFloat::from_f64(0.75, p);
+        let mut y = x.clone();
+        y.set_inexact(true);
+        assert!(!x.ceil().is_inexact());
+        assert!(y.ceil().is_inexact());
+        assert!(y.neg().floor().is_inexact());
+        assert!(y.signum().is_inexact());
+        assert!(y.clamp(&ONE, &TWO).is_inexact());
+
+        // exact results of transcendental functions
+        let hundred = BigFloat::from_word(100, p);
+        assert!(!hundred.log10(p, rm, &mut cc).is_inexact());
+        let mut one = ONE.clone();
+        assert!(!one.log10(p, rm, &mut cc).is_inexact());
+        one.set_inexact(true);
+        assert!(one.log10(p, rm, &mut cc).is_inexact());
+        assert!(one.ln(p, rm, &mut cc).is_inexact());
+    }
+
+    #[test]
+    fn test_cmp_prim() {
+        let rm = RoundingMode::ToEven;
+        let x = BigFloat::from_f64(1.5, 128);
+
+        assert!(x > 1);
+        assert!(x < 2);
+        assert!(1 < x && 2 > x);
+        assert!(x == 1.5 && 1.5 == x);
+        assert!(x != 1);
+        assert!(x >= 1.5f32 && x <= 1.5f64);
+        assert!(x > -1i8 && x > 0u8 && x < 2u128 && x > -2i128);
+        assert_eq!(x.partial_cmp(&1usize), Some(Ordering::Greater));
+        assert_eq!(2isize.partial_cmp(&x), Some(Ordering::Greater));
+
+        // the primitive is not rounded to the precision of the BigFloat
+        let m = BigFloat::from_u64(u64::MAX, 64);
+        let m1 = m.sub(&BigFloat::from_u8(1, 64), 64, rm);
+        assert!(m1 < u64::MAX && m1 == u64::MAX - 1);
+        assert!(BigFloat::from_u8(0, 64) > i64::MIN);
+        let t = BigFloat::from_f64(0.1, 64);
+        assert!(t == 0.1 && t < 0.1f32);
+        let t =
+            BigFloat::from_f64(0.1, 256).add(&BigFloat::from_f64(2.0f64.powi(-100), 64), 256, rm);
+        assert!(t > 0.1 && t != 0.1);
+
+        // special values
+        assert!(INF_POS > u128::MAX && INF_NEG < i128::MIN);
+        assert!(INF_POS == f64::INFINITY && f64::NEG_INFINITY == INF_NEG);
+        assert!(x < f64::INFINITY && x > f64::NEG_INFINITY);
+        assert_eq!(NAN.partial_cmp(&1), None);
+        assert_eq!(x.partial_cmp(&f64::NAN), None);
+        assert!(NAN != 0);
+        assert!(!x.is_nan());
+        assert!(BigFloat::from_f64(-0.0, 64) == 0);
+    }
+
+    #[test]
+    fn test_arith_prim() {
+        let rm = RoundingMode::ToEven;
+        let p = 256;
+        let x = BigFloat::from_f64(1.5, p);
+
+        assert!(&x + 2 == 3.5);
+        assert!(&x - 2u8 == -0.5);
+        assert!(&x * -2i64 == -3);
+        assert!(&x / 3 == 0.5);
+        assert!(2 + &x == 3.5);
+        assert!(2 - &x == 0.5);
+        assert!(3.0 * &x == 4.5);
+        assert!(3 / &x == 2);
+        assert!(&(&x * 2) + 1 == 4);
+        assert!(1 - &(&x / 3) == 0.5);
+
+        // the result has the precision of the BigFloat operand
+        let y = BigFloat::from_word(1, p).div(&BigFloat::from_word(3, p), p, rm);
+        let z = &y * 7u128;
+        assert_eq!(z.mantissa_max_bit_len(), Some(p));
+        assert_eq!(z, y.mul(&BigFloat::from_word(7, 64), p, rm));
+        let z = 0.1 / &y;
+        assert_eq!(z, BigFloat::from_f64(0.1, 64).div(&y, p, rm));
+
+        // the primitive is not rounded
+        let s = &BigFloat::from_word(1, 64) + u64::MAX;
+        assert_eq!(s.mantissa_max_bit_len(), Some(64));
+        assert_eq!(
+            s,
+            BigFloat::from_word(1, 64).add(&BigFloat::from_u64(u64::MAX, 64), 64, rm)
+        );
+        let d = &BigFloat::from_word(0, p) + i128::MIN;
+        assert!(d == i128::MIN);
+
+        // special values
+        assert!((&INF_POS + 1u8).is_inf_pos());
+        assert!((1u8 - &INF_POS).is_inf_neg());
+        assert!((&NAN * 2i8).is_nan());
+        assert!((&x + f64::NAN).is_nan());
+        assert!((&x / 0u8).is_inf_pos());
+        assert!((&x - f64::INFINITY).is_inf_neg());
+    }
+
+    #[test]
+    fn test_rem() {
+        // truncated and Euclidean modulo agree with f64 when the f64 results are exact
+        for (a, b) in [
+            (7.5, 2.0),
+            (-7.5, 2.0),
+            (7.5, -2.0),
+            (-7.5, -2.0),
+            (1.0, 3.0),
+            (-1.0, 3.0),
+            (-6.0, 3.0),
+            (0.0, -3.0),
+            (1e300, 3.0),
+        ] {
+            let x = BigFloat::from_f64(a, 64);
+            let y = BigFloat::from_f64(b, 64);
+
+            let r = &x % &y;
+            assert_eq!(r, BigFloat::from_f64(a % b, 64));
+            assert_eq!(r.is_negative(), (a % b).is_sign_negative());
+            assert_eq!(x.clone() % y.clone(), r);
+
+            let r = x.rem_euclid(&y);
+            assert_eq!(r, BigFloat::from_f64(a.rem_euclid(b), 64));
+            assert_eq!(r.is_negative(), a.rem_euclid(b).is_sign_negative());
+        }
+
+        // the result is exact
+        let x = BigFloat::from_f64(-1e-300, 64);
+        let y = BigFloat::from_f64(1e300, 64);
+        let r = x.rem_euclid(&y);
+        assert!(!r.inexact());
+        assert_eq!(r.sub(&y, 4096, RoundingMode::None), x);
+
+        // the sign of zero is kept
+        let zero = BigFloat::new(64);
+        assert!((&zero.neg() % &*TWO).is_negative());
+        assert!(zero.neg().rem_euclid(&TWO).is_negative());
+        assert!(TWO.neg().rem_euclid(&TWO).is_negative());
+
+        // zero divisor
+        assert!((&*TWO % &zero).is_nan());
+        assert!((&zero % &zero).is_nan());
+        assert!(TWO.neg().rem_euclid(&zero).is_nan());
+
+        // Inf and NaN
+        assert_eq!(&*TWO % &INF_NEG, *TWO);
+        assert_eq!(TWO.neg() % INF_POS, TWO.neg());
+        assert!((&INF_POS % &TWO).is_nan());
+        assert!((&NAN % &TWO).is_nan());
+        assert!((&*TWO % &NAN).is_nan());
+        assert_eq!(TWO.rem_euclid(&INF_NEG), *TWO);
+        assert!(TWO.neg().rem_euclid(&INF_POS).is_inf_pos());
+        assert!(INF_NEG.rem_euclid(&TWO).is_nan());
+        assert!(NAN.rem_euclid(&TWO).is_nan());
+        assert!(TWO.rem_euclid(&NAN).is_nan());
+    }
 }
 
 #[cfg(feature = "random")]
@@ -2378,11 +6377,21 @@ mod rand_tests {
         for _ in 0..1000 {
             let p = rand::random::<usize>() % 1000 + DEFAULT_P;
             let exp_from;
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(any(
+                target_arch = "x86",
+                target_arch = "wasm32",
+                feature = "portable",
+                feature = "exp64"
+            )))]
             {
                 exp_from = rand::random::<Exponent>().abs();
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(any(
+                target_arch = "x86",
+                target_arch = "wasm32",
+                feature = "portable",
+                feature = "exp64"
+            ))]
             {
                 use crate::defs::EXPONENT_MIN;
                 exp_from =
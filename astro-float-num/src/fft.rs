@@ -0,0 +1,478 @@
+//! Discrete Fourier transform of sequences of complex numbers.
+//!
+//! A sequence is given as two slices of the same length holding the real and the imaginary parts of its elements.
+//! The transform is computed with the extended precision which grows with the length of the sequence, and each part of the result
+//! is then rounded to the requested precision. Together with the result, an upper bound of its absolute error is returned,
+//! which can be used to determine how many digits of the components with small absolute values are correct.
+//!
+//! Sequences whose length is a power of 2 are transformed with the radix-2 fast Fourier transform in O(n log n) operations.
+//! Sequences of other lengths are transformed directly in O(n^2) operations.
+//!
+//! This transform is unrelated to the one used internally for multiplication of large mantissas.
+
+use crate::common::util::log2_ceil;
+use crate::common::util::round_p;
+use crate::BigFloat;
+use crate::Consts;
+use crate::Error;
+use crate::RoundingMode;
+use crate::ONE_HALF;
+use crate::WORD_BIT_SIZE;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Result of a discrete Fourier transform.
+#[derive(Debug, Clone)]
+pub struct Transform {
+    /// Real parts of the elements of the result.
+    pub re: Vec<BigFloat>,
+
+    /// Imaginary parts of the elements of the result.
+    pub im: Vec<BigFloat>,
+
+    /// Upper bound of the absolute error of the real and the imaginary part of each element of the result
+    /// before the parts are rounded to the requested precision.
+    pub err: BigFloat,
+}
+
+/// Computes the forward discrete Fourier transform `X(k) = sum(x(j) * exp(-2*pi*i*j*k/n))`
+/// of the sequence `x` with real parts `re` and imaginary parts `im`.
+/// The parts of the result have precision `p` and are rounded using the rounding mode `rm`.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+///
+/// ## Errors
+///
+///  - InvalidArgument: the slices are empty or have different lengths, any of the values is NaN or infinite, or the precision is incorrect.
+///  - MemoryAllocation: failed to allocate memory.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::BigFloat;
+/// # use astro_float_num::Consts;
+/// # use astro_float_num::RoundingMode;
+/// # use astro_float_num::fft::fft;
+/// let mut cc = Consts::new().expect("Constants cache initialized");
+/// let rm = RoundingMode::ToEven;
+///
+/// let re = [1, 2, 3, 4].map(|v| BigFloat::from_word(v, 64));
+/// let im = [0, 0, 0, 0].map(|v| BigFloat::from_word(v, 64));
+///
+/// let t = fft(&re, &im, 128, rm, &mut cc).expect("Transform computed");
+///
+/// assert_eq!(t.re[0], BigFloat::from_word(10, 64));
+/// assert_eq!(t.re[2], BigFloat::from_i8(-2, 64));
+/// assert_eq!(t.im[1], BigFloat::from_word(2, 64));
+/// ```
+pub fn fft(
+    re: &[BigFloat],
+    im: &[BigFloat],
+    p: usize,
+    rm: RoundingMode,
+    cc: &mut Consts,
+) -> Result<Transform, Error> {
+    transform(re, im, p, rm, cc, false)
+}
+
+/// Computes the inverse discrete Fourier transform `x(j) = sum(X(k) * exp(2*pi*i*j*k/n)) / n`
+/// of the sequence `X` with real parts `re` and imaginary parts `im`.
+/// The parts of the result have precision `p` and are rounded using the rounding mode `rm`.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+///
+/// ## Errors
+///
+///  - InvalidArgument: the slices are empty or have different lengths, any of the values is NaN or infinite, or the precision is incorrect.
+///  - MemoryAllocation: failed to allocate memory.
+pub fn ifft(
+    re: &[BigFloat],
+    im: &[BigFloat],
+    p: usize,
+    rm: RoundingMode,
+    cc: &mut Consts,
+) -> Result<Transform, Error> {
+    transform(re, im, p, rm, cc, true)
+}
+
+fn transform(
+    re: &[BigFloat],
+    im: &[BigFloat],
+    p: usize,
+    rm: RoundingMode,
+    cc: &mut Consts,
+    inverse: bool,
+) -> Result<Transform, Error> {
+    let n = re.len();
+
+    if n == 0 || n != im.len() {
+        return Err(Error::InvalidArgument);
+    }
+
+    for x in re.iter().chain(im.iter()) {
+        if x.is_nan() {
+            return Err(x.err().unwrap_or(Error::InvalidArgument));
+        }
+
+        if x.is_inf() {
+            return Err(Error::InvalidArgument);
+        }
+    }
+
+    let p_wrk = working_precision(p, n);
+
+    let (mut xr, mut xi) = if n.is_power_of_two() {
+        let (wr, wi) = twiddles(n, n / 2, p_wrk, cc, inverse);
+        let mut xr: Vec<BigFloat> = Vec::new();
+        let mut xi: Vec<BigFloat> = Vec::new();
+        xr.try_reserve_exact(n)?;
+        xi.try_reserve_exact(n)?;
+        xr.extend_from_slice(re);
+        xi.extend_from_slice(im);
+        radix2(&mut xr, &mut xi, &wr, &wi, p_wrk);
+        (xr, xi)
+    } else {
+        let (wr, wi) = twiddles(n, n, p_wrk, cc, inverse);
+        direct(re, im, &wr, &wi, p_wrk)?
+    };
+
+    let mut err = error_bound(re, im, p_wrk);
+
+    if inverse {
+        let d = BigFloat::from_u64(n as u64, 64);
+
+        for x in xr.iter_mut().chain(xi.iter_mut()) {
+            *x = x.div(&d, p_wrk, RoundingMode::ToEven);
+        }
+
+        // |x(j)| does not exceed the norm of X divided by sqrt(n), and the division adds one rounding error.
+        err = err.div(&d, 64, RoundingMode::Up).add(
+            &norm(re, im).mul(&ulp(p_wrk), 64, RoundingMode::Up),
+            64,
+            RoundingMode::Up,
+        );
+    }
+
+    for x in xr.iter_mut().chain(xi.iter_mut()) {
+        x.set_precision(p, rm)?;
+    }
+
+    if let Some(e) = err.err() {
+        return Err(e);
+    }
+
+    Ok(Transform {
+        re: xr,
+        im: xi,
+        err,
+    })
+}
+
+// Precision of intermediate computations for a sequence of `n` values and the target precision `p`.
+// The error of the transform grows at most as n^(3/2) relative to the norm of the sequence.
+fn working_precision(p: usize, n: usize) -> usize {
+    round_p(p.saturating_add(2 * log2_ceil(n) + 2 * WORD_BIT_SIZE))
+}
+
+// Returns the real and the imaginary parts of exp(-2*pi*i*k/n) for k in 0..m, or exp(2*pi*i*k/n) if `inverse` is true.
+// Each angle is reduced to the first quadrant, so that the values at multiples of pi/2 are exact.
+fn twiddles(
+    n: usize,
+    m: usize,
+    p: usize,
+    cc: &mut Consts,
+    inverse: bool,
+) -> (Vec<BigFloat>, Vec<BigFloat>) {
+    let p_arg = p + WORD_BIT_SIZE;
+    let half_pi = cc.pi(p_arg, RoundingMode::ToEven).div(
+        &BigFloat::from_word(2, 64),
+        p_arg,
+        RoundingMode::ToEven,
+    );
+    let d = BigFloat::from_u64(n as u64, 64);
+
+    let mut wr = Vec::with_capacity(m);
+    let mut wi = Vec::with_capacity(m);
+
+    for k in 0..m {
+        // 2*pi*k/n = pi/2 * (q + r/n)
+        let q = (4 * k as u128 / n as u128) as usize;
+        let r = (4 * k as u128 % n as u128) as u64;
+
+        let (c, s) = if r == 0 {
+            (BigFloat::from_word(1, p), BigFloat::new(p))
+        } else {
+            let a = half_pi
+                .mul(&BigFloat::from_u64(r, 64), p_arg, RoundingMode::ToEven)
+                .div(&d, p_arg, RoundingMode::ToEven);
+            (
+                a.cos(p, RoundingMode::ToEven, cc),
+                a.sin(p, RoundingMode::ToEven, cc),
+            )
+        };
+
+        let (c, s) = match q & 3 {
+            0 => (c, s),
+            1 => (s.neg(), c),
+            2 => (c.neg(), s.neg()),
+            _ => (s, c.neg()),
+        };
+
+        wr.push(c);
+        wi.push(if inverse { s } else { s.neg() });
+    }
+
+    (wr, wi)
+}
+
+// Returns (ar + i*ai) * (br + i*bi).
+fn cmul(
+    ar: &BigFloat,
+    ai: &BigFloat,
+    br: &BigFloat,
+    bi: &BigFloat,
+    p: usize,
+) -> (BigFloat, BigFloat) {
+    let rm = RoundingMode::ToEven;
+    (
+        ar.mul(br, p, rm).sub(&ai.mul(bi, p, rm), p, rm),
+        ar.mul(bi, p, rm).add(&ai.mul(br, p, rm), p, rm),
+    )
+}
+
+// In-place radix-2 decimation-in-time transform. `wr` and `wi` hold n/2 twiddle factors.
+fn radix2(xr: &mut [BigFloat], xi: &mut [BigFloat], wr: &[BigFloat], wi: &[BigFloat], p: usize) {
+    let n = xr.len();
+    let rm = RoundingMode::ToEven;
+
+    // bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+
+        if i < j {
+            xr.swap(i, j);
+            xi.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let step = n / len;
+
+        for i in (0..n).step_by(len) {
+            for k in 0..half {
+                let (a, b) = (i + k, i + k + half);
+                let (tr, ti) = cmul(&xr[b], &xi[b], &wr[k * step], &wi[k * step], p);
+
+                xr[b] = xr[a].sub(&tr, p, rm);
+                xi[b] = xi[a].sub(&ti, p, rm);
+                xr[a] = xr[a].add(&tr, p, rm);
+                xi[a] = xi[a].add(&ti, p, rm);
+            }
+        }
+
+        len *= 2;
+    }
+}
+
+// Transform computed by the definition. `wr` and `wi` hold n twiddle factors.
+fn direct(
+    re: &[BigFloat],
+    im: &[BigFloat],
+    wr: &[BigFloat],
+    wi: &[BigFloat],
+    p: usize,
+) -> Result<(Vec<BigFloat>, Vec<BigFloat>), Error> {
+    let n = re.len();
+    let rm = RoundingMode::ToEven;
+
+    let mut xr: Vec<BigFloat> = Vec::new();
+    let mut xi: Vec<BigFloat> = Vec::new();
+    xr.try_reserve_exact(n)?;
+    xi.try_reserve_exact(n)?;
+
+    for k in 0..n {
+        let mut sr = BigFloat::new(p);
+        let mut si = BigFloat::new(p);
+
+        for j in 0..n {
+            let w = (j as u128 * k as u128 % n as u128) as usize;
+            let (tr, ti) = cmul(&re[j], &im[j], &wr[w], &wi[w], p);
+            sr = sr.add(&tr, p, rm);
+            si = si.add(&ti, p, rm);
+        }
+
+        xr.push(sr);
+        xi.push(si);
+    }
+
+    Ok((xr, xi))
+}
+
+// Returns an upper bound of the Euclidean norm of the sequence.
+fn norm(re: &[BigFloat], im: &[BigFloat]) -> BigFloat {
+    let rm = RoundingMode::Up;
+    let mut s = BigFloat::new(64);
+
+    for x in re.iter().chain(im.iter()) {
+        s = s.add(&x.mul(x, 64, rm), 64, rm);
+    }
+
+    s.sqrt(64, rm)
+}
+
+// Returns an upper bound of the absolute error of the unscaled transform computed with precision `p`.
+// Each stage of the radix-2 transform multiplies the Euclidean norm of the sequence by sqrt(2)
+// and introduces relative errors of at most 8 * 2^-p in the computed butterflies,
+// which gives the bound 8 * log2(n) * sqrt(n) * |x| * 2^-p.
+// Each element of the direct transform is a sum of n products computed with the relative error at most 4 * 2^-p,
+// which gives the bound 2 * (n + 4) * sqrt(n) * |x| * 2^-p.
+fn error_bound(re: &[BigFloat], im: &[BigFloat], p: usize) -> BigFloat {
+    let rm = RoundingMode::Up;
+    let n = re.len();
+
+    let c = if n.is_power_of_two() {
+        8 * n.trailing_zeros() as u64
+    } else {
+        2 * (n as u64 + 4)
+    };
+
+    BigFloat::from_u64(c, 64)
+        .mul(&BigFloat::from_u64(n as u64, 64).sqrt(64, rm), 64, rm)
+        .mul(&norm(re, im), 64, rm)
+        .mul(&ulp(p), 64, rm)
+}
+
+// Returns 2^-p.
+fn ulp(p: usize) -> BigFloat {
+    ONE_HALF.powi(p, 64, RoundingMode::Up)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Word;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    fn assert_close(x: &BigFloat, y: &BigFloat, err: &BigFloat) {
+        let d = x.sub(y, 1024, RoundingMode::None).abs();
+        assert!(d.cmp(err).unwrap() <= 0, "{} {} {}", x, y, err);
+    }
+
+    #[test]
+    fn test_fft() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+        let p = 192;
+
+        // impulse
+        for n in [1, 2, 5, 8, 12, 64] {
+            let mut re = vec![BigFloat::new(64); n];
+            let im = vec![BigFloat::new(64); n];
+            re[0] = BigFloat::from_word(1, 64);
+
+            let t = fft(&re, &im, p, rm, &mut cc).unwrap();
+            for k in 0..n {
+                assert_eq!(t.re[k], BigFloat::from_word(1, p));
+                assert!(t.im[k].is_zero());
+            }
+        }
+
+        // sine wave of frequency 3
+        let eps = ulp(p - 8);
+        for n in [16, 17, 32] {
+            let re: Vec<BigFloat> = (0..n)
+                .map(|j| {
+                    cc.pi(p + 64, rm)
+                        .mul(&BigFloat::from_word(6 * j, 64), p + 64, rm)
+                        .div(&BigFloat::from_word(n, 64), p + 64, rm)
+                        .sin(p + 64, rm, &mut cc)
+                })
+                .collect();
+            let im = vec![BigFloat::new(64); n as usize];
+
+            let t = fft(&re, &im, p, rm, &mut cc).unwrap();
+            let half = BigFloat::from_f64(n as f64 / 2.0, 64);
+
+            for k in 0..n as usize {
+                let expected = if k == 3 {
+                    half.neg()
+                } else if k == n as usize - 3 {
+                    half.clone()
+                } else {
+                    BigFloat::new(64)
+                };
+                assert_close(&t.re[k], &BigFloat::new(64), &eps);
+                assert_close(&t.im[k], &expected, &eps);
+            }
+        }
+
+        // fast and direct transforms agree, and the inverse restores the sequence
+        let n = 32;
+        let seq = |m: Word| -> Vec<BigFloat> {
+            (0..n as Word)
+                .map(|j| {
+                    BigFloat::from_word(j * m % 101, 64).div(&BigFloat::from_word(7, 64), p, rm)
+                })
+                .collect()
+        };
+        let re = seq(7919);
+        let im = seq(104729);
+
+        let t = fft(&re, &im, p, rm, &mut cc).unwrap();
+        assert!(t.err.cmp(&ulp(p)).unwrap() < 0);
+
+        let p_wrk = working_precision(p, n);
+        let (wr, wi) = twiddles(n, n, p_wrk, &mut cc, false);
+        let (dr, di) = direct(&re, &im, &wr, &wi, p_wrk).unwrap();
+        for k in 0..n {
+            assert_close(&t.re[k], &dr[k], &eps);
+            assert_close(&t.im[k], &di[k], &eps);
+        }
+
+        let u = ifft(&t.re, &t.im, p, rm, &mut cc).unwrap();
+        for k in 0..n {
+            assert_close(&u.re[k], &re[k], &eps);
+            assert_close(&u.im[k], &im[k], &eps);
+        }
+
+        // result with higher precision agrees within the error bound and one ulp
+        let t2 = fft(&re, &im, 2 * p, rm, &mut cc).unwrap();
+        for k in 0..n {
+            let d = t.err.add(
+                &t.re[k].abs().mul(&ulp(p - 1), 64, RoundingMode::Up),
+                64,
+                RoundingMode::Up,
+            );
+            assert_close(&t.re[k], &t2.re[k], &d);
+        }
+
+        // errors
+        let x = [BigFloat::from_word(1, 64)];
+        assert_eq!(
+            fft(&x, &[], p, rm, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            fft(&[], &[], p, rm, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            ifft(&x, &[crate::NAN], p, rm, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            fft(&x, &[crate::INF_POS], p, rm, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+    }
+}
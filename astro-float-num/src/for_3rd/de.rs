@@ -34,14 +34,14 @@ impl<'de> Visitor<'de> for BigFloatVisitor {
     }
 
     fn visit_f32<E: Error>(self, v: f32) -> Result<Self::Value, E> {
-        match BigFloatNumber::from_f32(64, v) {
+        match BigFloatNumber::from_f32_minimal(v) {
             Ok(o) => Ok(o.into()),
             Err(e) => Err(Error::custom(format!("{e:?}"))),
         }
     }
 
     fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
-        match BigFloatNumber::from_f64(64, v) {
+        match BigFloatNumber::from_f64_minimal(v) {
             Ok(o) => Ok(o.into()),
             Err(e) => Err(Error::custom(format!("{e:?}"))),
         }
@@ -81,11 +81,11 @@ mod tests {
         assert_eq!("0.0", format!("{}", from_str::<BigFloat>("-0").unwrap()));
         assert_eq!("0.0", format!("{}", from_str::<BigFloat>("0.0").unwrap()));
         assert_eq!(
-            "2.99999999999999988897e-1",
+            "2.99999999999999988898e-1",
             format!("{}", from_str::<BigFloat>("0.3").unwrap())
         );
         assert_eq!(
-            "2.99999999999999999983e-1",
+            "2.99999999999999999984e-1",
             format!("{}", from_str::<BigFloat>("\"0.3\"").unwrap())
         );
     }
@@ -0,0 +1,148 @@
+//! Conversion between BigFloat and rust_decimal::Decimal.
+
+use crate::common::util::int_to_u128;
+use crate::defs::DEFAULT_P;
+use crate::defs::DEFAULT_RM;
+use crate::BigFloat;
+use crate::RoundingMode;
+use rust_decimal::Decimal;
+
+// Precision which is sufficient to represent the mantissa of a decimal number
+// and any power of 10 used as a scale exactly.
+const DECIMAL_P: usize = 128;
+
+// Number of bits in the mantissa of a decimal number.
+const DECIMAL_MANTISSA_BITS: usize = 96;
+
+impl BigFloat {
+    /// Constructs a number with precision `p` from the decimal number `d` rounded using rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Radix;
+    /// # use astro_float_num::RoundingMode;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let d = Decimal::new(-12345, 2);
+    /// let n = BigFloat::from_decimal(&d, 128, RoundingMode::ToEven);
+    ///
+    /// assert_eq!(n, BigFloat::parse("-123.45", Radix::Dec, 128, RoundingMode::ToEven));
+    /// assert_eq!(n.to_decimal(2, RoundingMode::ToEven), Some(d));
+    /// ```
+    pub fn from_decimal(d: &Decimal, p: usize, rm: RoundingMode) -> Self {
+        let m = BigFloat::from_i128(d.mantissa(), DECIMAL_P);
+        let q = BigFloat::from_u128(10u128.pow(d.scale()), DECIMAL_P);
+
+        m.div(&q, p, rm)
+    }
+
+    /// Converts the number to a decimal number with scale `scale`, i.e. with `scale` digits after the decimal point,
+    /// rounding it using rounding mode `rm`.
+    /// The function returns None if `self` is NaN or Inf, if the result does not fit in the decimal number,
+    /// or if `scale` is larger than `Decimal::MAX_SCALE`.
+    pub fn to_decimal(&self, scale: u32, rm: RoundingMode) -> Option<Decimal> {
+        if scale > Decimal::MAX_SCALE {
+            return None;
+        }
+
+        let p = self.precision()?;
+        let q = BigFloat::from_u128(10u128.pow(scale), DECIMAL_P);
+
+        // the product is exact
+        let x = self
+            .mul(&q, p.saturating_add(DECIMAL_P), RoundingMode::None)
+            .round(0, rm);
+
+        let (m, _, s, e, _) = x.as_raw_parts()?;
+
+        let v = int_to_u128(m, e as isize)?;
+
+        if v >> DECIMAL_MANTISSA_BITS != 0 {
+            return None;
+        }
+
+        let v = if s.is_negative() { -(v as i128) } else { v as i128 };
+
+        Decimal::try_from_i128_with_scale(v, scale).ok()
+    }
+}
+
+impl From<Decimal> for BigFloat {
+    fn from(d: Decimal) -> Self {
+        BigFloat::from_decimal(&d, DEFAULT_P, DEFAULT_RM)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn test_decimal() {
+        let rm = RoundingMode::ToEven;
+
+        for s in [
+            "0",
+            "1",
+            "-1",
+            "0.1",
+            "-123.45",
+            "79228162514264337593543950335",
+            "-79228162514264337593543950335",
+            "0.0000000000000000000000000001",
+            "3.1415926535897932384626433833",
+        ] {
+            let d = Decimal::from_str(s).unwrap();
+            let n = BigFloat::from_decimal(&d, 256, rm);
+
+            assert_eq!(n, BigFloat::parse(s, crate::Radix::Dec, 256, rm));
+            assert_eq!(n.to_decimal(d.scale(), rm), Some(d));
+        }
+
+        let d = Decimal::from_str("2.5").unwrap();
+        assert_eq!(BigFloat::from(d), BigFloat::from_f64(2.5, 64));
+
+        // rounding to the scale
+        let n = BigFloat::from_f64(2.5, 64);
+        assert_eq!(n.to_decimal(0, rm), Some(Decimal::from(2)));
+        assert_eq!(
+            n.to_decimal(0, RoundingMode::FromZero),
+            Some(Decimal::from(3))
+        );
+        assert_eq!(
+            n.neg().to_decimal(0, RoundingMode::Down),
+            Some(Decimal::from(-3))
+        );
+        assert_eq!(n.to_decimal(1, rm), Some(Decimal::new(25, 1)));
+        assert_eq!(
+            n.neg().to_decimal(0, RoundingMode::ToNearestAway),
+            Some(Decimal::from(-3))
+        );
+        assert_eq!(
+            BigFloat::from_f64(0.25, 64).to_decimal(1, RoundingMode::ToNearestAway),
+            Some(Decimal::new(3, 1))
+        );
+
+        let n = BigFloat::from_word(1, 128).div(&BigFloat::from_word(3, 128), 128, rm);
+        assert_eq!(n.to_decimal(5, rm), Some(Decimal::new(33333, 5)));
+
+        // out of range
+        let n = BigFloat::from_u128(1u128 << 96, 128);
+        assert_eq!(n.to_decimal(0, rm), None);
+        assert_eq!(
+            n.sub(&BigFloat::from_word(1, 64), 128, rm)
+                .to_decimal(0, rm),
+            Some(Decimal::MAX)
+        );
+        assert_eq!(BigFloat::from_word(1, 64).to_decimal(29, rm), None);
+        assert_eq!(BigFloat::from_word(10, 64).to_decimal(28, rm), None);
+        assert_eq!(crate::NAN.to_decimal(0, rm), None);
+        assert_eq!(crate::INF_POS.to_decimal(0, rm), None);
+    }
+}
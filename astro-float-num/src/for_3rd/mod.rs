@@ -1,4 +1,12 @@
 #[cfg(feature = "serde")]
 mod de;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+#[cfg(feature = "python")]
+pub mod python;
 #[cfg(feature = "serde")]
 mod ser;
+#[cfg(feature = "serde")]
+pub mod serde_precise;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,688 @@
+//! Python bindings of BigFloat.
+//! Numbers are passed to and from Python as strings, floats, and integers.
+//!
+//! The bindings are registered in a Python module by `register`, e.g. from the `#[pymodule]` function
+//! of an extension crate built as `cdylib`.
+
+// pyo3 macros convert the errors returned by the methods to the same type.
+#![allow(clippy::useless_conversion)]
+
+use crate::defs::{Radix, DEFAULT_P, DEFAULT_RM};
+use crate::ops::consts::with_consts;
+use crate::BigFloat;
+use crate::Consts;
+use crate::Error;
+use crate::RoundingMode;
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyOverflowError, PyValueError, PyZeroDivisionError};
+use pyo3::prelude::*;
+use pyo3::types::{PyInt, PyModule};
+
+/// BigFloat wrapper exported to Python as `BigFloat`.
+///
+/// Arithmetic operators compute results with the largest precision of the operands rounded to the nearest even,
+/// and accept `int` and `float` values along with `BigFloat`.
+/// Methods accept precision `p` and rounding mode `rm` explicitly.
+/// If `p` is not given, the precision of `self` is used.
+#[pyclass(name = "BigFloat", module = "astro_float")]
+#[derive(Debug, Clone)]
+pub struct PyBigFloat {
+    inner: BigFloat,
+}
+
+/// Rounding modes exported to Python as `RoundingMode`.
+/// The rounding mode `RoundingMode::None` is named `Faithful`, because `None` is a keyword in Python.
+#[pyclass(name = "RoundingMode", module = "astro_float", eq, eq_int)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PyRoundingMode {
+    /// Faithful rounding.
+    Faithful,
+    /// Round toward positive infinity.
+    Up,
+    /// Round toward negative infinity.
+    Down,
+    /// Round toward zero.
+    ToZero,
+    /// Round away from zero.
+    FromZero,
+    /// Round half to even.
+    ToEven,
+    /// Round half to odd.
+    ToOdd,
+    /// Round to nearest, ties away from zero.
+    ToNearestAway,
+}
+
+impl From<PyRoundingMode> for RoundingMode {
+    fn from(rm: PyRoundingMode) -> Self {
+        match rm {
+            PyRoundingMode::Faithful => RoundingMode::None,
+            PyRoundingMode::Up => RoundingMode::Up,
+            PyRoundingMode::Down => RoundingMode::Down,
+            PyRoundingMode::ToZero => RoundingMode::ToZero,
+            PyRoundingMode::FromZero => RoundingMode::FromZero,
+            PyRoundingMode::ToEven => RoundingMode::ToEven,
+            PyRoundingMode::ToOdd => RoundingMode::ToOdd,
+            PyRoundingMode::ToNearestAway => RoundingMode::ToNearestAway,
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for RoundingMode {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        ob.extract::<PyRoundingMode>().map(RoundingMode::from)
+    }
+}
+
+/// Value which can be converted to BigFloat.
+#[derive(FromPyObject)]
+enum Operand<'py> {
+    Num(PyRef<'py, PyBigFloat>),
+    Int(Bound<'py, PyInt>),
+    Float(f64),
+    Str(String),
+}
+
+impl Operand<'_> {
+    // Converts the value to BigFloat with precision `p` using rounding mode `rm`.
+    fn to_bigfloat(&self, p: usize, rm: RoundingMode) -> PyResult<BigFloat> {
+        Ok(match self {
+            Operand::Num(v) => v.inner.clone(),
+            Operand::Int(v) => BigFloat::parse(&v.str()?.to_cow()?, Radix::Dec, p, rm),
+            Operand::Float(f) => BigFloat::from_f64(*f, p),
+            Operand::Str(s) => {
+                let v = BigFloat::parse(s, Radix::Dec, p, rm);
+                if v.is_nan() && !s.trim().eq_ignore_ascii_case("nan") {
+                    return Err(PyValueError::new_err(format!(
+                        "could not convert string to BigFloat: '{}'",
+                        s
+                    )));
+                }
+                v
+            }
+        })
+    }
+
+    // Returns the precision of the value.
+    fn precision(&self) -> usize {
+        match self {
+            Operand::Num(v) => v.precision(),
+            _ => 0,
+        }
+    }
+}
+
+#[pymethods]
+impl PyBigFloat {
+    /// Constructs a number with precision `p` from `value`, which can be a BigFloat, an int, a float, or a decimal string.
+    /// The value is rounded using rounding mode `rm`.
+    #[new]
+    #[pyo3(signature = (value = None, p = DEFAULT_P, rm = DEFAULT_RM))]
+    fn new(value: Option<Operand<'_>>, p: usize, rm: RoundingMode) -> PyResult<Self> {
+        let mut inner = match value {
+            Some(v) => v.to_bigfloat(p, rm)?,
+            None => BigFloat::new(p),
+        };
+
+        if !inner.is_nan() && !inner.is_inf() {
+            inner.set_precision(p, rm).map_err(to_py_err)?;
+        }
+
+        Ok(inner.into())
+    }
+
+    /// Returns the value of pi with precision `p` rounded using rounding mode `rm`.
+    #[staticmethod]
+    #[pyo3(signature = (p = DEFAULT_P, rm = DEFAULT_RM))]
+    fn pi(p: usize, rm: RoundingMode) -> PyBigFloat {
+        crate::pi(p, rm).into()
+    }
+
+    /// Returns the value of the Euler number with precision `p` rounded using rounding mode `rm`.
+    #[staticmethod]
+    #[pyo3(signature = (p = DEFAULT_P, rm = DEFAULT_RM))]
+    fn e(p: usize, rm: RoundingMode) -> PyBigFloat {
+        crate::e(p, rm).into()
+    }
+
+    /// The precision of the number in bits. The precision of NaN and Inf is 0.
+    #[getter]
+    fn precision(&self) -> usize {
+        self.inner.precision().unwrap_or(0)
+    }
+
+    /// Returns the string representation of the number in radix `radix` which can be 2, 8, 10, or 16.
+    /// The digits are rounded using rounding mode `rm`.
+    /// If `digits` is given, only the first and the last `digits` digits of a long mantissa are printed.
+    #[pyo3(signature = (radix = 10, rm = DEFAULT_RM, digits = None))]
+    fn format(&self, radix: u32, rm: RoundingMode, digits: Option<usize>) -> PyResult<String> {
+        let rdx = match radix {
+            2 => Radix::Bin,
+            8 => Radix::Oct,
+            10 => Radix::Dec,
+            16 => Radix::Hex,
+            _ => return Err(PyValueError::new_err("radix must be 2, 8, 10, or 16")),
+        };
+
+        let mut buf = Vec::new();
+
+        match digits {
+            Some(k) => self.inner.format_abbrev_into(rdx, rm, k, &mut buf),
+            None => self.inner.format_into(rdx, rm, &mut buf),
+        }
+        .map_err(to_py_err)?;
+
+        // the output consists of ASCII characters and the ellipsis.
+        String::from_utf8(buf).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("BigFloat('{}', p={})", self.inner, self.precision())
+    }
+
+    fn __float__(&self) -> f64 {
+        self.inner.to_f64_round(DEFAULT_RM)
+    }
+
+    /// Converts the integer part of the number to int.
+    fn __int__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        if self.inner.is_nan() {
+            return Err(PyValueError::new_err("cannot convert NaN to integer"));
+        }
+
+        if self.inner.is_inf() {
+            return Err(PyOverflowError::new_err("cannot convert Inf to integer"));
+        }
+
+        let n = self
+            .inner
+            .abs()
+            .to_biguint(RoundingMode::ToZero)
+            .map_err(to_py_err)?;
+
+        let sign = if self.inner.is_negative() { "-" } else { "" };
+
+        py.get_type_bound::<PyInt>()
+            .call1((format!("{}{}", sign, n),))
+    }
+
+    fn __bool__(&self) -> bool {
+        !self.inner.is_zero()
+    }
+
+    fn __neg__(&self) -> PyBigFloat {
+        self.inner.neg().into()
+    }
+
+    fn __pos__(&self) -> PyBigFloat {
+        self.clone()
+    }
+
+    fn __abs__(&self) -> PyBigFloat {
+        self.inner.abs().into()
+    }
+
+    fn __add__(&self, d2: Operand<'_>) -> PyResult<PyBigFloat> {
+        self.binary(&d2, false, |a, b, p| a.add(b, p, DEFAULT_RM))
+    }
+
+    fn __radd__(&self, d2: Operand<'_>) -> PyResult<PyBigFloat> {
+        self.binary(&d2, true, |a, b, p| a.add(b, p, DEFAULT_RM))
+    }
+
+    fn __sub__(&self, d2: Operand<'_>) -> PyResult<PyBigFloat> {
+        self.binary(&d2, false, |a, b, p| a.sub(b, p, DEFAULT_RM))
+    }
+
+    fn __rsub__(&self, d2: Operand<'_>) -> PyResult<PyBigFloat> {
+        self.binary(&d2, true, |a, b, p| a.sub(b, p, DEFAULT_RM))
+    }
+
+    fn __mul__(&self, d2: Operand<'_>) -> PyResult<PyBigFloat> {
+        self.binary(&d2, false, |a, b, p| a.mul(b, p, DEFAULT_RM))
+    }
+
+    fn __rmul__(&self, d2: Operand<'_>) -> PyResult<PyBigFloat> {
+        self.binary(&d2, true, |a, b, p| a.mul(b, p, DEFAULT_RM))
+    }
+
+    fn __truediv__(&self, d2: Operand<'_>) -> PyResult<PyBigFloat> {
+        self.binary_checked(&d2, false, |a, b, p| a.div(b, p, DEFAULT_RM))
+    }
+
+    fn __rtruediv__(&self, d2: Operand<'_>) -> PyResult<PyBigFloat> {
+        self.binary_checked(&d2, true, |a, b, p| a.div(b, p, DEFAULT_RM))
+    }
+
+    fn __mod__(&self, d2: Operand<'_>) -> PyResult<PyBigFloat> {
+        self.binary_checked(&d2, false, |a, b, _| a.rem(b))
+    }
+
+    fn __pow__(&self, d2: Operand<'_>, _modulo: Option<Operand<'_>>) -> PyResult<PyBigFloat> {
+        let p = self.precision().max(d2.precision());
+        let d2 = d2.to_bigfloat(p, DEFAULT_RM)?;
+        with_cc(|cc| self.inner.pow(&d2, p, DEFAULT_RM, cc))
+    }
+
+    fn __rpow__(&self, d2: Operand<'_>, _modulo: Option<Operand<'_>>) -> PyResult<PyBigFloat> {
+        let p = self.precision().max(d2.precision());
+        let d2 = d2.to_bigfloat(p, DEFAULT_RM)?;
+        with_cc(|cc| d2.pow(&self.inner, p, DEFAULT_RM, cc))
+    }
+
+    fn __richcmp__(&self, d2: Operand<'_>, op: CompareOp) -> PyResult<bool> {
+        let d2 = d2.to_bigfloat(self.precision().max(DEFAULT_P), DEFAULT_RM)?;
+
+        Ok(match self.inner.partial_cmp(&d2) {
+            Some(ord) => op.matches(ord),
+            None => matches!(op, CompareOp::Ne),
+        })
+    }
+
+    /// Returns true if the number is NaN.
+    fn is_nan(&self) -> bool {
+        self.inner.is_nan()
+    }
+
+    /// Returns true if the number is Inf.
+    fn is_inf(&self) -> bool {
+        self.inner.is_inf()
+    }
+
+    /// Returns true if the number is zero.
+    fn is_zero(&self) -> bool {
+        self.inner.is_zero()
+    }
+
+    /// Returns true if the number is not exact.
+    fn is_inexact(&self) -> bool {
+        self.inner.inexact()
+    }
+
+    /// Returns a copy of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p, rm = DEFAULT_RM))]
+    fn with_precision(&self, p: usize, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let mut ret = self.inner.clone();
+        ret.set_precision(p, rm).map_err(to_py_err)?;
+        Ok(ret.into())
+    }
+
+    /// Adds `d2` to the number and returns the result with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (d2, p = None, rm = DEFAULT_RM))]
+    fn add(&self, d2: Operand<'_>, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        Ok(self.inner.add(&d2.to_bigfloat(p, rm)?, p, rm).into())
+    }
+
+    /// Subtracts `d2` from the number and returns the result with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (d2, p = None, rm = DEFAULT_RM))]
+    fn sub(&self, d2: Operand<'_>, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        Ok(self.inner.sub(&d2.to_bigfloat(p, rm)?, p, rm).into())
+    }
+
+    /// Multiplies the number by `d2` and returns the result with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (d2, p = None, rm = DEFAULT_RM))]
+    fn mul(&self, d2: Operand<'_>, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        Ok(self.inner.mul(&d2.to_bigfloat(p, rm)?, p, rm).into())
+    }
+
+    /// Divides the number by `d2` and returns the result with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (d2, p = None, rm = DEFAULT_RM))]
+    fn div(&self, d2: Operand<'_>, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        Ok(self.inner.div(&d2.to_bigfloat(p, rm)?, p, rm).into())
+    }
+
+    /// Computes the number to the power of `d2` with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (d2, p = None, rm = DEFAULT_RM))]
+    fn pow(&self, d2: Operand<'_>, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        let d2 = d2.to_bigfloat(p, rm)?;
+        with_cc(|cc| self.inner.pow(&d2, p, rm, cc))
+    }
+
+    /// Computes the logarithm base `d2` of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (d2, p = None, rm = DEFAULT_RM))]
+    fn log(&self, d2: Operand<'_>, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        let d2 = d2.to_bigfloat(p, rm)?;
+        with_cc(|cc| self.inner.log(&d2, p, rm, cc))
+    }
+
+    /// Returns the integer part of the number.
+    fn int(&self) -> PyBigFloat {
+        self.inner.int().into()
+    }
+
+    /// Returns the fractional part of the number.
+    fn fract(&self) -> PyBigFloat {
+        self.inner.fract().into()
+    }
+
+    /// Returns the largest integer less than or equal to the number.
+    fn floor(&self) -> PyBigFloat {
+        self.inner.floor().into()
+    }
+
+    /// Returns the smallest integer greater than or equal to the number.
+    fn ceil(&self) -> PyBigFloat {
+        self.inner.ceil().into()
+    }
+
+    /// Computes the square root of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn sqrt(&self, p: Option<usize>, rm: RoundingMode) -> PyBigFloat {
+        let p = p.unwrap_or(self.precision());
+        self.inner.sqrt(p, rm).into()
+    }
+
+    /// Computes the cube root of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn cbrt(&self, p: Option<usize>, rm: RoundingMode) -> PyBigFloat {
+        let p = p.unwrap_or(self.precision());
+        self.inner.cbrt(p, rm).into()
+    }
+
+    /// Computes the reciprocal of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn reciprocal(&self, p: Option<usize>, rm: RoundingMode) -> PyBigFloat {
+        let p = p.unwrap_or(self.precision());
+        self.inner.reciprocal(p, rm).into()
+    }
+
+    /// Computes the natural logarithm of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn ln(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.ln(p, rm, cc))
+    }
+
+    /// Computes the logarithm base 2 of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn log2(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.log2(p, rm, cc))
+    }
+
+    /// Computes the logarithm base 10 of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn log10(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.log10(p, rm, cc))
+    }
+
+    /// Computes `e` to the power of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn exp(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.exp(p, rm, cc))
+    }
+
+    /// Computes the sine of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn sin(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.sin(p, rm, cc))
+    }
+
+    /// Computes the cosine of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn cos(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.cos(p, rm, cc))
+    }
+
+    /// Computes the tangent of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn tan(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.tan(p, rm, cc))
+    }
+
+    /// Computes the arcsine of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn asin(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.asin(p, rm, cc))
+    }
+
+    /// Computes the arccosine of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn acos(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.acos(p, rm, cc))
+    }
+
+    /// Computes the arctangent of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn atan(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.atan(p, rm, cc))
+    }
+
+    /// Computes the hyperbolic sine of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn sinh(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.sinh(p, rm, cc))
+    }
+
+    /// Computes the hyperbolic cosine of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn cosh(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.cosh(p, rm, cc))
+    }
+
+    /// Computes the hyperbolic tangent of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn tanh(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.tanh(p, rm, cc))
+    }
+
+    /// Computes the hyperbolic arcsine of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn asinh(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.asinh(p, rm, cc))
+    }
+
+    /// Computes the hyperbolic arccosine of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn acosh(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.acosh(p, rm, cc))
+    }
+
+    /// Computes the hyperbolic arctangent of the number with precision `p` rounded using rounding mode `rm`.
+    #[pyo3(signature = (p = None, rm = DEFAULT_RM))]
+    fn atanh(&self, p: Option<usize>, rm: RoundingMode) -> PyResult<PyBigFloat> {
+        let p = p.unwrap_or(self.precision());
+        with_cc(|cc| self.inner.atanh(p, rm, cc))
+    }
+}
+
+impl PyBigFloat {
+    /// Returns a reference to the wrapped BigFloat.
+    pub fn as_bigfloat(&self) -> &BigFloat {
+        &self.inner
+    }
+
+    // Computes `f(self, d2, p)`, or `f(d2, self, p)` if `swap` is true, where `p` is the largest precision of the operands.
+    fn binary(
+        &self,
+        d2: &Operand<'_>,
+        swap: bool,
+        f: impl FnOnce(&BigFloat, &BigFloat, usize) -> BigFloat,
+    ) -> PyResult<PyBigFloat> {
+        let p = self.precision().max(d2.precision());
+        let d2 = d2.to_bigfloat(p, DEFAULT_RM)?;
+
+        Ok(if swap { f(&d2, &self.inner, p) } else { f(&self.inner, &d2, p) }.into())
+    }
+
+    // Same as `binary`, but raises ZeroDivisionError if the divisor is zero.
+    fn binary_checked(
+        &self,
+        d2: &Operand<'_>,
+        swap: bool,
+        f: impl FnOnce(&BigFloat, &BigFloat, usize) -> BigFloat,
+    ) -> PyResult<PyBigFloat> {
+        let p = self.precision().max(d2.precision());
+        let d2 = d2.to_bigfloat(p, DEFAULT_RM)?;
+
+        let divisor = if swap { &self.inner } else { &d2 };
+        if divisor.is_zero() {
+            return Err(PyZeroDivisionError::new_err("division by zero"));
+        }
+
+        Ok(if swap { f(&d2, &self.inner, p) } else { f(&self.inner, &d2, p) }.into())
+    }
+}
+
+impl From<BigFloat> for PyBigFloat {
+    fn from(inner: BigFloat) -> Self {
+        PyBigFloat { inner }
+    }
+}
+
+impl From<PyBigFloat> for BigFloat {
+    fn from(v: PyBigFloat) -> Self {
+        v.inner
+    }
+}
+
+// Calls `f` with the process-wide constants cache.
+fn with_cc(f: impl FnOnce(&mut Consts) -> BigFloat) -> PyResult<PyBigFloat> {
+    with_consts(f).map(PyBigFloat::from).map_err(to_py_err)
+}
+
+// Converts the error to a Python exception.
+fn to_py_err(e: Error) -> PyErr {
+    match e {
+        Error::ExponentOverflow(_) => PyOverflowError::new_err(e.to_string()),
+        Error::DivisionByZero => PyZeroDivisionError::new_err(e.to_string()),
+        _ => PyValueError::new_err(e.to_string()),
+    }
+}
+
+/// Adds the classes `BigFloat` and `RoundingMode` to the Python module `m`.
+///
+/// ## Examples
+///
+/// ``` ignore
+/// use pyo3::prelude::*;
+///
+/// #[pymodule]
+/// fn astro_float(m: &Bound<'_, PyModule>) -> PyResult<()> {
+///     astro_float_num::python::register(m)
+/// }
+/// ```
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyBigFloat>()?;
+    m.add_class::<PyRoundingMode>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pyo3::types::PyDict;
+
+    #[test]
+    fn test_python_bigfloat() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let m = PyModule::new_bound(py, "astro_float").unwrap();
+            register(&m).unwrap();
+
+            let locals = PyDict::new_bound(py);
+            locals.set_item("af", &m).unwrap();
+
+            let eval = |code: &str| py.eval_bound(code, None, Some(&locals)).unwrap();
+
+            assert_eq!(
+                eval("str(af.BigFloat('1.5') + af.BigFloat(-0.25))")
+                    .extract::<String>()
+                    .unwrap(),
+                "1.25e+0"
+            );
+            assert_eq!(
+                eval("float(1 - af.BigFloat(0.25) * 2)")
+                    .extract::<f64>()
+                    .unwrap(),
+                0.5
+            );
+            assert_eq!(
+                eval("float(1 / af.BigFloat(4))").extract::<f64>().unwrap(),
+                0.25
+            );
+            assert_eq!(
+                eval("int(af.BigFloat(-2) ** 100)")
+                    .extract::<u128>()
+                    .unwrap(),
+                1u128 << 100
+            );
+            assert_eq!(
+                eval("int(af.BigFloat('-123456789012345678901234567890.75', 256))")
+                    .str()
+                    .unwrap()
+                    .to_string(),
+                "-123456789012345678901234567890"
+            );
+            assert!(eval(
+                "af.BigFloat(2) > 1.5 and af.BigFloat(2) == 2 and af.BigFloat('nan') != 0"
+            )
+            .extract::<bool>()
+            .unwrap());
+            assert_eq!(
+                eval("af.BigFloat(2).sqrt(256).precision")
+                    .extract::<usize>()
+                    .unwrap(),
+                256
+            );
+            let mut buf = Vec::new();
+            BigFloat::from_word(1, 128)
+                .exp(128, RoundingMode::ToZero, &mut Consts::new().unwrap())
+                .format_into(Radix::Hex, RoundingMode::ToEven, &mut buf)
+                .unwrap();
+            assert_eq!(
+                eval("af.BigFloat(1).exp(128, af.RoundingMode.ToZero).format(16)")
+                    .extract::<String>()
+                    .unwrap(),
+                String::from_utf8(buf).unwrap()
+            );
+            assert_eq!(
+                eval("af.BigFloat.pi(4000).format(digits=6)")
+                    .extract::<String>()
+                    .unwrap(),
+                "3.14159…356372e+0 (1215 digits)"
+            );
+            assert_eq!(
+                eval("repr(af.BigFloat(1.5, 64))")
+                    .extract::<String>()
+                    .unwrap(),
+                "BigFloat('1.5e+0', p=64)"
+            );
+
+            assert!(py
+                .eval_bound("af.BigFloat(1) / 0", None, Some(&locals))
+                .is_err());
+            assert!(py
+                .eval_bound("af.BigFloat('abc')", None, Some(&locals))
+                .is_err());
+            assert!(py
+                .eval_bound("af.BigFloat(1) + []", None, Some(&locals))
+                .is_err());
+        });
+    }
+}
@@ -0,0 +1,221 @@
+//! Precision-preserving serialization of BigFloat.
+//!
+//! The default serialization of BigFloat is a decimal string, and deserialization parses it with the precision of 64 bits.
+//! The functions of this module serialize BigFloat as a structure containing the value `v` as a decimal string,
+//! the precision `p` of the mantissa in bits, and the `inexact` flag, e.g. `{"v":"1.5e+0","p":128,"inexact":false}`.
+//! Deserialization restores the number with exactly the same value, precision, and inexact flag.
+//! The precision of Inf and NaN is serialized as none.
+//!
+//! The module can be used with the serde field attribute `#[serde(with = "astro_float_num::serde_precise")]`.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use astro_float_num::BigFloat;
+//! # use astro_float_num::RoundingMode;
+//! # use astro_float_num::serde_precise;
+//! let one = BigFloat::from_word(1, 1024);
+//! let three = BigFloat::from_word(3, 1024);
+//! let n = one.div(&three, 1024, RoundingMode::ToEven);
+//!
+//! let mut buf = Vec::new();
+//! serde_precise::serialize(&n, &mut serde_json::Serializer::new(&mut buf)).unwrap();
+//!
+//! let json = String::from_utf8(buf).unwrap();
+//! assert!(json.ends_with(r#","p":1024,"inexact":true}"#));
+//!
+//! let d = serde_precise::deserialize(&mut serde_json::Deserializer::from_str(&json)).unwrap();
+//!
+//! assert_eq!(d.mantissa_max_bit_len(), Some(1024));
+//! assert!(d.inexact());
+//! assert_eq!(d.cmp(&n), Some(0));
+//! ```
+
+use core::fmt::Formatter;
+
+use crate::num::BigFloatNumber;
+use crate::{BigFloat, Radix, RoundingMode, INF_NEG, INF_POS, NAN};
+use serde::de::{Error, MapAccess, SeqAccess, Unexpected, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserializer, Serializer};
+
+#[cfg(not(feature = "std"))]
+use {alloc::format, alloc::string::String, alloc::string::ToString};
+
+const FIELDS: &[&str] = &["v", "p", "inexact"];
+
+/// Serializes `n` together with its precision and inexact flag.
+pub fn serialize<S: Serializer>(n: &BigFloat, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct("BigFloat", FIELDS.len())?;
+    state.serialize_field("v", &n.to_string())?;
+    state.serialize_field("p", &n.mantissa_max_bit_len())?;
+    state.serialize_field("inexact", &n.inexact())?;
+    state.end()
+}
+
+/// Deserializes a number serialized with [`serialize`] restoring its precision and inexact flag.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigFloat, D::Error> {
+    deserializer.deserialize_struct("BigFloat", FIELDS, PreciseVisitor {})
+}
+
+struct PreciseVisitor {}
+
+impl PreciseVisitor {
+    fn restore<E: Error>(v: &str, p: Option<usize>, inexact: bool) -> Result<BigFloat, E> {
+        match p {
+            Some(p) => match BigFloatNumber::parse(v, Radix::Dec, p, RoundingMode::ToEven) {
+                Ok(mut o) => {
+                    o.set_inexact(inexact);
+                    Ok(o.into())
+                }
+                Err(e) => Err(Error::custom(format!("{e:?}"))),
+            },
+            None => match v {
+                "Inf" => Ok(INF_POS),
+                "-Inf" => Ok(INF_NEG),
+                "NaN" => Ok(NAN),
+                _ => Err(Error::invalid_value(
+                    Unexpected::Str(v),
+                    &"Inf, -Inf, or NaN",
+                )),
+            },
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for PreciseVisitor {
+    type Value = BigFloat;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        write!(formatter, "struct with fields `v`, `p`, `inexact`")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let v: String = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let p: Option<usize> = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        let inexact: bool = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(2, &self))?;
+
+        Self::restore(&v, p, inexact)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut v: Option<String> = None;
+        let mut p: Option<Option<usize>> = None;
+        let mut inexact: Option<bool> = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "v" if v.is_none() => v = Some(map.next_value()?),
+                "p" if p.is_none() => p = Some(map.next_value()?),
+                "inexact" if inexact.is_none() => inexact = Some(map.next_value()?),
+                "v" | "p" | "inexact" => {
+                    return Err(Error::custom(format!("duplicate field `{key}`")))
+                }
+                _ => return Err(Error::unknown_field(&key, FIELDS)),
+            }
+        }
+
+        let v = v.ok_or_else(|| Error::missing_field("v"))?;
+        let p = p.ok_or_else(|| Error::missing_field("p"))?;
+        let inexact = inexact.ok_or_else(|| Error::missing_field("inexact"))?;
+
+        Self::restore(&v, p, inexact)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::util::random_subnormal;
+    use crate::{Exponent, EXPONENT_MAX, EXPONENT_MIN, WORD_BIT_SIZE};
+    use rand::random;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    fn to_json(n: &BigFloat) -> String {
+        let mut buf = Vec::new();
+        serialize(n, &mut serde_json::Serializer::new(&mut buf)).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    fn from_json(s: &str) -> Result<BigFloat, serde_json::Error> {
+        deserialize(&mut serde_json::Deserializer::from_str(s))
+    }
+
+    #[test]
+    fn test_serde_precise() {
+        // format
+        let n = BigFloat::from_f64(1.5, 128);
+        assert_eq!(to_json(&n), r#"{"v":"1.5e+0","p":128,"inexact":false}"#);
+        assert_eq!(
+            to_json(&INF_NEG),
+            r#"{"v":"-Inf","p":null,"inexact":false}"#
+        );
+
+        // round trip
+        for i in 0..1000 {
+            let p = (random::<usize>() % 32 + 1) * WORD_BIT_SIZE;
+
+            let mut n = if i & 1 == 0 {
+                BigFloat::random_normal(p, EXPONENT_MIN + p as Exponent, EXPONENT_MAX)
+            } else {
+                random_subnormal(p).into()
+            };
+            n.set_inexact(random::<bool>());
+
+            let d = from_json(&to_json(&n)).unwrap();
+
+            assert_eq!(d.cmp(&n), Some(0));
+            assert_eq!(d.mantissa_max_bit_len(), n.mantissa_max_bit_len());
+            assert_eq!(d.inexact(), n.inexact());
+        }
+
+        for n in [INF_POS, INF_NEG] {
+            assert_eq!(from_json(&to_json(&n)).unwrap().cmp(&n), Some(0));
+        }
+        assert!(from_json(&to_json(&NAN)).unwrap().is_nan());
+
+        let mut n = BigFloat::new(192);
+        n.set_inexact(true);
+        let d = from_json(&to_json(&n)).unwrap();
+        assert!(d.is_zero());
+        assert_eq!(d.mantissa_max_bit_len(), Some(192));
+        assert!(d.inexact());
+
+        // field order does not matter
+        let d = from_json(r#"{"inexact":true,"p":256,"v":"-2.5e-10"}"#).unwrap();
+        assert_eq!(d.mantissa_max_bit_len(), Some(256));
+        assert!(d.inexact());
+        assert_eq!(
+            d.cmp(&BigFloat::parse(
+                "-2.5e-10",
+                Radix::Dec,
+                256,
+                RoundingMode::ToEven
+            )),
+            Some(0)
+        );
+
+        // sequence
+        let d = from_json(r#"["1.25",64,true]"#).unwrap();
+        assert_eq!(d.cmp(&BigFloat::from_f64(1.25, 64)), Some(0));
+        assert!(d.inexact());
+
+        // errors
+        assert!(from_json(r#"{"v":"1.5","p":128}"#).is_err());
+        assert!(from_json(r#"{"v":"1.5","p":128,"inexact":false,"q":1}"#).is_err());
+        assert!(from_json(r#"{"v":"1.5","p":128,"p":128,"inexact":false}"#).is_err());
+        assert!(from_json(r#"{"v":"1.5","p":null,"inexact":false}"#).is_err());
+        assert!(from_json(r#"{"v":"abc","p":128,"inexact":false}"#).is_err());
+        assert!(from_json(r#"["1.5",128]"#).is_err());
+        assert!(from_json(r#""1.5""#).is_err());
+    }
+}
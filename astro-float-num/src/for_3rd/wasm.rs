@@ -0,0 +1,205 @@
+//! WebAssembly bindings of BigFloat.
+//! Numbers are passed to and from JavaScript as strings, numbers, and BigInt values.
+
+use crate::defs::Radix;
+use crate::BigFloat;
+use crate::RoundingMode;
+use crate::Sign;
+use js_sys::BigInt;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+
+const HEX_DIGITS: [char; 16] =
+    ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f'];
+
+/// BigFloat wrapper exported to JavaScript as `BigFloat`.
+#[wasm_bindgen(js_name = BigFloat)]
+#[derive(Debug, Clone)]
+pub struct WasmBigFloat {
+    inner: BigFloat,
+}
+
+#[wasm_bindgen(js_class = BigFloat)]
+impl WasmBigFloat {
+    /// Parses a decimal number from the string `s` using precision `p` and rounding mode `rm`.
+    /// The result is NaN if the string can't be parsed.
+    #[wasm_bindgen(constructor)]
+    pub fn new(s: &str, p: usize, rm: RoundingMode) -> WasmBigFloat {
+        BigFloat::parse(s, Radix::Dec, p, rm).into()
+    }
+
+    /// Constructs a number with precision `p` from the JavaScript number `f`.
+    #[wasm_bindgen(js_name = fromNumber)]
+    pub fn from_number(f: f64, p: usize) -> WasmBigFloat {
+        BigFloat::from_f64(f, p).into()
+    }
+
+    /// Constructs a number from the JavaScript BigInt `n` using precision `p` and rounding mode `rm`.
+    #[wasm_bindgen(js_name = fromBigInt)]
+    pub fn from_bigint(n: &BigInt, p: usize, rm: RoundingMode) -> WasmBigFloat {
+        match n.to_string(16) {
+            Ok(s) => BigFloat::parse(&String::from(s), Radix::Hex, p, rm).into(),
+            Err(_) => BigFloat::nan(None).into(),
+        }
+    }
+
+    /// Returns the decimal string representation of the number.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.inner.to_string()
+    }
+
+    /// Converts the number to a JavaScript number. The conversion rounds the number towards zero.
+    #[wasm_bindgen(js_name = toNumber)]
+    pub fn to_number(&self) -> f64 {
+        self.inner.to_f64()
+    }
+
+    /// Converts the integer part of the number to a JavaScript BigInt.
+    /// Returns `undefined` if the number is NaN or Inf.
+    #[wasm_bindgen(js_name = toBigInt)]
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        let (s, hex) = int_to_hex_string(&self.inner)?;
+        let n = BigInt::new(&JsValue::from_str(&hex)).ok()?;
+        Some(if s.is_negative() { -n } else { n })
+    }
+
+    /// Adds `d2` to `self` and returns the result with precision `p` rounded using rounding mode `rm`.
+    pub fn add(&self, d2: &WasmBigFloat, p: usize, rm: RoundingMode) -> WasmBigFloat {
+        self.inner.add(&d2.inner, p, rm).into()
+    }
+
+    /// Subtracts `d2` from `self` and returns the result with precision `p` rounded using rounding mode `rm`.
+    pub fn sub(&self, d2: &WasmBigFloat, p: usize, rm: RoundingMode) -> WasmBigFloat {
+        self.inner.sub(&d2.inner, p, rm).into()
+    }
+
+    /// Multiplies `self` by `d2` and returns the result with precision `p` rounded using rounding mode `rm`.
+    pub fn mul(&self, d2: &WasmBigFloat, p: usize, rm: RoundingMode) -> WasmBigFloat {
+        self.inner.mul(&d2.inner, p, rm).into()
+    }
+
+    /// Divides `self` by `d2` and returns the result with precision `p` rounded using rounding mode `rm`.
+    pub fn div(&self, d2: &WasmBigFloat, p: usize, rm: RoundingMode) -> WasmBigFloat {
+        self.inner.div(&d2.inner, p, rm).into()
+    }
+
+    /// Computes the square root of the number with precision `p` using rounding mode `rm`.
+    pub fn sqrt(&self, p: usize, rm: RoundingMode) -> WasmBigFloat {
+        self.inner.sqrt(p, rm).into()
+    }
+
+    /// Returns the absolute value of the number.
+    pub fn abs(&self) -> WasmBigFloat {
+        self.inner.abs().into()
+    }
+
+    /// Returns the number with the sign reversed.
+    pub fn neg(&self) -> WasmBigFloat {
+        self.inner.neg().into()
+    }
+
+    /// Compares `self` to `d2`.
+    /// Returns 1 if `self` > `d2`, -1 if `self` < `d2`, 0 if `self` == `d2`, `undefined` if `self` or `d2` is NaN.
+    pub fn cmp(&self, d2: &WasmBigFloat) -> Option<i8> {
+        self.inner.cmp(&d2.inner).map(|c| c.signum() as i8)
+    }
+
+    /// Returns true if the number is NaN.
+    #[wasm_bindgen(js_name = isNaN)]
+    pub fn is_nan(&self) -> bool {
+        self.inner.is_nan()
+    }
+
+    /// Returns true if the number is Inf.
+    #[wasm_bindgen(js_name = isInf)]
+    pub fn is_inf(&self) -> bool {
+        self.inner.is_inf()
+    }
+
+    /// Returns true if the number is zero.
+    #[wasm_bindgen(js_name = isZero)]
+    pub fn is_zero(&self) -> bool {
+        self.inner.is_zero()
+    }
+}
+
+impl WasmBigFloat {
+    /// Returns a reference to the wrapped BigFloat.
+    pub fn as_bigfloat(&self) -> &BigFloat {
+        &self.inner
+    }
+}
+
+impl From<BigFloat> for WasmBigFloat {
+    fn from(inner: BigFloat) -> Self {
+        WasmBigFloat { inner }
+    }
+}
+
+impl From<WasmBigFloat> for BigFloat {
+    fn from(v: WasmBigFloat) -> Self {
+        v.inner
+    }
+}
+
+// Returns the sign and the hexadecimal string representation of the absolute value of the integer part of `n`
+// prefixed with "0x", or None if `n` is NaN or Inf.
+fn int_to_hex_string(n: &BigFloat) -> Option<(Sign, String)> {
+    let int = n.int();
+    let (s, m, e) = int
+        .convert_to_radix_digits(Radix::Hex, None, RoundingMode::None)
+        .ok()?;
+
+    let mut ret = String::from("0x");
+
+    if m.is_empty() || int.is_zero() || e <= 0 {
+        ret.push('0');
+    } else {
+        let e = e as usize;
+        m.iter()
+            .take(e)
+            .for_each(|&d| ret.push(HEX_DIGITS[d as usize]));
+        (m.len()..e).for_each(|_| ret.push('0'));
+    }
+
+    Some((s, ret))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_wasm_bigfloat() {
+        let rm = RoundingMode::ToEven;
+        let d1 = WasmBigFloat::new("1.5", 128, rm);
+        let d2 = WasmBigFloat::from_number(-0.25, 128);
+
+        assert_eq!(d1.add(&d2, 128, rm).to_js_string(), "1.25e+0");
+        assert_eq!(d1.mul(&d2, 128, rm).to_number(), -0.375);
+        assert_eq!(d1.cmp(&d2), Some(1));
+        assert!(WasmBigFloat::new("abc", 128, rm).is_nan());
+
+        assert_eq!(
+            int_to_hex_string(&BigFloat::from_f64(-4096.75, 64)),
+            Some((Sign::Neg, String::from("0x1000")))
+        );
+        assert_eq!(
+            int_to_hex_string(&BigFloat::from_u128(
+                0xf000_0000_0000_0000_0000_0000_000a,
+                128
+            )),
+            Some((Sign::Pos, String::from("0xf00000000000000000000000000a")))
+        );
+        assert_eq!(
+            int_to_hex_string(&BigFloat::from_f64(0.5, 64)),
+            Some((Sign::Pos, String::from("0x0")))
+        );
+        assert_eq!(int_to_hex_string(&crate::NAN), None);
+    }
+}
@@ -5,6 +5,7 @@
 #![deny(missing_docs)]
 #![deny(clippy::suspicious)]
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "f128", feature(f128))]
 #![allow(clippy::comparison_chain)]
 #![allow(clippy::should_implement_trait)]
 #![allow(clippy::too_many_arguments)]
@@ -12,36 +13,114 @@
 #![allow(clippy::collapsible_if)]
 #![allow(clippy::module_inception)]
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+mod bid;
+#[cfg(feature = "alloc")]
+mod block;
+mod borrowed;
 mod common;
+#[cfg(feature = "alloc")]
 mod conv;
+#[cfg(feature = "alloc")]
 pub mod ctx;
 mod defs;
+#[cfg(feature = "alloc")]
+mod dual;
+#[cfg(feature = "alloc")]
 mod ext;
+#[cfg(feature = "alloc")]
+pub mod fft;
+#[cfg(feature = "alloc")]
 mod for_3rd;
+#[cfg(feature = "alloc")]
 mod mantissa;
+#[cfg(feature = "alloc")]
 mod num;
+#[cfg(feature = "alloc")]
 mod ops;
+#[cfg(feature = "alloc")]
 mod parser;
+#[cfg(feature = "alloc")]
+mod pool;
+#[cfg(feature = "posit")]
+mod posit;
+#[cfg(feature = "alloc")]
+pub mod series;
+#[cfg(feature = "alloc")]
+mod sexagesimal;
+mod stack;
+#[cfg(feature = "alloc")]
+pub mod statistics;
+#[cfg(feature = "alloc")]
 mod strop;
+#[cfg(feature = "alloc")]
+mod uint;
+#[cfg(feature = "alloc")]
+pub mod vectors;
 
+pub use crate::borrowed::BigFloatRef;
 pub use crate::defs::Error;
+pub use crate::defs::ErrorPolicy;
 pub use crate::defs::Exponent;
+pub use crate::defs::OpError;
 pub use crate::defs::Radix;
 pub use crate::defs::RoundingMode;
 pub use crate::defs::Sign;
 pub use crate::defs::Word;
+pub use crate::stack::StackBigFloat;
+
+#[cfg(feature = "alloc")]
+pub use crate::block::BlockFloatVec;
+#[cfg(feature = "alloc")]
+pub use crate::dual::BigFloatDual;
+#[cfg(feature = "alloc")]
+pub use crate::ext::Abbreviated;
+#[cfg(feature = "alloc")]
 pub use crate::ext::BigFloat;
+#[cfg(feature = "alloc")]
 pub use crate::ext::FromExt;
+#[cfg(feature = "alloc")]
+pub use crate::ext::PowCache;
+#[cfg(feature = "alloc")]
 pub use crate::ext::INF_NEG;
+#[cfg(feature = "alloc")]
 pub use crate::ext::INF_POS;
+#[cfg(feature = "alloc")]
 pub use crate::ext::NAN;
+#[cfg(feature = "alloc")]
+pub use crate::ext::ONE;
+#[cfg(feature = "alloc")]
+pub use crate::ext::ONE_HALF;
+#[cfg(feature = "alloc")]
+pub use crate::ext::TEN;
+#[cfg(feature = "alloc")]
+pub use crate::ext::TWO;
+#[cfg(feature = "alloc")]
+pub use crate::mantissa::MulThresholds;
+#[cfg(feature = "alloc")]
 pub use crate::ops::consts::Consts;
+#[cfg(feature = "alloc")]
+pub use crate::ops::consts::PiDigits;
+#[cfg(feature = "global-consts")]
+pub use crate::ops::consts::{e, ln_10, ln_2, pi, rem_pi2, with_consts};
+#[cfg(feature = "alloc")]
+pub use crate::pool::BigFloatPool;
+#[cfg(feature = "alloc")]
+pub use crate::uint::BigUInt;
+
+#[cfg(feature = "python")]
+pub use crate::for_3rd::python;
+#[cfg(feature = "serde")]
+pub use crate::for_3rd::serde_precise;
+#[cfg(feature = "wasm")]
+pub use crate::for_3rd::wasm::WasmBigFloat;
 
 pub use crate::defs::EXPONENT_MAX;
 pub use crate::defs::EXPONENT_MIN;
+pub use crate::defs::PRECISION_GRANULARITY;
 pub use crate::defs::WORD_BASE;
 pub use crate::defs::WORD_BIT_SIZE;
 pub use crate::defs::WORD_MAX;
@@ -86,4 +165,65 @@ mod tests {
         // Print using decimal radix.
         //println!("{}", pi);
     }
+
+    // The results must be identical on targets with different word sizes.
+    #[test]
+    #[cfg(any(feature = "portable", not(word32)))]
+    fn test_portable() {
+        use crate::BigFloat;
+        use crate::Consts;
+        use crate::RoundingMode;
+        use crate::WORD_BIT_SIZE;
+
+        #[cfg(not(feature = "std"))]
+        use alloc::{format, string::String, vec::Vec};
+
+        // mantissa in hex, the sign, and the exponent, independent of the word size
+        fn repr(n: &BigFloat) -> String {
+            let (m, _, s, e, _) = n.as_raw_parts().unwrap();
+            let mut ret = String::new();
+            for w in m.iter().rev() {
+                ret += &format!("{:0w$x}", w, w = WORD_BIT_SIZE / 4);
+            }
+            format!("{:?} {} {}", s, ret, e)
+        }
+
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+
+        let x = BigFloat::from_word(3, 1);
+        let y = BigFloat::parse(
+            "1.2345678901234567890123456789e+10",
+            crate::Radix::Dec,
+            96,
+            rm,
+        );
+
+        let expected = [
+            "Pos b7f7070e0f03290a3167edc690651a37 34",
+            "Pos 89f94549fb425ec7a50df254ec4bd3a94000000000000000 36",
+            "Pos 859735c12091d72b -31",
+            "Pos ddb3d742c265539d92ba16b83c5c1dc5 1",
+            "Pos a0af2dfb7d882f96a5efe29ba261d1c82c2c1cb945548509 5",
+            "Pos b9e47fd5f0ba1688f258d11fb4db80a1 5",
+            "Neg 89459dd67f2606135f07fca76517b4e2 -1",
+            "Pos 8000000030ebe40a4c3af15cb5e02c29 1",
+        ];
+
+        let results: Vec<String> = [
+            x.add(&y, 96, rm),
+            x.mul(&y, 160, rm),
+            x.div(&y, 33, rm),
+            x.sqrt(96, rm),
+            x.exp(160, rm, &mut cc),
+            y.ln(96, rm, &mut cc),
+            y.sin(96, rm, &mut cc),
+            x.pow(&y.reciprocal(96, rm), 96, rm, &mut cc),
+        ]
+        .iter()
+        .map(repr)
+        .collect();
+
+        assert_eq!(results, expected);
+    }
 }
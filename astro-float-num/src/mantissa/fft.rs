@@ -12,6 +12,7 @@ use crate::defs::Word;
 use crate::defs::WORD_BIT_SIZE;
 use crate::defs::WORD_MAX;
 use crate::mantissa::Mantissa;
+use crate::mantissa::MulThresholds;
 use itertools::izip;
 
 #[cfg(not(feature = "std"))]
@@ -209,34 +210,22 @@ impl Mantissa {
             let chunk_sz = (m + WORD_BIT_SIZE - 1) / WORD_BIT_SIZE + 1;
             let mask = WORD_MAX >> (WORD_BIT_SIZE - (m % WORD_BIT_SIZE));
             let mut s = 0;
-            let mut idx;
-            let mut shift;
-
-            loop {
-                idx = s / WORD_BIT_SIZE;
-                shift = s % WORD_BIT_SIZE;
-                if idx + chunk_sz > d.len() {
-                    break;
-                }
+
+            while s < d.len() * WORD_BIT_SIZE {
+                let idx = s / WORD_BIT_SIZE;
+                let shift = s % WORD_BIT_SIZE;
+                let end = d.len().min(idx + chunk_sz);
 
                 let part = parts_iter.next().unwrap();
 
-                part[chunk_sz..].fill(0);
-                part[..chunk_sz].copy_from_slice(&d[idx..idx + chunk_sz]);
+                part[end - idx..].fill(0);
+                part[..end - idx].copy_from_slice(&d[idx..end]);
                 part.shift_right(shift);
                 part[chunk_sz - 1] = 0;
                 part[chunk_sz - 2] &= mask;
 
                 s += m;
             }
-
-            if idx < d.len() {
-                let part = parts_iter.next().unwrap();
-
-                part[d.len() - idx..].fill(0);
-                part[..d.len() - idx].copy_from_slice(&d[idx..]);
-                part.shift_right(shift);
-            }
         }
 
         for rest in parts_iter {
@@ -400,7 +389,12 @@ impl Mantissa {
     }
 
     // multiply two integer numbers.
-    pub(super) fn fft_mul(d1: &[Word], d2: &[Word], d3: &mut [Word]) -> Result<(), Error> {
+    pub(super) fn fft_mul(
+        d1: &[Word],
+        d2: &[Word],
+        d3: &mut [Word],
+        thresholds: &MulThresholds,
+    ) -> Result<(), Error> {
         let l: usize = (d1.len() + d2.len()) * WORD_BIT_SIZE;
 
         let (_n, k1, k, m, n1, t) = Self::fft_params(l);
@@ -440,7 +434,7 @@ impl Mantissa {
         Self::fft_forward(&mut parts2, w, k1, k, 1, n1, &modulus, tmp_buf);
 
         for (part1, part2, part3) in izip!(parts1.iter(), parts2.iter(), parts3.iter_mut()) {
-            Self::mul_unbalanced(part1, part2, tmp_buf2)?;
+            Self::mul_unbalanced_with(part1, part2, tmp_buf2, thresholds)?;
 
             part3.set_sign(part1.sign() * part2.sign());
 
@@ -517,7 +511,7 @@ mod tests {
         let mut ret_s = [0; 16];
 
         mul(&s1, &s2, &mut ref_s);
-        Mantissa::fft_mul(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::fft_mul(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -529,7 +523,7 @@ mod tests {
         let mut ret_s = [0; 10];
 
         mul(&s1, &s2, &mut ref_s);
-        Mantissa::fft_mul(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::fft_mul(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -540,7 +534,7 @@ mod tests {
         let mut ref_s = [0; 16];
 
         mul(&s1, &s2, &mut ref_s);
-        Mantissa::fft_mul(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::fft_mul(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -559,7 +553,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 200];
-        Mantissa::fft_mul(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::fft_mul(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -570,10 +564,30 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 100];
-        Mantissa::fft_mul(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::fft_mul(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
+        // the last part of the decomposition is incomplete
+        let s1 = [Word::MAX; 160];
+        let s2 = [Word::MAX; 213];
+        let mut ref_s = [0; 373];
+        let mut ret_s = [0; 373];
+        for l1 in 1..160 {
+            let l2 = l1 + l1 / 3;
+
+            mul(&s1[..l1], &s2[..l2], &mut ref_s[..l1 + l2]);
+            Mantissa::fft_mul(
+                &s1[..l1],
+                &s2[..l2],
+                &mut ret_s[..l1 + l2],
+                &MulThresholds::default(),
+            )
+            .unwrap();
+
+            assert!(ret_s[..l1 + l2] == ref_s[..l1 + l2]);
+        }
+
         // 0*0
         let s1 = [0, 0, 0, 0, 0, 0, 0, 0];
         let s2 = [0, 0, 0, 0, 0, 0, 0, 0];
@@ -581,7 +595,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 15];
-        Mantissa::fft_mul(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::fft_mul(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -592,7 +606,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 30];
-        Mantissa::fft_mul(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::fft_mul(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -607,7 +621,7 @@ mod tests {
 
             let mut ret_s = Vec::new();
             ret_s.resize(s1.len() + s2.len(), 0);
-            Mantissa::fft_mul(&s1, &s2, &mut ret_s).unwrap();
+            Mantissa::fft_mul(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
             //println!("{:?}\n{:?}\n", s1, s2);
             //println!("{:?}\n{:?}\n", ret_s, ref_s);
@@ -636,7 +650,7 @@ mod tests {
 
             let start_time = std::time::Instant::now();
             for ni in n.iter() {
-                Mantissa::fft_mul(ni, &f, &mut ret1).unwrap();
+                Mantissa::fft_mul(ni, &f, &mut ret1, &MulThresholds::default()).unwrap();
             }
             let time = start_time.elapsed();
             println!("fft_mul {}", time.as_millis());
@@ -15,14 +15,20 @@ use crate::defs::WORD_BASE;
 use crate::defs::WORD_BIT_SIZE;
 use crate::defs::WORD_MAX;
 use crate::defs::WORD_SIGNIFICANT_BIT;
+use crate::mantissa::mul::MulThresholds;
 use crate::mantissa::util::ExtendedSlice;
 use crate::mantissa::util::NormalizedView;
 use crate::mantissa::util::RightShiftedSlice;
+use core::fmt::Debug;
+use core::fmt::Formatter;
 use core::mem::size_of;
 use itertools::izip;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 /// Mantissa representation.
-#[derive(Debug, Hash)]
+#[derive(Hash)]
 pub struct Mantissa {
     m: WordBuf,
     n: usize, // number of bits, 0 is for number 0
@@ -32,7 +38,7 @@ impl Mantissa {
     // bit lenth to length in words.
     #[inline]
     fn bit_len_to_word_len(p: usize) -> usize {
-        (p + WORD_BIT_SIZE - 1) / size_of::<Word>() / 8
+        p.div_ceil(WORD_BIT_SIZE)
     }
 
     // reserve a buffer for mantissa.
@@ -307,22 +313,8 @@ impl Mantissa {
 
         if full_prec {
             m3.m.trunc_trailing_zeroes();
-        } else if m3.len() < p {
-            let n = m3.len();
-            m3.m.try_extend(p * WORD_BIT_SIZE)?;
-            m3.m[..p - n].fill(0);
-        } else if m3.len() > p {
-            if m3.round_mantissa(
-                (m3.len() - p) * WORD_BIT_SIZE,
-                rm,
-                is_positive,
-                &mut false,
-                m3.max_bit_len(),
-                inexact,
-            ) {
-                shift -= 1;
-            }
-            m3.m.trunc_to(p * WORD_BIT_SIZE);
+        } else if m3.fit_to_words(p, rm, is_positive, inexact)? {
+            shift -= 1;
         }
 
         m3.n = m3.max_bit_len();
@@ -446,7 +438,7 @@ impl Mantissa {
         Ok((shift, m3))
     }
 
-    /// Multiply two mantissas, return result, exponent shift, and inexact flag.
+    /// Multiply two mantissas using thresholds `t`, return result, exponent shift, and inexact flag.
     pub fn mul(
         &self,
         m2: &Self,
@@ -455,20 +447,26 @@ impl Mantissa {
         is_positive: bool,
         full_prec: bool,
         inexact: &mut bool,
+        t: &MulThresholds,
     ) -> Result<(isize, Self), Error> {
         debug_assert!(self.m[self.len() - 1] & WORD_SIGNIFICANT_BIT != 0);
         debug_assert!(m2.m[m2.len() - 1] & WORD_SIGNIFICANT_BIT != 0);
 
-        let mut m3 = Self::reserve_new(self.len() + m2.len())?;
+        let p = Self::bit_len_to_word_len(p);
 
-        Self::mul_unbalanced(&self.m, &m2.m, &mut m3)?;
+        let mut m3 = match Self::mul_truncated(&self.m, &m2.m, p, full_prec, t)? {
+            Some(m3) => m3,
+            None => {
+                let mut m3 = Self::reserve_new(self.len() + m2.len())?;
+                Self::mul_unbalanced_with(&self.m, &m2.m, &mut m3, t)?;
+                m3
+            }
+        };
 
         let mut shift = Self::maximize(&mut m3) as isize;
 
         let mut m3 = Mantissa { m: m3, n: 0 };
 
-        let p = Self::bit_len_to_word_len(p);
-
         if full_prec {
             m3.m.trunc_trailing_zeroes();
         } else if m3.len() < p {
@@ -532,7 +530,7 @@ impl Mantissa {
         if r_sticky {
             *inexact |= true;
 
-            if rm as u32 & 0b1100000 != 0 {
+            if rm as u32 & 0b11100000 != 0 {
                 m3.m[0] |= 1;
             } else if rm == RoundingMode::FromZero
                 || (is_positive && rm == RoundingMode::Up)
@@ -565,6 +563,111 @@ impl Mantissa {
         Ok((e_shift, m3))
     }
 
+    /// Multiply mantissa by a nonzero word, return exponent shift and result.
+    pub fn mul_word(
+        &self,
+        d: Word,
+        p: usize,
+        rm: RoundingMode,
+        is_positive: bool,
+        inexact: &mut bool,
+    ) -> Result<(isize, Self), Error> {
+        debug_assert!(d != 0);
+
+        let mut m3 = Self::reserve_new(self.len() + 1)?;
+
+        Self::mul_by_word(&self.m, d as DoubleWord, &mut m3);
+
+        let mut e_shift = WORD_BIT_SIZE as isize - Self::maximize(&mut m3) as isize;
+
+        let mut m3 = Mantissa { m: m3, n: 0 };
+
+        if m3.fit_to_words(Self::bit_len_to_word_len(p), rm, is_positive, inexact)? {
+            e_shift += 1;
+        }
+
+        m3.n = m3.max_bit_len();
+
+        Ok((e_shift, m3))
+    }
+
+    /// Divide mantissa by a nonzero word, return exponent shift and result.
+    pub fn div_word(
+        &self,
+        d: Word,
+        p: usize,
+        rm: RoundingMode,
+        is_positive: bool,
+        inexact: &mut bool,
+    ) -> Result<(isize, Self), Error> {
+        debug_assert!(d != 0);
+
+        let p = Self::bit_len_to_word_len(p);
+
+        // one word for normalization, and one guard word for the sticky bit
+        let k = p.max(self.len()) + 2;
+        let l = k - self.len();
+
+        let mut q = Self::reserve_new(k)?;
+
+        let d = d as DoubleWord;
+        let mut r: DoubleWord = 0;
+
+        for (i, v) in q.iter_mut().enumerate().rev() {
+            let w = if i >= l { self.m[i - l] } else { 0 };
+            let n = (r << WORD_BIT_SIZE) | w as DoubleWord;
+            *v = (n / d) as Word;
+            r = n % d;
+        }
+
+        if r != 0 {
+            *inexact |= true;
+            q[0] |= 1;
+        }
+
+        let mut e_shift = -(Self::maximize(&mut q) as isize);
+
+        let mut m3 = Mantissa { m: q, n: 0 };
+
+        if m3.fit_to_words(p, rm, is_positive, inexact)? {
+            e_shift += 1;
+        }
+
+        m3.n = m3.max_bit_len();
+
+        Ok((e_shift, m3))
+    }
+
+    // Extends or rounds maximized mantissa to `p` words.
+    // Returns true if rounding has caused the mantissa to overflow.
+    fn fit_to_words(
+        &mut self,
+        p: usize,
+        rm: RoundingMode,
+        is_positive: bool,
+        inexact: &mut bool,
+    ) -> Result<bool, Error> {
+        let mut overflow = false;
+
+        if self.len() < p {
+            let n = self.len();
+            self.m.try_extend(p * WORD_BIT_SIZE)?;
+            self.m[..p - n].fill(0);
+        } else if self.len() > p {
+            overflow = self.round_mantissa(
+                (self.len() - p) * WORD_BIT_SIZE,
+                rm,
+                is_positive,
+                &mut false,
+                self.max_bit_len(),
+                inexact,
+            );
+            self.m.trunc_to(p * WORD_BIT_SIZE);
+        }
+
+        Ok(overflow)
+    }
+
     // Multiply d1 by word d and put result to d3 with overflow.
     pub(super) fn mul_by_word(d1: &[Word], d: DoubleWord, d3: &mut [Word]) {
         let mut m: DoubleWord = 0;
@@ -676,12 +779,12 @@ impl Mantissa {
         let nd = m.len() - size_of::<u64>() / size_of::<Word>();
         m[..nd].fill(0);
 
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(word32))]
         {
             m[nd] = u;
         }
 
-        #[cfg(target_arch = "x86")]
+        #[cfg(word32)]
         {
             let mut u = u;
             for v in &mut m[nd..] {
@@ -710,8 +813,22 @@ impl Mantissa {
             return Ok((0, ret));
         }
 
-        m = Self::reserve_new(1)?;
-        m[0] = u as Word;
+        #[cfg(not(word32))]
+        {
+            m = Self::reserve_new(1)?;
+            m[0] = u as Word;
+        }
+
+        #[cfg(word32)]
+        {
+            m = Self::reserve_new(size_of::<usize>() / size_of::<Word>())?;
+
+            let mut u = u as u64;
+            for v in m.iter_mut() {
+                *v = u as Word;
+                u >>= WORD_BIT_SIZE;
+            }
+        }
 
         let shift = Self::maximize(&mut m);
         let mut ret = Mantissa { m, n: 0 };
@@ -722,12 +839,12 @@ impl Mantissa {
     }
 
     pub fn to_u64(&self) -> u64 {
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(word32))]
         {
             self.m[self.m.len() - 1]
         }
 
-        #[cfg(target_arch = "x86")]
+        #[cfg(word32)]
         {
             let mut ret: u64 = 0;
             let nd = size_of::<u64>() / size_of::<Word>();
@@ -802,6 +919,24 @@ impl Mantissa {
         Ok(Mantissa { m: mm, n })
     }
 
+    /// Construct from the vector of words without copying.
+    pub fn from_vec(mut m: Vec<Word>) -> Self {
+        // the least significant words are added if the length does not correspond to precision granularity
+        let l = Self::bit_len_to_word_len(m.len() * WORD_BIT_SIZE);
+        if l > m.len() {
+            m.splice(0..0, core::iter::repeat_n(0, l - m.len()));
+        }
+
+        let m = WordBuf::from_vec(m);
+        let n = Self::find_bit_len(&m);
+        Mantissa { m, n }
+    }
+
+    /// Decompose into the vector of words and the number of significant bits without copying.
+    pub fn into_raw_parts(self) -> (Vec<Word>, usize) {
+        (self.m.into_vec(), self.n)
+    }
+
     /// Returns true if all digits are equal to 0.
     pub fn is_all_zero(&self) -> bool {
         for v in (self.m).iter() {
@@ -824,6 +959,25 @@ impl Mantissa {
         self.len() * WORD_BIT_SIZE
     }
 
+    // Returns true if all bits at positions from `from` to `to` exclusively are set to 1.
+    fn bits_all_ones(&self, from: usize, to: usize) -> bool {
+        let mut i = from;
+
+        while i < to {
+            let t = i % WORD_BIT_SIZE;
+            let l = (WORD_BIT_SIZE - t).min(to - i);
+            let msk = (WORD_MAX >> (WORD_BIT_SIZE - l)) << t;
+
+            if self.m[i / WORD_BIT_SIZE] & msk != msk {
+                return false;
+            }
+
+            i += l;
+        }
+
+        true
+    }
+
     /// Round n positions, return true if exponent is to be incremented.
     /// If `check_roundable` is true on input, the function verifies whether the mantissa is roundable, given it contains `s` correct digits.
     /// If `check_roundable` is set to false on return, in any case it means rounding was successful.
@@ -843,6 +997,29 @@ impl Mantissa {
         // and checks for roundability at the same time.
 
         if rm == RoundingMode::None {
+            if *check_roundable && n > 0 && n < self.max_bit_len() {
+                // faithful rounding: if all correct discarded bits are ones,
+                // the exact value can be greater than the truncated value plus ulp,
+                // and rounding upwards is faithful in any case
+                let from = self.max_bit_len().saturating_sub(s);
+
+                if from >= n {
+                    return false; // self is not roundable
+                }
+
+                if self.bits_all_ones(from, n) {
+                    *check_roundable = false;
+                    return self.round_mantissa(
+                        n,
+                        RoundingMode::FromZero,
+                        is_positive,
+                        &mut false,
+                        s,
+                        inexact,
+                    );
+                }
+            }
+
             *check_roundable = false;
 
             // inexact?
@@ -862,7 +1039,10 @@ impl Mantissa {
             let mut c = false;
             let mut cc = (n - (self.max_bit_len() - s)) / WORD_BIT_SIZE; // num of roundable significant words
 
-            if rm == RoundingMode::ToEven || rm == RoundingMode::ToOdd {
+            if rm == RoundingMode::ToEven
+                || rm == RoundingMode::ToOdd
+                || rm == RoundingMode::ToNearestAway
+            {
                 let n = n - 1;
                 let mut rem_zero = true;
                 let mut td = 0; // either all bits set, or zero (default)
@@ -945,6 +1125,12 @@ impl Mantissa {
                             c = true;
                         }
                     }
+                    RoundingMode::ToNearestAway => {
+                        if gt1 || eq1 {
+                            // add 1
+                            c = true;
+                        }
+                    }
                     _ => unreachable!(),
                 };
 
@@ -1079,7 +1265,7 @@ impl Mantissa {
         let sz = Self::bit_len_to_word_len(p);
         let orig_len = self.m.len();
         if sz < orig_len {
-            self.m.trunc_to(p);
+            self.m.trunc_to(sz * WORD_BIT_SIZE);
             let nn = (orig_len - sz) * WORD_BIT_SIZE;
             if self.n >= nn {
                 self.n -= nn;
@@ -1087,7 +1273,7 @@ impl Mantissa {
                 self.n = 0;
             }
         } else if sz > orig_len {
-            self.m.try_extend(p)?;
+            self.m.try_extend(sz * WORD_BIT_SIZE)?;
             if self.n != 0 {
                 self.n += (sz - orig_len) * WORD_BIT_SIZE;
             }
@@ -1095,6 +1281,33 @@ impl Mantissa {
         Ok(())
     }
 
+    /// Returns the number of bytes allocated for the mantissa.
+    pub fn allocated_bytes(&self) -> usize {
+        self.m.capacity() * core::mem::size_of::<Word>()
+    }
+
+    /// Releases the memory which is not used by the mantissa.
+    pub fn shrink_to_fit(&mut self) {
+        self.m.shrink_to_fit();
+    }
+
+    /// Removes the least significant words containing zeroes keeping at least one word, and releases the unused memory.
+    pub fn compact(&mut self) {
+        let n = self
+            .m
+            .iter()
+            .take_while(|w| **w == 0)
+            .count()
+            .min(self.m.len().saturating_sub(1));
+
+        if n > 0 {
+            self.m.trunc_to((self.m.len() - n) * WORD_BIT_SIZE);
+            self.n = self.n.saturating_sub(n * WORD_BIT_SIZE);
+        }
+
+        self.m.shrink_to_fit();
+    }
+
     pub fn most_significant_word(&self) -> Word {
         if self.n > 0 {
             self.m[(self.n - 1) / WORD_BIT_SIZE]
@@ -1119,6 +1332,34 @@ impl Mantissa {
         Ok(ret)
     }
 
+    #[cfg(feature = "random")]
+    /// Returns true with probability equal to the value of the `n` least significant bits divided by 2^n.
+    /// Random numbers are taken from `rng`.
+    pub fn stochastic_round_away<R: rand::Rng + ?Sized>(&self, n: usize, rng: &mut R) -> bool {
+        debug_assert!(n <= self.max_bit_len());
+
+        let i = n / WORD_BIT_SIZE;
+        let t = n % WORD_BIT_SIZE;
+
+        // compare discarded bits with random bits starting from the most significant one
+        if t > 0 {
+            let d = self.m[i] & (WORD_MAX >> (WORD_BIT_SIZE - t));
+            let r = rng.gen::<Word>() >> (WORD_BIT_SIZE - t);
+            if r != d {
+                return r < d;
+            }
+        }
+
+        for &d in self.m[..i].iter().rev() {
+            let r = rng.gen::<Word>();
+            if r != d {
+                return r < d;
+            }
+        }
+
+        false
+    }
+
     /// Clones the mantissa.
     pub fn clone(&self) -> Result<Self, Error> {
         let mut m = Self::reserve_new(self.m.len())?;
@@ -1203,7 +1444,7 @@ impl Mantissa {
         if r_sticky {
             *inexact |= true;
 
-            if rm as u32 & 0b1100000 != 0 {
+            if rm as u32 & 0b11100000 != 0 {
                 m3.m[0] |= 1;
             } else if rm == RoundingMode::FromZero
                 || (is_positive && rm == RoundingMode::Up)
@@ -1272,7 +1513,7 @@ impl Mantissa {
         if r_sticky {
             *inexact |= true;
 
-            if rm as u32 & 0b1100000 != 0 {
+            if rm as u32 & 0b11100000 != 0 {
                 m3.m[0] |= 1;
             } else if rm == RoundingMode::FromZero
                 || (is_positive && rm == RoundingMode::Up)
@@ -1307,3 +1548,33 @@ impl Mantissa {
         Ok((e_shift, m3))
     }
 }
+
+impl Debug for Mantissa {
+    // Words are printed in hex starting from the most significant one.
+    // For long mantissas only the leading and the trailing words are printed.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        const EDGE_WORDS: usize = 2;
+        const W: usize = WORD_BIT_SIZE / 4 + 2;
+
+        let mut list = f.debug_list();
+        let l = self.m.len();
+
+        if l > 2 * EDGE_WORDS + 1 {
+            for d in self.m[l - EDGE_WORDS..].iter().rev() {
+                list.entry(&format_args!("{:#0w$x}", d, w = W));
+            }
+
+            list.entry(&format_args!("... {} words ...", l - 2 * EDGE_WORDS));
+
+            for d in self.m[..EDGE_WORDS].iter().rev() {
+                list.entry(&format_args!("{:#0w$x}", d, w = W));
+            }
+        } else {
+            for d in self.m.iter().rev() {
+                list.entry(&format_args!("{:#0w$x}", d, w = W));
+            }
+        }
+
+        list.finish()
+    }
+}
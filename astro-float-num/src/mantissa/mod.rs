@@ -11,3 +11,4 @@ mod toom3;
 mod util;
 
 pub use mantissa::Mantissa;
+pub use mul::MulThresholds;
@@ -6,7 +6,85 @@ use crate::defs::DoubleWord;
 use crate::defs::Error;
 use crate::defs::Word;
 use crate::defs::WORD_BIT_SIZE;
+use crate::defs::WORD_MAX;
 use crate::mantissa::Mantissa;
+// Default thresholds in words for switching between multiplication algorithms.
+const TOOM2_THRESHOLD: usize = 32;
+const TOOM3_THRESHOLD: usize = 220;
+const FFT_THRESHOLD: usize = 5400;
+const BALANCING_THRESHOLD: usize = 70;
+
+/// Thresholds in words for switching between multiplication algorithms.
+///
+/// Numbers are multiplied using the schoolbook algorithm if either of the mantissas
+/// has at most `toom2` words, Toom-2 (Karatsuba) if either of the mantissas has at most `toom3` words,
+/// Toom-3 if both mantissas have at most `fft` words, and FFT otherwise.
+/// Setting a threshold to `usize::MAX` forbids the algorithms that follow it,
+/// and setting it to the smallest allowed value forces them for all but the shortest mantissas.
+///
+/// The thresholds are used by the multiplication of a context, see [crate::ctx::Context::set_mul_thresholds].
+/// Other operations use the default thresholds.
+///
+/// ## Examples
+///
+/// ```
+/// use astro_float_num::ctx::Context;
+/// use astro_float_num::{BigFloat, Consts, MulThresholds, RoundingMode};
+///
+/// let mut ctx = Context::new(1024, RoundingMode::ToEven, Consts::new().unwrap());
+///
+/// // forbid FFT multiplication
+/// ctx.set_mul_thresholds(MulThresholds {
+///     fft: usize::MAX,
+///     ..MulThresholds::default()
+/// });
+/// assert_eq!(ctx.mul_thresholds().fft, usize::MAX);
+///
+/// let a = BigFloat::from_word(3, 1024);
+/// assert_eq!(ctx.mul(&a, &a), BigFloat::from_word(9, 1024));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MulThresholds {
+    /// Mantissas of at most this size are multiplied using the schoolbook algorithm.
+    pub toom2: usize,
+
+    /// Mantissas of at most this size are multiplied using the Toom-2 algorithm.
+    pub toom3: usize,
+
+    /// Mantissas larger than this size are multiplied using FFT.
+    pub fft: usize,
+
+    /// If the shorter mantissa is larger than this size, and the longer mantissa is at least two times longer,
+    /// the longer mantissa is split in parts of the size of the shorter mantissa, and the parts are multiplied separately.
+    pub balancing: usize,
+}
+
+impl MulThresholds {
+    /// The smallest allowed value of the thresholds `toom2`, `toom3`, and `fft`.
+    pub const MIN: usize = 4;
+
+    // Returns the thresholds with `toom2`, `toom3`, and `fft` not smaller than `MIN`.
+    pub(crate) fn clamped(&self) -> Self {
+        MulThresholds {
+            toom2: self.toom2.max(Self::MIN),
+            toom3: self.toom3.max(Self::MIN),
+            fft: self.fft.max(Self::MIN),
+            balancing: self.balancing,
+        }
+    }
+}
+
+impl Default for MulThresholds {
+    /// Returns the default thresholds for the target platform.
+    fn default() -> Self {
+        MulThresholds {
+            toom2: TOOM2_THRESHOLD,
+            toom3: TOOM3_THRESHOLD,
+            fft: FFT_THRESHOLD,
+            balancing: BALANCING_THRESHOLD,
+        }
+    }
+}
 
 impl Mantissa {
     pub(super) fn mul_basic(m1: &[Word], m2: &[Word], m3: &mut [Word]) {
@@ -29,26 +107,41 @@ impl Mantissa {
         }
     }
 
-    fn mul_slices(m1: &[Word], m2: &[Word], m3: &mut [Word]) -> Result<(), Error> {
+    fn mul_slices(
+        m1: &[Word],
+        m2: &[Word],
+        m3: &mut [Word],
+        t: &MulThresholds,
+    ) -> Result<(), Error> {
         debug_assert!(m1.len() <= m2.len());
 
-        if m1.len() <= 32 || m2.len() <= 32 {
+        if m1.len() <= t.toom2 || m2.len() <= t.toom2 {
             Self::mul_basic(m1, m2, m3);
-        } else if m1.len() <= 220 || m2.len() <= 220 {
-            Self::toom2(m1, m2, m3)?;
-        } else if m1.len() <= 5400 && m2.len() <= 5400 {
-            Self::toom3(m1, m2, m3)?;
+        } else if m1.len() <= t.toom3 || m2.len() <= t.toom3 {
+            Self::toom2(m1, m2, m3, t)?;
+        } else if m1.len() <= t.fft && m2.len() <= t.fft {
+            Self::toom3(m1, m2, m3, t)?;
         } else {
-            Mantissa::fft_mul(m1, m2, m3)?;
+            Mantissa::fft_mul(m1, m2, m3, t)?;
         }
         Ok(())
     }
 
     // general case multiplication
-    pub(super) fn mul_unbalanced(m1: &[Word], m2: &[Word], m3: &mut [Word]) -> Result<(), Error> {
+    pub(crate) fn mul_unbalanced(m1: &[Word], m2: &[Word], m3: &mut [Word]) -> Result<(), Error> {
+        Self::mul_unbalanced_with(m1, m2, m3, &MulThresholds::default())
+    }
+
+    // general case multiplication using thresholds `t`
+    pub(super) fn mul_unbalanced_with(
+        m1: &[Word],
+        m2: &[Word],
+        m3: &mut [Word],
+        t: &MulThresholds,
+    ) -> Result<(), Error> {
         let (sm, lg) = if m1.len() < m2.len() { (m1, m2) } else { (m2, m1) };
 
-        if lg.len() / 2 >= sm.len() && sm.len() > 70 {
+        if lg.len() / 2 >= sm.len() && sm.len() > t.balancing {
             // balancing
 
             let mut buf = WordBuf::new(2 * sm.len())?;
@@ -60,7 +153,7 @@ impl Mantissa {
                 while lb < lg.len() {
                     ub = if lb + sm.len() <= lg.len() { lb + sm.len() } else { lg.len() };
 
-                    Self::mul_slices(&lg[lb..ub], sm, &mut buf)?;
+                    Self::mul_slices(&lg[lb..ub], sm, &mut buf, t)?;
 
                     let src = SliceWithSign::new(&buf[..ub - lb + sm.len()], 1);
                     let mut dst = SliceWithSign::new_mut(&mut m3[lb..], 1);
@@ -86,10 +179,48 @@ impl Mantissa {
 
             Ok(())
         } else {
-            Self::mul_slices(sm, lg, m3)
+            Self::mul_slices(sm, lg, m3, t)
         }
     }
 
+    // Short product for operands of mismatched lengths: if only `p` words of the result are needed,
+    // the low words of the longer operand are dropped, since they contribute to the sticky part of the product only.
+    // Returns None if the short product can't be used, or if it can't guarantee correct rounding.
+    pub(super) fn mul_truncated(
+        m1: &[Word],
+        m2: &[Word],
+        p: usize,
+        full_prec: bool,
+        t: &MulThresholds,
+    ) -> Result<Option<WordBuf>, Error> {
+        let (sm, lg) = if m1.len() < m2.len() { (m1, m2) } else { (m2, m1) };
+
+        // p words of the result, one word for normalization, and one guard word.
+        let keep = p + 2;
+
+        if full_prec || lg.len() <= keep {
+            return Ok(None);
+        }
+
+        let k = lg.len() - keep;
+
+        let mut m3 = WordBuf::new(keep + sm.len())?;
+        Self::mul_unbalanced_with(&lg[k..], sm, &mut m3, t)?;
+
+        if lg[..k].iter().any(|&w| w != 0) {
+            // The dropped part adds less than WORD_BASE^sm.len() to m3.
+            // If the carry can propagate beyond the guard word, rounding can't be determined.
+            if m3[sm.len()] == WORD_MAX {
+                return Ok(None);
+            }
+
+            // The result is strictly greater than the truncated product.
+            m3[0] |= 1;
+        }
+
+        Ok(Some(m3))
+    }
+
     // short multiplication
     #[allow(dead_code)] // TODO: can it be faster than mul_unbalanced by more than 90% ?
     pub(super) fn mul_short(m1: &[Word], m2: &[Word], m3: &mut [Word]) -> Result<(), Error> {
@@ -159,11 +290,46 @@ mod tests {
         for _ in 0..1000 {
             let v = random_slice(sz1, sz2);
             Mantissa::mul_unbalanced(&f, &v, &mut ret1).unwrap();
-            Mantissa::mul_slices(&f, &v, &mut ret2).unwrap();
+            Mantissa::mul_slices(&f, &v, &mut ret2, &MulThresholds::default()).unwrap();
             assert!(ret1[..] == ret2[..]);
         }
     }
 
+    #[test]
+    fn test_mul_thresholds() {
+        let min = MulThresholds::MIN;
+        let forced = [(min, usize::MAX, usize::MAX), (min, min, usize::MAX), (min, min, min)];
+
+        for (toom2, toom3, fft) in forced {
+            let t = MulThresholds {
+                toom2,
+                toom3,
+                fft,
+                balancing: random::<usize>() % 20,
+            };
+
+            for _ in 0..100 {
+                let v1 = random_slice(1, 400);
+                let v2 = random_slice(1, 400);
+                let mut ret1 = WordBuf::new(v1.len() + v2.len()).unwrap();
+                let mut ret2 = WordBuf::new(v1.len() + v2.len()).unwrap();
+                Mantissa::mul_unbalanced_with(&v1, &v2, &mut ret1, &t).unwrap();
+                Mantissa::mul_basic(&v1, &v2, &mut ret2);
+                assert!(ret1[..] == ret2[..]);
+            }
+        }
+
+        // values below the minimum are clamped
+        let t = MulThresholds {
+            toom2: 0,
+            toom3: 0,
+            fft: 0,
+            balancing: 0,
+        }
+        .clamped();
+        assert_eq!((t.toom2, t.toom3, t.fft, t.balancing), (min, min, min, 0));
+    }
+
     #[ignore]
     #[test]
     fn test_mul_short() {
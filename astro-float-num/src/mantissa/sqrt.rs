@@ -1,4 +1,8 @@
 //! Square root.
+//!
+//! The square root and the remainder are computed recursively with the Karatsuba square root:
+//! the root of the upper half of the input is computed first, and then it is refined with a single division
+//! of size n/2 and a single squaring of size n/4.
 
 use crate::common::buf::WordBuf;
 use crate::common::int::SliceWithSign;
@@ -410,4 +414,30 @@ mod tests {
             println!("sqrt {}", time.as_millis());
         }
     }
+
+    #[ignore]
+    #[test]
+    #[cfg(feature = "std")]
+    fn sqrt_large_perf() {
+        for sz in [1000, 4000, 16000] {
+            let s1 = random_normalized_slice(2 * sz, 2 * sz);
+            let s2 = random_normalized_slice(sz, sz);
+            let mut ret = WordBuf::new(2 * sz).unwrap();
+
+            let start_time = std::time::Instant::now();
+            Mantissa::mul_unbalanced(&s2, &s2, &mut ret).unwrap();
+            let mul_time = start_time.elapsed();
+
+            let start_time = std::time::Instant::now();
+            Mantissa::sqrt_rem(&s1).unwrap();
+            let sqrt_time = start_time.elapsed();
+
+            println!(
+                "{} words: mul {} us, sqrt {} us",
+                sz,
+                mul_time.as_micros(),
+                sqrt_time.as_micros()
+            );
+        }
+    }
 }
@@ -7,6 +7,7 @@ use crate::defs::Error;
 use crate::defs::Word;
 use crate::defs::WORD_BASE;
 use crate::mantissa::Mantissa;
+use crate::mantissa::MulThresholds;
 use itertools::izip;
 
 impl Mantissa {
@@ -107,7 +108,12 @@ impl Mantissa {
         }
     }
 
-    pub(super) fn toom2(m1: &[Word], m2: &[Word], m3: &mut [Word]) -> Result<(), Error> {
+    pub(super) fn toom2(
+        m1: &[Word],
+        m2: &[Word],
+        m3: &mut [Word],
+        t: &MulThresholds,
+    ) -> Result<(), Error> {
         let n = (m1.len().min(m2.len()) + 1) >> 1;
         let n2 = n << 1;
 
@@ -136,9 +142,9 @@ impl Mantissa {
         Self::add_slices(m11, m12, x1);
         Self::add_slices(m21, m22, x2);
 
-        Self::mul_unbalanced(x1, x2, z2buf)?;
-        Self::mul_unbalanced(m11, m21, m31)?;
-        Self::mul_unbalanced(m12, m22, m32)?;
+        Self::mul_unbalanced_with(x1, x2, z2buf, t)?;
+        Self::mul_unbalanced_with(m11, m21, m31, t)?;
+        Self::mul_unbalanced_with(m12, m22, m32, t)?;
 
         Self::paired_sub(m31, m32, z2buf);
         Self::add_assign_slices(&mut m3[n..], z2buf);
@@ -166,7 +172,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 36];
-        Mantissa::toom2(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::toom2(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -177,7 +183,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 15];
-        Mantissa::toom2(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::toom2(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -188,7 +194,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 13];
-        Mantissa::toom2(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::toom2(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -199,7 +205,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 15];
-        Mantissa::toom2(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::toom2(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -210,7 +216,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 15];
-        Mantissa::toom2(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::toom2(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -228,7 +234,7 @@ mod tests {
 
             let mut ret_s = Vec::new();
             ret_s.resize(s1.len() + s2.len(), 0);
-            Mantissa::toom2(&s1, &s2, &mut ret_s).unwrap();
+            Mantissa::toom2(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
             assert!(ret_s == ref_s);
         }
@@ -252,7 +258,7 @@ mod tests {
 
             let start_time = std::time::Instant::now();
             for ni in n.iter() {
-                Mantissa::toom2(ni, &f, &mut ret).unwrap();
+                Mantissa::toom2(ni, &f, &mut ret, &MulThresholds::default()).unwrap();
             }
             let time = start_time.elapsed();
             println!("toom2 {}", time.as_millis());
@@ -6,6 +6,7 @@ use crate::common::util::shift_slice_left_copy;
 use crate::defs::Error;
 use crate::defs::Word;
 use crate::mantissa::Mantissa;
+use crate::mantissa::MulThresholds;
 
 impl Mantissa {
     fn toom3_get_splits(m: &[Word], l: usize) -> (SliceWithSign, SliceWithSign, SliceWithSign) {
@@ -57,7 +58,12 @@ impl Mantissa {
     // Toom-3 multiplication.
     // d1 must contain input number + have reserve of d2.len() positions in addition for the output.
     // The result is placed in d1, and the sign is returned.
-    pub(super) fn toom3(d1: &[Word], d2: &[Word], d3: &mut [Word]) -> Result<(), Error> {
+    pub(super) fn toom3(
+        d1: &[Word],
+        d2: &[Word],
+        d3: &mut [Word],
+        t: &MulThresholds,
+    ) -> Result<(), Error> {
         let l = (d1.len().max(d2.len()) + 2) / 3;
 
         let mut buf = WordBuf::new(25 * (l + 1))?;
@@ -107,11 +113,11 @@ impl Mantissa {
         debug_assert!(p2.len() + q2.len() == s2.len());
         debug_assert!(p3.len() + q3.len() == s3.len());
 
-        Self::mul_unbalanced(&p0, &q0, &mut s0)?;
-        Self::mul_unbalanced(&p1, &q1, &mut s1)?;
-        Self::mul_unbalanced(&p2, &q2, &mut s2)?;
-        Self::mul_unbalanced(&p3, &q3, &mut s3)?;
-        Self::mul_unbalanced(&p4, &q4, &mut s4)?;
+        Self::mul_unbalanced_with(&p0, &q0, &mut s0, t)?;
+        Self::mul_unbalanced_with(&p1, &q1, &mut s1, t)?;
+        Self::mul_unbalanced_with(&p2, &q2, &mut s2, t)?;
+        Self::mul_unbalanced_with(&p3, &q3, &mut s3, t)?;
+        Self::mul_unbalanced_with(&p4, &q4, &mut s4, t)?;
 
         s1.set_sign(p1.sign() * q1.sign());
         s2.set_sign(p2.sign() * q2.sign());
@@ -201,7 +207,7 @@ mod tests {
 
             let start_time = std::time::Instant::now();
             for ni in &n {
-                Mantissa::toom3(ni, &f, &mut ret).unwrap();
+                Mantissa::toom3(ni, &f, &mut ret, &MulThresholds::default()).unwrap();
             }
             let time = start_time.elapsed();
             println!("toom3 {}", time.as_millis());
@@ -224,7 +230,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 36];
-        Mantissa::toom3(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::toom3(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -235,7 +241,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 15];
-        Mantissa::toom3(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::toom3(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -246,7 +252,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 13];
-        Mantissa::toom3(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::toom3(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -257,7 +263,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 15];
-        Mantissa::toom3(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::toom3(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -268,7 +274,7 @@ mod tests {
         mul(&s1, &s2, &mut ref_s);
 
         let mut ret_s = [0; 15];
-        Mantissa::toom3(&s1, &s2, &mut ret_s).unwrap();
+        Mantissa::toom3(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
         assert!(ret_s == ref_s);
 
@@ -286,7 +292,7 @@ mod tests {
 
             let mut ret_s = Vec::new();
             ret_s.resize(s1.len() + s2.len(), 0);
-            Mantissa::toom3(&s1, &s2, &mut ret_s).unwrap();
+            Mantissa::toom3(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
 
             assert!(ret_s == ref_s);
         }
@@ -305,7 +311,7 @@ mod tests {
 
             let mut ret_s = Vec::new();
             ret_s.resize(s1.len() + s2.len(), 0);
-            Mantissa::toom3(&s1, &s2, &mut ret_s).unwrap();
+            Mantissa::toom3(&s1, &s2, &mut ret_s, &MulThresholds::default()).unwrap();
             assert!(ret_s == ref_s);
         }
     }
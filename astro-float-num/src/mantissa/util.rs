@@ -1,10 +1,9 @@
 //! Auxiliary structures.
 
-use crate::{
-    common::{buf::WordBuf, util::log2_floor},
-    defs::WORD_SIGNIFICANT_BIT,
-    Error, Word, WORD_BIT_SIZE,
-};
+use crate::{common::buf::WordBuf, defs::WORD_SIGNIFICANT_BIT, Error, Word, WORD_BIT_SIZE};
+
+#[cfg(not(feature = "std"))]
+use crate::common::util::log2_floor;
 
 /// Length of the slice extended by extra size.
 pub struct ExtendedSlice<T, V>
@@ -256,6 +255,55 @@ pub fn root_estimate(m: &[Word], n: usize) -> Result<WordBuf, Error> {
  */
 
 /// Prepare an initial value for calculating n-root of an argument m.
+/// The value is not smaller than the integer part of the root.
+/// The n-root of the leading bits of m is computed in f64, and the exponent is handled separately,
+/// so the initial value has about 53/n correct bits.
+#[cfg(feature = "std")]
+pub fn root_estimate(m: &[Word], n: usize) -> Result<WordBuf, Error> {
+    debug_assert!((2..=3).contains(&n));
+
+    let top = match m.iter().rposition(|&w| w != 0) {
+        Some(top) => top,
+        None => {
+            let mut buf = WordBuf::new(1)?;
+            buf[0] = 1;
+            return Ok(buf);
+        }
+    };
+
+    let bits = (top + 1) * WORD_BIT_SIZE - m[top].leading_zeros() as usize;
+
+    // m < (t + 1) * 2^(n*k), where t has at most 52 + n bits.
+    let k = bits.saturating_sub(53) / n;
+    let shift = n * k;
+
+    let mut t: u128 = 0;
+    for &w in m[shift / WORD_BIT_SIZE..=top].iter().rev() {
+        t = (t << WORD_BIT_SIZE) | w as u128;
+    }
+    let t = (t >> (shift % WORD_BIT_SIZE)) as u64;
+
+    let r = if n == 2 { (t as f64).sqrt() } else { (t as f64).cbrt() };
+
+    // root(t + 1) < root(t) + 1, and the error of r is a few ulp.
+    let r = (r * (1.0 + 64.0 * f64::EPSILON)) as u64 + 2;
+
+    let rbits = 64 - r.leading_zeros() as usize;
+    let mut buf = WordBuf::new((rbits + k).div_ceil(WORD_BIT_SIZE))?;
+    buf.fill(0);
+
+    // r << k
+    let idx = k / WORD_BIT_SIZE;
+    let r = (r as u128) << (k % WORD_BIT_SIZE);
+    for (i, d) in buf[idx..].iter_mut().enumerate() {
+        *d = (r >> (i * WORD_BIT_SIZE)) as Word;
+    }
+
+    Ok(buf)
+}
+
+/// Prepare an initial value for calculating n-root of an argument m.
+#[cfg(not(feature = "std"))]
 pub fn root_estimate(m: &[Word], n: usize) -> Result<WordBuf, Error> {
     let mut buf = WordBuf::new(m.len() / n + 1)?;
 
@@ -268,3 +316,74 @@ pub fn root_estimate(m: &[Word], n: usize) -> Result<WordBuf, Error> {
     }
     Ok(buf)
 }
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+
+    use super::*;
+    use crate::common::int::SliceWithSign;
+    use crate::defs::WORD_MAX;
+    use crate::mantissa::Mantissa;
+    use rand::random;
+
+    fn bit_len(s: &[Word]) -> usize {
+        match s.iter().rposition(|&w| w != 0) {
+            Some(i) => (i + 1) * WORD_BIT_SIZE - s[i].leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    #[test]
+    fn test_root_estimate() {
+        const MAX_BUF: usize = 10;
+
+        let mut tests: Vec<Vec<Word>> = Vec::new();
+
+        for l in 1..MAX_BUF {
+            tests.push([WORD_MAX].repeat(l));
+            tests.push([1].repeat(l));
+
+            let mut v = [0].repeat(l);
+            *v.last_mut().unwrap() = WORD_SIGNIFICANT_BIT;
+            tests.push(v);
+        }
+
+        for _ in 0..1000 {
+            let l = random::<usize>() % (MAX_BUF - 1) + 1;
+            let mut v: Vec<Word> = (0..l).map(|_| random::<Word>()).collect();
+            let top = v.last_mut().unwrap();
+            *top = (*top >> (random::<usize>() % WORD_BIT_SIZE)).max(1);
+            tests.push(v);
+        }
+
+        for m in tests.iter() {
+            for n in 2..=3 {
+                let root = if n == 2 {
+                    Mantissa::sqrt_rem(m).unwrap().0
+                } else {
+                    let mut mb = WordBuf::new(m.len()).unwrap();
+                    mb.copy_from_slice(m);
+                    Mantissa::cbrt_rem(mb).unwrap().0
+                };
+
+                let est = root_estimate(m, n).unwrap();
+
+                let mut e = [0; MAX_BUF];
+                let mut r = [0; MAX_BUF];
+                e[..est.len()].copy_from_slice(&est);
+                r[..root.len()].copy_from_slice(&root);
+
+                let mut es = SliceWithSign::new_mut(&mut e, 1);
+                let rs = SliceWithSign::new(&r, 1);
+
+                assert!(es.cmp(&rs) >= 0, "{:?} {} {:?} {:?}", m, n, est, root);
+
+                // the estimate has about 53/n correct bits
+                es.sub_assign(&rs);
+                let d = bit_len(&e);
+                assert!(d <= 2 || d + 52 / n - 4 <= bit_len(&r), "{:?} {}", m, n);
+            }
+        }
+    }
+}
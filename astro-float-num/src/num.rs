@@ -3,8 +3,10 @@
 use crate::common::consts::ONE;
 use crate::common::util::count_leading_zeroes_skip_first;
 use crate::common::util::round_p;
+use crate::common::util::shift_slice_right;
 use crate::defs::Error;
 use crate::defs::Exponent;
+use crate::defs::Radix;
 use crate::defs::RoundingMode;
 use crate::defs::Sign;
 use crate::defs::SignedWord;
@@ -14,9 +16,16 @@ use crate::defs::EXPONENT_MIN;
 use crate::defs::WORD_BIT_SIZE;
 use crate::defs::WORD_SIGNIFICANT_BIT;
 use crate::mantissa::Mantissa;
+use crate::mantissa::MulThresholds;
+use crate::uint::BigUInt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 /// A finite floating point number with mantissa of an arbitrary size, an exponent, and the sign.
-#[derive(Debug, Hash)]
+#[derive(Hash)]
 pub(crate) struct BigFloatNumber {
     e: Exponent,
     s: Sign,
@@ -34,7 +43,7 @@ impl BigFloatNumber {
         }
     }
 
-    /// Returns a new number with value of 0 and precision of `p` bits. Precision is rounded upwards to the word size.
+    /// Returns a new number with value of 0 and precision of `p` bits. Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -43,7 +52,7 @@ impl BigFloatNumber {
     pub fn new(p: usize) -> Result<Self, Error> {
         Self::p_assertion(p)?;
         Ok(BigFloatNumber {
-            m: Mantissa::new(p)?,
+            m: Mantissa::new(round_p(p))?,
             e: 0,
             s: Sign::Pos,
             inexact: false,
@@ -51,7 +60,7 @@ impl BigFloatNumber {
     }
 
     /// Returns a new number with value of 0, precision of `p` bits, sign `s`, and marked as inexact if `inexact` is true.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -60,7 +69,7 @@ impl BigFloatNumber {
     pub fn new2(p: usize, s: Sign, inexact: bool) -> Result<Self, Error> {
         Self::p_assertion(p)?;
         Ok(BigFloatNumber {
-            m: Mantissa::new(p)?,
+            m: Mantissa::new(round_p(p))?,
             e: 0,
             s,
             inexact,
@@ -69,7 +78,7 @@ impl BigFloatNumber {
 
     /// Returns the maximum value for the specified precision `p`: all bits of the mantissa are set to 1,
     /// the exponent has the maximum possible value, and the sign is positive.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -78,14 +87,14 @@ impl BigFloatNumber {
     pub fn max_value(p: usize) -> Result<Self, Error> {
         Self::p_assertion(p)?;
         Ok(BigFloatNumber {
-            m: Mantissa::oned_mantissa(p)?,
+            m: Mantissa::oned_mantissa(round_p(p))?,
             e: EXPONENT_MAX,
             s: Sign::Pos,
             inexact: false,
         })
     }
 
-    /// Returns the minimum value for the specified precision `p`: all bits of the mantissa are set to 1, the exponent has the maximum possible value, and the sign is negative. Precision is rounded upwards to the word size.
+    /// Returns the minimum value for the specified precision `p`: all bits of the mantissa are set to 1, the exponent has the maximum possible value, and the sign is negative. Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -94,7 +103,7 @@ impl BigFloatNumber {
     pub fn min_value(p: usize) -> Result<Self, Error> {
         Self::p_assertion(p)?;
         Ok(BigFloatNumber {
-            m: Mantissa::oned_mantissa(p)?,
+            m: Mantissa::oned_mantissa(round_p(p))?,
             e: EXPONENT_MAX,
             s: Sign::Neg,
             inexact: false,
@@ -104,7 +113,7 @@ impl BigFloatNumber {
     /// Returns the minimum positive subnormal value for the specified precision `p`:
     /// only the least significant bit of the mantissa is set to 1, the exponent has
     /// the minimum possible value, and the sign is positive.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -113,7 +122,7 @@ impl BigFloatNumber {
     pub fn min_positive(p: usize) -> Result<Self, Error> {
         Self::p_assertion(p)?;
         Ok(BigFloatNumber {
-            m: Mantissa::min(p)?,
+            m: Mantissa::min(round_p(p))?,
             e: EXPONENT_MIN,
             s: Sign::Pos,
             inexact: false,
@@ -123,7 +132,7 @@ impl BigFloatNumber {
     /// Returns the minimum positive normal value for the specified precision `p`:
     /// only the most significant bit of the mantissa is set to 1, the exponent has
     /// the minimum possible value, and the sign is positive.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -132,14 +141,14 @@ impl BigFloatNumber {
     pub fn min_positive_normal(p: usize) -> Result<Self, Error> {
         Self::p_assertion(p)?;
         Ok(BigFloatNumber {
-            m: Mantissa::from_word(p, WORD_SIGNIFICANT_BIT)?,
+            m: Mantissa::from_word(round_p(p), WORD_SIGNIFICANT_BIT)?,
             e: EXPONENT_MIN,
             s: Sign::Pos,
             inexact: false,
         })
     }
 
-    /// Returns a new number with value `d` and the precision `p`. Precision is rounded upwards to the word size.
+    /// Returns a new number with value `d` and the precision `p`. Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -157,7 +166,7 @@ impl BigFloatNumber {
                 shift += 1;
             }
             Ok(BigFloatNumber {
-                m: Mantissa::from_word(p, d)?,
+                m: Mantissa::from_word(round_p(p), d)?,
                 e: (WORD_BIT_SIZE - shift) as Exponent,
                 s: Sign::Pos,
                 inexact: false,
@@ -166,12 +175,12 @@ impl BigFloatNumber {
     }
 
     pub(crate) fn from_u64_internal(d: u64, p: usize) -> Result<Self, Error> {
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(word32))]
         {
             Self::from_word(d, p)
         }
 
-        #[cfg(target_arch = "x86")]
+        #[cfg(word32)]
         {
             Self::p_assertion(p)?;
 
@@ -190,7 +199,7 @@ impl BigFloatNumber {
                         return Err(Error::InvalidArgument);
                     } else {
                         Ok(BigFloatNumber {
-                            m: Mantissa::from_word(p, words[1])?,
+                            m: Mantissa::from_word(round_p(p), words[1])?,
                             e: (64 - shift) as Exponent,
                             s: Sign::Pos,
                             inexact: false,
@@ -198,7 +207,10 @@ impl BigFloatNumber {
                     }
                 } else {
                     Ok(BigFloatNumber {
-                        m: Mantissa::from_words(p, &[d as Word, (d >> WORD_BIT_SIZE) as Word])?,
+                        m: Mantissa::from_words(
+                            round_p(p),
+                            &[d as Word, (d >> WORD_BIT_SIZE) as Word],
+                        )?,
                         e: (64 - shift) as Exponent,
                         s: Sign::Pos,
                         inexact: false,
@@ -220,7 +232,7 @@ impl BigFloatNumber {
     }
 
     /// Adds `d2` to `self` and returns the result of the operation with precision `p` rounded according to `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -233,7 +245,7 @@ impl BigFloatNumber {
     }
 
     /// Subtracts `d2` from `self` and returns the result of the operation with precision `p` rounded according to `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -272,7 +284,7 @@ impl BigFloatNumber {
     }
 
     /// Multiplies `d2` by `self` and returns the result of the operation with precision `p` rounded according to `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -281,7 +293,19 @@ impl BigFloatNumber {
     ///  - InvalidArgument: the precision is incorrect.
     #[inline]
     pub fn mul(&self, d2: &Self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
-        self.mul_general_case(d2, p, rm, false)
+        self.mul_general_case(d2, p, rm, false, &MulThresholds::default())
+    }
+
+    /// Same as `mul`, but the mantissas are multiplied using thresholds `t` for switching between multiplication algorithms.
+    #[inline]
+    pub(crate) fn mul_with_thresholds(
+        &self,
+        d2: &Self,
+        p: usize,
+        rm: RoundingMode,
+        t: &MulThresholds,
+    ) -> Result<Self, Error> {
+        self.mul_general_case(d2, p, rm, false, t)
     }
 
     /// Multiplies `d2` by `self` and returns the result of the operation.
@@ -294,7 +318,32 @@ impl BigFloatNumber {
     ///  - MemoryAllocation: failed to allocate memory for mantissa.
     #[inline]
     pub fn mul_full_prec(&self, d2: &Self) -> Result<Self, Error> {
-        self.mul_general_case(d2, 0, RoundingMode::None, true)
+        self.mul_general_case(d2, 0, RoundingMode::None, true, &MulThresholds::default())
+    }
+
+    /// Computes `self`·`b` + `c`·`d` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// The products are computed with full precision, so the result is rounded only once.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn mul_add_mul(
+        &self,
+        b: &Self,
+        c: &Self,
+        d: &Self,
+        p: usize,
+        rm: RoundingMode,
+    ) -> Result<Self, Error> {
+        Self::p_assertion(round_p(p))?;
+
+        let ab = self.mul_full_prec(b)?;
+        let cd = c.mul_full_prec(d)?;
+
+        ab.add(&cd, p, rm)
     }
 
     fn mul_general_case(
@@ -303,19 +352,16 @@ impl BigFloatNumber {
         p: usize,
         rm: RoundingMode,
         full_prec: bool,
+        t: &MulThresholds,
     ) -> Result<Self, Error> {
         let p = round_p(p);
 
-        // TODO: consider short multiplication.
-
         Self::p_assertion(p)?;
 
         let s = if self.s == d2.s { Sign::Pos } else { Sign::Neg };
 
         if self.m.is_zero() || d2.m.is_zero() {
-            let mut ret = Self::new(p)?;
-            ret.set_sign(s);
-            return Ok(ret);
+            return Self::new2(p, s, self.inexact || d2.inexact);
         }
 
         let (e1, m1_opt) = self.normalize()?;
@@ -332,6 +378,7 @@ impl BigFloatNumber {
             s == Sign::Pos,
             full_prec,
             &mut inexact,
+            t,
         )?;
 
         let e = e1 + e2 - e_shift as isize;
@@ -362,7 +409,7 @@ impl BigFloatNumber {
     }
 
     /// Divides `self` by `d2` and returns the result of the operation with precision `p` rounded according to `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -371,6 +418,21 @@ impl BigFloatNumber {
     ///  - MemoryAllocation: failed to allocate memory for mantissa.
     ///  - InvalidArgument: both `self` and `d2` are zero or precision is incorrect.
     pub fn div(&self, d2: &Self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        self.div_exact(d2, p, rm).map(|(ret, _)| ret)
+    }
+
+    /// Divides `self` by `d2` and returns the result of the operation with precision `p` rounded according to `rm`,
+    /// and a bool value which is true if the quotient of `self` and `d2` is representable exactly with precision `p`.
+    /// The returned bool value does not depend on whether `self` or `d2` are inexact.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - DivisionByZero: `d2` is zero.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: both `self` and `d2` are zero or precision is incorrect.
+    pub fn div_exact(&self, d2: &Self, p: usize, rm: RoundingMode) -> Result<(Self, bool), Error> {
         if d2.m.is_zero() {
             return if self.is_zero() {
                 Err(Error::InvalidArgument)
@@ -385,9 +447,8 @@ impl BigFloatNumber {
         let s = if self.s == d2.s { Sign::Pos } else { Sign::Neg };
 
         if self.m.is_zero() {
-            let mut ret = Self::new(p)?; // self / d2 = 0
-            ret.set_sign(s);
-            return Ok(ret);
+            let ret = Self::new2(p, s, self.inexact || d2.inexact)?; // self / d2 = 0
+            return Ok((ret, true));
         }
 
         let (e1, m1_opt) = self.normalize()?;
@@ -395,7 +456,7 @@ impl BigFloatNumber {
         let (e2, m2_opt) = d2.normalize()?;
         let m2_normalized = m2_opt.as_ref().unwrap_or(&d2.m);
 
-        let mut inexact = self.inexact || d2.inexact;
+        let mut inexact = false;
 
         let (e_shift, m3) =
             m1_normalized.div(m2_normalized, p, rm, s == Sign::Pos, &mut inexact)?;
@@ -406,7 +467,7 @@ impl BigFloatNumber {
             return Err(Error::ExponentOverflow(s));
         }
 
-        if e < EXPONENT_MIN as isize {
+        let mut ret = if e < EXPONENT_MIN as isize {
             let mut ret = BigFloatNumber {
                 m: m3,
                 s,
@@ -416,10 +477,129 @@ impl BigFloatNumber {
 
             ret.subnormalize(e, rm);
 
+            ret
+        } else {
+            BigFloatNumber {
+                m: m3,
+                s,
+                e: e as Exponent,
+                inexact,
+            }
+        };
+
+        let exact = !ret.inexact;
+        ret.inexact |= self.inexact || d2.inexact;
+
+        Ok((ret, exact))
+    }
+
+    /// Adds the word `d` to `self` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn add_word(&self, d: Word, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        if d == 0 {
+            let mut ret = self.clone()?;
+            ret.set_precision(round_p(p), rm)?;
+            return Ok(ret);
+        }
+
+        self.add(&Self::from_word(d, WORD_BIT_SIZE)?, p, rm)
+    }
+
+    /// Multiplies `self` by the word `d` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// Unlike `mul`, the multiplication is done in a single pass over the mantissa of `self`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn mul_word(&self, d: Word, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.m.is_zero() || d == 0 {
+            return Self::new2(p, self.s, self.inexact);
+        }
+
+        let (e1, m1_opt) = self.normalize()?;
+        let m1_normalized = m1_opt.as_ref().unwrap_or(&self.m);
+
+        let mut inexact = self.inexact;
+
+        let (e_shift, m3) = m1_normalized.mul_word(d, p, rm, self.is_positive(), &mut inexact)?;
+
+        Self::from_parts_rounded(m3, self.s, e1 + e_shift, inexact, rm)
+    }
+
+    /// Divides `self` by the word `d` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// Unlike `div`, the division is done in a single pass over the mantissa of `self`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - DivisionByZero: `d` is zero.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: both `self` and `d` are zero or precision is incorrect.
+    pub fn div_word(&self, d: Word, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        if d == 0 {
+            return if self.is_zero() {
+                Err(Error::InvalidArgument)
+            } else {
+                Err(Error::DivisionByZero)
+            };
+        }
+
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.m.is_zero() {
+            return Self::new2(p, self.s, self.inexact);
+        }
+
+        let (e1, m1_opt) = self.normalize()?;
+        let m1_normalized = m1_opt.as_ref().unwrap_or(&self.m);
+
+        let mut inexact = self.inexact;
+
+        let (e_shift, m3) = m1_normalized.div_word(d, p, rm, self.is_positive(), &mut inexact)?;
+
+        Self::from_parts_rounded(m3, self.s, e1 + e_shift, inexact, rm)
+    }
+
+    // Constructs a number from the rounded mantissa `m` and the exponent `e` which can be out of the exponent range.
+    fn from_parts_rounded(
+        m: Mantissa,
+        s: Sign,
+        e: isize,
+        inexact: bool,
+        rm: RoundingMode,
+    ) -> Result<Self, Error> {
+        if e > EXPONENT_MAX as isize {
+            return Err(Error::ExponentOverflow(s));
+        }
+
+        if e < EXPONENT_MIN as isize {
+            let mut ret = BigFloatNumber {
+                m,
+                s,
+                e: EXPONENT_MIN,
+                inexact,
+            };
+
+            ret.subnormalize(e, rm);
+
             Ok(ret)
         } else {
             Ok(BigFloatNumber {
-                m: m3,
+                m,
                 s,
                 e: e as Exponent,
                 inexact,
@@ -440,7 +620,9 @@ impl BigFloatNumber {
         }
 
         if self.m.is_zero() {
-            return self.clone();
+            let mut ret = self.clone()?;
+            ret.inexact |= d2.inexact;
+            return Ok(ret);
         }
 
         // represent as mN * 2 ^ eNeff
@@ -514,7 +696,9 @@ impl BigFloatNumber {
             < (m2_normalized.bit_len() as isize + e)
         {
             // self < d2, remainder = self
-            self.clone()
+            let mut ret = self.clone()?;
+            ret.inexact |= d2.inexact;
+            Ok(ret)
         } else {
             // since self.e >= d2.e and e1eff <= e2eff, then e2eff - e1eff < m1.len()
             // (m1 * 2 ^ e1eff) mod (m2 * 2 ^ e2eff) = m1 mod (m2 * 2 ^ (e2eff - e1eff))
@@ -542,6 +726,70 @@ impl BigFloatNumber {
         }
     }
 
+    /// Returns the Euclidean remainder of division of `self` by `d2`, which is the non-negative number `r` such that
+    /// `self = q * d2 + r` for an integer `q`, and `r < |d2|`. The result is exact.
+    /// A negative zero remainder keeps its sign.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: `d2` is zero.
+    pub fn rem_euclid(&self, d2: &Self) -> Result<Self, Error> {
+        let r = self.rem(d2)?;
+
+        if r.is_negative() && !r.is_zero() {
+            let mut ret = r.add_full_prec(&d2.abs()?)?;
+            ret.inexact |= r.inexact;
+            Ok(ret)
+        } else {
+            Ok(r)
+        }
+    }
+
+    /// Returns the integer quotient of division of `self` by `d2` rounded towards zero, and the remainder.
+    /// Both values are exact and satisfy `self = q * d2 + r`, where `|r| < |d2|`, and the sign of `r` is the sign of `self`.
+    /// The values are computed with a single integer division of the mantissas.
+    /// The precision of the quotient and of the remainder is the smallest precision which can hold the value.
+    /// Note that the quotient of numbers with distant exponents can take a large amount of memory.
+    ///
+    /// ## Errors
+    ///
+    ///  - DivisionByZero: `d2` is zero.
+    ///  - ExponentOverflow: the quotient is larger than the maximum value that can be represented.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision required to hold the quotient is incorrect.
+    pub fn div_rem(&self, d2: &Self) -> Result<(Self, Self), Error> {
+        if d2.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+
+        // self = m1 * 2^e1, d2 = m2 * 2^e2
+        let (s1, m1, e1) = self.to_int_ratio()?;
+        let (s2, m2, e2) = d2.to_int_ratio()?;
+
+        let m1 = BigUInt::from_words(&m1)?;
+        let m2 = BigUInt::from_words(&m2)?;
+
+        // the remainder is a multiple of 2^min(e1, e2)
+        let (q, r) = if e1 >= e2 {
+            m1.shl((e1 - e2) as usize)?.div_rem(&m2)?
+        } else {
+            m1.div_rem(&m2.shl((e2 - e1) as usize)?)?
+        };
+
+        let qs = if s1 == s2 { Sign::Pos } else { Sign::Neg };
+        let inexact = self.inexact || d2.inexact;
+
+        let mut q = Self::from_int_ratio(qs, q.as_words(), 0)?;
+        let mut r = Self::from_int_ratio(s1, r.as_words(), e1.min(e2))?;
+
+        q.inexact = inexact;
+        r.inexact = inexact;
+
+        Ok((q, r))
+    }
+
     // Return normilized mantissa and exponent with corresponding shift.
     pub(super) fn normalize(&self) -> Result<(isize, Option<Mantissa>), Error> {
         if self.is_subnormal() {
@@ -585,16 +833,22 @@ impl BigFloatNumber {
         // one of the args is zero
         if self.m.is_zero() {
             let mut ret = if op < 0 { d2.neg() } else { d2.clone() }?;
+            ret.inexact |= self.inexact;
 
-            ret.set_precision(p, rm)?;
+            if !full_prec {
+                ret.set_precision(p, rm)?;
+            }
 
             return Ok(ret);
         }
 
         if d2.m.is_zero() {
             let mut ret = self.clone()?;
+            ret.inexact |= d2.inexact;
 
-            ret.set_precision(p, rm)?;
+            if !full_prec {
+                ret.set_precision(p, rm)?;
+            }
 
             return Ok(ret);
         }
@@ -606,8 +860,10 @@ impl BigFloatNumber {
 
         let mut inexact = self.inexact || d2.inexact;
 
+        let subtract = (self.s != d2.s && op >= 0) || (op < 0 && self.s == d2.s);
+
         let mut e;
-        let (shift, m3) = if (self.s != d2.s && op >= 0) || (op < 0 && self.s == d2.s) {
+        let (shift, m3) = if subtract {
             // subtract
             let cmp = self.abs_cmp(d2);
             if cmp > 0 {
@@ -669,6 +925,15 @@ impl BigFloatNumber {
 
         d3.inexact |= inexact;
 
+        #[cfg(feature = "tracing")]
+        {
+            tracing::trace!(exponent_spread = (e1 - e2).unsigned_abs(), "addition");
+
+            if subtract && shift > 1 {
+                tracing::debug!(cancelled_bits = shift, "cancellation");
+            }
+        }
+
         #[cfg(not(target_arch = "x86"))]
         {
             debug_assert!(shift <= isize::MAX / 2 && e >= isize::MIN as isize / 2);
@@ -742,6 +1007,91 @@ impl BigFloatNumber {
         }
     }
 
+    /// Rounds `self` using the rounding mode `rm` so that it fits the exponent range from `emin` to `emax`.
+    /// If the exponent of `self` is smaller than `emin`, `self` is rounded as a subnormal number of that range,
+    /// i.e. the result is a multiple of 2^(`emin` - `p`), where `p` is the mantissa length of `self` in bits.
+    /// The rounding mode `RoundingMode::None` truncates the extra bits.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the exponent of `self` is greater than `emax`.
+    ///  - InvalidArgument: `emin` is greater than `emax`.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    pub fn fit_exponent_range(
+        &self,
+        emin: Exponent,
+        emax: Exponent,
+        rm: RoundingMode,
+    ) -> Result<Self, Error> {
+        if emin > emax {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut ret = self.clone()?;
+
+        if ret.is_zero() || (ret.e >= emin && ret.e <= emax) {
+            return Ok(ret);
+        }
+
+        if ret.e > emax {
+            return Err(Error::ExponentOverflow(ret.s));
+        }
+
+        let rm = if rm == RoundingMode::None { RoundingMode::ToZero } else { rm };
+        let is_positive = ret.is_positive();
+        let p = ret.mantissa_max_bit_len();
+        let shift = (emin as isize - ret.e as isize) as usize;
+
+        if shift < p {
+            let mut inexact = ret.inexact;
+
+            if ret
+                .m
+                .round_mantissa(shift, rm, is_positive, &mut false, p, &mut inexact)
+            {
+                ret.e += 1;
+            }
+
+            ret.inexact |= inexact;
+
+            if ret.m.is_all_zero() {
+                ret.m.set_bit_len(0);
+                ret.e = 0;
+            } else if ret.is_subnormal() {
+                ret.m.update_bit_len();
+            }
+        } else {
+            // |self| < 2^(emin - p): the result is either 0, or 2^(emin - p).
+            let half_or_more = shift == p && !ret.is_subnormal();
+            let above_half = half_or_more && ret.m.find_one_from(1).is_some();
+
+            let round_away = match rm {
+                RoundingMode::ToEven => above_half,
+                RoundingMode::ToOdd | RoundingMode::ToNearestAway => half_or_more,
+                RoundingMode::FromZero => true,
+                RoundingMode::Up => is_positive,
+                RoundingMode::Down => !is_positive,
+                RoundingMode::ToZero | RoundingMode::None => false,
+            };
+
+            ret.m.set_zero();
+            ret.inexact = true;
+
+            if round_away {
+                let e = (emin as isize - p as isize + 1).max(EXPONENT_MIN as isize);
+                let b = (emin as isize - e) as usize;
+
+                ret.m.digits_mut()[b / WORD_BIT_SIZE] = 1 << (b % WORD_BIT_SIZE);
+                ret.m.update_bit_len();
+                ret.e = e as Exponent;
+            } else {
+                ret.e = 0;
+            }
+        }
+
+        Ok(ret)
+    }
+
     /// Compares `self` to `d2`.
     /// Returns positive if `self` is greater than `d2`, negative if `self` is smaller than `d2`, 0 otherwise.
     pub fn cmp(&self, d2: &Self) -> SignedWord {
@@ -765,6 +1115,8 @@ impl BigFloatNumber {
             } else {
                 return -1;
             }
+        } else if d2.m.is_zero() {
+            return 1;
         }
 
         let n1 = self.mantissa_max_bit_len() as isize - self.precision() as isize;
@@ -793,7 +1145,7 @@ impl BigFloatNumber {
     }
 
     /// Constructs a number with precision `p` from f64 value.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -834,9 +1186,12 @@ impl BigFloatNumber {
             mantissa >>= 1;
             mantissa |= 0x8000000000000000u64;
             exponent += 1;
+        } else {
+            // subnormal numbers have the same exponent as the smallest normal numbers
+            exponent = 1;
         }
 
-        let (shift, m) = Mantissa::from_u64(p, mantissa)?;
+        let (shift, m) = Mantissa::from_u64(round_p(p), mantissa)?;
 
         ret.m = m;
         ret.e = exponent - 0b1111111111 - shift as Exponent;
@@ -844,6 +1199,47 @@ impl BigFloatNumber {
         Ok(ret)
     }
 
+    /// Constructs a number from f64 value using the smallest precision which holds `f` exactly.
+    /// The precision is the number of significant bits of `f` (at most 53, fewer if `f` is a dyadic
+    /// number with a short mantissa) rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `f` is NaN.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: `f` is Inf.
+    pub fn from_f64_minimal(f: f64) -> Result<Self, Error> {
+        let mut ret = Self::from_f64(64, f)?;
+
+        if !ret.is_zero() {
+            let u = f.to_bits();
+            let mut s = u & 0xfffffffffffff;
+            if u & 0x7ff0000000000000 != 0 {
+                s |= 0x10000000000000;
+            }
+
+            let bits = 64 - s.leading_zeros() - s.trailing_zeros();
+
+            ret.set_precision(round_p(bits as usize), RoundingMode::None)?;
+        }
+
+        Ok(ret)
+    }
+
+    /// Constructs a number from f32 value using the smallest precision which holds `f` exactly.
+    /// The precision is the number of significant bits of `f` (at most 24) rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `f` is NaN.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: `f` is Inf.
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn from_f32_minimal(f: f32) -> Result<Self, Error> {
+        Self::from_f64_minimal(f as f64)
+    }
+
     /// Converts a number to f64 value.
     /// Conversion rounds `self` to zero.
     pub(crate) fn to_f64(&self) -> f64 {
@@ -860,8 +1256,9 @@ impl BigFloatNumber {
                 Sign::Pos => f64::INFINITY,
                 Sign::Neg => f64::NEG_INFINITY,
             }
-        } else if e <= 0 {
-            let shift = -e;
+        } else if e <= 1 {
+            // subnormal
+            let shift = 1 - e;
             if shift < 52 {
                 ret |= mantissa >> (shift + 12);
                 if self.s == Sign::Neg {
@@ -886,7 +1283,7 @@ impl BigFloatNumber {
     }
 
     /// Constructs a number with precision `p` from f32 value.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -906,34 +1303,393 @@ impl BigFloatNumber {
         self.to_f64() as f32
     }
 
-    /// Returns true if `self` is subnormal. A number is subnormal if the most significant bit of the mantissa is not equal to 1.
-    #[inline]
-    pub fn is_subnormal(&self) -> bool {
-        self.m.is_subnormal()
-    }
-
-    /// Decomposes `self` into raw parts.
-    /// The function returns a reference to a slice of words representing mantissa,
-    /// numbers of significant bits in the mantissa,
-    /// sign, exponent, and a bool value which specify whether the number is inexact.
-    #[inline]
-    pub fn as_raw_parts(&self) -> (&[Word], usize, Sign, Exponent, bool) {
-        let (m, n) = self.m.as_raw_parts();
-        (m, n, self.s, self.e, self.inexact)
+    /// Converts a number to f64 value rounding it using rounding mode `rm`.
+    /// Rounding mode None is treated as ToZero.
+    /// The function returns the converted value and true if the conversion was inexact.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: the rounded value exceeds the range of f64.
+    pub(crate) fn to_f64_rm(&self, rm: RoundingMode) -> Result<(f64, bool), Error> {
+        self.round_to_binary_format(53, -1021, 1024, rm)
     }
 
-    /// Constructs a number from the raw parts:
-    ///
-    ///  - `m` is the mantisaa.
-    ///  - `n` is the number of significant bits in mantissa.
-    ///  - `s` is the sign.
-    ///  - `e` is the exponent.
-    ///  - `inexact` specify whether number is inexact.
+    /// Converts a number to f32 value rounding it using rounding mode `rm`.
+    /// Rounding mode None is treated as ToZero.
+    /// The function returns the converted value and true if the conversion was inexact.
     ///
     /// ## Errors
     ///
     ///  - MemoryAllocation: failed to allocate memory for mantissa.
-    ///  - InvalidArgument: `n` is larger than the number of bits in `m`;
+    ///  - ExponentOverflow: the rounded value exceeds the range of f32.
+    pub(crate) fn to_f32_rm(&self, rm: RoundingMode) -> Result<(f32, bool), Error> {
+        // f32 values are exactly representable as f64
+        self.round_to_binary_format(24, -125, 128, rm)
+            .map(|(f, inexact)| (f as f32, inexact))
+    }
+
+    // Rounds `self` to a binary floating point format with precision of `k` bits,
+    // minimum exponent of normal numbers `emin`, and maximum exponent `emax`,
+    // and returns the result as f64 value, and true if rounding was inexact.
+    // The format must be a subset of f64.
+    fn round_to_binary_format(
+        &self,
+        k: isize,
+        emin: isize,
+        emax: isize,
+        rm: RoundingMode,
+    ) -> Result<(f64, bool), Error> {
+        let rm = if rm == RoundingMode::None { RoundingMode::ToZero } else { rm };
+
+        let zero = if self.is_positive() { 0.0 } else { -0.0 };
+
+        if self.m.is_zero() {
+            return Ok((zero, false));
+        }
+
+        let e = self.e as isize;
+
+        // number of bits which fit in the mantissa of the format
+        let n = k - (emin - e).max(0);
+
+        // make the fractional part contain the bits which do not fit
+        let mut x = self.clone()?;
+        x.e = n as Exponent;
+        x.inexact = false;
+
+        let y = x.round(0, rm)?;
+
+        if y.is_zero() {
+            return Ok((zero, true));
+        }
+
+        let ye = y.e as isize;
+        if ye + e - n > emax {
+            return Err(Error::ExponentOverflow(self.s));
+        }
+
+        // y is an integer of at most k + 1 bits
+        let d = (y.m.to_u64() >> (64 - ye)) as f64;
+
+        // 2^(e - n) is representable in f64 since the result is representable
+        let pe = e - n;
+        let pow2 = if pe >= -1022 {
+            f64::from_bits(((pe + 1023) as u64) << 52)
+        } else {
+            f64::from_bits(1u64 << (pe + 1074))
+        };
+
+        let f = d * pow2;
+
+        Ok((if self.is_positive() { f } else { -f }, y.inexact))
+    }
+
+    /// Converts the integer number `self` to u64 value.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the value exceeds the range of u64.
+    ///  - InvalidArgument: `self` is negative.
+    ///  - Inexact: `self` is not an integer.
+    pub(crate) fn to_u64_exact(&self) -> Result<u64, Error> {
+        if !self.is_int() {
+            return Err(Error::Inexact);
+        }
+
+        self.to_u64(RoundingMode::None)
+    }
+
+    /// Converts the integer number `self` to i64 value.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the value exceeds the range of i64.
+    ///  - Inexact: `self` is not an integer.
+    pub(crate) fn to_i64_exact(&self) -> Result<i64, Error> {
+        if !self.is_int() {
+            return Err(Error::Inexact);
+        }
+
+        self.to_i64(RoundingMode::None)
+    }
+
+    /// Rounds `self` to an integer using rounding mode `rm` and converts it to u64 value.
+    /// Rounding mode None is treated as ToZero.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the rounded value exceeds the range of u64.
+    ///  - InvalidArgument: the rounded value is negative.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    pub fn to_u64(&self, rm: RoundingMode) -> Result<u64, Error> {
+        u64::try_from(self.to_u128(rm)?).map_err(|_| Error::ExponentOverflow(Sign::Pos))
+    }
+
+    /// Rounds `self` to an integer using rounding mode `rm` and converts it to i64 value.
+    /// Rounding mode None is treated as ToZero.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the rounded value exceeds the range of i64.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    pub fn to_i64(&self, rm: RoundingMode) -> Result<i64, Error> {
+        i64::try_from(self.to_i128(rm)?).map_err(|_| Error::ExponentOverflow(self.s))
+    }
+
+    /// Rounds `self` to an integer using rounding mode `rm` and converts it to u128 value.
+    /// Rounding mode None is treated as ToZero.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the rounded value exceeds the range of u128.
+    ///  - InvalidArgument: the rounded value is negative.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    pub fn to_u128(&self, rm: RoundingMode) -> Result<u128, Error> {
+        let u = self.round_to_u128(rm)?;
+
+        if self.is_negative() && u > 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(u)
+    }
+
+    /// Rounds `self` to an integer using rounding mode `rm` and converts it to i128 value.
+    /// Rounding mode None is treated as ToZero.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the rounded value exceeds the range of i128.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    pub fn to_i128(&self, rm: RoundingMode) -> Result<i128, Error> {
+        let u = self.round_to_u128(rm)?;
+
+        if self.is_positive() {
+            i128::try_from(u).map_err(|_| Error::ExponentOverflow(Sign::Pos))
+        } else if u <= i128::MIN.unsigned_abs() {
+            Ok((u as i128).wrapping_neg())
+        } else {
+            Err(Error::ExponentOverflow(Sign::Neg))
+        }
+    }
+
+    // Rounds `self` to an integer using rounding mode `rm` and returns the absolute value of the result.
+    fn round_to_u128(&self, rm: RoundingMode) -> Result<u128, Error> {
+        if self.is_zero() {
+            return Ok(0);
+        }
+
+        if self.e > 128 {
+            return Err(Error::ExponentOverflow(self.s));
+        }
+
+        let rm = if rm == RoundingMode::None { RoundingMode::ToZero } else { rm };
+        let y = self.round(0, rm)?;
+
+        if y.is_zero() {
+            return Ok(0);
+        }
+
+        let e = y.e as usize;
+        if e > 128 {
+            return Err(Error::ExponentOverflow(self.s));
+        }
+
+        // take at most 128 most significant bits of the mantissa
+        let mut u: u128 = 0;
+        let mut bits = 0;
+        for &d in y.m.digits().iter().rev() {
+            if bits >= 128 {
+                break;
+            }
+            u = (u << WORD_BIT_SIZE) | d as u128;
+            bits += WORD_BIT_SIZE;
+        }
+
+        Ok(if bits >= e { u >> (bits - e) } else { u << (e - bits) })
+    }
+
+    /// Constructs a number with precision `p` from f128 value.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// If `p` is smaller than 113, the value is rounded to the nearest even.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the precision is incorrect or `f` is NaN.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: `f` is Inf.
+    #[cfg(feature = "f128")]
+    pub fn from_f128(p: usize, f: f128) -> Result<Self, Error> {
+        Self::p_assertion(p)?;
+
+        if f.is_nan() {
+            return Err(Error::InvalidArgument);
+        }
+
+        let s = if f.is_sign_negative() { Sign::Neg } else { Sign::Pos };
+
+        if f.is_infinite() {
+            return Err(Error::ExponentOverflow(s));
+        }
+
+        let mut ret = Self::new2(p, s, false)?;
+
+        if f == 0.0 {
+            return Ok(ret);
+        }
+
+        let u = f.to_bits();
+        let mut mantissa = u << 16;
+        let mut e = ((u >> 112) & 0x7fff) as isize;
+
+        if e != 0 {
+            mantissa >>= 1;
+            mantissa |= 1u128 << 127;
+            e -= 16382;
+        } else {
+            let shift = mantissa.leading_zeros();
+            mantissa <<= shift;
+            e = -16382 - shift as isize;
+        }
+
+        let mut words = [0; 128 / WORD_BIT_SIZE];
+        for w in &mut words {
+            *w = mantissa as Word;
+            mantissa >>= WORD_BIT_SIZE;
+        }
+
+        ret.m = Mantissa::from_words(128, &words)?;
+        ret.e = e as Exponent;
+        ret.set_precision(p, RoundingMode::ToEven)?;
+
+        Ok(ret)
+    }
+
+    /// Converts a number to f128 value rounding it using rounding mode `rm`.
+    /// Rounding mode None is treated as ToZero.
+    /// Numbers which exceed the range of f128 become infinite,
+    /// or the largest finite f128 value if rounding is performed towards zero.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    #[cfg(feature = "f128")]
+    pub(crate) fn to_f128(&self, rm: RoundingMode) -> Result<f128, Error> {
+        const F128_P: isize = 113;
+        const F128_EMIN: isize = -16381;
+        const F128_EMAX: isize = 16384;
+
+        let rm = if rm == RoundingMode::None { RoundingMode::ToZero } else { rm };
+        let is_positive = self.is_positive();
+        let sign_bit = if is_positive { 0 } else { 1u128 << 127 };
+
+        if self.m.is_zero() {
+            return Ok(f128::from_bits(sign_bit));
+        }
+
+        let mut x = self.clone()?;
+        if x.mantissa_max_bit_len() < 128 {
+            x.set_precision(128, RoundingMode::None)?;
+        }
+
+        let p = x.mantissa_max_bit_len();
+        let mut e = x.e as isize;
+
+        // number of bits which fit in the f128 mantissa
+        let k = F128_P - (F128_EMIN - e).max(0);
+
+        let bits = if k > 0 {
+            let mut inexact = false;
+
+            if x.m
+                .round_mantissa(p - k as usize, rm, is_positive, &mut false, p, &mut inexact)
+            {
+                e += 1;
+            }
+
+            if e > F128_EMAX {
+                let towards_zero = match rm {
+                    RoundingMode::ToZero => true,
+                    RoundingMode::Up => !is_positive,
+                    RoundingMode::Down => is_positive,
+                    _ => false,
+                };
+
+                if towards_zero {
+                    (0x7ffeu128 << 112) | ((1u128 << 112) - 1)
+                } else {
+                    0x7fffu128 << 112
+                }
+            } else {
+                let mut top = 0u128;
+                for &w in x.m.digits().iter().rev().take(128 / WORD_BIT_SIZE) {
+                    top = (top << WORD_BIT_SIZE) | w as u128;
+                }
+
+                if e >= F128_EMIN {
+                    (((e + 16382) as u128) << 112) | ((top >> 15) & ((1u128 << 112) - 1))
+                } else {
+                    top >> (128 - 16494 - e)
+                }
+            }
+        } else {
+            // |self| < 2^-16494: the result is either 0, or the smallest subnormal number.
+            let half_or_more = k == 0 && !x.is_subnormal();
+            let above_half = half_or_more && x.m.find_one_from(1).is_some();
+
+            let round_away = match rm {
+                RoundingMode::ToEven => above_half,
+                RoundingMode::ToOdd | RoundingMode::ToNearestAway => half_or_more,
+                RoundingMode::FromZero => true,
+                RoundingMode::Up => is_positive,
+                RoundingMode::Down => !is_positive,
+                RoundingMode::ToZero | RoundingMode::None => false,
+            };
+
+            round_away as u128
+        };
+
+        Ok(f128::from_bits(sign_bit | bits))
+    }
+
+    /// Returns true if `self` is subnormal. A number is subnormal if the most significant bit of the mantissa is not equal to 1.
+    #[inline]
+    pub fn is_subnormal(&self) -> bool {
+        self.m.is_subnormal()
+    }
+
+    /// Returns true if `self` and `d2` have the same representation: the same sign, exponent, precision,
+    /// and mantissa words. Unlike numerical comparison, numbers with different precision are not identical
+    /// even if their values are equal. The inexact flag is not compared.
+    pub fn identical(&self, d2: &Self) -> bool {
+        let (m1, n1) = self.m.as_raw_parts();
+        let (m2, n2) = d2.m.as_raw_parts();
+        self.s == d2.s && self.e == d2.e && n1 == n2 && m1 == m2
+    }
+
+    /// Decomposes `self` into raw parts.
+    /// The function returns a reference to a slice of words representing mantissa,
+    /// numbers of significant bits in the mantissa,
+    /// sign, exponent, and a bool value which specify whether the number is inexact.
+    #[inline]
+    pub fn as_raw_parts(&self) -> (&[Word], usize, Sign, Exponent, bool) {
+        let (m, n) = self.m.as_raw_parts();
+        (m, n, self.s, self.e, self.inexact)
+    }
+
+    /// Constructs a number from the raw parts:
+    ///
+    ///  - `m` is the mantisaa.
+    ///  - `n` is the number of significant bits in mantissa.
+    ///  - `s` is the sign.
+    ///  - `e` is the exponent.
+    ///  - `inexact` specify whether number is inexact.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: `n` is larger than the number of bits in `m`;
     /// `n` is smaller than the number of bits in `m`, but `m` does not represent corresponding subnormal number mantissa;
     /// `n` is smaller than the number of bits in `m`, but `e` is not the minimum possible exponent;
     /// `n` or the size of `m` is too large (larger than isize::MAX / 2 + EXPONENT_MIN);
@@ -942,35 +1698,73 @@ impl BigFloatNumber {
         m: &[Word],
         n: usize,
         s: Sign,
-        mut e: Exponent,
+        e: Exponent,
         inexact: bool,
     ) -> Result<Self, Error> {
-        let p = m.len() * WORD_BIT_SIZE;
-        Self::p_assertion(p)?;
+        let p = Self::raw_parts_assertion(m.len(), n, e)?;
 
-        if e < EXPONENT_MIN || e > EXPONENT_MAX {
-            return Err(Error::InvalidArgument);
-        }
+        Self::from_raw_mantissa(Mantissa::from_words(p, m)?, n, s, e, inexact)
+    }
+
+    /// Constructs a number from the raw parts taking ownership of the mantissa `m` without copying it.
+    /// The parts are the same as in `from_raw_parts`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the same as in `from_raw_parts`.
+    pub fn from_raw_vec(
+        m: Vec<Word>,
+        n: usize,
+        s: Sign,
+        e: Exponent,
+        inexact: bool,
+    ) -> Result<Self, Error> {
+        Self::raw_parts_assertion(m.len(), n, e)?;
+
+        Self::from_raw_mantissa(Mantissa::from_vec(m), n, s, e, inexact)
+    }
+
+    // Checks the mantissa length `l` in words, the number of significant bits `n`, and the exponent `e`,
+    // and returns the precision of the mantissa.
+    fn raw_parts_assertion(l: usize, n: usize, e: Exponent) -> Result<usize, Error> {
+        let p = l * WORD_BIT_SIZE;
+        Self::p_assertion(p)?;
 
-        if n > p {
+        if e < EXPONENT_MIN || e > EXPONENT_MAX || n > p {
             Err(Error::InvalidArgument)
         } else {
-            let m = Mantissa::from_words(p, m)?;
-
-            if m.bit_len() != n {
-                return Err(Error::InvalidArgument);
-            }
+            Ok(p)
+        }
+    }
 
-            if n > 0 && n < p && e > EXPONENT_MIN {
-                return Err(Error::InvalidArgument);
-            }
+    // Checks that `n` is the number of significant bits of `m` and `e` corresponds to it, and constructs a number.
+    fn from_raw_mantissa(
+        m: Mantissa,
+        n: usize,
+        s: Sign,
+        mut e: Exponent,
+        inexact: bool,
+    ) -> Result<Self, Error> {
+        if m.bit_len() != n {
+            return Err(Error::InvalidArgument);
+        }
 
-            if n == 0 {
-                e = 0;
-            }
+        if n > 0 && n < m.max_bit_len() && e > EXPONENT_MIN {
+            return Err(Error::InvalidArgument);
+        }
 
-            Ok(BigFloatNumber { e, s, m, inexact })
+        if n == 0 {
+            e = 0;
         }
+
+        Ok(BigFloatNumber { e, s, m, inexact })
+    }
+
+    /// Decomposes `self` into raw parts taking the mantissa without copying it.
+    /// The parts are the same as returned by `as_raw_parts`, except the mantissa is returned as a vector of words.
+    pub fn into_raw_parts(self) -> (Vec<Word>, usize, Sign, Exponent, bool) {
+        let (m, n) = self.m.into_raw_parts();
+        (m, n, self.s, self.e, self.inexact)
     }
 
     /// Build BigFloatNumber from raw parts unchecked.
@@ -1022,44 +1816,153 @@ impl BigFloatNumber {
         })
     }
 
-    /// Returns the sign of a number.
-    #[inline]
-    pub fn sign(&self) -> Sign {
-        self.s
-    }
+    /// Decomposes `self` into an exact integer ratio `s * m * 2^e`.
+    /// The function returns the sign `s`, the odd integer `m` as a slice of words with the least significant word first,
+    /// and the binary exponent `e`. Zero is returned as an empty `m` and `e` equal to 0.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn to_int_ratio(&self) -> Result<(Sign, Vec<Word>, isize), Error> {
+        let (m, _) = self.m.as_raw_parts();
 
-    /// Returns true if `self` is positive.
-    #[inline]
-    pub fn is_positive(&self) -> bool {
-        self.s == Sign::Pos
-    }
+        let lo = match m.iter().position(|w| *w != 0) {
+            Some(i) => i,
+            None => return Ok((self.s, Vec::new(), 0)),
+        };
+        let hi = m.iter().rposition(|w| *w != 0).unwrap_or(lo) + 1;
 
-    /// Returns true if `self` is negative.
-    #[inline]
-    pub fn is_negative(&self) -> bool {
-        self.s == Sign::Neg
-    }
+        let mut ret = Vec::new();
+        ret.try_reserve_exact(hi - lo)?;
+        ret.extend_from_slice(&m[lo..hi]);
 
-    /// Returns the exponent of `self`.
-    #[inline]
-    pub fn exponent(&self) -> Exponent {
-        self.e
-    }
+        let tz = ret[0].trailing_zeros() as usize;
+        shift_slice_right(&mut ret, tz);
 
-    /// Returns true if `self` is zero.
-    #[inline]
-    pub fn is_zero(&self) -> bool {
-        self.m.is_zero()
+        if ret[ret.len() - 1] == 0 {
+            ret.pop();
+        }
+
+        let e = self.e as isize - ((m.len() - lo) * WORD_BIT_SIZE - tz) as isize;
+
+        Ok((self.s, ret, e))
     }
 
-    /// Returns the largest integer less than or equal to `self`.
+    /// Constructs a number from an integer ratio `s * m * 2^e` without rounding.
+    /// `m` is an integer given as a slice of words with the least significant word first.
+    /// The precision of the result is the number of significant bits of `m` rounded upwards to a multiple of `PRECISION_GRANULARITY`,
+    /// or larger if the result is subnormal.
     ///
     /// ## Errors
     ///
     ///  - MemoryAllocation: failed to allocate memory for mantissa.
-    pub fn floor(&self) -> Result<Self, Error> {
-        let int = self.int()?;
-        if self.is_negative() && !self.fract()?.m.is_zero() {
+    ///  - InvalidArgument: the precision required to hold the value is incorrect.
+    ///  - ExponentOverflow: the value is larger than the maximum value that can be represented.
+    pub fn from_int_ratio(s: Sign, m: &[Word], e: isize) -> Result<Self, Error> {
+        let len = match m.iter().rposition(|w| *w != 0) {
+            Some(i) => i + 1,
+            None => {
+                let mut ret = Self::new(WORD_BIT_SIZE)?;
+                ret.s = s;
+                return Ok(ret);
+            }
+        };
+
+        let m = &m[..len];
+        let lz = m[len - 1].leading_zeros() as usize;
+        let bl = len * WORD_BIT_SIZE - lz;
+
+        let e = e.saturating_add(bl as isize);
+
+        if e > EXPONENT_MAX as isize {
+            return Err(Error::ExponentOverflow(s));
+        }
+
+        let shift = if e < EXPONENT_MIN as isize { (EXPONENT_MIN as isize).abs_diff(e) } else { 0 };
+
+        let p = round_p(bl.saturating_add(shift));
+        Self::p_assertion(p)?;
+
+        let mut m = Mantissa::from_words(p, m)?;
+
+        if shift > lz {
+            m.shift_right(shift - lz);
+        } else {
+            m.shift_left(lz - shift);
+        }
+
+        m.set_bit_len(p - shift);
+
+        Ok(BigFloatNumber {
+            e: e.max(EXPONENT_MIN as isize) as Exponent,
+            s,
+            m,
+            inexact: false,
+        })
+    }
+
+    /// Computes `s * num / den` with precision `p` rounded according to `rm`.
+    /// `num` and `den` are integers given as slices of words with the least significant word first.
+    /// Both integers are converted to numbers exactly, so the quotient is rounded only once.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - DivisionByZero: `den` is zero.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: both `num` and `den` are zero or precision is incorrect.
+    pub fn from_ratio(
+        s: Sign,
+        num: &[Word],
+        den: &[Word],
+        p: usize,
+        rm: RoundingMode,
+    ) -> Result<Self, Error> {
+        let n = Self::from_int_ratio(s, num, 0)?;
+        let d = Self::from_int_ratio(Sign::Pos, den, 0)?;
+
+        n.div(&d, p, rm)
+    }
+
+    /// Returns the sign of a number.
+    #[inline]
+    pub fn sign(&self) -> Sign {
+        self.s
+    }
+
+    /// Returns true if `self` is positive.
+    #[inline]
+    pub fn is_positive(&self) -> bool {
+        self.s == Sign::Pos
+    }
+
+    /// Returns true if `self` is negative.
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.s == Sign::Neg
+    }
+
+    /// Returns the exponent of `self`.
+    #[inline]
+    pub fn exponent(&self) -> Exponent {
+        self.e
+    }
+
+    /// Returns true if `self` is zero.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.m.is_zero()
+    }
+
+    /// Returns the largest integer less than or equal to `self`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    pub fn floor(&self) -> Result<Self, Error> {
+        let int = self.int()?;
+        if self.is_negative() && !self.fract()?.m.is_zero() {
             return int.sub(&ONE, int.mantissa_max_bit_len(), RoundingMode::ToZero);
         }
         Ok(int)
@@ -1132,17 +2035,6 @@ impl BigFloatNumber {
         Ok(ret)
     }
 
-    /// Returns integer part as a word.
-    pub(crate) fn int_as_word(&self) -> Word {
-        if self.e > 0 && WORD_BIT_SIZE >= self.e as usize {
-            let d = self.m.most_significant_word();
-            let shift = WORD_BIT_SIZE - self.e as usize;
-            d >> shift
-        } else {
-            0
-        }
-    }
-
     /// Returns true if `self` is odd integer number.
     pub(crate) fn is_odd_int(&self) -> bool {
         if self.e > 0 {
@@ -1214,6 +2106,16 @@ impl BigFloatNumber {
         self.m.max_bit_len()
     }
 
+    /// Returns the exponent of `self` as if the mantissa of a subnormal number was normalized.
+    // The function returns 0 if `self` is zero.
+    pub(crate) fn normalized_exponent(&self) -> isize {
+        if self.is_zero() {
+            0
+        } else {
+            self.e as isize - (self.mantissa_max_bit_len() - self.precision()) as isize
+        }
+    }
+
     /// Returns the number of significant bits used in the mantissa. Normal numbers use all bits of the mantissa.
     /// Subnormal numbers use fewer bits than the mantissa can hold.
     #[inline]
@@ -1221,6 +2123,49 @@ impl BigFloatNumber {
         self.m.bit_len()
     }
 
+    /// Returns the number of bytes allocated on the heap for the mantissa of the number.
+    /// The value can be larger than the size of the mantissa, e.g. after the precision was reduced with `set_precision`.
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.m.allocated_bytes()
+    }
+
+    /// Releases the memory which is allocated for the mantissa, but is not used by it.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.m.shrink_to_fit();
+    }
+
+    /// Reduces the precision of `self` to the minimum number of words which represents the value of `self` exactly,
+    /// and releases the unused memory. The value of `self` does not change. The precision of zero is reduced to the word size.
+    #[inline]
+    pub fn compact(&mut self) {
+        self.m.compact();
+    }
+
+    /// Sets `self` to zero with precision `p` reusing the memory allocated for the mantissa.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    pub(crate) fn reset(&mut self, p: usize) -> Result<(), Error> {
+        Self::p_assertion(p)?;
+        self.m.set_length(round_p(p))?;
+        self.m.set_zero();
+        self.e = 0;
+        self.s = Sign::Pos;
+        self.inexact = false;
+        Ok(())
+    }
+
+    /// Sets `self` to the value of `d` reusing the memory allocated for the mantissa.
+    pub(crate) fn assign(&mut self, d: &Self) -> Result<(), Error> {
+        self.m.set_length(d.mantissa_max_bit_len())?;
+        self.m.digits_mut().copy_from_slice(d.m.digits());
+        self.m.set_bit_len(d.m.bit_len());
+        self.e = d.e;
+        self.s = d.s;
+        self.inexact = d.inexact;
+        Ok(())
+    }
+
     /// Returns the rounded number with `n` binary positions in the fractional part of the number using rounding mode `rm`.
     ///
     /// ## Errors
@@ -1250,7 +2195,8 @@ impl BigFloatNumber {
                     || (is_positive && rm == RoundingMode::Up)
                     || (!is_positive && rm == RoundingMode::Down)
                     || (m == self.mantissa_max_bit_len()
-                        && ((msb_set && rm == RoundingMode::ToOdd)
+                        && ((msb_set
+                            && (rm == RoundingMode::ToOdd || rm == RoundingMode::ToNearestAway))
                             || (rm == RoundingMode::ToEven
                                 && msb_set
                                 && count_leading_zeroes_skip_first(self.mantissa().digits())
@@ -1259,6 +2205,7 @@ impl BigFloatNumber {
                     // non zero for directed rounding modes,
                     // non zero for rounding to even/odd when msb of self is the rounding bit
                     *ret.m.digits_mut().last_mut().unwrap() = WORD_SIGNIFICANT_BIT;
+                    ret.m.set_bit_len(ret.m.max_bit_len());
 
                     let e = -(n as isize - 1);
                     if e < EXPONENT_MIN as isize {
@@ -1304,10 +2251,152 @@ impl BigFloatNumber {
         Ok(ret)
     }
 
+    /// Rounds `self` to a multiple of `rdx`^`e` using rounding mode `rm`, so the least significant retained digit
+    /// of the number in radix `rdx` is at the position `e`. The result is then rounded to precision `p` using the rounding mode `rm`.
+    /// Along with the result the function returns a bool value which is true if `self` is a multiple of `rdx`^`e`,
+    /// i.e. no non-zero digits were discarded.
+    /// If `rdx` is `Radix::Dec` and `e` is negative, the result is generally not representable exactly in binary,
+    /// and it is the quantized decimal value rounded to precision `p`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: the result is too large.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn quantize(
+        &self,
+        e: Exponent,
+        rdx: Radix,
+        p: usize,
+        rm: RoundingMode,
+    ) -> Result<(Self, bool), Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return Ok((Self::new2(p, self.sign(), self.inexact())?, true));
+        }
+
+        let (mut ret, exact) = match rdx {
+            Radix::Bin => self.quantize_bin(e as isize, rm)?,
+            Radix::Oct => self.quantize_bin(e as isize * 3, rm)?,
+            Radix::Hex => self.quantize_bin(e as isize * 4, rm)?,
+            Radix::Dec => {
+                let n = (e as isize).unsigned_abs();
+                let d = Self::from_word(10, WORD_BIT_SIZE)?.powi(
+                    n,
+                    round_p(n * 10 / 3 + WORD_BIT_SIZE),
+                    RoundingMode::None,
+                )?;
+
+                if e >= 0 {
+                    let (k, exact) = self.quantize_div(&d, rm)?;
+
+                    if k.is_zero() {
+                        (k, exact)
+                    } else {
+                        (k.mul_full_prec(&d)?, exact)
+                    }
+                } else {
+                    let mut y = self.mul_full_prec(&d)?;
+                    y.set_inexact(false);
+
+                    let k = y.round(0, rm)?;
+                    let exact = !k.inexact();
+
+                    if k.is_zero() {
+                        (k, exact)
+                    } else {
+                        (k.div(&d, p, rm)?, exact)
+                    }
+                }
+            }
+        };
+
+        if ret.is_zero() {
+            ret = Self::new2(p, self.sign(), true)?;
+        } else {
+            ret.set_precision(p, rm)?;
+        }
+
+        ret.set_inexact(self.inexact() || !exact || ret.inexact());
+
+        Ok((ret, exact))
+    }
+
+    // Rounds `self` to a multiple of 2^`shift`.
+    fn quantize_bin(&self, shift: isize, rm: RoundingMode) -> Result<(Self, bool), Error> {
+        let e = self.exponent() as isize - shift;
+
+        if e >= self.mantissa_max_bit_len() as isize {
+            // self is already a multiple of 2^shift
+            return Ok((self.clone()?, true));
+        }
+
+        // if |self / 2^shift| < 1/4, the result of rounding is the same as for a number in [1/4, 1/2)
+        let mut y = self.clone()?;
+        y.set_inexact(false);
+        y.set_exponent(e.max(-1) as Exponent);
+
+        let mut k = y.round(0, rm)?;
+        let exact = !k.inexact();
+
+        if !k.is_zero() {
+            let e = k.exponent() as isize + shift;
+
+            if e > EXPONENT_MAX as isize {
+                return Err(Error::ExponentOverflow(self.sign()));
+            }
+
+            k.set_exponent(e as Exponent);
+        }
+
+        Ok((k, exact))
+    }
+
+    // Rounds `self` / `d` to an integer, where `d` is a positive integer.
+    fn quantize_div(&self, d: &Self, rm: RoundingMode) -> Result<(Self, bool), Error> {
+        // truncated quotient q, and the remainder r = self - q * d
+        let p_q = round_p((self.exponent() as isize - d.exponent() as isize + 2).max(1) as usize)
+            + WORD_BIT_SIZE;
+        let q = self
+            .div(d, p_q, RoundingMode::ToZero)?
+            .round(0, RoundingMode::ToZero)?;
+
+        let r = if q.is_zero() {
+            self.clone()?
+        } else {
+            self.sub_full_prec(&q.mul_full_prec(d)?)?
+        };
+
+        if r.is_zero() {
+            let mut q = q;
+            q.set_inexact(false);
+            return Ok((q, true));
+        }
+
+        // q + f, where f is 1/4, 1/2, or 3/4 depending on how |r| compares to d/2, rounds like self / d
+        let mut r2 = r.abs()?;
+        r2.set_exponent(r2.exponent() + 1);
+
+        let c = r2.cmp(d);
+        let mut f = Self::from_word(if c > 0 { 3 } else { 1 }, WORD_BIT_SIZE)?;
+        f.set_exponent(if c < 0 { -1 } else { 0 });
+        f.set_sign(self.sign());
+
+        let t = if q.is_zero() { f } else { q.add_full_prec(&f)? };
+
+        let mut k = t.round(0, rm)?;
+        k.set_inexact(true);
+
+        Ok((k, false))
+    }
+
     #[cfg(feature = "random")]
     /// Returns a random normalized (not subnormal) BigFloat number with exponent in the range
     /// from `exp_from` to `exp_to` inclusive. The sign can be positive and negative. Zero is excluded.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     /// Function does not follow any specific distribution law.
     /// The intended use of this function is for testing.
     ///
@@ -1322,7 +2411,7 @@ impl BigFloatNumber {
             return Err(Error::InvalidArgument);
         }
 
-        let m = Mantissa::random_normal(p)?;
+        let m = Mantissa::random_normal(round_p(p))?;
         let e = if exp_from < exp_to {
             (rand::random::<isize>().abs() % (exp_to as isize - exp_from as isize)
                 + exp_from as isize) as Exponent
@@ -1364,6 +2453,56 @@ impl BigFloatNumber {
             .map(|_| {})
     }
 
+    /// Extends the mantissa of `self` with zero bits to precision `p`. The value of `self` does not change, and no rounding is performed.
+    /// If `p` is not greater than the length of the mantissa of `self`, the function has no effect.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn grow_precision(&mut self, p: usize) -> Result<(), Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if p > self.mantissa_max_bit_len() {
+            self.m.set_length(p)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the precision of `self` to `p` using stochastic rounding.
+    /// If the new precision is smaller than the existing one, the number is rounded away from zero with probability
+    /// equal to the discarded part of the mantissa divided by the unit in the last place, and towards zero otherwise.
+    /// Random numbers are taken from `rng`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    #[cfg(feature = "random")]
+    pub fn set_precision_stochastic<R: rand::Rng + ?Sized>(
+        &mut self,
+        p: usize,
+        rng: &mut R,
+    ) -> Result<(), Error> {
+        Self::p_assertion(p)?;
+
+        let rm = if self.mantissa_max_bit_len() > p
+            && p > 0
+            && self
+                .m
+                .stochastic_round_away(self.mantissa_max_bit_len() - p, rng)
+        {
+            RoundingMode::FromZero
+        } else {
+            RoundingMode::ToZero
+        };
+
+        self.set_precision(p, rm)
+    }
+
     /// Try to round and then set the precision to `p`, given `self` has `s` correct digits in mantissa.
     /// Returns true if rounding succeeded. If the fuction returns `false`, `self` is still modified, and should be discarded.
     ///
@@ -1377,7 +2516,18 @@ impl BigFloatNumber {
         rm: RoundingMode,
         s: usize,
     ) -> Result<bool, Error> {
-        self.set_precision_internal(p, rm, true, s)
+        let ret = self.set_precision_internal(p, rm, true, s);
+
+        #[cfg(feature = "tracing")]
+        if let Ok(roundable) = ret {
+            tracing::trace!(precision = p, working_precision = s, roundable, "rounding");
+
+            if !roundable {
+                tracing::debug!(working_precision = s, "recomputation");
+            }
+        }
+
+        ret
     }
 
     fn set_precision_internal(
@@ -1429,13 +2579,13 @@ impl BigFloatNumber {
             }
         }
 
-        self.m.set_length(p)?;
+        self.m.set_length(round_p(p))?;
 
         Ok(true)
     }
 
     /// Computes the reciprocal of a number with precision `p`. The result is rounded using the rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -1446,6 +2596,87 @@ impl BigFloatNumber {
         ONE.div(self, p, rm)
     }
 
+    /// Computes the reciprocal of a number with precision `p` by refining `prev`, a previously computed approximation
+    /// of the reciprocal of `self`, with Newton's iteration `y + y * (1 - self * y)`.
+    /// Each iteration doubles the number of correct bits, so a single iteration is needed if `prev` has slightly more than half of `p` correct bits.
+    /// If `prev` is not close enough to the reciprocal, the result is computed with division.
+    /// The result is rounded using the rounding mode `rm`, and is the same as the result of `reciprocal`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - DivisionByZero: `self` is zero.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn reciprocal_refine(
+        &self,
+        prev: &Self,
+        p: usize,
+        rm: RoundingMode,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        // self * prev must be close to 1, and the exponent of the reciprocal must be in range.
+        let esum = self.e as isize + prev.e as isize;
+        if self.is_zero()
+            || prev.is_zero()
+            || self.is_subnormal()
+            || prev.is_subnormal()
+            || self.s != prev.s
+            || !(1..=2).contains(&esum)
+            || (self.e as isize) < EXPONENT_MIN as isize + 3
+        {
+            return self.reciprocal(p, rm);
+        }
+
+        // the computation is done with the exponent of the argument set to 0, so the correction does not underflow.
+        let mut x = self.clone()?;
+        x.set_exponent(0);
+
+        let mut y = prev.clone()?;
+        y.set_exponent(esum as Exponent);
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + 2 * p_inc;
+
+        loop {
+            y.set_precision(p_wrk, RoundingMode::None)?;
+
+            // d = 1 - x * y is the relative error of y.
+            let xy = x.mul(&y, p_wrk, RoundingMode::None)?;
+            let d = ONE.sub(&xy, p_wrk, RoundingMode::None)?;
+
+            if d.is_zero() || d.e > -2 {
+                // y is exact, or too far from the reciprocal.
+                return self.reciprocal(p, rm);
+            }
+
+            let t = y.mul(&d, p_wrk, RoundingMode::None)?;
+            y = y.add(&t, p_wrk, RoundingMode::None)?;
+
+            // the error of y is about d^2 plus a few ulp.
+            let k = (-d.e) as usize;
+            let s = (2 * k - 2).min(p_wrk - 4);
+            let s_aligned = s / WORD_BIT_SIZE * WORD_BIT_SIZE;
+
+            if s_aligned > p {
+                // the exponent is in range, because y is in the range (1, 2].
+                let mut ret = y.clone()?;
+                ret.set_exponent((y.e as isize - self.e as isize) as Exponent);
+                if ret.try_set_precision(p, rm, s_aligned)? {
+                    return Ok(ret);
+                }
+            }
+
+            if s >= p_wrk - 4 {
+                p_wrk += p_inc;
+                p_inc = round_p(p_wrk / 5);
+            }
+        }
+    }
+
     /// Sets the sign of `self`.
     pub fn set_sign(&mut self, s: Sign) {
         self.s = s;
@@ -1485,7 +2716,7 @@ impl BigFloatNumber {
     }
 
     /// Constructs BigFloatNumber with precision `p` from a signed integer value `i`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -1499,7 +2730,7 @@ impl BigFloatNumber {
     }
 
     /// Constructs BigFloatNumber with precision `p` from an unsigned integer value `u`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -1530,7 +2761,7 @@ impl BigFloatNumber {
             }
 
             Ok(BigFloatNumber {
-                m: Mantissa::from_words(p, &words)?,
+                m: Mantissa::from_words(round_p(p), &words)?,
                 e: (SZ - shift) as Exponent,
                 s: Sign::Pos,
                 inexact: false,
@@ -1574,7 +2805,7 @@ macro_rules! impl_int_conv {
     ($s:ty, $u:ty, $from_s:ident, $from_u:ident) => {
         impl BigFloatNumber {
             /// Constructs BigFloatNumber with precision `p` from a signed integer value `i`.
-            /// Precision is rounded upwards to the word size.
+            /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
             ///
             /// ## Errors
             ///
@@ -1588,7 +2819,7 @@ macro_rules! impl_int_conv {
             }
 
             /// Constructs BigFloatNumber with precision `p` from an unsigned integer value `u`.
-            /// Precision is rounded upwards to the word size.
+            /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
             ///
             /// ## Errors
             ///
@@ -1612,6 +2843,25 @@ impl_int_conv!(i16, u16, from_i16, from_u16);
 impl_int_conv!(i32, u32, from_i32, from_u32);
 impl_int_conv!(i64, u64, from_i64, from_u64);
 
+impl BigFloatNumber {
+    // Writes the structure of the number under the name `name` for debugging purposes.
+    pub(crate) fn fmt_debug(&self, name: &str, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct(name)
+            .field("s", &self.s)
+            .field("e", &self.e)
+            .field("p", &self.mantissa_max_bit_len())
+            .field("inexact", &self.inexact)
+            .field("m", &self.m)
+            .finish()
+    }
+}
+
+impl Debug for BigFloatNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_debug("BigFloatNumber", f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -1776,6 +3026,13 @@ mod tests {
         assert!(d1.abs_cmp(&d2) < 0);
         assert!(d2.abs_cmp(&d1) > 0);
 
+        // subnormal and zero
+        d3 = BigFloatNumber::new(WORD_BIT_SIZE).unwrap();
+        assert!(d1.abs_cmp(&d3) > 0);
+        assert!(d3.abs_cmp(&d1) < 0);
+        assert!(d2.cmp(&d3) > 0);
+        assert!(d3.cmp(&d2) < 0);
+
         // nan
         assert!(BigFloatNumber::from_f64(p, f64::NAN).unwrap_err() == Error::InvalidArgument);
 
@@ -1896,7 +3153,7 @@ mod tests {
         for _ in 0..10000 {
             let p1 = (random::<usize>() % p_rng + p_min) * WORD_BIT_SIZE;
             let p2 = (random::<usize>() % p_rng + p_min) * WORD_BIT_SIZE;
-            let p = (random::<usize>() % p_rng + p_min) * WORD_BIT_SIZE;
+            let p = round_p((random::<usize>() % p_rng + p_min) * WORD_BIT_SIZE);
 
             d1 = BigFloatNumber::random_normal(p1, -(p1 as Exponent) / 2, p1 as Exponent).unwrap();
             d2 = BigFloatNumber::random_normal(p2, -(p2 as Exponent) / 2, p2 as Exponent).unwrap();
@@ -1955,7 +3212,7 @@ mod tests {
 
         d3 = d1.sub(&d2, WORD_BIT_SIZE, RoundingMode::None).unwrap();
 
-        assert_eq!(w, d3.mantissa().digits()[0]);
+        assert_eq!(Some(&w), d3.mantissa().digits().last());
 
         // increase precision
         d1 = BigFloatNumber::from_raw_parts(
@@ -2067,20 +3324,20 @@ mod tests {
         let mut d4 = d1
             .add(&d2, WORD_BIT_SIZE * 3, RoundingMode::ToEven)
             .unwrap();
-        d4.set_precision(WORD_BIT_SIZE, RoundingMode::ToEven)
+        d4.set_precision(round_p(WORD_BIT_SIZE), RoundingMode::ToEven)
             .unwrap();
 
-        assert!(d4.mantissa_max_bit_len() == WORD_BIT_SIZE);
+        assert!(d4.mantissa_max_bit_len() == round_p(WORD_BIT_SIZE));
         assert!(d3.cmp(&d4) == 0);
 
         d3 = d1.sub(&d2, WORD_BIT_SIZE, RoundingMode::ToEven).unwrap();
         let mut d4 = d1
             .sub(&d2, WORD_BIT_SIZE * 3, RoundingMode::ToEven)
             .unwrap();
-        d4.set_precision(WORD_BIT_SIZE, RoundingMode::ToEven)
+        d4.set_precision(round_p(WORD_BIT_SIZE), RoundingMode::ToEven)
             .unwrap();
 
-        assert!(d4.mantissa_max_bit_len() == WORD_BIT_SIZE);
+        assert!(d4.mantissa_max_bit_len() == round_p(WORD_BIT_SIZE));
         assert!(d3.cmp(&d4) == 0);
 
         // full prec
@@ -2166,7 +3423,13 @@ mod tests {
 
             if !d2.is_zero() {
                 let d3 = d1.mul_full_prec(&d2).unwrap();
-                let d4 = d1.mul(&d2, p1 + p2, rm).unwrap();
+                let d4 = d1
+                    .mul(
+                        &d2,
+                        d1.mantissa_max_bit_len() + d2.mantissa_max_bit_len(),
+                        rm,
+                    )
+                    .unwrap();
                 //println!("\n{:?}\n{:?}\n{:?}\n{:?}", d1,d2,d3,d4);
                 assert!(d3.cmp(&d4) == 0);
             }
@@ -2233,7 +3496,7 @@ mod tests {
             .mul(&d2, WORD_BIT_SIZE * 5, RoundingMode::ToEven)
             .unwrap();
 
-        assert!(d4.mantissa_max_bit_len() == WORD_BIT_SIZE * 5);
+        assert!(d4.mantissa_max_bit_len() == round_p(WORD_BIT_SIZE * 5));
         assert!(d3.cmp(&d4) == 0);
 
         let d4 = d1
@@ -2243,20 +3506,20 @@ mod tests {
         assert!(d4.mantissa_max_bit_len() == WORD_BIT_SIZE * 4);
         assert!(d3.cmp(&d4) == 0);
 
-        d3.set_precision(WORD_BIT_SIZE * 3, RoundingMode::ToEven)
+        d3.set_precision(round_p(WORD_BIT_SIZE * 3), RoundingMode::ToEven)
             .unwrap();
         let d4 = d1
             .mul(&d2, WORD_BIT_SIZE * 3, RoundingMode::ToEven)
             .unwrap();
 
-        assert!(d4.mantissa_max_bit_len() == WORD_BIT_SIZE * 3);
+        assert!(d4.mantissa_max_bit_len() == round_p(WORD_BIT_SIZE * 3));
         assert!(d3.cmp(&d4) == 0);
 
-        d3.set_precision(WORD_BIT_SIZE, RoundingMode::ToEven)
+        d3.set_precision(round_p(WORD_BIT_SIZE), RoundingMode::ToEven)
             .unwrap();
         let d4 = d1.mul(&d2, WORD_BIT_SIZE, RoundingMode::ToEven).unwrap();
 
-        assert!(d4.mantissa_max_bit_len() == WORD_BIT_SIZE);
+        assert!(d4.mantissa_max_bit_len() == round_p(WORD_BIT_SIZE));
         assert!(d3.cmp(&d4) == 0);
 
         d1 = BigFloatNumber::from_i8(2, WORD_BIT_SIZE * 2).unwrap();
@@ -2267,7 +3530,7 @@ mod tests {
             .unwrap();
 
         let words = {
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(word32))]
             {
                 [
                     12297829382473034411,
@@ -2277,13 +3540,17 @@ mod tests {
                     12297829382473034410,
                 ]
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(all(word32, not(feature = "portable")))]
             {
                 [2863311531, 2863311530, 2863311530, 2863311530, 2863311530]
             }
+            #[cfg(all(word32, feature = "portable"))]
+            {
+                [2863311531, 2863311530, 2863311530, 2863311530, 2863311530, 2863311530]
+            }
         };
 
-        assert!(d3.mantissa_max_bit_len() == WORD_BIT_SIZE * 5);
+        assert!(d3.mantissa_max_bit_len() == round_p(WORD_BIT_SIZE * 5));
         assert!(d3.mantissa().digits() == words);
 
         d3 = d1
@@ -2291,33 +3558,41 @@ mod tests {
             .unwrap();
 
         let words = {
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(word32))]
             {
                 [12297829382473034411, 12297829382473034410, 12297829382473034410]
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(all(word32, not(feature = "portable")))]
             {
                 [2863311531, 2863311530, 2863311530]
             }
+            #[cfg(all(word32, feature = "portable"))]
+            {
+                [2863311531, 2863311530, 2863311530, 2863311530]
+            }
         };
 
-        assert!(d3.mantissa_max_bit_len() == WORD_BIT_SIZE * 3);
+        assert!(d3.mantissa_max_bit_len() == round_p(WORD_BIT_SIZE * 3));
         assert!(d3.mantissa().digits() == words);
 
         d3 = d1.div(&d2, WORD_BIT_SIZE, RoundingMode::ToEven).unwrap();
 
         let words = {
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(word32))]
             {
                 [12297829382473034411]
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(all(word32, not(feature = "portable")))]
             {
                 [2863311531]
             }
+            #[cfg(all(word32, feature = "portable"))]
+            {
+                [2863311531, 2863311530]
+            }
         };
 
-        assert!(d3.mantissa_max_bit_len() == WORD_BIT_SIZE);
+        assert!(d3.mantissa_max_bit_len() == round_p(WORD_BIT_SIZE));
         assert!(d3.mantissa().digits() == words);
 
         // reciprocal
@@ -2383,7 +3658,7 @@ mod tests {
             .unwrap();
 
         let words = {
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(word32))]
             {
                 [
                     12297829382473034411,
@@ -2393,10 +3668,14 @@ mod tests {
                     12297829382473034410,
                 ]
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(all(word32, not(feature = "portable")))]
             {
                 [2863311531, 2863311530, 2863311530, 2863311530, 2863311530]
             }
+            #[cfg(all(word32, feature = "portable"))]
+            {
+                [2863311531, 2863311530, 2863311530, 2863311530, 2863311530, 2863311530]
+            }
         };
 
         assert!(d2.mantissa().digits() == words);
@@ -2404,14 +3683,18 @@ mod tests {
         d2 = d1.reciprocal(WORD_BIT_SIZE, RoundingMode::ToEven).unwrap();
 
         let words = {
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(word32))]
             {
                 [12297829382473034411]
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(all(word32, not(feature = "portable")))]
             {
                 [2863311531]
             }
+            #[cfg(all(word32, feature = "portable"))]
+            {
+                [2863311531, 2863311530]
+            }
         };
 
         assert!(d2.mantissa().digits() == words);
@@ -2659,8 +3942,8 @@ mod tests {
         }
 
         for _ in 0..1000 {
-            let p1 = (random::<usize>() % p_rng + p_min) * WORD_BIT_SIZE;
-            let p2 = (random::<usize>() % p_rng + p_min) * WORD_BIT_SIZE;
+            let p1 = round_p((random::<usize>() % p_rng + p_min) * WORD_BIT_SIZE);
+            let p2 = round_p((random::<usize>() % p_rng + p_min) * WORD_BIT_SIZE);
             let p = p1.max(p2);
 
             d1 = BigFloatNumber::random_normal(
@@ -2789,14 +4072,14 @@ mod tests {
         assert_eq!(d1.sign(), Sign::Neg);
 
         let d1 = BigFloatNumber::from_words(&[3, 1], Sign::Pos, EXPONENT_MAX).unwrap();
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(word32))]
         {
             assert_eq!(
                 d1.mantissa().digits(),
                 [0x8000000000000000u64, 0x8000000000000001u64]
             );
         }
-        #[cfg(target_arch = "x86")]
+        #[cfg(word32)]
         {
             assert_eq!(d1.mantissa().digits(), [0x80000000u32, 0x80000001u32]);
         }
@@ -2820,11 +4103,11 @@ mod tests {
         assert_eq!(d1.sign(), Sign::Pos);
 
         let words = {
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(word32))]
             {
                 [3, 0x8000000000000000u64]
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(word32)]
             {
                 [3, 0x80000000u32]
             }
@@ -2851,17 +4134,609 @@ mod tests {
     }
 
     #[test]
-    fn test_rounding() {
-        // trailing bits
-        // 0 0000
-        // 0 0001
+    fn test_quantize() {
+        let p = 128;
+
+        let q = |x: f64, e: Exponent, rdx: Radix, rm: RoundingMode| {
+            let (v, exact) = BigFloatNumber::from_f64(p, x)
+                .unwrap()
+                .quantize(e, rdx, p, rm)
+                .unwrap();
+            assert!(v.inexact() == !exact);
+            (v, exact)
+        };
+
+        let d = |n: i64, s: usize| {
+            let n = BigFloatNumber::from_i64(n, 64).unwrap();
+            let d = BigFloatNumber::from_word(10, p)
+                .unwrap()
+                .powi(s, p, RoundingMode::None)
+                .unwrap();
+            n.div(&d, p, RoundingMode::ToEven).unwrap()
+        };
+
+        // ties
+        for (rm, r_pos, r_neg) in [
+            (RoundingMode::ToEven, 2, -2),
+            (RoundingMode::ToOdd, 3, -3),
+            (RoundingMode::ToNearestAway, 3, -3),
+            (RoundingMode::Up, 3, -2),
+            (RoundingMode::Down, 2, -3),
+            (RoundingMode::ToZero, 2, -2),
+            (RoundingMode::FromZero, 3, -3),
+        ] {
+            let (v, exact) = q(2.5, 0, Radix::Dec, rm);
+            assert!(v.cmp(&d(r_pos, 0)) == 0 && !exact);
+            let (v, exact) = q(-2.5, 0, Radix::Dec, rm);
+            assert!(v.cmp(&d(r_neg, 0)) == 0 && !exact);
+        }
+
+        // positive decimal exponent
+        assert!(
+            q(1234.5678, 2, Radix::Dec, RoundingMode::ToEven)
+                .0
+                .cmp(&d(1200, 0))
+                == 0
+        );
+        assert!(
+            q(1234.5678, 1, Radix::Dec, RoundingMode::ToEven)
+                .0
+                .cmp(&d(1230, 0))
+                == 0
+        );
+        assert!(
+            q(1234.5678, 3, Radix::Dec, RoundingMode::Up)
+                .0
+                .cmp(&d(2000, 0))
+                == 0
+        );
+        assert!(
+            q(-1250.0, 2, Radix::Dec, RoundingMode::ToEven)
+                .0
+                .cmp(&d(-1200, 0))
+                == 0
+        );
+        assert!(
+            q(-1350.0, 2, Radix::Dec, RoundingMode::ToEven)
+                .0
+                .cmp(&d(-1400, 0))
+                == 0
+        );
+        assert!(
+            q(-1350.1, 2, Radix::Dec, RoundingMode::ToEven)
+                .0
+                .cmp(&d(-1400, 0))
+                == 0
+        );
+        assert!(
+            q(-1349.9, 2, Radix::Dec, RoundingMode::ToEven)
+                .0
+                .cmp(&d(-1300, 0))
+                == 0
+        );
+        assert!(
+            q(7.0, 10, Radix::Dec, RoundingMode::FromZero)
+                .0
+                .cmp(&d(10_000_000_000, 0))
+                == 0
+        );
+        assert!(q(7.0, 10, Radix::Dec, RoundingMode::ToEven).0.is_zero());
+        let (v, exact) = q(1300.0, 2, Radix::Dec, RoundingMode::ToEven);
+        assert!(v.cmp(&d(1300, 0)) == 0 && exact);
+
+        // negative decimal exponent
+        let (v, exact) = q(0.125, -2, Radix::Dec, RoundingMode::ToEven);
+        assert!(v.cmp(&d(12, 2)) == 0 && !exact);
+        let (v, exact) = q(1.005, -2, Radix::Dec, RoundingMode::ToEven);
+        assert!(v.cmp(&d(100, 2)) == 0 && !exact);
+        let (v, exact) = q(-0.13, -2, Radix::Dec, RoundingMode::ToEven);
+        assert!(v.cmp(&d(-13, 2)) == 0 && !exact);
+        let (v, exact) = q(0.25, -2, Radix::Dec, RoundingMode::ToEven);
+        assert!(v.cmp(&d(25, 2)) == 0 && exact);
+        assert!(q(1e-30, 0, Radix::Dec, RoundingMode::Up).0.cmp(&d(1, 0)) == 0);
+        assert!(q(1e-30, 0, Radix::Dec, RoundingMode::ToEven).0.is_zero());
+        assert!(
+            q(-1e-30, -5, Radix::Dec, RoundingMode::Down)
+                .0
+                .cmp(&d(-1, 5))
+                == 0
+        );
+
+        // binary radices
+        let (v, exact) = q(5.75, -1, Radix::Bin, RoundingMode::ToEven);
+        assert!(v.cmp(&d(6, 0)) == 0 && !exact);
+        let (v, exact) = q(5.75, -2, Radix::Bin, RoundingMode::ToEven);
+        assert!(v.cmp(&d(575, 2)) == 0 && exact);
+        let (v, exact) = q(5.75, 2, Radix::Bin, RoundingMode::ToEven);
+        assert!(v.cmp(&d(4, 0)) == 0 && !exact);
+        let (v, _) = q(-5.75, 3, Radix::Bin, RoundingMode::Down);
+        assert!(v.cmp(&d(-8, 0)) == 0);
+        let (v, _) = q(0x1234 as f64, 1, Radix::Hex, RoundingMode::ToZero);
+        assert!(v.cmp(&d(0x1230, 0)) == 0);
+        let (v, _) = q(0x1234 as f64, 2, Radix::Hex, RoundingMode::Up);
+        assert!(v.cmp(&d(0x1300, 0)) == 0);
+        let (v, _) = q(0o1234 as f64, 1, Radix::Oct, RoundingMode::ToEven);
+        assert!(v.cmp(&d(0o1240, 0)) == 0);
+        let (v, exact) = q(1e-30, 0, Radix::Bin, RoundingMode::FromZero);
+        assert!(v.cmp(&d(1, 0)) == 0 && !exact);
+        let (v, exact) = q(-1e-30, 100, Radix::Bin, RoundingMode::ToEven);
+        assert!(v.is_zero() && v.is_negative() && !exact);
+
+        // zero
+        let (v, exact) = BigFloatNumber::new(p)
+            .unwrap()
+            .quantize(-2, Radix::Dec, p, RoundingMode::ToEven)
+            .unwrap();
+        assert!(v.is_zero() && exact);
+    }
+
+    #[test]
+    #[cfg(all(feature = "tracing", feature = "std"))]
+    fn test_tracing() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        #[derive(Default)]
+        struct Counter {
+            spans: AtomicUsize,
+            cancellations: AtomicUsize,
+            roundings: AtomicUsize,
+        }
+
+        impl Subscriber for &'static Counter {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(self.spans.fetch_add(1, Ordering::Relaxed) as u64 + 1)
+            }
+
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                let fields = event.metadata().fields();
+                if fields.field("cancelled_bits").is_some() {
+                    self.cancellations.fetch_add(1, Ordering::Relaxed);
+                }
+                if fields.field("working_precision").is_some() {
+                    self.roundings.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        let counter: &'static Counter = Box::leak(Box::default());
+
+        tracing::subscriber::with_default(counter, || {
+            let mut cc = crate::Consts::new().unwrap();
+            let rm = RoundingMode::ToEven;
+
+            let d1 = BigFloatNumber::from_f64(128, 1.0000001).unwrap();
+            let d2 = BigFloatNumber::from_word(1, 128).unwrap();
+            d1.sub(&d2, 128, rm).unwrap();
+
+            assert_eq!(counter.cancellations.load(Ordering::Relaxed), 1);
+
+            let d1 = crate::BigFloat::from_word(3, 128);
+            d1.ln(128, rm, &mut cc);
+
+            assert!(counter.spans.load(Ordering::Relaxed) > 0);
+            assert!(counter.roundings.load(Ordering::Relaxed) > 0);
+        });
+    }
+
+    #[test]
+    fn test_grow_precision() {
+        let rm = RoundingMode::ToEven;
+
+        let mut d1 = BigFloatNumber::from_f64(64, 1.5).unwrap();
+        d1.set_inexact(true);
+        d1.grow_precision(200).unwrap();
+        assert_eq!(d1.mantissa_max_bit_len(), round_p(200));
+        assert_eq!(d1.precision(), round_p(200));
+        assert!(d1.inexact());
+        assert!(d1.cmp(&BigFloatNumber::from_f64(64, 1.5).unwrap()) == 0);
+
+        // shrinking has no effect
+        d1.grow_precision(64).unwrap();
+        assert_eq!(d1.mantissa_max_bit_len(), round_p(200));
+
+        // subnormal
+        let mut d1 = BigFloatNumber::min_positive(128).unwrap();
+        let p = d1.precision();
+        d1.grow_precision(256).unwrap();
+        assert_eq!(d1.precision(), p + 128);
+        assert!(d1.cmp(&BigFloatNumber::min_positive(128).unwrap()) == 0);
+
+        // zero
+        let mut d1 = BigFloatNumber::new(64).unwrap();
+        d1.grow_precision(128).unwrap();
+        assert!(d1.is_zero());
+        assert_eq!(d1.mantissa_max_bit_len(), 128);
+
+        assert!(d1.grow_precision(usize::MAX).unwrap_err() == Error::InvalidArgument);
+
+        // value after growing and rounding back is unchanged
+        let d1 = BigFloatNumber::from_word(1, 128)
+            .unwrap()
+            .div(&BigFloatNumber::from_word(3, 128).unwrap(), 128, rm)
+            .unwrap();
+        let mut d2 = d1.clone().unwrap();
+        d2.grow_precision(1024).unwrap();
+        d2.set_precision(128, rm).unwrap();
+        assert!(d1.cmp(&d2) == 0);
+    }
+
+    #[test]
+    fn test_compact() {
+        let rm = RoundingMode::ToEven;
+
+        // integer
+        let d0 = BigFloatNumber::from_word(12345, 64).unwrap();
+        let mut d1 = d0.clone().unwrap();
+        d1.set_precision(1024, rm).unwrap();
+        d1.compact();
+        assert_eq!(d1.mantissa_max_bit_len(), WORD_BIT_SIZE);
+        assert_eq!(d1.allocated_bytes(), WORD_BIT_SIZE / 8);
+        assert!(d1.cmp(&d0) == 0);
+
+        // bits in the second word remain
+        let mut d0 = BigFloatNumber::from_word(1, 256).unwrap();
+        d0.set_exponent(-(WORD_BIT_SIZE as Exponent));
+        let d0 = d0.add(&ONE, 256, rm).unwrap();
+        let mut d1 = d0.clone().unwrap();
+        d1.compact();
+        assert_eq!(d1.mantissa_max_bit_len(), 2 * WORD_BIT_SIZE);
+        assert!(d1.cmp(&d0) == 0);
+
+        // nothing to remove
+        let d0 = ONE
+            .div(&BigFloatNumber::from_word(3, 256).unwrap(), 256, rm)
+            .unwrap();
+        let mut d1 = d0.clone().unwrap();
+        d1.compact();
+        assert_eq!(d1.mantissa_max_bit_len(), 256);
+        assert!(d1.cmp(&d0) == 0);
+        assert!(d1.inexact());
+
+        // subnormal
+        let mut d1 = BigFloatNumber::min_positive(256).unwrap();
+        d1.compact();
+        assert_eq!(d1.mantissa_max_bit_len(), 256);
+        assert_eq!(d1.precision(), 1);
+
+        let mut d0 = BigFloatNumber::min_positive_normal(256).unwrap();
+        d0.set_exponent(EXPONENT_MIN);
+        let d0 = d0
+            .div(&BigFloatNumber::from_word(4, 64).unwrap(), 256, rm)
+            .unwrap();
+        assert!(d0.is_subnormal());
+        let mut d1 = d0.clone().unwrap();
+        d1.compact();
+        assert_eq!(d1.mantissa_max_bit_len(), WORD_BIT_SIZE);
+        assert_eq!(d1.precision(), WORD_BIT_SIZE - 2);
+        assert!(d1.cmp(&d0) == 0);
+
+        // zero
+        let mut d1 = BigFloatNumber::new(256).unwrap();
+        d1.compact();
+        assert!(d1.is_zero());
+        assert_eq!(d1.mantissa_max_bit_len(), WORD_BIT_SIZE);
+    }
+
+    #[test]
+    fn test_int_ratio() {
+        let rm = RoundingMode::ToEven;
+
+        // -0.375 = -3 * 2^-3
+        let d1 = BigFloatNumber::from_f64(128, -0.375).unwrap();
+        let (s, m, e) = d1.to_int_ratio().unwrap();
+        assert_eq!(
+            (s, m.as_slice(), e),
+            (Sign::Neg, [3 as Word].as_slice(), -3)
+        );
+        let d2 = BigFloatNumber::from_int_ratio(s, &m, e).unwrap();
+        assert!(d2.cmp(&d1) == 0);
+        assert_eq!(d2.mantissa_max_bit_len(), round_p(WORD_BIT_SIZE));
+
+        // zero
+        let z = BigFloatNumber::new(128).unwrap();
+        let (_, m, e) = z.to_int_ratio().unwrap();
+        assert!(m.is_empty() && e == 0);
+        assert!(BigFloatNumber::from_int_ratio(Sign::Pos, &[0, 0], 5)
+            .unwrap()
+            .is_zero());
+
+        // large integer words with leading zero words
+        let d2 = BigFloatNumber::from_int_ratio(Sign::Pos, &[WORD_MAX, 1, 0], 0).unwrap();
+        assert_eq!(d2.exponent() as usize, WORD_BIT_SIZE + 1);
+        assert_eq!(d2.mantissa_max_bit_len(), round_p(WORD_BIT_SIZE + 1));
+        let (_, m, e) = d2.to_int_ratio().unwrap();
+        assert_eq!((m.as_slice(), e), ([WORD_MAX, 1].as_slice(), 0));
+
+        // round trip of random numbers including subnormal
+        for _ in 0..1000 {
+            let mut d1 = BigFloatNumber::random_normal(320, -100, 100).unwrap();
+            if random::<u8>() & 1 == 0 {
+                d1 = d1
+                    .div(&BigFloatNumber::from_word(3, 64).unwrap(), 320, rm)
+                    .unwrap();
+            }
+            if random::<u8>() & 7 == 0 {
+                d1 = random_subnormal(320);
+            }
+
+            let (s, m, e) = d1.to_int_ratio().unwrap();
+            assert!(m.is_empty() || m[0] & 1 == 1);
+            let d2 = BigFloatNumber::from_int_ratio(s, &m, e).unwrap();
+            assert!(d2.cmp(&d1) == 0);
+            assert!(d2.mantissa_max_bit_len() <= 320 || d1.is_subnormal());
+            assert!(!d2.inexact());
+        }
+
+        // subnormal result
+        let d2 =
+            BigFloatNumber::from_int_ratio(Sign::Neg, &[5], EXPONENT_MIN as isize - 10).unwrap();
+        assert!(d2.is_subnormal());
+        assert_eq!(d2.exponent(), EXPONENT_MIN);
+        let (s, m, e) = d2.to_int_ratio().unwrap();
+        assert_eq!(
+            (s, m.as_slice(), e),
+            (
+                Sign::Neg,
+                [5 as Word].as_slice(),
+                EXPONENT_MIN as isize - 10
+            )
+        );
+
+        // overflow
+        assert!(
+            BigFloatNumber::from_int_ratio(Sign::Pos, &[1], EXPONENT_MAX as isize).unwrap_err()
+                == Error::ExponentOverflow(Sign::Pos)
+        );
+        assert!(BigFloatNumber::from_int_ratio(Sign::Pos, &[1], EXPONENT_MAX as isize - 1).is_ok());
+    }
+
+    #[test]
+    fn test_from_ratio() {
+        // 1/3 rounded once matches the division of exact numbers
+        for rm in [
+            RoundingMode::Up,
+            RoundingMode::Down,
+            RoundingMode::ToEven,
+            RoundingMode::FromZero,
+        ] {
+            let d1 = BigFloatNumber::from_ratio(Sign::Neg, &[1], &[3], 128, rm).unwrap();
+            let d2 = BigFloatNumber::from_i8(-1, 64)
+                .unwrap()
+                .div(&BigFloatNumber::from_word(3, 64).unwrap(), 128, rm)
+                .unwrap();
+            assert!(d1.identical(&d2));
+            assert!(d1.inexact());
+        }
+
+        // numerator wider than the precision: converting first would round twice
+        let num = [1, 0, 1 << (WORD_BIT_SIZE - 1)];
+        let d1 = BigFloatNumber::from_ratio(Sign::Pos, &num, &[3], 64, RoundingMode::Up).unwrap();
+        let n = BigFloatNumber::from_int_ratio(Sign::Pos, &num, 0).unwrap();
+        let d2 = n
+            .div(
+                &BigFloatNumber::from_word(3, 64).unwrap(),
+                64,
+                RoundingMode::Up,
+            )
+            .unwrap();
+        assert!(d1.identical(&d2));
+
+        // exact quotient
+        let d1 = BigFloatNumber::from_ratio(Sign::Pos, &[0, 6], &[0, 4], 64, RoundingMode::None)
+            .unwrap();
+        assert!(d1.cmp(&BigFloatNumber::from_f64(64, 1.5).unwrap()) == 0);
+        assert!(!d1.inexact());
+
+        assert!(
+            BigFloatNumber::from_ratio(Sign::Pos, &[1], &[0], 64, RoundingMode::None).unwrap_err()
+                == Error::DivisionByZero
+        );
+        assert!(
+            BigFloatNumber::from_ratio(Sign::Pos, &[0], &[7], 64, RoundingMode::None)
+                .unwrap()
+                .is_zero()
+        );
+    }
+
+    #[test]
+    fn test_identical() {
+        let rm = RoundingMode::ToEven;
+
+        let d1 = BigFloatNumber::from_word(3, 128).unwrap();
+        let mut d2 = d1.clone().unwrap();
+        assert!(d1.identical(&d2));
+
+        // inexact flag is ignored
+        d2.set_inexact(true);
+        assert!(d1.identical(&d2));
+
+        // equal values with different precision
+        let d2 = BigFloatNumber::from_word(3, 64).unwrap();
+        assert!(d1.cmp(&d2) == 0);
+        assert!(!d1.identical(&d2));
+
+        let d2 = d1.neg().unwrap();
+        assert!(!d1.identical(&d2));
+
+        let mut d2 = d1.clone().unwrap();
+        d2.set_exponent(d1.exponent() + 1);
+        assert!(!d1.identical(&d2));
+
+        let d2 = d1.add(&ONE.mul(&ONE, 128, rm).unwrap(), 128, rm).unwrap();
+        let d3 = BigFloatNumber::from_word(4, 128).unwrap();
+        assert!(d2.identical(&d3));
+        assert!(!d1.identical(&d3));
+
+        let z = BigFloatNumber::new(128).unwrap();
+        assert!(z.identical(&BigFloatNumber::new(128).unwrap()));
+        assert!(!z.identical(&BigFloatNumber::new(64).unwrap()));
+    }
+
+    #[test]
+    fn test_from_f64_minimal() {
+        for (f, bits) in [
+            (1.0, 1),
+            (-0.75, 2),
+            (f64::from_bits(0b1100), 2),
+            (f64::MIN_POSITIVE / 3.0, 51),
+            (0.1, 53),
+            (f64::MAX, 53),
+        ] {
+            let d = BigFloatNumber::from_f64_minimal(f).unwrap();
+            assert_eq!(d.mantissa_max_bit_len(), round_p(bits));
+            assert_eq!(d.to_f64_rm(RoundingMode::None).unwrap(), (f, false));
+            assert!(!d.inexact());
+        }
+
+        #[cfg(feature = "serde")]
+        {
+            let d = BigFloatNumber::from_f32_minimal(0.1f32).unwrap();
+            assert_eq!(d.mantissa_max_bit_len(), round_p(24));
+            assert_eq!(d.to_f32(), 0.1f32);
+        }
+
+        assert!(BigFloatNumber::from_f64_minimal(0.0).unwrap().is_zero());
+        assert!(BigFloatNumber::from_f64_minimal(f64::NAN).unwrap_err() == Error::InvalidArgument);
+        assert!(
+            BigFloatNumber::from_f64_minimal(f64::NEG_INFINITY).unwrap_err()
+                == Error::ExponentOverflow(Sign::Neg)
+        );
+    }
+
+    #[test]
+    fn test_to_int_rm() {
+        let rms = [
+            RoundingMode::None,
+            RoundingMode::ToZero,
+            RoundingMode::FromZero,
+            RoundingMode::Up,
+            RoundingMode::Down,
+            RoundingMode::ToEven,
+            RoundingMode::ToNearestAway,
+        ];
+
+        for (f, expected) in [
+            (2.5, [2, 2, 3, 3, 2, 2, 3]),
+            (-2.5, [-2, -2, -3, -2, -3, -2, -3]),
+            (3.5, [3, 3, 4, 4, 3, 4, 4]),
+            (0.25, [0, 0, 1, 1, 0, 0, 0]),
+            (-0.75, [0, 0, -1, 0, -1, -1, -1]),
+            (7.0, [7, 7, 7, 7, 7, 7, 7]),
+            (0.0, [0, 0, 0, 0, 0, 0, 0]),
+        ] {
+            let d = BigFloatNumber::from_f64(64, f).unwrap();
+            for (rm, i) in rms.iter().zip(expected) {
+                assert_eq!(d.to_i64(*rm).unwrap(), i);
+                assert_eq!(d.to_i128(*rm).unwrap(), i as i128);
+                if i >= 0 {
+                    assert_eq!(d.to_u64(*rm).unwrap(), i as u64);
+                    assert_eq!(d.to_u128(*rm).unwrap(), i as u128);
+                } else {
+                    assert_eq!(d.to_u64(*rm).unwrap_err(), Error::InvalidArgument);
+                    assert_eq!(d.to_u128(*rm).unwrap_err(), Error::InvalidArgument);
+                }
+            }
+        }
+
+        // boundaries
+        let d = BigFloatNumber::from_i128(i128::MIN, 128).unwrap();
+        assert_eq!(d.to_i128(RoundingMode::None).unwrap(), i128::MIN);
+        assert_eq!(
+            d.to_i64(RoundingMode::None).unwrap_err(),
+            Error::ExponentOverflow(Sign::Neg)
+        );
+
+        let d = BigFloatNumber::from_i128(i128::MAX, 128).unwrap();
+        assert_eq!(d.to_i128(RoundingMode::None).unwrap(), i128::MAX);
+        assert_eq!(d.to_u128(RoundingMode::None).unwrap(), i128::MAX as u128);
+
+        let d = BigFloatNumber::from_u64(u64::MAX, 64).unwrap();
+        assert_eq!(d.to_u64(RoundingMode::None).unwrap(), u64::MAX);
+        assert_eq!(
+            d.to_i64(RoundingMode::None).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+
+        // rounding up causes overflow
+        let mut d = BigFloatNumber::from_u64(u64::MAX, 128).unwrap();
+        d = d
+            .add(
+                &BigFloatNumber::from_f64(64, 0.5).unwrap(),
+                128,
+                RoundingMode::None,
+            )
+            .unwrap();
+        assert_eq!(d.to_u64(RoundingMode::ToZero).unwrap(), u64::MAX);
+        assert_eq!(
+            d.to_u64(RoundingMode::ToEven).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+        assert_eq!(
+            d.to_u128(RoundingMode::ToEven).unwrap(),
+            u64::MAX as u128 + 1
+        );
+
+        // the mantissa is shorter than the integer
+        let d = BigFloatNumber::from_f64(64, 1.0e30).unwrap();
+        assert_eq!(d.to_i128(RoundingMode::None).unwrap(), 1.0e30 as i128);
+
+        let d = BigFloatNumber::from_f64(64, 1.0e40).unwrap();
+        assert_eq!(
+            d.to_u128(RoundingMode::None).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+        assert_eq!(
+            d.neg().unwrap().to_i128(RoundingMode::None).unwrap_err(),
+            Error::ExponentOverflow(Sign::Neg)
+        );
+
+        // the mantissa is longer than 128 bits
+        let d = BigFloatNumber::from_u64(0x8000000000000001, 256).unwrap();
+        assert_eq!(d.to_u64(RoundingMode::None).unwrap(), 0x8000000000000001);
+
+        // exact conversion
+        assert_eq!(
+            BigFloatNumber::from_f64(64, 2.5)
+                .unwrap()
+                .to_i64_exact()
+                .unwrap_err(),
+            Error::Inexact
+        );
+        assert_eq!(
+            BigFloatNumber::from_f64(64, -3.0)
+                .unwrap()
+                .to_i64_exact()
+                .unwrap(),
+            -3
+        );
+    }
+
+    #[test]
+    fn test_rounding() {
+        // trailing bits
+        // 0 0000
+        // 0 0001
         // 0 1000
         // 0 1001
         // 1 1000
         // 1 1001
 
         let mantissas = {
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(word32))]
             {
                 [
                     [0x8000000000000000u64, 0x8000000000000000u64],
@@ -2872,7 +4747,7 @@ mod tests {
                     [0x8000000000000019u64, 0x8000000000000000u64],
                 ]
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(word32)]
             {
                 [
                     [0x80000000u32, 0x80000000u32],
@@ -2886,7 +4761,7 @@ mod tests {
         };
 
         let rounding_results_posnum = {
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(word32))]
             {
                 [
                     (RoundingMode::None, mantissas),
@@ -2956,9 +4831,20 @@ mod tests {
                             [0x8000000000000020u64, 0x8000000000000000u64],
                         ],
                     ),
+                    (
+                        RoundingMode::ToNearestAway,
+                        [
+                            [0x8000000000000000u64, 0x8000000000000000u64],
+                            [0x8000000000000000u64, 0x8000000000000000u64],
+                            [0x8000000000000010u64, 0x8000000000000000u64],
+                            [0x8000000000000010u64, 0x8000000000000000u64],
+                            [0x8000000000000020u64, 0x8000000000000000u64],
+                            [0x8000000000000020u64, 0x8000000000000000u64],
+                        ],
+                    ),
                 ]
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(word32)]
             {
                 [
                     (RoundingMode::None, mantissas),
@@ -3028,12 +4914,23 @@ mod tests {
                             [0x80000020u32, 0x80000000u32],
                         ],
                     ),
+                    (
+                        RoundingMode::ToNearestAway,
+                        [
+                            [0x80000000u32, 0x80000000u32],
+                            [0x80000000u32, 0x80000000u32],
+                            [0x80000010u32, 0x80000000u32],
+                            [0x80000010u32, 0x80000000u32],
+                            [0x80000020u32, 0x80000000u32],
+                            [0x80000020u32, 0x80000000u32],
+                        ],
+                    ),
                 ]
             }
         };
 
         let rounding_results_negnum = {
-            #[cfg(not(target_arch = "x86"))]
+            #[cfg(not(word32))]
             {
                 [
                     (RoundingMode::None, mantissas),
@@ -3103,9 +5000,20 @@ mod tests {
                             [0x8000000000000020u64, 0x8000000000000000u64],
                         ],
                     ),
+                    (
+                        RoundingMode::ToNearestAway,
+                        [
+                            [0x8000000000000000u64, 0x8000000000000000u64],
+                            [0x8000000000000000u64, 0x8000000000000000u64],
+                            [0x8000000000000010u64, 0x8000000000000000u64],
+                            [0x8000000000000010u64, 0x8000000000000000u64],
+                            [0x8000000000000020u64, 0x8000000000000000u64],
+                            [0x8000000000000020u64, 0x8000000000000000u64],
+                        ],
+                    ),
                 ]
             }
-            #[cfg(target_arch = "x86")]
+            #[cfg(word32)]
             {
                 [
                     (RoundingMode::None, mantissas),
@@ -3175,6 +5083,17 @@ mod tests {
                             [0x80000020u32, 0x80000000u32],
                         ],
                     ),
+                    (
+                        RoundingMode::ToNearestAway,
+                        [
+                            [0x80000000u32, 0x80000000u32],
+                            [0x80000000u32, 0x80000000u32],
+                            [0x80000010u32, 0x80000000u32],
+                            [0x80000010u32, 0x80000000u32],
+                            [0x80000020u32, 0x80000000u32],
+                            [0x80000020u32, 0x80000000u32],
+                        ],
+                    ),
                 ]
             }
         };
@@ -3248,6 +5167,15 @@ mod tests {
                 [0, 0],
                 0,
             ),
+            (
+                [0, WORD_SIGNIFICANT_BIT],
+                Sign::Pos,
+                -99,
+                99,
+                RoundingMode::ToNearestAway,
+                [0, WORD_SIGNIFICANT_BIT],
+                -98,
+            ),
             (
                 [1, WORD_SIGNIFICANT_BIT],
                 Sign::Pos,
@@ -3517,6 +5445,785 @@ mod tests {
         assert!(d2.inexact());
     }
 
+    #[test]
+    fn test_div_exact() {
+        let p = 256;
+
+        // exact quotient
+        for _ in 0..1000 {
+            let d1 = BigFloatNumber::random_normal(p / 2, -100, 100).unwrap();
+            let d2 = BigFloatNumber::random_normal(p / 2, -100, 100).unwrap();
+            let d3 = d1.mul(&d2, p, RoundingMode::None).unwrap();
+            assert!(!d3.inexact());
+
+            let (q, exact) = d3.div_exact(&d2, p, RoundingMode::ToEven).unwrap();
+            assert!(exact);
+            assert!(!q.inexact());
+            assert!(q.cmp(&d1) == 0);
+        }
+
+        // inexact quotient
+        let d1 = BigFloatNumber::from_word(1, p).unwrap();
+        let d2 = BigFloatNumber::from_word(3, p).unwrap();
+        let (q, exact) = d1.div_exact(&d2, p, RoundingMode::ToEven).unwrap();
+        assert!(!exact);
+        assert!(q.inexact());
+
+        // exactness does not depend on the inexactness of the arguments
+        let mut d1 = BigFloatNumber::from_word(6, p).unwrap();
+        d1.set_inexact(true);
+        let (q, exact) = d1.div_exact(&d2, p, RoundingMode::ToEven).unwrap();
+        assert!(exact);
+        assert!(q.inexact());
+
+        // subnormal result
+        let d1 =
+            BigFloatNumber::from_words(&[0, 3 << (WORD_BIT_SIZE - 2)], Sign::Pos, EXPONENT_MIN)
+                .unwrap();
+        let d2 = BigFloatNumber::from_word(1 << 20, p).unwrap();
+        let (q, exact) = d1.div_exact(&d2, p, RoundingMode::ToEven).unwrap();
+        assert!(exact);
+        assert!(q.is_subnormal());
+
+        let d2 = BigFloatNumber::from_word(3 << (WORD_BIT_SIZE - 2), p).unwrap();
+        let d2 = d2.mul(&d2, p, RoundingMode::None).unwrap();
+        let (q, exact) = d1.div_exact(&d2, p, RoundingMode::ToEven).unwrap();
+        assert!(!exact);
+        assert!(q.inexact());
+    }
+
+    #[test]
+    fn test_div_rem() {
+        for _ in 0..1000 {
+            let p1 = (random::<usize>() % 8 + 1) * WORD_BIT_SIZE;
+            let p2 = (random::<usize>() % 8 + 1) * WORD_BIT_SIZE;
+
+            let mut d1 = BigFloatNumber::random_normal(p1, -200, 200).unwrap();
+            let mut d2 = BigFloatNumber::random_normal(p2, -200, 200).unwrap();
+            if random::<bool>() {
+                d1.inv_sign();
+            }
+            if random::<bool>() {
+                d2.inv_sign();
+            }
+
+            let (q, r) = d1.div_rem(&d2).unwrap();
+
+            assert!(q.is_int());
+            assert!(
+                q.is_zero()
+                    || q.sign() == if d1.sign() == d2.sign() { Sign::Pos } else { Sign::Neg }
+            );
+            assert!(r.is_zero() || r.sign() == d1.sign());
+            assert!(r.abs_cmp(&d2) < 0);
+            assert!(r.cmp(&d1.rem(&d2).unwrap()) == 0);
+            assert!(!q.inexact() && !r.inexact());
+
+            // d1 = q * d2 + r
+            let d3 = q.mul_full_prec(&d2).unwrap().add_full_prec(&r).unwrap();
+            assert!(d3.cmp(&d1) == 0);
+        }
+
+        // signs
+        let d1 = BigFloatNumber::from_f64(64, 7.5).unwrap();
+        let d2 = BigFloatNumber::from_word(3, 64).unwrap();
+        for (s1, s2, qv, rv) in [
+            (1.0, 1.0, 2.0, 1.5),
+            (-1.0, 1.0, -2.0, -1.5),
+            (1.0, -1.0, -2.0, 1.5),
+            (-1.0, -1.0, 2.0, -1.5),
+        ] {
+            let n1 = d1
+                .mul(
+                    &BigFloatNumber::from_f64(64, s1).unwrap(),
+                    64,
+                    RoundingMode::None,
+                )
+                .unwrap();
+            let n2 = d2
+                .mul(
+                    &BigFloatNumber::from_f64(64, s2).unwrap(),
+                    64,
+                    RoundingMode::None,
+                )
+                .unwrap();
+            let (q, r) = n1.div_rem(&n2).unwrap();
+            assert!(q.cmp(&BigFloatNumber::from_f64(64, qv).unwrap()) == 0);
+            assert!(r.cmp(&BigFloatNumber::from_f64(64, rv).unwrap()) == 0);
+        }
+
+        // the quotient larger than the precision of the arguments
+        let d1 = BigFloatNumber::from_f64(64, 1.0e30).unwrap();
+        let d2 = BigFloatNumber::from_f64(64, 3.0e-30).unwrap();
+        let (q, r) = d1.div_rem(&d2).unwrap();
+        assert!(q.mantissa_max_bit_len() > 64);
+        let d3 = q.mul_full_prec(&d2).unwrap().add_full_prec(&r).unwrap();
+        assert!(d3.cmp(&d1) == 0);
+
+        // the quotient is zero
+        let (q, r) = d2.div_rem(&d1).unwrap();
+        assert!(q.is_zero());
+        assert!(r.cmp(&d2) == 0);
+
+        // subnormal
+        let d1 = BigFloatNumber::from_words(&[123, 456], Sign::Neg, EXPONENT_MIN).unwrap();
+        let d2 = BigFloatNumber::from_words(&[0, 7], Sign::Pos, EXPONENT_MIN).unwrap();
+        let (q, r) = d1.div_rem(&d2).unwrap();
+        assert!(q.is_int() && q.is_negative());
+        assert!(r.is_subnormal() && r.is_negative());
+        let d3 = q.mul_full_prec(&d2).unwrap().add_full_prec(&r).unwrap();
+        assert!(d3.cmp(&d1) == 0);
+
+        // zero and inexact arguments
+        let zero = BigFloatNumber::new(64).unwrap();
+        let (q, r) = zero.div_rem(&d2).unwrap();
+        assert!(q.is_zero() && r.is_zero());
+
+        let mut d1 = BigFloatNumber::from_word(10, 64).unwrap();
+        d1.set_inexact(true);
+        let (q, r) = d1
+            .div_rem(&BigFloatNumber::from_word(4, 64).unwrap())
+            .unwrap();
+        assert!(q.cmp(&BigFloatNumber::from_word(2, 64).unwrap()) == 0);
+        assert!(r.cmp(&BigFloatNumber::from_word(2, 64).unwrap()) == 0);
+        assert!(q.inexact() && r.inexact());
+
+        assert!(matches!(d1.div_rem(&zero), Err(Error::DivisionByZero)));
+    }
+
+    #[test]
+    fn test_reciprocal_refine() {
+        let rms = [
+            RoundingMode::Up,
+            RoundingMode::Down,
+            RoundingMode::ToZero,
+            RoundingMode::FromZero,
+            RoundingMode::ToEven,
+            RoundingMode::ToOdd,
+        ];
+
+        for _ in 0..1000 {
+            let p = (random::<usize>() % 32 + 1) * WORD_BIT_SIZE;
+            let p_prev = (random::<usize>() % 32 + 1) * WORD_BIT_SIZE;
+            let rm = rms[random::<usize>() % rms.len()];
+
+            let mut d1 = BigFloatNumber::random_normal(p, EXPONENT_MIN + 1, EXPONENT_MAX).unwrap();
+            if random::<bool>() {
+                d1.inv_sign();
+            }
+
+            let prev = d1.reciprocal(p_prev, RoundingMode::None).unwrap();
+
+            let d2 = d1.reciprocal_refine(&prev, p, rm).unwrap();
+            let d3 = d1.reciprocal(p, rm).unwrap();
+
+            assert!(d2.cmp(&d3) == 0);
+            assert_eq!(d2.mantissa_max_bit_len(), d3.mantissa_max_bit_len());
+            assert_eq!(d2.inexact(), d3.inexact());
+        }
+
+        let p = 320;
+        let rm = RoundingMode::ToEven;
+        let d1 = BigFloatNumber::from_word(7, p).unwrap();
+        let d3 = d1.reciprocal(p, rm).unwrap();
+
+        // prev is far from the reciprocal, has the wrong sign, or is zero
+        for prev in [
+            BigFloatNumber::from_word(1, p).unwrap(),
+            BigFloatNumber::from_word(100, p).unwrap(),
+            d3.neg().unwrap(),
+            BigFloatNumber::new(p).unwrap(),
+        ] {
+            assert!(d1.reciprocal_refine(&prev, p, rm).unwrap().cmp(&d3) == 0);
+        }
+
+        // exact reciprocal
+        let d1 = BigFloatNumber::from_word(8, p).unwrap();
+        let prev = BigFloatNumber::from_f64(64, 0.125).unwrap();
+        let d2 = d1.reciprocal_refine(&prev, p, rm).unwrap();
+        assert!(d2.cmp(&BigFloatNumber::from_f64(p, 0.125).unwrap()) == 0);
+        assert!(!d2.inexact());
+
+        // extreme exponents
+        let d1 = BigFloatNumber::max_value(p).unwrap();
+        let prev = d1.reciprocal(p / 2, rm).unwrap();
+        assert!(
+            d1.reciprocal_refine(&prev, p, rm)
+                .unwrap()
+                .cmp(&d1.reciprocal(p, rm).unwrap())
+                == 0
+        );
+
+        let mut d1 = BigFloatNumber::from_word(3, p).unwrap();
+        for e in EXPONENT_MIN + 2..EXPONENT_MIN + 5 {
+            d1.set_exponent(e);
+            let prev = d1.reciprocal(p / 2, rm).unwrap();
+            assert!(
+                d1.reciprocal_refine(&prev, p, rm)
+                    .unwrap()
+                    .cmp(&d1.reciprocal(p, rm).unwrap())
+                    == 0
+            );
+        }
+
+        d1.set_exponent(EXPONENT_MIN);
+        let prev = BigFloatNumber::new(p).unwrap();
+        assert!(matches!(
+            d1.reciprocal_refine(&prev, p, rm),
+            Err(Error::ExponentOverflow(Sign::Pos))
+        ));
+
+        // division by zero
+        let d1 = BigFloatNumber::new(p).unwrap();
+        assert!(matches!(
+            d1.reciprocal_refine(&d3, p, rm),
+            Err(Error::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    fn test_mul_truncated() {
+        let rms = [
+            RoundingMode::ToEven,
+            RoundingMode::ToOdd,
+            RoundingMode::Up,
+            RoundingMode::Down,
+            RoundingMode::FromZero,
+            RoundingMode::ToZero,
+        ];
+
+        // high-precision operand multiplied by a low-precision one
+        for _ in 0..1000 {
+            let p1 = (random::<usize>() % 32 + 8) * WORD_BIT_SIZE;
+            let p2 = (random::<usize>() % 3 + 1) * WORD_BIT_SIZE;
+            let p = (random::<usize>() % 4 + 1) * WORD_BIT_SIZE;
+
+            let d1 = BigFloatNumber::random_normal(p1, -100, 100).unwrap();
+            let d2 = BigFloatNumber::random_normal(p2, -100, 100).unwrap();
+            let full = d1.mul_full_prec(&d2).unwrap();
+
+            for rm in rms {
+                let mut d3 = full.clone().unwrap();
+                d3.set_precision(round_p(p), rm).unwrap();
+
+                let d4 = d1.mul(&d2, p, rm).unwrap();
+                assert!(d4.cmp(&d3) == 0);
+                assert_eq!(d4.inexact(), d3.inexact());
+
+                let d4 = d2.mul(&d1, p, rm).unwrap();
+                assert!(d4.cmp(&d3) == 0);
+            }
+        }
+
+        // carry from the dropped words reaches the result, or the dropped words affect only rounding
+        let mut words = [WORD_MAX; 16];
+        words[0] = 1;
+        let mut d1 = BigFloatNumber::from_words(&words, Sign::Pos, 0).unwrap();
+        let d2 = BigFloatNumber::from_word(1, WORD_BIT_SIZE).unwrap();
+        for _ in 0..2 {
+            let full = d1.mul_full_prec(&d2).unwrap();
+            for rm in rms {
+                let mut d3 = full.clone().unwrap();
+                d3.set_precision(round_p(WORD_BIT_SIZE), rm).unwrap();
+
+                let d4 = d1.mul(&d2, WORD_BIT_SIZE, rm).unwrap();
+                assert!(d4.cmp(&d3) == 0);
+                assert!(d4.inexact());
+            }
+
+            words.fill(0);
+            words[0] = 1;
+            words[15] = WORD_SIGNIFICANT_BIT;
+            d1 = BigFloatNumber::from_words(&words, Sign::Pos, 0).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_faithful_rounding() {
+        let p = WORD_BIT_SIZE;
+        let s = 3 * WORD_BIT_SIZE;
+
+        // correct discarded bits are all ones
+        let mut d1 =
+            BigFloatNumber::from_words(&[WORD_MAX, WORD_MAX, WORD_SIGNIFICANT_BIT], Sign::Pos, 1)
+                .unwrap();
+        d1.set_inexact(true);
+        assert!(d1.try_set_precision(p, RoundingMode::None, s).unwrap());
+        let d2 = BigFloatNumber::from_words(&[WORD_SIGNIFICANT_BIT | 1], Sign::Pos, 1).unwrap();
+        assert!(d1.cmp(&d2) == 0);
+
+        // truncation
+        let mut d1 = BigFloatNumber::from_words(
+            &[WORD_MAX, WORD_MAX >> 1, WORD_SIGNIFICANT_BIT],
+            Sign::Neg,
+            1,
+        )
+        .unwrap();
+        d1.set_inexact(true);
+        let mut d2 = d1.clone().unwrap();
+        d2.set_precision(round_p(p), RoundingMode::ToZero).unwrap();
+        assert!(d1.try_set_precision(p, RoundingMode::None, s).unwrap());
+        assert!(d1.cmp(&d2) == 0);
+
+        // no correct discarded bits
+        let mut d1 =
+            BigFloatNumber::from_words(&[0, 0, WORD_SIGNIFICANT_BIT], Sign::Pos, 1).unwrap();
+        d1.set_inexact(true);
+        assert!(!d1.try_set_precision(p, RoundingMode::None, p).unwrap());
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn test_set_precision_stochastic() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(12345);
+
+        // discarded part is 3/4 of ulp
+        for s in [Sign::Pos, Sign::Neg] {
+            let d1 =
+                BigFloatNumber::from_words(&[3 << (WORD_BIT_SIZE - 2), WORD_SIGNIFICANT_BIT], s, 1)
+                    .unwrap();
+
+            let mut down = d1.clone().unwrap();
+            down.set_precision(WORD_BIT_SIZE, RoundingMode::ToZero)
+                .unwrap();
+            let mut up = d1.clone().unwrap();
+            up.set_precision(WORD_BIT_SIZE, RoundingMode::FromZero)
+                .unwrap();
+
+            let n = 4000;
+            let mut cnt = 0;
+            for _ in 0..n {
+                let mut d2 = d1.clone().unwrap();
+                d2.set_precision_stochastic(WORD_BIT_SIZE, &mut rng)
+                    .unwrap();
+                assert!(d2.inexact());
+
+                if d2.cmp(&up) == 0 {
+                    cnt += 1;
+                } else {
+                    assert!(d2.cmp(&down) == 0);
+                }
+            }
+
+            assert!(cnt > n * 7 / 10 && cnt < n * 8 / 10);
+        }
+
+        // exact value is not changed
+        let mut d1 = BigFloatNumber::from_words(&[0, WORD_SIGNIFICANT_BIT], Sign::Pos, 1).unwrap();
+        let d2 = d1.clone().unwrap();
+        d1.set_precision_stochastic(WORD_BIT_SIZE, &mut rng)
+            .unwrap();
+        assert!(d1.cmp(&d2) == 0);
+        assert!(!d1.inexact());
+
+        // small discarded part is rarely rounded away
+        let d1 = BigFloatNumber::from_words(&[1, 0, WORD_SIGNIFICANT_BIT], Sign::Pos, 1).unwrap();
+        for _ in 0..100 {
+            let mut d2 = d1.clone().unwrap();
+            d2.set_precision_stochastic(WORD_BIT_SIZE, &mut rng)
+                .unwrap();
+            assert!(d2.cmp(&ONE) == 0);
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        let w = WORD_BIT_SIZE / 4;
+
+        let d1 = BigFloatNumber::from_word(3, 2 * WORD_BIT_SIZE).unwrap();
+        assert_eq!(
+            format!("{:?}", d1),
+            format!(
+                "BigFloatNumber {{ s: Pos, e: 2, p: {}, inexact: false, m: [0xc{:0w2$x}, 0x{:0w$x}] }}",
+                2 * WORD_BIT_SIZE,
+                0,
+                0,
+                w = w,
+                w2 = w - 1,
+            )
+        );
+
+        // only leading and trailing words are printed
+        let mut d1 = BigFloatNumber::max_value(100000).unwrap().neg().unwrap();
+        d1.set_inexact(true);
+        let l = d1.mantissa().len();
+        let s = format!("{:?}", d1);
+        assert!(s.len() < 200);
+        assert!(s.starts_with(&format!(
+            "BigFloatNumber {{ s: Neg, e: {}, p: {}, inexact: true, m: [0x{:x}, 0x{:x}, ... {} words ..., ",
+            EXPONENT_MAX,
+            l * WORD_BIT_SIZE,
+            WORD_MAX,
+            WORD_MAX,
+            l - 4
+        )));
+    }
+
+    #[test]
+    fn test_word_ops() {
+        let rms = [
+            RoundingMode::ToEven,
+            RoundingMode::ToOdd,
+            RoundingMode::Up,
+            RoundingMode::Down,
+            RoundingMode::FromZero,
+            RoundingMode::ToZero,
+            RoundingMode::None,
+        ];
+
+        for i in 0..1000 {
+            let p1 = (random::<usize>() % 5 + 1) * WORD_BIT_SIZE;
+            let p = (random::<usize>() % 5 + 1) * WORD_BIT_SIZE;
+
+            let d1 = if i % 10 == 0 {
+                random_subnormal(p1)
+            } else {
+                BigFloatNumber::random_normal(p1, EXPONENT_MIN, EXPONENT_MAX).unwrap()
+            };
+
+            let d = match i % 4 {
+                0 => 1 << (random::<usize>() % WORD_BIT_SIZE),
+                1 => WORD_MAX,
+                _ => random::<Word>() >> (random::<usize>() % WORD_BIT_SIZE),
+            };
+            let d2 = BigFloatNumber::from_word(d, WORD_BIT_SIZE).unwrap();
+
+            for rm in rms {
+                match (d1.mul_word(d, p, rm), d1.mul(&d2, p, rm)) {
+                    (Ok(d3), Ok(d4)) => {
+                        assert!(d3.cmp(&d4) == 0);
+                        assert_eq!(d3.inexact(), d4.inexact());
+                        assert_eq!(d3.mantissa_max_bit_len(), d4.mantissa_max_bit_len());
+                    }
+                    (d3, d4) => assert_eq!(d3.map(|_| ()), d4.map(|_| ())),
+                }
+
+                match (d1.div_word(d, p, rm), d1.div(&d2, p, rm)) {
+                    (Ok(d3), Ok(d4)) => {
+                        assert!(d3.cmp(&d4) == 0);
+                        assert_eq!(d3.inexact(), d4.inexact());
+                        assert_eq!(d3.mantissa_max_bit_len(), d4.mantissa_max_bit_len());
+                    }
+                    (d3, d4) => assert_eq!(d3.map(|_| ()), d4.map(|_| ())),
+                }
+
+                let d3 = d1.add_word(d, p, rm).unwrap();
+                let d4 = d1.add(&d2, p, rm).unwrap();
+                assert!(d3.cmp(&d4) == 0);
+            }
+        }
+
+        // special cases
+        let p = 128;
+        let rm = RoundingMode::ToEven;
+        let max = BigFloatNumber::max_value(p).unwrap();
+        assert_eq!(
+            max.mul_word(2, p, rm).map(|_| ()),
+            Err(Error::ExponentOverflow(Sign::Pos))
+        );
+        assert!(max.mul_word(1, p, rm).unwrap().cmp(&max) == 0);
+        assert!(max.div_word(1, p, rm).unwrap().cmp(&max) == 0);
+        assert!(max.mul_word(0, p, rm).unwrap().is_zero());
+
+        let min = BigFloatNumber::min_positive(p).unwrap();
+        assert!(min.div_word(2, p, RoundingMode::Down).unwrap().is_zero());
+        assert!(min.div_word(2, p, RoundingMode::Up).unwrap().cmp(&min) == 0);
+        assert!(min.div_word(3, p, rm).unwrap().is_zero());
+        assert!(min.mul_word(3, p, rm).unwrap().is_subnormal());
+
+        assert_eq!(
+            max.div_word(0, p, rm).map(|_| ()),
+            Err(Error::DivisionByZero)
+        );
+        assert_eq!(
+            BigFloatNumber::new(p)
+                .unwrap()
+                .div_word(0, p, rm)
+                .map(|_| ()),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            max.mul_word(2, usize::MAX, rm).map(|_| ()),
+            Err(Error::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_fit_exponent_range() {
+        let emin = -100;
+        let emax = 100;
+        let p = 2 * WORD_BIT_SIZE;
+        let rm = RoundingMode::ToEven;
+
+        // in range
+        let d1 = BigFloatNumber::from_words(&[3, WORD_SIGNIFICANT_BIT], Sign::Neg, emin).unwrap();
+        let d2 = d1.fit_exponent_range(emin, emax, rm).unwrap();
+        assert_eq!(d1.cmp(&d2), 0);
+        assert!(!d2.inexact());
+
+        // overflow
+        let d1 =
+            BigFloatNumber::from_words(&[0, WORD_SIGNIFICANT_BIT], Sign::Neg, emax + 1).unwrap();
+        assert!(
+            d1.fit_exponent_range(emin, emax, rm).unwrap_err()
+                == Error::ExponentOverflow(Sign::Neg)
+        );
+
+        // invalid range
+        assert!(d1.fit_exponent_range(emax, emin, rm).unwrap_err() == Error::InvalidArgument);
+
+        // subnormal: 2 bits are rounded off
+        let d1 = BigFloatNumber::from_words(&[0b111, WORD_SIGNIFICANT_BIT], Sign::Pos, emin - 2)
+            .unwrap();
+        let d2 = d1
+            .fit_exponent_range(emin, emax, RoundingMode::ToZero)
+            .unwrap();
+        let d3 = BigFloatNumber::from_words(&[0b100, WORD_SIGNIFICANT_BIT], Sign::Pos, emin - 2)
+            .unwrap();
+        assert_eq!(d2.cmp(&d3), 0);
+        assert!(d2.inexact());
+
+        let d2 = d1.fit_exponent_range(emin, emax, RoundingMode::Up).unwrap();
+        let d3 = BigFloatNumber::from_words(&[0b1000, WORD_SIGNIFICANT_BIT], Sign::Pos, emin - 2)
+            .unwrap();
+        assert_eq!(d2.cmp(&d3), 0);
+
+        // subnormal: rounding to a normal number
+        let d1 = BigFloatNumber::from_words(&[WORD_MAX, WORD_MAX], Sign::Pos, emin - 1).unwrap();
+        let d2 = d1.fit_exponent_range(emin, emax, rm).unwrap();
+        let d3 = BigFloatNumber::from_words(&[0, WORD_SIGNIFICANT_BIT], Sign::Pos, emin).unwrap();
+        assert_eq!(d2.cmp(&d3), 0);
+
+        // half of the smallest subnormal
+        let min_sub = BigFloatNumber::from_words(
+            &[0, WORD_SIGNIFICANT_BIT],
+            Sign::Pos,
+            emin - p as Exponent + 1,
+        )
+        .unwrap();
+        let d1 =
+            BigFloatNumber::from_words(&[0, WORD_SIGNIFICANT_BIT], Sign::Pos, emin - p as Exponent)
+                .unwrap();
+        assert!(d1
+            .fit_exponent_range(emin, emax, RoundingMode::ToEven)
+            .unwrap()
+            .is_zero());
+        assert!(d1
+            .fit_exponent_range(emin, emax, RoundingMode::ToZero)
+            .unwrap()
+            .is_zero());
+        assert_eq!(
+            d1.fit_exponent_range(emin, emax, RoundingMode::ToOdd)
+                .unwrap()
+                .cmp(&min_sub),
+            0
+        );
+        assert_eq!(
+            d1.fit_exponent_range(emin, emax, RoundingMode::Up)
+                .unwrap()
+                .cmp(&min_sub),
+            0
+        );
+
+        // above half of the smallest subnormal
+        let d1 =
+            BigFloatNumber::from_words(&[1, WORD_SIGNIFICANT_BIT], Sign::Neg, emin - p as Exponent)
+                .unwrap();
+        let d2 = d1
+            .fit_exponent_range(emin, emax, RoundingMode::ToEven)
+            .unwrap();
+        assert_eq!(d2.cmp(&min_sub.neg().unwrap()), 0);
+        assert!(d2.inexact());
+        assert!(d1
+            .fit_exponent_range(emin, emax, RoundingMode::Up)
+            .unwrap()
+            .is_zero());
+        assert_eq!(
+            d1.fit_exponent_range(emin, emax, RoundingMode::Down)
+                .unwrap()
+                .cmp(&min_sub.neg().unwrap()),
+            0
+        );
+
+        // far below the range
+        let d1 = BigFloatNumber::from_words(&[WORD_MAX, WORD_MAX], Sign::Pos, emin - 1000).unwrap();
+        assert!(d1.fit_exponent_range(emin, emax, rm).unwrap().is_zero());
+        assert_eq!(
+            d1.fit_exponent_range(emin, emax, RoundingMode::FromZero)
+                .unwrap()
+                .cmp(&min_sub),
+            0
+        );
+
+        // smallest subnormal of the range is a subnormal number of the global range
+        let d1 =
+            BigFloatNumber::from_words(&[0, WORD_SIGNIFICANT_BIT], Sign::Pos, EXPONENT_MIN + 1)
+                .unwrap();
+        let d2 = d1
+            .fit_exponent_range(EXPONENT_MIN + 1, emax, RoundingMode::Up)
+            .unwrap();
+        assert_eq!(d2.cmp(&d1), 0);
+        let d1 = BigFloatNumber::from_words(&[1, 0], Sign::Pos, EXPONENT_MIN).unwrap();
+        let d2 = d1
+            .fit_exponent_range(EXPONENT_MIN + 10, emax, RoundingMode::Up)
+            .unwrap();
+        assert!(d2.is_subnormal());
+        let d3 = BigFloatNumber::from_words(&[1 << 10, 0], Sign::Pos, EXPONENT_MIN).unwrap();
+        assert_eq!(d2.cmp(&d3), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "f128")]
+    fn test_f128() {
+        let rms = [
+            RoundingMode::None,
+            RoundingMode::Up,
+            RoundingMode::Down,
+            RoundingMode::ToZero,
+            RoundingMode::FromZero,
+            RoundingMode::ToEven,
+            RoundingMode::ToOdd,
+        ];
+
+        // 2^e
+        let pow2 = |e: Exponent| {
+            let mut ret = BigFloatNumber::from_word(1, 256).unwrap();
+            ret.set_exponent(e + 1);
+            ret
+        };
+
+        // exact conversion
+        for _ in 0..10000 {
+            let f = f128::from_bits(rand::random::<u128>());
+
+            if f.is_nan() || f.is_infinite() {
+                continue;
+            }
+
+            let d = BigFloatNumber::from_f128(128, f).unwrap();
+            for rm in rms {
+                assert_eq!(d.to_f128(rm).unwrap().to_bits(), f.to_bits());
+            }
+
+            let f = rand::random::<f64>();
+            assert!(
+                BigFloatNumber::from_f128(64, f as f128)
+                    .unwrap()
+                    .cmp(&BigFloatNumber::from_f64(64, f).unwrap())
+                    == 0
+            );
+        }
+
+        for f in [f128::MAX, f128::MIN_POSITIVE, f128::from_bits(1), -0.0, 0.0] {
+            let d = BigFloatNumber::from_f128(128, f).unwrap();
+            assert_eq!(
+                d.to_f128(RoundingMode::ToEven).unwrap().to_bits(),
+                f.to_bits()
+            );
+        }
+
+        assert!(BigFloatNumber::from_f128(128, f128::NAN).is_err());
+        assert!(matches!(
+            BigFloatNumber::from_f128(128, f128::NEG_INFINITY),
+            Err(Error::ExponentOverflow(Sign::Neg))
+        ));
+
+        // rounding of the mantissa
+        let one = BigFloatNumber::from_word(1, 256).unwrap();
+        let half_ulp = one.add(&pow2(-113), 256, RoundingMode::None).unwrap();
+        let above_half_ulp = half_ulp.add(&pow2(-200), 256, RoundingMode::None).unwrap();
+        let next = 1.0f128 + 2.0f128.powi(-112);
+
+        assert_eq!(half_ulp.to_f128(RoundingMode::ToEven).unwrap(), 1.0);
+        assert_eq!(half_ulp.to_f128(RoundingMode::ToOdd).unwrap(), next);
+        assert_eq!(half_ulp.to_f128(RoundingMode::Up).unwrap(), next);
+        assert_eq!(half_ulp.to_f128(RoundingMode::None).unwrap(), 1.0);
+        assert_eq!(above_half_ulp.to_f128(RoundingMode::ToEven).unwrap(), next);
+        assert_eq!(
+            above_half_ulp
+                .neg()
+                .unwrap()
+                .to_f128(RoundingMode::Up)
+                .unwrap(),
+            -1.0
+        );
+
+        // precision smaller than the precision of f128
+        let d = BigFloatNumber::from_f128(64, next).unwrap();
+        assert_eq!(d.to_f128(RoundingMode::ToEven).unwrap(), 1.0);
+
+        // overflow
+        let max = BigFloatNumber::from_f128(128, f128::MAX).unwrap();
+        let huge = pow2(16384);
+        assert_eq!(huge.to_f128(RoundingMode::ToEven).unwrap(), f128::INFINITY);
+        assert_eq!(huge.to_f128(RoundingMode::ToZero).unwrap(), f128::MAX);
+        assert_eq!(
+            huge.neg().unwrap().to_f128(RoundingMode::Up).unwrap(),
+            f128::MIN
+        );
+        let above_max = max
+            .add(&pow2(16383 - 113), 256, RoundingMode::None)
+            .unwrap();
+        assert_eq!(
+            above_max.to_f128(RoundingMode::ToEven).unwrap(),
+            f128::INFINITY
+        );
+        assert_eq!(above_max.to_f128(RoundingMode::Down).unwrap(), f128::MAX);
+
+        // subnormal numbers and underflow
+        let min_subnormal = f128::from_bits(1);
+        let half_min = pow2(-16495);
+        assert_eq!(half_min.to_f128(RoundingMode::ToEven).unwrap(), 0.0);
+        assert_eq!(
+            half_min.to_f128(RoundingMode::ToOdd).unwrap(),
+            min_subnormal
+        );
+        assert_eq!(half_min.to_f128(RoundingMode::Up).unwrap(), min_subnormal);
+        assert_eq!(
+            half_min
+                .neg()
+                .unwrap()
+                .to_f128(RoundingMode::Up)
+                .unwrap()
+                .to_bits(),
+            (-0.0f128).to_bits()
+        );
+        let above_half_min = half_min
+            .add(&pow2(-16500), 256, RoundingMode::None)
+            .unwrap();
+        assert_eq!(
+            above_half_min.to_f128(RoundingMode::ToEven).unwrap(),
+            min_subnormal
+        );
+        assert_eq!(
+            pow2(-16600).to_f128(RoundingMode::FromZero).unwrap(),
+            min_subnormal
+        );
+        assert_eq!(pow2(-16600).to_f128(RoundingMode::ToEven).unwrap(), 0.0);
+
+        let d = BigFloatNumber::from_f128(128, f128::from_bits(2))
+            .unwrap()
+            .add(&half_min, 256, RoundingMode::None)
+            .unwrap();
+        assert_eq!(d.to_f128(RoundingMode::ToEven).unwrap(), f128::from_bits(2));
+        assert_eq!(
+            d.to_f128(RoundingMode::FromZero).unwrap(),
+            f128::from_bits(3)
+        );
+
+        let d = BigFloatNumber::from_f128(128, f128::MIN_POSITIVE)
+            .unwrap()
+            .sub(&half_min, 256, RoundingMode::None)
+            .unwrap();
+        assert_eq!(d.to_f128(RoundingMode::ToEven).unwrap(), f128::MIN_POSITIVE);
+        assert_eq!(
+            d.to_f128(RoundingMode::ToZero).unwrap().to_bits(),
+            f128::MIN_POSITIVE.to_bits() - 1
+        );
+    }
+
     #[ignore]
     #[test]
     #[cfg(feature = "std")]
@@ -15,7 +15,7 @@ const ACOS_EXP_THRES: Exponent = -32;
 impl BigFloatNumber {
     /// Computes the arccosine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
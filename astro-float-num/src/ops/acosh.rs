@@ -14,7 +14,7 @@ use crate::WORD_BIT_SIZE;
 impl BigFloatNumber {
     /// Computes the hyperbolic arccosine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
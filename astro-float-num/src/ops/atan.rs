@@ -81,7 +81,7 @@ impl ArgReductionEstimator for AtanArgReductionEstimator {
 impl BigFloatNumber {
     /// Computes the arctangent of a number with precision `p`. The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -0,0 +1,533 @@
+//! Bessel functions of the first and second kind of integer order.
+
+use crate::common::util::log2_ceil;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::defs::Word;
+use crate::num::BigFloatNumber;
+use crate::Consts;
+use crate::Sign;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Computes the Bessel function of the first kind J_n(x) of order `n` at `self` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// The function is evaluated with the power series for small arguments and with the Hankel asymptotic expansion for large arguments.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn bessel_j(
+        &self,
+        n: usize,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return if n == 0 {
+                let mut ret = Self::from_word(1, p)?;
+                ret.set_inexact(self.inexact());
+                Ok(ret)
+            } else {
+                Self::new2(p, Sign::Pos, self.inexact())
+            };
+        }
+
+        // J_n(-x) = (-1)^n J_n(x)
+        let x = self.abs()?;
+        let inv = self.is_negative() && n & 1 == 1;
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + 8;
+
+            let mut ret = x.bessel_abs(n, false, p_x, cc)?;
+            if inv {
+                ret.inv_sign();
+            }
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    /// Computes the Bessel function of the second kind Y_n(x) of order `n` at `self` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// The function is evaluated with the power series for small arguments and with the Hankel asymptotic expansion for large arguments.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large, or `self` is zero.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: `self` is negative, or the precision is incorrect.
+    pub fn bessel_y(
+        &self,
+        n: usize,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return Err(Error::ExponentOverflow(Sign::Neg));
+        }
+
+        if self.is_negative() {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + 8;
+
+            let mut ret = self.bessel_abs(n, true, p_x, cc)?;
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Computes J_n(x), or Y_n(x) if `second` is true, for positive x with precision `p` and the relative error of a few units in the last place.
+    fn bessel_abs(&self, n: usize, second: bool, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        // the smallest term of the asymptotic series is about e^(-2x)
+        if self.exponent() as isize > log2_ceil(p) as isize - 1 {
+            if let Some(ret) = self.bessel_asymptotic(n, second, p, cc)? {
+                return Ok(ret);
+            }
+        }
+
+        // the terms of the series reach e^x, and the result can be much smaller
+        let mut guard = if self.exponent() > 0 {
+            (self.to_f64() * core::f64::consts::LOG2_E) as usize + WORD_BIT_SIZE
+        } else {
+            WORD_BIT_SIZE
+        };
+
+        loop {
+            let (ret, lost) = self.bessel_series(n, second, p + guard, cc)?;
+
+            if lost + 8 <= guard as isize {
+                return Ok(ret);
+            }
+
+            guard = (lost.max(guard as isize) as usize) + WORD_BIT_SIZE;
+        }
+    }
+
+    // Computes J_n(x), or Y_n(x) if `second` is true, for positive x with the absolute error of a few units in the last place of precision `p`
+    // relative to the largest intermediate value, and returns the result together with the number of bits lost to cancellation.
+    //
+    // J_n(x) = (x/2)^n * sum((-z)^k / (k! * (n+k)!)), z = x^2/4, k = 0, 1, ...
+    //
+    // Y_n(x) = (2 * J_n(x) * (ln(x/2) + gamma) - sum((n-k-1)! / k! * (x/2)^(2k-n), k = 0..n-1)
+    //          - (x/2)^n * sum((-z)^k * (H_k + H_(n+k)) / (k! * (n+k)!))) / pi,
+    // where gamma is the Euler-Mascheroni constant, and H_k is the k-th harmonic number.
+    fn bessel_series(
+        &self,
+        n: usize,
+        second: bool,
+        p: usize,
+        cc: &mut Consts,
+    ) -> Result<(Self, isize), Error> {
+        let rm = RoundingMode::None;
+
+        // the number of terms is less than 2x + p
+        let k_max = if self.exponent() > 0 { (self.to_f64() * 2.0) as usize } else { 0 } + p;
+        let p_s = p + 2 * log2_ceil(k_max + n + 1) + 8;
+
+        let mut x2 = self.clone()?;
+        x2.set_exponent(x2.exponent() - 1);
+
+        let z = x2.mul(&x2, p_s, rm)?.neg()?;
+
+        // b = (-z)^k / (k! * (n+k)!)
+        let mut b = Self::from_word(1, p_s)?;
+        for k in 2..=n {
+            b = b.div_word(k as Word, p_s, rm)?;
+        }
+
+        let mut sj = b.clone()?;
+
+        // h = H_k + H_(n+k)
+        let mut h_k = Self::new(p_s)?;
+        let mut h_nk = Self::new(p_s)?;
+        for k in 1..=n {
+            h_nk = h_nk.add(&Self::from_usize(k)?.reciprocal(p_s, rm)?, p_s, rm)?;
+        }
+
+        let mut sh = b.mul(&h_nk, p_s, rm)?;
+        let mut e_max = sj.exponent() as isize;
+
+        let mut k = 1;
+        loop {
+            let d = Self::from_usize(k)?.mul(&Self::from_usize(n + k)?, p_s, rm)?;
+            b = b.mul(&z, p_s, rm)?.div(&d, p_s, rm)?;
+
+            sj = sj.add(&b, p_s, rm)?;
+            e_max = e_max.max(b.exponent() as isize);
+
+            if second {
+                h_k = h_k.add(&Self::from_usize(k)?.reciprocal(p_s, rm)?, p_s, rm)?;
+                h_nk = h_nk.add(&Self::from_usize(n + k)?.reciprocal(p_s, rm)?, p_s, rm)?;
+
+                let t = b.mul(&h_k.add(&h_nk, p_s, rm)?, p_s, rm)?;
+                sh = sh.add(&t, p_s, rm)?;
+                e_max = e_max.max(t.exponent() as isize);
+            }
+
+            if b.is_zero()
+                || (d.cmp(&z.abs()?) > 0 && (b.exponent() as isize) < e_max - p_s as isize)
+            {
+                break;
+            }
+
+            k += 1;
+        }
+
+        let xn = x2.powi(n, p_s, rm)?;
+
+        // (x/2)^n underflows
+        if xn.is_zero() {
+            return if second { Err(Error::ExponentOverflow(Sign::Neg)) } else { Ok((xn, 0)) };
+        }
+
+        let j = sj.mul(&xn, p_s, rm)?;
+
+        if !second {
+            let lost = if j.is_zero() { p as isize } else { e_max - sj.exponent() as isize };
+            return Ok((j, lost));
+        }
+
+        // 2 * J_n(x) * (ln(x/2) + gamma)
+        let l = x2.ln(p_s, rm, cc)?;
+        let g = Self::euler_gamma(p_s, cc)?;
+        let lg = l.add(&g, p_s, rm)?;
+        let mut a = j.mul(&lg, p_s, rm)?;
+        a.set_exponent(a.exponent() + 1);
+
+        let mut e_max =
+            (e_max + xn.exponent() as isize).max(l.exponent() as isize + j.exponent() as isize + 1);
+
+        // sum((n-k-1)! / k! * (x/2)^(2k-n), k = 0..n-1)
+        let mut fs = Self::new(p_s)?;
+        if n > 0 {
+            let mut s = Self::from_word(1, p_s)?;
+            for k in 2..n {
+                s = s.mul_word(k as Word, p_s, rm)?;
+            }
+            s = s.div(&xn, p_s, rm)?;
+
+            for k in 0..n {
+                fs = fs.add(&s, p_s, rm)?;
+
+                if k + 1 < n {
+                    let d = Self::from_usize((n - k - 1) * (k + 1))?;
+                    s = s.mul(&z, p_s, rm)?.div(&d, p_s, rm)?.neg()?;
+                }
+            }
+
+            e_max = e_max.max(fs.exponent() as isize);
+        }
+
+        let c = sh.mul(&xn, p_s, rm)?;
+
+        let ret = a.sub(&fs, p_s, rm)?.sub(&c, p_s, rm)?;
+        let lost = if ret.is_zero() { p as isize } else { e_max - ret.exponent() as isize };
+
+        let pi = cc.pi_num(p_s, rm)?;
+
+        Ok((ret.div(&pi, p_s, rm)?, lost))
+    }
+
+    // Computes J_n(x), or Y_n(x) if `second` is true, for positive x with precision `p` and the relative error of a few units in the last place
+    // using the Hankel asymptotic expansion, or returns None if the expansion does not give enough precision for `x`:
+    //
+    // J_n(x) = sqrt(2 / (pi*x)) * (P * cos(w) - Q * sin(w)),
+    // Y_n(x) = sqrt(2 / (pi*x)) * (P * sin(w) + Q * cos(w)),
+    //
+    // where w = x - (2n + 1) * pi / 4, P = u_0 - u_2 + u_4 - ..., Q = u_1 - u_3 + u_5 - ...,
+    // u_k = u_(k-1) * (4n^2 - (2k-1)^2) / (8kx), u_0 = 1.
+    fn bessel_asymptotic(
+        &self,
+        n: usize,
+        second: bool,
+        p: usize,
+        cc: &mut Consts,
+    ) -> Result<Option<Self>, Error> {
+        let rm = RoundingMode::None;
+
+        let mut guard = WORD_BIT_SIZE;
+
+        loop {
+            let p_s = p + guard;
+
+            let mut x8 = self.clone()?;
+            x8.set_exponent(x8.exponent() + 3);
+
+            let mu = Self::from_usize(n)?
+                .powi(2, p_s, rm)?
+                .mul_word(4, p_s, rm)?;
+
+            let mut u = Self::from_word(1, p_s)?;
+            let mut sp = u.clone()?;
+            let mut sq = Self::new(p_s)?;
+
+            let mut k: usize = 1;
+            loop {
+                let d = Self::from_usize(2 * k - 1)?.powi(2, p_s, rm)?;
+                let c = mu.sub(&d, p_s, rm)?;
+
+                let u_next = u
+                    .mul(&c, p_s, rm)?
+                    .div(&x8, p_s, rm)?
+                    .div_word(k as Word, p_s, rm)?;
+
+                if (u_next.exponent() as isize) < -(p_s as isize) {
+                    break;
+                }
+
+                // the terms are too large, or start growing before the required precision is reached
+                if u_next.exponent() > 0 || (k > n && u_next.abs_cmp(&u) >= 0) {
+                    return Ok(None);
+                }
+
+                u = u_next;
+
+                let t = if k & 2 == 0 { u.clone()? } else { u.neg()? };
+                if k & 1 == 0 {
+                    sp = sp.add(&t, p_s, rm)?;
+                } else {
+                    sq = sq.add(&t, p_s, rm)?;
+                }
+
+                k += 1;
+            }
+
+            // w is computed with the absolute error of about 2^(-p_s)
+            let p_w = p_s + (self.exponent() as isize).max(0) as usize + log2_ceil(2 * n + 2);
+            let mut q = cc
+                .pi_num(p_w, rm)?
+                .mul(&Self::from_usize(2 * n + 1)?, p_w, rm)?;
+            q.set_exponent(q.exponent() - 2);
+
+            let w = self.sub(&q, p_w, rm)?;
+            let sin = w.sin(p_s, rm, cc)?;
+            let cos = w.cos(p_s, rm, cc)?;
+
+            let (a, b) = if second {
+                (sp.mul(&sin, p_s, rm)?, sq.mul(&cos, p_s, rm)?)
+            } else {
+                (sp.mul(&cos, p_s, rm)?, sq.mul(&sin, p_s, rm)?.neg()?)
+            };
+
+            let s = a.add(&b, p_s, rm)?;
+
+            // cancellation near the zeros of the function
+            let lost = if s.is_zero() { p_s as isize } else { -(s.exponent() as isize) };
+
+            if lost + 8 > guard as isize {
+                guard = lost as usize + WORD_BIT_SIZE;
+                continue;
+            }
+
+            let pi = cc.pi_num(p_s, rm)?;
+            let mut f = pi.mul(self, p_s, rm)?.reciprocal(p_s, rm)?;
+            f.set_exponent(f.exponent() + 1);
+            let f = f.sqrt(p_s, rm)?;
+
+            return Ok(Some(s.mul(&f, p_s, rm)?));
+        }
+    }
+
+    // Computes the Euler-Mascheroni constant with precision `p` and the relative error of a few units in the last place
+    // using the Brent-McMillan algorithm: gamma = U / V - ln(N), where
+    // U = sum((N^k / k!)^2 * H_k), V = sum((N^k / k!)^2), k = 0, 1, ..., and the error is about e^(-4N).
+    fn euler_gamma(p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        let rm = RoundingMode::None;
+
+        let nn = p / 5 + 2;
+
+        // the terms reach e^(2N)
+        let p_s = p + 3 * nn + log2_ceil(4 * nn) + 8;
+
+        let n2 = Self::from_usize(nn)?.powi(2, p_s, rm)?;
+
+        // a = (N^k / k!)^2 * (H_k - ln(N)), b = (N^k / k!)^2
+        let mut a = Self::from_usize(nn)?.ln(p_s, rm, cc)?.neg()?;
+        let mut b = Self::from_word(1, p_s)?;
+        let mut u = a.clone()?;
+        let mut v = b.clone()?;
+
+        let mut k = 1;
+        loop {
+            let kk = Self::from_usize(k)?;
+
+            b = b.mul(&n2, p_s, rm)?.div(&kk, p_s, rm)?.div(&kk, p_s, rm)?;
+            a = a
+                .mul(&n2, p_s, rm)?
+                .div(&kk, p_s, rm)?
+                .add(&b, p_s, rm)?
+                .div(&kk, p_s, rm)?;
+
+            u = u.add(&a, p_s, rm)?;
+            v = v.add(&b, p_s, rm)?;
+
+            if k > nn && (b.exponent() as isize) < v.exponent() as isize - p_s as isize {
+                break;
+            }
+
+            k += 1;
+        }
+
+        u.div(&v, p_s, rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::consts::ONE;
+
+    #[test]
+    fn test_bessel() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+        let p = 192;
+
+        let mut eps = ONE.clone().unwrap();
+        eps.set_exponent(-185);
+
+        // relative difference of `a` and the decimal number `s` is less than 2^(-185)
+        let check = |a: &BigFloatNumber, s: &str| {
+            let b = BigFloatNumber::parse(s, crate::Radix::Dec, 256, RoundingMode::None).unwrap();
+            let d = a.sub(&b, 256, rm).unwrap().div(&b, 256, rm).unwrap();
+            assert!(d.abs_cmp(&eps) < 0, "{:?} {}", a, s);
+        };
+
+        let g = BigFloatNumber::euler_gamma(p, &mut cc).unwrap();
+        check(
+            &g,
+            "0.577215664901532860606512090082402431042159335939923598805767",
+        );
+
+        // zero
+        let z = BigFloatNumber::new(p).unwrap();
+        assert!(z.bessel_j(0, p, rm, &mut cc).unwrap().cmp(&ONE) == 0);
+        assert!(z.bessel_j(3, p, rm, &mut cc).unwrap().is_zero());
+        assert_eq!(
+            z.bessel_y(1, p, rm, &mut cc).unwrap_err(),
+            Error::ExponentOverflow(Sign::Neg)
+        );
+        assert_eq!(
+            ONE.neg().unwrap().bessel_y(1, p, rm, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+
+        let one = BigFloatNumber::from_word(1, p).unwrap();
+        check(
+            &one.bessel_j(0, p, rm, &mut cc).unwrap(),
+            "0.765197686557966551449717526102663220909274289755325241861548",
+        );
+        check(
+            &one.bessel_y(0, p, rm, &mut cc).unwrap(),
+            "0.0882569642156769579829267660235151628278175230906755467110438",
+        );
+        check(
+            &one.neg().unwrap().bessel_j(1, p, rm, &mut cc).unwrap(),
+            "-0.440050585744933515959682203718914913127372301992765251136758",
+        );
+
+        let x = BigFloatNumber::from_f64(p, 2.5).unwrap();
+        check(
+            &x.bessel_j(5, p, rm, &mut cc).unwrap(),
+            "0.01950162513450321988647198392586573259235728330215881955762",
+        );
+        check(
+            &x.bessel_y(3, p, rm, &mut cc).unwrap(),
+            "-0.756055496753670996837902977229297969826797597217828206922965",
+        );
+
+        // the series with cancellation
+        let x = BigFloatNumber::from_word(30, p).unwrap();
+        check(
+            &x.bessel_j(2, p, rm, &mut cc).unwrap(),
+            "0.0784512460732653489012800398800397552702138207132934237144265",
+        );
+        check(
+            &x.bessel_y(7, p, rm, &mut cc).unwrap(),
+            "0.0272021183952055919818307721553266416711266887327044206547741",
+        );
+
+        // the asymptotic expansion
+        let x = BigFloatNumber::from_word(1000, p).unwrap();
+        check(
+            &x.bessel_j(1, p, rm, &mut cc).unwrap(),
+            "0.00472831190708952391757607190121691628541802420205963686871972",
+        );
+        check(
+            &x.bessel_y(2, p, rm, &mut cc).unwrap(),
+            "-0.0047654866402075169576029861778595375917362686065317182427967",
+        );
+
+        // close to the first zero of J_0
+        let x = BigFloatNumber::parse("2.4048255576957727686", crate::Radix::Dec, 64, rm).unwrap();
+        let j = x.bessel_j(0, p, rm, &mut cc).unwrap();
+        let j2 = x.bessel_j(0, p + 128, rm, &mut cc).unwrap();
+        let d = j.sub(&j2, p, rm).unwrap().div(&j2, p, rm).unwrap();
+        assert!(d.abs_cmp(&eps) < 0);
+
+        // J_(n-1)(x) + J_(n+1)(x) = 2n/x * J_n(x) for both expansions
+        for x in [0.001, 0.7, 12.5, 150.0, 3000.0] {
+            let x = BigFloatNumber::from_f64(p, x).unwrap();
+            for n in [1, 4, 9] {
+                for second in [false, true] {
+                    let f = |n: usize, cc: &mut Consts| {
+                        if second {
+                            x.bessel_y(n, p + 64, rm, cc).unwrap()
+                        } else {
+                            x.bessel_j(n, p + 64, rm, cc).unwrap()
+                        }
+                    };
+
+                    let l = f(n - 1, &mut cc)
+                        .add(&f(n + 1, &mut cc), p + 64, rm)
+                        .unwrap();
+                    let mut r = f(n, &mut cc)
+                        .mul_word(n as Word, p + 64, rm)
+                        .unwrap()
+                        .div(&x, p + 64, rm)
+                        .unwrap();
+                    r.set_exponent(r.exponent() + 1);
+
+                    let d = l.sub(&r, p, rm).unwrap().div(&r, p, rm).unwrap();
+                    assert!(d.abs_cmp(&eps) < 0, "{:?} {} {}", x, n, second);
+                }
+            }
+        }
+    }
+}
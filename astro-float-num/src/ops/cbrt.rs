@@ -9,7 +9,7 @@ use crate::{
 
 impl BigFloatNumber {
     /// Computes the cube root of a number with precision `p`. The result is rounded using the rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -20,9 +20,7 @@ impl BigFloatNumber {
         Self::p_assertion(p)?;
 
         if self.is_zero() {
-            let mut ret = Self::new(p)?;
-            ret.set_sign(self.sign());
-            return Ok(ret);
+            return Self::new2(p, self.sign(), self.inexact());
         }
 
         let (e1, m1_opt) = self.normalize()?;
@@ -0,0 +1,434 @@
+//! Compound interest (1 + x)^n and power of a ratio (a / b)^n.
+
+use crate::common::consts::ONE;
+use crate::common::util::invert_rm_for_sign;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::ops::util::compute_small_exp;
+use crate::Consts;
+use crate::Sign;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Computes (1 + `self`)^`n` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is accurate even if `self` is close to zero and `n` is large,
+    /// because 1 + `self` is never rounded before it is raised to the power.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large, or `self` is -1 and `n` is negative.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: `self` is less than -1, or the precision is incorrect.
+    pub fn compound(
+        &self,
+        n: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() || n.is_zero() {
+            let mut ret = Self::from_word(1, p)?;
+            ret.set_inexact(self.inexact() || n.inexact());
+            return Ok(ret);
+        }
+
+        let e = self.exponent() as isize;
+
+        if self.is_negative() && e >= 1 && self.abs_cmp(&ONE) >= 0 {
+            return if self.abs_cmp(&ONE) == 0 {
+                let z = Self::new2(p, Sign::Pos, self.inexact())?;
+                z.pow(n, p, rm, cc)
+            } else {
+                Err(Error::InvalidArgument)
+            };
+        }
+
+        if Self::one_plus_is_cheap(self, p) {
+            // 1 + x is exact, and pow handles exact results
+            let d = ONE.add_full_prec(self)?;
+            return d.pow(n, p, rm, cc);
+        }
+
+        Self::exp_mul_ln(n, p, rm, cc, |q, cc| Self::ln1p(self, q, cc))
+    }
+
+    /// Computes (`self` / `d2`)^`n` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is accurate even if the ratio is close to 1 and `n` is large,
+    /// because the ratio is never rounded before it is raised to the power.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large, or `self` is zero and `n` is negative.
+    ///  - DivisionByZero: `d2` is zero.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the ratio is negative, and `n` is not an integer number; the precision is incorrect.
+    pub fn pow_ratio(
+        &self,
+        d2: &Self,
+        n: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if d2.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+
+        if n.is_zero() {
+            let mut ret = Self::from_word(1, p)?;
+            ret.set_inexact(n.inexact());
+            return Ok(ret);
+        }
+
+        if self.is_zero() {
+            let s = if self.sign() == d2.sign() { Sign::Pos } else { Sign::Neg };
+            let z = Self::new2(p, s, self.inexact())?;
+            return z.pow(n, p, rm, cc);
+        }
+
+        if self.sign() != d2.sign() {
+            // (-r)^n = r^n for even n, and -(r^n) for odd n
+            if !n.is_int() {
+                return Err(Error::InvalidArgument);
+            }
+
+            let mut a = self.clone()?;
+            a.inv_sign();
+
+            return if n.is_odd_int() {
+                let mut ret =
+                    a.pow_ratio(d2, n, p, invert_rm_for_sign(rm), cc)
+                        .map_err(|e| match e {
+                            Error::ExponentOverflow(_) => Error::ExponentOverflow(Sign::Neg),
+                            e => e,
+                        })?;
+                ret.inv_sign();
+                Ok(ret)
+            } else {
+                a.pow_ratio(d2, n, p, rm, cc)
+            };
+        }
+
+        // the ratio can be exact, and then pow handles exact results
+        let q = self.div(
+            d2,
+            self.mantissa_max_bit_len() + WORD_BIT_SIZE,
+            RoundingMode::None,
+        )?;
+
+        if !q.inexact() {
+            return q.abs()?.pow(n, p, rm, cc);
+        }
+
+        if n.is_negative() {
+            // the inverted ratio can be exact as well, and then (a/b)^n = (b/a)^(-n)
+            let q = d2.div(
+                self,
+                d2.mantissa_max_bit_len() + WORD_BIT_SIZE,
+                RoundingMode::None,
+            )?;
+
+            if !q.inexact() {
+                return q.abs()?.pow(&n.neg()?, p, rm, cc);
+            }
+        }
+
+        // Neither a/b, nor b/a is exactly representable, and then (a/b)^n is not representable for n != 0.
+
+        let a = self.abs()?;
+        let b = d2.abs()?;
+
+        Self::exp_mul_ln(n, p, rm, cc, |q, cc| {
+            // x = a/b - 1 has the relative error not larger than 2^(1-q)
+            let x = a.sub(&b, q + 1, RoundingMode::None)?;
+            let x = x.div(&b, q + 1, RoundingMode::None)?;
+
+            if (x.exponent() as isize) < 0 {
+                // ln(1 + x) is well conditioned for |x| < 1/2
+                Self::ln1p(&x, q, cc)
+            } else {
+                // |ln(a/b)| is not smaller than ln(3/2)
+                let r = a.div(&b, q + 2, RoundingMode::None)?;
+                r.ln(q, RoundingMode::None, cc)
+            }
+        })
+    }
+
+    // True if 1 + x can be computed exactly using the number of bits comparable to the precision `p`
+    // and the precision of x.
+    fn one_plus_is_cheap(x: &Self, p: usize) -> bool {
+        let e = x.exponent() as isize;
+        let lim = (p + 2 * WORD_BIT_SIZE) as isize;
+
+        e >= -lim && e <= lim.max(x.mantissa_max_bit_len() as isize)
+    }
+
+    // Computes ln(1 + x) for x > -1 with precision `p` and the relative error of a few units in the last place.
+    fn ln1p(x: &Self, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        let e = x.exponent() as isize;
+
+        if e < -(p as isize) - 2 {
+            // ln(1 + x) = x - x^2/2 + ..., where x^2/2 is below the error
+            let mut ret = x.clone()?;
+            ret.set_precision(p, RoundingMode::None)?;
+            Ok(ret)
+        } else if Self::one_plus_is_cheap(x, p) {
+            let d = ONE.add_full_prec(x)?;
+            d.ln(p, RoundingMode::None, cc)
+        } else {
+            // x is large, and ln(1 + x) is well conditioned
+            let d = ONE.add(x, p + 2, RoundingMode::None)?;
+            d.ln(p, RoundingMode::None, cc)
+        }
+    }
+
+    // Computes e^(n * l), where the function `ln` computes l with the given precision
+    // and the relative error of a few units in the last place.
+    fn exp_mul_ln<F>(
+        n: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+        mut ln: F,
+    ) -> Result<Self, Error>
+    where
+        F: FnMut(usize, &mut Consts) -> Result<Self, Error>,
+    {
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p.max(n.mantissa_max_bit_len()) + p_inc;
+
+        loop {
+            let p_x = p_wrk + 4;
+
+            // the absolute error of n * l must be smaller than 2^(-p_x)
+            let mut l = ln(p_x, cc)?;
+            let e_m = n.exponent() as isize + l.exponent() as isize;
+            let m_neg = n.is_negative() != l.is_negative();
+
+            // |n * l| < 2^e_m
+            compute_small_exp!(ONE, e_m, m_neg, p, rm);
+
+            // |n * l| >= 2^(e_m - 2), and e^(n * l) is out of the range of the exponent
+            if e_m > WORD_BIT_SIZE as isize + 2 {
+                return if m_neg {
+                    Self::new2(p, Sign::Pos, true)
+                } else {
+                    Err(Error::ExponentOverflow(Sign::Pos))
+                };
+            }
+
+            let p_m = if e_m > 0 {
+                let p_m = p_x + e_m as usize + 4;
+                l = ln(p_m, cc)?;
+                p_m
+            } else {
+                p_x + 4
+            };
+
+            let m = match n.mul(&l, p_m, RoundingMode::None) {
+                Err(Error::ExponentOverflow(Sign::Neg)) => {
+                    return Self::new2(p, Sign::Pos, true);
+                }
+                m => m?,
+            };
+
+            compute_small_exp!(ONE, m.exponent() as isize, m.is_negative(), p, rm);
+
+            let mut ret = m.exp(p_x, RoundingMode::None, cc)?;
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                ret.set_inexact(true);
+                return Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::util::rand_p;
+    use crate::Exponent;
+
+    #[test]
+    fn test_compound() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+        let n = |i: i64| BigFloatNumber::from_i64(i, 128).unwrap();
+
+        // exact results
+        for rm in [RoundingMode::Up, RoundingMode::Down, RoundingMode::ToEven] {
+            let half = BigFloatNumber::from_f64(128, 0.5).unwrap();
+            let d = half.compound(&n(3), 128, rm, &mut cc).unwrap();
+            assert_eq!(d.cmp(&BigFloatNumber::from_f64(128, 3.375).unwrap()), 0);
+            assert!(!d.inexact());
+
+            let d = n(3).compound(&half, 128, rm, &mut cc).unwrap();
+            assert_eq!(d.cmp(&n(2)), 0);
+
+            let d = n(12).pow_ratio(&n(3), &half, 128, rm, &mut cc).unwrap();
+            assert_eq!(d.cmp(&n(2)), 0);
+            assert!(!d.inexact());
+
+            let d = n(-12).pow_ratio(&n(8), &n(3), 128, rm, &mut cc).unwrap();
+            assert_eq!(d.cmp(&BigFloatNumber::from_f64(128, -3.375).unwrap()), 0);
+        }
+
+        // (1 - 2^-100)^(2^100) = e^(-1) * (1 - 2^-101 + ...)
+        let p = 256;
+        let mut x = BigFloatNumber::from_word(1, p).unwrap();
+        x.set_exponent(-99);
+        x.inv_sign();
+        let mut m = BigFloatNumber::from_word(1, p).unwrap();
+        m.set_exponent(101);
+        let d = x.compound(&m, p, rm, &mut cc).unwrap();
+        let e1 = ONE.neg().unwrap().exp(p + 128, rm, &mut cc).unwrap();
+        let d0 = d.sub(&e1, p, rm).unwrap();
+        assert!(d0.is_negative());
+        assert_eq!(d0.exponent(), d.exponent() - 101);
+
+        // tiny x and huge n: (1 + 2^-1000)^(3*2^998) = e^(3/4) * (1 - 3*2^-1002 + ...)
+        let mut x = BigFloatNumber::from_word(1, p).unwrap();
+        x.set_exponent(-999);
+        let mut m = BigFloatNumber::from_word(3, p).unwrap();
+        m.set_exponent(1000);
+        let d = x.compound(&m, p, rm, &mut cc).unwrap();
+        let mut a = BigFloatNumber::from_word(3, p).unwrap();
+        a.set_exponent(0);
+        let d0 = a.exp(p, rm, &mut cc).unwrap();
+        assert_eq!(d.cmp(&d0), 0);
+
+        // 1 + tiny
+        let mut m = n(5);
+        m.set_exponent(-1500);
+        let d = x.compound(&m, p, RoundingMode::Up, &mut cc).unwrap();
+        assert_eq!(d.cmp(&ONE), 1);
+        let d = x.compound(&m, p, RoundingMode::Down, &mut cc).unwrap();
+        assert_eq!(d.cmp(&ONE), 0);
+
+        // ratio close to 1: (1 + 1/b)^b = e * (1 - 1/(2b) + ...)
+        let p = 640;
+        let mut b = BigFloatNumber::from_word(3, p).unwrap();
+        b.set_exponent(400);
+        let a = b.add(&ONE, p, RoundingMode::None).unwrap();
+        let d = a.pow_ratio(&b, &b, p, rm, &mut cc).unwrap();
+        let e = ONE.exp(p, rm, &mut cc).unwrap();
+        let d0 = d.sub(&e, p, rm).unwrap();
+        assert!(d0.is_negative());
+        assert_eq!(d0.exponent(), -399);
+
+        // comparison with pow
+        for _ in 0..50 {
+            let p = rand_p();
+            let x = BigFloatNumber::random_normal(p, -2, 2).unwrap();
+            let x = if x.is_negative() && x.abs_cmp(&ONE) >= 0 {
+                x.div(&n(8), p, rm).unwrap()
+            } else {
+                x
+            };
+            let m = BigFloatNumber::random_normal(p, -5, 5).unwrap();
+            let d = x.compound(&m, p, rm, &mut cc).unwrap();
+            let d0 = ONE
+                .add_full_prec(&x)
+                .unwrap()
+                .pow(&m, p, rm, &mut cc)
+                .unwrap();
+            assert_eq!(d.cmp(&d0), 0);
+
+            let b = BigFloatNumber::random_normal(p, -5, 5)
+                .unwrap()
+                .abs()
+                .unwrap();
+            let a = b.mul_full_prec(&ONE.add_full_prec(&x).unwrap()).unwrap();
+            let d = a.pow_ratio(&b, &m, p, rm, &mut cc).unwrap();
+            let eps = (d.exponent() as isize - p as isize + 2) as Exponent;
+            let diff = d.sub(&d0, p, rm).unwrap();
+            assert!(diff.is_zero() || diff.exponent() <= eps);
+        }
+
+        // special cases
+        let m1 = n(-1);
+        assert!(m1.compound(&n(2), 128, rm, &mut cc).unwrap().is_zero());
+        assert_eq!(
+            m1.compound(&n(-2), 128, rm, &mut cc).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+        assert_eq!(
+            n(-2).compound(&n(2), 128, rm, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            n(2).pow_ratio(&n(0), &n(2), 128, rm, &mut cc).unwrap_err(),
+            Error::DivisionByZero
+        );
+        assert_eq!(
+            n(2).pow_ratio(&n(-3), &m1.div(&n(2), 128, rm).unwrap(), 128, rm, &mut cc)
+                .unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert!(n(0)
+            .pow_ratio(&n(3), &n(2), 128, rm, &mut cc)
+            .unwrap()
+            .is_zero());
+
+        // inexact ratio with zero and integer exponents
+        let p = 64;
+        let d = n(1).pow_ratio(&n(3), &n(0), p, rm, &mut cc).unwrap();
+        assert_eq!(d.cmp(&ONE), 0);
+        assert!(!d.inexact());
+        let d = n(1).pow_ratio(&n(3), &n(-1), p, rm, &mut cc).unwrap();
+        assert_eq!(d.cmp(&n(3)), 0);
+        assert!(!d.inexact());
+        let d = n(2).pow_ratio(&n(3), &n(-1), p, rm, &mut cc).unwrap();
+        assert_eq!(d.cmp(&BigFloatNumber::from_f64(p, 1.5).unwrap()), 0);
+        let d = n(-1).pow_ratio(&n(3), &n(-1), p, rm, &mut cc).unwrap();
+        assert_eq!(d.cmp(&n(-3)), 0);
+        let d = n(1).pow_ratio(&n(3), &n(2), p, rm, &mut cc).unwrap();
+        let d0 = ONE.div(&n(9), p, rm).unwrap();
+        assert_eq!(d.cmp(&d0), 0);
+        assert!(d.inexact());
+
+        // overflow and underflow
+        let tiny = BigFloatNumber::min_positive(p).unwrap();
+        let huge = BigFloatNumber::max_value(p).unwrap();
+        let x = BigFloatNumber::from_f64(p, 1e-300).unwrap();
+        assert_eq!(
+            x.compound(&huge, p, rm, &mut cc).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+        assert!(x
+            .compound(&huge.neg().unwrap(), p, rm, &mut cc)
+            .unwrap()
+            .is_zero());
+        let d = tiny.compound(&tiny, p, RoundingMode::Up, &mut cc).unwrap();
+        assert_eq!(d.cmp(&ONE), 1);
+        let d = tiny
+            .compound(&tiny, p, RoundingMode::Down, &mut cc)
+            .unwrap();
+        assert_eq!(d.cmp(&ONE), 0);
+        assert_eq!(
+            huge.pow_ratio(&n(3), &huge, p, rm, &mut cc).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+        assert!(huge
+            .pow_ratio(&n(3), &huge.neg().unwrap(), p, rm, &mut cc)
+            .unwrap()
+            .is_zero());
+    }
+}
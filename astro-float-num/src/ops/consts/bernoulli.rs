@@ -0,0 +1,182 @@
+//! Bernoulli numbers
+
+use crate::common::consts::ONE;
+use crate::defs::Error;
+use crate::num::BigFloatNumber;
+use crate::RoundingMode;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+// Computes tangent numbers T_1, ..., T_n exactly (Brent and Harvey, "Fast computation of Bernoulli, Tangent and Secant numbers").
+fn tangent_numbers(n: usize) -> Result<Vec<BigFloatNumber>, Error> {
+    let mut t = Vec::new();
+    t.try_reserve_exact(n)?;
+
+    t.push(BigFloatNumber::from_usize(1)?);
+    for k in 1..n {
+        let v = t[k - 1].mul_full_prec(&BigFloatNumber::from_usize(k)?)?;
+        t.push(v);
+    }
+
+    for k in 1..n {
+        for j in k..n {
+            // T[j] = (j - k) * T[j - 1] + (j - k + 2) * T[j]
+            let mut v = t[j].mul_full_prec(&BigFloatNumber::from_usize(j - k + 2)?)?;
+
+            if j > k {
+                let d = t[j - 1].mul_full_prec(&BigFloatNumber::from_usize(j - k)?)?;
+                v = v.add_full_prec(&d)?;
+            }
+
+            t[j] = v;
+        }
+    }
+
+    Ok(t)
+}
+
+// Returns the denominator of B_n for even n using von Staudt-Clausen theorem:
+// the product of all primes q such that q - 1 divides n.
+fn denominator(n: usize) -> Result<BigFloatNumber, Error> {
+    let mut ret = BigFloatNumber::from_usize(1)?;
+
+    for d in 1..=n {
+        if n.is_multiple_of(d) && is_prime(d + 1) {
+            ret = ret.mul_full_prec(&BigFloatNumber::from_usize(d + 1)?)?;
+        }
+    }
+
+    Ok(ret)
+}
+
+fn is_prime(q: usize) -> bool {
+    if q < 2 {
+        return false;
+    }
+
+    let mut d = 2;
+    while d * d <= q {
+        if q.is_multiple_of(d) {
+            return false;
+        }
+        d += 1;
+    }
+
+    true
+}
+
+/// Holds Bernoulli numbers B_0, B_2, B_4, ... as exact ratios of integers.
+#[derive(Debug)]
+pub struct BernoulliCache {
+    b: Vec<(BigFloatNumber, BigFloatNumber)>,
+}
+
+impl BernoulliCache {
+    pub fn new() -> Result<Self, Error> {
+        let mut b = Vec::new();
+        b.try_reserve_exact(1)?;
+        b.push((ONE.clone()?, ONE.clone()?));
+
+        Ok(BernoulliCache { b })
+    }
+
+    /// Returns the numerator and the denominator of B_n for even `n`.
+    pub(crate) fn for_index(
+        &mut self,
+        n: usize,
+    ) -> Result<(BigFloatNumber, BigFloatNumber), Error> {
+        debug_assert!(n.is_multiple_of(2));
+
+        let k = n / 2;
+
+        if k >= self.b.len() {
+            // tangent numbers are recomputed from scratch, so compute ahead
+            let kmax = k.max(self.b.len() * 2);
+            let t = tangent_numbers(kmax)?;
+
+            self.b.try_reserve_exact(kmax + 1 - self.b.len())?;
+
+            for i in self.b.len()..=kmax {
+                // B_2i = (-1)^(i - 1) * 2i * T_i / (2^2i * (2^2i - 1))
+                let d = denominator(2 * i)?;
+
+                let num = t[i - 1]
+                    .mul_full_prec(&BigFloatNumber::from_usize(2 * i)?)?
+                    .mul_full_prec(&d)?;
+
+                let mut pow4 = ONE.clone()?;
+                pow4.set_exponent(2 * i as crate::Exponent + 1);
+                let den = pow4.sub_full_prec(&ONE)?;
+
+                // the division is exact
+                let mut q = num.div(&den, num.mantissa_max_bit_len(), RoundingMode::ToEven)?;
+                q.set_exponent(q.exponent() - 2 * i as crate::Exponent);
+                q.set_inexact(false);
+
+                if i % 2 == 0 {
+                    q.inv_sign();
+                }
+
+                self.b.push((q, d));
+            }
+        }
+
+        let (n, d) = &self.b[k];
+
+        Ok((n.clone()?, d.clone()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_bernoulli() {
+        let mut b = BernoulliCache::new().unwrap();
+
+        // B_0 .. B_20
+        let expected: [(i64, u64); 11] = [
+            (1, 1),
+            (1, 6),
+            (-1, 30),
+            (1, 42),
+            (-1, 30),
+            (5, 66),
+            (-691, 2730),
+            (7, 6),
+            (-3617, 510),
+            (43867, 798),
+            (-174611, 330),
+        ];
+
+        // access out of order to exercise the cache growth
+        for i in [3, 0, 10, 1, 2, 4, 5, 6, 7, 8, 9] {
+            let (n, d) = b.for_index(2 * i).unwrap();
+            assert!(
+                n.cmp(&BigFloatNumber::from_i64(expected[i].0, 64).unwrap()) == 0,
+                "{}",
+                i
+            );
+            assert!(
+                d.cmp(&BigFloatNumber::from_u64(expected[i].1, 64).unwrap()) == 0,
+                "{}",
+                i
+            );
+        }
+
+        // B_60 = -1215233140483755572040304994079820246041491 / 56786730
+        let (n, d) = b.for_index(60).unwrap();
+        let n0 = BigFloatNumber::parse(
+            "-1215233140483755572040304994079820246041491",
+            crate::Radix::Dec,
+            192,
+            RoundingMode::None,
+        )
+        .unwrap();
+        assert!(n.cmp(&n0) == 0);
+        assert!(d.cmp(&BigFloatNumber::from_u64(56786730, 64).unwrap()) == 0);
+    }
+}
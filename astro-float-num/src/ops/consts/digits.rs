@@ -0,0 +1,204 @@
+//! Streaming digits of π.
+
+use crate::defs::{Error, Radix, Word, WORD_BIT_SIZE, WORD_MAX};
+use crate::num::BigFloatNumber;
+use crate::ops::consts::Consts;
+use crate::RoundingMode;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+// Initial working precision of the digit generator.
+const START_PRECISION: usize = 256;
+
+/// Iterator over successive digits of π in a given radix.
+///
+/// The first digits are the digits of the integer part of π (`3`, or `1`, `1` in binary),
+/// followed by the digits of the fractional part.
+/// Every returned digit is final: the generator computes lower and upper bounds of π and yields only
+/// the digits on which both bounds agree. When the certified digits are exhausted, the working precision
+/// is doubled and π is taken from the constants cache at the new precision.
+///
+/// Once an error has been returned the iterator ends.
+#[derive(Debug)]
+pub struct PiDigits<'a> {
+    cc: &'a mut Consts,
+    rdx: Radix,
+    p: usize,
+    buf: Vec<u8>,
+    pos: usize,
+    frac_digits: usize,
+    failed: bool,
+}
+
+impl<'a> PiDigits<'a> {
+    pub(super) fn new(cc: &'a mut Consts, rdx: Radix) -> Self {
+        let buf = match rdx {
+            Radix::Bin => [1, 1].to_vec(),
+            _ => [3].to_vec(),
+        };
+
+        PiDigits {
+            cc,
+            rdx,
+            p: START_PRECISION / 2,
+            buf,
+            pos: 0,
+            frac_digits: 0,
+            failed: false,
+        }
+    }
+
+    // Largest power of the radix that fits in a word, and its exponent.
+    fn chunk_base(&self) -> (Word, usize) {
+        let r = self.rdx as Word;
+        let mut base = 1;
+        let mut n = 0;
+        while base <= WORD_MAX / r {
+            base *= r;
+            n += 1;
+        }
+        (base, n)
+    }
+
+    // Splits chunk `w` into `n` digits, most significant first.
+    fn chunk_digits(&self, mut w: Word, n: usize, digits: &mut [u8]) {
+        let r = self.rdx as Word;
+        for d in digits[..n].iter_mut().rev() {
+            *d = (w % r) as u8;
+            w /= r;
+        }
+    }
+
+    // Multiplies the fractional part `f` by `base` exactly and returns the integer part of the product
+    // together with the new fractional part.
+    fn next_chunk(
+        f: &BigFloatNumber,
+        base: Word,
+        p: usize,
+    ) -> Result<(Word, BigFloatNumber), Error> {
+        let v = f.mul_word(base, p + WORD_BIT_SIZE, RoundingMode::None)?;
+        let w = v.to_u64(RoundingMode::ToZero)? as Word;
+        let mut f = v.fract()?;
+        f.set_precision(p, RoundingMode::None)?;
+        Ok((w, f))
+    }
+
+    // Doubles the working precision and fills the buffer with the digits which follow those already returned.
+    fn refill(&mut self) -> Result<(), Error> {
+        self.p *= 2;
+
+        let mut lo = self.cc.pi_num(self.p, RoundingMode::Down)?.fract()?;
+        let mut hi = self.cc.pi_num(self.p, RoundingMode::Up)?.fract()?;
+
+        let (base, n) = self.chunk_base();
+        let mut dlo = [0u8; WORD_BIT_SIZE];
+        let mut dhi = [0u8; WORD_BIT_SIZE];
+
+        self.buf.clear();
+        self.pos = 0;
+
+        let mut skip = self.frac_digits;
+
+        loop {
+            let (wlo, flo) = Self::next_chunk(&lo, base, self.p)?;
+            let (whi, fhi) = Self::next_chunk(&hi, base, self.p)?;
+
+            self.chunk_digits(wlo, n, &mut dlo);
+            self.chunk_digits(whi, n, &mut dhi);
+
+            let common = dlo[..n]
+                .iter()
+                .zip(dhi[..n].iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+
+            if common > skip {
+                self.buf.try_reserve(common - skip)?;
+                self.buf.extend_from_slice(&dlo[skip..common]);
+            }
+
+            skip = skip.saturating_sub(common);
+
+            if common < n {
+                break;
+            }
+
+            lo = flo;
+            hi = fhi;
+        }
+
+        self.frac_digits += self.buf.len();
+
+        Ok(())
+    }
+}
+
+impl Iterator for PiDigits<'_> {
+    type Item = Result<u8, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        while self.pos == self.buf.len() {
+            if let Err(e) = self.refill() {
+                self.failed = true;
+                return Some(Err(e));
+            }
+        }
+
+        let d = self.buf[self.pos];
+        self.pos += 1;
+
+        Some(Ok(d))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+
+    fn digits_str(rdx: Radix, n: usize) -> String {
+        let mut cc = Consts::new().unwrap();
+        cc.pi_digits(rdx)
+            .take(n)
+            .map(|d| char::from_digit(d.unwrap() as u32, 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_pi_digits() {
+        assert_eq!(
+            digits_str(Radix::Dec, 101),
+            "31415926535897932384626433832795028841971693993751\
+            058209749445923078164062862089986280348253421170679"
+        );
+        assert_eq!(
+            digits_str(Radix::Hex, 65),
+            "3243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89"
+        );
+        assert_eq!(digits_str(Radix::Oct, 12), "311037552421");
+        assert_eq!(digits_str(Radix::Bin, 16), "1100100100001111");
+
+        // many refills agree with a directly computed value
+        let n = 3000;
+        let s = digits_str(Radix::Hex, n + 1);
+        let mut cc = Consts::new().unwrap();
+        let pi = cc.pi_num(4 * n + 64, RoundingMode::ToZero).unwrap();
+        let (_, m, e) = pi
+            .convert_to_radix(Radix::Hex, None, RoundingMode::ToZero)
+            .unwrap();
+        assert_eq!(e, 1);
+        let expected: String = m[..n + 1]
+            .iter()
+            .map(|&d| char::from_digit(d as u32, 16).unwrap())
+            .collect();
+        assert_eq!(s, expected);
+    }
+}
@@ -136,7 +136,7 @@ mod tests {
     use crate::{RoundingMode, Sign};
 
     #[test]
-    #[cfg(target_arch = "x86")]
+    #[cfg(word32)]
     fn test_e_const() {
         let mut e = ECache::new().unwrap();
         let c = e.for_prec(320, RoundingMode::ToEven).unwrap();
@@ -156,7 +156,7 @@ mod tests {
     }
 
     #[test]
-    #[cfg(not(target_arch = "x86"))]
+    #[cfg(not(word32))]
     fn test_e_const() {
         let mut e = ECache::new().unwrap();
         let c = e.for_prec(320, RoundingMode::ToEven).unwrap();
@@ -0,0 +1,170 @@
+//! Process-wide constants cache.
+
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+use crate::BigFloat;
+use crate::Consts;
+use crate::Error;
+use crate::RoundingMode;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    // The cache is created on first use.
+    static ref GLOBAL_CONSTS: Mutex<Option<Consts>> = Mutex::new(None);
+}
+
+fn lock() -> MutexGuard<'static, Option<Consts>> {
+    // a panic in another thread can't leave the cache in an inconsistent state,
+    // because cached values are replaced only when the computation completes.
+    GLOBAL_CONSTS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Calls `f` with the process-wide constants cache and returns its result.
+/// The cache is initialized on the first call, and is locked until `f` returns,
+/// so calling any of the functions which use the process-wide cache from `f` causes a deadlock.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::with_consts;
+/// # use astro_float_num::BigFloat;
+/// # use astro_float_num::RoundingMode;
+/// let x = BigFloat::from_word(2, 128);
+///
+/// let y = with_consts(|cc| x.ln(128, RoundingMode::ToEven, cc)).unwrap();
+///
+/// assert_eq!(y, astro_float_num::ln_2(128, RoundingMode::ToEven));
+/// ```
+///
+/// ## Errors
+///
+///  - MemoryAllocation: failed to allocate memory for the constants cache.
+pub fn with_consts<R>(f: impl FnOnce(&mut Consts) -> R) -> Result<R, Error> {
+    let mut cc = lock();
+
+    if cc.is_none() {
+        *cc = Some(Consts::new()?);
+    }
+
+    // call to unwrap() is unreachable, because the cache has just been initialized.
+    Ok(f(cc.as_mut().unwrap()))
+}
+
+fn const_value(f: impl FnOnce(&mut Consts) -> BigFloat) -> BigFloat {
+    match with_consts(f) {
+        Ok(v) => v,
+        Err(e) => BigFloat::nan(Some(e)),
+    }
+}
+
+/// Returns the value of the pi number with precision `p` using rounding mode `rm`
+/// computed using the process-wide constants cache.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::BigFloat;
+/// # use astro_float_num::Consts;
+/// # use astro_float_num::RoundingMode;
+/// let pi = astro_float_num::pi(256, RoundingMode::ToEven);
+///
+/// let mut cc = Consts::new().unwrap();
+/// assert_eq!(pi, cc.pi(256, RoundingMode::ToEven));
+/// ```
+pub fn pi(p: usize, rm: RoundingMode) -> BigFloat {
+    const_value(|cc| cc.pi(p, rm))
+}
+
+/// Returns the value of the Euler number with precision `p` using rounding mode `rm`
+/// computed using the process-wide constants cache.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+pub fn e(p: usize, rm: RoundingMode) -> BigFloat {
+    const_value(|cc| cc.e(p, rm))
+}
+
+/// Returns the value of the natural logarithm of 2 with precision `p` using rounding mode `rm`
+/// computed using the process-wide constants cache.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+pub fn ln_2(p: usize, rm: RoundingMode) -> BigFloat {
+    const_value(|cc| cc.ln_2(p, rm))
+}
+
+/// Returns the value of the natural logarithm of 10 with precision `p` using rounding mode `rm`
+/// computed using the process-wide constants cache.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+pub fn ln_10(p: usize, rm: RoundingMode) -> BigFloat {
+    const_value(|cc| cc.ln_10(p, rm))
+}
+
+/// Reduces `x` by multiples of pi/2 using the process-wide constants cache.
+/// Returns the reduced value `r` with precision `p` rounded using the rounding mode `rm`, and the quadrant `k`,
+/// such that `x` = pi/2*(4*n + k) + r for an integer n, and `r` is in the interval (-pi/4, pi/4].
+/// The reduction is correct for arguments of any magnitude. See [BigFloat::rem_pi2] for details.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+/// The function returns NaN and quadrant 0 if `x` is NaN or Inf, or if the precision `p` is incorrect.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::BigFloat;
+/// # use astro_float_num::RoundingMode;
+/// let rm = RoundingMode::ToEven;
+/// let x = BigFloat::from_word(3, 64);
+///
+/// let (r, k) = astro_float_num::rem_pi2(&x, 128, rm);
+///
+/// assert_eq!(k, 2);
+/// assert_eq!(r, x.sub(&astro_float_num::pi(256, rm), 128, rm));
+/// ```
+pub fn rem_pi2(x: &BigFloat, p: usize, rm: RoundingMode) -> (BigFloat, usize) {
+    match with_consts(|cc| x.rem_pi2(p, rm, cc)) {
+        Ok(v) => v,
+        Err(e) => (BigFloat::nan(Some(e)), 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::thread;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_global_consts() {
+        let rm = RoundingMode::ToEven;
+        let mut cc = Consts::new().unwrap();
+
+        let handles: Vec<_> = (1..9)
+            .map(|i| {
+                thread::spawn(move || {
+                    let p = i * 320;
+                    (p, pi(p, rm), e(p, rm), ln_2(p, rm), ln_10(p, rm))
+                })
+            })
+            .collect();
+
+        for h in handles {
+            let (p, pi, e, ln_2, ln_10) = h.join().unwrap();
+
+            assert_eq!(pi, cc.pi(p, rm));
+            assert_eq!(e, cc.e(p, rm));
+            assert_eq!(ln_2, cc.ln_2(p, rm));
+            assert_eq!(ln_10, cc.ln_10(p, rm));
+        }
+
+        let x = BigFloat::from_word(1, 128);
+        let y = with_consts(|cc| x.exp(128, rm, cc)).unwrap();
+        assert_eq!(y, e(128, rm));
+
+        let x = pi(128, rm).powi(40, 128, rm);
+        assert_eq!(rem_pi2(&x, 256, rm), x.rem_pi2(256, rm, &mut cc));
+        let x = BigFloat::from_f64(-0.5, 128);
+        let (r, k) = rem_pi2(&x, 128, rm);
+        assert_eq!((&r, k), (&x, 0));
+        assert!(!r.inexact());
+        assert!(rem_pi2(&crate::NAN, 128, rm).0.is_nan());
+    }
+}
@@ -1,17 +1,28 @@
+mod bernoulli;
+mod digits;
 mod e;
+#[cfg(feature = "global-consts")]
+mod global;
 mod ln10;
 mod ln2;
 mod pi;
 
 use crate::common::util::round_p;
 use crate::num::BigFloatNumber;
+use crate::ops::consts::bernoulli::BernoulliCache;
 use crate::ops::consts::e::ECache;
 use crate::ops::consts::ln10::Ln10Cache;
 use crate::ops::consts::ln2::Ln2Cache;
 use crate::ops::consts::pi::PiCache;
 use crate::BigFloat;
 use crate::Error;
+use crate::Radix;
 use crate::RoundingMode;
+use crate::WORD_BIT_SIZE;
+
+pub use crate::ops::consts::digits::PiDigits;
+#[cfg(feature = "global-consts")]
+pub use crate::ops::consts::global::{e, ln_10, ln_2, pi, rem_pi2, with_consts};
 
 /// Constants cache contains arbitrary-precision mathematical constants.
 #[derive(Debug)]
@@ -20,6 +31,7 @@ pub struct Consts {
     e: ECache,
     ln2: Ln2Cache,
     ln10: Ln10Cache,
+    bernoulli: BernoulliCache,
 }
 
 /// In an ideal situation, the `Consts` structure is initialized with `Consts::new` only once,
@@ -36,11 +48,12 @@ impl Consts {
             e: ECache::new()?,
             ln2: Ln2Cache::new()?,
             ln10: Ln10Cache::new()?,
+            bernoulli: BernoulliCache::new()?,
         })
     }
 
     /// Returns the value of the pi number with precision `p` using rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -52,7 +65,7 @@ impl Consts {
     }
 
     /// Returns the value of the Euler number with precision `p` using rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -64,7 +77,7 @@ impl Consts {
     }
 
     /// Returns the value of the natural logarithm of 2 with precision `p` using rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -76,7 +89,7 @@ impl Consts {
     }
 
     /// Returns the value of the natural logarithm of 10 with precision `p` using rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -91,8 +104,57 @@ impl Consts {
         self.ln10.for_prec(p, rm)
     }
 
+    /// Returns the Bernoulli number B_`n` as the numerator and the denominator of the reduced fraction.
+    /// B_1 is -1/2.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    pub(crate) fn bernoulli_ratio_num(
+        &mut self,
+        n: usize,
+    ) -> Result<(BigFloatNumber, BigFloatNumber), Error> {
+        if n == 1 {
+            Ok((
+                BigFloatNumber::from_i8(-1, WORD_BIT_SIZE)?,
+                BigFloatNumber::from_word(2, WORD_BIT_SIZE)?,
+            ))
+        } else if n % 2 == 1 {
+            Ok((
+                BigFloatNumber::new(WORD_BIT_SIZE)?,
+                BigFloatNumber::from_word(1, WORD_BIT_SIZE)?,
+            ))
+        } else {
+            self.bernoulli.for_index(n)
+        }
+    }
+
+    /// Returns the value of the Bernoulli number B_`n` with precision `p` using rounding mode `rm`.
+    /// B_1 is -1/2.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub(crate) fn bernoulli_num(
+        &mut self,
+        n: usize,
+        p: usize,
+        rm: RoundingMode,
+    ) -> Result<BigFloatNumber, Error> {
+        let p = round_p(p);
+        let (num, den) = self.bernoulli_ratio_num(n)?;
+
+        if num.is_zero() {
+            BigFloatNumber::new(p)
+        } else {
+            num.div(&den, p, rm)
+        }
+    }
+
     /// Returns the value of the pi number with precision `p` using rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     pub fn pi(&mut self, p: usize, rm: RoundingMode) -> BigFloat {
         match self.pi_num(p, rm) {
             Ok(v) => v.into(),
@@ -100,8 +162,34 @@ impl Consts {
         }
     }
 
+    /// Returns an iterator over the digits of the pi number in radix `rdx`.
+    /// Digits of the integer part are returned first, followed by the digits of the fractional part.
+    /// The total number of digits does not need to be known in advance: the precision of pi
+    /// is increased as the iteration goes on, and only the digits that are known to be correct are returned.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use astro_float_num::{Consts, Radix};
+    ///
+    /// let mut cc = Consts::new().expect("Constants cache initialized.");
+    ///
+    /// let digits: Vec<u8> = cc.pi_digits(Radix::Dec).take(10).map(|d| d.unwrap()).collect();
+    ///
+    /// assert_eq!(digits, [3, 1, 4, 1, 5, 9, 2, 6, 5, 3]);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// The iterator returns an error and stops if memory for the computation cannot be allocated:
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    pub fn pi_digits(&mut self, rdx: Radix) -> PiDigits<'_> {
+        PiDigits::new(self, rdx)
+    }
+
     /// Returns the value of the Euler number with precision `p` using rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     pub fn e(&mut self, p: usize, rm: RoundingMode) -> BigFloat {
         match self.e_num(p, rm) {
             Ok(v) => v.into(),
@@ -110,7 +198,7 @@ impl Consts {
     }
 
     /// Returns the value of the natural logarithm of 2 with precision `p` using rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     pub fn ln_2(&mut self, p: usize, rm: RoundingMode) -> BigFloat {
         match self.ln_2_num(p, rm) {
             Ok(v) => v.into(),
@@ -119,11 +207,48 @@ impl Consts {
     }
 
     /// Returns the value of the natural logarithm of 10 with precision `p` using rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     pub fn ln_10(&mut self, p: usize, rm: RoundingMode) -> BigFloat {
         match self.ln_10_num(p, rm) {
             Ok(v) => v.into(),
             Err(e) => BigFloat::nan(Some(e)),
         }
     }
+
+    /// Returns the value of the Bernoulli number B_`n` with precision `p` using rounding mode `rm`.
+    /// B_1 is -1/2. Bernoulli numbers are computed exactly and cached, so subsequent calls for the same
+    /// or smaller `n` are cheap.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::Consts;
+    /// # use astro_float_num::RoundingMode;
+    /// let mut cc = Consts::new().unwrap();
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// // B_12 = -691/2730
+    /// let b = cc.bernoulli(12, 128, rm);
+    /// let r = BigFloat::from_i32(-691, 128).div(&BigFloat::from_i32(2730, 128), 128, rm);
+    ///
+    /// assert_eq!(b, r);
+    /// ```
+    pub fn bernoulli(&mut self, n: usize, p: usize, rm: RoundingMode) -> BigFloat {
+        match self.bernoulli_num(n, p, rm) {
+            Ok(v) => v.into(),
+            Err(e) => BigFloat::nan(Some(e)),
+        }
+    }
+
+    /// Returns the Bernoulli number B_`n` as the integer numerator and the positive integer denominator
+    /// of the reduced fraction. B_1 is -1/2.
+    /// Bernoulli numbers are computed exactly and cached.
+    pub fn bernoulli_ratio(&mut self, n: usize) -> (BigFloat, BigFloat) {
+        match self.bernoulli_ratio_num(n) {
+            Ok((num, den)) => (num.into(), den.into()),
+            Err(e) => (BigFloat::nan(Some(e)), BigFloat::nan(Some(e))),
+        }
+    }
 }
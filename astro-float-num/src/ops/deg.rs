@@ -0,0 +1,426 @@
+//! Angle conversion and trigonometric functions of arguments in degrees.
+
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::ops::consts::Consts;
+use crate::ops::util::compute_small_exp;
+use crate::Sign;
+use crate::WORD_BIT_SIZE;
+
+// Function of the reduced angle.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DegFn {
+    Sin,
+    Cos,
+    Tan,
+    Cot,
+}
+
+impl BigFloatNumber {
+    /// Converts an angle in degrees to radians with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn to_radians(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        self.mul_by_deg_ratio(true, p, rm, cc)
+    }
+
+    /// Converts an angle in radians to degrees with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn to_degrees(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        self.mul_by_deg_ratio(false, p, rm, cc)
+    }
+
+    /// Computes the sine of an angle in degrees with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The argument is reduced modulo 360 exactly before conversion to radians.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn sin_deg(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+
+        if self.is_zero() {
+            return Self::new2(p, self.sign(), self.inexact());
+        }
+
+        let (t, q) = self.deg_reduce()?;
+
+        match q {
+            0 => t.deg_fn(DegFn::Sin, false, p, rm, cc),
+            1 => t.deg_fn(DegFn::Cos, false, p, rm, cc),
+            2 => t.deg_fn(DegFn::Sin, true, p, rm, cc),
+            _ => t.deg_fn(DegFn::Cos, true, p, rm, cc),
+        }
+    }
+
+    /// Computes the cosine of an angle in degrees with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The argument is reduced modulo 360 exactly before conversion to radians.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn cos_deg(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+
+        if self.is_zero() {
+            let mut ret = Self::from_word(1, p)?;
+            ret.set_inexact(self.inexact());
+            return Ok(ret);
+        }
+
+        let (t, q) = self.deg_reduce()?;
+
+        match q {
+            0 => t.deg_fn(DegFn::Cos, false, p, rm, cc),
+            1 => t.deg_fn(DegFn::Sin, true, p, rm, cc),
+            2 => t.deg_fn(DegFn::Cos, true, p, rm, cc),
+            _ => t.deg_fn(DegFn::Sin, false, p, rm, cc),
+        }
+    }
+
+    /// Computes the tangent of an angle in degrees with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The argument is reduced modulo 360 exactly before conversion to radians.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the argument is an odd multiple of 90.
+    ///    The sign of the error is positive for 90 + 360 * k, and negative for 270 + 360 * k.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn tan_deg(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+
+        if self.is_zero() {
+            return Self::new2(p, self.sign(), self.inexact());
+        }
+
+        let (t, q) = self.deg_reduce()?;
+
+        if q & 1 == 0 {
+            t.deg_fn(DegFn::Tan, false, p, rm, cc)
+        } else if t.is_zero() {
+            Err(Error::ExponentOverflow(if q == 1 {
+                Sign::Pos
+            } else {
+                Sign::Neg
+            }))
+        } else {
+            t.deg_fn(DegFn::Cot, true, p, rm, cc)
+        }
+    }
+
+    // Multiplies `self` by pi/180 if `to_rad` is true, or by 180/pi otherwise.
+    fn mul_by_deg_ratio(
+        &self,
+        to_rad: bool,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+
+        if self.is_zero() {
+            return Self::new2(p, self.sign(), self.inexact());
+        }
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p.max(self.mantissa_max_bit_len()) + p_inc;
+
+        loop {
+            let mut ret = self.mul_by_deg_ratio_raw(to_rad, p_wrk + 4, cc)?;
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                ret.set_inexact(ret.inexact() | self.inexact());
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Multiplies `self` by pi/180 or 180/pi with precision `p`.
+    // The relative error of the result is less than 2^(-p+2).
+    fn mul_by_deg_ratio_raw(&self, to_rad: bool, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        let pi = cc.pi_num(p, RoundingMode::None)?;
+        let d180 = Self::from_word(180, WORD_BIT_SIZE)?;
+
+        let ratio = if to_rad {
+            pi.div(&d180, p, RoundingMode::None)?
+        } else {
+            d180.div(&pi, p, RoundingMode::None)?
+        };
+
+        self.mul(&ratio, p, RoundingMode::None)
+    }
+
+    // Represents `self` as 360*k + 90*q + t exactly, where |t| <= 45 (up to a tiny excess at the boundary).
+    // Returns t and q modulo 4.
+    fn deg_reduce(&self) -> Result<(Self, usize), Error> {
+        let d90 = Self::from_word(90, WORD_BIT_SIZE)?;
+        let d360 = Self::from_word(360, WORD_BIT_SIZE)?;
+
+        // |r| < 360, the remainder is exact
+        let r = self.rem(&d360)?;
+
+        let q = r
+            .div(&d90, WORD_BIT_SIZE, RoundingMode::None)?
+            .round(0, RoundingMode::ToEven)?;
+
+        let mut t =
+            if q.is_zero() { r.clone()? } else { r.sub_full_prec(&q.mul_full_prec(&d90)?)? };
+        t.set_inexact(self.inexact());
+
+        let q = q.to_i64(RoundingMode::None)?.rem_euclid(4) as usize;
+
+        Ok((t, q))
+    }
+
+    // Computes the function `f` of the reduced angle `self` in degrees negated if `neg` is true.
+    fn deg_fn(
+        &self,
+        f: DegFn,
+        neg: bool,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let s = if neg { self.sign().invert() } else { self.sign() };
+
+        // exact values
+        if self.is_zero() {
+            // cot of zero is handled by the caller
+            let mut ret = if f == DegFn::Cos { Self::from_word(1, p)? } else { Self::new(p)? };
+            ret.set_sign(if neg { Sign::Neg } else { Sign::Pos });
+            ret.set_inexact(self.inexact());
+            return Ok(ret);
+        }
+
+        let special = match f {
+            DegFn::Sin => Some((30, 0)),
+            DegFn::Tan | DegFn::Cot => Some((45, 1)),
+            DegFn::Cos => None,
+        };
+
+        if let Some((a, e)) = special {
+            if self.abs_cmp(&Self::from_word(a, WORD_BIT_SIZE)?) == 0 {
+                let mut ret = Self::from_word(1, p)?;
+                ret.set_exponent(e);
+                ret.set_sign(s);
+                ret.set_inexact(self.inexact());
+                return Ok(ret);
+            }
+        }
+
+        if f == DegFn::Cos {
+            // cos(x) = 1 - x^2/2 + ..., where |x| < |self| in radians
+            let mut one = Self::from_word(1, p)?;
+            one.set_sign(if neg { Sign::Neg } else { Sign::Pos });
+            compute_small_exp!(one, self.exponent() as isize * 2 - 1, true, p, rm);
+        }
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + 6;
+
+            let x = self.mul_by_deg_ratio_raw(true, p_x, cc)?;
+
+            let mut ret = match f {
+                DegFn::Sin => x.sin(p_x, RoundingMode::None, cc)?,
+                DegFn::Cos => x.cos(p_x, RoundingMode::None, cc)?,
+                DegFn::Tan => x.tan(p_x, RoundingMode::None, cc)?,
+                DegFn::Cot => x
+                    .tan(p_x, RoundingMode::None, cc)?
+                    .reciprocal(p_x, RoundingMode::None)?,
+            };
+
+            if neg {
+                ret.inv_sign();
+            }
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                ret.set_inexact(ret.inexact() | self.inexact());
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Exponent;
+
+    #[test]
+    fn test_deg() {
+        let p = 320;
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+
+        let mut eps = BigFloatNumber::from_word(1, p).unwrap();
+        eps.set_exponent(-(p as Exponent) + 3);
+
+        // conversion
+        let n = BigFloatNumber::from_word(180, p).unwrap();
+        let pi = cc.pi_num(p, rm).unwrap();
+        assert!(n.to_radians(p, rm, &mut cc).unwrap().cmp(&pi) == 0);
+        assert!(pi.to_degrees(p, rm, &mut cc).unwrap().cmp(&n) == 0);
+
+        let n = BigFloatNumber::from_word(1, p).unwrap();
+        let d = n.to_degrees(p, rm, &mut cc).unwrap();
+        let r = d.to_radians(p, rm, &mut cc).unwrap();
+        assert!(r.sub(&n, p, rm).unwrap().abs_cmp(&eps) < 0);
+
+        assert!(BigFloatNumber::new(p)
+            .unwrap()
+            .to_radians(p, rm, &mut cc)
+            .unwrap()
+            .is_zero());
+
+        // exact values: angle, sin, cos, tan; infinity is represented as ExponentOverflow
+        let inf = f64::INFINITY;
+        for (d, s, c, t) in [
+            (0, 0.0, 1.0, Some(0.0)),
+            (30, 0.5, 0.0, None),
+            (45, 0.0, 0.0, Some(1.0)),
+            (90, 1.0, 0.0, Some(inf)),
+            (135, 0.0, 0.0, Some(-1.0)),
+            (150, 0.5, 0.0, None),
+            (180, 0.0, -1.0, Some(0.0)),
+            (210, -0.5, 0.0, None),
+            (225, 0.0, 0.0, Some(1.0)),
+            (270, -1.0, 0.0, Some(-inf)),
+            (315, 0.0, 0.0, Some(-1.0)),
+            (330, -0.5, 0.0, None),
+        ] {
+            for k in [0i64, 1, -1, 1000, -1_000_000_007] {
+                let deg = BigFloatNumber::from_i64(d + 360 * k, p).unwrap();
+
+                if s != 0.0 || d % 180 == 0 {
+                    let v = deg.sin_deg(p, rm, &mut cc).unwrap();
+                    assert!(v.cmp(&BigFloatNumber::from_f64(p, s).unwrap()) == 0);
+                }
+
+                if c != 0.0 || d % 180 == 90 {
+                    let v = deg.cos_deg(p, rm, &mut cc).unwrap();
+                    assert!(v.cmp(&BigFloatNumber::from_f64(p, c).unwrap()) == 0);
+                }
+
+                match t {
+                    Some(t) if t.is_infinite() => {
+                        let s = if t > 0.0 { Sign::Pos } else { Sign::Neg };
+                        assert_eq!(
+                            deg.tan_deg(p, rm, &mut cc).unwrap_err(),
+                            Error::ExponentOverflow(s)
+                        );
+                    }
+                    Some(t) => {
+                        let v = deg.tan_deg(p, rm, &mut cc).unwrap();
+                        assert!(v.cmp(&BigFloatNumber::from_f64(p, t).unwrap()) == 0);
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        // comparison with radians
+        for d in [1, 17, 44, 46, 89, 91, 123, 179, 181, 269, 271, 359] {
+            let deg = BigFloatNumber::from_word(d, p).unwrap();
+            let rad = deg
+                .to_radians(p + 128, RoundingMode::None, &mut cc)
+                .unwrap();
+
+            let s1 = deg.sin_deg(p, rm, &mut cc).unwrap();
+            let s2 = rad.sin(p, rm, &mut cc).unwrap();
+            assert!(s1.sub(&s2, p, rm).unwrap().abs_cmp(&eps) <= 0);
+
+            let c1 = deg.cos_deg(p, rm, &mut cc).unwrap();
+            let c2 = rad.cos(p, rm, &mut cc).unwrap();
+            assert!(c1.sub(&c2, p, rm).unwrap().abs_cmp(&eps) <= 0);
+
+            let t1 = deg.tan_deg(p, rm, &mut cc).unwrap();
+            let t2 = rad.tan(p, rm, &mut cc).unwrap();
+            let teps = eps.mul(&t2, p, rm).unwrap().abs().unwrap();
+            assert!(t1.sub(&t2, p, rm).unwrap().abs_cmp(&teps) <= 0);
+
+            // large angle is reduced exactly
+            let mut big = BigFloatNumber::from_word(360, p).unwrap();
+            big.set_exponent(1000);
+            let big = big.add_full_prec(&deg).unwrap();
+            assert!(big.sin_deg(p, rm, &mut cc).unwrap().cmp(&s1) == 0);
+            assert!(big.cos_deg(p, rm, &mut cc).unwrap().cmp(&c1) == 0);
+            assert!(big.tan_deg(p, rm, &mut cc).unwrap().cmp(&t1) == 0);
+
+            // negative angles
+            let neg = deg.neg().unwrap();
+            assert!(neg.sin_deg(p, rm, &mut cc).unwrap().cmp(&s1.neg().unwrap()) == 0);
+            assert!(neg.cos_deg(p, rm, &mut cc).unwrap().cmp(&c1) == 0);
+            assert!(neg.tan_deg(p, rm, &mut cc).unwrap().cmp(&t1.neg().unwrap()) == 0);
+        }
+
+        // tiny angles
+        let one = BigFloatNumber::from_word(1, p).unwrap();
+        let mut tiny = one.clone().unwrap();
+        tiny.set_exponent(-100000);
+        for x in [tiny, BigFloatNumber::min_positive(p).unwrap()] {
+            for x in [x.clone().unwrap(), x.neg().unwrap()] {
+                let c = x.cos_deg(p, rm, &mut cc).unwrap();
+                assert!(c.cmp(&one) == 0);
+                assert!(c.inexact());
+                let c = x.cos_deg(p, RoundingMode::Down, &mut cc).unwrap();
+                assert!(c.cmp(&one) < 0);
+
+                let r = x.to_radians(p, rm, &mut cc).unwrap();
+                let s = x.sin_deg(p, rm, &mut cc).unwrap();
+                assert!(s.cmp(&r) == 0);
+                let t = x.tan_deg(p, rm, &mut cc).unwrap();
+                assert!(t.cmp(&r) == 0);
+            }
+        }
+
+        let mut x = BigFloatNumber::from_word(180, p).unwrap();
+        let mut t = one.clone().unwrap();
+        t.set_exponent(-200);
+        x = x.add_full_prec(&t).unwrap();
+        let c = x.cos_deg(p, rm, &mut cc).unwrap();
+        assert!(c.cmp(&one.neg().unwrap()) == 0);
+        let c = x.cos_deg(p, RoundingMode::Up, &mut cc).unwrap();
+        assert!(c.cmp(&one.neg().unwrap()) > 0);
+
+        // directed rounding
+        let deg = BigFloatNumber::from_word(200, p).unwrap();
+        let up = deg.sin_deg(p, RoundingMode::Up, &mut cc).unwrap();
+        let down = deg.sin_deg(p, RoundingMode::Down, &mut cc).unwrap();
+        assert!(up.cmp(&down) > 0);
+        assert!(up.is_negative());
+    }
+}
@@ -0,0 +1,378 @@
+//! Error function and complementary error function.
+
+use crate::common::consts::ONE;
+use crate::common::util::log2_ceil;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::defs::Word;
+use crate::num::BigFloatNumber;
+use crate::Consts;
+use crate::Sign;
+use crate::EXPONENT_MIN;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Computes the error function of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn erf(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return Self::new2(p, self.sign(), self.inexact());
+        }
+
+        // e^(-x^2) is too small to be represented
+        let underflow = self.exponent() > 32;
+
+        // x^2 is not needed if it underflows, because then x^2/3 is always negligible
+        let y2 = if underflow || self.exponent() < EXPONENT_MIN / 2 {
+            Self::new(WORD_BIT_SIZE)?
+        } else {
+            self.mul_full_prec(self)?
+        };
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + 8;
+
+            // |erf(x)| = 1 - erfc(|x|), where erfc(|x|) < e^(-x^2) is too small
+            if underflow || y2.to_f64() * core::f64::consts::LOG2_E > (p_x + 16) as f64 {
+                let mut ret = Self::from_word(1, p)?.add_correction(true)?;
+                ret.set_sign(self.sign());
+                ret.set_precision(p, rm)?;
+                return Ok(ret);
+            }
+
+            let mut ret = if (self.exponent() as isize) < -(p_x as isize) {
+                // erf(x) = 2*x/sqrt(pi) * (1 - x^2/3 + ...), where x^2/3 is too small
+                let sqrt_pi = cc
+                    .pi_num(p_x, RoundingMode::None)?
+                    .sqrt(p_x, RoundingMode::None)?;
+                let mut ret = self.div(&sqrt_pi, p_x, RoundingMode::None)?;
+                ret.set_exponent(ret.exponent() + 1);
+                ret
+            } else {
+                let mut ret = Self::erf_pos(&y2, p_x, cc)?;
+                ret.set_sign(self.sign());
+                ret
+            };
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    /// Computes the complementary error function 1 - erf(x) of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// For large positive arguments the asymptotic expansion is used,
+    /// so the result keeps its relative precision until it underflows the exponent range.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn erfc(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            let mut ret = Self::from_word(1, p)?;
+            ret.set_inexact(self.inexact());
+            return Ok(ret);
+        }
+
+        // 1 - 2*x/sqrt(pi), where 2*x/sqrt(pi) is too small
+        if (self.exponent() as isize) < -(p as isize) - 2 {
+            let mut ret = Self::from_word(1, p)?.add_correction(self.is_positive())?;
+            ret.set_precision(p, rm)?;
+            return Ok(ret);
+        }
+
+        // e^(-x^2) is too small to be represented
+        if self.exponent() > 32 {
+            return if self.is_positive() {
+                Self::new2(p, Sign::Pos, true)
+            } else {
+                let mut ret = Self::from_word(2, p)?.add_correction(true)?;
+                ret.set_precision(p, rm)?;
+                Ok(ret)
+            };
+        }
+
+        let y2 = self.mul_full_prec(self)?;
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + 8;
+
+            let mut ret = if self.is_positive() {
+                Self::erfc_pos(&y2, p_x, cc)?
+            } else {
+                // erfc(x) = 1 + erf(|x|), where erf(|x|) < 1 - 2^(-p_x) can't be distinguished from 1
+                if y2.to_f64() * core::f64::consts::LOG2_E > (p_x + 16) as f64 {
+                    let mut ret = Self::from_word(2, p)?.add_correction(true)?;
+                    ret.set_precision(p, rm)?;
+                    return Ok(ret);
+                }
+
+                let e = Self::erf_pos(&y2, p_x, cc)?;
+                ONE.add(&e, p_x, RoundingMode::None)?
+            };
+
+            if ret.is_zero() {
+                return Self::new2(p, Sign::Pos, true);
+            }
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    /// Computes erf(y) for positive y = sqrt(`y2`) with precision `p` and the relative error of a few units in the last place.
+    pub(crate) fn erf_pos(y2: &Self, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        if let Some(c) = Self::erfc_asymptotic(y2, p, cc)? {
+            // erfc(y) < 1/2
+            ONE.sub(&c, p, RoundingMode::None)
+        } else {
+            Self::erf_series(y2, p, cc)
+        }
+    }
+
+    /// Computes erfc(y) for positive y = sqrt(`y2`) with precision `p` and the relative error of a few units in the last place.
+    pub(crate) fn erfc_pos(y2: &Self, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        if let Some(c) = Self::erfc_asymptotic(y2, p, cc)? {
+            Ok(c)
+        } else {
+            // erfc(y) is about e^(-y^2) / y, and 1 - erf(y) loses the corresponding number of bits
+            let lost = (y2.to_f64() * core::f64::consts::LOG2_E) as usize
+                + (y2.exponent() as isize).max(0) as usize
+                + 8;
+
+            let p_x = p + lost;
+            let e = Self::erf_series(y2, p_x, cc)?;
+
+            ONE.sub(&e, p_x, RoundingMode::None)
+        }
+    }
+
+    // Computes erf(y) = 2/sqrt(pi) * e^(-y^2) * (y + 2*y^3/3 + 4*y^5/(3*5) + ...).
+    // All terms of the series are positive, so there is no cancellation.
+    fn erf_series(y2: &Self, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        // the number of terms is less than 8*y^2 + 2*p
+        let n_max = (y2.to_f64() * 8.0) as usize + 2 * p;
+        let p_s = p + log2_ceil(n_max) + 8;
+
+        let mut x2 = y2.clone()?;
+        x2.set_exponent(x2.exponent() + 1);
+
+        let mut term = y2.sqrt(p_s, RoundingMode::None)?;
+        let mut sum = term.clone()?;
+        let mut n: Word = 1;
+
+        loop {
+            term = term.mul(&x2, p_s, RoundingMode::None)?.div_word(
+                2 * n + 1,
+                p_s,
+                RoundingMode::None,
+            )?;
+
+            sum = sum.add(&term, p_s, RoundingMode::None)?;
+
+            if (term.exponent() as isize) < sum.exponent() as isize - p_s as isize {
+                break;
+            }
+
+            n += 1;
+        }
+
+        let mut f = y2.neg()?.exp(p_s, RoundingMode::None, cc)?;
+        f = f.mul(&sum, p_s, RoundingMode::None)?;
+
+        let sqrt_pi = cc
+            .pi_num(p_s, RoundingMode::None)?
+            .sqrt(p_s, RoundingMode::None)?;
+        let mut ret = f.div(&sqrt_pi, p_s, RoundingMode::None)?;
+        ret.set_exponent(ret.exponent() + 1);
+
+        Ok(ret)
+    }
+
+    // Computes erfc(y) = e^(-y^2) / (y*sqrt(pi)) * (1 - 1/(2*y^2) + 1*3/(2*y^2)^2 - ...),
+    // or returns None if the asymptotic series does not give enough precision for `y`.
+    fn erfc_asymptotic(y2: &Self, p: usize, cc: &mut Consts) -> Result<Option<Self>, Error> {
+        let p_s = p + 8;
+
+        // the smallest term of the series is about e^(-y^2)
+        if (y2.exponent() as isize) < 32
+            && y2.to_f64() * core::f64::consts::LOG2_E < (p_s + 8) as f64
+        {
+            return Ok(None);
+        }
+
+        let mut x2 = y2.clone()?;
+        x2.set_exponent(x2.exponent() + 1);
+
+        let mut term = Self::from_word(1, p_s)?;
+        let mut sum = term.clone()?;
+        let mut n: Word = 1;
+
+        loop {
+            term = term
+                .mul_word(2 * n - 1, p_s, RoundingMode::None)?
+                .div(&x2, p_s, RoundingMode::None)?
+                .neg()?;
+
+            sum = sum.add(&term, p_s, RoundingMode::None)?;
+
+            if (term.exponent() as isize) < -(p_s as isize) {
+                break;
+            }
+
+            n += 1;
+        }
+
+        let f = y2.neg()?.exp(p_s, RoundingMode::None, cc)?;
+        if f.is_zero() {
+            return Ok(Some(f));
+        }
+
+        let sqrt_pi = cc
+            .pi_num(p_s, RoundingMode::None)?
+            .sqrt(p_s, RoundingMode::None)?;
+        let d = y2
+            .sqrt(p_s, RoundingMode::None)?
+            .mul(&sqrt_pi, p_s, RoundingMode::None)?;
+
+        let ret = f
+            .mul(&sum, p_s, RoundingMode::None)?
+            .div(&d, p_s, RoundingMode::None)?;
+
+        Ok(Some(ret))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_erf() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+        let p = 192;
+
+        let mut eps = ONE.clone().unwrap();
+        eps.set_exponent(-185);
+
+        // relative difference of `a` and the decimal number `s` is less than 2^(-185)
+        let check = |a: &BigFloatNumber, s: &str| {
+            let b = BigFloatNumber::parse(s, crate::Radix::Dec, 256, RoundingMode::None).unwrap();
+            let d = a.sub(&b, 256, rm).unwrap().div(&b, 256, rm).unwrap();
+            assert!(d.abs_cmp(&eps) < 0, "{:?} {}", a, s);
+        };
+
+        let z = BigFloatNumber::new(p).unwrap();
+        assert!(z.erf(p, rm, &mut cc).unwrap().is_zero());
+        assert!(z.erfc(p, rm, &mut cc).unwrap().cmp(&ONE) == 0);
+
+        let one = BigFloatNumber::from_word(1, p).unwrap();
+        check(
+            &one.erf(p, rm, &mut cc).unwrap(),
+            "0.842700792949714869341220635082609259296066997966302908459938",
+        );
+        check(
+            &one.neg().unwrap().erf(p, rm, &mut cc).unwrap(),
+            "-0.842700792949714869341220635082609259296066997966302908459938",
+        );
+
+        let x = BigFloatNumber::from_f64(p, -0.5).unwrap();
+        check(
+            &x.erfc(p, rm, &mut cc).unwrap(),
+            "1.52049987781304653768274665389196452873645157575796370005881",
+        );
+
+        let x = BigFloatNumber::parse("0.001", crate::Radix::Dec, 512, rm).unwrap();
+        check(
+            &x.erf(p, rm, &mut cc).unwrap(),
+            "0.00112837879096923637994847765690481259924686321264664213879756",
+        );
+
+        // large arguments keep the relative precision
+        let x = BigFloatNumber::from_word(10, p).unwrap();
+        check(
+            &x.erfc(p, rm, &mut cc).unwrap(),
+            "2.08848758376254475700078629495778861156081811932116372701221e-45",
+        );
+        let x = BigFloatNumber::from_word(30, p).unwrap();
+        check(
+            &x.erfc(p, rm, &mut cc).unwrap(),
+            "2.5646562037561116000333972775014471465488897227786170541226e-393",
+        );
+
+        // erf(x) + erfc(x) = 1
+        for x in [1e-10, 0.3, 0.9, 1.5, 4.0, 7.25] {
+            for x in [x, -x] {
+                let x = BigFloatNumber::from_f64(p, x).unwrap();
+                let e = x.erf(p + 128, rm, &mut cc).unwrap();
+                let c = x.erfc(p + 128, rm, &mut cc).unwrap();
+                let d = e.add(&c, p + 128, rm).unwrap().sub(&ONE, p, rm).unwrap();
+                assert!(d.abs_cmp(&eps) < 0);
+            }
+        }
+
+        // results close to 1 and 2 are rounded in the correct direction
+        let x = BigFloatNumber::from_word(100, p).unwrap();
+        assert!(x.erf(p, RoundingMode::Down, &mut cc).unwrap().cmp(&ONE) < 0);
+        assert!(x.erf(p, RoundingMode::Up, &mut cc).unwrap().cmp(&ONE) == 0);
+        assert!(x.erf(p, rm, &mut cc).unwrap().cmp(&ONE) == 0);
+        let two = BigFloatNumber::from_word(2, p).unwrap();
+        let c = x
+            .neg()
+            .unwrap()
+            .erfc(p, RoundingMode::Down, &mut cc)
+            .unwrap();
+        assert!(c.cmp(&two) < 0);
+
+        let x = BigFloatNumber::from_f64(p, 1e-100).unwrap();
+        assert!(x.erfc(p, RoundingMode::Down, &mut cc).unwrap().cmp(&ONE) < 0);
+        assert!(x.erfc(p, RoundingMode::Up, &mut cc).unwrap().cmp(&ONE) == 0);
+
+        // tiny arguments
+        let x = BigFloatNumber::from_f64(p, 1e-300).unwrap();
+        let e = x.erf(p, rm, &mut cc).unwrap();
+        let sqrt_pi = cc.pi_num(p + 64, rm).unwrap().sqrt(p + 64, rm).unwrap();
+        let mut r = x.div(&sqrt_pi, p, rm).unwrap();
+        r.set_exponent(r.exponent() + 1);
+        assert!(e.cmp(&r) == 0);
+
+        // underflow
+        let x = BigFloatNumber::from_f64(p, 1e20).unwrap();
+        assert!(x.erfc(p, rm, &mut cc).unwrap().is_zero());
+        assert!(x.erf(p, rm, &mut cc).unwrap().cmp(&ONE) == 0);
+        // e^(-x^2) underflows for both 32-bit and 64-bit exponents
+        let x = BigFloatNumber::from_f64(p, 2147483648.0).unwrap();
+        assert!(x.erfc(p, rm, &mut cc).unwrap().is_zero());
+    }
+}
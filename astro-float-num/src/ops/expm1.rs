@@ -0,0 +1,452 @@
+//! Base 2 and base 10 exponent minus one, and logarithm of one plus argument.
+
+use crate::common::consts::ONE;
+use crate::common::consts::TEN;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::defs::EXPONENT_MAX;
+use crate::num::BigFloatNumber;
+use crate::Consts;
+use crate::Exponent;
+use crate::Sign;
+use crate::WORD_BIT_SIZE;
+
+// Base of the exponent and the logarithm.
+#[derive(Clone, Copy, PartialEq)]
+enum Base {
+    Two,
+    Ten,
+}
+
+impl Base {
+    fn ln(self, p: usize, cc: &mut Consts) -> Result<BigFloatNumber, Error> {
+        match self {
+            Base::Two => cc.ln_2_num(p, RoundingMode::None),
+            Base::Ten => cc.ln_10_num(p, RoundingMode::None),
+        }
+    }
+
+    fn log(
+        self,
+        x: &BigFloatNumber,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<BigFloatNumber, Error> {
+        match self {
+            Base::Two => x.log2(p, rm, cc),
+            Base::Ten => x.log10(p, rm, cc),
+        }
+    }
+}
+
+impl BigFloatNumber {
+    /// Computes 2^`self` - 1 with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is accurate even if `self` is close to zero.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn exp2m1(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        self.expm1_base(Base::Two, p, rm, cc)
+    }
+
+    /// Computes 10^`self` - 1 with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is accurate even if `self` is close to zero.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn exp10m1(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        self.expm1_base(Base::Ten, p, rm, cc)
+    }
+
+    /// Computes log2(1 + `self`) with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is accurate even if `self` is close to zero.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `self` is less than or equal to -1, or the precision is incorrect.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn log2p1(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        self.logp1_base(Base::Two, p, rm, cc)
+    }
+
+    /// Computes log10(1 + `self`) with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is accurate even if `self` is close to zero.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `self` is less than or equal to -1, or the precision is incorrect.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn log10p1(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        self.logp1_base(Base::Ten, p, rm, cc)
+    }
+
+    fn expm1_base(
+        &self,
+        b: Base,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return Self::new2(p, self.sign(), self.inexact());
+        }
+
+        // b^n - 1 for integer n can be exact
+        if self.is_int() && (self.exponent() as isize) < WORD_BIT_SIZE as isize {
+            if let Some(mut ret) = self.expm1_int(b, p, rm)? {
+                ret.set_inexact(ret.inexact() || self.inexact());
+                return Ok(ret);
+            }
+        }
+
+        let e = self.exponent() as isize;
+
+        // |x| >= 2^(e - 1), and b^x >= 2^x
+        if self.is_positive() && e > Exponent::BITS as isize + 1 {
+            return Err(Error::ExponentOverflow(Sign::Pos));
+        }
+
+        if self.is_negative() && e > (usize::BITS - p.leading_zeros()) as isize + 2 {
+            // b^x < 2^-(p + 2) is below the rounding error of -1
+            return Self::neg_one_corrected(p, rm);
+        }
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p.max(self.mantissa_max_bit_len()) + p_inc;
+
+        loop {
+            let p_x = p_wrk + 4;
+
+            let mut ret = if e < -(p_x as isize) {
+                // b^x - 1 = x*ln(b) + (x*ln(b))^2/2 + ..., where the second term is below the error of x*ln(b)
+                let lnb = b.ln(p_x, cc)?;
+                self.mul(&lnb, p_x, RoundingMode::None)?
+            } else {
+                // cancellation compensation for small x, and the absolute error of x*ln(b) for large x
+                let p_y = p_x + e.unsigned_abs() + 4;
+
+                let lnb = b.ln(p_y, cc)?;
+                let y = self.mul(&lnb, p_y, RoundingMode::None)?;
+                let t = y.exp(p_y, RoundingMode::None, cc)?;
+
+                if self.is_negative() && (t.is_zero() || (t.exponent() as isize) < -(p_x as isize))
+                {
+                    // -1 + b^x, where b^x is too small
+                    return Self::neg_one_corrected(p, rm);
+                } else {
+                    t.sub(&ONE, p_y, RoundingMode::None)?
+                }
+            };
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                ret.set_inexact(true);
+                return Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Returns -1 plus a small positive correction rounded to precision `p`.
+    fn neg_one_corrected(p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let mut ret = ONE.neg()?;
+        ret.set_precision(p + WORD_BIT_SIZE, RoundingMode::None)?;
+        ret = ret.add_correction(true)?;
+        ret.set_precision(p, rm)?;
+        ret.set_inexact(true);
+        Ok(ret)
+    }
+
+    // Computes b^n - 1 for integer `self` = n if the result can be exact or close to exact value.
+    // Returns None if the result must be computed in the general way.
+    fn expm1_int(&self, b: Base, p: usize, rm: RoundingMode) -> Result<Option<Self>, Error> {
+        let n = self.int_as_usize()?;
+        let lim = p + 2 * WORD_BIT_SIZE;
+
+        match b {
+            Base::Two => {
+                if self.is_positive() && n > EXPONENT_MAX as usize - 1 {
+                    return Err(Error::ExponentOverflow(Sign::Pos));
+                }
+
+                if n > lim {
+                    // 2^n - 1 is 2^n or -1 plus a correction
+                    if self.is_negative() {
+                        return Self::neg_one_corrected(p, rm).map(Some);
+                    }
+
+                    let mut ret = Self::from_word(1, p + WORD_BIT_SIZE)?;
+                    ret.set_exponent(n as Exponent + 1);
+                    ret = ret.add_correction(true)?;
+                    ret.set_precision(p, rm)?;
+                    ret.set_inexact(true);
+
+                    Ok(Some(ret))
+                } else {
+                    let mut t = Self::from_word(1, WORD_BIT_SIZE)?;
+                    let e = if self.is_positive() { n as isize } else { -(n as isize) };
+                    t.set_exponent((e + 1) as Exponent);
+
+                    let mut ret = t.sub_full_prec(&ONE)?;
+                    ret.set_precision(p, rm)?;
+
+                    Ok(Some(ret))
+                }
+            }
+            Base::Ten => {
+                // 10^n - 1 requires less than 4*n bits; otherwise the result is not exactly representable,
+                // and it is not a midpoint, because it is odd
+                if self.is_negative() || n > lim / 4 {
+                    return Ok(None);
+                }
+
+                let t = TEN.powi(n, 4 * n + WORD_BIT_SIZE, RoundingMode::None)?;
+
+                let mut ret = t.sub_full_prec(&ONE)?;
+                ret.set_precision(p, rm)?;
+
+                Ok(Some(ret))
+            }
+        }
+    }
+
+    fn logp1_base(
+        &self,
+        b: Base,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return Self::new2(p, self.sign(), self.inexact());
+        }
+
+        let e = self.exponent() as isize;
+
+        if e >= -(p as isize) - 2 * WORD_BIT_SIZE as isize
+            && e <= self.mantissa_max_bit_len() as isize
+        {
+            // 1 + x is computed exactly, and the logarithm handles exact results and cancellation
+            let d = ONE.add_full_prec(self)?;
+            return b.log(&d, p, rm, cc);
+        }
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p.max(self.mantissa_max_bit_len()) + p_inc;
+
+        loop {
+            let p_x = p_wrk + 4;
+
+            let mut ret = if e < 0 {
+                // log_b(1 + x) = (x - x^2/2 + ...) / ln(b), where x^2/2 is below the error of x / ln(b)
+                let lnb = b.ln(p_x, cc)?;
+                self.div(&lnb, p_x, RoundingMode::None)?
+            } else if e > p_x as isize + 4 {
+                // log_b(1 + x) = log_b(x) + log_b(1 + 1/x), where the second term is positive and below the error of log_b(x)
+                let ret = b.log(self, p_x, RoundingMode::None, cc)?;
+
+                if !ret.inexact() {
+                    // the second term only affects the rounding of the exact log_b(x)
+                    let mut ret = ret.add_correction(false)?;
+                    ret.set_precision(p, rm)?;
+                    ret.set_inexact(true);
+                    return Ok(ret);
+                }
+
+                ret
+            } else {
+                // x is a large integer, and the result is well conditioned
+                let d = ONE.add(self, p_x, RoundingMode::None)?;
+                b.log(&d, p_x, RoundingMode::None, cc)?
+            };
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                ret.set_inexact(true);
+                return Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::util::rand_p;
+    use crate::defs::EXPONENT_MIN;
+    use crate::Word;
+
+    #[test]
+    fn test_expm1_logp1() {
+        let mut cc = Consts::new().unwrap();
+
+        // exact values
+        for rm in [RoundingMode::Up, RoundingMode::Down, RoundingMode::ToEven] {
+            for (x, r) in [(3, 7.0), (-2, -0.75), (0, 0.0)] {
+                let x = BigFloatNumber::from_i64(x, 128).unwrap();
+                let r = BigFloatNumber::from_f64(128, r).unwrap();
+                let d = x.exp2m1(128, rm, &mut cc).unwrap();
+                assert_eq!(d.cmp(&r), 0);
+                assert!(!d.inexact());
+                let d = r.log2p1(128, rm, &mut cc).unwrap();
+                assert_eq!(d.cmp(&x), 0);
+                assert!(!d.inexact());
+            }
+
+            let x = BigFloatNumber::from_word(3, 128).unwrap();
+            let r = BigFloatNumber::from_word(999, 128).unwrap();
+            let d = x.exp10m1(128, rm, &mut cc).unwrap();
+            assert_eq!(d.cmp(&r), 0);
+            assert!(!d.inexact());
+            let d = r.log10p1(128, rm, &mut cc).unwrap();
+            assert_eq!(d.cmp(&x), 0);
+        }
+
+        // large integer arguments
+        let x = BigFloatNumber::from_word(1000, 128).unwrap();
+        let d = x.exp2m1(128, RoundingMode::Down, &mut cc).unwrap();
+        assert_eq!(d.exponent(), 1000);
+        assert!(d.inexact());
+        let d = x
+            .neg()
+            .unwrap()
+            .exp2m1(128, RoundingMode::Up, &mut cc)
+            .unwrap();
+        assert_eq!(d.cmp(&ONE.neg().unwrap()), 1);
+        let d = x
+            .neg()
+            .unwrap()
+            .exp10m1(128, RoundingMode::Down, &mut cc)
+            .unwrap();
+        assert_eq!(d.cmp(&ONE.neg().unwrap()), 0);
+        let x = BigFloatNumber::from_word(EXPONENT_MAX as Word, 128).unwrap();
+        assert_eq!(
+            x.exp2m1(128, RoundingMode::ToEven, &mut cc).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+
+        // huge arguments
+        for e in [1000000, EXPONENT_MAX] {
+            let mut x = BigFloatNumber::from_word(3, 128).unwrap();
+            x.set_exponent(e);
+            assert_eq!(
+                x.exp2m1(128, RoundingMode::ToEven, &mut cc).unwrap_err(),
+                Error::ExponentOverflow(Sign::Pos)
+            );
+            assert_eq!(
+                x.exp10m1(128, RoundingMode::ToEven, &mut cc).unwrap_err(),
+                Error::ExponentOverflow(Sign::Pos)
+            );
+
+            let d = x.log2p1(128, RoundingMode::ToEven, &mut cc).unwrap();
+            assert!(d.is_positive() && d.inexact());
+            let d = x.log10p1(128, RoundingMode::ToEven, &mut cc).unwrap();
+            assert!(d.is_positive() && d.inexact());
+
+            let x = x.neg().unwrap();
+            let d = x.exp2m1(128, RoundingMode::Up, &mut cc).unwrap();
+            assert_eq!(d.cmp(&ONE.neg().unwrap()), 1);
+            assert!(d.inexact());
+            let d = x.exp10m1(128, RoundingMode::Down, &mut cc).unwrap();
+            assert_eq!(d.cmp(&ONE.neg().unwrap()), 0);
+            assert!(d.inexact());
+        }
+
+        // log2(1 + 2^1000000) is slightly greater than 1000000
+        let mut x = BigFloatNumber::from_word(1, 64).unwrap();
+        x.set_exponent(1000001);
+        let r = BigFloatNumber::from_word(1000000, 128).unwrap();
+        let d = x.log2p1(128, RoundingMode::Up, &mut cc).unwrap();
+        assert_eq!(d.cmp(&r), 1);
+        let d = x.log2p1(128, RoundingMode::Down, &mut cc).unwrap();
+        assert_eq!(d.cmp(&r), 0);
+
+        // small arguments: 2^x - 1 ~ x*ln(2)
+        let p = 256;
+        for e in [-1, -10, -100, -300, -1000, EXPONENT_MIN + 1] {
+            for s in [Sign::Pos, Sign::Neg] {
+                let mut x = BigFloatNumber::from_word(3, p).unwrap();
+                x.set_exponent(e);
+                x.set_sign(s);
+
+                let ln2 = cc.ln_2_num(p, RoundingMode::None).unwrap();
+                let y = x.exp2m1(p, RoundingMode::ToEven, &mut cc).unwrap();
+                let y0 = x.mul(&ln2, p, RoundingMode::None).unwrap();
+                let rel = y.sub(&y0, p, RoundingMode::None).unwrap();
+                // the relative difference is about x*ln(2)/2
+                let bound = y.exponent() as isize + (e as isize).max(-(p as isize)) + 2;
+                assert!(rel.is_zero() || rel.is_subnormal() || (rel.exponent() as isize) <= bound);
+                assert!(y.is_negative() == x.is_negative());
+
+                let z = y.log2p1(p, RoundingMode::ToEven, &mut cc).unwrap();
+                let d = z.sub(&x, p, RoundingMode::None).unwrap();
+                assert!(d.is_zero() || d.exponent() < x.exponent() - p as Exponent + 3);
+
+                let y = x.exp10m1(p, RoundingMode::ToEven, &mut cc).unwrap();
+                let z = y.log10p1(p, RoundingMode::ToEven, &mut cc).unwrap();
+                let d = z.sub(&x, p, RoundingMode::None).unwrap();
+                assert!(d.is_zero() || d.exponent() < x.exponent() - p as Exponent + 3);
+            }
+        }
+
+        // inverse functions for random arguments
+        for _ in 0..100 {
+            let p = rand_p();
+            let x = BigFloatNumber::random_normal(p, -20, 5).unwrap();
+
+            let y = x.exp10m1(p + 64, RoundingMode::ToEven, &mut cc).unwrap();
+            let z = y.log10p1(p, RoundingMode::ToEven, &mut cc).unwrap();
+            let d = z.sub(&x, p, RoundingMode::None).unwrap();
+            assert!(d.is_zero() || d.exponent() < x.exponent() - p as Exponent + 3);
+        }
+
+        // 1 + x is less than or equal to zero
+        assert_eq!(
+            ONE.neg()
+                .unwrap()
+                .log2p1(128, RoundingMode::ToEven, &mut cc)
+                .unwrap_err(),
+            Error::InvalidArgument
+        );
+        let x = BigFloatNumber::from_i64(-1 << 62, 64).unwrap();
+        assert_eq!(
+            x.log10p1(128, RoundingMode::ToEven, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+
+        // large argument
+        let mut x = BigFloatNumber::from_word(1, 64).unwrap();
+        x.set_exponent(1001);
+        let d = x.log2p1(128, RoundingMode::Up, &mut cc).unwrap();
+        let r = BigFloatNumber::from_word(1000, 128).unwrap();
+        assert_eq!(d.cmp(&r), 1);
+        let d = x.log2p1(128, RoundingMode::Down, &mut cc).unwrap();
+        assert_eq!(d.cmp(&r), 0);
+    }
+}
@@ -0,0 +1,344 @@
+//! Gamma function.
+
+use crate::common::consts::ONE;
+use crate::common::util::log2_ceil;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::defs::Word;
+use crate::num::BigFloatNumber;
+use crate::Consts;
+use crate::Sign;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Computes the gamma function of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Positive arguments are shifted upwards and the Stirling series is used for the logarithm of the gamma function,
+    /// and negative arguments are handled using the reflection formula.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large, or `self` is zero (the sign of the error is the sign of the zero).
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: `self` is a negative integer, or the precision is incorrect.
+    pub fn gamma(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return Err(Error::ExponentOverflow(self.sign()));
+        }
+
+        if self.is_int() {
+            if self.is_negative() {
+                return Err(Error::InvalidArgument);
+            }
+
+            if let Some(ret) = self.gamma_int(p, rm)? {
+                return Ok(ret);
+            }
+        }
+
+        // |ln(gamma(x))| > 2^64 is out of the exponent range
+        if self.exponent() > 64 {
+            if self.is_positive() {
+                return Err(Error::ExponentOverflow(Sign::Pos));
+            } else {
+                let k = self.int()?;
+                let s = if !k.is_zero() && k.is_odd_int() { Sign::Pos } else { Sign::Neg };
+                return Self::new2(p, s, true);
+            }
+        }
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p.max(self.mantissa_max_bit_len()) + p_inc;
+
+        loop {
+            let p_x = p_wrk + 8;
+
+            let mut ret = if self.is_negative() {
+                match self.gamma_neg(p_x, cc) {
+                    Err(Error::ExponentOverflow(s)) => return Self::new2(p, s, true),
+                    r => r?,
+                }
+            } else {
+                self.gamma_pos(p_x, cc)?
+            };
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Computes gamma(x) = (x - 1)! for a positive integer x, or returns None if the result is not exactly representable with precision `p`.
+    fn gamma_int(&self, p: usize, rm: RoundingMode) -> Result<Option<Self>, Error> {
+        // (x - 1)! has more than p significant bits for x > p
+        if self.exponent() as usize > WORD_BIT_SIZE || self.int_as_usize()? > p {
+            return Ok(None);
+        }
+
+        let n = self.int_as_usize()?;
+        let p_x = p + WORD_BIT_SIZE;
+
+        let mut ret = Self::from_word(1, p_x)?;
+        for k in 2..n {
+            ret = ret.mul_word(k as Word, p_x, RoundingMode::None)?;
+        }
+
+        if ret.inexact() {
+            return Ok(None);
+        }
+
+        ret.set_precision(p, rm)?;
+
+        Ok(Some(ret))
+    }
+
+    // Computes gamma(x) for negative x with precision `p` and the relative error of a few units in the last place
+    // using the reflection formula gamma(x) = pi / (sin(pi*x) * gamma(1 - x)).
+    fn gamma_neg(&self, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        let p_x = p + 8;
+
+        // sin(pi*x) = (-1)^k * sin(pi*f), where x = k + f, and the reduction is exact
+        let mut f = self.fract()?;
+        let k = self.int()?;
+        let mut inv = !k.is_zero() && k.is_odd_int();
+
+        let mut half = Self::from_word(1, WORD_BIT_SIZE)?;
+        half.set_exponent(0);
+
+        if f.abs_cmp(&half) > 0 {
+            f = if f.is_negative() { f.add_full_prec(&ONE)? } else { f.sub_full_prec(&ONE)? };
+            inv = !inv;
+        }
+
+        let pi = cc.pi_num(p_x, RoundingMode::None)?;
+        let mut s = pi
+            .mul(&f, p_x, RoundingMode::None)?
+            .sin(p_x, RoundingMode::None, cc)?;
+
+        if inv {
+            s.inv_sign();
+        }
+
+        let y = ONE.sub_full_prec(self)?;
+        let g = y.gamma_pos(p_x, cc)?;
+
+        pi.div(&s, p_x, RoundingMode::None)?
+            .div(&g, p_x, RoundingMode::None)
+    }
+
+    // Computes gamma(x) for positive x with precision `p` and the relative error of a few units in the last place:
+    // gamma(x) = gamma(x + n) / (x * (x + 1) * ... * (x + n - 1)), where x + n is large enough for the Stirling series.
+    fn gamma_pos(&self, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        // the terms of the Stirling series reach 2^(-p) before they start to grow for y >= p/4
+        let y_min = p / 4 + 16;
+
+        let n = if (self.exponent() as isize) < WORD_BIT_SIZE as isize {
+            let i = self.int_as_usize()?;
+            y_min.saturating_sub(i)
+        } else {
+            0
+        };
+
+        // the sum and the product accumulate up to n rounding errors, and the error of the logarithm is
+        // multiplied by its magnitude, which is less than y * log2(y)
+        let e_y = (self.exponent() as isize).max(log2_ceil(y_min + 1) as isize) as usize;
+        let p_x = p + 2 * log2_ceil(n + 1) + e_y + log2_ceil(e_y) + 8;
+
+        let mut y = self.clone()?;
+        let mut prod = Self::from_word(1, p_x)?;
+
+        for _ in 0..n {
+            prod = prod.mul(&y, p_x, RoundingMode::None)?;
+            y = y.add(&ONE, p_x, RoundingMode::None)?;
+        }
+
+        let lg = y.ln_gamma_stirling(p_x, cc)?;
+        let g = lg.exp(p_x, RoundingMode::None, cc)?;
+
+        if n > 0 {
+            g.div(&prod, p_x, RoundingMode::None)
+        } else {
+            Ok(g)
+        }
+    }
+
+    // Computes ln(gamma(y)) for y >= 16 with the absolute error of a few units in the last place of precision `p`
+    // using the Stirling series ln(gamma(y)) = (y - 1/2) * ln(y) - y + ln(2*pi)/2 + sum(B_2j / (2j * (2j - 1) * y^(2j - 1))), j = 1, 2, ...
+    fn ln_gamma_stirling(&self, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        let y = self;
+
+        let mut half = Self::from_word(1, WORD_BIT_SIZE)?;
+        half.set_exponent(0);
+
+        let mut ret = y
+            .sub(&half, p, RoundingMode::None)?
+            .mul(&y.ln(p, RoundingMode::None, cc)?, p, RoundingMode::None)?
+            .sub(y, p, RoundingMode::None)?;
+
+        let mut pi2 = cc.pi_num(p, RoundingMode::None)?;
+        pi2.set_exponent(pi2.exponent() + 1);
+        let mut l = pi2.ln(p, RoundingMode::None, cc)?;
+        l.set_exponent(l.exponent() - 1);
+
+        ret = ret.add(&l, p, RoundingMode::None)?;
+
+        // w = 1 / y^(2j - 1)
+        let mut w = y.reciprocal(p, RoundingMode::None)?;
+        let y2 = w.mul(&w, p, RoundingMode::None)?;
+
+        let mut j: usize = 1;
+
+        loop {
+            let b = cc.bernoulli_num(2 * j, p, RoundingMode::None)?;
+            let d = Self::from_usize(2 * j * (2 * j - 1))?;
+            let t = b
+                .mul(&w, p, RoundingMode::None)?
+                .div(&d, p, RoundingMode::None)?;
+
+            ret = ret.add(&t, p, RoundingMode::None)?;
+
+            if (t.exponent() as isize) < -(p as isize) {
+                break;
+            }
+
+            w = w.mul(&y2, p, RoundingMode::None)?;
+            j += 1;
+        }
+
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_gamma() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+
+        // factorials are exact in all rounding modes
+        let mut f = 1u64;
+        for n in 1..21u64 {
+            let x = BigFloatNumber::from_u64(n, 64).unwrap();
+            for rm in
+                [RoundingMode::Up, RoundingMode::Down, RoundingMode::ToOdd, RoundingMode::ToEven]
+            {
+                let g = x.gamma(64, rm, &mut cc).unwrap();
+                assert!(g.cmp(&BigFloatNumber::from_u64(f, 64).unwrap()) == 0);
+                assert!(!g.inexact());
+            }
+            f *= n;
+        }
+
+        // 100! is rounded
+        let x = BigFloatNumber::from_word(101, 64).unwrap();
+        let g1 = x.gamma(128, RoundingMode::Down, &mut cc).unwrap();
+        let g2 = x.gamma(128, RoundingMode::Up, &mut cc).unwrap();
+        assert!(g1.cmp(&g2) < 0);
+
+        // gamma(1/2) = sqrt(pi)
+        let p = 320;
+        let mut half = BigFloatNumber::from_word(1, p).unwrap();
+        half.set_exponent(0);
+        let g = half.gamma(p, rm, &mut cc).unwrap();
+        let s = cc.pi_num(p + 64, rm).unwrap().sqrt(p, rm).unwrap();
+        assert!(g.cmp(&s) == 0);
+
+        // gamma(-1/2) = -2 sqrt(pi), gamma(-3/2) = 4 sqrt(pi) / 3
+        let g = half.neg().unwrap().gamma(p, rm, &mut cc).unwrap();
+        let mut s2 = s.clone().unwrap();
+        s2.set_exponent(s2.exponent() + 1);
+        assert!(g.cmp(&s2.neg().unwrap()) == 0);
+
+        let x = BigFloatNumber::from_f64(p, -1.5).unwrap();
+        let g = x.gamma(p, rm, &mut cc).unwrap();
+        let mut s4 = s.clone().unwrap();
+        s4.set_exponent(s4.exponent() + 2);
+        let r = s4.div_word(3, p, rm).unwrap();
+        assert!(g.cmp(&r) == 0);
+
+        // gamma(x + 1) = x * gamma(x)
+        for x in [1e-30, 0.001, 0.3, 1.7, 5.5, 33.25, 1000.125, -0.7, -7.3, -60.01] {
+            for p in [64, 256, 1024] {
+                let x = BigFloatNumber::from_f64(p, x).unwrap();
+                let g1 = x
+                    .add(&ONE, p, RoundingMode::None)
+                    .unwrap()
+                    .gamma(p + 64, rm, &mut cc)
+                    .unwrap();
+                let g2 = x
+                    .gamma(p + 64, rm, &mut cc)
+                    .unwrap()
+                    .mul(&x, p + 64, rm)
+                    .unwrap();
+                let d = g1.sub(&g2, p, rm).unwrap();
+                assert!(
+                    d.is_zero() || (d.exponent() as isize) < g1.exponent() as isize - p as isize,
+                    "{:?} {}",
+                    x,
+                    p
+                );
+            }
+        }
+
+        // gamma(1/3) = 2.6789385347077476336556929409746776441286893779573...
+        let x = BigFloatNumber::from_word(1, 256)
+            .unwrap()
+            .div_word(3, 256, rm)
+            .unwrap();
+        let g = x.gamma(128, rm, &mut cc).unwrap();
+        let r = BigFloatNumber::parse(
+            "2.6789385347077476336556929409746776441286893779573",
+            crate::Radix::Dec,
+            128,
+            rm,
+        )
+        .unwrap();
+        let mut eps = ONE.clone().unwrap();
+        eps.set_exponent(-125);
+        assert!(g.sub(&r, 128, rm).unwrap().abs_cmp(&eps) < 0);
+
+        // poles, overflow, and underflow
+        let z = BigFloatNumber::new(p).unwrap();
+        assert_eq!(
+            z.gamma(p, rm, &mut cc).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+        assert_eq!(
+            z.neg().unwrap().gamma(p, rm, &mut cc).unwrap_err(),
+            Error::ExponentOverflow(Sign::Neg)
+        );
+        assert_eq!(
+            BigFloatNumber::from_i8(-3, p)
+                .unwrap()
+                .gamma(p, rm, &mut cc)
+                .unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            BigFloatNumber::from_f64(p, 1e30)
+                .unwrap()
+                .gamma(p, rm, &mut cc)
+                .unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+
+        let x = BigFloatNumber::from_f64(p, -1e30)
+            .unwrap()
+            .sub(&half, p, rm)
+            .unwrap();
+        let g = x.gamma(p, rm, &mut cc).unwrap();
+        assert!(g.is_zero());
+    }
+}
@@ -0,0 +1,301 @@
+//! Harmonic numbers and generalized harmonic numbers.
+
+use crate::common::consts::ONE;
+use crate::common::util::log2_ceil;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::Consts;
+use crate::Exponent;
+use crate::EXPONENT_MIN;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Computes the harmonic number H_n = 1 + 1/2 + ... + 1/n with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// For large `n` the asymptotic expansion of the digamma function is used instead of summing all terms.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn harmonic(n: usize, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        Self::generalized_harmonic(n, 1, p, rm, cc)
+    }
+
+    /// Computes the generalized harmonic number H(n, s) = 1 + 1/2^s + ... + 1/n^s with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// For large `n` the sum is computed with the Euler-Maclaurin formula instead of summing all terms.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn generalized_harmonic(
+        n: usize,
+        s: usize,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if n == 0 {
+            return Self::new(p);
+        }
+
+        if s == 0 {
+            let mut ret = Self::from_usize(n)?;
+            ret.set_precision(p, rm)?;
+            return Ok(ret);
+        }
+
+        if n <= 2 {
+            // 1, or 1 + 2^(-s)
+            let mut ret = Self::from_word(1, p)?;
+            if n == 2 {
+                let mut t = Self::from_word(1, WORD_BIT_SIZE)?;
+                t.set_exponent(
+                    (1 - s.min(isize::MAX as usize) as isize).max(EXPONENT_MIN as isize)
+                        as Exponent,
+                );
+                ret = ret.add(&t, p, rm)?;
+            }
+            return Ok(ret);
+        }
+
+        if s > p + 2 * WORD_BIT_SIZE {
+            // 1 + x, where 0 < x < 3 * 2^(-s) is too small
+            let mut ret = ONE.add_correction(false)?;
+            ret.set_precision(p, rm)?;
+            return Ok(ret);
+        }
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            // the terms of the asymptotic series decrease at least 16 times each for k >= m
+            let m = 3 * s + 2 * p_wrk;
+
+            let mut ret = if n <= m {
+                Self::harmonic_sum(n, s, p_wrk)?
+            } else {
+                Self::harmonic_em(n, m, s, p_wrk, cc)?
+            };
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Returns 1/k^s.
+    fn harmonic_term(k: usize, s: usize, p: usize) -> Result<Self, Error> {
+        let k = Self::from_usize(k)?;
+
+        if s == 1 {
+            ONE.div(&k, p, RoundingMode::None)
+        } else {
+            k.powi(s, p, RoundingMode::None)?
+                .reciprocal(p, RoundingMode::None)
+        }
+    }
+
+    // Computes H(n, s) by summing the terms with precision `p` and the relative error of a few units in the last place.
+    fn harmonic_sum(n: usize, s: usize, p: usize) -> Result<Self, Error> {
+        let guard = log2_ceil(n) + 4;
+        let p_x = p + guard;
+
+        let mut ret = Self::from_word(1, p_x)?;
+
+        for k in 2..=n {
+            let t = Self::harmonic_term(k, s, p_x)?;
+
+            if (t.exponent() as isize) < ret.exponent() as isize - p_x as isize - guard as isize {
+                // the remaining terms are less than (k+1)^(1-s) / (s-1) + k^(-s)
+                if k < n {
+                    ret = ret.add_correction(false)?;
+                }
+                break;
+            }
+
+            ret = ret.add(&t, p_x, RoundingMode::None)?;
+        }
+
+        Ok(ret)
+    }
+
+    // Computes H(n, s) for n > m with precision `p` as H(m, s) + the Euler-Maclaurin approximation of the sum of 1/k^s, k = m..n, minus 1/m^s:
+    // H(n, s) = H(m, s) - f(m)/2 + f(n)/2 + integral(f, m, n) + g(m) - g(n),
+    // where f(x) = 1/x^s, g(x) = sum(B_2j / (2j)! * s*(s+1)*...*(s+2j-2) / x^(s+2j-1)), j = 1, 2, ...
+    fn harmonic_em(n: usize, m: usize, s: usize, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        let p_x = p + log2_ceil(m) + 8;
+
+        let h_m = Self::harmonic_sum(m, s, p_x)?;
+
+        let n_num = Self::from_usize(n)?;
+        let m_num = Self::from_usize(m)?;
+
+        let f_n = Self::harmonic_term(n, s, p_x)?;
+        let f_m = Self::harmonic_term(m, s, p_x)?;
+
+        let integral = if s == 1 {
+            n_num
+                .div(&m_num, p_x, RoundingMode::None)?
+                .ln(p_x, RoundingMode::None, cc)?
+        } else {
+            // (m^(1-s) - n^(1-s)) / (s - 1)
+            let a = f_m.mul(&m_num, p_x, RoundingMode::None)?;
+            let b = f_n.mul(&n_num, p_x, RoundingMode::None)?;
+            a.sub(&b, p_x, RoundingMode::None)?.div(
+                &Self::from_usize(s - 1)?,
+                p_x,
+                RoundingMode::None,
+            )?
+        };
+
+        let g_m = Self::harmonic_em_g(&m_num, &f_m, s, p_x, cc)?;
+        let g_n = Self::harmonic_em_g(&n_num, &f_n, s, p_x, cc)?;
+
+        let mut d = f_n.sub(&f_m, p_x, RoundingMode::None)?;
+        if !d.is_zero() {
+            d.set_exponent(d.exponent() - 1);
+        }
+
+        h_m.add(&d, p_x, RoundingMode::None)?
+            .add(&integral, p_x, RoundingMode::None)?
+            .add(&g_m, p_x, RoundingMode::None)?
+            .sub(&g_n, p_x, RoundingMode::None)
+    }
+
+    // Computes g(x) for the Euler-Maclaurin formula, `f` is 1/x^s.
+    fn harmonic_em_g(
+        x: &Self,
+        f: &Self,
+        s: usize,
+        p: usize,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let x2 = x.mul(x, p, RoundingMode::None)?;
+
+        // w = s*(s+1)*...*(s+2j-2) / (2j)! / x^(s+2j-1)
+        let mut w =
+            f.div(x, p, RoundingMode::None)?
+                .mul(&Self::from_usize(s)?, p, RoundingMode::None)?;
+        w.set_exponent(w.exponent() - 1);
+
+        let mut ret = Self::new(p)?;
+        let mut j = 1;
+
+        loop {
+            let b = cc.bernoulli_num(2 * j, p, RoundingMode::None)?;
+            let t = w.mul(&b, p, RoundingMode::None)?;
+
+            ret = ret.add(&t, p, RoundingMode::None)?;
+
+            if (t.exponent() as isize) < f.exponent() as isize - p as isize {
+                break;
+            }
+
+            let c = Self::from_usize((s + 2 * j - 1) * (s + 2 * j))?;
+            let d = Self::from_usize((2 * j + 1) * (2 * j + 2))?;
+
+            w = w
+                .mul(&c, p, RoundingMode::None)?
+                .div(&d, p, RoundingMode::None)?
+                .div(&x2, p, RoundingMode::None)?;
+
+            j += 1;
+        }
+
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_harmonic() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+
+        let p = 128;
+
+        // small values
+        assert!(BigFloatNumber::harmonic(0, p, rm, &mut cc)
+            .unwrap()
+            .is_zero());
+        assert!(
+            BigFloatNumber::harmonic(1, p, rm, &mut cc)
+                .unwrap()
+                .cmp(&ONE)
+                == 0
+        );
+        let h = BigFloatNumber::harmonic(2, p, rm, &mut cc).unwrap();
+        assert!(h.cmp(&BigFloatNumber::from_f64(p, 1.5).unwrap()) == 0);
+
+        // H_3 = 11/6
+        let h = BigFloatNumber::harmonic(3, p, rm, &mut cc).unwrap();
+        let r = BigFloatNumber::from_word(11, p)
+            .unwrap()
+            .div(&BigFloatNumber::from_word(6, p).unwrap(), p, rm)
+            .unwrap();
+        assert!(h.cmp(&r) == 0);
+
+        // H(10, 0) = 10
+        let h = BigFloatNumber::generalized_harmonic(10, 0, p, rm, &mut cc).unwrap();
+        assert!(h.cmp(&BigFloatNumber::from_word(10, p).unwrap()) == 0);
+
+        // both summation and asymptotic expansion give the same result
+        for p in [64, 128, 320] {
+            for s in [1, 2, 3, 7] {
+                for n in [1000, 2000, 5000] {
+                    let mut h1 = BigFloatNumber::harmonic_sum(n, s, p + 128).unwrap();
+                    h1.set_precision(p, rm).unwrap();
+                    let h2 = BigFloatNumber::generalized_harmonic(n, s, p, rm, &mut cc).unwrap();
+                    assert!(h1.cmp(&h2) == 0, "{} {} {}", p, s, n);
+                }
+            }
+        }
+
+        // H_(10^18) = ln(10^18) + gamma + 1/(2*10^18) - ...
+        let h = BigFloatNumber::harmonic(1_000_000_000_000_000_000, 256, rm, &mut cc).unwrap();
+        let h0 = BigFloatNumber::parse(
+            "4.20237473387943551734303582744009581677786527979245038340723e+1",
+            crate::Radix::Dec,
+            256,
+            RoundingMode::None,
+        )
+        .unwrap();
+        let mut eps = ONE.clone().unwrap();
+        eps.set_exponent(-150);
+        assert!(h.sub(&h0, 256, rm).unwrap().abs_cmp(&eps) < 0);
+
+        // H(n, 2) approaches pi^2/6
+        let h = BigFloatNumber::generalized_harmonic(usize::MAX, 2, p, rm, &mut cc).unwrap();
+        let pi = cc.pi_num(p, rm).unwrap();
+        let z2 = pi.mul(&pi, p, rm).unwrap().div_word(6, p, rm).unwrap();
+        let mut eps = ONE.clone().unwrap();
+        eps.set_exponent(-60);
+        assert!(h.sub(&z2, p, rm).unwrap().abs_cmp(&eps) < 0);
+
+        // large order
+        let h = BigFloatNumber::generalized_harmonic(100, 1000, p, rm, &mut cc).unwrap();
+        assert!(h.cmp(&ONE) == 0);
+        let h =
+            BigFloatNumber::generalized_harmonic(100, 1000, p, RoundingMode::Up, &mut cc).unwrap();
+        assert!(h.cmp(&ONE) > 0);
+    }
+}
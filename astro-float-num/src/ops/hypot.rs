@@ -0,0 +1,200 @@
+//! Euclidean norm computation.
+
+use crate::{
+    common::util::round_p,
+    defs::{Error, EXPONENT_MAX, EXPONENT_MIN, WORD_BIT_SIZE},
+    num::BigFloatNumber,
+    Exponent, RoundingMode, Sign,
+};
+
+use core::cmp::Reverse;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+impl BigFloatNumber {
+    /// Computes the Euclidean norm of the vector `xs`, i.e. the square root of the sum of squares of its elements,
+    /// with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn norm(xs: &[&Self], p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        let mut inexact = false;
+        let mut v = Vec::new();
+        v.try_reserve_exact(xs.len())?;
+
+        for x in xs {
+            inexact |= x.inexact();
+
+            if !x.is_zero() {
+                let (e, _) = x.normalize()?;
+                v.push((e, *x));
+            }
+        }
+
+        if v.is_empty() {
+            let mut ret = Self::new(p)?;
+            ret.set_inexact(inexact);
+            return Ok(ret);
+        }
+
+        // The elements are scaled so that the largest of them has exponent 0,
+        // which excludes overflow and underflow of the squares.
+        // The squares are summed exactly, and the result is rounded only once by sqrt.
+        // Elements which are too small to affect the rounding of the result
+        // are replaced by a single positive value below the least significant bit of the sum.
+        v.sort_by_key(|a| Reverse(a.0));
+
+        let e_max = v[0].0;
+        let log_n = (usize::BITS - v.len().leading_zeros()) as isize;
+
+        // exponent of the least significant bit of the sum and of the squares of possible rounding boundaries
+        let mut lsb = -2 * p as isize - 8;
+        let mut dropped = false;
+        let mut sum = Self::new(WORD_BIT_SIZE)?;
+
+        for (e, x) in v {
+            let es = e - e_max;
+
+            if 2 * es + log_n + 2 < lsb {
+                dropped = true;
+                break;
+            }
+
+            let mut y = x.clone()?;
+            y.set_sign(Sign::Pos);
+            y.set_exponent((x.exponent() as isize - e_max) as Exponent);
+
+            lsb = lsb.min(2 * (es - y.mantissa_max_bit_len() as isize));
+
+            let sq = y.mul_full_prec(&y)?;
+            sum = sum.add_full_prec(&sq)?;
+        }
+
+        if dropped {
+            let mut t = Self::from_word(1, WORD_BIT_SIZE)?;
+            t.set_exponent((lsb - 1) as Exponent);
+            sum = sum.add_full_prec(&t)?;
+        }
+
+        let mut ret = sum.sqrt(p, rm)?;
+
+        let e = ret.exponent() as isize + e_max;
+
+        if e > EXPONENT_MAX as isize {
+            return Err(Error::ExponentOverflow(Sign::Pos));
+        }
+
+        if e < EXPONENT_MIN as isize {
+            ret.set_exponent(EXPONENT_MIN);
+            ret.subnormalize(e, rm);
+        } else {
+            ret.set_exponent(e as Exponent);
+        }
+
+        ret.set_inexact(ret.inexact() || inexact || dropped);
+
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::util::rand_p;
+
+    #[test]
+    fn test_hypot() {
+        let rm = RoundingMode::ToEven;
+
+        // exact results
+        let n = |w| BigFloatNumber::from_word(w, 128).unwrap();
+        let d = BigFloatNumber::norm(&[&n(2), &n(3), &n(6)], 128, rm).unwrap();
+        assert_eq!(d.cmp(&n(7)), 0);
+        assert!(!d.inexact());
+
+        let d = BigFloatNumber::norm(&[&n(1), &n(2), &n(4), &n(10)], 128, rm).unwrap();
+        assert_eq!(d.cmp(&n(11)), 0);
+        assert!(!d.inexact());
+
+        let d = BigFloatNumber::norm(&[], 128, rm).unwrap();
+        assert!(d.is_zero());
+
+        // large and small values
+        let mut x = n(3);
+        x.set_exponent(EXPONENT_MAX - 1);
+        let mut y = n(4);
+        y.set_exponent(EXPONENT_MAX);
+        let z = BigFloatNumber::new(128).unwrap();
+        let mut r = n(5);
+        r.set_exponent(EXPONENT_MAX);
+        let m = BigFloatNumber::max_value(128).unwrap();
+        assert_eq!(
+            BigFloatNumber::norm(&[&x, &y.neg().unwrap(), &z], 128, rm)
+                .unwrap()
+                .cmp(&r),
+            0
+        );
+        assert_eq!(
+            BigFloatNumber::norm(&[&m, &z, &m], 128, rm).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+
+        let mut x = BigFloatNumber::min_positive(128).unwrap();
+        let d = BigFloatNumber::norm(&[&x, &x, &x], 128, rm).unwrap();
+        assert!(d.is_subnormal());
+        assert_eq!(d.cmp(&x), 1);
+        x.set_exponent(EXPONENT_MIN);
+        let mut y = BigFloatNumber::min_positive_normal(128).unwrap();
+        y.set_exponent(EXPONENT_MIN + 2);
+        assert_eq!(
+            BigFloatNumber::norm(&[&x, &y, &x], 128, rm)
+                .unwrap()
+                .cmp(&y),
+            0
+        );
+        assert_eq!(
+            BigFloatNumber::norm(&[&x, &y, &x], 128, RoundingMode::Up)
+                .unwrap()
+                .cmp(&y),
+            1
+        );
+
+        // tiny elements affect the directed rounding
+        let x = n(1);
+        let mut t = n(1);
+        t.set_exponent(EXPONENT_MIN);
+        let d = BigFloatNumber::norm(&[&x, &t, &t], 128, RoundingMode::Up).unwrap();
+        assert_eq!(d.cmp(&x), 1);
+        assert!(d.inexact());
+        let d = BigFloatNumber::norm(&[&x, &t, &t], 128, RoundingMode::Down).unwrap();
+        assert_eq!(d.cmp(&x), 0);
+
+        // comparison with the formula
+        for _ in 0..100 {
+            let p = rand_p();
+            let xs: Vec<BigFloatNumber> = (0..5)
+                .map(|_| BigFloatNumber::random_normal(p, -100, 100).unwrap())
+                .collect();
+            let xr: Vec<&BigFloatNumber> = xs.iter().collect();
+
+            let d = BigFloatNumber::norm(&xr, p, rm).unwrap();
+
+            let mut s = BigFloatNumber::new(p).unwrap();
+            for x in xs.iter() {
+                s = s.add_full_prec(&x.mul_full_prec(x).unwrap()).unwrap();
+            }
+            let d0 = s.sqrt(p, rm).unwrap();
+
+            assert_eq!(d.cmp(&d0), 0);
+        }
+    }
+}
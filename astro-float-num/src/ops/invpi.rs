@@ -0,0 +1,414 @@
+//! Inverse trigonometric functions returning angles in half-revolutions.
+
+use crate::common::consts::ONE;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::ops::consts::Consts;
+use crate::ops::util::compute_small_exp;
+use crate::Exponent;
+use crate::Sign;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Computes the arcsine of a number divided by pi with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is in the interval [-1/2, 1/2].
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: when |`self`| > 1, or the precision is incorrect.
+    pub fn asinpi(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return Self::new2(p, self.sign(), self.inexact());
+        }
+
+        let onecmp = self.abs_cmp(&ONE);
+        if onecmp > 0 {
+            return Err(Error::InvalidArgument);
+        } else if onecmp == 0 {
+            return Self::half_revolution_frac(0, self.sign(), p, self.inexact());
+        }
+
+        Self::div_by_pi(p.max(self.mantissa_max_bit_len()), p, rm, cc, |p_x, cc| {
+            self.asin(p_x, RoundingMode::None, cc)
+        })
+    }
+
+    /// Computes the arccosine of a number divided by pi with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is in the interval [0, 1].
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: when |`self`| > 1, or the precision is incorrect.
+    pub fn acospi(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return Self::half_revolution_frac(0, Sign::Pos, p, self.inexact());
+        }
+
+        let onecmp = self.abs_cmp(&ONE);
+        if onecmp > 0 {
+            return Err(Error::InvalidArgument);
+        } else if onecmp == 0 {
+            return if self.is_positive() {
+                Self::new2(p, Sign::Pos, self.inexact())
+            } else {
+                Self::half_revolution_frac(1, Sign::Pos, p, self.inexact())
+            };
+        }
+
+        // acospi(x) = 1/2 - x/pi - ...
+        let half = Self::half_revolution_frac(0, Sign::Pos, p, true)?;
+        compute_small_exp!(
+            half,
+            self.normalized_exponent() - 1,
+            self.is_positive(),
+            p,
+            rm
+        );
+
+        Self::div_by_pi(p.max(self.mantissa_max_bit_len()), p, rm, cc, |p_x, cc| {
+            self.acos(p_x, RoundingMode::None, cc)
+        })
+    }
+
+    /// Computes the arctangent of a number divided by pi with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is in the interval [-1/2, 1/2].
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn atanpi(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return Self::new2(p, self.sign(), self.inexact());
+        }
+
+        if self.abs_cmp(&ONE) == 0 {
+            return Self::half_revolution_frac(-1, self.sign(), p, self.inexact());
+        }
+
+        // atanpi(x) = sign(x)/2 - 1/(pi*x) + ...
+        let half = Self::half_revolution_frac(0, self.sign(), p, true)?;
+        compute_small_exp!(half, -self.normalized_exponent(), true, p, rm);
+
+        Self::div_by_pi(p.max(self.mantissa_max_bit_len()), p, rm, cc, |p_x, cc| {
+            self.atan(p_x, RoundingMode::None, cc)
+        })
+    }
+
+    /// Computes the angle between the positive x axis and the point (`x`, `self`) divided by pi with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// The result is in the interval [-1, 1], and its sign is the sign of `self`, including the sign of zero.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn atan2pi(
+        &self,
+        x: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        let y = self;
+        let s = y.sign();
+        let inexact = y.inexact() || x.inexact();
+
+        // exact values
+        if y.is_zero() {
+            return if x.is_positive() {
+                Self::new2(p, s, inexact)
+            } else {
+                Self::half_revolution_frac(1, s, p, inexact)
+            };
+        }
+
+        if x.is_zero() {
+            return Self::half_revolution_frac(0, s, p, inexact);
+        }
+
+        let cmp = x.abs_cmp(y);
+
+        if cmp == 0 {
+            let mut ret = Self::from_word(if x.is_positive() { 1 } else { 3 }, p)?;
+            ret.set_exponent(ret.exponent() - 2);
+            ret.set_sign(s);
+            ret.set_inexact(inexact);
+            return Ok(ret);
+        }
+
+        // the smaller of |x/y| and |y/x| is less than 2^ed
+        let ed = 1 - (y.normalized_exponent() - x.normalized_exponent()).abs();
+
+        if cmp > 0 && x.is_negative() {
+            // the result is sign(y) * (1 - |y/x|/pi + ...)
+            let b = Self::half_revolution_frac(1, s, p, true)?;
+            compute_small_exp!(b, ed, true, p, rm);
+        } else if cmp < 0 {
+            // the result is sign(y) * 1/2 - atan(x/y)/pi
+            let b = Self::half_revolution_frac(0, s, p, true)?;
+            compute_small_exp!(b, ed, x.is_positive(), p, rm);
+        }
+
+        let p_wrk = p
+            .max(x.mantissa_max_bit_len())
+            .max(y.mantissa_max_bit_len());
+
+        let ret = Self::div_by_pi(p_wrk, p, rm, cc, |p_x, cc| {
+            if cmp > 0 {
+                // |y/x| < 1
+                let a = y
+                    .div(x, p_x, RoundingMode::None)?
+                    .atan(p_x, RoundingMode::None, cc)?;
+
+                if x.is_positive() {
+                    Ok(a)
+                } else {
+                    // a + pi * sign(y)
+                    let mut pi = cc.pi_num(p_x, RoundingMode::None)?;
+                    pi.set_sign(s);
+                    a.add(&pi, p_x, RoundingMode::None)
+                }
+            } else {
+                // |x/y| < 1, the result is pi/2 * sign(y) - atan(x/y)
+                let a = x
+                    .div(y, p_x, RoundingMode::None)?
+                    .atan(p_x, RoundingMode::None, cc)?;
+
+                let mut pi = cc.pi_num(p_x, RoundingMode::None)?;
+                pi.set_exponent(pi.exponent() - 1);
+                pi.set_sign(s);
+                pi.sub(&a, p_x, RoundingMode::None)
+            }
+        });
+
+        match ret {
+            // y/x is too small
+            Err(Error::ExponentOverflow(Sign::Neg)) => Self::new2(p, s, true),
+            ret => ret,
+        }
+    }
+
+    // Returns 2^(e-1) with sign `s`, i.e. 1 for e = 1, 1/2 for e = 0, and 1/4 for e = -1.
+    fn half_revolution_frac(e: Exponent, s: Sign, p: usize, inexact: bool) -> Result<Self, Error> {
+        let mut ret = Self::from_word(1, p)?;
+        ret.set_exponent(e);
+        ret.set_sign(s);
+        ret.set_inexact(inexact);
+        Ok(ret)
+    }
+
+    // Computes the angle returned by `f` divided by pi.
+    // The function `f` computes the angle with the given precision and the relative error of a few units in the last place.
+    fn div_by_pi<F>(
+        p_start: usize,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+        mut f: F,
+    ) -> Result<Self, Error>
+    where
+        F: FnMut(usize, &mut Consts) -> Result<Self, Error>,
+    {
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p_start + p_inc;
+
+        loop {
+            let p_x = p_wrk + 4;
+
+            let a = f(p_x, cc)?;
+
+            if a.is_zero() {
+                // the angle underflows
+                return Self::new2(p, a.sign(), true);
+            }
+
+            let pi = cc.pi_num(p_x, RoundingMode::None)?;
+
+            let mut ret = a.div(&pi, p_x, RoundingMode::None)?;
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                ret.set_inexact(true);
+                return Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::util::rand_p;
+
+    #[test]
+    fn test_invpi() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+        let p = 256;
+
+        let f = |v: f64| BigFloatNumber::from_f64(p, v).unwrap();
+        let one = f(1.0);
+        let zero = BigFloatNumber::new(p).unwrap();
+        let nzero = zero.neg().unwrap();
+
+        // exact values
+        for rm in [RoundingMode::Up, RoundingMode::Down, RoundingMode::ToEven] {
+            let cases = [
+                (one.asinpi(p, rm, &mut cc), 0.5),
+                (one.neg().unwrap().asinpi(p, rm, &mut cc), -0.5),
+                (one.acospi(p, rm, &mut cc), 0.0),
+                (one.neg().unwrap().acospi(p, rm, &mut cc), 1.0),
+                (zero.acospi(p, rm, &mut cc), 0.5),
+                (one.atanpi(p, rm, &mut cc), 0.25),
+                (one.neg().unwrap().atanpi(p, rm, &mut cc), -0.25),
+                (f(3.0).atan2pi(&f(3.0), p, rm, &mut cc), 0.25),
+                (f(3.0).atan2pi(&f(-3.0), p, rm, &mut cc), 0.75),
+                (f(-3.0).atan2pi(&f(-3.0), p, rm, &mut cc), -0.75),
+                (f(-3.0).atan2pi(&zero, p, rm, &mut cc), -0.5),
+                (zero.atan2pi(&f(-2.0), p, rm, &mut cc), 1.0),
+                (nzero.atan2pi(&f(-2.0), p, rm, &mut cc), -1.0),
+                (nzero.atan2pi(&nzero, p, rm, &mut cc), -1.0),
+                (zero.atan2pi(&f(2.0), p, rm, &mut cc), 0.0),
+            ];
+
+            for (d, v) in cases {
+                let d = d.unwrap();
+                assert_eq!(d.cmp(&f(v)), 0);
+                assert!(!d.inexact());
+            }
+        }
+
+        assert!(nzero.asinpi(p, rm, &mut cc).unwrap().is_negative());
+        assert!(nzero.atan2pi(&one, p, rm, &mut cc).unwrap().is_negative());
+        assert_eq!(
+            f(1.5).asinpi(p, rm, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            f(-1.5).acospi(p, rm, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+
+        // asinpi(1/2) = 1/6, acospi(1/2) = 1/3
+        let half = f(0.5);
+        let d = half.asinpi(p, rm, &mut cc).unwrap();
+        let d0 = one.div(&f(6.0), p, rm).unwrap();
+        assert_eq!(d.cmp(&d0), 0);
+        let d = half.acospi(p, rm, &mut cc).unwrap();
+        let d0 = one.div(&f(3.0), p, rm).unwrap();
+        assert_eq!(d.cmp(&d0), 0);
+        let d = half.neg().unwrap().acospi(p, rm, &mut cc).unwrap();
+        let d0 = f(2.0).div(&f(3.0), p, rm).unwrap();
+        assert_eq!(d.cmp(&d0), 0);
+
+        // quadrants of atan2pi
+        let t = f(0.75).atanpi(p, rm, &mut cc).unwrap();
+        let u = f(4.0)
+            .div(&f(3.0), p + 128, rm)
+            .unwrap()
+            .atanpi(p, rm, &mut cc)
+            .unwrap();
+        let t1 = f(0.75).atanpi(p + 128, rm, &mut cc).unwrap();
+        let u1 = f(4.0)
+            .div(&f(3.0), p + 256, rm)
+            .unwrap()
+            .atanpi(p + 128, rm, &mut cc)
+            .unwrap();
+        let mut q = |y: f64, x: f64| f(y).atan2pi(&f(x), p, rm, &mut cc).unwrap();
+        assert_eq!(q(3.0, 4.0).cmp(&t), 0);
+        assert_eq!(q(4.0, 3.0).cmp(&u), 0);
+        assert_eq!(q(-3.0, 4.0).cmp(&t.neg().unwrap()), 0);
+        assert_eq!(q(3.0, -4.0).cmp(&one.sub(&t1, p, rm).unwrap()), 0);
+        assert_eq!(q(-4.0, -3.0).cmp(&u1.sub(&one, p, rm).unwrap()), 0);
+
+        // extreme ratios
+        let mut big = f(1.0);
+        big.set_exponent(crate::EXPONENT_MAX);
+        let mut small = f(1.0);
+        small.set_exponent(crate::EXPONENT_MIN);
+        let d = big.atan2pi(&small, p, rm, &mut cc).unwrap();
+        assert_eq!(d.cmp(&half), 0);
+        assert!(d.inexact());
+        let d = small.atan2pi(&big.neg().unwrap(), p, rm, &mut cc).unwrap();
+        assert_eq!(d.cmp(&one), 0);
+        let d = small.atan2pi(&big, p, RoundingMode::Up, &mut cc).unwrap();
+        assert!(d.is_positive());
+
+        for (d, c) in [
+            (big.atanpi(p, RoundingMode::Up, &mut cc), 0),
+            (big.atanpi(p, RoundingMode::Down, &mut cc), -1),
+            (small.acospi(p, RoundingMode::Up, &mut cc), 0),
+            (small.acospi(p, RoundingMode::Down, &mut cc), -1),
+            (big.atan2pi(&small, p, RoundingMode::Down, &mut cc), -1),
+        ] {
+            let d = d.unwrap();
+            assert_eq!(d.cmp(&half).signum(), c);
+            assert!(d.inexact());
+        }
+
+        // comparison with the radian functions
+        for _ in 0..50 {
+            let p = rand_p();
+            let x = BigFloatNumber::random_normal(p, -20, 0).unwrap();
+            let y = BigFloatNumber::random_normal(p, -20, 20).unwrap();
+            let p1 = p + 128;
+            let pi = cc.pi_num(p1, RoundingMode::None).unwrap();
+
+            let d = x.asinpi(p, rm, &mut cc).unwrap();
+            let d0 = x.asin(p1, rm, &mut cc).unwrap().div(&pi, p, rm).unwrap();
+            assert_eq!(d.cmp(&d0), 0);
+
+            let d = x.acospi(p, rm, &mut cc).unwrap();
+            let d0 = x.acos(p1, rm, &mut cc).unwrap().div(&pi, p, rm).unwrap();
+            assert_eq!(d.cmp(&d0), 0);
+
+            let d = y.atanpi(p, rm, &mut cc).unwrap();
+            let d0 = y.atan(p1, rm, &mut cc).unwrap().div(&pi, p, rm).unwrap();
+            assert_eq!(d.cmp(&d0), 0);
+
+            let d = y.atan2pi(&x, p, rm, &mut cc).unwrap();
+            let d0 = if x.is_positive() {
+                y.div(&x, p1, rm)
+                    .unwrap()
+                    .atan(p1, rm, &mut cc)
+                    .unwrap()
+                    .div(&pi, p, rm)
+                    .unwrap()
+            } else {
+                let a = y.div(&x, p1, rm).unwrap().atan(p1, rm, &mut cc).unwrap();
+                let mut pi1 = pi.clone().unwrap();
+                pi1.set_sign(y.sign());
+                a.add(&pi1, p1, rm).unwrap().div(&pi, p, rm).unwrap()
+            };
+            assert_eq!(d.cmp(&d0), 0);
+        }
+    }
+}
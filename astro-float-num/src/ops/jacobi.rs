@@ -0,0 +1,490 @@
+//! Jacobi elliptic functions sn, cn, and dn.
+
+use crate::common::consts::ONE;
+use crate::common::util::log2_ceil;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::ops::consts::Consts;
+use crate::Exponent;
+use crate::Sign;
+use crate::WORD_BIT_SIZE;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+impl BigFloatNumber {
+    /// Computes the Jacobi elliptic function sn(u | m) of the argument `self` and the parameter `m` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: `m` is outside of the interval [0, 1], or the precision is incorrect.
+    pub fn jacobi_sn(
+        &self,
+        m: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let [sn, _, _] = self.jacobi(m, [true, false, false], p, rm, cc)?;
+        Ok(sn)
+    }
+
+    /// Computes the Jacobi elliptic function cn(u | m) of the argument `self` and the parameter `m` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: `m` is outside of the interval [0, 1], or the precision is incorrect.
+    pub fn jacobi_cn(
+        &self,
+        m: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let [_, cn, _] = self.jacobi(m, [false, true, false], p, rm, cc)?;
+        Ok(cn)
+    }
+
+    /// Computes the Jacobi elliptic function dn(u | m) of the argument `self` and the parameter `m` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: `m` is outside of the interval [0, 1], or the precision is incorrect.
+    pub fn jacobi_dn(
+        &self,
+        m: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let [_, _, dn] = self.jacobi(m, [false, false, true], p, rm, cc)?;
+        Ok(dn)
+    }
+
+    /// Computes the Jacobi elliptic functions sn(u | m), cn(u | m), and dn(u | m) of the argument `self` and the parameter `m`
+    /// with precision `p`. Each of the results is rounded using the rounding mode `rm`.
+    /// The functions share the arithmetic-geometric mean sequence, so computing them together is faster than computing them one by one.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: `m` is outside of the interval [0, 1], or the precision is incorrect.
+    pub fn jacobi_elliptic(
+        &self,
+        m: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<(Self, Self, Self), Error> {
+        let [sn, cn, dn] = self.jacobi(m, [true, true, true], p, rm, cc)?;
+        Ok((sn, cn, dn))
+    }
+
+    // Computes sn, cn, and dn, and rounds the values for which `mask` is true to precision `p` using the rounding mode `rm`.
+    // The other values are returned unrounded.
+    fn jacobi(
+        &self,
+        m: &Self,
+        mask: [bool; 3],
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<[Self; 3], Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if m.is_negative() || m.cmp(&ONE) > 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let inexact = self.inexact() || m.inexact();
+
+        if self.is_zero() {
+            let mut one = Self::from_word(1, p)?;
+            one.set_inexact(inexact);
+            return Ok([Self::new2(p, self.sign(), inexact)?, one.clone()?, one]);
+        }
+
+        if m.is_zero() {
+            // sn = sin(u), cn = cos(u), dn = 1
+            let mut dn = Self::from_word(1, p)?;
+            dn.set_inexact(inexact);
+            let mut sn = self.sin(p, rm, cc)?;
+            let mut cn = self.cos(p, rm, cc)?;
+            sn.set_inexact(sn.inexact() || inexact);
+            cn.set_inexact(cn.inexact() || inexact);
+            return Ok([sn, cn, dn]);
+        }
+
+        if 2 * (self.exponent() as isize) < -(p as isize) - 4 {
+            // sn = u - (1 + m)*u^3/6 + ..., cn = 1 - u^2/2 + ..., dn = 1 - m*u^2/2 + ...,
+            // where the terms after the first one are below the rounding error
+            let mut sn = self.clone()?;
+            sn.set_precision(
+                p.max(sn.mantissa_max_bit_len()) + WORD_BIT_SIZE,
+                RoundingMode::None,
+            )?;
+            sn = sn.add_correction(true)?;
+            sn.set_precision(p, rm)?;
+            sn.set_inexact(true);
+
+            let mut cn = Self::from_word(1, p + WORD_BIT_SIZE)?.add_correction(true)?;
+            cn.set_precision(p, rm)?;
+            cn.set_inexact(true);
+
+            return Ok([sn, cn.clone()?, cn]);
+        }
+
+        if m.cmp(&ONE) == 0 {
+            // sn = tanh(u), cn = dn = 1 / cosh(u)
+            let mut sn = self.tanh(p, rm, cc)?;
+            let mut cn = self.sech(p, rm, cc)?;
+            sn.set_inexact(sn.inexact() || inexact);
+            cn.set_inexact(cn.inexact() || inexact);
+            return Ok([sn, cn.clone()?, cn]);
+        }
+
+        // the absolute error of the amplitude grows with the argument
+        let e_u = (self.exponent() as isize).max(0) as usize;
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        let mut ret: [Option<Self>; 3] = [None, None, None];
+
+        loop {
+            let p_x = p_wrk + e_u + 2 * log2_ceil(p_wrk) + 8;
+
+            let (vals, loss) = self.jacobi_approx(m, p_x, cc)?;
+
+            for (i, mut x) in vals.into_iter().enumerate() {
+                if ret[i].is_some() {
+                    continue;
+                }
+
+                if !mask[i] {
+                    ret[i] = Some(x);
+                    continue;
+                }
+
+                if x.is_zero() {
+                    continue;
+                }
+
+                let s = (p_wrk as isize - loss as isize + (x.exponent() as isize).min(0)).max(0)
+                    as usize
+                    / WORD_BIT_SIZE
+                    * WORD_BIT_SIZE;
+
+                if s > p && x.try_set_precision(p, rm, s)? {
+                    x.set_inexact(true);
+                    ret[i] = Some(x);
+                }
+            }
+
+            if let [Some(sn), Some(cn), Some(dn)] = ret {
+                return Ok([sn, cn, dn]);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Computes 1 / cosh(`self`) with precision `p` rounded using the rounding mode `rm`.
+    fn sech(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + 4;
+
+            let ch = match self.cosh(p_x, RoundingMode::None, cc) {
+                Ok(v) => v,
+                Err(Error::ExponentOverflow(_)) => {
+                    // 1 / cosh(u) is too small
+                    return Self::new2(p, Sign::Pos, true);
+                }
+                Err(e) => return Err(e),
+            };
+
+            let mut ret = ONE.div(&ch, p_x, RoundingMode::None)?;
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                ret.set_inexact(true);
+                return Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Computes sn, cn, and dn for 0 < m < 1 and non-zero argument with the absolute error less than 2^(loss - p),
+    // and returns the approximations and the number of bits `loss`.
+    // The amplitude is computed using the descending Landen transformation:
+    // a_0 = 1, b_0 = sqrt(1 - m), c_0 = sqrt(m),
+    // a_(n+1) = (a_n + b_n) / 2, b_(n+1) = sqrt(a_n * b_n), c_(n+1) = (a_n - b_n) / 2,
+    // phi_N = 2^N * a_N * u, phi_(n-1) = (phi_n + asin(c_n / a_n * sin(phi_n))) / 2,
+    // and then sn = sin(phi_0), cn = cos(phi_0), dn = sqrt(cn^2 + (1 - m) * sn^2).
+    fn jacobi_approx(
+        &self,
+        m: &Self,
+        p: usize,
+        cc: &mut Consts,
+    ) -> Result<([Self; 3], usize), Error> {
+        let rm = RoundingMode::None;
+
+        let m1 = ONE.sub(m, p, rm)?;
+
+        let mut a = Self::from_word(1, p)?;
+        let mut b = m1.sqrt(p, rm)?;
+        let mut c = m.sqrt(p, rm)?;
+
+        let mut cs = Vec::new();
+
+        // stopping at N introduces the error of phi_0 of about |u| * c_N^2
+        let e_u = (self.exponent() as isize).max(0);
+
+        while !c.is_zero() && 2 * c.exponent() as isize > a.exponent() as isize - p as isize - e_u {
+            let mut a1 = a.add(&b, p, rm)?;
+            a1.set_exponent(a1.exponent() - 1);
+
+            let mut c1 = a.sub(&b, p, rm)?;
+            c1.set_exponent(c1.exponent() - 1);
+
+            b = a.mul(&b, p, rm)?.sqrt(p, rm)?;
+            a = a1;
+            c = c1;
+
+            cs.try_reserve(1)?;
+            cs.push(c.div(&a, p, rm)?);
+        }
+
+        let mut phi = self.abs()?.mul(&a, p, rm)?;
+        phi.set_exponent(phi.exponent() + cs.len() as Exponent);
+
+        let mut loss = 4;
+
+        while let Some(r) = cs.pop() {
+            let mut t = r.mul(&phi.sin(p, rm, cc)?, p, rm)?;
+
+            if t.abs_cmp(&ONE) >= 0 {
+                let s = t.sign();
+                t = Self::from_word(1, p)?;
+                t.set_sign(s);
+                loss += p / 2;
+            } else {
+                // asin(t) amplifies the error by 1 / sqrt(1 - t^2)
+                let d = ONE.sub(&t.abs()?, p, rm)?;
+                loss += ((-(d.exponent() as isize)).max(0) / 2 + 1) as usize;
+            }
+
+            phi = phi.add(&t.asin(p, rm, cc)?, p, rm)?;
+            phi.set_exponent(phi.exponent() - 1);
+        }
+
+        let mut sn = phi.sin(p, rm, cc)?;
+        let cn = phi.cos(p, rm, cc)?;
+
+        let dn = cn
+            .mul(&cn, p, rm)?
+            .add(&sn.mul(&sn, p, rm)?.mul(&m1, p, rm)?, p, rm)?
+            .sqrt(p, rm)?;
+
+        if self.is_negative() {
+            sn.inv_sign();
+        }
+
+        Ok(([sn, cn, dn], loss))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_jacobi() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+        let p = 128;
+
+        let n = |f: f64| BigFloatNumber::from_f64(p, f).unwrap();
+        let zero = BigFloatNumber::new(p).unwrap();
+
+        // sn^2 + cn^2 = 1, dn^2 + m sn^2 = 1
+        let pe = p + 64;
+        for m in [0.1, 0.5, 0.9, 0.999999, 1e-30] {
+            let m = n(m);
+            for u in [0.3, -1.7, 5.0, 100.0, 1e-20] {
+                let u = n(u);
+                let (sn, cn, dn) = u.jacobi_elliptic(&m, pe, rm, &mut cc).unwrap();
+
+                let mut eps = ONE.clone().unwrap();
+                eps.set_exponent(-(p as Exponent));
+
+                let s1 = sn
+                    .mul(&sn, pe, rm)
+                    .unwrap()
+                    .add(&cn.mul(&cn, pe, rm).unwrap(), pe, rm)
+                    .unwrap();
+                assert!(s1.sub(&ONE, pe, rm).unwrap().abs_cmp(&eps) < 0);
+
+                let s2 = dn
+                    .mul(&dn, pe, rm)
+                    .unwrap()
+                    .add(
+                        &sn.mul(&sn, pe, rm).unwrap().mul(&m, pe, rm).unwrap(),
+                        pe,
+                        rm,
+                    )
+                    .unwrap();
+                assert!(s2.sub(&ONE, pe, rm).unwrap().abs_cmp(&eps) < 0);
+
+                // sn is odd, cn and dn are even
+                let (sn2, cn2, dn2) = u
+                    .neg()
+                    .unwrap()
+                    .jacobi_elliptic(&m, pe, rm, &mut cc)
+                    .unwrap();
+                assert!(sn2.cmp(&sn.neg().unwrap()) == 0);
+                assert!(cn2.cmp(&cn) == 0);
+                assert!(dn2.cmp(&dn) == 0);
+
+                // individual functions give the same values
+                for rm in [RoundingMode::ToEven, RoundingMode::Up, RoundingMode::Down] {
+                    let (sn, cn, dn) = u.jacobi_elliptic(&m, p, rm, &mut cc).unwrap();
+                    assert!(u.jacobi_sn(&m, p, rm, &mut cc).unwrap().cmp(&sn) == 0);
+                    assert!(u.jacobi_cn(&m, p, rm, &mut cc).unwrap().cmp(&cn) == 0);
+                    assert!(u.jacobi_dn(&m, p, rm, &mut cc).unwrap().cmp(&dn) == 0);
+                }
+            }
+        }
+
+        // sn(u | m) ~ u - (1 + m) u^3 / 6 for small u
+        let u = n(1e-10);
+        let m = n(0.5);
+        let sn = u.jacobi_sn(&m, p, rm, &mut cc).unwrap();
+        let u3 = u.mul(&u, pe, rm).unwrap().mul(&u, pe, rm).unwrap();
+        let t = u3
+            .mul(&n(1.5), pe, rm)
+            .unwrap()
+            .div_word(6, pe, rm)
+            .unwrap();
+        let d = u.sub(&t, p, rm).unwrap();
+        assert!(sn.cmp(&d) == 0);
+
+        // sn(K(m) | m) = 1, cn(K(m) | m) = 0, dn(K(m) | m) = sqrt(1 - m), where K(1/2) = 1.854074677301371918433850347195260046217598823521766905585928045
+        let k = BigFloatNumber::parse(
+            "1.854074677301371918433850347195260046217598823521766905585928045",
+            crate::Radix::Dec,
+            256,
+            RoundingMode::ToEven,
+        )
+        .unwrap();
+        let (sn, cn, dn) = k.jacobi_elliptic(&m, p, rm, &mut cc).unwrap();
+        assert!(sn.cmp(&ONE) == 0);
+        assert!(cn.exponent() < -150);
+        assert!(dn.cmp(&m.sqrt(p, rm).unwrap()) == 0);
+
+        // m = 0 and m = 1
+        let u = n(0.75);
+        let one = n(1.0);
+        assert!(
+            u.jacobi_sn(&zero, p, rm, &mut cc)
+                .unwrap()
+                .cmp(&u.sin(p, rm, &mut cc).unwrap())
+                == 0
+        );
+        assert!(
+            u.jacobi_cn(&zero, p, rm, &mut cc)
+                .unwrap()
+                .cmp(&u.cos(p, rm, &mut cc).unwrap())
+                == 0
+        );
+        assert!(u.jacobi_dn(&zero, p, rm, &mut cc).unwrap().cmp(&one) == 0);
+        assert!(
+            u.jacobi_sn(&one, p, rm, &mut cc)
+                .unwrap()
+                .cmp(&u.tanh(p, rm, &mut cc).unwrap())
+                == 0
+        );
+        let sech = ONE
+            .div(&u.cosh(pe, RoundingMode::None, &mut cc).unwrap(), p, rm)
+            .unwrap();
+        assert!(u.jacobi_cn(&one, p, rm, &mut cc).unwrap().cmp(&sech) == 0);
+        assert!(u.jacobi_dn(&one, p, rm, &mut cc).unwrap().cmp(&sech) == 0);
+
+        // zero argument
+        let (sn, cn, dn) = zero.jacobi_elliptic(&m, p, rm, &mut cc).unwrap();
+        assert!(sn.is_zero() && cn.cmp(&one) == 0 && dn.cmp(&one) == 0);
+        assert!(!sn.inexact() && !cn.inexact() && !dn.inexact());
+
+        // tiny arguments
+        let mut u = BigFloatNumber::from_word(3, p).unwrap();
+        for e in [-1000000, crate::defs::EXPONENT_MIN] {
+            u.set_exponent(e);
+            for m in [n(0.5), one.clone().unwrap()] {
+                let (sn, cn, dn) = u.jacobi_elliptic(&m, p, rm, &mut cc).unwrap();
+                assert!(sn.cmp(&u) == 0 && cn.cmp(&one) == 0 && dn.cmp(&one) == 0);
+                assert!(sn.inexact() && cn.inexact() && dn.inexact());
+
+                let (sn, cn, dn) = u
+                    .jacobi_elliptic(&m, p, RoundingMode::Down, &mut cc)
+                    .unwrap();
+                assert!(sn.cmp(&u) < 0 && cn.cmp(&one) < 0 && dn.cmp(&one) < 0);
+
+                let sn = u
+                    .neg()
+                    .unwrap()
+                    .jacobi_sn(&m, p, RoundingMode::Down, &mut cc)
+                    .unwrap();
+                assert!(sn.cmp(&u.neg().unwrap()) == 0);
+            }
+        }
+
+        // tiny parameter
+        let mut m = BigFloatNumber::from_word(1, p).unwrap();
+        m.set_exponent(-1000000);
+        let u = n(0.75);
+        let sn = u.jacobi_sn(&m, p, rm, &mut cc).unwrap();
+        assert!(sn.cmp(&u.sin(p, rm, &mut cc).unwrap()) == 0);
+
+        // m = 1 and large argument
+        let u = n(1000.0);
+        let (sn, cn, _) = u.jacobi_elliptic(&one, p, rm, &mut cc).unwrap();
+        assert!(sn.cmp(&u.tanh(p, rm, &mut cc).unwrap()) == 0);
+        let sech = ONE
+            .div(&u.cosh(pe, RoundingMode::None, &mut cc).unwrap(), p, rm)
+            .unwrap();
+        assert!(cn.cmp(&sech) == 0);
+
+        // invalid parameter
+        assert_eq!(
+            u.jacobi_sn(&n(1.5), p, rm, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            u.jacobi_cn(&n(-0.5), p, rm, &mut cc).unwrap_err(),
+            Error::InvalidArgument
+        );
+    }
+}
@@ -0,0 +1,504 @@
+//! Legendre polynomials and associated Legendre functions.
+
+use crate::common::consts::ONE;
+use crate::common::util::invert_rm_for_sign;
+use crate::common::util::log2_ceil;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::Sign;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Computes the Legendre polynomial P_n(x) of degree `n` at `self` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// The polynomial is evaluated with the three-term recurrence (k+1)P_(k+1)(x) = (2k+1)xP_k(x) - kP_(k-1)(x).
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn legendre(&self, n: usize, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        self.assoc_legendre(n, 0, p, rm)
+    }
+
+    /// Computes the associated Legendre function P_n^m(x) of degree `n` and order `m` at `self` with precision `p`.
+    /// The result is rounded using the rounding mode `rm`.
+    /// The function includes the Condon-Shortley phase (-1)^m, i.e. P_n^m(x) = (-1)^m (1-x^2)^(m/2) d^m/dx^m P_n(x).
+    /// The result is zero if `m` is larger than `n`.
+    /// The function is evaluated with the three-term recurrence over the degree starting from P_m^m(x).
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect, or `m` is not zero and `self` is outside of the interval [-1, 1].
+    pub fn assoc_legendre(
+        &self,
+        n: usize,
+        m: usize,
+        p: usize,
+        rm: RoundingMode,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        let c = self.abs_cmp(&ONE);
+
+        if m > 0 && c > 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        if m > n || (m > 0 && c == 0) || (self.is_zero() && (n - m) & 1 == 1) {
+            return Self::new2(p, Sign::Pos, self.inexact());
+        }
+
+        if n == 0 || c == 0 {
+            // P_n(1) = 1, P_n(-1) = (-1)^n
+            let mut ret = Self::from_word(1, p)?;
+            if self.is_negative() && n & 1 == 1 {
+                ret.set_sign(Sign::Neg);
+            }
+            ret.set_inexact(self.inexact());
+            return Ok(ret);
+        }
+
+        // The value of the polynomial part at a dyadic rational number is a dyadic rational number,
+        // which can be computed exactly. The size of the exact value is estimated by the number of bits
+        // between the least significant bit of x and max(1, |x|) for each power of x, and the size of the coefficients.
+        let d = self.mantissa().digits();
+        let tz = match d.iter().position(|&w| w != 0) {
+            Some(i) => i * WORD_BIT_SIZE + d[i].trailing_zeros() as usize,
+            None => self.mantissa_max_bit_len(),
+        };
+        let e = self.exponent() as isize;
+        let lsb = e - (self.mantissa_max_bit_len() - tz) as isize;
+        let w = (e.max(0) - lsb.min(0)) as usize;
+        let l = log2_ceil(n + 1);
+        let exact_bits = (n + m + 1).saturating_mul(2 * w + 2 * l + 4);
+
+        // x^2 is too small to affect the leading term of the Taylor series at zero,
+        // and the leading term is far enough from the numbers with precision p.
+        let tiny = (n + 4).saturating_mul(l).saturating_add(p + 4);
+        if e < -((tiny / 2) as isize) {
+            return self.assoc_legendre_tiny(n, m, p, rm);
+        }
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            if exact_bits <= 2 * p_wrk {
+                return self.assoc_legendre_exact(n, m, p, rm);
+            }
+
+            // the error of the recurrence is less than 2^(max_e + 2*l + 4 - p_x)
+            let p_x = p_wrk + 3 * l + 5;
+
+            let (r, max_e) = self.assoc_legendre_poly(n, m, p_x)?;
+
+            if !r.is_zero() {
+                // bits lost due to cancellation
+                let c = (max_e - r.exponent() as isize).max(0) as usize;
+
+                let mut ret = if m > 0 {
+                    let p_m = p_x + log2_ceil(m) + 4;
+                    let u = ONE
+                        .sub_full_prec(self)?
+                        .mul_full_prec(&ONE.add_full_prec(self)?)?;
+                    let mut t = u.powi(m / 2, p_m, RoundingMode::None)?;
+                    if m & 1 == 1 {
+                        t = t.mul(&u.sqrt(p_m, RoundingMode::None)?, p_m, RoundingMode::None)?;
+                        t.inv_sign();
+                    }
+                    r.mul(&t, p_x, RoundingMode::None)?
+                } else {
+                    r
+                };
+
+                let s = (p_x - 2 * l - 4).saturating_sub(c) / WORD_BIT_SIZE * WORD_BIT_SIZE;
+
+                if s > p && ret.try_set_precision(p, rm, s)? {
+                    ret.set_inexact(true);
+                    break Ok(ret);
+                }
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Computes P_n^m(x) for 0 < n, m <= n and tiny x using the leading term of the Taylor series at zero:
+    // P_n^m(0) = (-1)^((n+m)/2) (n+m-1)!! / (n-m)!! if n - m is even, and
+    // P_n^m(x) ~ (-1)^((n+m-1)/2) (n+m)!! / (n-m-1)!! x if n - m is odd.
+    // From the differential equation, the next term of the series has the opposite sign of the leading term,
+    // and it is smaller than (n+1)^2 x^2 times the leading term, unless P_1(x) = x.
+    fn assoc_legendre_tiny(
+        &self,
+        n: usize,
+        m: usize,
+        p: usize,
+        rm: RoundingMode,
+    ) -> Result<Self, Error> {
+        let odd = (n - m) & 1 == 1;
+
+        if odd && n == 1 {
+            let mut ret = self.clone()?;
+            ret.set_precision(p, rm)?;
+            return Ok(ret);
+        }
+
+        let (a, b, k) = if odd {
+            (n + m, n - m - 1, (n + m - 1) / 2)
+        } else {
+            (n + m - 1, n - m, (n + m) / 2)
+        };
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            // the error of less than n roundings
+            let p_x = p_wrk + log2_ceil(n + 1) + 4;
+
+            let mut num = Self::from_word(1, p_x)?;
+            let mut den = Self::from_word(1, p_x)?;
+
+            for i in (1..=a).rev().step_by(2) {
+                num = num.mul(&Self::from_usize(i)?, p_x, RoundingMode::None)?;
+            }
+
+            for i in (1..=b).rev().step_by(2) {
+                den = den.mul(&Self::from_usize(i)?, p_x, RoundingMode::None)?;
+            }
+
+            let mut ret = num.div(&den, p_x, RoundingMode::None)?;
+
+            if odd {
+                ret = ret.mul(self, p_x, RoundingMode::None)?;
+            }
+
+            if k & 1 == 1 {
+                ret.inv_sign();
+            }
+
+            if !ret.inexact() {
+                ret.set_precision(p_x + WORD_BIT_SIZE, RoundingMode::None)?;
+                ret = ret.add_correction(true)?;
+                ret.set_precision(p, rm)?;
+                ret.set_inexact(true);
+                return Ok(ret);
+            }
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                ret.set_inexact(true);
+                return Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Computes d^m/dx^m P_n(x) with precision `p` using the recurrence
+    // (k-m+1)R_(k+1) = (2k+1)xR_k - (k+m)R_(k-1), where R_m = (2m-1)!!, R_(m+1) = (2m+1)x(2m-1)!!.
+    // Returns the result and the maximum exponent of the terms of the recurrence.
+    fn assoc_legendre_poly(&self, n: usize, m: usize, p: usize) -> Result<(Self, isize), Error> {
+        let mut r0 = Self::from_word(1, p)?;
+        for k in 1..m {
+            r0 = r0.mul(&Self::from_usize(2 * k + 1)?, p, RoundingMode::None)?;
+        }
+
+        let mut max_e = r0.exponent() as isize;
+
+        if n == m {
+            return Ok((r0, max_e));
+        }
+
+        let mut r1 = self.mul(&r0, p, RoundingMode::None)?.mul(
+            &Self::from_usize(2 * m + 1)?,
+            p,
+            RoundingMode::None,
+        )?;
+
+        max_e = max_e.max(r1.exponent() as isize);
+
+        for k in m + 1..n {
+            let t1 = self.mul(&r1, p, RoundingMode::None)?.mul(
+                &Self::from_usize(2 * k + 1)?,
+                p,
+                RoundingMode::None,
+            )?;
+            let t2 = r0.mul(&Self::from_usize(k + m)?, p, RoundingMode::None)?;
+
+            max_e = max_e
+                .max(t1.exponent() as isize)
+                .max(t2.exponent() as isize);
+
+            let r2 = t1.sub(&t2, p, RoundingMode::None)?.div(
+                &Self::from_usize(k - m + 1)?,
+                p,
+                RoundingMode::None,
+            )?;
+
+            r0 = r1;
+            r1 = r2;
+        }
+
+        Ok((r1, max_e))
+    }
+
+    // Computes P_n^m(x) for 0 < n, m <= n exactly and rounds the result to precision `p` using rounding mode `rm`.
+    fn assoc_legendre_exact(
+        &self,
+        n: usize,
+        m: usize,
+        p: usize,
+        rm: RoundingMode,
+    ) -> Result<Self, Error> {
+        // S_k = (k-m)! R_k satisfies S_(k+1) = (2k+1)xS_k - (k+m)(k-m)S_(k-1) and does not require division.
+        let mut s0 = Self::from_word(1, WORD_BIT_SIZE)?;
+        for k in 1..m {
+            s0 = s0.mul_full_prec(&Self::from_usize(2 * k + 1)?)?;
+        }
+
+        let mut f = Self::from_word(1, WORD_BIT_SIZE)?;
+
+        if n > m {
+            let mut s1 = self
+                .mul_full_prec(&s0)?
+                .mul_full_prec(&Self::from_usize(2 * m + 1)?)?;
+
+            for k in m + 1..n {
+                let t1 = self
+                    .mul_full_prec(&s1)?
+                    .mul_full_prec(&Self::from_usize(2 * k + 1)?)?;
+                let t2 = s0
+                    .mul_full_prec(&Self::from_usize(k + m)?)?
+                    .mul_full_prec(&Self::from_usize(k - m)?)?;
+
+                let s2 = t1.sub_full_prec(&t2)?;
+
+                f = f.mul_full_prec(&Self::from_usize(k - m + 1)?)?;
+
+                s0 = s1;
+                s1 = s2;
+            }
+
+            s0 = s1;
+        }
+
+        // R_n = S_n / (n-m)! is a dyadic rational number which has fewer significant bits than S_n
+        let mut ret = if s0.is_zero() {
+            s0
+        } else {
+            s0.div(&f, s0.mantissa_max_bit_len(), RoundingMode::None)?
+        };
+
+        if m > 0 {
+            let u = ONE
+                .sub_full_prec(self)?
+                .mul_full_prec(&ONE.add_full_prec(self)?)?;
+
+            for _ in 0..m / 2 {
+                ret = ret.mul_full_prec(&u)?;
+            }
+
+            if m & 1 == 1 {
+                ret.inv_sign();
+
+                // R * sqrt(u) = sqrt(R^2 * u) with the sign of R
+                let s = ret.sign();
+                let t = ret.mul_full_prec(&ret)?.mul_full_prec(&u)?;
+
+                let rm = if s.is_negative() { invert_rm_for_sign(rm) } else { rm };
+
+                ret = t.sqrt(p, rm)?;
+                ret.set_sign(s);
+
+                return Ok(ret);
+            }
+        }
+
+        ret.set_precision(p, rm)?;
+
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_legendre() {
+        let rm = RoundingMode::ToEven;
+        let p = 128;
+
+        let n = |f: f64| BigFloatNumber::from_f64(p, f).unwrap();
+
+        // small degrees
+        let x = n(0.5);
+        assert!(x.legendre(0, p, rm).unwrap().cmp(&ONE) == 0);
+        assert!(x.legendre(1, p, rm).unwrap().cmp(&x) == 0);
+        let d = x.legendre(2, p, rm).unwrap();
+        assert!(d.cmp(&n(-0.125)) == 0);
+        assert!(!d.inexact());
+        let d = x.legendre(3, p, RoundingMode::Up).unwrap();
+        assert!(d.cmp(&n(-0.4375)) == 0);
+        assert!(!d.inexact());
+
+        // P_5(x) = (63x^5 - 70x^3 + 15x) / 8
+        let x = n(0.3);
+        let pe = 512;
+        let x2 = x.mul(&x, pe, rm).unwrap();
+        let d2 = BigFloatNumber::from_word(63, pe)
+            .unwrap()
+            .mul(&x2, pe, rm)
+            .unwrap()
+            .sub(&BigFloatNumber::from_word(70, pe).unwrap(), pe, rm)
+            .unwrap()
+            .mul(&x2, pe, rm)
+            .unwrap()
+            .add(&BigFloatNumber::from_word(15, pe).unwrap(), pe, rm)
+            .unwrap()
+            .mul(&x, pe, rm)
+            .unwrap()
+            .div_word(8, p, rm)
+            .unwrap();
+        let d1 = x.legendre(5, p, rm).unwrap();
+        assert!(d1.cmp(&d2) == 0, "{:?} {:?}", d1, d2);
+        assert!(d1.inexact());
+
+        // P_n(1) = 1, P_n(-1) = (-1)^n, P_n(0) = 0 for odd n
+        for deg in [1, 2, 17, 100] {
+            assert!(ONE.legendre(deg, p, rm).unwrap().cmp(&ONE) == 0);
+            let d = ONE.neg().unwrap().legendre(deg, p, rm).unwrap();
+            assert!(d.cmp(&ONE) == 0 || d.cmp(&ONE.neg().unwrap()) == 0);
+            assert_eq!(d.is_negative(), deg & 1 == 1);
+        }
+        assert!(BigFloatNumber::new(p)
+            .unwrap()
+            .legendre(7, p, rm)
+            .unwrap()
+            .is_zero());
+
+        // P_2(0) = -1/2
+        let d = BigFloatNumber::new(p).unwrap().legendre(2, p, rm).unwrap();
+        assert!(d.cmp(&n(-0.5)) == 0);
+
+        // tiny arguments: P_1(x) = x, P_2(x) ~ -1/2, P_5(x) ~ 15x/8, P_3^1(x) ~ 3/2, P_4^1(x) ~ 15x/2
+        let mut x = BigFloatNumber::from_word(3, p).unwrap();
+        for e in [-1000000, crate::defs::EXPONENT_MIN] {
+            x.set_exponent(e);
+            for x in [x.clone().unwrap(), x.neg().unwrap()] {
+                let d = x.legendre(1, p, rm).unwrap();
+                assert!(d.cmp(&x) == 0 && !d.inexact());
+
+                let d = x.legendre(2, p, rm).unwrap();
+                assert!(d.cmp(&n(-0.5)) == 0 && d.inexact());
+                let d = x.legendre(2, p, RoundingMode::Up).unwrap();
+                assert!(d.cmp(&n(-0.5)) > 0);
+
+                let r = x.mul(&n(1.875), p, rm).unwrap();
+                let d = x.legendre(5, p, rm).unwrap();
+                assert!(d.cmp(&r) == 0 && d.inexact());
+                let d = x.legendre(5, p, RoundingMode::ToZero).unwrap();
+                assert!(d.abs_cmp(&r) < 0);
+
+                let d = x.assoc_legendre(3, 1, p, rm).unwrap();
+                assert!(d.cmp(&n(1.5)) == 0);
+                let r = x.mul(&n(7.5), p, rm).unwrap();
+                let d = x.assoc_legendre(4, 1, p, rm).unwrap();
+                assert!(d.cmp(&r) == 0);
+            }
+        }
+
+        let mut x = BigFloatNumber::from_word(3, p).unwrap();
+        x.set_exponent(-300);
+        for (deg, ord) in [(5, 0), (6, 0), (7, 2), (8, 3), (9, 1)] {
+            for rm in [RoundingMode::ToEven, RoundingMode::Up, RoundingMode::Down] {
+                let d1 = x.assoc_legendre(deg, ord, p, rm).unwrap();
+                let d2 = x.assoc_legendre_exact(deg, ord, p, rm).unwrap();
+                assert!(d1.cmp(&d2) == 0, "{} {} {:?} {:?}", deg, ord, d1, d2);
+            }
+        }
+
+        // the approximation and the exact computation agree at high degree
+        for (x, deg) in [(0.7, 200), (-0.3, 333), (1.5, 50), (1e-5, 101)] {
+            let x = n(x);
+            for rm in [RoundingMode::ToEven, RoundingMode::Up, RoundingMode::Down] {
+                let d1 = x.assoc_legendre(deg, 0, p, rm).unwrap();
+                let d2 = x.assoc_legendre_exact(deg, 0, p, rm).unwrap();
+                assert!(d1.cmp(&d2) == 0, "{:?} {:?}", d1, d2);
+
+                let d1 = x.assoc_legendre(deg, 3, p, rm);
+                if x.abs_cmp(&ONE) > 0 {
+                    assert_eq!(d1.unwrap_err(), Error::InvalidArgument);
+                } else {
+                    let d2 = x.assoc_legendre_exact(deg, 3, p, rm).unwrap();
+                    assert!(d1.unwrap().cmp(&d2) == 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_assoc_legendre() {
+        let rm = RoundingMode::ToEven;
+        let p = 128;
+
+        let n = |f: f64| BigFloatNumber::from_f64(p, f).unwrap();
+        let x = n(0.5);
+
+        // P_1^1(x) = -sqrt(1 - x^2)
+        let d1 = x.assoc_legendre(1, 1, p, rm).unwrap();
+        let d2 = n(0.75).sqrt(p, rm).unwrap().neg().unwrap();
+        assert!(d1.cmp(&d2) == 0);
+
+        // P_2^2(x) = 3(1 - x^2)
+        let d1 = x.assoc_legendre(2, 2, p, rm).unwrap();
+        assert!(d1.cmp(&n(2.25)) == 0);
+        assert!(!d1.inexact());
+
+        // P_3^1(x) = -3/2 (5x^2 - 1) sqrt(1 - x^2)
+        let d1 = x.assoc_legendre(3, 1, p, RoundingMode::Down).unwrap();
+        let d2 = n(0.75)
+            .sqrt(p + 64, RoundingMode::None)
+            .unwrap()
+            .mul(&n(-0.375), p, RoundingMode::Down)
+            .unwrap();
+        assert!(d1.cmp(&d2) == 0);
+
+        // m > n, and |x| = 1
+        assert!(x.assoc_legendre(2, 3, p, rm).unwrap().is_zero());
+        assert!(ONE.assoc_legendre(5, 2, p, rm).unwrap().is_zero());
+        assert!(ONE.assoc_legendre(5, 0, p, rm).unwrap().cmp(&ONE) == 0);
+
+        // P_n^m(0) = 0 for odd n - m
+        assert!(BigFloatNumber::new(p)
+            .unwrap()
+            .assoc_legendre(8, 3, p, rm)
+            .unwrap()
+            .is_zero());
+
+        // high degree and order near the poles
+        let x = n(0.999);
+        for (deg, ord) in [(300, 150), (500, 499), (64, 64)] {
+            let d1 = x.assoc_legendre(deg, ord, p, rm).unwrap();
+            let d2 = x.assoc_legendre_exact(deg, ord, p, rm).unwrap();
+            assert!(d1.cmp(&d2) == 0, "{} {} {:?} {:?}", deg, ord, d1, d2);
+        }
+
+        assert_eq!(
+            n(1.5).assoc_legendre(3, 1, p, rm).unwrap_err(),
+            Error::InvalidArgument
+        );
+    }
+}
@@ -8,6 +8,7 @@ use crate::common::util::calc_mul_cost;
 use crate::common::util::calc_sqrt_cost;
 use crate::common::util::count_leading_ones;
 use crate::common::util::count_leading_zeroes_skip_first;
+use crate::common::util::log2_ceil;
 use crate::common::util::round_p;
 use crate::defs::Error;
 use crate::defs::RoundingMode;
@@ -62,6 +63,9 @@ impl PolycoeffGen for AtanhPolycoeffGen {
     }
 }
 
+// Precision starting from which the logarithm is computed using the arithmetic-geometric mean.
+const LN_AGM_THRESHOLD: usize = 4096;
+
 struct LnArgReductionEstimator {}
 
 impl ArgReductionEstimator for LnArgReductionEstimator {
@@ -85,7 +89,7 @@ impl ArgReductionEstimator for LnArgReductionEstimator {
 impl BigFloatNumber {
     /// Computes the natural logarithm of a number with precision `p`. The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -132,7 +136,7 @@ impl BigFloatNumber {
             let p_x = p_wrk + additional_prec;
             x.set_precision(p_x, RoundingMode::None)?;
 
-            let p1 = Self::ln_series(x, RoundingMode::None)?;
+            let p1 = Self::ln_mantissa(x, cc)?;
 
             let mut ret = if e == 0 {
                 p1
@@ -194,6 +198,65 @@ impl BigFloatNumber {
         Self::ln_arg_restore(ret, reduction_times + 1)
     }
 
+    // Computes ln(x), 0.5 <= x < 1, with the precision of x.
+    fn ln_mantissa(x: Self, cc: &mut Consts) -> Result<Self, Error> {
+        if x.mantissa_max_bit_len() >= LN_AGM_THRESHOLD {
+            Self::ln_agm(&x, cc)
+        } else {
+            Self::ln_series(x, RoundingMode::None)
+        }
+    }
+
+    // Computes ln(x), 0.5 <= x < 1, with the precision of x using the arithmetic-geometric mean:
+    // ln(s) = pi / (2 * AGM(1, 4/s)) with the error less than 4/s^2 * ln(s), where s = x * 2^k,
+    // and ln(x) = ln(s) - k * ln(2).
+    fn ln_agm(x: &Self, cc: &mut Consts) -> Result<Self, Error> {
+        let p = x.mantissa_max_bit_len();
+        let rm = RoundingMode::None;
+
+        // 4/s^2 * ln(s) < 2^(-p - 4)
+        let k = p / 2 + log2_ceil(p) + 8;
+
+        // AGM loses about log2(p) bits, and subtraction of k * ln(2) loses about log2(k) bits
+        let p_w = p + 2 * log2_ceil(p) + 16;
+
+        let mut a = Self::from_word(1, p_w)?;
+
+        // 4/s
+        let mut b = ONE.div(x, p_w, rm)?;
+        b.set_exponent(b.exponent() + 2 - k as Exponent);
+
+        loop {
+            let mut a1 = a.add(&b, p_w, rm)?;
+            a1.set_exponent(a1.exponent() - 1);
+
+            let d = a.sub(&b, p_w, rm)?;
+
+            // the error of (a + b) / 2 is less than (a - b)^2 / a
+            if d.is_zero()
+                || (d.exponent() as isize) < a.exponent() as isize - (p_w / 2 + 2) as isize
+            {
+                a = a1;
+                break;
+            }
+
+            b = a.mul(&b, p_w, rm)?.sqrt(p_w, rm)?;
+            a = a1;
+        }
+
+        let pi = cc.pi_num(p_w, rm)?;
+        let mut ret = pi.div(&a, p_w, rm)?;
+        ret.set_exponent(ret.exponent() - 1);
+
+        let ln2 = cc.ln_2_num(p_w, rm)?;
+        let kln2 = ln2.mul(&Self::from_usize(k)?, p_w, rm)?;
+
+        let mut ret = ret.sub(&kln2, p_w, rm)?;
+        ret.set_precision(p, rm)?;
+
+        Ok(ret)
+    }
+
     // reduce argument n times.
     fn ln_arg_reduce(mut x: Self, n: usize, _rm: RoundingMode) -> Result<Self, Error> {
         for _ in 0..n {
@@ -212,7 +275,7 @@ impl BigFloatNumber {
 
     /// Computes the logarithm base 2 of a number with precision `p`. The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -265,7 +328,7 @@ impl BigFloatNumber {
             let p_x = p_wrk + additional_prec;
             x.set_precision(p_x, RoundingMode::None)?;
 
-            let p1 = Self::ln_series(x, RoundingMode::None)?;
+            let p1 = Self::ln_mantissa(x, cc)?;
 
             let p2 = cc.ln_2_num(p_x, RoundingMode::None)?;
 
@@ -289,7 +352,7 @@ impl BigFloatNumber {
 
     /// Computes the logarithm base 10 of a number with precision `p`. The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -323,6 +386,7 @@ impl BigFloatNumber {
 
                 if tp.cmp(&x) == 0 {
                     ret.set_precision(p, rm)?;
+                    ret.set_inexact(self.inexact());
                     return Ok(ret);
                 }
             }
@@ -338,7 +402,7 @@ impl BigFloatNumber {
 
     /// Computes the logarithm base `n` of a number with precision `p`. The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -358,6 +422,19 @@ impl BigFloatNumber {
             return Err(Error::InvalidArgument);
         }
 
+        // log_n(self) = k1 / k2, if self = 2^k1, and n = 2^k2
+        if let (Some(k1), Some(k2)) = (self.exact_log2()?, n.exact_log2()?) {
+            if k2 == 0 {
+                return Err(if k1 == 0 { Error::InvalidArgument } else { Error::DivisionByZero });
+            }
+
+            let mut ret =
+                Self::from_i64(k1 as i64, 64)?.div(&Self::from_i64(k2 as i64, 64)?, p, rm)?;
+            ret.set_inexact(ret.inexact() || self.inexact() || n.inexact());
+
+            return Ok(ret);
+        }
+
         // ln(self) / ln(n)
 
         let mut p_inc = WORD_BIT_SIZE;
@@ -406,7 +483,7 @@ mod tests {
 
     use crate::{
         common::{
-            consts::TEN,
+            consts::{TEN, THREE},
             util::{log2_ceil, random_subnormal},
         },
         WORD_BIT_SIZE,
@@ -414,6 +491,9 @@ mod tests {
 
     use super::*;
 
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
     #[cfg(not(feature = "std"))]
     use alloc::vec::Vec;
 
@@ -608,6 +688,18 @@ mod tests {
         assert!(d1.log(&d2, prec, rm, &mut cc).is_ok());
         assert!(d2.log(&d1, prec, rm, &mut cc).is_ok());
 
+        // powers of two
+        let d1 = BigFloatNumber::from_word(8, p).unwrap();
+        let d2 = BigFloatNumber::from_word(4, p).unwrap();
+        let d3 = d1.log(&d2, p, rm, &mut cc).unwrap();
+        assert!(d3.cmp(&BigFloatNumber::from_f64(p, 1.5).unwrap()) == 0);
+        assert!(!d3.inexact());
+
+        let d3 = TWO.log(&d1, p, RoundingMode::Up, &mut cc).unwrap();
+        let d4 = ONE.div(&THREE, p, RoundingMode::Up).unwrap();
+        assert!(d3.cmp(&d4) == 0);
+        assert!(d3.inexact());
+
         // base close to 0, 1, or a large value
         let mut nums = Vec::new();
         for s in [
@@ -775,6 +867,62 @@ mod tests {
         assert!(d1.cmp(&refn) == 0);
     }
 
+    #[test]
+    fn test_ln_agm() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+
+        // ln(x) computed with the series at higher precision
+        fn ln_ref(x: &BigFloatNumber, p: usize, cc: &mut Consts) -> BigFloatNumber {
+            let p_x = p + 512;
+
+            let mut m = x.clone().unwrap();
+            m.set_exponent(0);
+            m.set_precision(p_x, RoundingMode::None).unwrap();
+
+            let mut n = BigFloatNumber::from_usize(x.exponent().unsigned_abs() as usize).unwrap();
+            if x.exponent() < 0 {
+                n.set_sign(Sign::Neg);
+            }
+
+            let ln2n = cc
+                .ln_2_num(p_x, RoundingMode::None)
+                .unwrap()
+                .mul(&n, p_x, RoundingMode::None)
+                .unwrap();
+
+            let mut ret = BigFloatNumber::ln_series(m, RoundingMode::None)
+                .unwrap()
+                .add(&ln2n, p_x, RoundingMode::None)
+                .unwrap();
+
+            ret.set_precision(p, RoundingMode::ToEven).unwrap();
+            ret
+        }
+
+        let p = LN_AGM_THRESHOLD + 2 * WORD_BIT_SIZE;
+
+        let mut near_one = ONE.clone().unwrap();
+        near_one.set_exponent(-300);
+        let above_one = ONE.add(&near_one, p, rm).unwrap();
+        let below_one = ONE.sub(&near_one, p, rm).unwrap();
+
+        let mut xs = vec![above_one, below_one, BigFloatNumber::max_value(p).unwrap()];
+
+        for _ in 0..5 {
+            let mut x = BigFloatNumber::random_normal(p, -100, 100).unwrap();
+            x.set_sign(Sign::Pos);
+            xs.push(x);
+        }
+
+        for x in xs.iter() {
+            let d1 = x.ln(p, rm, &mut cc).unwrap();
+            let d2 = ln_ref(x, p, &mut cc);
+
+            assert!(d1.cmp(&d2) == 0);
+        }
+    }
+
     #[ignore]
     #[test]
     #[cfg(feature = "std")]
@@ -797,4 +945,34 @@ mod tests {
             println!("{}", time.as_millis());
         }
     }
+
+    #[ignore]
+    #[test]
+    #[cfg(feature = "std")]
+    fn ln_agm_perf() {
+        let mut cc = Consts::new().unwrap();
+
+        for p in [1024, 4096, 16384, 65536] {
+            let mut x = BigFloatNumber::random_normal(p, 0, 0).unwrap();
+            x.set_sign(Sign::Pos);
+
+            // warm up the constants cache
+            let _ = BigFloatNumber::ln_agm(&x, &mut cc).unwrap();
+
+            let start_time = std::time::Instant::now();
+            let _ = BigFloatNumber::ln_series(x.clone().unwrap(), RoundingMode::None).unwrap();
+            let series_time = start_time.elapsed();
+
+            let start_time = std::time::Instant::now();
+            let _ = BigFloatNumber::ln_agm(&x, &mut cc).unwrap();
+            let agm_time = start_time.elapsed();
+
+            println!(
+                "{} bits: series {} us, agm {} us",
+                p,
+                series_time.as_micros(),
+                agm_time.as_micros()
+            );
+        }
+    }
 }
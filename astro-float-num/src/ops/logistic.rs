@@ -0,0 +1,442 @@
+//! Logistic function, softplus, and log-sum-exp.
+
+use crate::common::consts::ONE;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::Consts;
+use crate::Sign;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Computes the logistic function 1 / (1 + e^(-x)) of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn sigmoid(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+
+        if self.is_zero() {
+            let mut ret = Self::from_word(1, p)?;
+            ret.set_exponent(0);
+            ret.set_inexact(self.inexact());
+            return Ok(ret);
+        }
+
+        // 1/2 + x/4, where x/4 is too small
+        if (self.exponent() as isize) < -(p as isize) - 2 {
+            let mut ret = Self::from_word(1, p)?;
+            ret.set_exponent(0);
+            ret = ret.add_correction(self.is_negative())?;
+            ret.set_precision(p, rm)?;
+            return Ok(ret);
+        }
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + 8;
+
+            // t = e^(-|x|), then the result is 1 / (1 + t) for positive x, and t / (1 + t) for negative x.
+            let t = self.abs()?.neg()?.exp(p_x, RoundingMode::None, cc)?;
+
+            if t.is_zero() {
+                return if self.is_positive() {
+                    let mut ret = Self::from_word(1, p)?.add_correction(true)?;
+                    ret.set_precision(p, rm)?;
+                    Ok(ret)
+                } else {
+                    Self::new2(p, Sign::Pos, true)
+                };
+            }
+
+            // 1 / (1 + t) = 1 - t + t^2 - ..., where t is too small
+            if self.is_positive() && (t.exponent() as isize) < -(p as isize) - 2 {
+                let mut ret = Self::from_word(1, p)?.add_correction(true)?;
+                ret.set_precision(p, rm)?;
+                return Ok(ret);
+            }
+
+            let d = Self::one_plus(&t, p_x)?;
+
+            let mut ret = if self.is_positive() {
+                ONE.div(&d, p_x, RoundingMode::None)
+            } else {
+                t.div(&d, p_x, RoundingMode::None)
+            }?;
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    /// Computes the softplus function ln(1 + e^x) of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn softplus(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + 8;
+
+            // t = e^(-|x|), then the result is x + ln(1 + t) for positive x, and ln(1 + t) for negative x.
+            let t = self.abs()?.neg()?.exp(p_x, RoundingMode::None, cc)?;
+
+            if t.is_zero() {
+                return if self.is_negative() {
+                    Self::new2(p, Sign::Pos, true)
+                } else {
+                    self.add_small_correction(p, rm)
+                };
+            }
+
+            let l = Self::ln_1p(&t, p_x, cc)?;
+
+            let mut ret = if self.is_negative() {
+                l
+            } else if !self.is_zero()
+                && (l.exponent() as isize)
+                    < self.exponent() as isize - p_x as isize - WORD_BIT_SIZE as isize
+            {
+                return self.add_small_correction(p, rm);
+            } else {
+                self.add(&l, p_x, RoundingMode::None)?
+            };
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    /// Computes ln(e^x1 + e^x2 + ... + e^xn) for the numbers `xs` with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The maximum of the numbers is subtracted before exponentiation, so the function does not overflow for large arguments.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: `xs` is empty, or the precision is incorrect.
+    pub fn log_sum_exp(
+        xs: &[&Self],
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if xs.is_empty() {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut i_max = 0;
+        for (i, x) in xs.iter().enumerate().skip(1) {
+            if x.cmp(xs[i_max]) > 0 {
+                i_max = i;
+            }
+        }
+        let m = xs[i_max];
+
+        if xs.len() == 1 {
+            let mut ret = m.clone()?;
+            ret.set_precision(p, rm)?;
+            return Ok(ret);
+        }
+
+        let n_bits = (usize::BITS - xs.len().leading_zeros()) as usize;
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + n_bits + 8;
+
+            // result = m + ln(1 + u), where u is the sum of e^(x - m) for all x except the maximum
+            let mut u = Self::new(p_x)?;
+            for (i, x) in xs.iter().enumerate() {
+                if i != i_max {
+                    let t = x
+                        .sub(m, p_x, RoundingMode::None)?
+                        .exp(p_x, RoundingMode::None, cc)?;
+                    u = u.add(&t, p_x, RoundingMode::None)?;
+                }
+            }
+
+            if u.is_zero() {
+                if m.is_zero() {
+                    return Self::new2(p, Sign::Pos, true);
+                }
+
+                return m.add_small_correction(p, rm);
+            }
+
+            let l = Self::ln_1p(&u, p_x, cc)?;
+
+            if !m.is_zero()
+                && (l.exponent() as isize)
+                    < m.exponent() as isize - p_x as isize - WORD_BIT_SIZE as isize
+            {
+                return m.add_small_correction(p, rm);
+            }
+
+            let mut ret = m.add(&l, p_x, RoundingMode::None)?;
+
+            // bits lost due to cancellation
+            let c = if ret.is_zero() {
+                p_x
+            } else {
+                (m.exponent().max(l.exponent()) as isize - ret.exponent() as isize).max(0) as usize
+            };
+
+            let s = p_wrk.saturating_sub(c) / WORD_BIT_SIZE * WORD_BIT_SIZE;
+
+            if s > p && ret.try_set_precision(p, rm, s)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    /// Computes ln(e^`self` + e^`d2`) with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The larger of the numbers is subtracted before exponentiation, so the function does not overflow for large arguments.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large.
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn log_add_exp(
+        &self,
+        d2: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        Self::log_sum_exp(&[self, d2], p, rm, cc)
+    }
+
+    // Returns 1 + t for 0 < t <= 1 with precision `p`.
+    // If t is too small, the result is 1 plus a positive value smaller than t.
+    fn one_plus(t: &Self, p: usize) -> Result<Self, Error> {
+        if (t.exponent() as isize) < -(p as isize) {
+            Self::from_word(1, p)?.add_correction(false)
+        } else {
+            ONE.add(t, p, RoundingMode::None)
+        }
+    }
+
+    // Returns non-zero `self` plus a positive value which is too small to be represented with precision `p`,
+    // rounded using the rounding mode `rm`.
+    fn add_small_correction(&self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let mut x = self.clone()?;
+        x.set_precision(
+            x.mantissa_max_bit_len().max(p) + WORD_BIT_SIZE,
+            RoundingMode::None,
+        )?;
+        let mut ret = x.add_correction(self.is_negative())?;
+        ret.set_precision(p, rm)?;
+        ret.set_inexact(true);
+        Ok(ret)
+    }
+
+    // Computes ln(1 + t) for positive t with precision `p` and the relative error of a few units in the last place.
+    fn ln_1p(t: &Self, p: usize, cc: &mut Consts) -> Result<Self, Error> {
+        let e = t.exponent() as isize;
+
+        if e < -(p as isize) - 2 {
+            // ln(1 + t) = t - t^2/2 + ..., where t^2/2 is too small
+            let mut x = t.clone()?;
+            x.set_precision(p, RoundingMode::None)?;
+            x.add_correction(true)
+        } else if e < -(p as isize) / 2 - 2 {
+            // t^3/3 is too small
+            let mut t2 = t.mul(t, p, RoundingMode::None)?;
+            t2.set_exponent(t2.exponent() - 1);
+            t.sub(&t2, p, RoundingMode::None)
+        } else {
+            // 1 + t with the absolute error less than 2^(e - p)
+            let d = ONE.add(t, p + (-e).max(0) as usize, RoundingMode::None)?;
+            d.ln(p, RoundingMode::None, cc)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Exponent;
+
+    #[test]
+    fn test_logistic() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+
+        for p in [64, 128, 320] {
+            let pe = p + 256;
+
+            for x in [0.5, -0.5, 3.0, -3.0, 40.0, -40.0, 1e-10, -1e-10, 1000.0, -1000.0] {
+                let x = BigFloatNumber::from_f64(p, x).unwrap();
+
+                // sigmoid
+                let d1 = x.sigmoid(p, rm, &mut cc).unwrap();
+                let t = x
+                    .neg()
+                    .unwrap()
+                    .exp(pe, RoundingMode::None, &mut cc)
+                    .unwrap();
+                let d2 = ONE
+                    .div(&ONE.add(&t, pe, RoundingMode::None).unwrap(), p, rm)
+                    .unwrap();
+                assert!(d1.cmp(&d2) == 0, "{:?} {:?}", d1, d2);
+
+                // softplus
+                let d1 = x.softplus(p, rm, &mut cc).unwrap();
+                let t = x.exp(pe + 1500, RoundingMode::None, &mut cc).unwrap();
+                let d2 = ONE
+                    .add(&t, pe + 1500, RoundingMode::None)
+                    .unwrap()
+                    .ln(p, rm, &mut cc)
+                    .unwrap();
+                assert!(d1.cmp(&d2) == 0, "{:?} {:?}", d1, d2);
+            }
+
+            // sigmoid of large arguments
+            let x = BigFloatNumber::from_f64(p, 123456.789).unwrap();
+            let d1 = x.sigmoid(p, rm, &mut cc).unwrap();
+            assert!(d1.cmp(&ONE) == 0);
+            assert!(d1.inexact());
+            let d1 = x.sigmoid(p, RoundingMode::Down, &mut cc).unwrap();
+            assert!(d1.cmp(&ONE) < 0);
+            let mut ulp = BigFloatNumber::from_word(1, p).unwrap();
+            ulp.set_exponent(1 - p as Exponent);
+            assert!(d1.add(&ulp, p, RoundingMode::None).unwrap().cmp(&ONE) == 0);
+            let x = x.neg().unwrap();
+            let d1 = x.sigmoid(p, rm, &mut cc).unwrap();
+            let d2 = x.exp(p, rm, &mut cc).unwrap();
+            assert!(d1.cmp(&d2) == 0);
+
+            // log-sum-exp
+            let xs = [1.0, 2.0, -3.5, 0.25].map(|v| BigFloatNumber::from_f64(p, v).unwrap());
+            let refs: [&BigFloatNumber; 4] = [&xs[0], &xs[1], &xs[2], &xs[3]];
+            let d1 = BigFloatNumber::log_sum_exp(&refs, p, rm, &mut cc).unwrap();
+            let mut s = BigFloatNumber::new(pe).unwrap();
+            for x in xs.iter() {
+                s = s
+                    .add(
+                        &x.exp(pe, RoundingMode::None, &mut cc).unwrap(),
+                        pe,
+                        RoundingMode::None,
+                    )
+                    .unwrap();
+            }
+            let d2 = s.ln(p, rm, &mut cc).unwrap();
+            assert!(d1.cmp(&d2) == 0, "{:?} {:?}", d1, d2);
+
+            // large arguments don't overflow
+            let big = BigFloatNumber::from_f64(p, 1e10).unwrap();
+            let d1 = BigFloatNumber::log_sum_exp(&[&big, &big], p, rm, &mut cc).unwrap();
+            let d2 = big
+                .add(&cc.ln_2_num(p + 128, RoundingMode::None).unwrap(), p, rm)
+                .unwrap();
+            assert!(d1.cmp(&d2) == 0);
+
+            // single value
+            let d1 = BigFloatNumber::log_sum_exp(&[&xs[2]], p, rm, &mut cc).unwrap();
+            assert!(d1.cmp(&xs[2]) == 0);
+
+            // two values
+            let d1 = xs[1].log_add_exp(&xs[2], p, rm, &mut cc).unwrap();
+            let d2 = xs[2].log_add_exp(&xs[1], p, rm, &mut cc).unwrap();
+            let t = xs[2]
+                .sub(&xs[1], pe, RoundingMode::None)
+                .unwrap()
+                .exp(pe, RoundingMode::None, &mut cc)
+                .unwrap();
+            let d3 = xs[1]
+                .add(
+                    &ONE.add(&t, pe, RoundingMode::None)
+                        .unwrap()
+                        .ln(pe, RoundingMode::None, &mut cc)
+                        .unwrap(),
+                    p,
+                    rm,
+                )
+                .unwrap();
+            assert!(d1.cmp(&d2) == 0);
+            assert!(d1.cmp(&d3) == 0, "{:?} {:?}", d1, d3);
+        }
+
+        // extreme values
+        let p = 128;
+        let one = BigFloatNumber::from_word(1, p).unwrap();
+        let mut x = BigFloatNumber::from_word(1, p).unwrap();
+        x.set_exponent(100);
+        assert!(x.sigmoid(p, rm, &mut cc).unwrap().cmp(&one) == 0);
+        let d1 = x.sigmoid(p, RoundingMode::Down, &mut cc).unwrap();
+        assert!(d1.cmp(&one) < 0);
+        assert!(x.neg().unwrap().sigmoid(p, rm, &mut cc).unwrap().is_zero());
+        assert!(x.softplus(p, rm, &mut cc).unwrap().cmp(&x) == 0);
+        assert!(x.softplus(p, RoundingMode::Up, &mut cc).unwrap().cmp(&x) > 0);
+        assert!(x.neg().unwrap().softplus(p, rm, &mut cc).unwrap().is_zero());
+
+        let mut x = BigFloatNumber::from_word(1, p).unwrap();
+        x.set_exponent(-1000);
+        let mut half = one.clone().unwrap();
+        half.set_exponent(0);
+        assert!(x.sigmoid(p, rm, &mut cc).unwrap().cmp(&half) == 0);
+        assert!(x.sigmoid(p, RoundingMode::Up, &mut cc).unwrap().cmp(&half) > 0);
+        assert!(
+            x.neg()
+                .unwrap()
+                .sigmoid(p, RoundingMode::Down, &mut cc)
+                .unwrap()
+                .cmp(&half)
+                < 0
+        );
+
+        let zero = BigFloatNumber::new(p).unwrap();
+        let d1 = BigFloatNumber::log_sum_exp(&[&zero, &x.neg().unwrap()], p, rm, &mut cc).unwrap();
+        assert!(
+            d1.cmp(
+                &one.add(&x.neg().unwrap().exp(p, rm, &mut cc).unwrap(), p, rm)
+                    .unwrap()
+                    .ln(p, rm, &mut cc)
+                    .unwrap()
+            ) == 0
+        );
+
+        assert!(
+            BigFloatNumber::log_sum_exp(&[], p, rm, &mut cc).unwrap_err() == Error::InvalidArgument
+        );
+    }
+}
@@ -6,13 +6,30 @@ mod asin;
 mod asinh;
 mod atan;
 mod atanh;
+mod bessel;
 mod cbrt;
+mod compound;
 pub mod consts;
 mod cos;
 mod cosh;
+mod deg;
+mod erf;
+mod expm1;
+mod gamma;
+mod harmonic;
+mod hypot;
+mod invpi;
+mod jacobi;
+mod legendre;
 mod log;
+mod logistic;
+mod normal;
 mod pow;
-mod series;
+mod quadrant;
+mod rational;
+mod rem2pi;
+mod rootn;
+pub(crate) mod series;
 mod sin;
 mod sinh;
 mod sqrt;
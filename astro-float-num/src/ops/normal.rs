@@ -0,0 +1,348 @@
+//! Cumulative distribution function of the standard normal distribution and its inverse.
+
+use crate::common::consts::ONE;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::Consts;
+use crate::Sign;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Computes the cumulative distribution function of the standard normal distribution (1 + erf(x / sqrt(2))) / 2
+    /// of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The tail probabilities are computed with erfc, so small results keep their relative precision.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn norm_cdf(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        let mut half = Self::from_word(1, p)?;
+        half.set_exponent(0);
+
+        if self.is_zero() {
+            half.set_inexact(self.inexact());
+            return Ok(half);
+        }
+
+        // 1/2 + x/sqrt(2*pi), where x/sqrt(2*pi) is too small
+        if (self.exponent() as isize) < -(p as isize) - 2 {
+            let mut ret = half.add_correction(self.is_negative())?;
+            ret.set_precision(p, rm)?;
+            return Ok(ret);
+        }
+
+        // e^(-x^2/2) is too small to be represented
+        let underflow = self.exponent() > 32;
+
+        let y2 = if underflow {
+            Self::new(WORD_BIT_SIZE)?
+        } else {
+            let mut y2 = self.mul_full_prec(self)?;
+            y2.set_exponent(y2.exponent() - 1);
+            y2
+        };
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + 8;
+
+            // tail probability t = erfc(|x| / sqrt(2)) / 2
+            let t = if underflow {
+                Self::new(p_x)?
+            } else {
+                let mut t = Self::erfc_pos(&y2, p_x, cc)?;
+                if !t.is_zero() {
+                    t.set_exponent(t.exponent() - 1);
+                }
+                t
+            };
+
+            let mut ret = if self.is_negative() {
+                if t.is_zero() {
+                    return Self::new2(p, Sign::Pos, true);
+                }
+
+                t
+            } else {
+                if t.is_zero() || (t.exponent() as isize) < -(p_x as isize) {
+                    let mut ret = Self::from_word(1, p)?.add_correction(true)?;
+                    ret.set_precision(p, rm)?;
+                    return Ok(ret);
+                }
+
+                ONE.sub(&t, p_x, RoundingMode::None)?
+            };
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    /// Computes the inverse of the cumulative distribution function of the standard normal distribution (probit)
+    /// of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// The result is refined with Newton's method, so probabilities close to 0 or 1 are inverted with full precision.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the argument is not in the interval (0, 1), or the precision is incorrect.
+    pub fn norm_cdf_inv(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if self.is_zero() || self.is_negative() || self.cmp(&ONE) >= 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut half = Self::from_word(1, WORD_BIT_SIZE)?;
+        half.set_exponent(0);
+
+        if self.cmp(&half) == 0 {
+            return Self::new2(p, Sign::Pos, self.inexact());
+        }
+
+        // the result is -z for q < 1/2, and z for q > 1/2, where z > 0 is the root of erfc(z / sqrt(2)) / 2 = r,
+        // r = min(q, 1 - q), h = 1/2 - r
+        let is_neg = self.cmp(&half) < 0;
+        let r = if is_neg { self.clone()? } else { ONE.sub_full_prec(self)? };
+        let h = half.sub_full_prec(&r)?;
+
+        let mut z = Self::norm_cdf_inv_estimate(&r, &h, cc)?;
+        let mut p_z = WORD_BIT_SIZE;
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            let p_x = p_wrk + WORD_BIT_SIZE;
+
+            loop {
+                p_z = (p_z * 2).min(p_x);
+
+                let d = Self::norm_cdf_inv_step(&z, &r, &h, p_z, cc)?;
+                z = z.add(&d, p_z, RoundingMode::None)?;
+
+                // the error of z after the step is about z*d^2
+                if p_z == p_x
+                    && (d.is_zero()
+                        || (d.exponent() as isize)
+                            < z.exponent() as isize
+                                - (p_x / 2) as isize
+                                - (z.exponent() as isize).max(0)
+                                - 4)
+                {
+                    break;
+                }
+            }
+
+            let mut ret = z.clone()?;
+            if is_neg {
+                ret.inv_sign();
+            }
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                break Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Returns an estimate of the root z of erfc(z / sqrt(2)) / 2 = r with the absolute error less than 0.001.
+    fn norm_cdf_inv_estimate(r: &Self, h: &Self, cc: &mut Consts) -> Result<Self, Error> {
+        let p = 64;
+
+        if (h.exponent() as isize) > -10 {
+            // Abramowitz and Stegun, 26.2.23
+            let t = r
+                .ln(p, RoundingMode::None, cc)?
+                .neg()?
+                .mul_word(2, p, RoundingMode::None)?
+                .sqrt(p, RoundingMode::None)?
+                .to_f64();
+
+            let z = t
+                - (2.515517 + 0.802853 * t + 0.010328 * t * t)
+                    / (1.0 + 1.432788 * t + 0.189269 * t * t + 0.001308 * t * t * t);
+
+            if z > 0.0 {
+                return Self::from_f64(p, z);
+            }
+        }
+
+        // r is close to 1/2, and z = h * sqrt(2*pi) + O(h^3)
+        h.mul(
+            &Self::from_f64(p, 2.5066282746310002)?,
+            p,
+            RoundingMode::None,
+        )
+    }
+
+    // Returns the Newton's correction for the root z of erfc(z / sqrt(2)) / 2 = r.
+    fn norm_cdf_inv_step(
+        z: &Self,
+        r: &Self,
+        h: &Self,
+        p: usize,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let mut y2 = z.mul_full_prec(z)?;
+        y2.set_exponent(y2.exponent() - 1);
+
+        // density e^(-z^2/2) / sqrt(2*pi)
+        let mut two_pi = cc.pi_num(p, RoundingMode::None)?;
+        two_pi.set_exponent(two_pi.exponent() + 1);
+        let f = y2.neg()?.exp(p, RoundingMode::None, cc)?.div(
+            &two_pi.sqrt(p, RoundingMode::None)?,
+            p,
+            RoundingMode::None,
+        )?;
+
+        if z.exponent() <= 0 {
+            // z < 1: the step is (h - erf(z / sqrt(2)) / 2) / f
+            let mut e = Self::erf_pos(&y2, p, cc)?;
+            e.set_exponent(e.exponent() - 1);
+
+            h.sub(&e, p, RoundingMode::None)?
+                .div(&f, p, RoundingMode::None)
+        } else {
+            // z >= 1: Newton's method for ln(erfc(z / sqrt(2)) / 2) = ln(r),
+            // the step is ln(g / r) * g / f, where g = erfc(z / sqrt(2)) / 2
+            let mut g = Self::erfc_pos(&y2, p, cc)?;
+            g.set_exponent(g.exponent() - 1);
+
+            g.div(r, p, RoundingMode::None)?
+                .ln(p, RoundingMode::None, cc)?
+                .mul(&g, p, RoundingMode::None)?
+                .div(&f, p, RoundingMode::None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_norm_cdf() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+
+        for p in [64, 128, 320] {
+            for x in [0.5, -0.5, 1.0, 2.0, -3.0, 7.5, -7.5, 40.0, -40.0, 1e-10, -1e-10] {
+                let x = BigFloatNumber::from_f64(p, x).unwrap();
+
+                // q = norm_cdf(x) and the inverse gives x back
+                let q = x.norm_cdf(p + 128, rm, &mut cc).unwrap();
+                if q.cmp(&ONE) < 0 {
+                    let x1 = q.norm_cdf_inv(p, rm, &mut cc).unwrap();
+                    assert!(x1.cmp(&x) == 0, "{:?} {:?}", x, x1);
+                }
+
+                // symmetry
+                let q1 = x.neg().unwrap().norm_cdf(p + 128, rm, &mut cc).unwrap();
+                let s = q.add(&q1, p, rm).unwrap();
+                assert!(s.cmp(&ONE) == 0);
+
+                // rounding to p of the more precise value
+                let mut q2 = q.clone().unwrap();
+                q2.set_precision(p, rm).unwrap();
+                let q3 = x.norm_cdf(p, rm, &mut cc).unwrap();
+                assert!(q2.cmp(&q3) == 0);
+            }
+        }
+
+        let p = 256;
+
+        // known values
+        let x = BigFloatNumber::from_word(1, p).unwrap();
+        let q = x.norm_cdf(p, rm, &mut cc).unwrap();
+        let q0 = BigFloatNumber::parse(
+            "8.41344746068542948585232545632037922477912966726604390987394e-1",
+            crate::Radix::Dec,
+            p,
+            RoundingMode::None,
+        )
+        .unwrap();
+        let mut eps = ONE.clone().unwrap();
+        eps.set_exponent(-160);
+        assert!(q.sub(&q0, p, rm).unwrap().abs_cmp(&eps) < 0);
+
+        // deep tail
+        let x = BigFloatNumber::from_f64(p, -40.0).unwrap();
+        let q = x.norm_cdf(p, rm, &mut cc).unwrap();
+        let q0 = BigFloatNumber::parse(
+            "3.65589354091502970374898580268828366505394461997737262498776e-350",
+            crate::Radix::Dec,
+            p,
+            RoundingMode::None,
+        )
+        .unwrap();
+        let mut eps = q0.clone().unwrap();
+        eps.set_exponent(eps.exponent() - 160);
+        assert!(q.sub(&q0, p, rm).unwrap().abs_cmp(&eps) < 0);
+
+        let mut x = BigFloatNumber::from_word(1, p).unwrap();
+        x.set_exponent(40);
+        assert!(x.neg().unwrap().norm_cdf(p, rm, &mut cc).unwrap().is_zero());
+        assert!(x.norm_cdf(p, rm, &mut cc).unwrap().cmp(&ONE) == 0);
+        assert!(
+            x.norm_cdf(p, RoundingMode::Down, &mut cc)
+                .unwrap()
+                .cmp(&ONE)
+                < 0
+        );
+
+        // tiny argument
+        let mut x = BigFloatNumber::from_word(1, p).unwrap();
+        x.set_exponent(-1000);
+        let mut half = ONE.clone().unwrap();
+        half.set_exponent(0);
+        assert!(x.norm_cdf(p, rm, &mut cc).unwrap().cmp(&half) == 0);
+        assert!(x.norm_cdf(p, RoundingMode::Up, &mut cc).unwrap().cmp(&half) > 0);
+        let x = x.neg().unwrap();
+        assert!(
+            x.norm_cdf(p, RoundingMode::Down, &mut cc)
+                .unwrap()
+                .cmp(&half)
+                < 0
+        );
+
+        // inverse
+        assert!(half.norm_cdf_inv(p, rm, &mut cc).unwrap().is_zero());
+        assert!(
+            BigFloatNumber::new(p)
+                .unwrap()
+                .norm_cdf_inv(p, rm, &mut cc)
+                .unwrap_err()
+                == Error::InvalidArgument
+        );
+        assert!(ONE.norm_cdf_inv(p, rm, &mut cc).unwrap_err() == Error::InvalidArgument);
+
+        let mut q = BigFloatNumber::from_word(1, p).unwrap();
+        q.set_exponent(-3000);
+        let x = q.norm_cdf_inv(p, rm, &mut cc).unwrap();
+        let q1 = x.norm_cdf(p, rm, &mut cc).unwrap();
+        let mut eps = q.clone().unwrap();
+        eps.set_exponent(eps.exponent() - 200);
+        assert!(q1.sub(&q, p, rm).unwrap().abs_cmp(&eps) < 0);
+    }
+}
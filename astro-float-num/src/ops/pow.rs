@@ -1,9 +1,11 @@
 //! Exponentiation.
 
 use crate::common::consts::{FOUR, THREE};
-use crate::common::util::{calc_add_cost, calc_mul_cost, round_p};
+use crate::common::util::{calc_add_cost, calc_mul_cost, invert_rm_for_sign, round_p};
 use crate::ops::consts::Consts;
 use crate::ops::util::compute_small_exp;
+use crate::Exponent;
+use crate::EXPONENT_MAX;
 use crate::EXPONENT_MIN;
 use crate::{
     common::consts::ONE,
@@ -12,6 +14,9 @@ use crate::{
     RoundingMode, Sign,
 };
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 use super::series::{series_cost_optimize, series_run, ArgReductionEstimator, PolycoeffGen};
 
 // Polynomial coefficient generator.
@@ -85,7 +90,7 @@ impl ArgReductionEstimator for SinhArgReductionEstimator {
 impl BigFloatNumber {
     /// Computes `e` to the power of `self` with precision `p`. The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -119,6 +124,7 @@ impl BigFloatNumber {
                         Error::DivisionByZero => Err(Error::DivisionByZero),
                         Error::InvalidArgument => Err(Error::InvalidArgument),
                         Error::MemoryAllocation => Err(Error::MemoryAllocation),
+                        Error::Inexact => Err(Error::Inexact),
                     },
                 }?;
 
@@ -136,7 +142,7 @@ impl BigFloatNumber {
         }
     }
 
-    // exp for positive argument
+    // exp for the absolute value of the argument
     fn exp_positive_arg(&self, p: usize, cc: &mut Consts) -> Result<Self, Error> {
         debug_assert!(!self.is_zero());
 
@@ -146,36 +152,113 @@ impl BigFloatNumber {
 
         let p_work = p + 4;
 
-        // compute separately for int and fract parts, then combine the results.
-        let int = self.int_as_usize().map_err(|e| {
-            if matches!(e, Error::InvalidArgument) {
+        let x = self.abs()?;
+
+        // |self| = k*ln(2) + r, where 0 <= r < ln(2), then e^|self| = 2^k * e^r.
+        // k < 2^(e + 1), and ln(2) must be precise enough for the absolute error of r to be less than 2^(-p_work).
+        let e = (x.exponent() as isize).max(0) as usize;
+        let p_ln2 = p_work + e + WORD_BIT_SIZE;
+        let ln2 = cc.ln_2_num(p_ln2, RoundingMode::None)?;
+
+        let q = x.div(&ln2, e + WORD_BIT_SIZE, RoundingMode::None)?;
+        let mut k = q.int_as_usize().map_err(|err| {
+            if matches!(err, Error::InvalidArgument) {
                 Error::ExponentOverflow(Sign::Pos)
             } else {
-                e
+                err
             }
         })?;
-        let e_int = if int > 0 {
-            let e_const = cc.e_num(p_work, RoundingMode::None)?;
 
-            e_const.powi(int, p_work, RoundingMode::None)
+        if k > EXPONENT_MAX as usize {
+            return Err(Error::ExponentOverflow(Sign::Pos));
+        }
+
+        let mut r = if k > 0 {
+            let kln2 = Self::from_usize(k)?.mul(&ln2, p_ln2, RoundingMode::None)?;
+            x.sub(&kln2, p_ln2, RoundingMode::None)?
         } else {
-            ONE.clone()
-        }?;
-
-        let mut fract = self.fract()?;
-        let e_fract = if !fract.is_zero() {
-            fract.set_precision(p_work, RoundingMode::None)?;
-            fract.set_sign(Sign::Pos);
-            fract.expf()
+            x
+        };
+
+        // the quotient computed with limited precision can be off by one
+        if r.is_negative() {
+            k -= 1;
+            r = r.add(&ln2, p_ln2, RoundingMode::None)?;
+        } else if r.cmp(&ln2) >= 0 {
+            k += 1;
+            r = r.sub(&ln2, p_ln2, RoundingMode::None)?;
+        }
+
+        let mut ret = if !r.is_zero() {
+            r.set_precision(p_work, RoundingMode::None)?;
+            r.expf()?
         } else {
-            ONE.clone()
-        }?;
+            Self::from_word(1, p_work)?
+        };
 
-        e_int.mul(&e_fract, p, RoundingMode::FromZero)
+        let e_ret = ret.exponent() as isize + k as isize;
+        if e_ret > EXPONENT_MAX as isize {
+            return Err(Error::ExponentOverflow(Sign::Pos));
+        }
+
+        ret.set_exponent(e_ret as Exponent);
+        ret.set_inexact(true);
+        ret.set_precision(p, RoundingMode::FromZero)?;
+
+        Ok(ret)
+    }
+
+    // Computes self^n for positive `self` in the cases when the result is computed without series evaluation:
+    // `self` is a power of two and the result is a power of two, or n = 1/2.
+    fn pow_exact(&self, n: &Self, p: usize, rm: RoundingMode) -> Result<Option<Self>, Error> {
+        debug_assert!(self.is_positive());
+
+        if let Some(k) = self.exact_log2()? {
+            // self^n = 2^(k*n)
+            let t = match Self::from_i64(k as i64, 64)?.mul_full_prec(n) {
+                Ok(t) => t.to_i64_exact().ok(),
+                Err(Error::ExponentOverflow(_)) => None,
+                Err(e) => return Err(e),
+            };
+
+            if let Some(t) = t {
+                if t < EXPONENT_MAX as i64 && t >= EXPONENT_MIN as i64 - 1 {
+                    let mut ret = Self::from_word(1, p)?;
+                    ret.set_exponent((t + 1) as Exponent);
+                    ret.set_inexact(self.inexact() || n.inexact());
+                    return Ok(Some(ret));
+                }
+            }
+        }
+
+        if n.is_positive() && n.exact_log2()? == Some(-1) {
+            let mut ret = self.sqrt(p, rm)?;
+            ret.set_inexact(ret.inexact() || n.inexact());
+            return Ok(Some(ret));
+        }
+
+        Ok(None)
+    }
+
+    // Returns `k` if `self` is equal to 2^k by absolute value.
+    pub(crate) fn exact_log2(&self) -> Result<Option<isize>, Error> {
+        if self.is_zero() {
+            return Ok(None);
+        }
+
+        let (e, m_opt) = self.normalize()?;
+        let m = m_opt.as_ref().unwrap_or_else(|| self.mantissa());
+        let d = m.digits();
+
+        if d[d.len() - 1] == WORD_SIGNIFICANT_BIT && d[..d.len() - 1].iter().all(|&w| w == 0) {
+            Ok(Some(e - 1))
+        } else {
+            Ok(None)
+        }
     }
 
     /// Compute the power of `self` to the signed integer `n` with precision `p`. The result is rounded using the rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -222,7 +305,7 @@ impl BigFloatNumber {
     }
 
     /// Compute the power of `self` to the integer `n` with precision `p`. The result is rounded using the rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -232,32 +315,25 @@ impl BigFloatNumber {
     pub fn powi(&self, n: usize, p: usize, rm: RoundingMode) -> Result<Self, Error> {
         let p = round_p(p);
 
-        let mut i = n;
-
-        if i == 0 {
+        if n == 0 {
             return Self::from_word(1, p);
         }
 
-        if self.is_zero() || i == 1 {
+        if self.is_zero() || n == 1 {
             let mut ret = self.clone()?;
             ret.set_precision(p, rm)?;
             return Ok(ret);
         }
 
-        let mut bit_pos = WORD_BIT_SIZE;
-        while bit_pos > 0 {
-            if i & WORD_SIGNIFICANT_BIT as usize != 0 {
-                bit_pos -= 1;
-                i <<= 1;
-                break;
-            }
-
-            bit_pos -= 1;
-            i <<= 1;
-        }
-        let bit_pos = bit_pos; // make immutable
+        // number of significant bits of n
+        let bit_pos = (usize::BITS - n.leading_zeros()) as usize;
 
-        let p = round_p(p);
+        // window size of the sliding window exponentiation
+        let k = match bit_pos {
+            0..=8 => 1,
+            9..=24 => 2,
+            _ => 3,
+        };
 
         let mut p_inc = WORD_BIT_SIZE;
         let mut p_wrk = p.max(self.mantissa_max_bit_len()) + p_inc;
@@ -271,22 +347,53 @@ impl BigFloatNumber {
 
                 x.set_precision(p_x, RoundingMode::FromZero)?;
 
-                // TODO: consider windowing and precomputed values.
-                let mut bp = bit_pos;
-                let mut j = i;
-                while bp > 0 {
-                    bp -= 1;
+                // odd powers x, x^3, ..., x^(2^k - 1)
+                let mut tbl = Vec::new();
+                tbl.try_reserve_exact(1 << (k - 1))?;
+                if k > 1 {
+                    let x2 = x.mul(&x, p_x, RoundingMode::FromZero)?;
+                    tbl.push(x);
+                    for j in 1..1 << (k - 1) {
+                        let t = tbl[j - 1].mul(&x2, p_x, RoundingMode::FromZero)?;
+                        tbl.push(t);
+                    }
+                } else {
+                    tbl.push(x);
+                }
 
-                    x = x.mul(&x, p_x, RoundingMode::FromZero)?;
+                // scan bits of n from the most significant one, each window ends with bit 1
+                let mut ret: Option<Self> = None;
+                let mut i = bit_pos;
+                while i > 0 {
+                    if (n >> (i - 1)) & 1 == 0 {
+                        if let Some(r) = ret.as_mut() {
+                            *r = r.mul(r, p_x, RoundingMode::FromZero)?;
+                        }
+                        i -= 1;
+                        continue;
+                    }
 
-                    if j & WORD_SIGNIFICANT_BIT as usize != 0 {
-                        x = x.mul(self, p_x, RoundingMode::FromZero)?;
+                    let mut j = i.saturating_sub(k);
+                    while (n >> j) & 1 == 0 {
+                        j += 1;
                     }
 
-                    j <<= 1;
+                    let w = (n >> j) & ((1 << (i - j)) - 1);
+
+                    ret = Some(match ret {
+                        Some(mut r) => {
+                            for _ in j..i {
+                                r = r.mul(&r, p_x, RoundingMode::FromZero)?;
+                            }
+                            r.mul(&tbl[w >> 1], p_x, RoundingMode::FromZero)?
+                        }
+                        None => tbl[w >> 1].clone()?,
+                    });
+
+                    i = j;
                 }
 
-                Ok(x)
+                ret.ok_or(Error::InvalidArgument)
             }()
             .map_err(|e| -> Error {
                 if let Error::ExponentOverflow(_) = e {
@@ -405,7 +512,8 @@ impl BigFloatNumber {
 
     /// Compute the power of `self` to the `n` with precision `p`. The result is rounded using the rounding mode `rm`.
     /// This function requires constants cache `cc` for computing the result.
-    /// Precision is rounded upwards to the word size.
+    /// If `n` is an integer smaller than 2^(WORD_BIT_SIZE-1) by absolute value, the result is computed with `powsi`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
@@ -418,6 +526,19 @@ impl BigFloatNumber {
         p: usize,
         rm: RoundingMode,
         cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        self.pow_with_ln(n, p, rm, cc, &mut None)
+    }
+
+    // Computes the power of `self` to the `n`, reusing ln(self) stored in `ln_cache` if its precision is sufficient.
+    // If ln(self) is computed, it is stored in `ln_cache`.
+    pub(crate) fn pow_with_ln(
+        &self,
+        n: &Self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+        ln_cache: &mut Option<Self>,
     ) -> Result<Self, Error> {
         if n.is_zero() {
             let mut ret = Self::from_word(1, p)?;
@@ -449,38 +570,48 @@ impl BigFloatNumber {
                     return Err(Error::InvalidArgument);
                 }
             };
-        } else if self.is_negative() {
-            if n.is_int() {
-                let int = n.int_as_usize().map_err(|e| {
-                    if matches!(e, Error::InvalidArgument) {
-                        let sign = if n.is_odd_int() { Sign::Neg } else { Sign::Pos };
-                        Error::ExponentOverflow(sign)
-                    } else {
-                        e
-                    }
-                })?;
-
-                let int = int as isize * n.sign().to_int() as isize;
+        }
 
-                let mut ret = self.powsi(int, p, rm)?;
+        if n.is_int() && (n.exponent() as isize) < WORD_BIT_SIZE as isize {
+            // small integer power is computed by multiplication, and the result of it can be exact
+            let int = n.int_as_usize()? as isize * n.sign().to_int() as isize;
 
-                ret.set_inexact(ret.inexact() || n.inexact());
+            match self.powsi(int, p, rm) {
+                Ok(mut ret) => {
+                    ret.set_inexact(ret.inexact() || n.inexact());
+                    return Ok(ret);
+                }
+                // self^(-n) overflows, but self^n can still be subnormal
+                Err(Error::ExponentOverflow(_)) if n.is_negative() => {}
+                Err(e) => return Err(e),
+            }
+        }
 
-                return Ok(ret);
+        if self.is_negative() {
+            if n.is_int() {
+                // (-x)^n = x^n for even n, and -(x^n) for odd n
+                let mut x = self.clone()?;
+                x.inv_sign();
+
+                return if n.is_odd_int() {
+                    let mut ret = x
+                        .pow_with_ln(n, p, invert_rm_for_sign(rm), cc, ln_cache)
+                        .map_err(|e| match e {
+                            Error::ExponentOverflow(_) => Error::ExponentOverflow(Sign::Neg),
+                            e => e,
+                        })?;
+                    ret.inv_sign();
+                    Ok(ret)
+                } else {
+                    x.pow_with_ln(n, p, rm, cc, ln_cache)
+                };
             } else {
                 return Err(Error::InvalidArgument);
             }
-        } else if n.exponent() == 1 && n.abs_cmp(&ONE) == 0 {
-            if n.is_positive() {
-                let mut ret = self.clone()?;
-                ret.set_precision(p, rm)?;
-                ret.set_inexact(ret.inexact() || n.inexact());
-                return Ok(ret);
-            } else {
-                let mut ret = ONE.div(self, p, rm)?;
-                ret.set_inexact(ret.inexact() || n.inexact());
-                return Ok(ret);
-            }
+        }
+
+        if let Some(ret) = self.pow_exact(n, p, rm)? {
+            return Ok(ret);
         }
 
         // actual pow : self^n = e^(n * ln(self))
@@ -494,11 +625,16 @@ impl BigFloatNumber {
 
         loop {
             let p_ext = p_wrk + 2;
-            x.set_precision(p_ext, RoundingMode::None)?;
 
-            let ln = x.ln(p_ext, RoundingMode::None, cc)?;
+            let ln = match ln_cache {
+                Some(ln) if ln.mantissa_max_bit_len() >= p_ext => ln,
+                _ => {
+                    x.set_precision(p_ext, RoundingMode::None)?;
+                    ln_cache.insert(x.ln(p_ext, RoundingMode::None, cc)?)
+                }
+            };
 
-            let m = match n.mul(&ln, p_ext, RoundingMode::None) {
+            let m = match n.mul(ln, p_ext, RoundingMode::None) {
                 Ok(v) => Ok(v),
                 Err(e) => match e {
                     Error::ExponentOverflow(Sign::Neg) => {
@@ -508,6 +644,7 @@ impl BigFloatNumber {
                     Error::DivisionByZero => Err(Error::DivisionByZero),
                     Error::InvalidArgument => Err(Error::InvalidArgument),
                     Error::MemoryAllocation => Err(Error::MemoryAllocation),
+                    Error::Inexact => Err(Error::Inexact),
                 },
             }?;
 
@@ -717,6 +854,123 @@ mod test {
         let d2 = ONE.div(&TWO, p, rm).unwrap();
 
         assert!(d1.cmp(&d2) == 0);
+
+        // exact results
+        for rm in [RoundingMode::Up, RoundingMode::Down, RoundingMode::ToEven] {
+            for (b, n, r) in [
+                (4.0, 0.5, 2.0),
+                (2.0, -3.0, 0.125),
+                (0.25, 1.5, 0.125),
+                (1024.0, 3.5, 34359738368.0),
+                (2.25, 0.5, 1.5),
+                (0.0625, -0.25, 2.0),
+                (3.0, 2.0, 9.0),
+                (-3.0, 3.0, -27.0),
+                (1.5, 4.0, 5.0625),
+                (-0.5, -3.0, -8.0),
+                (-0.75, 2.0, 0.5625),
+                (10.0, 15.0, 1e15),
+            ] {
+                let b = BigFloatNumber::from_f64(p, b).unwrap();
+                let n = BigFloatNumber::from_f64(p, n).unwrap();
+                let r = BigFloatNumber::from_f64(p, r).unwrap();
+
+                let ret = b.pow(&n, p, rm, &mut cc).unwrap();
+                assert!(ret.cmp(&r) == 0);
+                assert!(!ret.inexact());
+            }
+        }
+
+        let mut d1 = ONE.clone().unwrap();
+        d1.set_exponent(EXPONENT_MIN + 1);
+        let d2 = BigFloatNumber::from_f64(p, 0.5).unwrap();
+        let mut d3 = ONE.clone().unwrap();
+        d3.set_exponent(EXPONENT_MIN / 2 + 1);
+        assert!(d1.pow(&d2, p, rm, &mut cc).unwrap().cmp(&d3) == 0);
+
+        // irrational result
+        let d1 = TWO.pow(&d2, p, rm, &mut cc).unwrap();
+        assert!(d1.cmp(&TWO.sqrt(p, rm).unwrap()) == 0);
+        assert!(d1.inexact());
+
+        // negative base and large integer power
+        let mut n = ONE.clone().unwrap();
+        n.set_exponent(71);
+        let n = n.add(&ONE, p, RoundingMode::None).unwrap();
+        let d1 = BigFloatNumber::from_f64(p, -0.5).unwrap();
+        let d2 = d1.pow(&n, p, rm, &mut cc).unwrap();
+        assert!(d2.is_zero() && d2.is_negative());
+        let d1 = BigFloatNumber::from_f64(p, -2.0).unwrap();
+        assert_eq!(
+            d1.pow(&n, p, rm, &mut cc).unwrap_err(),
+            Error::ExponentOverflow(Sign::Neg)
+        );
+        let n = n.sub(&ONE, p, RoundingMode::None).unwrap();
+        assert_eq!(
+            d1.pow(&n, p, rm, &mut cc).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+
+        // integer power against repeated multiplication
+        for _ in 0..20 {
+            let x = BigFloatNumber::random_normal(p, -5, 5).unwrap();
+            let n = rand::random::<usize>() % 3000 + 2;
+            let ret = x.powsi(n as isize, p, rm).unwrap();
+
+            let mut y = x.clone().unwrap();
+            for _ in 1..n {
+                y = y.mul(&x, p * 4, RoundingMode::None).unwrap();
+            }
+            y.set_precision(p, rm).unwrap();
+
+            assert!(ret.cmp(&y) == 0);
+
+            let nf = BigFloatNumber::from_word(n as crate::Word, 64).unwrap();
+            assert!(x.pow(&nf, p, rm, &mut cc).unwrap().cmp(&y) == 0);
+        }
+    }
+
+    #[test]
+    fn test_exp_large() {
+        let mut cc = Consts::new().unwrap();
+        let p = 320;
+        let rm = RoundingMode::ToEven;
+
+        // ln(e^x) = x
+        for x in [1000.5, -1000.5, 123456.789, 1e8, -1e8] {
+            let x = BigFloatNumber::from_f64(p, x).unwrap();
+            let y = x.exp(p, rm, &mut cc).unwrap();
+            let z = y.ln(p + 64, rm, &mut cc).unwrap();
+
+            let mut eps = ONE.clone().unwrap();
+            eps.set_exponent(-(p as Exponent) + 2);
+            assert!(z.sub(&x, p, rm).unwrap().abs_cmp(&eps) <= 0);
+        }
+
+        // e^(k*ln(2)) = 2^k
+        let ln2 = cc.ln_2_num(p + 256, RoundingMode::None).unwrap();
+        for k in [1, 2, 1000, 123456789, EXPONENT_MAX as i64 - 1] {
+            let x = BigFloatNumber::from_i64(k, 64)
+                .unwrap()
+                .mul(&ln2, p + 256, RoundingMode::None)
+                .unwrap();
+
+            let y = x.exp(p, rm, &mut cc).unwrap();
+            let mut pow2 = ONE.clone().unwrap();
+            pow2.set_exponent((k + 1) as Exponent);
+            assert!(y.cmp(&pow2) == 0);
+        }
+
+        // overflow
+        let x = BigFloatNumber::from_i64(EXPONENT_MAX as i64 + 1, 64)
+            .unwrap()
+            .mul(&ln2, p + 256, RoundingMode::None)
+            .unwrap();
+        assert_eq!(
+            x.exp(p, rm, &mut cc).map(|_| ()),
+            Err(Error::ExponentOverflow(Sign::Pos))
+        );
+        assert!(x.neg().unwrap().exp(p, rm, &mut cc).unwrap().is_zero());
     }
 
     #[ignore]
@@ -0,0 +1,203 @@
+//! Sine, cosine, and tangent returning the quadrant of the reduced argument.
+
+use crate::common::consts::ONE;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::ops::consts::Consts;
+use crate::WORD_BIT_SIZE;
+
+#[derive(Clone, Copy)]
+enum TrigFn {
+    Sin,
+    Cos,
+    Tan,
+}
+
+impl BigFloatNumber {
+    /// Computes the sine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Along with the result the function returns the quadrant of the argument as described in [BigFloatNumber::rem_pi2].
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn sin_quadrant(
+        &self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<(Self, usize), Error> {
+        self.trig_quadrant(TrigFn::Sin, p, rm, cc)
+    }
+
+    /// Computes the cosine of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Along with the result the function returns the quadrant of the argument as described in [BigFloatNumber::rem_pi2].
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn cos_quadrant(
+        &self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<(Self, usize), Error> {
+        self.trig_quadrant(TrigFn::Cos, p, rm, cc)
+    }
+
+    /// Computes the tangent of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Along with the result the function returns the quadrant of the argument as described in [BigFloatNumber::rem_pi2].
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn tan_quadrant(
+        &self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<(Self, usize), Error> {
+        self.trig_quadrant(TrigFn::Tan, p, rm, cc)
+    }
+
+    fn trig_quadrant(
+        &self,
+        f: TrigFn,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<(Self, usize), Error> {
+        let p = round_p(p);
+
+        if self.exponent() < 0 || self.is_zero() {
+            // |self| < 1/2 < pi/4
+            let ret = match f {
+                TrigFn::Sin => self.sin(p, rm, cc),
+                TrigFn::Cos => self.cos(p, rm, cc),
+                TrigFn::Tan => self.tan(p, rm, cc),
+            }?;
+
+            return Ok((ret, 0));
+        }
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p.max(self.mantissa_max_bit_len()) + p_inc;
+
+        loop {
+            let p_x = p_wrk + 4;
+
+            // |r| <= pi/4, and each of the functions below is well conditioned on this interval
+            let (r, k) = self.rem_pi2(p_x, RoundingMode::None, cc)?;
+
+            let mut ret = match (f, k) {
+                (TrigFn::Sin, 0) | (TrigFn::Cos, 3) => r.sin(p_x, RoundingMode::None, cc)?,
+                (TrigFn::Sin, 2) | (TrigFn::Cos, 1) => r.sin(p_x, RoundingMode::None, cc)?.neg()?,
+                (TrigFn::Sin, 1) | (TrigFn::Cos, 0) => r.cos(p_x, RoundingMode::None, cc)?,
+                (TrigFn::Sin, _) | (TrigFn::Cos, _) => r.cos(p_x, RoundingMode::None, cc)?.neg()?,
+                (TrigFn::Tan, 0) | (TrigFn::Tan, 2) => r.tan(p_x, RoundingMode::None, cc)?,
+                (TrigFn::Tan, _) => {
+                    // -cot(r)
+                    let t = r.tan(p_x, RoundingMode::None, cc)?;
+                    ONE.div(&t, p_x, RoundingMode::None)?.neg()?
+                }
+            };
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                ret.set_inexact(true);
+                return Ok((ret, k));
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::util::rand_p;
+
+    #[test]
+    fn test_trig_quadrant() {
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+
+        // quadrants of k*pi/2 + 1/8
+        let p = 256;
+        let mut pi2 = cc.pi_num(p + 128, RoundingMode::None).unwrap();
+        pi2.set_exponent(pi2.exponent() - 1);
+        let t = BigFloatNumber::from_f64(p, 0.125).unwrap();
+        for k in [0i64, 1, 2, 3, 4, 5, -1, -2, -3, -5, 1_000_001] {
+            let x = BigFloatNumber::from_i64(k, 64)
+                .unwrap()
+                .mul(&pi2, p + 128, rm)
+                .unwrap()
+                .add(&t, p + 128, rm)
+                .unwrap();
+
+            let (r, q) = x.rem_pi2(p, rm, &mut cc).unwrap();
+            assert_eq!(q, k.rem_euclid(4) as usize);
+            let mut eps = BigFloatNumber::from_word(1, p).unwrap();
+            eps.set_exponent(-(p as crate::Exponent) + 8);
+            assert!(r.sub(&t, p, rm).unwrap().abs_cmp(&eps) <= 0);
+
+            let (s, qs) = x.sin_quadrant(p, rm, &mut cc).unwrap();
+            let (c, qc) = x.cos_quadrant(p, rm, &mut cc).unwrap();
+            let (tn, qt) = x.tan_quadrant(p, rm, &mut cc).unwrap();
+            assert_eq!(qs, q);
+            assert_eq!(qc, q);
+            assert_eq!(qt, q);
+            assert_eq!(s.cmp(&x.sin(p, rm, &mut cc).unwrap()), 0);
+            assert_eq!(c.cmp(&x.cos(p, rm, &mut cc).unwrap()), 0);
+            assert_eq!(tn.cmp(&x.tan(p, rm, &mut cc).unwrap()), 0);
+        }
+
+        // small and zero arguments
+        let x = BigFloatNumber::from_f64(p, -0.25).unwrap();
+        let (s, q) = x.sin_quadrant(p, rm, &mut cc).unwrap();
+        assert_eq!(q, 0);
+        assert_eq!(s.cmp(&x.sin(p, rm, &mut cc).unwrap()), 0);
+        let z = BigFloatNumber::new(p).unwrap();
+        let (r, q) = z.rem_pi2(p, rm, &mut cc).unwrap();
+        assert!(r.is_zero());
+        assert_eq!(q, 0);
+
+        // the reduction of |x| < pi/4 is exact
+        let mut tiny = BigFloatNumber::from_word(1, p).unwrap();
+        tiny.set_exponent(-1000000);
+        for x in [
+            BigFloatNumber::from_f64(p, 0.5).unwrap(),
+            BigFloatNumber::from_f64(p, -0.75).unwrap(),
+            tiny,
+            BigFloatNumber::min_positive(p).unwrap().neg().unwrap(),
+        ] {
+            let (r, q) = x.rem_pi2(p, rm, &mut cc).unwrap();
+            assert_eq!(r.cmp(&x), 0);
+            assert_eq!(q, 0);
+            assert!(!r.inexact());
+        }
+
+        // large arguments
+        for _ in 0..20 {
+            let p = rand_p();
+            let x = BigFloatNumber::random_normal(p, 0, 1000).unwrap();
+
+            let (s, _) = x.sin_quadrant(p, rm, &mut cc).unwrap();
+            let (c, _) = x.cos_quadrant(p, rm, &mut cc).unwrap();
+            assert_eq!(s.cmp(&x.sin(p, rm, &mut cc).unwrap()), 0);
+            assert_eq!(c.cmp(&x.cos(p, rm, &mut cc).unwrap()), 0);
+        }
+    }
+}
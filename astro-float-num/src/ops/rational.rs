@@ -0,0 +1,225 @@
+//! Best rational approximation.
+
+use crate::common::util::int_to_u128;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::defs::Sign;
+use crate::defs::SignedWord;
+use crate::defs::WORD_BIT_SIZE;
+use crate::num::BigFloatNumber;
+
+impl BigFloatNumber {
+    /// Returns the best rational approximation `num / den` of `self` with `1 <= den <= max_den`.
+    /// The approximation is found using the continued fraction expansion of `self`, which is computed exactly.
+    /// The numerator is returned as an integer number, and the denominator as an unsigned integer.
+    /// If two approximations are equally close, the one with the smaller denominator is returned.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `max_den` is zero.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn best_rational(&self, max_den: u64) -> Result<(Self, u64), Error> {
+        if max_den == 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        if self.is_int() {
+            return Ok((self.clone()?, 1));
+        }
+
+        // |self| < 2^-66 is closer to 0 than to 1/max_den.
+        if self.exponent() <= -66 {
+            return Ok((Self::new(WORD_BIT_SIZE)?, 1));
+        }
+
+        let x = self.abs()?;
+
+        // x = n / d with d a power of two.
+        let (_, m, e) = x.to_int_ratio()?;
+        let mut n = Self::from_int_ratio(Sign::Pos, &m, 0)?;
+        let mut d = Self::from_int_ratio(Sign::Pos, &[1], -e)?;
+
+        // convergents h / k
+        let mut h2 = Self::new(WORD_BIT_SIZE)?;
+        let mut h1 = Self::from_u8(1, WORD_BIT_SIZE)?;
+        let mut k2: u64 = 1;
+        let mut k1: u64 = 0;
+
+        loop {
+            let a = Self::floor_div(&n, &d)?;
+            let r = n.sub_full_prec(&a.mul_full_prec(&d)?)?;
+
+            if let Some(t) = (max_den - k2).checked_div(k1) {
+                if a.cmp(&Self::from_u64(t, 64)?) > 0 {
+                    // the next convergent exceeds max_den: choose between the last convergent
+                    // and the semiconvergent with the largest allowed denominator.
+                    if t > 0 {
+                        let ts = Self::from_u64(t, 64)?;
+                        let hs = ts.mul_full_prec(&h1)?.add_full_prec(&h2)?;
+                        let ks = t * k1 + k2;
+
+                        if Self::rational_dist_cmp(&x, &hs, ks, &h1, k1)? < 0 {
+                            h1 = hs;
+                            k1 = ks;
+                        }
+                    }
+                    break;
+                }
+            }
+
+            // a fits in u64 after the first step
+            let k = if k1 > 0 {
+                let (m, _, _, e, _) = a.as_raw_parts();
+                int_to_u128(m, e as isize).unwrap_or(0) as u64 * k1 + k2
+            } else {
+                k2
+            };
+            let h = a.mul_full_prec(&h1)?.add_full_prec(&h2)?;
+
+            h2 = h1;
+            h1 = h;
+            k2 = k1;
+            k1 = k;
+
+            if r.is_zero() {
+                break;
+            }
+
+            n = d;
+            d = r;
+        }
+
+        h1.set_sign(self.sign());
+
+        Ok((h1, k1))
+    }
+
+    // Returns floor(n / d) for positive integers `n` and `d`.
+    fn floor_div(n: &Self, d: &Self) -> Result<Self, Error> {
+        let p = round_p((n.exponent() - d.exponent()).max(0) as usize + 1) + WORD_BIT_SIZE;
+
+        // rounding down to a precision sufficient for the integer part preserves the integer part.
+        n.div(d, p, RoundingMode::Down)?.int()
+    }
+
+    // Compares the distances |x - h1/k1| and |x - h2/k2|.
+    fn rational_dist_cmp(
+        x: &Self,
+        h1: &Self,
+        k1: u64,
+        h2: &Self,
+        k2: u64,
+    ) -> Result<SignedWord, Error> {
+        let k1 = Self::from_u64(k1, 64)?;
+        let k2 = Self::from_u64(k2, 64)?;
+
+        // |x*k1 - h1| * k2 vs |x*k2 - h2| * k1
+        let d1 = x
+            .mul_full_prec(&k1)?
+            .sub_full_prec(h1)?
+            .abs()?
+            .mul_full_prec(&k2)?;
+        let d2 = x
+            .mul_full_prec(&k2)?
+            .sub_full_prec(h2)?
+            .abs()?
+            .mul_full_prec(&k1)?;
+
+        Ok(d1.cmp(&d2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_best_rational() {
+        let rm = RoundingMode::ToEven;
+
+        let to_u64 = |n: &BigFloatNumber| {
+            let (m, _, _, e, _) = n.as_raw_parts();
+            int_to_u128(m, e as isize).unwrap() as u64
+        };
+
+        // pi
+        let mut cc = crate::Consts::new().unwrap();
+        let pi = cc.pi_num(256, rm).unwrap();
+        for (max_den, num, den) in [
+            (1, 3, 1),
+            (7, 22, 7),
+            (100, 311, 99),
+            (113, 355, 113),
+            (1000, 355, 113),
+            (33215, 104348, 33215),
+            (33216, 104348, 33215),
+        ] {
+            let (n, d) = pi.best_rational(max_den).unwrap();
+            assert_eq!((to_u64(&n), d), (num, den), "{}", max_den);
+        }
+
+        // negative number
+        let x = BigFloatNumber::from_f64(64, -0.3).unwrap();
+        let (n, d) = x.best_rational(10).unwrap();
+        assert!(n.is_negative());
+        assert_eq!((to_u64(&n.abs().unwrap()), d), (3, 10));
+
+        // exact rational and ties
+        let x = BigFloatNumber::from_f64(64, 0.375).unwrap();
+        assert_eq!(
+            x.best_rational(8).map(|(n, d)| (to_u64(&n), d)).unwrap(),
+            (3, 8)
+        );
+        assert_eq!(
+            x.best_rational(1000).map(|(n, d)| (to_u64(&n), d)).unwrap(),
+            (3, 8)
+        );
+        assert_eq!(
+            x.best_rational(2).map(|(n, d)| (to_u64(&n), d)).unwrap(),
+            (1, 2)
+        );
+        let x = BigFloatNumber::from_f64(64, 0.25).unwrap();
+        assert_eq!(
+            x.best_rational(1).map(|(n, d)| (to_u64(&n), d)).unwrap(),
+            (0, 1)
+        );
+
+        // integer, large numerator, and tiny numbers
+        let x = BigFloatNumber::from_word(12345, 64).unwrap();
+        assert_eq!(
+            x.best_rational(7).map(|(n, d)| (to_u64(&n), d)).unwrap(),
+            (12345, 1)
+        );
+        let x = BigFloatNumber::from_f64(128, 1.5e30)
+            .unwrap()
+            .add(&BigFloatNumber::from_f64(64, 0.5).unwrap(), 128, rm)
+            .unwrap();
+        let (n, d) = x.best_rational(4).unwrap();
+        assert_eq!(d, 2);
+        assert!(n.cmp(&x.mul_word(2, 192, rm).unwrap()) == 0);
+        let x = BigFloatNumber::min_positive(128).unwrap();
+        let (n, d) = x.best_rational(u64::MAX).unwrap();
+        assert!(n.is_zero() && d == 1);
+
+        assert!(pi.best_rational(0).unwrap_err() == Error::InvalidArgument);
+
+        // random values: the result is not worse than any neighbour fraction with a smaller denominator
+        for _ in 0..100 {
+            let x = BigFloatNumber::random_normal(128, -3, 3).unwrap();
+            let max_den = rand::random::<u64>() % 50 + 1;
+            let (n, d) = x.best_rational(max_den).unwrap();
+            assert!(d <= max_den);
+
+            for k in 1..=max_den {
+                let h = x
+                    .mul_full_prec(&BigFloatNumber::from_u64(k, 64).unwrap())
+                    .unwrap()
+                    .round(0, rm)
+                    .unwrap();
+                assert!(BigFloatNumber::rational_dist_cmp(&x, &n, d, &h, k).unwrap() <= 0);
+            }
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! Reduction of an angle modulo 2*pi.
+
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::ops::consts::Consts;
+use crate::Exponent;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Reduces an angle in radians to the interval [0, 2*pi) with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn rem_2pi(&self, p: usize, rm: RoundingMode, cc: &mut Consts) -> Result<Self, Error> {
+        self.rem_2pi_quo(false, p, rm, cc).map(|(r, _)| r)
+    }
+
+    /// Reduces an angle in radians to the interval (-pi, pi] with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn rem_2pi_centered(
+        &self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        self.rem_2pi_quo(true, p, rm, cc).map(|(r, _)| r)
+    }
+
+    /// Reduces an angle in radians modulo 2*pi and returns the remainder `r` with precision `p` rounded using the rounding mode `rm`,
+    /// and the integer quotient `q`, such that `self` = 2*pi*q + r.
+    /// If `centered` is true, the remainder is in the interval (-pi, pi], otherwise it is in the interval [0, 2*pi).
+    /// After rounding, the remainder can be equal to the upper bound of the interval.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn rem_2pi_quo(
+        &self,
+        centered: bool,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<(Self, Self), Error> {
+        self.rem_pi_quo(1, centered, p, rm, cc)
+    }
+
+    /// Reduces an angle in radians modulo pi/2 and returns the remainder `r` with precision `p` rounded using the rounding mode `rm`,
+    /// and the quadrant `k`, such that `self` = pi/2*(4*n + k) + r for an integer n, and `r` is in the interval (-pi/4, pi/4].
+    /// After rounding, the remainder can be equal to the bound of the interval.
+    /// The reduction is correct for arguments with large exponents, since pi is computed with the precision sufficient to cover them.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: the precision is incorrect.
+    pub fn rem_pi2(
+        &self,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<(Self, usize), Error> {
+        let (r, q) = self.rem_pi_quo(-1, true, p, rm, cc)?;
+
+        Ok((r, q.int_mod4()?))
+    }
+
+    // Reduces `self` modulo pi*2^e_shift. See `rem_2pi_quo` for details.
+    fn rem_pi_quo(
+        &self,
+        e_shift: Exponent,
+        centered: bool,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<(Self, Self), Error> {
+        let p = round_p(p);
+
+        if self.is_zero() {
+            return Ok((
+                Self::new2(p, self.sign(), self.inexact())?,
+                Self::new2(WORD_BIT_SIZE, self.sign(), false)?,
+            ));
+        }
+
+        if !centered && self.is_negative() && (self.exponent() as isize) < -(p as isize) - 4 {
+            // r = pi*2^e_shift - |self|, where |self| is smaller than the rounding error
+            let mut p_inc = WORD_BIT_SIZE;
+            let mut p_wrk = p + p_inc;
+
+            loop {
+                let mut d = cc.pi_num(p_wrk + 2, RoundingMode::None)?;
+                d.set_exponent(d.exponent() + e_shift);
+
+                let mut r = d.add_correction(true)?;
+
+                if r.try_set_precision(p, rm, p_wrk)? {
+                    r.set_inexact(true);
+                    return Ok((r, Self::from_i8(-1, WORD_BIT_SIZE)?));
+                }
+
+                p_wrk += p_inc;
+                p_inc = round_p(p_wrk / 5);
+            }
+        }
+
+        // |self| < 2^e
+        let e = (self.exponent() as isize).max(2) as usize;
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p + p_inc;
+
+        loop {
+            // pi*2^e_shift with the error less than 2^(2 + e_shift - p_pi), and |q| <= 2^(e - 1 - e_shift) + 1,
+            // so the absolute error of r is less than 2^(e + 2 - p_pi).
+            let p_pi = p_wrk + e + WORD_BIT_SIZE;
+
+            let mut d = cc.pi_num(p_pi, RoundingMode::None)?;
+            d.set_exponent(d.exponent() + e_shift);
+
+            let (mut r, q) = self.rem_pi_raw(&d, centered)?;
+
+            if q.is_zero() {
+                // the remainder is `self`, if `self` is not too close to the upper bound of the interval
+                let mut hi = d;
+                if centered {
+                    hi.set_exponent(hi.exponent() - 1);
+                }
+                let dist = hi.sub(&r.abs()?, p_pi, RoundingMode::None)?;
+
+                if !dist.is_zero()
+                    && dist.exponent() as isize > e_shift as isize + 3 - p_pi as isize
+                {
+                    let mut r = self.clone()?;
+                    r.set_precision(p, rm)?;
+                    break Ok((r, q));
+                }
+            } else if !r.is_zero() {
+                let err_exp = e as isize + 2 - p_pi as isize;
+                let s = r.exponent() as isize - err_exp - 1;
+
+                // the quotient is ambiguous if the remainder is close to the upper bound of the interval
+                let mut hi = d;
+                if centered {
+                    hi.set_exponent(hi.exponent() - 1);
+                }
+                let dist = hi.sub(&r.abs()?, p_pi, RoundingMode::None)?;
+
+                if s > p as isize && !dist.is_zero() && dist.exponent() as isize > err_exp + 1 {
+                    // number of correct bits is aligned to the word size
+                    let s =
+                        (s as usize).min(r.mantissa_max_bit_len()) / WORD_BIT_SIZE * WORD_BIT_SIZE;
+
+                    if r.try_set_precision(p, rm, s)? {
+                        r.set_inexact(true);
+                        break Ok((r, q));
+                    }
+                }
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Computes the remainder of division of `self` by `d`, and the quotient exactly.
+    fn rem_pi_raw(&self, d: &Self, centered: bool) -> Result<(Self, Self), Error> {
+        let p_q = round_p((self.exponent() as isize).max(0) as usize + WORD_BIT_SIZE);
+
+        let q_rm = if centered { RoundingMode::ToEven } else { RoundingMode::Down };
+
+        let mut q = self.div(d, p_q, RoundingMode::None)?.round(0, q_rm)?;
+
+        let mut r = if q.is_zero() {
+            // the remainder is exact
+            let mut r = self.clone()?;
+            r.set_inexact(false);
+            r
+        } else {
+            self.sub_full_prec(&q.mul_full_prec(d)?)?
+        };
+
+        // the quotient computed with limited precision can be off by one
+        let (lo, hi) = if centered {
+            let mut h = d.clone()?;
+            h.set_exponent(h.exponent() - 1);
+            (h.neg()?, h)
+        } else {
+            (Self::new(WORD_BIT_SIZE)?, d.clone()?)
+        };
+
+        // for centered, r must be in (lo, hi], otherwise in [lo, hi)
+        while r.cmp(&lo) < 0 || (centered && r.cmp(&lo) == 0) {
+            r = r.add_full_prec(d)?;
+            q = q.add_int(-1)?;
+        }
+
+        while r.cmp(&hi) > 0 || (!centered && r.cmp(&hi) == 0) {
+            r = r.sub_full_prec(d)?;
+            q = q.add_int(1)?;
+        }
+
+        Ok((r, q))
+    }
+
+    // Returns the integer `self` modulo 4 as a non-negative number.
+    fn int_mod4(&self) -> Result<usize, Error> {
+        if self.is_zero() {
+            return Ok(0);
+        }
+
+        // self - 4*floor(self/4)
+        let mut t = self.clone()?;
+        t.set_exponent(t.exponent() - 2);
+        let mut f = t.floor()?;
+
+        let m = if f.is_zero() {
+            self.clone()?
+        } else {
+            f.set_exponent(f.exponent() + 2);
+            self.sub_full_prec(&f)?
+        };
+
+        m.int_as_usize()
+    }
+
+    // Adds the integer `d` to the integer `self` exactly.
+    fn add_int(&self, d: i8) -> Result<Self, Error> {
+        let d = Self::from_i8(d, WORD_BIT_SIZE)?;
+
+        if self.is_zero() {
+            Ok(d)
+        } else {
+            self.add_full_prec(&d)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_rem_2pi() {
+        let p = 320;
+        let mut cc = Consts::new().unwrap();
+        let rm = RoundingMode::ToEven;
+
+        let mut eps = BigFloatNumber::from_word(1, p).unwrap();
+        eps.set_exponent(-(p as Exponent) + 4);
+
+        let pi = cc.pi_num(p + 1024, RoundingMode::None).unwrap();
+        let mut two_pi = pi.clone().unwrap();
+        two_pi.set_exponent(two_pi.exponent() + 1);
+
+        // x = 2*pi*k + t
+        for t in [0.5, 1.0, 3.0, 3.2, 6.0, -0.5, -3.0, -3.2, -6.0] {
+            let t = BigFloatNumber::from_f64(p, t).unwrap();
+
+            for k in [0i64, 1, -1, 3, 1_000_000, -123_456_789_012] {
+                let q = BigFloatNumber::from_i64(k, 64).unwrap();
+                let x = q
+                    .mul(&two_pi, p + 512, RoundingMode::ToEven)
+                    .unwrap()
+                    .add(&t, p + 128, RoundingMode::ToEven)
+                    .unwrap();
+
+                // expected remainder in [0, 2*pi)
+                let (t1, k1) = if t.is_negative() {
+                    (t.add(&two_pi, p + 128, rm).unwrap(), k - 1)
+                } else {
+                    (t.clone().unwrap(), k)
+                };
+
+                let (r, q) = x.rem_2pi_quo(false, p, rm, &mut cc).unwrap();
+                assert!(r.sub(&t1, p, rm).unwrap().abs_cmp(&eps) <= 0);
+                assert!(q.cmp(&BigFloatNumber::from_i64(k1, 64).unwrap()) == 0);
+                assert!(!r.is_negative());
+                assert!(r.cmp(&x.rem_2pi(p, rm, &mut cc).unwrap()) == 0);
+
+                // expected remainder in (-pi, pi]
+                let (t2, k2) = if t.cmp(&pi) > 0 {
+                    (t.sub(&two_pi, p + 128, rm).unwrap(), k + 1)
+                } else if t.cmp(&pi.neg().unwrap()) <= 0 {
+                    (t.add(&two_pi, p + 128, rm).unwrap(), k - 1)
+                } else {
+                    (t.clone().unwrap(), k)
+                };
+
+                let (r, q) = x.rem_2pi_quo(true, p, rm, &mut cc).unwrap();
+                assert!(r.sub(&t2, p, rm).unwrap().abs_cmp(&eps) <= 0);
+                assert!(q.cmp(&BigFloatNumber::from_i64(k2, 64).unwrap()) == 0);
+                assert!(r.cmp(&x.rem_2pi_centered(p, rm, &mut cc).unwrap()) == 0);
+            }
+        }
+
+        // large argument close to a multiple of 2*pi
+        let mut q = BigFloatNumber::from_word(1, 64).unwrap();
+        q.set_exponent(200);
+        let x = q.mul(&two_pi, p, RoundingMode::ToEven).unwrap();
+        let r = x.rem_2pi_centered(p, rm, &mut cc).unwrap();
+        let r_exp = x
+            .sub(
+                &q.mul(&two_pi, p + 1024, RoundingMode::None).unwrap(),
+                p + 1024,
+                rm,
+            )
+            .unwrap();
+        let mut r_exp = r_exp;
+        r_exp.set_precision(p, rm).unwrap();
+        assert!(r.cmp(&r_exp) == 0);
+
+        // small arguments
+        let x = BigFloatNumber::from_f64(p, 1e-100).unwrap();
+        assert!(x.rem_2pi(p, rm, &mut cc).unwrap().cmp(&x) == 0);
+        assert!(x.rem_2pi_centered(p, rm, &mut cc).unwrap().cmp(&x) == 0);
+        let x = x.neg().unwrap();
+        assert!(x.rem_2pi_centered(p, rm, &mut cc).unwrap().cmp(&x) == 0);
+        let r = x.rem_2pi(p, RoundingMode::Down, &mut cc).unwrap();
+        assert!(r.cmp(&two_pi) < 0);
+
+        // the remainder of a number inside the interval is exact
+        let x = BigFloatNumber::from_f64(p, 0.5).unwrap();
+        let (r, q) = x.rem_2pi_quo(false, p, rm, &mut cc).unwrap();
+        assert!(r.cmp(&x) == 0 && q.is_zero());
+        assert!(!r.inexact());
+        let x = x.neg().unwrap();
+        let r = x.rem_2pi_centered(p, rm, &mut cc).unwrap();
+        assert!(r.cmp(&x) == 0);
+        assert!(!r.inexact());
+
+        // tiny arguments
+        let mut two_pi_p = two_pi.clone().unwrap();
+        two_pi_p.set_precision(p, rm).unwrap();
+        let mut x = BigFloatNumber::from_word(1, p).unwrap();
+        x.set_exponent(-1000000);
+        for x in [x, BigFloatNumber::min_positive(p).unwrap()] {
+            let r = x.rem_2pi(p, rm, &mut cc).unwrap();
+            assert!(r.cmp(&x) == 0);
+            assert!(!r.inexact());
+            let x = x.neg().unwrap();
+            let (r, q) = x.rem_2pi_quo(true, p, rm, &mut cc).unwrap();
+            assert!(r.cmp(&x) == 0 && q.is_zero());
+            let r = x.rem_2pi(p, rm, &mut cc).unwrap();
+            assert!(r.cmp(&two_pi_p) == 0);
+            assert!(r.inexact());
+            let r = x.rem_2pi(p, RoundingMode::Down, &mut cc).unwrap();
+            assert!(r.cmp(&two_pi) < 0);
+        }
+
+        assert!(BigFloatNumber::new(p)
+            .unwrap()
+            .rem_2pi(p, rm, &mut cc)
+            .unwrap()
+            .is_zero());
+    }
+}
@@ -0,0 +1,239 @@
+//! Root of an arbitrary integer order.
+
+use crate::common::consts::ONE;
+use crate::common::util::invert_rm_for_sign;
+use crate::common::util::round_p;
+use crate::defs::Error;
+use crate::defs::RoundingMode;
+use crate::num::BigFloatNumber;
+use crate::ops::util::compute_small_exp;
+use crate::Consts;
+use crate::Exponent;
+use crate::WORD_BIT_SIZE;
+
+impl BigFloatNumber {
+    /// Computes the root of order `n` of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// If `neg_odd` is true and `n` is odd, the root of a negative number is the negative real root,
+    /// otherwise the root of a negative number is not defined.
+    /// This function requires constants cache `cc` for computing the result.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    ///  - InvalidArgument: `n` is zero; `self` is negative, and `n` is even or `neg_odd` is false; the precision is incorrect.
+    pub fn rootn(
+        &self,
+        n: usize,
+        neg_odd: bool,
+        p: usize,
+        rm: RoundingMode,
+        cc: &mut Consts,
+    ) -> Result<Self, Error> {
+        let p = round_p(p);
+        Self::p_assertion(p)?;
+
+        if n == 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        if self.is_zero() {
+            return Self::new2(p, self.sign(), self.inexact());
+        }
+
+        if self.is_negative() {
+            return if neg_odd && n & 1 == 1 {
+                let mut x = self.clone()?;
+                x.inv_sign();
+                let mut ret = x.rootn(n, neg_odd, p, invert_rm_for_sign(rm), cc)?;
+                ret.inv_sign();
+                Ok(ret)
+            } else {
+                Err(Error::InvalidArgument)
+            };
+        }
+
+        match n {
+            1 => {
+                let mut ret = self.clone()?;
+                ret.set_precision(p, rm)?;
+                return Ok(ret);
+            }
+            2 => return self.sqrt(p, rm),
+            3 => return self.cbrt(p, rm),
+            _ => {}
+        }
+
+        if let Some(k) = self.exact_log2()? {
+            if k % n as isize == 0 {
+                let mut ret = Self::from_word(1, p)?;
+                ret.set_exponent((k / n as isize + 1) as Exponent);
+                ret.set_inexact(self.inexact());
+                return Ok(ret);
+            }
+        }
+
+        let nf = Self::from_u64(n as u64, 64)?;
+
+        let mut p_inc = WORD_BIT_SIZE;
+        let mut p_wrk = p.max(self.mantissa_max_bit_len()) + p_inc;
+        let mut exact_checked = false;
+
+        loop {
+            let p_x = p_wrk + 4;
+
+            // the absolute error of ln(self) / n must be smaller than 2^(-p_x)
+            let mut l = self.ln(p_x + 4, RoundingMode::None, cc)?;
+            let e_m = l.exponent() as isize - n.ilog2() as isize;
+            if e_m > 0 {
+                l = self.ln(p_x + e_m as usize + 4, RoundingMode::None, cc)?;
+            }
+
+            let m = l.div(&nf, l.mantissa_max_bit_len(), RoundingMode::None)?;
+
+            compute_small_exp!(ONE, m.exponent() as isize, m.is_negative(), p, rm);
+
+            let mut ret = m.exp(p_x, RoundingMode::None, cc)?;
+
+            if !exact_checked {
+                // the result can be exactly representable, in which case it is equal to the rounded approximation
+                exact_checked = true;
+
+                if let Some(mut ret) = self.rootn_exact(&ret, n, p)? {
+                    ret.set_precision(p, rm)?;
+                    return Ok(ret);
+                }
+            }
+
+            if ret.try_set_precision(p, rm, p_wrk)? {
+                ret.set_inexact(true);
+                return Ok(ret);
+            }
+
+            p_wrk += p_inc;
+            p_inc = round_p(p_wrk / 5);
+        }
+    }
+
+    // Rounds the approximation `r` of the root of order `n` of `self` to the precision larger than `p`
+    // and returns it if its power `n` is exactly equal to `self`.
+    fn rootn_exact(&self, r: &Self, n: usize, p: usize) -> Result<Option<Self>, Error> {
+        let mut c = r.clone()?;
+        c.set_precision(p + WORD_BIT_SIZE, RoundingMode::ToEven)?;
+        c.set_inexact(false);
+
+        // number of significant bits of c
+        let d = c.mantissa().digits();
+        let tz = match d.iter().position(|&w| w != 0) {
+            Some(i) => i * WORD_BIT_SIZE + d[i].trailing_zeros() as usize,
+            None => return Ok(None),
+        };
+        let s = c.mantissa_max_bit_len() - tz;
+
+        // c^n has at least n*(s - 1) + 1 significant bits
+        match n.checked_mul(s) {
+            Some(q) if q - n < self.mantissa_max_bit_len() => {
+                let z = match c.powi(n, q, RoundingMode::ToEven) {
+                    Ok(z) => z,
+                    Err(Error::ExponentOverflow(_)) => return Ok(None),
+                    Err(e) => return Err(e),
+                };
+
+                if z.cmp(self) == 0 {
+                    c.set_inexact(self.inexact());
+                    Ok(Some(c))
+                } else {
+                    Ok(None)
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::util::rand_p;
+
+    #[test]
+    fn test_rootn() {
+        let mut cc = Consts::new().unwrap();
+        let n = |i: i64| BigFloatNumber::from_i64(i, 128).unwrap();
+
+        // exact results
+        for rm in [RoundingMode::Up, RoundingMode::Down, RoundingMode::ToEven] {
+            for (x, k, r) in [(81, 4, 3), (-243, 5, -3), (1 << 40, 8, 32), (-1, 7, -1), (1, 6, 1)] {
+                let d = n(x).rootn(k, true, 128, rm, &mut cc).unwrap();
+                assert_eq!(d.cmp(&n(r)), 0);
+                assert!(!d.inexact());
+            }
+
+            let d = n(-8).rootn(3, true, 128, rm, &mut cc).unwrap();
+            assert_eq!(d.cmp(&n(-2)), 0);
+
+            let d = n(7).rootn(1, false, 128, rm, &mut cc).unwrap();
+            assert_eq!(d.cmp(&n(7)), 0);
+        }
+
+        // negative radicands
+        assert_eq!(
+            n(-243)
+                .rootn(5, false, 128, RoundingMode::ToEven, &mut cc)
+                .unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            n(-16)
+                .rootn(4, true, 128, RoundingMode::ToEven, &mut cc)
+                .unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            n(16)
+                .rootn(0, true, 128, RoundingMode::ToEven, &mut cc)
+                .unwrap_err(),
+            Error::InvalidArgument
+        );
+
+        let z = BigFloatNumber::new(128).unwrap().neg().unwrap();
+        let d = z
+            .rootn(4, false, 128, RoundingMode::ToEven, &mut cc)
+            .unwrap();
+        assert!(d.is_zero());
+        assert!(d.is_negative());
+
+        // directed rounding of a negative root
+        let up = n(-5)
+            .rootn(5, true, 128, RoundingMode::Up, &mut cc)
+            .unwrap();
+        let down = n(-5)
+            .rootn(5, true, 128, RoundingMode::Down, &mut cc)
+            .unwrap();
+        assert!(up.inexact());
+        assert_eq!(up.cmp(&down), 1);
+        assert!(up.powi(5, 640, RoundingMode::ToEven).unwrap().cmp(&n(-5)) > 0);
+        assert!(down.powi(5, 640, RoundingMode::ToEven).unwrap().cmp(&n(-5)) < 0);
+
+        // comparison with the power
+        let rm = RoundingMode::ToEven;
+        for _ in 0..100 {
+            let p = rand_p();
+            let x = BigFloatNumber::random_normal(p, -100, 100)
+                .unwrap()
+                .abs()
+                .unwrap();
+            let k = 4 + rand::random::<usize>() % 1000;
+
+            let d = x.rootn(k, false, p, rm, &mut cc).unwrap();
+            let d0 = d.powi(k, p + 64, rm).unwrap();
+
+            let mut e = d0.sub(&x, p + 64, rm).unwrap();
+            if !e.is_zero() {
+                e = e.div(&x, p, rm).unwrap();
+                assert!((e.exponent() as isize) < -(p as isize) + 12);
+            }
+        }
+    }
+}
@@ -108,7 +108,7 @@ pub(crate) fn series_run<T: PolycoeffGen>(
 /// Estimate of the number of series iterations.
 /// p is the precision, m is the negative power of x
 /// (i.e. x = f*2^(-m), where 0.5 <= f < 1).
-fn series_niter(p: usize, m: usize) -> usize {
+pub(crate) fn series_niter(p: usize, m: usize) -> usize {
     let ln = log2_floor(p);
     let lln = log2_floor(ln);
     p / (ln - lln + m - 2)
@@ -10,18 +10,32 @@ use crate::{
 
 impl BigFloatNumber {
     /// Computes the square root of a number with precision `p`. The result is rounded using the rounding mode `rm`.
-    /// Precision is rounded upwards to the word size.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
     ///
     /// ## Errors
     ///
     ///  - InvalidArgument: argument is negative, or the precision is incorrect.
     ///  - MemoryAllocation: failed to allocate memory.
     pub fn sqrt(&self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        self.sqrt_exact(p, rm).map(|(ret, _)| ret)
+    }
+
+    /// Computes the square root of a number with precision `p`. The result is rounded using the rounding mode `rm`.
+    /// Along with the result the function returns a bool value which is true if the square root of `self`
+    /// is representable exactly with precision `p`.
+    /// The returned bool value does not depend on whether `self` is inexact.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: argument is negative, or the precision is incorrect.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn sqrt_exact(&self, p: usize, rm: RoundingMode) -> Result<(Self, bool), Error> {
         let p = round_p(p);
         Self::p_assertion(p)?;
 
         if self.is_zero() {
-            return Self::new(p);
+            return Ok((Self::new2(p, Sign::Pos, self.inexact())?, true));
         }
 
         if self.is_negative() {
@@ -31,26 +45,26 @@ impl BigFloatNumber {
         let (e1, m1_opt) = self.normalize()?;
         let m1_normalized = m1_opt.as_ref().unwrap_or_else(|| self.mantissa());
 
-        let mut inexact = self.inexact();
+        let mut inexact = false;
 
         let (e_shift, m3) = m1_normalized.sqrt(p, rm, true, &mut inexact, e1 & 1 == 1)?;
 
         let e = (e1 + (e1 & 1)) / 2 + e_shift;
 
-        if e < EXPONENT_MIN as isize {
+        let mut ret = if e < EXPONENT_MIN as isize {
             let mut ret = BigFloatNumber::from_raw_unchecked(m3, Sign::Pos, EXPONENT_MIN, inexact);
 
             ret.subnormalize(e, rm);
 
-            Ok(ret)
+            ret
         } else {
-            Ok(BigFloatNumber::from_raw_unchecked(
-                m3,
-                Sign::Pos,
-                e as Exponent,
-                inexact,
-            ))
-        }
+            BigFloatNumber::from_raw_unchecked(m3, Sign::Pos, e as Exponent, inexact)
+        };
+
+        let exact = !ret.inexact();
+        ret.set_inexact(!exact || self.inexact());
+
+        Ok((ret, exact))
     }
 }
 
@@ -197,6 +211,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sqrt_exact() {
+        let p = 256;
+
+        // exact square of a random number
+        for _ in 0..1000 {
+            let mut d1 = BigFloatNumber::random_normal(p / 2, -100, 100).unwrap();
+            d1.set_sign(Sign::Pos);
+            let d2 = d1.mul(&d1, p, RoundingMode::None).unwrap();
+            assert!(!d2.inexact());
+
+            let (d3, exact) = d2.sqrt_exact(p, RoundingMode::ToEven).unwrap();
+            assert!(exact);
+            assert!(!d3.inexact());
+            assert!(d3.cmp(&d1) == 0);
+        }
+
+        // inexact result
+        let d1 = BigFloatNumber::from_word(2, p).unwrap();
+        let (d2, exact) = d1.sqrt_exact(p, RoundingMode::ToEven).unwrap();
+        assert!(!exact);
+        assert!(d2.inexact());
+
+        // exactness does not depend on the inexactness of the argument
+        let mut d1 = BigFloatNumber::from_word(4, p).unwrap();
+        d1.set_inexact(true);
+        let (d2, exact) = d1.sqrt_exact(p, RoundingMode::ToEven).unwrap();
+        assert!(exact);
+        assert!(d2.inexact());
+
+        let (d2, exact) = BigFloatNumber::new(p)
+            .unwrap()
+            .sqrt_exact(p, RoundingMode::ToEven)
+            .unwrap();
+        assert!(exact);
+        assert!(d2.is_zero());
+    }
+
     #[ignore]
     #[test]
     #[cfg(feature = "std")]
@@ -1,7 +1,9 @@
 //! tests
 
 use crate::common::consts::ONE;
-use crate::common::util::{count_leading_ones, count_leading_zeroes_skip_first, log2_floor};
+use crate::common::util::{
+    count_leading_ones, count_leading_zeroes_skip_first, log2_floor, round_p,
+};
 use crate::defs::{RoundingMode, EXPONENT_MAX, EXPONENT_MIN, WORD_BIT_SIZE};
 use crate::num::BigFloatNumber;
 use crate::ops::consts::Consts;
@@ -134,7 +136,7 @@ fn test_powi() {
     for _ in 0..1000 {
         let i = random::<usize>() % 1000 + 1;
         let p1 = (rand::random::<usize>() % prec_rng + 1) * WORD_BIT_SIZE;
-        let prec = (rand::random::<usize>() % prec_rng + 1) * WORD_BIT_SIZE;
+        let prec = round_p((rand::random::<usize>() % prec_rng + 1) * WORD_BIT_SIZE);
         let mut d1 = BigFloatNumber::random_normal(
             p1,
             EXPONENT_MIN / i as Exponent,
@@ -772,7 +774,7 @@ fn test_sinh_asinh() {
 
     for i in 0..1000 {
         let p1 = (rand::random::<usize>() % prec_rng + 1) * WORD_BIT_SIZE;
-        let prec = (rand::random::<usize>() % prec_rng + 1) * WORD_BIT_SIZE;
+        let prec = round_p((rand::random::<usize>() % prec_rng + 1) * WORD_BIT_SIZE);
 
         if i & 1 == 0 {
             let d1 = BigFloatNumber::random_normal(p1, -100, 10).unwrap();
@@ -837,7 +839,7 @@ fn test_cosh_acosh() {
 
     for i in 0..1000 {
         let p1 = (rand::random::<usize>() % prec_rng + 1) * WORD_BIT_SIZE;
-        let prec = (rand::random::<usize>() % prec_rng + 1) * WORD_BIT_SIZE;
+        let prec = round_p((rand::random::<usize>() % prec_rng + 1) * WORD_BIT_SIZE);
 
         if i & 1 == 0 {
             let d1 = BigFloatNumber::random_normal(p1, -100, 10).unwrap();
@@ -896,11 +898,11 @@ fn test_tanh_atanh() {
     let mut cc = Consts::new().unwrap();
 
     let exp_to;
-    #[cfg(not(target_arch = "x86"))]
+    #[cfg(not(word32))]
     {
         exp_to = 5;
     }
-    #[cfg(target_arch = "x86")]
+    #[cfg(word32)]
     {
         exp_to = 3;
     }
@@ -952,6 +954,41 @@ fn test_tanh_atanh() {
     }
 }
 
+#[test]
+fn test_faithful_rounding() {
+    let prec_rng = get_prec_rng();
+    let mut cc = Consts::new().unwrap();
+
+    // the result with rounding mode None is either of the results rounded downwards or upwards
+    fn check<F>(mut f: F)
+    where
+        F: FnMut(RoundingMode) -> BigFloatNumber,
+    {
+        let d = f(RoundingMode::None);
+        let lo = f(RoundingMode::Down);
+        let hi = f(RoundingMode::Up);
+        assert!(d.cmp(&lo) == 0 || d.cmp(&hi) == 0, "{:?}", d);
+    }
+
+    for _ in 0..100 {
+        let p = (random::<usize>() % prec_rng + 1) * WORD_BIT_SIZE;
+
+        let d1 = BigFloatNumber::random_normal(p, -3, 3).unwrap();
+        let d2 = d1.abs().unwrap();
+        let d3 = BigFloatNumber::random_normal(p, -3, 3).unwrap();
+
+        check(|rm| d1.mul(&d3, p, rm).unwrap());
+        check(|rm| d1.div(&d3, p, rm).unwrap());
+        check(|rm| d2.sqrt(p, rm).unwrap());
+        check(|rm| d1.exp(p, rm, &mut cc).unwrap());
+        check(|rm| d2.ln(p, rm, &mut cc).unwrap());
+        check(|rm| d2.pow(&d3, p, rm, &mut cc).unwrap());
+        check(|rm| d1.sin(p, rm, &mut cc).unwrap());
+        check(|rm| d1.atan(p, rm, &mut cc).unwrap());
+        check(|rm| d1.tanh(p, rm, &mut cc).unwrap());
+    }
+}
+
 // Test operations error.
 /* #[test]
 fn test_err() {
@@ -20,6 +20,8 @@ pub struct ParserState<'a> {
     e: isize,
     inf: bool,
     nan: bool,
+    exp_overflow: bool,
+    exp_underflow: bool,
 }
 
 impl<'a> ParserState<'a> {
@@ -33,6 +35,8 @@ impl<'a> ParserState<'a> {
             e: 0,
             inf: false,
             nan: true,
+            exp_overflow: false,
+            exp_underflow: false,
         }
     }
 
@@ -55,6 +59,16 @@ impl<'a> ParserState<'a> {
         self.nan
     }
 
+    /// Returns true if the number is Inf because its exponent is greater than the maximum exponent.
+    pub fn is_exp_overflow(&self) -> bool {
+        self.exp_overflow
+    }
+
+    /// Returns true if the number is zero because its exponent is smaller than the minimum exponent.
+    pub fn is_exp_underflow(&self) -> bool {
+        self.exp_underflow
+    }
+
     pub fn sign(&self) -> Sign {
         self.sign
     }
@@ -146,14 +160,20 @@ fn parse_num(parser_state: &mut ParserState, rdx: Radix) -> Result<(), Error> {
             parser_state.e = parser_state.e.saturating_add(int_len as isize);
         }
 
-        if parser_state.e < EXPONENT_MIN as isize {
+        let is_zero = parser_state.mantissa_bytes.iter().all(|&d| d == 0);
+
+        if parser_state.e < EXPONENT_MIN as isize
+            || (is_zero && parser_state.e > EXPONENT_MAX as isize)
+        {
             let mut zero = Vec::new();
             zero.try_reserve_exact(1)?;
             zero.push(0);
             parser_state.mantissa_bytes = zero;
             parser_state.e = 0;
+            parser_state.exp_underflow = !is_zero;
         } else if parser_state.e > EXPONENT_MAX as isize {
             parser_state.inf = true;
+            parser_state.exp_overflow = true;
         }
     } else if skip_cnt1 > 0 {
         // just zeroes
@@ -350,6 +370,7 @@ mod tests {
                 let ps = parse(&numstr, Radix::Dec).unwrap();
 
                 assert!(ps.is_inf());
+                assert!(!ps.is_exp_overflow());
                 assert!(ps.sign() == expected_signs[i]);
                 assert!(!ps.is_nan());
             }
@@ -385,29 +406,49 @@ mod tests {
 
         // large exp
         let numstr;
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(any(
+            target_arch = "x86",
+            target_arch = "wasm32",
+            feature = "portable",
+            feature = "exp64"
+        )))]
         {
             numstr = "abc.def09123e_e+7FFFFFFF";
         }
-        #[cfg(target_arch = "x86")]
+        #[cfg(any(target_arch = "x86", target_arch = "wasm32", feature = "portable"))]
         {
             numstr = "abc.def09123e_e+1FFFFFFF";
         }
+        #[cfg(feature = "exp64")]
+        {
+            numstr = "abc.def09123e_e+1FFFFFFFFFFFFFFF";
+        }
         let ps = parse(numstr, Radix::Hex).unwrap();
         assert!(ps.is_inf());
+        assert!(ps.is_exp_overflow());
         assert!(ps.sign().is_positive());
 
         let numstr;
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(any(
+            target_arch = "x86",
+            target_arch = "wasm32",
+            feature = "portable",
+            feature = "exp64"
+        )))]
         {
             numstr = "-abc.def09123e_e+7FFFFFFF";
         }
-        #[cfg(target_arch = "x86")]
+        #[cfg(any(target_arch = "x86", target_arch = "wasm32", feature = "portable"))]
         {
             numstr = "-abc.def09123e_e+1FFFFFFF";
         }
+        #[cfg(feature = "exp64")]
+        {
+            numstr = "-abc.def09123e_e+1FFFFFFFFFFFFFFF";
+        }
         let ps = parse(numstr, Radix::Hex).unwrap();
         assert!(ps.is_inf());
+        assert!(ps.is_exp_overflow());
         assert!(!ps.is_nan());
         assert!(ps.sign().is_negative());
 
@@ -417,49 +458,87 @@ mod tests {
         )
         .unwrap();
         assert!(ps.is_inf());
+        assert!(ps.is_exp_overflow());
         assert!(!ps.is_nan());
         assert!(ps.sign().is_negative());
 
         let numstr;
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(any(
+            target_arch = "x86",
+            target_arch = "wasm32",
+            feature = "portable",
+            feature = "exp64"
+        )))]
         {
             numstr = "0.0000abc_e+7FFFFFFF";
         }
-        #[cfg(target_arch = "x86")]
+        #[cfg(any(target_arch = "x86", target_arch = "wasm32", feature = "portable"))]
         {
             numstr = "0.0000abc_e+1FFFFFFF";
         }
+        #[cfg(feature = "exp64")]
+        {
+            numstr = "0.0000abc_e+1FFFFFFFFFFFFFFF";
+        }
         let ps = parse(numstr, Radix::Hex).unwrap();
         assert!(!ps.is_inf());
         assert!(!ps.is_nan());
         let (m, _s, e) = ps.raw_parts();
         assert_eq!(m, [0, 0, 0, 0, 0xa, 0xb, 0xc]);
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(any(
+            target_arch = "x86",
+            target_arch = "wasm32",
+            feature = "portable",
+            feature = "exp64"
+        )))]
         {
             assert_eq!(e, 0x7FFFFFFF);
         }
-        #[cfg(target_arch = "x86")]
+        #[cfg(any(target_arch = "x86", target_arch = "wasm32", feature = "portable"))]
         {
             assert_eq!(e, 0x1FFFFFFF);
         }
+        #[cfg(feature = "exp64")]
+        {
+            assert_eq!(e, 0x1FFFFFFFFFFFFFFF);
+        }
 
         // small exp
-        let ps = parse("abc.def09123e_e-80000004", Radix::Hex).unwrap();
+        let numstr;
+        #[cfg(not(feature = "exp64"))]
+        {
+            numstr = "abc.def09123e_e-80000004";
+        }
+        #[cfg(feature = "exp64")]
+        {
+            numstr = "abc.def09123e_e-8000000000000004";
+        }
+        let ps = parse(numstr, Radix::Hex).unwrap();
         assert!(!ps.is_inf());
         assert!(!ps.is_nan());
         let (m, _s, e) = ps.raw_parts();
         assert_eq!(m.iter().filter(|&&x| x != 0).count(), 0);
         assert!(e == 0);
+        assert!(ps.is_exp_underflow());
 
         let numstr;
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(any(
+            target_arch = "x86",
+            target_arch = "wasm32",
+            feature = "portable",
+            feature = "exp64"
+        )))]
         {
             numstr = "0.0000abcdef09123e_e-80000000";
         }
-        #[cfg(target_arch = "x86")]
+        #[cfg(any(target_arch = "x86", target_arch = "wasm32", feature = "portable"))]
         {
             numstr = "0.0000abcdef09123e_e-20000000";
         }
+        #[cfg(feature = "exp64")]
+        {
+            numstr = "0.0000abcdef09123e_e-2000000000000000";
+        }
         let ps = parse(numstr, Radix::Hex).unwrap();
         assert!(!ps.is_inf());
         assert!(!ps.is_nan());
@@ -468,14 +547,23 @@ mod tests {
             m,
             [0, 0, 0, 0, 0xa, 0xb, 0xc, 0xd, 0xe, 0xf, 0x0, 0x9, 0x1, 0x2, 0x3, 0xe]
         );
-        #[cfg(not(target_arch = "x86"))]
+        #[cfg(not(any(
+            target_arch = "x86",
+            target_arch = "wasm32",
+            feature = "portable",
+            feature = "exp64"
+        )))]
         {
             assert_eq!(e, -0x80000000);
         }
-        #[cfg(target_arch = "x86")]
+        #[cfg(any(target_arch = "x86", target_arch = "wasm32", feature = "portable"))]
         {
             assert_eq!(e, -0x20000000);
         }
+        #[cfg(feature = "exp64")]
+        {
+            assert_eq!(e, -0x2000000000000000);
+        }
 
         let ps = parse(
             "abc.def09123e_e-ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
@@ -487,5 +575,6 @@ mod tests {
         let (m, _s, e) = ps.raw_parts();
         assert_eq!(m.iter().filter(|&&x| x != 0).count(), 0);
         assert!(e == 0);
+        assert!(ps.is_exp_underflow());
     }
 }
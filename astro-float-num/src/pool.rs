@@ -0,0 +1,202 @@
+//! Pool of numbers for reusing allocated memory.
+
+use crate::ext::BigFloat;
+use crate::Error;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A pool of numbers which keeps the memory allocated for mantissas of numbers returned to it,
+/// and reuses the memory for numbers taken from it.
+///
+/// Iterative algorithms, which create and drop many temporary numbers of the same precision,
+/// can take the numbers from the pool and put them back when they are not needed anymore,
+/// so that the memory is allocated only once.
+///
+/// ## Examples
+///
+/// ```
+/// use astro_float_num::{BigFloat, BigFloatPool};
+///
+/// let mut pool = BigFloatPool::with_capacity(2, 256).unwrap();
+///
+/// // zero with precision 128 using the memory of a number from the pool
+/// let x = pool.take(128);
+/// assert!(x.is_zero());
+/// assert_eq!(pool.len(), 1);
+///
+/// // copy of a number using the memory of a number from the pool
+/// let y = BigFloat::from_word(3, 256);
+/// let z = pool.take_copy(&y);
+/// assert_eq!(y, z);
+/// assert!(pool.is_empty());
+///
+/// pool.put(x);
+/// pool.put(z);
+/// assert_eq!(pool.len(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct BigFloatPool {
+    free: Vec<BigFloat>,
+}
+
+impl BigFloatPool {
+    /// Returns a new empty pool.
+    pub fn new() -> Self {
+        BigFloatPool { free: Vec::new() }
+    }
+
+    /// Returns a new pool containing `n` numbers with precision `p`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the precision is incorrect.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn with_capacity(n: usize, p: usize) -> Result<Self, Error> {
+        let mut free = Vec::new();
+        free.try_reserve_exact(n)?;
+
+        for _ in 0..n {
+            let x = BigFloat::new(p);
+            if let Some(e) = x.err() {
+                return Err(e);
+            }
+            free.push(x);
+        }
+
+        Ok(BigFloatPool { free })
+    }
+
+    /// Takes a number from the pool and returns it set to zero with precision `p`.
+    /// The number is chosen so that its memory is sufficient for precision `p`, if such number is in the pool.
+    /// If the pool is empty, a new number is allocated.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn take(&mut self, p: usize) -> BigFloat {
+        match self.find(p) {
+            Some(i) => {
+                let mut x = self.free.swap_remove(i);
+                x.reset(p);
+                x
+            }
+            None => BigFloat::new(p),
+        }
+    }
+
+    /// Takes a number from the pool and returns it set to the value and precision of `x`.
+    /// The number is chosen in the same way as in `take`.
+    pub fn take_copy(&mut self, x: &BigFloat) -> BigFloat {
+        match self.find(x.mantissa_max_bit_len().unwrap_or(0)) {
+            Some(i) => {
+                let mut ret = self.free.swap_remove(i);
+                ret.assign(x);
+                ret
+            }
+            None => x.clone(),
+        }
+    }
+
+    /// Puts the number `x` to the pool, so that its memory can be reused.
+    /// Inf and NaN are dropped, because they do not hold memory.
+    pub fn put(&mut self, x: BigFloat) {
+        if x.allocated_bytes() > 0 && self.free.try_reserve(1).is_ok() {
+            self.free.push(x);
+        }
+    }
+
+    /// Returns the number of numbers in the pool.
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Returns true if the pool contains no numbers.
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    /// Drops all numbers in the pool, releasing their memory.
+    pub fn clear(&mut self) {
+        self.free.clear();
+    }
+
+    // Returns the index of the number with the smallest allocated memory sufficient for precision `p`,
+    // or the number with the largest allocated memory if none is sufficient, or None if the pool is empty.
+    fn find(&self, p: usize) -> Option<usize> {
+        let sz = p.div_ceil(8);
+
+        let mut fit: Option<(usize, usize)> = None;
+        let mut largest: Option<(usize, usize)> = None;
+
+        for (i, x) in self.free.iter().enumerate() {
+            let b = x.allocated_bytes();
+
+            if b >= sz && fit.is_none_or(|(_, fb)| b < fb) {
+                fit = Some((i, b));
+            }
+
+            if largest.is_none_or(|(_, lb)| b > lb) {
+                largest = Some((i, b));
+            }
+        }
+
+        fit.or(largest).map(|(i, _)| i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::RoundingMode;
+
+    #[test]
+    fn test_pool() {
+        let mut pool = BigFloatPool::with_capacity(3, 128).unwrap();
+        assert_eq!(pool.len(), 3);
+
+        // memory of the taken numbers is reused
+        let mut xs: Vec<BigFloat> = (0..3).map(|_| pool.take(64)).collect();
+        assert!(pool.is_empty());
+        for x in xs.iter() {
+            assert!(x.is_zero());
+            assert_eq!(x.mantissa_max_bit_len(), Some(64));
+            assert_eq!(x.allocated_bytes(), 128 / 8);
+        }
+
+        let y =
+            BigFloat::from_word(7, 512).div(&BigFloat::from_word(3, 64), 512, RoundingMode::ToEven);
+
+        for x in xs.drain(..) {
+            pool.put(x);
+        }
+
+        // the number with the most memory is grown if none is large enough
+        let z = pool.take_copy(&y);
+        assert_eq!(z, y);
+        assert!(z.inexact());
+        assert_eq!(z.mantissa_max_bit_len(), Some(512));
+        assert_eq!(pool.len(), 2);
+
+        pool.put(z);
+        let z = pool.take(320);
+        assert_eq!(z.allocated_bytes(), 512 / 8);
+        assert_eq!(z.mantissa_max_bit_len(), Some(320));
+        assert!(z.is_zero());
+        assert!(!z.inexact());
+
+        // Inf and NaN are not pooled
+        pool.put(crate::INF_POS);
+        pool.put(crate::NAN);
+        assert_eq!(pool.len(), 2);
+
+        // empty pool allocates
+        pool.clear();
+        assert!(pool.take(128).is_zero());
+        assert!(pool.take(usize::MAX).is_nan());
+        assert_eq!(
+            BigFloatPool::with_capacity(1, usize::MAX).unwrap_err(),
+            Error::InvalidArgument
+        );
+    }
+}
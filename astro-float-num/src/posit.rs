@@ -0,0 +1,302 @@
+//! Conversion between BigFloat and the posit formats posit8, posit16, and posit32.
+//!
+//! The formats follow the posit standard (2022): the number of exponent bits is 2 for all sizes,
+//! rounding is to nearest with ties to even performed on the encoding,
+//! nonzero values never round to zero, and finite values never round to NaR.
+
+use crate::BigFloat;
+use crate::BigUInt;
+use crate::Error;
+use crate::RoundingMode;
+use crate::Sign;
+use crate::Word;
+
+// Number of exponent bits.
+const ES: u32 = 2;
+
+// Number of fraction bits taken from the mantissa before rounding.
+const FRAC_BITS: u32 = 63;
+
+// Returns the posit with `n` bits closest to `x`.
+fn encode(x: &BigFloat, n: u32) -> u64 {
+    let nar = 1u64 << (n - 1);
+    let mask = (nar << 1).wrapping_sub(1);
+
+    if x.is_nan() || x.is_inf() {
+        return nar;
+    }
+
+    if x.is_zero() {
+        return 0;
+    }
+
+    // minpos = 2^-smax, maxpos = 2^smax
+    let smax = ((n - 2) << ES) as isize;
+
+    let v = match x.exponent() {
+        Some(e) if (e as isize) <= -smax => 1,
+        Some(e) if (e as isize) > smax => nar - 1,
+        _ => match encode_abs(x, n, smax) {
+            Ok(v) => v,
+            Err(_) => return nar,
+        },
+    };
+
+    if x.is_negative() {
+        v.wrapping_neg() & mask
+    } else {
+        v
+    }
+}
+
+// Returns the posit with `n` bits closest to `|x|`, where minpos <= |x| < 2*maxpos.
+fn encode_abs(x: &BigFloat, n: u32, smax: isize) -> Result<u64, Error> {
+    let (_, m, ex) = x.to_int_ratio()?;
+
+    let m = BigUInt::from_words(&m)?;
+    let l = m.bit_len();
+
+    // the mantissa normalized to FRAC_BITS + 1 bits, and an indicator of any discarded nonzero bits
+    let (t, sticky) = if l > FRAC_BITS as usize + 1 {
+        let sh = l - FRAC_BITS as usize - 1;
+        let t = m.shr(sh)?;
+        let sticky = t.shl(sh)? != m;
+        (t.to_u64().ok_or(Error::InvalidArgument)?, sticky)
+    } else {
+        (
+            m.to_u64().ok_or(Error::InvalidArgument)? << (FRAC_BITS as usize + 1 - l),
+            false,
+        )
+    };
+
+    // |x| = 1.f * 2^s
+    let s = (l as isize - 1 + ex).clamp(-smax, smax);
+
+    let k = s.div_euclid(1 << ES);
+    let e = s.rem_euclid(1 << ES) as u128;
+
+    let (regime, regime_len) = if k >= 0 {
+        (((1u128 << (k + 1)) - 1) << 1, k as u32 + 2)
+    } else {
+        (1u128, (-k) as u32 + 1)
+    };
+
+    let bits = (((regime << ES) | e) << FRAC_BITS) | (t as u128 & ((1 << FRAC_BITS) - 1));
+    let sh = regime_len + ES + FRAC_BITS - (n - 1);
+
+    let mut v = (bits >> sh) as u64;
+    let guard = (bits >> (sh - 1)) & 1 == 1;
+    let rest = sticky || bits & ((1 << (sh - 1)) - 1) != 0;
+
+    if guard && (rest || v & 1 == 1) {
+        v += 1;
+    }
+
+    Ok(v)
+}
+
+// Returns the value of the posit `v` with `n` bits with precision `p`.
+fn decode(v: u64, n: u32, p: usize) -> BigFloat {
+    let nar = 1u64 << (n - 1);
+    let mask = (nar << 1).wrapping_sub(1);
+
+    if v == nar {
+        return BigFloat::nan(None);
+    }
+
+    if v == 0 {
+        return BigFloat::new(p);
+    }
+
+    let (s, v) = if v & nar != 0 { (Sign::Neg, v.wrapping_neg() & mask) } else { (Sign::Pos, v) };
+
+    let w = n - 1;
+    let r0 = (v >> (w - 1)) & 1;
+
+    let mut run = 1;
+    while run < w && (v >> (w - 1 - run)) & 1 == r0 {
+        run += 1;
+    }
+
+    let k = if r0 == 1 { run as isize - 1 } else { -(run as isize) };
+
+    // exponent and fraction bits following the regime and its terminating bit
+    let rest_len = w.saturating_sub(run + 1);
+    let rest = v & ((1 << rest_len) - 1);
+
+    let (e, f, fl) = if rest_len >= ES {
+        let fl = rest_len - ES;
+        (rest >> fl, rest & ((1 << fl) - 1), fl)
+    } else {
+        (rest << (ES - rest_len), 0, 0)
+    };
+
+    let m = (1 << fl) | f;
+    let scale = (k << ES) + e as isize - fl as isize;
+
+    let mut x = BigFloat::from_int_ratio(s, &[m as Word], scale);
+
+    if let Err(err) = x.set_precision(p, RoundingMode::None) {
+        return BigFloat::nan(Some(err));
+    }
+
+    x
+}
+
+impl BigFloat {
+    /// Converts the number to the posit8 format.
+    /// The number is rounded to nearest with ties to even.
+    /// Nonzero numbers with the absolute value smaller than the smallest positive posit become the smallest posit of the same sign,
+    /// and numbers with the absolute value larger than the largest posit become the largest posit of the same sign.
+    /// NaN and Inf are converted to NaR.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// let n = BigFloat::from_word(2, 64);
+    ///
+    /// assert_eq!(n.to_posit8(), 0x48);
+    /// assert_eq!(BigFloat::from_posit8(0x48, 64), n);
+    /// ```
+    pub fn to_posit8(&self) -> u8 {
+        encode(self, 8) as u8
+    }
+
+    /// Converts the number to the posit16 format.
+    /// The number is rounded to nearest with ties to even.
+    /// Nonzero numbers with the absolute value smaller than the smallest positive posit become the smallest posit of the same sign,
+    /// and numbers with the absolute value larger than the largest posit become the largest posit of the same sign.
+    /// NaN and Inf are converted to NaR.
+    pub fn to_posit16(&self) -> u16 {
+        encode(self, 16) as u16
+    }
+
+    /// Converts the number to the posit32 format.
+    /// The number is rounded to nearest with ties to even.
+    /// Nonzero numbers with the absolute value smaller than the smallest positive posit become the smallest posit of the same sign,
+    /// and numbers with the absolute value larger than the largest posit become the largest posit of the same sign.
+    /// NaN and Inf are converted to NaR.
+    pub fn to_posit32(&self) -> u32 {
+        encode(self, 32) as u32
+    }
+
+    /// Constructs a number with precision `p` from the posit8 value `v`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`, and the conversion is always exact.
+    /// NaR is converted to NaN.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn from_posit8(v: u8, p: usize) -> Self {
+        decode(v as u64, 8, p)
+    }
+
+    /// Constructs a number with precision `p` from the posit16 value `v`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`, and the conversion is always exact.
+    /// NaR is converted to NaN.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn from_posit16(v: u16, p: usize) -> Self {
+        decode(v as u64, 16, p)
+    }
+
+    /// Constructs a number with precision `p` from the posit32 value `v`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`, and the conversion is always exact.
+    /// NaR is converted to NaN.
+    /// The function returns NaN if the precision `p` is incorrect.
+    pub fn from_posit32(v: u32, p: usize) -> Self {
+        decode(v as u64, 32, p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Consts;
+    use crate::INF_NEG;
+    use crate::INF_POS;
+    use crate::NAN;
+
+    #[test]
+    fn test_posit() {
+        let p = 256;
+        let rm = RoundingMode::ToEven;
+
+        // known encodings
+        for (f, v) in [
+            (1.0, 0x40),
+            (-1.0, 0xC0),
+            (2.0, 0x48),
+            (0.5, 0x38),
+            (16777216.0, 0x7F),
+            (1.0 / 16777216.0, 0x01),
+        ] {
+            let x = BigFloat::from_f64(f, 64);
+            assert_eq!(x.to_posit8(), v, "{}", f);
+            assert_eq!(BigFloat::from_posit8(v, p), x, "{}", f);
+        }
+
+        assert_eq!(BigFloat::from_f64(1.0, 64).to_posit16(), 0x4000);
+        assert_eq!(BigFloat::from_f64(-1.0, 64).to_posit32(), 0xC000_0000);
+
+        let mut cc = Consts::new().unwrap();
+        assert_eq!(cc.pi(p, rm).to_posit32(), 0x4C90_FDAA);
+
+        // special values and saturation
+        assert_eq!(NAN.to_posit8(), 0x80);
+        assert_eq!(INF_POS.to_posit16(), 0x8000);
+        assert_eq!(INF_NEG.to_posit32(), 0x8000_0000);
+        assert!(BigFloat::from_posit8(0x80, p).is_nan());
+        assert!(BigFloat::from_posit8(0, p).is_zero());
+
+        assert_eq!(BigFloat::from_f64(1e30, 64).to_posit8(), 0x7F);
+        assert_eq!(BigFloat::from_f64(-1e30, 64).to_posit8(), 0x81);
+        assert_eq!(BigFloat::from_f64(1e-30, 64).to_posit8(), 0x01);
+        assert_eq!(BigFloat::from_f64(-1e-30, 64).to_posit8(), 0xFF);
+        assert_eq!(BigFloat::from_f64(1e300, 64).to_posit32(), 0x7FFF_FFFF);
+
+        let mut tiny = BigFloat::from_word(1, 64);
+        tiny.set_exponent(crate::EXPONENT_MIN);
+        assert_eq!(tiny.to_posit16(), 0x0001);
+
+        // every posit16 is converted exactly, the order of values is the order of encodings,
+        // and midpoints between neighbours in the same binade are rounded to the even encoding
+        let mut prev: Option<BigFloat> = None;
+        for i in i16::MIN + 1..=i16::MAX {
+            let v = i as u16;
+            let x = BigFloat::from_posit16(v, p);
+            assert_eq!(x.to_posit16(), v);
+
+            if let Some(a) = prev {
+                assert!(a < x);
+
+                if a.exponent() == x.exponent()
+                    && !a.is_zero()
+                    && a.is_positive() == x.is_positive()
+                {
+                    let mid = a.add(&x, p, rm).div(&BigFloat::from_word(2, p), p, rm);
+                    let d = mid.abs().div(&BigFloat::from_word(1 << 40, p), p, rm);
+                    let u = v.wrapping_sub(1);
+                    let even = if u & 1 == 0 { u } else { v };
+
+                    assert_eq!(mid.to_posit16(), even);
+                    assert_eq!(mid.add(&d, p, rm).to_posit16(), v);
+                    assert_eq!(mid.sub(&d, p, rm).to_posit16(), u);
+                }
+            }
+
+            prev = Some(x);
+        }
+
+        // posit32 round trip
+        for v in [
+            1u32,
+            0x0000_0100,
+            0x3FFF_FFFF,
+            0x4C90_FDAA,
+            0x7FFF_FFFE,
+            0x8000_0001,
+            0xB000_0003,
+        ] {
+            assert_eq!(BigFloat::from_posit32(v, p).to_posit32(), v);
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! Evaluation of power series with user-supplied coefficients.
+//!
+//! The series is evaluated by the same engine which is used for the elementary functions of the library.
+//! Depending on the number of terms, the coefficients are summed using Horner's method, the linear summation,
+//! or the rectangular splitting method. The working precision is increased until the result can be correctly rounded.
+
+use crate::common::util::log2_ceil;
+use crate::common::util::round_p;
+use crate::num::BigFloatNumber;
+use crate::ops::series::series_niter;
+use crate::ops::series::series_run;
+use crate::ops::series::PolycoeffGen;
+use crate::BigFloat;
+use crate::Error;
+use crate::RoundingMode;
+use crate::WORD_BIT_SIZE;
+
+/// Generator of the coefficients of a power series.
+///
+/// The absolute value of each coefficient must not be greater than 1, or, if the coefficients are divisors, not less than 1.
+/// Each coefficient must be accurate to within one unit in the last place of the precision requested by `start`.
+pub trait SeriesCoeffs {
+    /// Restarts generation of the coefficients from the first one with precision `p`.
+    /// The function is called once for each attempt to compute the series with a given working precision.
+    fn start(&mut self, p: usize);
+
+    /// Returns the next coefficient.
+    fn next(&mut self) -> BigFloat;
+
+    /// Returns true if the terms of the series are the powers of `x` divided by the coefficients, e.g. factorials.
+    /// Returns false if the terms are the powers of `x` multiplied by the coefficients.
+    fn is_div(&self) -> bool {
+        false
+    }
+}
+
+/// Computes the sum of the power series `c0*x^k + c1*x^(k+s) + c2*x^(k+2*s) + ...`, where `k` is `pwr_first`, `s` is `pwr_step`,
+/// and `c0`, `c1`, ... are the coefficients generated by `coeffs`.
+/// The result has precision `p` and is rounded using the rounding mode `rm`.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+/// The function returns NaN if `|x| >= 1/2`, if `x` is NaN, if `pwr_step` is 0, if any of the coefficients is NaN or Inf,
+/// or if the precision `p` is incorrect.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::BigFloat;
+/// # use astro_float_num::Consts;
+/// # use astro_float_num::RoundingMode;
+/// # use astro_float_num::series::{power_series, SeriesCoeffs};
+/// // Factorials 1!, 2!, 3!, ...
+/// struct Factorials {
+///     n: u64,
+///     f: BigFloat,
+/// }
+///
+/// impl SeriesCoeffs for Factorials {
+///     fn start(&mut self, _p: usize) {
+///         self.n = 0;
+///         self.f = BigFloat::from_word(1, 64);
+///     }
+///
+///     fn next(&mut self) -> BigFloat {
+///         self.n += 1;
+///         self.f = self.f.mul_full_prec(&BigFloat::from_u64(self.n, 64));
+///         self.f.clone()
+///     }
+///
+///     fn is_div(&self) -> bool {
+///         true
+///     }
+/// }
+///
+/// let mut cc = Consts::new().expect("Constants cache initialized");
+/// let rm = RoundingMode::ToEven;
+/// let mut coeffs = Factorials { n: 0, f: BigFloat::from_word(1, 64) };
+///
+/// // e^x - 1 = x/1! + x^2/2! + x^3/3! + ...
+/// let x = BigFloat::from_f64(0.3, 256);
+/// let expm1 = power_series(&x, 1, 1, &mut coeffs, 256, rm);
+///
+/// let mut expected = x.exp(512, RoundingMode::None, &mut cc).sub(&BigFloat::from_word(1, 64), 512, RoundingMode::None);
+/// expected.set_precision(256, rm).unwrap();
+///
+/// assert_eq!(expm1, expected);
+/// ```
+pub fn power_series<T: SeriesCoeffs>(
+    x: &BigFloat,
+    pwr_first: usize,
+    pwr_step: usize,
+    coeffs: &mut T,
+    p: usize,
+    rm: RoundingMode,
+) -> BigFloat {
+    if x.is_nan() || x.is_inf() || pwr_step == 0 {
+        return BigFloat::nan(Some(Error::InvalidArgument));
+    }
+
+    match x.clone().try_into_number() {
+        Ok(v) => BigFloat::result_to_ext(
+            power_series_num(&v, pwr_first, pwr_step, coeffs, p, rm),
+            false,
+            true,
+        ),
+        Err(e) => BigFloat::nan(Some(e)),
+    }
+}
+
+// Adapter of the user coefficient generator to the series engine.
+struct CoeffsAdapter<'a, T: SeriesCoeffs> {
+    coeffs: &'a mut T,
+    cur: BigFloatNumber,
+}
+
+impl<'a, T: SeriesCoeffs> PolycoeffGen for CoeffsAdapter<'a, T> {
+    fn next(&mut self, _rm: RoundingMode) -> Result<&BigFloatNumber, Error> {
+        self.cur = self.coeffs.next().try_into_number()?;
+
+        if self.cur.is_zero() && self.is_div() {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(&self.cur)
+    }
+
+    fn iter_cost(&self) -> usize {
+        0
+    }
+
+    fn is_div(&self) -> bool {
+        self.coeffs.is_div()
+    }
+}
+
+fn power_series_num<T: SeriesCoeffs>(
+    x: &BigFloatNumber,
+    pwr_first: usize,
+    pwr_step: usize,
+    coeffs: &mut T,
+    p: usize,
+    rm: RoundingMode,
+) -> Result<BigFloatNumber, Error> {
+    let p = round_p(p);
+    BigFloatNumber::p_assertion(p)?;
+
+    if !x.is_zero() && x.exponent() >= 0 {
+        return Err(Error::InvalidArgument);
+    }
+
+    // x = f*2^(-m), where 0.5 <= f < 1
+    let m = if x.is_zero() { p } else { -(x.exponent() as isize) as usize };
+
+    let mut p_inc = WORD_BIT_SIZE;
+    let mut p_wrk = p + p_inc;
+
+    loop {
+        let niter = (series_niter(p_wrk, m) / pwr_step).max(1);
+
+        // each term has the relative error of a few units in the last place,
+        // the sum is at most 1 by absolute value, and the terms are decreasing
+        let err_bits = log2_ceil(niter + 1) + 4;
+        let p_ext = p_wrk + err_bits;
+
+        coeffs.start(p_ext);
+
+        let mut gen = CoeffsAdapter {
+            coeffs,
+            cur: BigFloatNumber::new(WORD_BIT_SIZE)?,
+        };
+
+        let mut x_ext = x.clone()?;
+        x_ext.set_precision(p_ext, RoundingMode::None)?;
+
+        let x_step = x_ext.powi(pwr_step, p_ext, RoundingMode::None)?;
+
+        // the sum is less than 2^e_max by absolute value
+        let (acc, x_first, e_max) = if pwr_first == 0 {
+            let is_div = gen.is_div();
+            let c0 = gen.next(RoundingMode::None)?;
+
+            let mut acc =
+                if is_div { c0.reciprocal(p_ext, RoundingMode::None)? } else { c0.clone()? };
+            acc.set_precision(p_ext, RoundingMode::None)?;
+
+            (acc, x_step.clone()?, 1)
+        } else {
+            let x_first = x_ext.powi(pwr_first, p_ext, RoundingMode::None)?;
+            let e_max = x_first.exponent() as isize;
+
+            (BigFloatNumber::new(p_ext)?, x_first, e_max)
+        };
+
+        let mut ret = series_run(acc, x_first, x_step, niter, &mut gen)?;
+
+        if !ret.inexact() {
+            ret.set_precision(p, rm)?;
+            return Ok(ret);
+        }
+
+        if !ret.is_zero() {
+            // cancellation in the sum reduces the number of correct bits
+            let s = p_ext as isize - err_bits as isize - (e_max - ret.exponent() as isize).max(0);
+
+            if s > p as isize && ret.try_set_precision(p, rm, s as usize)? {
+                return Ok(ret);
+            }
+        }
+
+        p_wrk += p_inc;
+        p_inc = round_p(p_wrk / 5);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Consts;
+
+    // Divisors 1, 3, 5, ... for atanh.
+    struct OddNumbers {
+        n: u64,
+    }
+
+    impl SeriesCoeffs for OddNumbers {
+        fn start(&mut self, _p: usize) {
+            self.n = 1;
+        }
+
+        fn next(&mut self) -> BigFloat {
+            let ret = BigFloat::from_u64(self.n, 64);
+            self.n += 2;
+            ret
+        }
+
+        fn is_div(&self) -> bool {
+            true
+        }
+    }
+
+    // Coefficients 1, -1/2, 1/3, -1/4, ... for ln(1 + x).
+    struct Alternating {
+        n: u64,
+        p: usize,
+    }
+
+    impl SeriesCoeffs for Alternating {
+        fn start(&mut self, p: usize) {
+            self.n = 0;
+            self.p = p;
+        }
+
+        fn next(&mut self) -> BigFloat {
+            self.n += 1;
+            let ret = BigFloat::from_word(1, 64).div(
+                &BigFloat::from_u64(self.n, 64),
+                self.p,
+                RoundingMode::ToEven,
+            );
+            if self.n % 2 == 0 {
+                -ret
+            } else {
+                ret
+            }
+        }
+    }
+
+    #[test]
+    fn test_power_series() {
+        let mut cc = Consts::new().unwrap();
+
+        for p in [64, 128, 320, 1024, 5120] {
+            for x in [0.3, -0.1, 1e-5, -0.4999] {
+                let x = BigFloat::from_f64(x, p);
+
+                for rm in [
+                    RoundingMode::ToEven,
+                    RoundingMode::Up,
+                    RoundingMode::Down,
+                    RoundingMode::FromZero,
+                ] {
+                    let mut coeffs = OddNumbers { n: 1 };
+                    let ret = power_series(&x, 1, 2, &mut coeffs, p, rm);
+                    let mut expected = x.atanh(p + 256, RoundingMode::None, &mut cc);
+                    expected.set_precision(p, rm).unwrap();
+                    assert_eq!(ret, expected);
+
+                    let mut coeffs = Alternating { n: 0, p };
+                    let ret = power_series(&x, 1, 1, &mut coeffs, p, rm);
+                    let mut expected = x
+                        .add(&BigFloat::from_word(1, 64), p + 256, RoundingMode::None)
+                        .ln(p + 256, RoundingMode::None, &mut cc);
+                    expected.set_precision(p, rm).unwrap();
+                    assert_eq!(ret, expected);
+                }
+            }
+        }
+
+        // exact sum
+        let p = 128;
+        let rm = RoundingMode::Up;
+        let mut coeffs = OddNumbers { n: 1 };
+        let ret = power_series(&BigFloat::new(p), 0, 1, &mut coeffs, p, rm);
+        assert_eq!(ret, BigFloat::from_word(1, p));
+        let ret = power_series(&BigFloat::new(p), 1, 1, &mut coeffs, p, rm);
+        assert!(ret.is_zero());
+
+        // invalid arguments
+        let half = BigFloat::from_f64(0.5, p);
+        assert!(power_series(&half, 1, 1, &mut coeffs, p, rm).is_nan());
+        assert!(power_series(&crate::NAN, 1, 1, &mut coeffs, p, rm).is_nan());
+        assert!(power_series(&crate::INF_POS, 1, 1, &mut coeffs, p, rm).is_nan());
+        let x = BigFloat::from_f64(0.25, p);
+        assert!(power_series(&x, 1, 0, &mut coeffs, p, rm).is_nan());
+        assert!(power_series(&x, 1, 1, &mut coeffs, usize::MAX, rm).is_nan());
+    }
+}
@@ -0,0 +1,341 @@
+//! Conversion of angles and times to and from sexagesimal notation,
+//! i.e. degrees-minutes-seconds and hours-minutes-seconds.
+
+use crate::common::util::int_to_u128;
+use crate::defs::Error;
+use crate::defs::Radix;
+use crate::BigFloat;
+use crate::RoundingMode;
+use crate::WORD_BIT_SIZE;
+
+#[cfg(feature = "std")]
+use std::fmt::Write;
+
+#[cfg(not(feature = "std"))]
+use {alloc::string::String, core::fmt::Write};
+
+// Separators of degrees, minutes, and seconds.
+const DMS_SEPARATORS: [&[char]; 3] = [&['°', 'd', ':'], &['\'', '′', 'm', ':'], &['"', '″', 's']];
+
+// Separators of hours, minutes, and seconds.
+const HMS_SEPARATORS: [&[char]; 3] = [&['h', ':'], &['m', ':'], &['s']];
+
+impl BigFloat {
+    /// Formats the angle `self` in degrees as degrees, minutes, and seconds, e.g. `-12°34'56.789"`.
+    /// The seconds are rounded to `digits` decimal places using rounding mode `rm`.
+    /// The function returns None if `self` is NaN or Inf, if the number of degrees is larger than `u128::MAX` / 3600 / 10^`digits`,
+    /// or if `digits` is larger than 34.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let rm = RoundingMode::ToEven;
+    /// let n = BigFloat::from_f64(-12.5824425, 64);
+    ///
+    /// assert_eq!(n.to_dms(3, rm).unwrap(), "-12°34'56.793\"");
+    /// assert_eq!(BigFloat::from_dms("-12°34'56.793\"", 64, rm).to_dms(3, rm).unwrap(), "-12°34'56.793\"");
+    /// ```
+    pub fn to_dms(&self, digits: usize, rm: RoundingMode) -> Option<String> {
+        self.to_sexagesimal(digits, rm, ['°', '\'', '"'])
+    }
+
+    /// Formats the time `self` in hours as hours, minutes, and seconds, e.g. `5h04m07.25s`.
+    /// The seconds are rounded to `digits` decimal places using rounding mode `rm`.
+    /// The function returns None if `self` is NaN or Inf, if the number of hours is larger than `u128::MAX` / 3600 / 10^`digits`,
+    /// or if `digits` is larger than 34.
+    pub fn to_hms(&self, digits: usize, rm: RoundingMode) -> Option<String> {
+        self.to_sexagesimal(digits, rm, ['h', 'm', 's'])
+    }
+
+    /// Parses an angle in degrees, minutes, and seconds from the string `s` and returns the angle in degrees
+    /// with precision `p` rounded using rounding mode `rm`. The result is rounded once.
+    /// Degrees can be separated by `°`, `d`, or `:`, minutes by `'`, `′`, `m`, or `:`,
+    /// and seconds can be followed by `"`, `″`, or `s`. Whitespace is allowed after separators.
+    /// Minutes and seconds must be less than 60.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the string can't be parsed, or if the precision `p` is incorrect.
+    pub fn from_dms(s: &str, p: usize, rm: RoundingMode) -> Self {
+        Self::from_sexagesimal(s, &DMS_SEPARATORS, p, rm)
+    }
+
+    /// Parses a time in hours, minutes, and seconds from the string `s` and returns the time in hours
+    /// with precision `p` rounded using rounding mode `rm`. The result is rounded once.
+    /// Hours can be separated by `h` or `:`, minutes by `m` or `:`, and seconds can be followed by `s`.
+    /// Whitespace is allowed after separators. Minutes and seconds must be less than 60.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the string can't be parsed, or if the precision `p` is incorrect.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use astro_float_num::BigFloat;
+    /// # use astro_float_num::RoundingMode;
+    /// let rm = RoundingMode::ToEven;
+    ///
+    /// assert_eq!(BigFloat::from_hms("5h 30m 0s", 64, rm), BigFloat::from_f64(5.5, 64));
+    /// assert_eq!(BigFloat::from_hms("05:30:00", 64, rm), BigFloat::from_f64(5.5, 64));
+    /// assert!(BigFloat::from_hms("5h 60m 0s", 64, rm).is_nan());
+    /// ```
+    pub fn from_hms(s: &str, p: usize, rm: RoundingMode) -> Self {
+        Self::from_sexagesimal(s, &HMS_SEPARATORS, p, rm)
+    }
+
+    fn to_sexagesimal(&self, digits: usize, rm: RoundingMode, seps: [char; 3]) -> Option<String> {
+        let unit = 10u128.checked_pow(digits.try_into().ok()?)?;
+        let scale = unit.checked_mul(3600)?;
+
+        // the product is exact
+        let p = self.precision()?;
+        let x = self
+            .mul(
+                &BigFloat::from_u128(scale, 128),
+                p.saturating_add(128),
+                RoundingMode::None,
+            )
+            .round(0, rm);
+
+        let (m, _, s, e, _) = x.as_raw_parts()?;
+        let n = int_to_u128(m, e as isize)?;
+
+        let frac = n % unit;
+        let secs = n / unit;
+
+        let mut ret = String::new();
+
+        if s.is_negative() && n > 0 {
+            ret.push('-');
+        }
+
+        write!(
+            ret,
+            "{}{}{:02}{}{:02}",
+            secs / 3600,
+            seps[0],
+            secs / 60 % 60,
+            seps[1],
+            secs % 60
+        )
+        .ok()?;
+
+        if digits > 0 {
+            write!(ret, ".{:0width$}", frac, width = digits).ok()?;
+        }
+
+        ret.push(seps[2]);
+
+        Some(ret)
+    }
+
+    fn from_sexagesimal(s: &str, seps: &[&[char]; 3], p: usize, rm: RoundingMode) -> Self {
+        match SexagesimalParts::parse(s, seps) {
+            Some(parts) => parts.to_big_float(p, rm),
+            None => BigFloat::nan(Some(Error::InvalidArgument)),
+        }
+    }
+}
+
+// Components of a number in sexagesimal notation.
+struct SexagesimalParts<'a> {
+    neg: bool,
+    int: &'a str,
+    min: u128,
+    sec: u128,
+    frac: &'a str,
+}
+
+impl<'a> SexagesimalParts<'a> {
+    fn parse(s: &'a str, seps: &[&[char]; 3]) -> Option<Self> {
+        let s = s.trim();
+
+        let (neg, s) = if let Some(s) = s.strip_prefix('-') {
+            (true, s)
+        } else {
+            (false, s.strip_prefix('+').unwrap_or(s))
+        };
+
+        let (int, s) = split_digits(s);
+        let s = strip_separator(s, seps[0])?;
+
+        let (min, s) = split_digits(s);
+        let s = strip_separator(s, seps[1])?;
+
+        let (sec, s) = split_digits(s);
+
+        let (frac, s) = if let Some(s) = s.strip_prefix('.') {
+            let (frac, s) = split_digits(s);
+            if frac.is_empty() {
+                return None;
+            }
+            (frac, s)
+        } else {
+            ("", s)
+        };
+
+        let s = s.trim_start();
+        let s = s.strip_prefix(seps[2]).unwrap_or(s);
+
+        if !s.is_empty()
+            || int.is_empty()
+            || min.is_empty()
+            || min.len() > 2
+            || sec.is_empty()
+            || sec.len() > 2
+        {
+            return None;
+        }
+
+        let min = min.parse().ok()?;
+        let sec = sec.parse().ok()?;
+
+        if min >= 60 || sec >= 60 {
+            return None;
+        }
+
+        Some(SexagesimalParts {
+            neg,
+            int,
+            min,
+            sec,
+            frac,
+        })
+    }
+
+    // Computes (int*3600*10^k + (min*60 + sec)*10^k + frac) / (3600*10^k), where k is the number of digits in frac.
+    fn to_big_float(&self, p: usize, rm: RoundingMode) -> BigFloat {
+        // less than 4 bits per decimal digit
+        let p_int = (self.int.len() + self.frac.len()) * 4 + 2 * WORD_BIT_SIZE;
+
+        let int = parse_int(self.int);
+        let frac = parse_int(self.frac);
+
+        let mut unit = String::from("1");
+        (0..self.frac.len()).for_each(|_| unit.push('0'));
+        let unit = parse_int(&unit);
+
+        let ms = BigFloat::from_u128(self.min * 60 + self.sec, 128);
+        let c3600 = BigFloat::from_word(3600, WORD_BIT_SIZE);
+
+        // all operations are exact
+        let rm_ex = RoundingMode::None;
+        let mut n = int
+            .mul(&c3600, p_int, rm_ex)
+            .add(&ms, p_int, rm_ex)
+            .mul(&unit, p_int, rm_ex)
+            .add(&frac, p_int, rm_ex);
+
+        if self.neg {
+            n = n.neg();
+        }
+
+        let d = unit.mul(&c3600, p_int, rm_ex);
+
+        n.div(&d, p, rm)
+    }
+}
+
+// Parses a string of decimal digits. Empty string is parsed as zero.
+fn parse_int(s: &str) -> BigFloat {
+    let p = s.len() * 4 + WORD_BIT_SIZE;
+
+    // leading zeros are not treated as part of the integer by the parser
+    let s = s.trim_start_matches('0');
+    if s.is_empty() {
+        return BigFloat::from_word(0, p);
+    }
+
+    BigFloat::parse(s, Radix::Dec, p, RoundingMode::None)
+}
+
+// Splits `s` into the leading decimal digits and the rest.
+fn split_digits(s: &str) -> (&str, &str) {
+    let n = s.bytes().take_while(|b| b.is_ascii_digit()).count();
+    s.split_at(n)
+}
+
+// Removes a separator and the whitespace around it from the beginning of `s`.
+// Whitespace alone is also accepted as a separator.
+fn strip_separator<'a>(s: &'a str, seps: &[char]) -> Option<&'a str> {
+    let t = s.trim_start();
+    match t.strip_prefix(seps) {
+        Some(t) => Some(t.trim_start()),
+        None if t.len() < s.len() => Some(t),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_sexagesimal() {
+        let rm = RoundingMode::ToEven;
+
+        // formatting
+        let n = BigFloat::from_f64(-12.5824425, 128);
+        assert_eq!(n.to_dms(3, rm).unwrap(), "-12°34'56.793\"");
+        assert_eq!(n.to_dms(0, rm).unwrap(), "-12°34'57\"");
+        assert_eq!(n.to_hms(1, rm).unwrap(), "-12h34m56.8s");
+
+        let n = BigFloat::from_f64(359.99999, 128);
+        assert_eq!(n.to_dms(2, rm).unwrap(), "359°59'59.96\"");
+        assert_eq!(n.to_dms(1, rm).unwrap(), "360°00'00.0\"");
+        assert_eq!(n.to_dms(0, rm).unwrap(), "360°00'00\"");
+        assert_eq!(n.to_dms(0, RoundingMode::Down).unwrap(), "359°59'59\"");
+
+        let n = BigFloat::from_f64(-1e-9, 128);
+        assert_eq!(n.to_dms(2, rm).unwrap(), "0°00'00.00\"");
+        assert_eq!(n.to_dms(2, RoundingMode::Down).unwrap(), "-0°00'00.01\"");
+
+        assert_eq!(
+            BigFloat::from_word(0, 64).to_hms(0, rm).unwrap(),
+            "0h00m00s"
+        );
+        assert!(crate::NAN.to_dms(2, rm).is_none());
+        assert!(crate::INF_POS.to_hms(2, rm).is_none());
+        assert!(BigFloat::from_word(1, 64).to_dms(35, rm).is_none());
+        assert!(BigFloat::from_u128(u128::MAX, 128).to_dms(0, rm).is_none());
+
+        // parsing
+        let p = 256;
+        let third = BigFloat::from_word(1, p).div(&BigFloat::from_word(3, p), p, rm);
+        assert_eq!(BigFloat::from_dms("0°20'00\"", p, rm), third);
+        assert_eq!(BigFloat::from_dms("0d 20m 0s", p, rm), third);
+        assert_eq!(BigFloat::from_dms("+0:20:0", p, rm), third);
+        assert_eq!(BigFloat::from_dms("0 20 0", p, rm), third);
+        assert_eq!(BigFloat::from_dms("-0:20:0", p, rm), third.neg());
+        assert_eq!(BigFloat::from_hms("  0h20m00.000s ", p, rm), third);
+        assert_eq!(
+            BigFloat::from_hms("05:03:02.007", p, rm),
+            BigFloat::from_word(18182007, p).div(&BigFloat::from_word(3600000, p), p, rm)
+        );
+
+        // single rounding
+        let n = BigFloat::from_dms("10°00'00.1\"", p, RoundingMode::Up);
+        let d =
+            BigFloat::from_word(360001, p).div(&BigFloat::from_word(36000, p), p, RoundingMode::Up);
+        assert_eq!(n, d);
+
+        let s = "1234567890°59'59.999999999999\"";
+        assert_eq!(BigFloat::from_dms(s, 512, rm).to_dms(12, rm).unwrap(), s);
+
+        for s in [
+            "",
+            "12",
+            "12°",
+            "12°34'",
+            "°34'56\"",
+            "12°60'00\"",
+            "12°00'60\"",
+            "12°00'0.\"",
+            "12°00'00\"x",
+            "12h00m00s",
+            "1°2°3",
+        ] {
+            assert!(BigFloat::from_dms(s, p, rm).is_nan(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,1079 @@
+//! Fixed-capacity floating point number which does not require dynamic memory allocation.
+
+use crate::common::util::{
+    add_slices, cmp_slices, leading_zeros, round_p, round_slice, shift_right_sticky,
+    shift_slice_left, shift_slice_right, sub_slices,
+};
+use crate::defs::{
+    DoubleWord, Error, Exponent, RoundingMode, Sign, SignedWord, Word, EXPONENT_MAX, EXPONENT_MIN,
+    WORD_BIT_SIZE, WORD_SIGNIFICANT_BIT,
+};
+
+#[cfg(feature = "alloc")]
+use crate::ext::BigFloat;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+// Number of words in u64.
+const U64_WORDS: usize = 64 / WORD_BIT_SIZE;
+
+/// A floating point number which stores its mantissa in a fixed-size array of `W` words.
+///
+/// `StackBigFloat` never allocates memory, and can be used in environments where neither the standard library
+/// nor `alloc` is available. The precision of a number is limited by the capacity of `W * WORD_BIT_SIZE` bits:
+/// functions return `Error::InvalidArgument` when a larger precision is requested.
+///
+/// Numbers which are smaller than the smallest normal number representable with `EXPONENT_MIN`
+/// are flushed to zero, i.e. subnormal numbers are not supported.
+///
+/// The arithmetic operations, the square root, the reciprocal, and the integer and fractional parts
+/// are correctly rounded like the corresponding operations of `BigFloatNumber`.
+/// Functions which require the constants cache are not available, because the cache allocates memory.
+///
+/// ## Examples
+///
+/// ```
+/// use astro_float_num::{RoundingMode, StackBigFloat};
+///
+/// let rm = RoundingMode::ToEven;
+///
+/// let a = StackBigFloat::<4>::from_word(1, 128).unwrap();
+/// let b = StackBigFloat::<4>::from_word(3, 128).unwrap();
+///
+/// let c = a.div(&b, 128, rm).unwrap();
+/// let d = c.mul(&b, 128, rm).unwrap();
+///
+/// assert_eq!(d.cmp(&a), 0);
+///
+/// // Precision exceeds the capacity.
+/// assert!(a.add(&b, 320, rm).is_err());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct StackBigFloat<const W: usize> {
+    m: [Word; W],
+    n: usize,
+    e: Exponent,
+    s: Sign,
+    inexact: bool,
+}
+
+impl<const W: usize> StackBigFloat<W> {
+    /// The largest precision in bits a number can have.
+    pub const CAPACITY: usize = W * WORD_BIT_SIZE;
+
+    // Checks precision `p` and rounds it upwards to a multiple of `PRECISION_GRANULARITY`.
+    fn p_assertion(p: usize) -> Result<usize, Error> {
+        let p = round_p(p);
+
+        if p == 0 || p > Self::CAPACITY {
+            Err(Error::InvalidArgument)
+        } else {
+            Ok(p)
+        }
+    }
+
+    // Returns zero with precision `p`, sign `s`, and inexact flag `inexact`.
+    fn zero(p: usize, s: Sign, inexact: bool) -> Self {
+        StackBigFloat {
+            m: [0; W],
+            n: p,
+            e: 0,
+            s,
+            inexact,
+        }
+    }
+
+    /// Returns a new number with value of 0 and precision of `p` bits. Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity.
+    pub fn new(p: usize) -> Result<Self, Error> {
+        let p = Self::p_assertion(p)?;
+
+        Ok(Self::zero(p, Sign::Pos, false))
+    }
+
+    /// Returns a new number with value `d` and the precision `p`. Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity.
+    pub fn from_word(d: Word, p: usize) -> Result<Self, Error> {
+        let p = Self::p_assertion(p)?;
+
+        let mut ret = Self::zero(p, Sign::Pos, false);
+
+        if d != 0 {
+            let shift = d.leading_zeros() as usize;
+            ret.m[W - 1] = d << shift;
+            ret.e = (WORD_BIT_SIZE - shift) as Exponent;
+        }
+
+        Ok(ret)
+    }
+
+    /// Constructs a number from the mantissa words `m` with the least significant word first, sign `s`, and exponent `e`.
+    /// The precision of the number is equal to the capacity.
+    /// Unlike other constructors the function can be evaluated at compile time,
+    /// and allows declaring constants and statics without runtime initialization.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `m` is not zero and the most significant bit of the mantissa is not set,
+    /// or if `e` is outside the range from `EXPONENT_MIN` to `EXPONENT_MAX`.
+    /// In a const context the panic results in a compilation error.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use astro_float_num::{BigFloat, Sign, StackBigFloat, WORD_SIGNIFICANT_BIT};
+    ///
+    /// // 0.11 * 2^1 in binary
+    /// static ONE_AND_HALF: StackBigFloat<2> = StackBigFloat::from_raw_words(
+    ///     [0, WORD_SIGNIFICANT_BIT | (WORD_SIGNIFICANT_BIT >> 1)],
+    ///     Sign::Pos,
+    ///     1,
+    /// );
+    ///
+    /// assert_eq!(ONE_AND_HALF.to_f64(), 1.5);
+    /// assert_eq!(ONE_AND_HALF.to_big_float(), BigFloat::from_f64(1.5, 128));
+    /// ```
+    pub const fn from_raw_words(m: [Word; W], s: Sign, e: Exponent) -> Self {
+        let mut is_zero = true;
+        let mut i = 0;
+        while i < W {
+            if m[i] != 0 {
+                is_zero = false;
+            }
+            i += 1;
+        }
+
+        if is_zero {
+            return StackBigFloat {
+                m,
+                n: Self::CAPACITY,
+                e: 0,
+                s,
+                inexact: false,
+            };
+        }
+
+        assert!(
+            m[W - 1] & WORD_SIGNIFICANT_BIT != 0,
+            "mantissa is not normalized"
+        );
+        assert!(
+            e as isize >= EXPONENT_MIN as isize && e as isize <= EXPONENT_MAX as isize,
+            "exponent is out of range"
+        );
+
+        StackBigFloat {
+            m,
+            n: Self::CAPACITY,
+            e,
+            s,
+            inexact: false,
+        }
+    }
+
+    /// Constructs a number with precision `p` from f64 value.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// If `p` is smaller than 53 the value is rounded to the nearest even.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: `f` is Inf.
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity, or `f` is NaN.
+    pub fn from_f64(f: f64, p: usize) -> Result<Self, Error> {
+        let p = Self::p_assertion(p)?;
+
+        let s = if f.is_sign_negative() { Sign::Neg } else { Sign::Pos };
+
+        if f.is_nan() {
+            return Err(Error::InvalidArgument);
+        }
+
+        if f.is_infinite() {
+            return Err(Error::ExponentOverflow(s));
+        }
+
+        if f == 0.0 {
+            return Ok(Self::zero(p, s, false));
+        }
+
+        let u = f.to_bits();
+        let mut mantissa = u << 12;
+        let mut e = ((u >> 52) & 0b11111111111) as isize;
+
+        if e != 0 {
+            mantissa >>= 1;
+            mantissa |= 0x8000000000000000u64;
+            e -= 1022;
+        } else {
+            let shift = mantissa.leading_zeros();
+            mantissa <<= shift;
+            e = -1022 - shift as isize;
+        }
+
+        let mut buf = [0 as Word; U64_WORDS];
+        for v in buf.iter_mut() {
+            *v = mantissa as Word;
+            mantissa = mantissa.checked_shr(WORD_BIT_SIZE as u32).unwrap_or(0);
+        }
+
+        Self::from_normalized(&mut buf, false, e, s, p, RoundingMode::ToEven)
+    }
+
+    /// Converts the number to f64 value. Conversion rounds the number to zero.
+    pub fn to_f64(&self) -> f64 {
+        if self.is_zero() {
+            return if self.is_negative() { -0.0 } else { 0.0 };
+        }
+
+        #[cfg(not(word32))]
+        let top = self.m[W - 1];
+
+        #[cfg(word32)]
+        let top = {
+            let mut top = 0u64;
+            for (i, &v) in self.m.iter().rev().take(U64_WORDS).enumerate() {
+                top |= (v as u64) << (64 - WORD_BIT_SIZE * (i + 1));
+            }
+            top
+        };
+
+        // f64 biased exponent of the most significant bit.
+        let be = self.e as isize + 1022;
+
+        let bits = if be >= 0b11111111111 {
+            0b11111111111u64 << 52
+        } else if be > 0 {
+            ((be as u64) << 52) | ((top >> 11) & 0xfffffffffffff)
+        } else if be > -52 {
+            top >> (12 - be)
+        } else {
+            0
+        };
+
+        let f = f64::from_bits(bits);
+
+        if self.is_negative() {
+            -f
+        } else {
+            f
+        }
+    }
+
+    // Builds a number from the mantissa `m` of arbitrary length with the most significant bit set,
+    // exponent `e`, and sign `s`, rounding the mantissa to `p` bits using rounding mode `rm`.
+    // `sticky` is true if any nonzero bits were discarded beyond the least significant bit of `m`.
+    fn from_normalized(
+        m: &mut [Word],
+        sticky: bool,
+        mut e: isize,
+        s: Sign,
+        p: usize,
+        rm: RoundingMode,
+    ) -> Result<Self, Error> {
+        debug_assert!(m[m.len() - 1] & WORD_SIGNIFICANT_BIT != 0);
+
+        let mut inexact = sticky;
+
+        if p < m.len() * WORD_BIT_SIZE {
+            let (carry, rinexact) = round_slice(m, p, rm, s == Sign::Pos, sticky);
+
+            inexact = rinexact;
+
+            if carry {
+                let l = m.len();
+                m[l - 1] = WORD_SIGNIFICANT_BIT;
+                e += 1;
+            }
+        }
+
+        if e > EXPONENT_MAX as isize {
+            return Err(Error::ExponentOverflow(s));
+        }
+
+        if e < EXPONENT_MIN as isize {
+            return Ok(Self::zero(p, s, true));
+        }
+
+        let mut ret = Self::zero(p, s, inexact);
+        let l = m.len().min(p / WORD_BIT_SIZE);
+        ret.m[W - l..].copy_from_slice(&m[m.len() - l..]);
+        ret.e = e as Exponent;
+
+        Ok(ret)
+    }
+
+    /// Adds `d2` to `self` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large or too small number.
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity.
+    pub fn add(&self, d2: &Self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        self.add_sub(d2, d2.s, p, rm)
+    }
+
+    /// Subtracts `d2` from `self` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large or too small number.
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity.
+    pub fn sub(&self, d2: &Self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        self.add_sub(d2, d2.s.invert(), p, rm)
+    }
+
+    // Adds `d2` with sign `s2` to `self`.
+    fn add_sub(&self, d2: &Self, s2: Sign, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let p = Self::p_assertion(p)?;
+
+        let inexact = self.inexact || d2.inexact;
+
+        if self.is_zero() && d2.is_zero() {
+            let s = if self.s == s2 {
+                self.s
+            } else if rm == RoundingMode::Down {
+                Sign::Neg
+            } else {
+                Sign::Pos
+            };
+
+            return Ok(Self::zero(p, s, inexact));
+        }
+
+        // arrange operands so that |a| >= |b|
+        let (a, b, sa, sb) = if self.abs_cmp(d2) >= 0 {
+            (self, d2, self.s, s2)
+        } else {
+            (d2, self, s2, self.s)
+        };
+
+        let mut buf = [[0 as Word; W]; 2];
+        let m = buf.as_flattened_mut();
+        let mut e = a.e as isize;
+        let mut sticky = false;
+
+        if b.is_zero() {
+            m[W..].copy_from_slice(&a.m);
+        } else {
+            let mut buf2 = [[0 as Word; W]; 2];
+            let m2 = buf2.as_flattened_mut();
+
+            m[W..].copy_from_slice(&a.m);
+            m2[W..].copy_from_slice(&b.m);
+
+            // align and jam the discarded bits into the least significant bit
+            let shift = (a.e as isize - b.e as isize) as usize;
+            if shift_right_sticky(m2, shift) {
+                m2[0] |= 1;
+            }
+
+            if sa == sb {
+                let carry = add_slices(m, m2);
+
+                if carry {
+                    sticky = m[0] & 1 != 0;
+                    shift_slice_right(m, 1);
+                    m[2 * W - 1] |= WORD_SIGNIFICANT_BIT;
+                    e += 1;
+                }
+            } else {
+                sub_slices(m, m2);
+
+                if m.iter().all(|&v| v == 0) {
+                    let s = if rm == RoundingMode::Down { Sign::Neg } else { Sign::Pos };
+
+                    return Ok(Self::zero(p, s, inexact));
+                }
+
+                let shift = leading_zeros(m);
+                shift_slice_left(m, shift);
+                e -= shift as isize;
+            }
+        }
+
+        let mut ret = Self::from_normalized(m, sticky, e, sa, p, rm)?;
+        ret.inexact |= inexact;
+
+        Ok(ret)
+    }
+
+    /// Multiplies `d2` by `self` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large or too small number.
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity.
+    pub fn mul(&self, d2: &Self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let p = Self::p_assertion(p)?;
+
+        let s = if self.s == d2.s { Sign::Pos } else { Sign::Neg };
+        let inexact = self.inexact || d2.inexact;
+
+        if self.is_zero() || d2.is_zero() {
+            return Ok(Self::zero(p, s, inexact));
+        }
+
+        let mut buf = [[0 as Word; W]; 2];
+        let m = buf.as_flattened_mut();
+
+        for (i, &v1) in self.m.iter().enumerate() {
+            if v1 == 0 {
+                continue;
+            }
+
+            let mut k: DoubleWord = 0;
+            for (j, &v2) in d2.m.iter().enumerate() {
+                let t = v1 as DoubleWord * v2 as DoubleWord + m[i + j] as DoubleWord + k;
+                m[i + j] = t as Word;
+                k = t >> WORD_BIT_SIZE;
+            }
+            m[i + W] = k as Word;
+        }
+
+        let mut e = self.e as isize + d2.e as isize;
+
+        if m[2 * W - 1] & WORD_SIGNIFICANT_BIT == 0 {
+            shift_slice_left(m, 1);
+            e -= 1;
+        }
+
+        let mut ret = Self::from_normalized(m, false, e, s, p, rm)?;
+        ret.inexact |= inexact;
+
+        Ok(ret)
+    }
+
+    /// Divides `self` by `d2` and returns the result of the operation with precision `p` rounded according to `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large or too small number.
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity.
+    ///  - DivisionByZero: `d2` is zero.
+    pub fn div(&self, d2: &Self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let p = Self::p_assertion(p)?;
+
+        let s = if self.s == d2.s { Sign::Pos } else { Sign::Neg };
+        let inexact = self.inexact || d2.inexact;
+
+        if d2.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+
+        if self.is_zero() {
+            return Ok(Self::zero(p, s, inexact));
+        }
+
+        let mut buf = [[0 as Word; W]; 2];
+        let m = buf.as_flattened_mut();
+
+        // restoring division: `r` < 2 * `d2.m` holds on each step,
+        // the bit of `r` which does not fit in W words is kept in `rtop`.
+        let mut r = self.m;
+        let mut rtop = false;
+        let nbits = ((W + 1) * WORD_BIT_SIZE).min(2 * Self::CAPACITY);
+
+        for i in 0..nbits {
+            if rtop || cmp_slices(&r, &d2.m) >= 0 {
+                sub_slices(&mut r, &d2.m);
+                let pos = 2 * Self::CAPACITY - 1 - i;
+                m[pos / WORD_BIT_SIZE] |= 1 << (pos % WORD_BIT_SIZE);
+            }
+
+            rtop = r[W - 1] & WORD_SIGNIFICANT_BIT != 0;
+            shift_slice_left(&mut r, 1);
+        }
+
+        let sticky = rtop || r.iter().any(|&v| v != 0);
+        let mut e = self.e as isize - d2.e as isize + 1;
+
+        if m[2 * W - 1] & WORD_SIGNIFICANT_BIT == 0 {
+            shift_slice_left(m, 1);
+            e -= 1;
+        }
+
+        let mut ret = Self::from_normalized(m, sticky, e, s, p, rm)?;
+        ret.inexact |= inexact;
+
+        Ok(ret)
+    }
+
+    /// Computes the reciprocal of a number with precision `p` rounded according to `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large or too small number.
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity.
+    ///  - DivisionByZero: `self` is zero.
+    pub fn reciprocal(&self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        Self::from_word(1, p)?.div(self, p, rm)
+    }
+
+    /// Computes the square root of a number with precision `p` rounded according to `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity, or `self` is negative.
+    pub fn sqrt(&self, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let p = Self::p_assertion(p)?;
+
+        if self.is_zero() {
+            return Ok(Self::zero(p, Sign::Pos, self.inexact));
+        }
+
+        if self.is_negative() {
+            return Err(Error::InvalidArgument);
+        }
+
+        // self = 0.S * 2^(e + odd), where S is the mantissa preceded by a zero bit if the exponent is odd,
+        // and sqrt(self) = 0.Q * 2^((e + odd) / 2), where Q is computed bit by bit with one extra word for rounding.
+        let odd = (self.e & 1) as usize;
+        let nbits = (W + 1) * WORD_BIT_SIZE;
+
+        let bit = |i: usize| -> Word {
+            if i < odd || i - odd >= Self::CAPACITY {
+                0
+            } else {
+                let j = Self::CAPACITY - 1 - (i - odd);
+                (self.m[j / WORD_BIT_SIZE] >> (j % WORD_BIT_SIZE)) & 1
+            }
+        };
+
+        let mut rbuf = [[0 as Word; W]; 3];
+        let mut tbuf = [[0 as Word; W]; 3];
+        let mut qbuf = [[0 as Word; W]; 3];
+        let r = rbuf.as_flattened_mut();
+        let t = tbuf.as_flattened_mut();
+        let q = qbuf.as_flattened_mut();
+
+        for i in 0..nbits {
+            shift_slice_left(r, 2);
+            r[0] |= (bit(2 * i) << 1) | bit(2 * i + 1);
+
+            t.copy_from_slice(q);
+            shift_slice_left(t, 2);
+            t[0] |= 1;
+
+            shift_slice_left(q, 1);
+
+            if cmp_slices(r, t) >= 0 {
+                sub_slices(r, t);
+                q[0] |= 1;
+            }
+        }
+
+        let sticky = r.iter().any(|&v| v != 0);
+        let e = (self.e as isize + odd as isize) / 2;
+
+        let mut ret = Self::from_normalized(&mut q[..W + 1], sticky, e, Sign::Pos, p, rm)?;
+        ret.inexact |= self.inexact;
+
+        Ok(ret)
+    }
+
+    /// Returns the integer part of a number.
+    pub fn int(&self) -> Self {
+        let mut ret = *self;
+
+        if self.e <= 0 {
+            ret.m = [0; W];
+            ret.e = 0;
+        } else if (self.e as usize) < Self::CAPACITY {
+            let k = Self::CAPACITY - self.e as usize;
+            ret.m[..k / WORD_BIT_SIZE].fill(0);
+            if !k.is_multiple_of(WORD_BIT_SIZE) {
+                ret.m[k / WORD_BIT_SIZE] &= Word::MAX << (k % WORD_BIT_SIZE);
+            }
+        }
+
+        ret
+    }
+
+    /// Returns the fractional part of a number.
+    /// The fractional part is flushed to zero if it is smaller than the smallest normal number.
+    pub fn fract(&self) -> Self {
+        if self.e <= 0 {
+            return *self;
+        }
+
+        let mut m = self.m;
+        if (self.e as usize) < Self::CAPACITY {
+            let k = Self::CAPACITY - self.e as usize;
+            m[k / WORD_BIT_SIZE + 1..].fill(0);
+            if k.is_multiple_of(WORD_BIT_SIZE) {
+                m[k / WORD_BIT_SIZE] = 0;
+            } else {
+                m[k / WORD_BIT_SIZE] &= !(Word::MAX << (k % WORD_BIT_SIZE));
+            }
+        } else {
+            m = [0; W];
+        }
+
+        if m.iter().all(|&v| v == 0) {
+            return Self::zero(self.n, self.s, self.inexact);
+        }
+
+        let shift = leading_zeros(&m);
+        shift_slice_left(&mut m, shift);
+
+        // the fractional part fits in the precision of the number, and no rounding happens
+        match Self::from_normalized(
+            &mut m,
+            false,
+            self.e as isize - shift as isize,
+            self.s,
+            self.n,
+            RoundingMode::ToZero,
+        ) {
+            Ok(mut ret) => {
+                ret.inexact |= self.inexact;
+                ret
+            }
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Returns true if the number is an integer.
+    pub fn is_int(&self) -> bool {
+        self.fract().is_zero()
+    }
+
+    /// Returns the largest integer less than or equal to `self`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large number.
+    pub fn floor(&self) -> Result<Self, Error> {
+        let int = self.int();
+
+        if self.is_negative() && !self.is_int() {
+            int.sub(&Self::from_word(1, self.n)?, self.n, RoundingMode::ToZero)
+        } else {
+            Ok(int)
+        }
+    }
+
+    /// Returns the smallest integer greater than or equal to `self`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: the result is too large number.
+    pub fn ceil(&self) -> Result<Self, Error> {
+        let int = self.int();
+
+        if self.is_positive() && !self.is_int() {
+            int.add(&Self::from_word(1, self.n)?, self.n, RoundingMode::ToZero)
+        } else {
+            Ok(int)
+        }
+    }
+
+    /// Sets the precision of the number to `p` bits rounding the mantissa according to `rm` if needed.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: rounding resulted in too large number.
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity.
+    pub fn set_precision(&mut self, p: usize, rm: RoundingMode) -> Result<(), Error> {
+        let p = Self::p_assertion(p)?;
+
+        if !self.is_zero() && p < self.n {
+            let mut m = self.m;
+            let inexact = self.inexact;
+            *self = Self::from_normalized(&mut m, false, self.e as isize, self.s, p, rm)?;
+            self.inexact |= inexact;
+        }
+
+        self.n = p;
+
+        Ok(())
+    }
+
+    /// Compares `self` to `d2`.
+    /// Returns positive if `self` is greater than `d2`, negative if `self` is smaller than `d2`, 0 otherwise.
+    pub fn cmp(&self, d2: &Self) -> SignedWord {
+        if self.is_zero() && d2.is_zero() {
+            return 0;
+        }
+
+        if self.s != d2.s {
+            return self.s as SignedWord;
+        }
+
+        self.abs_cmp(d2) * self.s as SignedWord
+    }
+
+    /// Compares the absolute value of `self` to the absolute value of `d2`.
+    /// Returns positive if `|self|` is greater than `|d2|`, negative if `|self|` is smaller than `|d2|`, 0 otherwise.
+    pub fn abs_cmp(&self, d2: &Self) -> SignedWord {
+        match (self.is_zero(), d2.is_zero()) {
+            (true, true) => 0,
+            (true, false) => -1,
+            (false, true) => 1,
+            (false, false) => {
+                if self.e > d2.e {
+                    1
+                } else if self.e < d2.e {
+                    -1
+                } else {
+                    cmp_slices(&self.m, &d2.m)
+                }
+            }
+        }
+    }
+
+    /// Returns a copy of the number with the sign reversed.
+    pub fn neg(&self) -> Self {
+        let mut ret = *self;
+        ret.s = ret.s.invert();
+        ret
+    }
+
+    /// Returns the absolute value of the number.
+    pub fn abs(&self) -> Self {
+        let mut ret = *self;
+        ret.s = Sign::Pos;
+        ret
+    }
+
+    /// Returns true if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.m[W - 1] == 0
+    }
+
+    /// Returns the sign of the number.
+    pub fn sign(&self) -> Sign {
+        self.s
+    }
+
+    /// Returns true if the number is positive.
+    pub fn is_positive(&self) -> bool {
+        self.s == Sign::Pos
+    }
+
+    /// Returns true if the number is negative.
+    pub fn is_negative(&self) -> bool {
+        self.s == Sign::Neg
+    }
+
+    /// Returns the exponent of the number.
+    pub fn exponent(&self) -> Exponent {
+        self.e
+    }
+
+    /// Returns the maximum mantissa length of the number in bits.
+    pub fn mantissa_max_bit_len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the words of the mantissa, the most significant word comes last.
+    pub fn mantissa(&self) -> &[Word] {
+        &self.m[W - self.n / WORD_BIT_SIZE..]
+    }
+
+    /// Returns true if the number is inexact.
+    pub fn inexact(&self) -> bool {
+        self.inexact
+    }
+
+    /// Converts the number to BigFloat.
+    #[cfg(feature = "alloc")]
+    pub fn to_big_float(&self) -> BigFloat {
+        let n = if self.is_zero() { 0 } else { self.n };
+
+        BigFloat::from_raw_parts(self.mantissa(), n, self.s, self.e, self.inexact)
+    }
+
+    /// Constructs a number with precision `p` from `x` rounding it according to `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    ///
+    /// ## Errors
+    ///
+    ///  - ExponentOverflow: `x` is Inf, or rounding resulted in too large number.
+    ///  - InvalidArgument: the precision is zero or exceeds the capacity, or `x` is NaN.
+    #[cfg(feature = "alloc")]
+    pub fn from_big_float(x: &BigFloat, p: usize, rm: RoundingMode) -> Result<Self, Error> {
+        let p = Self::p_assertion(p)?;
+
+        let (m, _, s, e, inexact) = match x.as_raw_parts() {
+            Some(parts) => parts,
+            None if x.is_inf() => return Err(Error::ExponentOverflow(x.sign().unwrap())),
+            None => return Err(Error::InvalidArgument),
+        };
+
+        if m.iter().all(|&v| v == 0) {
+            return Ok(Self::zero(p, s, inexact));
+        }
+
+        // subnormal numbers are normalized
+        let mut m: Vec<Word> = m.to_vec();
+        let shift = leading_zeros(&m);
+        shift_slice_left(&mut m, shift);
+
+        let mut ret = Self::from_normalized(&mut m, false, e as isize - shift as isize, s, p, rm)?;
+        ret.inexact |= inexact;
+
+        Ok(ret)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const W: usize> From<&StackBigFloat<W>> for BigFloat {
+    fn from(x: &StackBigFloat<W>) -> Self {
+        x.to_big_float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::util::rand_p;
+
+    type Sbf = StackBigFloat<4>;
+
+    #[test]
+    fn test_stack_bigfloat() {
+        let rm = RoundingMode::ToEven;
+
+        // capacity
+        assert!(Sbf::new(Sbf::CAPACITY).is_ok());
+        assert!(matches!(
+            Sbf::new(Sbf::CAPACITY + 1),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(Sbf::new(0), Err(Error::InvalidArgument)));
+
+        let one = Sbf::from_word(1, 128).unwrap();
+        let two = Sbf::from_word(2, 128).unwrap();
+        let three = Sbf::from_word(3, 128).unwrap();
+
+        assert_eq!(one.add(&two, 128, rm).unwrap().cmp(&three), 0);
+        assert_eq!(three.sub(&two, 128, rm).unwrap().cmp(&one), 0);
+        assert_eq!(one.sub(&three, 128, rm).unwrap().cmp(&two.neg()), 0);
+        assert!(one.sub(&one, 128, rm).unwrap().is_zero());
+        assert_eq!(two.mul(&three, 128, rm).unwrap().to_f64(), 6.0);
+        assert_eq!(three.div(&two, 128, rm).unwrap().to_f64(), 1.5);
+        assert!(matches!(
+            one.div(&Sbf::new(128).unwrap(), 128, rm),
+            Err(Error::DivisionByZero)
+        ));
+
+        // square root
+        let four = two.mul(&two, 128, rm).unwrap();
+        assert_eq!(four.sqrt(128, rm).unwrap().cmp(&two), 0);
+        assert!(!four.sqrt(128, rm).unwrap().inexact());
+        assert!(two.sqrt(128, rm).unwrap().inexact());
+        assert!(Sbf::new(128).unwrap().sqrt(128, rm).unwrap().is_zero());
+        assert!(matches!(
+            two.neg().sqrt(128, rm),
+            Err(Error::InvalidArgument)
+        ));
+
+        // integer and fractional parts
+        let x = Sbf::from_f64(-2.75, 128).unwrap();
+        assert_eq!(x.int().to_f64(), -2.0);
+        assert_eq!(x.fract().to_f64(), -0.75);
+        assert_eq!(x.floor().unwrap().to_f64(), -3.0);
+        assert_eq!(x.ceil().unwrap().to_f64(), -2.0);
+        assert!(!x.is_int() && x.int().is_int());
+
+        // inexact result
+        let d = one.div(&three, 128, rm).unwrap();
+        assert!(d.inexact());
+        assert!(!one.div(&two, 128, rm).unwrap().inexact());
+        assert_eq!(d.mul(&three, 128, rm).unwrap().cmp(&one), 0);
+
+        // const construction
+        const NEG_THREE: Sbf = Sbf::from_raw_words(
+            [0, 0, 0, WORD_SIGNIFICANT_BIT | (WORD_SIGNIFICANT_BIT >> 1)],
+            Sign::Neg,
+            2,
+        );
+        const ZERO: Sbf = Sbf::from_raw_words([0; 4], Sign::Pos, 5);
+
+        assert_eq!(NEG_THREE.cmp(&three.neg()), 0);
+        assert_eq!(NEG_THREE.mantissa_max_bit_len(), Sbf::CAPACITY);
+        assert!(!NEG_THREE.inexact());
+        assert!(ZERO.is_zero());
+        assert_eq!(ZERO.exponent(), 0);
+
+        // f64 conversion
+        for f in [
+            1.0,
+            -0.1,
+            123456.789,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f64::MIN_POSITIVE / 1024.0,
+            -5e-324,
+        ] {
+            assert_eq!(Sbf::from_f64(f, 64).unwrap().to_f64(), f);
+        }
+        assert!(matches!(
+            Sbf::from_f64(f64::NAN, 64),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            Sbf::from_f64(f64::NEG_INFINITY, 64),
+            Err(Error::ExponentOverflow(Sign::Neg))
+        ));
+
+        // single-word numbers require word-granular precision
+        #[cfg(not(all(word32, feature = "portable")))]
+        {
+            use crate::defs::WORD_MAX;
+
+            // rounding
+            let mut m = [0, WORD_SIGNIFICANT_BIT, WORD_MAX];
+            let mut x = StackBigFloat::<3>::from_normalized(
+                &mut m,
+                false,
+                0,
+                Sign::Pos,
+                3 * WORD_BIT_SIZE,
+                rm,
+            )
+            .unwrap();
+            x.set_precision(WORD_BIT_SIZE, RoundingMode::ToZero)
+                .unwrap();
+            assert_eq!(x.mantissa(), &[WORD_MAX]);
+            assert!(x.inexact());
+            x.set_precision(2 * WORD_BIT_SIZE, rm).unwrap();
+            assert_eq!(x.mantissa(), &[0, WORD_MAX]);
+
+            let mut m = [WORD_SIGNIFICANT_BIT, WORD_MAX];
+            let x =
+                StackBigFloat::<1>::from_normalized(&mut m, false, 0, Sign::Pos, WORD_BIT_SIZE, rm)
+                    .unwrap();
+            assert_eq!(x.mantissa(), &[WORD_SIGNIFICANT_BIT]);
+            assert_eq!(x.exponent(), 1);
+            let mut m = [WORD_SIGNIFICANT_BIT, WORD_MAX];
+            let x = StackBigFloat::<1>::from_normalized(
+                &mut m,
+                false,
+                0,
+                Sign::Pos,
+                WORD_BIT_SIZE,
+                RoundingMode::Down,
+            )
+            .unwrap();
+            assert_eq!(x.mantissa(), &[WORD_MAX]);
+
+            // overflow and underflow
+            let mut m = [WORD_SIGNIFICANT_BIT];
+            let max = StackBigFloat::<1>::from_normalized(
+                &mut m,
+                false,
+                EXPONENT_MAX as isize,
+                Sign::Neg,
+                WORD_BIT_SIZE,
+                rm,
+            )
+            .unwrap();
+            assert!(matches!(
+                max.mul(&max, WORD_BIT_SIZE, rm),
+                Err(Error::ExponentOverflow(Sign::Pos))
+            ));
+            let mut m = [WORD_SIGNIFICANT_BIT];
+            let min = StackBigFloat::<1>::from_normalized(
+                &mut m,
+                false,
+                EXPONENT_MIN as isize,
+                Sign::Pos,
+                WORD_BIT_SIZE,
+                rm,
+            )
+            .unwrap();
+            assert!(min.mul(&min, WORD_BIT_SIZE, rm).unwrap().is_zero());
+        }
+    }
+
+    #[test]
+    fn test_stack_bigfloat_vs_bigfloat() {
+        let rms = [
+            RoundingMode::None,
+            RoundingMode::Up,
+            RoundingMode::Down,
+            RoundingMode::ToZero,
+            RoundingMode::FromZero,
+            RoundingMode::ToEven,
+            RoundingMode::ToOdd,
+            RoundingMode::ToNearestAway,
+        ];
+
+        for _ in 0..1000 {
+            let p1 = rand_p() % Sbf::CAPACITY + 1;
+            let p2 = rand_p() % Sbf::CAPACITY + 1;
+            let p = rand_p() % Sbf::CAPACITY + 1;
+            let rm = rms[rand::random::<usize>() % rms.len()];
+
+            let e1 = rand::random::<Exponent>() % 300;
+            let e2 = if rand::random::<bool>() {
+                e1 + rand::random::<Exponent>() % (2 * Sbf::CAPACITY as Exponent)
+            } else {
+                rand::random::<Exponent>() % 300
+            };
+
+            let n1 = BigFloat::random_normal(p1, e1, e1);
+            let n2 = BigFloat::random_normal(p2, e2, e2);
+
+            let d1 = Sbf::from_big_float(&n1, p1, rm).unwrap();
+            let d2 = Sbf::from_big_float(&n2, p2, rm).unwrap();
+
+            assert_eq!(d1.to_big_float(), n1);
+            assert_eq!(d2.to_big_float(), n2);
+
+            assert_eq!(
+                d1.add(&d2, p, rm).unwrap().to_big_float(),
+                n1.add(&n2, p, rm)
+            );
+            assert_eq!(
+                d1.sub(&d2, p, rm).unwrap().to_big_float(),
+                n1.sub(&n2, p, rm)
+            );
+            assert_eq!(
+                d1.mul(&d2, p, rm).unwrap().to_big_float(),
+                n1.mul(&n2, p, rm)
+            );
+            assert_eq!(
+                d1.div(&d2, p, rm).unwrap().to_big_float(),
+                n1.div(&n2, p, rm)
+            );
+            assert_eq!(d1.cmp(&d2), n1.cmp(&n2).unwrap().signum());
+            assert_eq!(
+                d1.reciprocal(p, rm).unwrap().to_big_float(),
+                n1.reciprocal(p, rm)
+            );
+            assert_eq!(
+                d1.abs().sqrt(p, rm).unwrap().to_big_float(),
+                n1.abs().sqrt(p, rm)
+            );
+
+            // integer and fractional parts
+            let e = rand::random::<Exponent>() % (Sbf::CAPACITY as Exponent + 20) - 10;
+            let n3 = BigFloat::random_normal(p1, e, e);
+            let d3 = Sbf::from_big_float(&n3, p1, rm).unwrap();
+
+            assert_eq!(d3.int().to_big_float(), n3.int());
+            assert_eq!(d3.fract().to_big_float(), n3.fract());
+            assert_eq!(d3.floor().unwrap().to_big_float(), n3.floor());
+            assert_eq!(d3.ceil().unwrap().to_big_float(), n3.ceil());
+            assert_eq!(d3.is_int(), n3.is_int());
+        }
+    }
+}
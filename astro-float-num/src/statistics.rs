@@ -0,0 +1,558 @@
+//! Statistical functions computing mean, variance, and standard deviation of a set of numbers.
+//!
+//! Intermediate sums are accumulated with the extended precision which grows with the number of values,
+//! so that the result is rounded only once to the requested precision.
+
+use crate::BigFloat;
+use crate::Error;
+use crate::Exponent;
+use crate::RoundingMode;
+use crate::INF_NEG;
+use crate::INF_POS;
+use crate::NAN;
+use crate::WORD_BIT_SIZE;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Computes the arithmetic mean of the values `xs` with precision `p` using rounding mode `rm`.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+/// The function returns NaN if `xs` is empty, if any of the values is NaN, or if the precision `p` is incorrect.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::BigFloat;
+/// # use astro_float_num::RoundingMode;
+/// # use astro_float_num::statistics::mean;
+/// let xs = [1, 2, 3, 4].map(|v| BigFloat::from_word(v, 64));
+///
+/// assert_eq!(mean(&xs, 64, RoundingMode::ToEven), BigFloat::from_f64(2.5, 64));
+/// ```
+pub fn mean(xs: &[BigFloat], p: usize, rm: RoundingMode) -> BigFloat {
+    if xs.is_empty() {
+        return BigFloat::nan(Some(Error::InvalidArgument));
+    }
+
+    let wp = working_precision(p, xs.len());
+    let m = sum(xs, wp).div(&count(xs.len()), wp, RoundingMode::None);
+
+    round(m, p, rm)
+}
+
+/// Computes the population variance of the values `xs` with precision `p` using rounding mode `rm`.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+/// The function returns NaN if `xs` is empty, if any of the values is NaN, or if the precision `p` is incorrect.
+pub fn variance(xs: &[BigFloat], p: usize, rm: RoundingMode) -> BigFloat {
+    if xs.is_empty() {
+        return BigFloat::nan(Some(Error::InvalidArgument));
+    }
+
+    let wp = working_precision(p, xs.len());
+
+    round(
+        sum_sq_dev(xs, wp).div(&count(xs.len()), wp, RoundingMode::None),
+        p,
+        rm,
+    )
+}
+
+/// Computes the sample variance of the values `xs`, i.e. variance with the Bessel's correction,
+/// with precision `p` using rounding mode `rm`.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+/// The function returns NaN if `xs` contains less than two values, if any of the values is NaN, or if the precision `p` is incorrect.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::BigFloat;
+/// # use astro_float_num::RoundingMode;
+/// # use astro_float_num::statistics::sample_variance;
+/// let xs = [2, 4, 4, 4, 5, 5, 7, 9].map(|v| BigFloat::from_word(v, 64));
+///
+/// let v = sample_variance(&xs, 128, RoundingMode::ToEven);
+///
+/// assert_eq!(v, BigFloat::from_word(32, 128).div(&BigFloat::from_word(7, 128), 128, RoundingMode::ToEven));
+/// ```
+pub fn sample_variance(xs: &[BigFloat], p: usize, rm: RoundingMode) -> BigFloat {
+    if xs.len() < 2 {
+        return BigFloat::nan(Some(Error::InvalidArgument));
+    }
+
+    let wp = working_precision(p, xs.len());
+
+    round(
+        sum_sq_dev(xs, wp).div(&count(xs.len() - 1), wp, RoundingMode::None),
+        p,
+        rm,
+    )
+}
+
+/// Computes the population standard deviation of the values `xs` with precision `p` using rounding mode `rm`.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+/// The function returns NaN if `xs` is empty, if any of the values is NaN, or if the precision `p` is incorrect.
+pub fn std_dev(xs: &[BigFloat], p: usize, rm: RoundingMode) -> BigFloat {
+    if xs.is_empty() {
+        return BigFloat::nan(Some(Error::InvalidArgument));
+    }
+
+    let wp = working_precision(p, xs.len());
+    let v = sum_sq_dev(xs, wp).div(&count(xs.len()), wp, RoundingMode::None);
+
+    round(v.sqrt(wp, RoundingMode::None), p, rm)
+}
+
+/// Computes the sample standard deviation of the values `xs`, i.e. the square root of the sample variance,
+/// with precision `p` using rounding mode `rm`.
+/// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+/// The function returns NaN if `xs` contains less than two values, if any of the values is NaN, or if the precision `p` is incorrect.
+pub fn sample_std_dev(xs: &[BigFloat], p: usize, rm: RoundingMode) -> BigFloat {
+    if xs.len() < 2 {
+        return BigFloat::nan(Some(Error::InvalidArgument));
+    }
+
+    let wp = working_precision(p, xs.len());
+    let v = sum_sq_dev(xs, wp).div(&count(xs.len() - 1), wp, RoundingMode::None);
+
+    round(v.sqrt(wp, RoundingMode::None), p, rm)
+}
+
+/// Accumulator of the running sum, mean, and variance of a stream of numbers.
+///
+/// The sum, the mean, and the sum of squared deviations from the mean (M2) are updated with each added value
+/// using the Welford's algorithm, and maintained with the internal precision given at construction.
+/// The values are rounded to the requested precision only when they are queried.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::BigFloat;
+/// # use astro_float_num::RoundingMode;
+/// # use astro_float_num::statistics::Accumulator;
+/// let rm = RoundingMode::ToEven;
+/// let mut acc = Accumulator::new(256);
+///
+/// for v in [2, 4, 4, 4, 5, 5, 7, 9] {
+///     acc.push(&BigFloat::from_word(v, 64));
+/// }
+///
+/// assert_eq!(acc.count(), 8);
+/// assert_eq!(acc.sum(64, rm), BigFloat::from_word(40, 64));
+/// assert_eq!(acc.mean(64, rm), BigFloat::from_word(5, 64));
+/// assert_eq!(acc.variance(64, rm), BigFloat::from_word(4, 64));
+/// ```
+#[derive(Debug)]
+pub struct Accumulator {
+    p: usize,
+    n: usize,
+    sum: BigFloat,
+    mean: BigFloat,
+    m2: BigFloat,
+}
+
+impl Accumulator {
+    /// Returns a new accumulator which maintains its values with the internal precision `p`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    pub fn new(p: usize) -> Self {
+        Accumulator {
+            p,
+            n: 0,
+            sum: BigFloat::from_word(0, p),
+            mean: BigFloat::from_word(0, p),
+            m2: BigFloat::from_word(0, p),
+        }
+    }
+
+    /// Returns the internal precision of the accumulator.
+    pub fn precision(&self) -> usize {
+        self.p
+    }
+
+    /// Adds the value `x` to the accumulator.
+    pub fn push(&mut self, x: &BigFloat) {
+        let rm = RoundingMode::ToEven;
+
+        self.n += 1;
+        self.sum = self.sum.add(x, self.p, rm);
+
+        let d1 = x.sub(&self.mean, self.p, rm);
+        self.mean = self
+            .mean
+            .add(&d1.div(&count(self.n), self.p, rm), self.p, rm);
+
+        let d2 = x.sub(&self.mean, self.p, rm);
+        self.m2 = self.m2.add(&d1.mul(&d2, self.p, rm), self.p, rm);
+    }
+
+    /// Returns the number of added values.
+    pub fn count(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the sum of the added values with precision `p` rounded using rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    pub fn sum(&self, p: usize, rm: RoundingMode) -> BigFloat {
+        round(self.sum.clone(), p, rm)
+    }
+
+    /// Returns the mean of the added values with precision `p` rounded using rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if no values were added.
+    pub fn mean(&self, p: usize, rm: RoundingMode) -> BigFloat {
+        if self.n == 0 {
+            return BigFloat::nan(Some(Error::InvalidArgument));
+        }
+
+        round(self.mean.clone(), p, rm)
+    }
+
+    /// Returns the sum of squared deviations of the added values from their mean (M2)
+    /// with precision `p` rounded using rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    pub fn m2(&self, p: usize, rm: RoundingMode) -> BigFloat {
+        round(self.m2.clone(), p, rm)
+    }
+
+    /// Returns the population variance of the added values with precision `p` rounded using rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if no values were added.
+    pub fn variance(&self, p: usize, rm: RoundingMode) -> BigFloat {
+        if self.n == 0 {
+            return BigFloat::nan(Some(Error::InvalidArgument));
+        }
+
+        round(
+            self.m2.div(&count(self.n), self.p, RoundingMode::None),
+            p,
+            rm,
+        )
+    }
+
+    /// Returns the sample variance of the added values with precision `p` rounded using rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if less than two values were added.
+    pub fn sample_variance(&self, p: usize, rm: RoundingMode) -> BigFloat {
+        if self.n < 2 {
+            return BigFloat::nan(Some(Error::InvalidArgument));
+        }
+
+        round(
+            self.m2.div(&count(self.n - 1), self.p, RoundingMode::None),
+            p,
+            rm,
+        )
+    }
+}
+
+// Width of the exponent range of a bin of ReproducibleSum in bits.
+const BIN_BITS: Exponent = 256;
+
+/// Summation of numbers with the result which does not depend on the order of addition.
+///
+/// The numbers are distributed among bins by their exponents, and the sum in each bin is computed exactly.
+/// The final result is computed from the bins in a fixed order, so it is bitwise identical for any order
+/// of the added numbers, and for any partitioning of the numbers between several accumulators which are merged afterwards.
+///
+/// ## Examples
+///
+/// ```
+/// # use astro_float_num::BigFloat;
+/// # use astro_float_num::RoundingMode;
+/// # use astro_float_num::statistics::ReproducibleSum;
+/// let xs = [1e30, 1.0, -1e30, 1e-30].map(|v| BigFloat::from_f64(v, 64));
+/// let rm = RoundingMode::ToEven;
+///
+/// let mut s1 = ReproducibleSum::new();
+/// xs.iter().for_each(|x| s1.add(x));
+///
+/// let mut s2 = ReproducibleSum::new();
+/// xs.iter().rev().for_each(|x| s2.add(x));
+///
+/// assert_eq!(s1.sum(64, rm), s2.sum(64, rm));
+/// assert_eq!(s1.sum(64, rm), BigFloat::from_word(1, 64));
+/// ```
+#[derive(Debug, Default)]
+pub struct ReproducibleSum {
+    bins: BTreeMap<Exponent, BigFloat>,
+    nan: bool,
+    inf_pos: bool,
+    inf_neg: bool,
+}
+
+impl ReproducibleSum {
+    /// Returns a new accumulator with the sum of 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `x` to the sum.
+    pub fn add(&mut self, x: &BigFloat) {
+        if x.is_nan() {
+            self.nan = true;
+        } else if x.is_inf_pos() {
+            self.inf_pos = true;
+        } else if x.is_inf_neg() {
+            self.inf_neg = true;
+        } else if let Some(e) = x.exponent() {
+            if !x.is_zero() {
+                self.add_to_bin(e.div_euclid(BIN_BITS), x);
+            }
+        }
+    }
+
+    /// Adds the numbers accumulated in `other` to the sum.
+    pub fn merge(&mut self, other: &Self) {
+        self.nan |= other.nan;
+        self.inf_pos |= other.inf_pos;
+        self.inf_neg |= other.inf_neg;
+
+        for (k, b) in other.bins.iter() {
+            self.add_to_bin(*k, b);
+        }
+    }
+
+    /// Returns the sum of all added numbers with precision `p` rounded using rounding mode `rm`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if any of the added numbers is NaN, if both positive and negative infinity were added,
+    /// or if the precision `p` is incorrect.
+    pub fn sum(&self, p: usize, rm: RoundingMode) -> BigFloat {
+        if self.nan {
+            return NAN;
+        }
+
+        match (self.inf_pos, self.inf_neg) {
+            (true, true) => return BigFloat::nan(Some(Error::InvalidArgument)),
+            (true, false) => return INF_POS,
+            (false, true) => return INF_NEG,
+            _ => {}
+        }
+
+        let wp = p.saturating_add(2 * WORD_BIT_SIZE);
+        let mut acc = BigFloat::from_word(0, wp);
+
+        // bins are added exactly starting from the largest one until the remaining bins can't cancel the sum
+        for b in self.bins.values().rev() {
+            acc = match (acc.exponent(), b.exponent()) {
+                (Some(e1), Some(e2))
+                    if !acc.is_zero() && e1 as isize - e2 as isize > wp as isize =>
+                {
+                    acc.add(b, wp, RoundingMode::None)
+                }
+                _ => acc.add_full_prec(b),
+            };
+        }
+
+        round(acc, p, rm)
+    }
+
+    fn add_to_bin(&mut self, k: Exponent, x: &BigFloat) {
+        let s = match self.bins.get(&k) {
+            Some(b) => b.add_full_prec(x),
+            None => x.clone(),
+        };
+
+        if s.is_zero() {
+            self.bins.remove(&k);
+        } else {
+            self.bins.insert(k, s);
+        }
+    }
+}
+
+// Precision of intermediate computations for `n` values and the target precision `p`.
+// Each value contributes at most one rounding error to a sum, and the error of the sum grows as n.
+fn working_precision(p: usize, n: usize) -> usize {
+    let n_bits = (usize::BITS - n.leading_zeros()) as usize;
+    p.saturating_add(2 * n_bits + 2 * WORD_BIT_SIZE)
+}
+
+fn count(n: usize) -> BigFloat {
+    BigFloat::from_u64(n as u64, 64)
+}
+
+fn sum(xs: &[BigFloat], wp: usize) -> BigFloat {
+    let mut acc = BigFloat::from_word(0, wp);
+
+    for x in xs {
+        acc = acc.add(x, wp, RoundingMode::ToEven);
+    }
+
+    acc
+}
+
+// Returns the sum of squared deviations from the mean using the corrected two-pass algorithm:
+// sum((x - m)^2) - (sum(x - m))^2 / n, where the second term compensates for the error of the mean.
+fn sum_sq_dev(xs: &[BigFloat], wp: usize) -> BigFloat {
+    let n = count(xs.len());
+    let m = sum(xs, wp).div(&n, wp, RoundingMode::ToEven);
+
+    let mut s1 = BigFloat::from_word(0, wp);
+    let mut s2 = BigFloat::from_word(0, wp);
+
+    for x in xs {
+        let d = x.sub(&m, wp, RoundingMode::ToEven);
+        s2 = s2.add(
+            &d.mul(&d, wp, RoundingMode::ToEven),
+            wp,
+            RoundingMode::ToEven,
+        );
+        s1 = s1.add(&d, wp, RoundingMode::ToEven);
+    }
+
+    let c = s1
+        .mul(&s1, wp, RoundingMode::ToEven)
+        .div(&n, wp, RoundingMode::ToEven);
+
+    s2.sub(&c, wp, RoundingMode::ToEven)
+}
+
+fn round(mut x: BigFloat, p: usize, rm: RoundingMode) -> BigFloat {
+    if let Err(err) = x.set_precision(p, rm) {
+        return BigFloat::nan(Some(err));
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Radix;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_statistics() {
+        let rm = RoundingMode::ToEven;
+        let p = 256;
+
+        let xs = [2, 4, 4, 4, 5, 5, 7, 9].map(|v| BigFloat::from_word(v, 64));
+
+        assert_eq!(mean(&xs, p, rm), BigFloat::from_word(5, p));
+        assert_eq!(variance(&xs, p, rm), BigFloat::from_word(4, p));
+        assert_eq!(std_dev(&xs, p, rm), BigFloat::from_word(2, p));
+        assert_eq!(
+            sample_variance(&xs, p, rm),
+            BigFloat::from_word(32, p).div(&BigFloat::from_word(7, p), p, rm)
+        );
+        assert_eq!(
+            sample_std_dev(&xs, p, rm),
+            BigFloat::from_word(32, p)
+                .div(
+                    &BigFloat::from_word(7, p),
+                    p + 2 * WORD_BIT_SIZE,
+                    RoundingMode::None
+                )
+                .sqrt(p, rm)
+        );
+
+        // large offset does not cause loss of accuracy
+        let off = BigFloat::parse("1e+40", Radix::Dec, 256, rm);
+        let ys: Vec<BigFloat> = xs.iter().map(|x| x.add(&off, 256, rm)).collect();
+        assert_eq!(variance(&ys, 128, rm), BigFloat::from_word(4, 128));
+        assert_eq!(
+            mean(&ys, 256, rm),
+            off.add(&BigFloat::from_word(5, 64), 256, rm)
+        );
+
+        // one third is rounded once
+        let xs = [0, 0, 1].map(|v| BigFloat::from_word(v, 64));
+        assert_eq!(
+            mean(&xs, p, RoundingMode::Up),
+            BigFloat::from_word(1, p).div(&BigFloat::from_word(3, p), p, RoundingMode::Up)
+        );
+        assert_eq!(
+            mean(&xs, p, RoundingMode::Down),
+            BigFloat::from_word(1, p).div(&BigFloat::from_word(3, p), p, RoundingMode::Down)
+        );
+
+        // reproducible sum
+        let xs: Vec<BigFloat> = (0..300)
+            .map(|i| {
+                let e = rand::random::<Exponent>() % 1000;
+                let mut x = BigFloat::random_normal(64 * (1 + i % 4), e - 10, e);
+                if i % 3 == 0 {
+                    x = x.mul(&BigFloat::from_f64(1e-20, 64), 128, rm);
+                }
+                x
+            })
+            .collect();
+
+        let mut s1 = ReproducibleSum::new();
+        xs.iter().for_each(|x| s1.add(x));
+
+        let mut s2 = ReproducibleSum::new();
+        let mut s3 = ReproducibleSum::new();
+        for (i, x) in xs.iter().rev().enumerate() {
+            if i % 2 == 0 {
+                s2.add(x);
+            } else {
+                s3.add(x);
+            }
+        }
+        s2.merge(&s3);
+
+        let mut exact = BigFloat::from_word(0, 64);
+        xs.iter().for_each(|x| exact = exact.add_full_prec(x));
+
+        for p in [64, 256, 1024] {
+            let r1 = s1.sum(p, rm);
+            let r2 = s2.sum(p, rm);
+            assert_eq!(r1, r2);
+            assert_eq!(r1.precision(), r2.precision());
+            assert_eq!(r1, round(exact.clone(), p, rm));
+        }
+
+        // cancellation
+        let mut s1 = ReproducibleSum::new();
+        for x in [1e300, 1.0, -1e300, 3.0, 1e-300] {
+            s1.add(&BigFloat::from_f64(x, 64));
+        }
+        assert_eq!(s1.sum(64, rm), BigFloat::from_word(4, 64));
+
+        let mut s1 = ReproducibleSum::new();
+        assert!(s1.sum(p, rm).is_zero());
+        s1.add(&crate::INF_POS);
+        assert!(s1.sum(p, rm).is_inf_pos());
+        s1.add(&crate::INF_NEG);
+        assert!(s1.sum(p, rm).is_nan());
+        let mut s1 = ReproducibleSum::new();
+        s1.add(&crate::NAN);
+        assert!(s1.sum(p, rm).is_nan());
+
+        // running statistics
+        let mut acc = Accumulator::new(p + 128);
+        assert!(acc.mean(p, rm).is_nan());
+        assert!(acc.variance(p, rm).is_nan());
+        assert!(acc.sum(p, rm).is_zero());
+        for x in ys.iter() {
+            acc.push(x);
+        }
+        assert_eq!(acc.count(), ys.len());
+        assert_eq!(acc.precision(), p + 128);
+        assert_eq!(acc.mean(p, rm), mean(&ys, p, rm));
+        assert_eq!(
+            acc.sum(p, rm),
+            off.mul(&BigFloat::from_word(8, 64), p, rm)
+                .add(&BigFloat::from_word(40, 64), p, rm)
+        );
+        assert_eq!(acc.variance(128, rm), BigFloat::from_word(4, 128));
+        assert_eq!(acc.m2(128, rm), BigFloat::from_word(32, 128));
+        assert_eq!(acc.sample_variance(128, rm), sample_variance(&ys, 128, rm));
+        acc.push(&crate::NAN);
+        assert!(acc.mean(p, rm).is_nan());
+
+        // invalid input
+        assert!(mean(&[], p, rm).is_nan());
+        assert!(variance(&[], p, rm).is_nan());
+        assert!(std_dev(&[], p, rm).is_nan());
+        assert!(sample_variance(&xs[..1], p, rm).is_nan());
+        assert!(sample_std_dev(&xs[..1], p, rm).is_nan());
+        assert!(mean(&[crate::NAN], p, rm).is_nan());
+        assert!(variance(&[BigFloat::from_word(1, 64), crate::NAN], p, rm).is_nan());
+    }
+}
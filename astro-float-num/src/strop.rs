@@ -4,40 +4,66 @@ use crate::defs::Error;
 use crate::defs::Radix;
 use crate::defs::RoundingMode;
 use crate::num::BigFloatNumber;
+#[cfg(any(test, feature = "serde"))]
 use crate::parser;
+use crate::parser::ParserState;
 use crate::Exponent;
-use crate::Sign;
-
-#[cfg(feature = "std")]
-use std::fmt::Write;
 
 #[cfg(not(feature = "std"))]
-use {alloc::string::String, core::fmt::Write};
+use {alloc::string::String, alloc::vec::Vec};
 
 const DIGIT_CHARS: [char; 16] =
     ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F'];
 
+// UTF-8 encoding of the horizontal ellipsis.
+const ELLIPSIS: [u8; 3] = [0xE2, 0x80, 0xA6];
+
 impl BigFloatNumber {
     /// Parses the number from the string `s` using radix `rdx`, precision `p`, and rounding mode `rm`.
     /// Note, since hexadecimal digits include the character "e", the exponent part is separated
     /// from the mantissa by "_".
     /// For example, a number with mantissa `123abcdef` and exponent `123` would be formatted as `123abcdef_e+123`.
+    /// If the number is too small to be represented, the result is zero, or the minimum
+    /// positive subnormal number with the sign of the parsed number when `rm` rounds away from zero.
+    /// If the number is too large to be represented, and `rm` rounds towards zero, the result is the largest finite number
+    /// with the sign of the parsed number.
+    /// In all these cases the result is marked as inexact.
     ///
     /// ## Errors
     ///
     ///  - InvalidArgument: failed to parse input or precision is incorrect.
     ///  - MemoryAllocation: failed to allocate memory for mantissa.
-    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent, and `rm` does not round towards zero.
+    #[cfg(any(test, feature = "serde"))]
     pub fn parse(s: &str, rdx: Radix, p: usize, rm: RoundingMode) -> Result<Self, Error> {
         Self::p_assertion(p)?;
 
         let ps = parser::parse(s, rdx)?;
 
-        if ps.is_nan() || ps.is_inf() {
+        if ps.is_nan() || (ps.is_inf() && !ps.is_exp_overflow()) {
             Err(Error::InvalidArgument)
+        } else {
+            Self::from_parser_state(&ps, rdx, p, rm)
+        }
+    }
+
+    // Converts the parsed finite number `ps` to BigFloatNumber.
+    pub(crate) fn from_parser_state(
+        ps: &ParserState,
+        rdx: Radix,
+        p: usize,
+        rm: RoundingMode,
+    ) -> Result<Self, Error> {
+        if ps.is_exp_overflow() {
+            Self::overflow(ps.sign(), p, rm)
+        } else if ps.is_exp_underflow() {
+            Self::underflow(ps.sign(), p, rm)
         } else {
             let (m, s, e) = ps.raw_parts();
-            BigFloatNumber::convert_from_radix(s, m, e, rdx, p, rm)
+            match BigFloatNumber::convert_from_radix(s, m, e, rdx, p, rm) {
+                Err(Error::ExponentOverflow(s)) => Self::overflow(s, p, rm),
+                r => r,
+            }
         }
     }
 
@@ -51,9 +77,78 @@ impl BigFloatNumber {
     ///  - MemoryAllocation: failed to allocate memory for mantissa.
     ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
     pub fn format(&self, rdx: Radix, rm: RoundingMode) -> Result<String, Error> {
-        let (s, m, e) = self.convert_to_radix(rdx, rm)?;
+        let mut buf = Vec::new();
+        self.format_into(rdx, rm, &mut buf)?;
+
+        // only ASCII characters are written
+        String::from_utf8(buf).map_err(|_| Error::InvalidArgument)
+    }
+
+    /// Formats the number using radix `rdx` and rounding mode `rm` like `format`,
+    /// but appends the ASCII characters of the result to `buf` instead of returning a new string.
+    /// The function returns the number of bytes appended to `buf`.
+    /// Formatting in radixes 2, 8, and 16 does not allocate memory if `buf` has enough capacity.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    pub fn format_into(
+        &self,
+        rdx: Radix,
+        rm: RoundingMode,
+        buf: &mut Vec<u8>,
+    ) -> Result<usize, Error> {
+        self.format_impl(rdx, rm, None, None, buf)
+    }
+
+    // Formats the number like `format_into`, but the mantissa is rounded to `prec` digits after the point,
+    // and trailing zeros are added if the mantissa has fewer digits.
+    pub(crate) fn format_prec_into(
+        &self,
+        rdx: Radix,
+        rm: RoundingMode,
+        prec: usize,
+        buf: &mut Vec<u8>,
+    ) -> Result<usize, Error> {
+        self.format_impl(rdx, rm, None, Some(prec), buf)
+    }
+
+    /// Formats the number using radix `rdx` and rounding mode `rm` like `format_into`,
+    /// but if the mantissa has more than `2*k` digits, only the first and the last `k` digits are written,
+    /// separated by an ellipsis, and the total number of digits is appended in parentheses,
+    /// e.g. `3.14159…02134e+0 (1000000 digits)`.
+    /// The function returns the number of bytes appended to `buf`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory for mantissa.
+    ///  - ExponentOverflow: the resulting exponent becomes greater than the maximum allowed value for the exponent.
+    ///  - InvalidArgument: `k` is zero.
+    pub fn format_abbrev_into(
+        &self,
+        rdx: Radix,
+        rm: RoundingMode,
+        k: usize,
+        buf: &mut Vec<u8>,
+    ) -> Result<usize, Error> {
+        if k == 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        self.format_impl(rdx, rm, Some(k), None, buf)
+    }
 
-        let mut mstr = String::new();
+    // Formats the number, and if `abbrev` is Some(k), keeps only the first and the last k digits of the mantissa.
+    // If `prec` is Some(n), the mantissa is rounded to n digits after the point.
+    fn format_impl(
+        &self,
+        rdx: Radix,
+        rm: RoundingMode,
+        abbrev: Option<usize>,
+        prec: Option<usize>,
+        buf: &mut Vec<u8>,
+    ) -> Result<usize, Error> {
         let mstr_sz = 8
             + (self.mantissa_max_bit_len() + core::mem::size_of::<Exponent>() * 8)
                 / match rdx {
@@ -63,56 +158,109 @@ impl BigFloatNumber {
                     Radix::Hex => 4,
                 };
 
-        mstr.try_reserve_exact(mstr_sz)?;
+        buf.try_reserve(mstr_sz + prec.unwrap_or(0))?;
+
+        let start = buf.len();
 
-        if s == Sign::Neg {
-            mstr.push('-');
+        if self.is_negative() {
+            buf.push(b'-');
         }
 
-        if m.is_empty() {
-            mstr.push_str("0.0");
+        let dstart = buf.len();
+        // the digit before the point is not counted in the precision of a normal number
+        let max_digits = prec.map(
+            |n| {
+                if self.is_subnormal() {
+                    n.max(1)
+                } else {
+                    n.saturating_add(1)
+                }
+            },
+        );
+
+        let (_, n, e) = self
+            .convert_to_radix_into(rdx, max_digits, rm, buf)
+            .inspect_err(|_| {
+                buf.truncate(start);
+            })?;
+
+        if n == 0 {
+            match prec {
+                Some(0) => buf.push(b'0'),
+                Some(prec) => {
+                    buf.extend_from_slice(b"0.");
+                    buf.resize(buf.len() + prec, b'0');
+                }
+                None => buf.extend_from_slice(b"0.0"),
+            }
         } else {
-            let mut iter = m.iter();
+            for d in buf[dstart..].iter_mut() {
+                *d = DIGIT_CHARS[*d as usize] as u8;
+            }
 
-            if self.is_subnormal() {
-                mstr.push('0');
-            } else {
-                mstr.push(DIGIT_CHARS[*iter.next().unwrap() as usize]); // m is not empty as checked above, hence unwrap
+            let abbrev = abbrev.filter(|k| n > k.saturating_mul(2));
+
+            if let Some(k) = abbrev {
+                buf.splice(dstart + k..dstart + n - k, ELLIPSIS.iter().copied());
             }
 
-            mstr.push('.');
+            if self.is_subnormal() {
+                buf.splice(dstart..dstart, *b"0.");
+            } else if prec != Some(0) {
+                buf.insert(dstart + 1, b'.');
+            }
 
-            iter.map(|&d| DIGIT_CHARS[d as usize])
-                .for_each(|v| mstr.push(v));
+            if let Some(max_digits) = max_digits {
+                if n < max_digits {
+                    buf.resize(buf.len() + max_digits - n, b'0');
+                }
+            }
 
             if rdx == Radix::Hex {
-                let _ = write!(mstr, "_");
+                buf.push(b'_');
             }
 
-            if e < 1 {
+            let (sign, val) = if e < 1 {
                 let val = if self.is_subnormal() {
                     e.unsigned_abs() as usize
                 } else {
                     (e as isize - 1).unsigned_abs()
                 };
-
-                let _ = match rdx {
-                    Radix::Bin => write!(mstr, "e-{:b}", val),
-                    Radix::Oct => write!(mstr, "e-{:o}", val),
-                    Radix::Dec => write!(mstr, "e-{}", val),
-                    Radix::Hex => write!(mstr, "e-{:x}", val),
-                };
+                (b'-', val)
             } else {
-                let _ = match rdx {
-                    Radix::Bin => write!(mstr, "e+{:b}", e as isize - 1),
-                    Radix::Oct => write!(mstr, "e+{:o}", e as isize - 1),
-                    Radix::Dec => write!(mstr, "e+{}", e as isize - 1),
-                    Radix::Hex => write!(mstr, "e+{:x}", e as isize - 1),
-                };
+                (b'+', (e as isize - 1) as usize)
             };
+
+            buf.push(b'e');
+            buf.push(sign);
+            Self::push_exponent(buf, val, rdx as usize);
+
+            if abbrev.is_some() {
+                buf.extend_from_slice(b" (");
+                Self::push_exponent(buf, n, 10);
+                buf.extend_from_slice(b" digits)");
+            }
+        }
+
+        Ok(buf.len() - start)
+    }
+
+    // Appends the digits of `val` in radix `rdx` to `buf`.
+    fn push_exponent(buf: &mut Vec<u8>, mut val: usize, rdx: usize) {
+        let mut digits = [0u8; usize::BITS as usize];
+        let mut i = digits.len();
+
+        loop {
+            i -= 1;
+            digits[i] = DIGIT_CHARS[val % rdx].to_ascii_lowercase() as u8;
+            val /= rdx;
+
+            if val == 0 {
+                break;
+            }
         }
 
-        Ok(mstr)
+        buf.extend_from_slice(&digits[i..]);
     }
 }
 
@@ -122,19 +270,23 @@ mod tests {
     use rand::random;
 
     use crate::{
-        common::util::random_subnormal, Exponent, EXPONENT_MAX, EXPONENT_MIN, WORD_BIT_SIZE,
+        common::util::{random_subnormal, round_p},
+        Exponent, Sign, EXPONENT_MAX, EXPONENT_MIN, WORD_BIT_SIZE,
     };
 
     use super::*;
 
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
     #[test]
     fn test_strop() {
         let mut eps = BigFloatNumber::from_word(1, 192).unwrap();
         let rm = RoundingMode::ToEven;
 
         for i in 0..1000 {
-            let p1 = (random::<usize>() % 32 + 3) * WORD_BIT_SIZE;
-            let p2 = (random::<usize>() % 32 + 3) * WORD_BIT_SIZE;
+            let p1 = round_p((random::<usize>() % 32 + 3) * WORD_BIT_SIZE);
+            let p2 = round_p((random::<usize>() % 32 + 3) * WORD_BIT_SIZE);
             let p = p1.min(p2);
 
             for rdx in [Radix::Bin, Radix::Oct, Radix::Hex, Radix::Dec] {
@@ -170,8 +322,8 @@ mod tests {
         }
 
         // MIN, MAX, min_positive
-        let p1 = (random::<usize>() % 32 + 1) * WORD_BIT_SIZE;
-        let p2 = (random::<usize>() % 32 + 1) * WORD_BIT_SIZE;
+        let p1 = round_p((random::<usize>() % 32 + 1) * WORD_BIT_SIZE);
+        let p2 = round_p((random::<usize>() % 32 + 1) * WORD_BIT_SIZE);
         let p = p1.min(p2);
 
         for rdx in [Radix::Bin, Radix::Oct, Radix::Dec, Radix::Hex] {
@@ -224,4 +376,204 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_format_into() {
+        let rm = RoundingMode::ToEven;
+        let mut buf = Vec::new();
+        let mut digits = Vec::new();
+
+        for i in 0..100 {
+            let p = (random::<usize>() % 32 + 3) * WORD_BIT_SIZE;
+            let n = if i & 1 == 0 {
+                BigFloatNumber::random_normal(p, EXPONENT_MIN + p as Exponent, EXPONENT_MAX)
+                    .unwrap()
+            } else {
+                random_subnormal(p)
+            };
+
+            for rdx in [Radix::Bin, Radix::Oct, Radix::Hex, Radix::Dec] {
+                // appends to existing content
+                buf.clear();
+                buf.extend_from_slice(b"x=");
+                let len = n.format_into(rdx, rm, &mut buf).unwrap();
+                let s = n.format(rdx, rm).unwrap();
+                assert_eq!(len, s.len());
+                assert_eq!(&buf[2..], s.as_bytes());
+
+                digits.clear();
+                let (s1, len, e1) = n.convert_to_radix_into(rdx, None, rm, &mut digits).unwrap();
+                let (s2, m2, e2) = n.convert_to_radix(rdx, None, rm).unwrap();
+                assert_eq!(len, digits.len());
+                assert_eq!((s1, &digits, e1), (s2, &m2, e2));
+            }
+        }
+
+        // no reallocation when the buffer is large enough
+        let n = BigFloatNumber::random_normal(640, -100, 100).unwrap();
+        let mut buf = Vec::with_capacity(1024);
+        let ptr = buf.as_ptr();
+        for rdx in [Radix::Bin, Radix::Oct, Radix::Hex] {
+            buf.clear();
+            n.format_into(rdx, rm, &mut buf).unwrap();
+            assert_eq!(ptr, buf.as_ptr());
+        }
+
+        let mut buf = Vec::new();
+        let len = BigFloatNumber::new(64)
+            .unwrap()
+            .format_into(Radix::Dec, rm, &mut buf)
+            .unwrap();
+        assert_eq!((len, buf.as_slice()), (3, b"0.0".as_slice()));
+
+        // abbreviated
+        for i in 0..100 {
+            let p = (random::<usize>() % 32 + 3) * WORD_BIT_SIZE;
+            let n = if i & 1 == 0 {
+                BigFloatNumber::random_normal(p, EXPONENT_MIN + p as Exponent, EXPONENT_MAX)
+                    .unwrap()
+            } else {
+                random_subnormal(p)
+            };
+            let k = random::<usize>() % 40 + 1;
+
+            for rdx in [Radix::Bin, Radix::Oct, Radix::Hex, Radix::Dec] {
+                let s = n.format(rdx, rm).unwrap();
+                let a = format_abbrev(&n, rdx, rm, k).unwrap();
+
+                digits.clear();
+                let (_, len, _) = n.convert_to_radix_into(rdx, None, rm, &mut digits).unwrap();
+
+                if len > 2 * k {
+                    let (m, e) = s.split_at(s.find(['_', 'e']).unwrap());
+                    let head = m.len() - len;
+                    let expected = format!(
+                        "{}…{}{} ({} digits)",
+                        &m[..head + k],
+                        &m[m.len() - k..],
+                        e,
+                        len
+                    );
+                    assert_eq!(a, expected);
+                } else {
+                    assert_eq!(a, s);
+                }
+            }
+        }
+
+        let n = BigFloatNumber::from_word(1234567, 64).unwrap();
+        assert_eq!(
+            format_abbrev(&n, Radix::Dec, rm, 3).unwrap(),
+            "1.23…567e+6 (7 digits)"
+        );
+        assert_eq!(format_abbrev(&n, Radix::Dec, rm, 4).unwrap(), "1.234567e+6");
+        assert_eq!(
+            format_abbrev(&n, Radix::Dec, rm, 0),
+            Err(Error::InvalidArgument)
+        );
+    }
+
+    fn format_abbrev(
+        n: &BigFloatNumber,
+        rdx: Radix,
+        rm: RoundingMode,
+        k: usize,
+    ) -> Result<String, Error> {
+        let mut buf = Vec::new();
+        n.format_abbrev_into(rdx, rm, k, &mut buf)?;
+        Ok(String::from_utf8(buf).unwrap())
+    }
+
+    #[test]
+    fn test_parse_exp_range() {
+        // exponent overflow: the parser's exponent is out of range,
+        // the decimal exponent is out of range, the binary exponent is out of range.
+        for (s, rdx) in [
+            ("1e99999999999999999999999".into(), Radix::Dec),
+            ("-1e99999999999999999999999".into(), Radix::Dec),
+            (format!("1e{}", EXPONENT_MAX), Radix::Dec),
+            (format!("-f_e+{:x}", EXPONENT_MAX), Radix::Hex),
+        ] {
+            let s: &str = &s;
+            let sign = if s.starts_with('-') { Sign::Neg } else { Sign::Pos };
+            for rm in [
+                RoundingMode::None,
+                RoundingMode::ToEven,
+                RoundingMode::ToZero,
+                RoundingMode::Up,
+                RoundingMode::Down,
+                RoundingMode::FromZero,
+                RoundingMode::ToOdd,
+                RoundingMode::ToNearestAway,
+            ] {
+                let towards_zero = rm == RoundingMode::None
+                    || rm == RoundingMode::ToZero
+                    || (rm == RoundingMode::Up && sign == Sign::Neg)
+                    || (rm == RoundingMode::Down && sign == Sign::Pos);
+
+                if towards_zero {
+                    let n = BigFloatNumber::parse(s, rdx, 64, rm).unwrap();
+                    let mut max = BigFloatNumber::max_value(64).unwrap();
+                    max.set_sign(sign);
+                    assert_eq!(n.cmp(&max), 0);
+                    assert!(n.inexact());
+                } else {
+                    assert_eq!(
+                        BigFloatNumber::parse(s, rdx, 64, rm).unwrap_err(),
+                        Error::ExponentOverflow(sign)
+                    );
+                }
+            }
+        }
+
+        // exponent underflow: the parser's exponent is out of range,
+        // the decimal exponent is out of range, the binary exponent is out of range.
+        for (s, rdx) in [
+            ("1e-99999999999999999999999".into(), Radix::Dec),
+            ("-1e-99999999999999999999999".into(), Radix::Dec),
+            (format!("1e-{}", EXPONENT_MAX), Radix::Dec),
+            (format!("-1e-{}", EXPONENT_MAX), Radix::Dec),
+            (format!("8_e-{:x}", EXPONENT_MAX), Radix::Hex),
+            (format!("-8_e-{:x}", EXPONENT_MAX), Radix::Hex),
+            (format!("1e-{:o}", EXPONENT_MAX), Radix::Oct),
+        ] {
+            let s: &str = &s;
+            let sign = if s.starts_with('-') { Sign::Neg } else { Sign::Pos };
+
+            for rm in [
+                RoundingMode::None,
+                RoundingMode::ToEven,
+                RoundingMode::ToZero,
+                RoundingMode::Up,
+                RoundingMode::Down,
+                RoundingMode::FromZero,
+                RoundingMode::ToOdd,
+                RoundingMode::ToNearestAway,
+            ] {
+                let n = BigFloatNumber::parse(s, rdx, 64, rm).unwrap();
+
+                assert_eq!(n.sign(), sign);
+                assert!(n.inexact());
+
+                let away = rm == RoundingMode::FromZero
+                    || (rm == RoundingMode::Up && sign == Sign::Pos)
+                    || (rm == RoundingMode::Down && sign == Sign::Neg);
+
+                if away {
+                    let mut min = BigFloatNumber::min_positive(64).unwrap();
+                    min.set_sign(sign);
+                    assert_eq!(n.cmp(&min), 0);
+                } else {
+                    assert!(n.is_zero());
+                }
+            }
+        }
+
+        // zero mantissa is not affected
+        for s in ["0.000e-99999999999999999999999", "0.000e99999999999999999999999"] {
+            let n = BigFloatNumber::parse(s, Radix::Dec, 64, RoundingMode::Up).unwrap();
+            assert!(n.is_zero());
+            assert!(!n.inexact());
+        }
+    }
 }
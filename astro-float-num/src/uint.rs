@@ -0,0 +1,677 @@
+//! Arbitrary precision unsigned integer.
+
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display, Formatter};
+use core::str::FromStr;
+
+use crate::common::util::{add_carry, sub_borrow};
+use crate::defs::{DoubleWord, Error, RoundingMode, Sign, Word, WORD_BIT_SIZE};
+use crate::ext::BigFloat;
+use crate::mantissa::Mantissa;
+use crate::num::BigFloatNumber;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+// 10^DEC_CHUNK is the largest power of 10 which fits in a word.
+#[cfg(not(word32))]
+const DEC_CHUNK: usize = 19;
+#[cfg(word32)]
+const DEC_CHUNK: usize = 9;
+const DEC_CHUNK_BASE: Word = (10 as Word).pow(DEC_CHUNK as u32);
+
+/// An unsigned integer of arbitrary size.
+///
+/// The integer is stored as a sequence of words with the least significant word first.
+/// Multiplication and division use the same algorithms as the mantissa of `BigFloat`
+/// (schoolbook, Toom-2, Toom-3, FFT, and recursive division), so large operands are handled efficiently.
+///
+/// Operations return `Error::MemoryAllocation` if memory can't be allocated instead of panicking.
+///
+/// ## Examples
+///
+/// ```
+/// use astro_float_num::{BigFloat, BigUInt, RoundingMode};
+///
+/// let a: BigUInt = "123456789012345678901234567890".parse().unwrap();
+/// let b = BigUInt::from_u64(987654321).unwrap();
+///
+/// let c = a.mul(&b).unwrap();
+/// assert_eq!(c.to_string(), "121932631124828532112482853211126352690");
+///
+/// let (q, r) = c.add(&BigUInt::from_u64(5).unwrap()).unwrap().div_rem(&a).unwrap();
+/// assert_eq!(q, b);
+/// assert_eq!(r.to_u64(), Some(5));
+///
+/// // conversion to and from BigFloat
+/// let f = BigFloat::from_biguint(&q, 128, RoundingMode::ToEven);
+/// assert_eq!(f, BigFloat::from_u64(987654321, 128));
+/// assert_eq!(f.to_biguint(RoundingMode::ToEven).unwrap(), q);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUInt {
+    m: Vec<Word>,
+}
+
+impl BigUInt {
+    /// Returns zero.
+    pub fn new() -> Self {
+        BigUInt { m: Vec::new() }
+    }
+
+    /// Constructs an integer from the word `d`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn from_word(d: Word) -> Result<Self, Error> {
+        Self::from_words(&[d])
+    }
+
+    /// Constructs an integer from `u64` value.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn from_u64(u: u64) -> Result<Self, Error> {
+        Self::from_u128(u as u128)
+    }
+
+    /// Constructs an integer from `u128` value.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn from_u128(mut u: u128) -> Result<Self, Error> {
+        let mut w = [0; 128 / WORD_BIT_SIZE];
+        for v in w.iter_mut() {
+            *v = u as Word;
+            u >>= WORD_BIT_SIZE;
+        }
+        Self::from_words(&w)
+    }
+
+    /// Constructs an integer from the slice of words `m` with the least significant word first.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn from_words(m: &[Word]) -> Result<Self, Error> {
+        let l = m.iter().rposition(|&v| v != 0).map_or(0, |i| i + 1);
+
+        let mut ret = Self::with_capacity(l)?;
+        ret.m.extend_from_slice(&m[..l]);
+
+        Ok(ret)
+    }
+
+    /// Returns the words of the integer with the least significant word first.
+    /// The most significant word is never zero, and zero has no words.
+    pub fn as_words(&self) -> &[Word] {
+        &self.m
+    }
+
+    /// Returns the value of the integer as `u64`, or None if it does not fit in `u64`.
+    pub fn to_u64(&self) -> Option<u64> {
+        self.to_u128().and_then(|v| u64::try_from(v).ok())
+    }
+
+    /// Returns the value of the integer as `u128`, or None if it does not fit in `u128`.
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.m.len() > 128 / WORD_BIT_SIZE {
+            return None;
+        }
+
+        Some(
+            self.m
+                .iter()
+                .rev()
+                .fold(0, |acc, &v| (acc << WORD_BIT_SIZE) | v as u128),
+        )
+    }
+
+    /// Returns true if the integer is zero.
+    pub fn is_zero(&self) -> bool {
+        self.m.is_empty()
+    }
+
+    /// Returns the number of significant bits of the integer.
+    pub fn bit_len(&self) -> usize {
+        match self.m.last() {
+            Some(v) => self.m.len() * WORD_BIT_SIZE - v.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Returns the sum of `self` and `d2`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn add(&self, d2: &Self) -> Result<Self, Error> {
+        let (lg, sm) = if self.m.len() >= d2.m.len() { (self, d2) } else { (d2, self) };
+
+        let mut ret = Self::with_capacity(lg.m.len() + 1)?;
+
+        let mut c = 0;
+        for (i, &a) in lg.m.iter().enumerate() {
+            let mut v = 0;
+            c = add_carry(a, sm.m.get(i).copied().unwrap_or(0), c, &mut v);
+            ret.m.push(v);
+        }
+
+        if c > 0 {
+            ret.m.push(c);
+        }
+
+        Ok(ret)
+    }
+
+    /// Returns the difference of `self` and `d2`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `d2` is greater than `self`.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn sub(&self, d2: &Self) -> Result<Self, Error> {
+        if *self < *d2 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut ret = Self::with_capacity(self.m.len())?;
+
+        let mut c = 0;
+        for (i, &a) in self.m.iter().enumerate() {
+            let mut v = 0;
+            c = sub_borrow(a, d2.m.get(i).copied().unwrap_or(0), c, &mut v);
+            ret.m.push(v);
+        }
+
+        debug_assert!(c == 0);
+
+        ret.normalize();
+
+        Ok(ret)
+    }
+
+    /// Returns the product of `self` and `d2`.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn mul(&self, d2: &Self) -> Result<Self, Error> {
+        if self.is_zero() || d2.is_zero() {
+            return Ok(Self::new());
+        }
+
+        let mut ret = Self::with_capacity(self.m.len() + d2.m.len())?;
+        ret.m.resize(self.m.len() + d2.m.len(), 0);
+
+        Mantissa::mul_unbalanced(&self.m, &d2.m, &mut ret.m)?;
+
+        ret.normalize();
+
+        Ok(ret)
+    }
+
+    /// Returns the quotient and the remainder of the division of `self` by `d2`.
+    ///
+    /// ## Errors
+    ///
+    ///  - DivisionByZero: `d2` is zero.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn div_rem(&self, d2: &Self) -> Result<(Self, Self), Error> {
+        if d2.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+
+        if *self < *d2 {
+            return Ok((Self::new(), Self::from_words(&self.m)?));
+        }
+
+        if d2.m.len() == 1 {
+            let (q, r) = self.div_rem_word(d2.m[0])?;
+            return Ok((q, Self::from_word(r)?));
+        }
+
+        // the divisor must be normalized
+        let shift = d2.m[d2.m.len() - 1].leading_zeros() as usize;
+        let n = self.shl(shift)?;
+        let d = d2.shl(shift)?;
+
+        let (q, r) = Mantissa::div_unbalanced(&n.m, &d.m)?;
+
+        let q = Self::from_words(&q)?;
+        let r = Self::from_words(&r)?.shr(shift)?;
+
+        Ok((q, r))
+    }
+
+    /// Returns the quotient of the division of `self` by `d2` rounded towards zero.
+    ///
+    /// ## Errors
+    ///
+    ///  - DivisionByZero: `d2` is zero.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn div(&self, d2: &Self) -> Result<Self, Error> {
+        self.div_rem(d2).map(|(q, _)| q)
+    }
+
+    /// Returns the remainder of the division of `self` by `d2`.
+    ///
+    /// ## Errors
+    ///
+    ///  - DivisionByZero: `d2` is zero.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn rem(&self, d2: &Self) -> Result<Self, Error> {
+        self.div_rem(d2).map(|(_, r)| r)
+    }
+
+    /// Returns `self` shifted left by `n` bits.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn shl(&self, n: usize) -> Result<Self, Error> {
+        if self.is_zero() {
+            return Ok(Self::new());
+        }
+
+        let idx = n / WORD_BIT_SIZE;
+        let shift = n % WORD_BIT_SIZE;
+
+        let mut ret = Self::with_capacity(self.m.len() + idx + 1)?;
+        ret.m.resize(idx, 0);
+
+        if shift > 0 {
+            let mut c = 0;
+            for &v in self.m.iter() {
+                ret.m.push((v << shift) | c);
+                c = v >> (WORD_BIT_SIZE - shift);
+            }
+
+            if c > 0 {
+                ret.m.push(c);
+            }
+        } else {
+            ret.m.extend_from_slice(&self.m);
+        }
+
+        Ok(ret)
+    }
+
+    /// Returns `self` shifted right by `n` bits.
+    ///
+    /// ## Errors
+    ///
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn shr(&self, n: usize) -> Result<Self, Error> {
+        let idx = n / WORD_BIT_SIZE;
+        let shift = n % WORD_BIT_SIZE;
+
+        if idx >= self.m.len() {
+            return Ok(Self::new());
+        }
+
+        let mut ret = Self::with_capacity(self.m.len() - idx)?;
+
+        if shift > 0 {
+            for (i, &v) in self.m[idx..].iter().enumerate() {
+                let hi = self.m.get(idx + i + 1).copied().unwrap_or(0);
+                ret.m.push((v >> shift) | (hi << (WORD_BIT_SIZE - shift)));
+            }
+        } else {
+            ret.m.extend_from_slice(&self.m[idx..]);
+        }
+
+        ret.normalize();
+
+        Ok(ret)
+    }
+
+    /// Converts `self` to BigFloatNumber with precision `p` using rounding mode `rm`.
+    pub(crate) fn to_number(&self, p: usize, rm: RoundingMode) -> Result<BigFloatNumber, Error> {
+        let mut ret = BigFloatNumber::from_int_ratio(Sign::Pos, &self.m, 0)?;
+        ret.set_precision(crate::common::util::round_p(p), rm)?;
+        Ok(ret)
+    }
+
+    /// Returns the absolute value of the integer `d`.
+    pub(crate) fn from_number(d: &BigFloatNumber) -> Result<Self, Error> {
+        debug_assert!(d.is_int());
+
+        let (_, m, e) = d.to_int_ratio()?;
+
+        Self { m }.shl(e as usize)
+    }
+
+    /// Appends the decimal digits of `self` to `buf`, most significant first.
+    /// Zero has no digits.
+    pub(crate) fn dec_digits(&self, buf: &mut Vec<u8>) -> Result<(), Error> {
+        let mut w = Self::from_words(&self.m)?;
+
+        // chunks of digits, least significant first
+        let mut chunks = Vec::new();
+        chunks.try_reserve_exact(w.m.len() * WORD_BIT_SIZE / (3 * DEC_CHUNK) + 1)?;
+
+        while !w.is_zero() {
+            let r = w.div_rem_word_assign(DEC_CHUNK_BASE);
+            chunks.push(r);
+        }
+
+        buf.try_reserve(chunks.len() * DEC_CHUNK)?;
+
+        for (i, &v) in chunks.iter().rev().enumerate() {
+            let mut digits = [0u8; DEC_CHUNK];
+            let mut v = v;
+            for b in digits.iter_mut().rev() {
+                *b = (v % 10) as u8;
+                v /= 10;
+            }
+
+            if i == 0 {
+                let lz = digits.iter().take_while(|x| **x == 0).count();
+                buf.extend_from_slice(&digits[lz..]);
+            } else {
+                buf.extend_from_slice(&digits);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Returns zero with reserved space for `n` words.
+    fn with_capacity(n: usize) -> Result<Self, Error> {
+        let mut m = Vec::new();
+        m.try_reserve_exact(n)?;
+        Ok(BigUInt { m })
+    }
+
+    // Removes the most significant zero words.
+    fn normalize(&mut self) {
+        while self.m.last() == Some(&0) {
+            self.m.pop();
+        }
+    }
+
+    // Returns the quotient and the remainder of the division of `self` by the word `d`.
+    fn div_rem_word(&self, d: Word) -> Result<(Self, Word), Error> {
+        let mut q = Self::from_words(&self.m)?;
+        let r = q.div_rem_word_assign(d);
+        Ok((q, r))
+    }
+
+    // Divides `self` by the word `d` in place and returns the remainder.
+    fn div_rem_word_assign(&mut self, d: Word) -> Word {
+        debug_assert!(d != 0);
+
+        let d = d as DoubleWord;
+        let mut r: DoubleWord = 0;
+        for v in self.m.iter_mut().rev() {
+            let a = (r << WORD_BIT_SIZE) | *v as DoubleWord;
+            *v = (a / d) as Word;
+            r = a % d;
+        }
+
+        self.normalize();
+
+        r as Word
+    }
+
+    // Multiplies `self` by the word `d` and adds the word `a` in place.
+    fn mul_add_word_assign(&mut self, d: Word, a: Word) -> Result<(), Error> {
+        let mut c = a as DoubleWord;
+        for v in self.m.iter_mut() {
+            let t = *v as DoubleWord * d as DoubleWord + c;
+            *v = t as Word;
+            c = t >> WORD_BIT_SIZE;
+        }
+
+        if c > 0 {
+            self.m.try_reserve(1)?;
+            self.m.push(c as Word);
+        }
+
+        Ok(())
+    }
+}
+
+impl BigFloat {
+    /// Constructs a number with precision `p` from the integer `n`.
+    /// The result is rounded using the rounding mode `rm` if `n` does not fit in precision `p`.
+    /// Precision is rounded upwards to a multiple of `PRECISION_GRANULARITY`.
+    /// The function returns NaN if the precision `p` is incorrect, and Inf if `n` is too large.
+    pub fn from_biguint(n: &BigUInt, p: usize, rm: RoundingMode) -> Self {
+        Self::result_to_ext(n.to_number(p, rm), false, true)
+    }
+
+    /// Converts `self` to an integer after rounding it to an integer using the rounding mode `rm`.
+    ///
+    /// ## Errors
+    ///
+    ///  - InvalidArgument: `self` is NaN, or the rounded value is negative.
+    ///  - ExponentOverflow: `self` is Inf.
+    ///  - MemoryAllocation: failed to allocate memory.
+    pub fn to_biguint(&self, rm: RoundingMode) -> Result<BigUInt, Error> {
+        let (s, m, e) = self.round(0, rm).to_int_ratio()?;
+
+        if s.is_negative() && !m.is_empty() {
+            return Err(Error::InvalidArgument);
+        }
+
+        BigUInt { m }.shl(e as usize)
+    }
+}
+
+impl PartialOrd for BigUInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.m
+            .len()
+            .cmp(&other.m.len())
+            .then_with(|| self.m.iter().rev().cmp(other.m.iter().rev()))
+    }
+}
+
+impl FromStr for BigUInt {
+    type Err = Error;
+
+    /// Parses a decimal integer. Returns `Error::InvalidArgument` if `src` is not a sequence of decimal digits.
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        if src.is_empty() || !src.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut ret = Self::with_capacity(src.len() / (3 * DEC_CHUNK) + 1)?;
+
+        for chunk in src.as_bytes().chunks(DEC_CHUNK) {
+            let v = chunk
+                .iter()
+                .fold(0, |acc: Word, &b| acc * 10 + (b - b'0') as Word);
+            ret.mul_add_word_assign((10 as Word).pow(chunk.len() as u32), v)?;
+        }
+
+        ret.normalize();
+
+        Ok(ret)
+    }
+}
+
+impl Display for BigUInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+
+        let mut digits = Vec::new();
+        self.dec_digits(&mut digits).map_err(|_| core::fmt::Error)?;
+
+        for d in digits {
+            core::fmt::Write::write_char(f, (b'0' + d) as char)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for BigUInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "BigUInt({})", self)
+    }
+}
+
+macro_rules! impl_from_uint {
+    ($t:ty) => {
+        impl TryFrom<$t> for BigUInt {
+            type Error = Error;
+
+            fn try_from(v: $t) -> Result<Self, Self::Error> {
+                Self::from_u128(v as u128)
+            }
+        }
+    };
+}
+
+impl_from_uint!(u8);
+impl_from_uint!(u16);
+impl_from_uint!(u32);
+impl_from_uint!(u64);
+impl_from_uint!(u128);
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    fn random_uint(max_words: usize) -> BigUInt {
+        let l = rand::random::<usize>() % max_words + 1;
+        let mut m = Vec::new();
+        for _ in 0..l {
+            m.push(rand::random::<Word>());
+        }
+        BigUInt::from_words(&m).unwrap()
+    }
+
+    #[test]
+    fn test_biguint() {
+        // against u128
+        for _ in 0..10000 {
+            let a = rand::random::<u64>() as u128 >> (rand::random::<u32>() % 64);
+            let b = rand::random::<u64>() as u128 >> (rand::random::<u32>() % 64);
+            let ba = BigUInt::from_u128(a).unwrap();
+            let bb = BigUInt::from_u128(b).unwrap();
+
+            assert_eq!(ba.to_u128(), Some(a));
+            assert_eq!(ba.cmp(&bb), a.cmp(&b));
+            assert_eq!(ba.add(&bb).unwrap().to_u128(), Some(a + b));
+            assert_eq!(ba.mul(&bb).unwrap().to_u128(), Some(a * b));
+            if a >= b {
+                assert_eq!(ba.sub(&bb).unwrap().to_u128(), Some(a - b));
+            } else {
+                assert_eq!(ba.sub(&bb).unwrap_err(), Error::InvalidArgument);
+            }
+            if b > 0 {
+                let ab = ba.mul(&bb).unwrap().add(&ba).unwrap();
+                let (q, r) = ab.div_rem(&bb).unwrap();
+                assert_eq!(q.to_u128(), Some((a * b + a) / b));
+                assert_eq!(r.to_u128(), Some((a * b + a) % b));
+            } else {
+                assert_eq!(ba.div_rem(&bb).unwrap_err(), Error::DivisionByZero);
+            }
+            let s = rand::random::<usize>() % 64;
+            assert_eq!(ba.shl(s).unwrap().to_u128(), Some(a << s));
+            assert_eq!(ba.shr(s).unwrap().to_u128(), Some(a >> s));
+            assert_eq!(ba.to_string(), a.to_string());
+            assert_eq!(a.to_string().parse::<BigUInt>().unwrap(), ba);
+            assert_eq!(ba.bit_len(), 128 - a.leading_zeros() as usize);
+        }
+
+        // large operands: (a * b + r) / b = a, remainder r
+        for (max_words, n) in [(4, 20), (50, 20), (300, 20), (1000, 20), (6000, 2)] {
+            for _ in 0..n {
+                let a = random_uint(max_words);
+                let b = random_uint(max_words);
+                let r = random_uint(max_words).rem(&b).unwrap();
+
+                let n = a.mul(&b).unwrap().add(&r).unwrap();
+                let (q, r2) = n.div_rem(&b).unwrap();
+                assert_eq!(q, a);
+                assert_eq!(r2, r);
+
+                assert_eq!(n.sub(&r).unwrap().div(&a).unwrap(), b);
+
+                let s = rand::random::<usize>() % 500;
+                assert_eq!(n.shl(s).unwrap().shr(s).unwrap(), n);
+
+                assert_eq!(n.to_string().parse::<BigUInt>().unwrap(), n);
+            }
+        }
+
+        // zero
+        let z = BigUInt::new();
+        assert!(z.is_zero() && z.as_words().is_empty());
+        assert_eq!(z.to_string(), "0");
+        assert_eq!("000".parse::<BigUInt>().unwrap(), z);
+        assert!("".parse::<BigUInt>().is_err());
+        assert!("12a".parse::<BigUInt>().is_err());
+        assert_eq!(BigUInt::from_words(&[0, 0]).unwrap(), z);
+        assert!(z.mul(&random_uint(3)).unwrap().is_zero());
+        assert!(z.shl(100).unwrap().is_zero());
+        assert_eq!(BigUInt::try_from(7u8).unwrap().to_u64(), Some(7));
+        assert_eq!(BigUInt::from_u128(u128::MAX).unwrap().to_u64(), None);
+    }
+
+    #[test]
+    fn test_biguint_big_float() {
+        let rm = RoundingMode::ToEven;
+
+        for _ in 0..1000 {
+            let n = random_uint(10);
+            let p = n.bit_len();
+
+            let f = BigFloat::from_biguint(&n, p, rm);
+            assert_eq!(f.to_biguint(rm).unwrap(), n);
+
+            // rounding to a smaller precision
+            if p > 64 {
+                let f = BigFloat::from_biguint(&n, 64, RoundingMode::Down);
+                let d = f.to_biguint(rm).unwrap();
+                assert!(d <= n);
+                assert!(n.shr(p - 64).unwrap() == d.shr(p - 64).unwrap());
+            }
+        }
+
+        let x = BigFloat::parse("12345.5", crate::Radix::Dec, 128, rm);
+        assert_eq!(x.to_biguint(rm).unwrap().to_u64(), Some(12346));
+        assert_eq!(
+            x.to_biguint(RoundingMode::ToZero).unwrap().to_u64(),
+            Some(12345)
+        );
+
+        let x = BigFloat::from_f64(-0.25, 64);
+        assert!(x.to_biguint(rm).unwrap().is_zero());
+        assert_eq!(
+            x.to_biguint(RoundingMode::Down).unwrap_err(),
+            Error::InvalidArgument
+        );
+
+        assert_eq!(
+            crate::NAN.to_biguint(rm).unwrap_err(),
+            Error::InvalidArgument
+        );
+        assert_eq!(
+            crate::INF_POS.to_biguint(rm).unwrap_err(),
+            Error::ExponentOverflow(Sign::Pos)
+        );
+
+        assert!(BigFloat::from_biguint(&BigUInt::new(), 64, rm).is_zero());
+    }
+}
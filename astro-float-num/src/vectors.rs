@@ -0,0 +1,599 @@
+//! Export and verification of golden test vectors.
+//!
+//! A test vector records an operation, its arguments, the precision, the rounding mode, and the expected result.
+//! Vectors are serialized as text lines which can be shipped together with the software using the library
+//! and re-verified on a different target or with a different version of the library to detect regressions.
+//!
+//! Each line has the form `<op> <p> <rm> <arg>... <expected>`, where numbers are written as
+//! `[~](+|-)<mantissa>@<exponent>`, `nan`, `+inf`, or `-inf`. The mantissa is written in hexadecimal,
+//! most significant digits first, `@` separates the binary exponent, and `~` marks an inexact number.
+//! Empty lines and lines starting with `#` are ignored.
+//!
+//! The mantissa representation does not depend on the word size, but the precision of results is rounded upwards
+//! to a multiple of `PRECISION_GRANULARITY`, which is 32 or 64 bits. Vectors with precision which is a multiple of 64
+//! are therefore portable across all targets.
+
+use crate::BigFloat;
+use crate::Consts;
+use crate::Error;
+use crate::Exponent;
+use crate::RoundingMode;
+use crate::Sign;
+use crate::Word;
+use crate::INF_NEG;
+use crate::INF_POS;
+use crate::NAN;
+use crate::WORD_BIT_SIZE;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::str::FromStr;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Operation of a test vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// Addition.
+    Add,
+    /// Subtraction.
+    Sub,
+    /// Multiplication.
+    Mul,
+    /// Division.
+    Div,
+    /// Reciprocal.
+    Reciprocal,
+    /// Square root.
+    Sqrt,
+    /// Cube root.
+    Cbrt,
+    /// Power.
+    Pow,
+    /// Logarithm with an arbitrary base.
+    Log,
+    /// Natural logarithm.
+    Ln,
+    /// Logarithm base 2.
+    Log2,
+    /// Logarithm base 10.
+    Log10,
+    /// Exponent.
+    Exp,
+    /// Sine.
+    Sin,
+    /// Cosine.
+    Cos,
+    /// Tangent.
+    Tan,
+    /// Arcsine.
+    Asin,
+    /// Arccosine.
+    Acos,
+    /// Arctangent.
+    Atan,
+    /// Hyperbolic sine.
+    Sinh,
+    /// Hyperbolic cosine.
+    Cosh,
+    /// Hyperbolic tangent.
+    Tanh,
+    /// Hyperbolic arcsine.
+    Asinh,
+    /// Hyperbolic arccosine.
+    Acosh,
+    /// Hyperbolic arctangent.
+    Atanh,
+    /// Logistic sigmoid.
+    Sigmoid,
+    /// Softplus.
+    Softplus,
+    /// Cumulative distribution function of the standard normal distribution.
+    NormCdf,
+    /// Inverse of the cumulative distribution function of the standard normal distribution.
+    NormCdfInv,
+}
+
+impl Op {
+    /// All operations.
+    pub const ALL: [Op; 29] = [
+        Op::Add,
+        Op::Sub,
+        Op::Mul,
+        Op::Div,
+        Op::Reciprocal,
+        Op::Sqrt,
+        Op::Cbrt,
+        Op::Pow,
+        Op::Log,
+        Op::Ln,
+        Op::Log2,
+        Op::Log10,
+        Op::Exp,
+        Op::Sin,
+        Op::Cos,
+        Op::Tan,
+        Op::Asin,
+        Op::Acos,
+        Op::Atan,
+        Op::Sinh,
+        Op::Cosh,
+        Op::Tanh,
+        Op::Asinh,
+        Op::Acosh,
+        Op::Atanh,
+        Op::Sigmoid,
+        Op::Softplus,
+        Op::NormCdf,
+        Op::NormCdfInv,
+    ];
+
+    /// Returns the name of the operation used in the text representation.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Div => "div",
+            Op::Reciprocal => "reciprocal",
+            Op::Sqrt => "sqrt",
+            Op::Cbrt => "cbrt",
+            Op::Pow => "pow",
+            Op::Log => "log",
+            Op::Ln => "ln",
+            Op::Log2 => "log2",
+            Op::Log10 => "log10",
+            Op::Exp => "exp",
+            Op::Sin => "sin",
+            Op::Cos => "cos",
+            Op::Tan => "tan",
+            Op::Asin => "asin",
+            Op::Acos => "acos",
+            Op::Atan => "atan",
+            Op::Sinh => "sinh",
+            Op::Cosh => "cosh",
+            Op::Tanh => "tanh",
+            Op::Asinh => "asinh",
+            Op::Acosh => "acosh",
+            Op::Atanh => "atanh",
+            Op::Sigmoid => "sigmoid",
+            Op::Softplus => "softplus",
+            Op::NormCdf => "norm_cdf",
+            Op::NormCdfInv => "norm_cdf_inv",
+        }
+    }
+
+    /// Returns the operation with the name `name`, or None if there is no such operation.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().find(|op| op.name() == name).copied()
+    }
+
+    /// Returns the number of arguments of the operation.
+    pub fn arity(&self) -> usize {
+        match self {
+            Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Pow | Op::Log => 2,
+            _ => 1,
+        }
+    }
+
+    /// Computes the operation for the arguments `args` with precision `p` using rounding mode `rm`.
+    /// The function returns NaN if the number of arguments does not match the arity of the operation.
+    pub fn eval(&self, args: &[BigFloat], p: usize, rm: RoundingMode, cc: &mut Consts) -> BigFloat {
+        if args.len() != self.arity() {
+            return BigFloat::nan(Some(Error::InvalidArgument));
+        }
+
+        let x = &args[0];
+
+        match self {
+            Op::Add => x.add(&args[1], p, rm),
+            Op::Sub => x.sub(&args[1], p, rm),
+            Op::Mul => x.mul(&args[1], p, rm),
+            Op::Div => x.div(&args[1], p, rm),
+            Op::Reciprocal => x.reciprocal(p, rm),
+            Op::Sqrt => x.sqrt(p, rm),
+            Op::Cbrt => x.cbrt(p, rm),
+            Op::Pow => x.pow(&args[1], p, rm, cc),
+            Op::Log => x.log(&args[1], p, rm, cc),
+            Op::Ln => x.ln(p, rm, cc),
+            Op::Log2 => x.log2(p, rm, cc),
+            Op::Log10 => x.log10(p, rm, cc),
+            Op::Exp => x.exp(p, rm, cc),
+            Op::Sin => x.sin(p, rm, cc),
+            Op::Cos => x.cos(p, rm, cc),
+            Op::Tan => x.tan(p, rm, cc),
+            Op::Asin => x.asin(p, rm, cc),
+            Op::Acos => x.acos(p, rm, cc),
+            Op::Atan => x.atan(p, rm, cc),
+            Op::Sinh => x.sinh(p, rm, cc),
+            Op::Cosh => x.cosh(p, rm, cc),
+            Op::Tanh => x.tanh(p, rm, cc),
+            Op::Asinh => x.asinh(p, rm, cc),
+            Op::Acosh => x.acosh(p, rm, cc),
+            Op::Atanh => x.atanh(p, rm, cc),
+            Op::Sigmoid => x.sigmoid(p, rm, cc),
+            Op::Softplus => x.softplus(p, rm, cc),
+            Op::NormCdf => x.norm_cdf(p, rm, cc),
+            Op::NormCdfInv => x.norm_cdf_inv(p, rm, cc),
+        }
+    }
+}
+
+/// Test vector: an operation with its arguments, precision, rounding mode, and the expected result.
+#[derive(Debug, Clone)]
+pub struct TestVector {
+    /// Operation.
+    pub op: Op,
+
+    /// Precision of the result.
+    pub p: usize,
+
+    /// Rounding mode.
+    pub rm: RoundingMode,
+
+    /// Arguments of the operation.
+    pub args: Vec<BigFloat>,
+
+    /// Expected result.
+    pub expected: BigFloat,
+}
+
+impl TestVector {
+    /// Creates a test vector for the operation `op` with the arguments `args`, precision `p`, and rounding mode `rm`.
+    /// The expected result is computed using the current version of the library.
+    pub fn new(op: Op, args: Vec<BigFloat>, p: usize, rm: RoundingMode, cc: &mut Consts) -> Self {
+        let expected = op.eval(&args, p, rm, cc);
+
+        TestVector {
+            op,
+            p,
+            rm,
+            args,
+            expected,
+        }
+    }
+
+    /// Computes the operation and returns true if the result is identical to the expected result,
+    /// i.e. the mantissa, the sign, the exponent, and the inexact flag are all the same.
+    /// NaN is considered identical to NaN regardless of the error.
+    pub fn verify(&self, cc: &mut Consts) -> bool {
+        let ret = self.op.eval(&self.args, self.p, self.rm, cc);
+
+        ret.identical(&self.expected) && ret.inexact() == self.expected.inexact()
+    }
+}
+
+impl Display for TestVector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{} {} {}", self.op.name(), self.p, rm_name(self.rm))?;
+
+        for arg in self.args.iter() {
+            f.write_str(" ")?;
+            write_number(f, arg)?;
+        }
+
+        f.write_str(" ")?;
+        write_number(f, &self.expected)
+    }
+}
+
+impl FromStr for TestVector {
+    type Err = Error;
+
+    /// Parses a test vector from its text representation.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+
+        let op = tokens
+            .next()
+            .and_then(Op::from_name)
+            .ok_or(Error::InvalidArgument)?;
+
+        let p = tokens
+            .next()
+            .and_then(|t| t.parse::<usize>().ok())
+            .ok_or(Error::InvalidArgument)?;
+
+        let rm = tokens
+            .next()
+            .and_then(rm_from_name)
+            .ok_or(Error::InvalidArgument)?;
+
+        let mut args = Vec::new();
+        args.try_reserve_exact(op.arity())?;
+
+        for _ in 0..op.arity() {
+            args.push(parse_number(tokens.next().ok_or(Error::InvalidArgument)?)?);
+        }
+
+        let expected = parse_number(tokens.next().ok_or(Error::InvalidArgument)?)?;
+
+        if tokens.next().is_some() {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(TestVector {
+            op,
+            p,
+            rm,
+            args,
+            expected,
+        })
+    }
+}
+
+/// Generates test vectors for each of the operations `ops`, precisions `ps`, and rounding modes `rms`.
+/// Unary operations are applied to each of the `inputs`, and binary operations are applied to each pair of the `inputs`.
+pub fn generate(
+    ops: &[Op],
+    inputs: &[BigFloat],
+    ps: &[usize],
+    rms: &[RoundingMode],
+    cc: &mut Consts,
+) -> Vec<TestVector> {
+    let mut ret = Vec::new();
+
+    for op in ops {
+        for &p in ps {
+            for &rm in rms {
+                for x in inputs {
+                    if op.arity() == 1 {
+                        ret.push(TestVector::new(*op, [x.clone()].into(), p, rm, cc));
+                    } else {
+                        for y in inputs {
+                            ret.push(TestVector::new(
+                                *op,
+                                [x.clone(), y.clone()].into(),
+                                p,
+                                rm,
+                                cc,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    ret
+}
+
+/// Verifies the test vectors in the text `text`, one vector per line.
+/// Empty lines and lines starting with `#` are skipped.
+/// The function returns the numbers of the lines (starting from 1) for which verification has failed.
+///
+/// ## Errors
+///
+///  - InvalidArgument: a line can't be parsed as a test vector.
+///  - MemoryAllocation: failed to allocate memory.
+pub fn verify_all(text: &str, cc: &mut Consts) -> Result<Vec<usize>, Error> {
+    let mut ret = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let v: TestVector = line.parse()?;
+
+        if !v.verify(cc) {
+            ret.try_reserve(1)?;
+            ret.push(i + 1);
+        }
+    }
+
+    Ok(ret)
+}
+
+fn rm_name(rm: RoundingMode) -> &'static str {
+    match rm {
+        RoundingMode::None => "none",
+        RoundingMode::Up => "up",
+        RoundingMode::Down => "down",
+        RoundingMode::ToZero => "tozero",
+        RoundingMode::FromZero => "fromzero",
+        RoundingMode::ToEven => "toeven",
+        RoundingMode::ToOdd => "toodd",
+        RoundingMode::ToNearestAway => "tonearestaway",
+    }
+}
+
+fn rm_from_name(name: &str) -> Option<RoundingMode> {
+    let rm = match name {
+        "none" => RoundingMode::None,
+        "up" => RoundingMode::Up,
+        "down" => RoundingMode::Down,
+        "tozero" => RoundingMode::ToZero,
+        "fromzero" => RoundingMode::FromZero,
+        "toeven" => RoundingMode::ToEven,
+        "toodd" => RoundingMode::ToOdd,
+        "tonearestaway" => RoundingMode::ToNearestAway,
+        _ => return None,
+    };
+
+    Some(rm)
+}
+
+fn write_number(f: &mut Formatter<'_>, x: &BigFloat) -> Result<(), core::fmt::Error> {
+    if let Some((m, _, s, e, inexact)) = x.as_raw_parts() {
+        if inexact {
+            f.write_str("~")?;
+        }
+
+        f.write_str(if s.is_negative() { "-" } else { "+" })?;
+
+        for w in m.iter().rev() {
+            write!(f, "{:0w$x}", w, w = WORD_BIT_SIZE / 4)?;
+        }
+
+        write!(f, "@{}", e)
+    } else if x.is_inf_pos() {
+        f.write_str("+inf")
+    } else if x.is_inf_neg() {
+        f.write_str("-inf")
+    } else {
+        f.write_str("nan")
+    }
+}
+
+fn parse_number(s: &str) -> Result<BigFloat, Error> {
+    match s {
+        "nan" => return Ok(NAN),
+        "+inf" => return Ok(INF_POS),
+        "-inf" => return Ok(INF_NEG),
+        _ => {}
+    };
+
+    let (inexact, s) = match s.strip_prefix('~') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+
+    let (sign, s) = if let Some(s) = s.strip_prefix('+') {
+        (Sign::Pos, s)
+    } else if let Some(s) = s.strip_prefix('-') {
+        (Sign::Neg, s)
+    } else {
+        return Err(Error::InvalidArgument);
+    };
+
+    let (digits, e) = s.split_once('@').ok_or(Error::InvalidArgument)?;
+    let e = e.parse::<Exponent>().map_err(|_| Error::InvalidArgument)?;
+
+    if digits.is_empty() {
+        return Err(Error::InvalidArgument);
+    }
+
+    // the last word is padded with zeroes
+    let word_digits = WORD_BIT_SIZE / 4;
+    let mut m = Vec::new();
+    m.try_reserve_exact(digits.len().div_ceil(word_digits))?;
+
+    for chunk in digits.as_bytes().chunks(word_digits) {
+        let mut w: Word = 0;
+
+        for c in chunk {
+            let d = (*c as char).to_digit(16).ok_or(Error::InvalidArgument)?;
+            w = (w << 4) | d as Word;
+        }
+
+        w <<= 4 * (word_digits - chunk.len());
+        m.push(w);
+    }
+
+    m.reverse();
+
+    let mut ret = BigFloat::from_words(&m, sign, e);
+
+    if ret.is_nan() {
+        return Err(Error::InvalidArgument);
+    }
+
+    ret.set_inexact(inexact);
+
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_vectors() {
+        let mut cc = Consts::new().unwrap();
+
+        // sqrt(2) = 1.6a09e667f3bcc908b2fb1366...
+        let v: TestVector = "sqrt 64 toeven +8000000000000000@2 ~+b504f333f9de6484@1"
+            .parse()
+            .unwrap();
+        assert!(v.verify(&mut cc));
+
+        let v: TestVector = "sqrt 64 up +8000000000000000@2 ~+b504f333f9de6484@1"
+            .parse()
+            .unwrap();
+        assert!(!v.verify(&mut cc));
+
+        let v: TestVector = "div 64 toeven +8000000000000000@1 +0000000000000000@0 +inf"
+            .parse()
+            .unwrap();
+        assert!(v.verify(&mut cc));
+
+        let v: TestVector = "ln 64 toeven -8000000000000000@1 nan".parse().unwrap();
+        assert!(v.verify(&mut cc));
+
+        // malformed lines
+        for s in [
+            "",
+            "foo 64 toeven +8@1 +8@1",
+            "sqrt x toeven +8@1 +8@1",
+            "sqrt 64 foo +8@1 +8@1",
+            "sqrt 64 toeven +8@1",
+            "sqrt 64 toeven 8@1 +8@1",
+            "sqrt 64 toeven +8g@1 +8@1",
+            "sqrt 64 toeven +8@1 +8@1 +8@1",
+            "add 64 toeven +8@1 +8@1",
+        ] {
+            assert!(s.parse::<TestVector>().is_err(), "{}", s);
+        }
+
+        // roundtrip of generated vectors
+        let inputs = [
+            BigFloat::from_f64(0.3, 128),
+            BigFloat::from_f64(-2.5, 64),
+            BigFloat::from_word(3, 64),
+            BigFloat::from_word(0, 64),
+            BigFloat::min_positive(64),
+            INF_POS,
+            NAN,
+        ];
+
+        let ps = [64, 128];
+        let rms = [RoundingMode::ToEven, RoundingMode::Down];
+
+        let unary: Vec<Op> = Op::ALL
+            .iter()
+            .filter(|op| op.arity() == 1)
+            .copied()
+            .collect();
+        let binary: Vec<Op> = Op::ALL
+            .iter()
+            .filter(|op| op.arity() == 2)
+            .copied()
+            .collect();
+
+        let mut vectors = generate(&unary, &inputs, &ps, &rms, &mut cc);
+        vectors.extend(generate(&binary, &inputs[..2], &ps, &rms, &mut cc));
+        vectors.extend(generate(&binary, &inputs[3..], &ps, &rms, &mut cc));
+
+        let mut text = String::new();
+        text += "# golden vectors\n\n";
+        for v in vectors.iter() {
+            text += &v.to_string();
+            text += "\n";
+        }
+
+        assert!(verify_all(&text, &mut cc).unwrap().is_empty());
+
+        for (v, line) in vectors.iter().zip(text.lines().skip(2)) {
+            let v2: TestVector = line.parse().unwrap();
+            assert_eq!(v.op, v2.op);
+            assert!(v.expected.identical(&v2.expected), "{}", line);
+            for (a1, a2) in v.args.iter().zip(v2.args.iter()) {
+                assert!(a1.identical(a2), "{}", line);
+            }
+        }
+
+        // a regression is detected
+        let text = "sqrt 64 toeven +8000000000000000@2 ~+b504f333f9de6485@1\n";
+        assert_eq!(verify_all(text, &mut cc).unwrap(), [1]);
+    }
+}
@@ -183,7 +183,9 @@ extern crate alloc;
 ///  - `acosh(x)`: hyperbolic arccosine of `x`.
 ///  - `atanh(x)`: hyperbolic arctangent of `x`.
 ///
-/// The context determines the precision, the rounding mode of the result, and also contains the cache of constants.
+/// The context determines the precision, the rounding mode, and the exponent range of the result, and also contains the cache of constants.
+/// Results with the exponent smaller than the minimum exponent of the context are rounded as subnormal numbers of that range,
+/// i.e. to a multiple of 2^(emin - p), and results with the exponent greater than the maximum exponent of the context become infinite.
 /// Tuple `(usize, RoundingMode, &mut Consts)` can also be used as a temporary context (see example below).
 ///
 /// The macro will determine additional precision needed to compensate error and perform correct rounding.
@@ -237,4 +239,26 @@ extern crate alloc;
 /// ```
 pub use astro_float_macro::expr;
 
+/// Constructs a `BigFloat` from a literal at compile time.
+///
+/// The macro accepts a string, integer, or floating point literal, optionally preceded by `-`, and a precision given as an integer literal.
+/// The literal is parsed during compilation with the given precision and rounded to the nearest even,
+/// and the macro expands to a call of `BigFloat::from_words`, so there is no parsing cost at runtime.
+/// A malformed literal, or a literal which is too large to be represented, results in a compilation error.
+///
+/// ## Examples
+///
+/// ```
+/// use astro_float::BigFloat;
+/// use astro_float::bigfloat;
+///
+/// let pi: BigFloat = bigfloat!("3.14159265358979323846264338327950288e+0", 1024);
+/// let half: BigFloat = bigfloat!(0.5, 64);
+/// let neg: BigFloat = bigfloat!(-12, 64);
+///
+/// assert_eq!(half, BigFloat::from(0.5));
+/// assert_eq!(neg, BigFloat::from(-12));
+/// ```
+pub use astro_float_macro::bigfloat;
+
 pub use astro_float_num::*;
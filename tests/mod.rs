@@ -10,6 +10,8 @@ use astro_float_num::{
 fn macro_compile_tests() {
     let t = trybuild::TestCases::new();
     t.pass("./tests/tests/expr.rs");
+    t.pass("./tests/tests/bigfloat.rs");
+    t.compile_fail("./tests/tests/bigfloat_malformed.rs");
 }
 
 #[test]
@@ -426,3 +428,40 @@ fn macro_run_err_test() {
     assert_ne!(y1, z);
     assert_eq!(y2, z);
 }
+
+#[test]
+fn macro_run_exponent_range_test() {
+    // emulation of f64
+    let mut ctx = Context::new(53, RoundingMode::ToEven, Consts::new().unwrap());
+    ctx.set_emin(-1021);
+    ctx.set_emax(1024);
+
+    let vals = [
+        (f64::MIN_POSITIVE, 3.0),
+        (f64::MIN_POSITIVE, -7.0),
+        (5e-324, 2.0),
+        (3.0 * 5e-324, 2.0),
+        (1e-300, 1e300),
+        (1e-300, 3e10),
+        (f64::MAX, 0.5),
+    ];
+
+    for (a, b) in vals {
+        let (x, y) = (BigFloat::from(a), BigFloat::from(b));
+
+        let res: BigFloat = expr!(x / y, &mut ctx);
+        assert_eq!(res.to_f64_round(RoundingMode::None), a / b);
+
+        let res: BigFloat = expr!(x * y, &mut ctx);
+        assert_eq!(res.to_f64_round(RoundingMode::None), a * b);
+    }
+
+    let x = BigFloat::from(f64::MAX);
+    let res: BigFloat = expr!(x * 2, &mut ctx);
+    assert!(res.is_inf_pos());
+
+    let x = BigFloat::from(5e-324);
+    let res: BigFloat = expr!(x / 3, &mut ctx);
+    assert!(res.is_zero());
+    assert!(res.inexact());
+}
@@ -0,0 +1,16 @@
+use astro_float::bigfloat;
+use astro_float::BigFloat;
+
+fn main() {
+    let x: BigFloat = bigfloat!("1.5", 64);
+    assert_eq!(x, BigFloat::from(1.5));
+
+    let x: BigFloat = bigfloat!(-0.25, 128);
+    assert_eq!(x, BigFloat::from(-0.25));
+
+    let x: BigFloat = bigfloat!(-7, 64);
+    assert_eq!(x, BigFloat::from(-7));
+
+    let x: BigFloat = bigfloat!("-1.0e-3", 256);
+    assert!(x.is_negative() && x.inexact());
+}
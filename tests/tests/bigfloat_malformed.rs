@@ -0,0 +1,6 @@
+use astro_float::bigfloat;
+use astro_float::BigFloat;
+
+fn main() {
+    let _x: BigFloat = bigfloat!("abc", 64);
+}